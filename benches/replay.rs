@@ -0,0 +1,140 @@
+//! Replay and pipeline benchmarks over a synthetic 10k-changeset history.
+//!
+//! Run with `cargo bench --bench replay`. The history cycles through
+//! `CREATE TABLE`, `ALTER TABLE ... ADD COLUMN`, `CREATE INDEX`, and foreign
+//! keys over a growing set of tables, which is roughly the statement mix of
+//! a long-lived Liquibase changelog. Snapshot save/load is measured as
+//! plain JSON, and also zstd-compressed when built with `--features zstd`.
+
+use std::hint::black_box;
+use std::path::PathBuf;
+
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use pg_migration_lint::catalog::snapshot::{CatalogSnapshot, SnapshotUnit};
+use pg_migration_lint::input::{ChangesetMeta, MigrationUnit, RawMigrationUnit};
+use pg_migration_lint::suppress::Suppressions;
+use pg_migration_lint::{LintPipeline, RuleId};
+
+/// Changesets in the synthetic history.
+const HISTORY_LEN: usize = 10_000;
+
+/// Changesets linted at the end of the history, like a pull request.
+const CHANGED_LEN: usize = 20;
+
+/// SQL of changeset `n`. Every fourth changeset creates a table; the others
+/// alter one of the tables created so far.
+fn changeset_sql(n: usize) -> String {
+    let table = n / 4;
+    match n % 4 {
+        0 => format!(
+            "CREATE TABLE t{table} (\n    id bigint PRIMARY KEY,\n    name text NOT NULL,\n    created_at timestamptz NOT NULL DEFAULT now()\n);"
+        ),
+        1 => format!("ALTER TABLE t{table} ADD COLUMN c{n} integer;"),
+        2 => {
+            let column = n - 1;
+            format!("CREATE INDEX CONCURRENTLY idx_t{table}_c{column} ON t{table} (c{column});")
+        }
+        _ if table == 0 => format!("ALTER TABLE t{table} ADD COLUMN note text;"),
+        _ => format!(
+            "ALTER TABLE t{table} ADD COLUMN parent_id bigint REFERENCES t{} (id);",
+            table - 1
+        ),
+    }
+}
+
+fn history() -> Vec<MigrationUnit> {
+    (0..HISTORY_LEN)
+        .map(|n| {
+            RawMigrationUnit {
+                id: format!("bench-{n}"),
+                sql: changeset_sql(n),
+                source_file: PathBuf::from(format!("db/changelog/{:05}.sql", n)),
+                source_line_offset: 1,
+                run_in_transaction: n % 4 != 2,
+                is_down: false,
+                suppressions: Suppressions::default(),
+                changeset: ChangesetMeta::default(),
+            }
+            .into_migration_unit()
+        })
+        .collect()
+}
+
+fn replay_all(units: &[MigrationUnit]) -> LintPipeline {
+    let mut pipeline = LintPipeline::new();
+    for unit in units {
+        pipeline.replay(unit);
+    }
+    pipeline
+}
+
+fn bench_parse(c: &mut Criterion) {
+    c.bench_function("parse 10k changesets", |b| b.iter(|| black_box(history())));
+}
+
+fn bench_replay(c: &mut Criterion) {
+    let units = history();
+    c.bench_function("replay 10k changesets", |b| {
+        b.iter(|| black_box(replay_all(&units)))
+    });
+}
+
+fn bench_pipeline(c: &mut Criterion) {
+    let units = history();
+    let rules: Vec<RuleId> = RuleId::lint_rules().collect();
+    let (history, changed) = units.split_at(HISTORY_LEN - CHANGED_LEN);
+    c.bench_function("full pipeline, 10k changesets", |b| {
+        b.iter(|| {
+            let mut pipeline = replay_all(history);
+            let mut findings = Vec::new();
+            for unit in changed {
+                findings.extend(pipeline.lint(unit, &rules));
+            }
+            findings.extend(pipeline.finish(&rules));
+            black_box(findings)
+        })
+    });
+}
+
+fn bench_incremental(c: &mut Criterion) {
+    let units = history();
+    let (history, changed) = units.split_at(HISTORY_LEN - CHANGED_LEN);
+    let snapshot = replay_all(history).catalog().clone();
+    c.bench_function("lint_incremental against a 10k-changeset catalog", |b| {
+        b.iter_batched(
+            LintPipeline::new,
+            |mut pipeline| black_box(pipeline.lint_incremental(&snapshot, changed)),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn bench_snapshot(c: &mut Criterion) {
+    let units = history();
+    let snapshot = CatalogSnapshot::new(
+        replay_all(&units).catalog().clone(),
+        units.iter().map(SnapshotUnit::of).collect(),
+    );
+    let dir = tempfile::tempdir().expect("tempdir");
+    let mut names = vec!["catalog.json"];
+    if cfg!(feature = "zstd") {
+        names.push("catalog.json.zst");
+    }
+    for name in names {
+        let path = dir.path().join(name);
+        c.bench_function(&format!("save 10k-changeset snapshot as {name}"), |b| {
+            b.iter(|| snapshot.write(&path).expect("write snapshot"))
+        });
+        c.bench_function(&format!("load 10k-changeset snapshot from {name}"), |b| {
+            b.iter(|| black_box(CatalogSnapshot::read(&path).expect("read snapshot")))
+        });
+    }
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = bench_parse, bench_replay, bench_pipeline, bench_incremental,
+        bench_snapshot
+}
+criterion_main!(benches);
@@ -21,6 +21,7 @@ import java.io.IOException;
 import java.nio.file.Files;
 import java.nio.file.Path;
 import java.util.ArrayList;
+import java.util.Collection;
 import java.util.HashMap;
 import java.util.List;
 import java.util.Map;
@@ -121,6 +122,13 @@ public class LiquibaseBridge {
                 ChangesetEntry entry = new ChangesetEntry();
                 entry.changeset_id = changeSet.getId();
                 entry.author = changeSet.getAuthor() != null ? changeSet.getAuthor() : "";
+                entry.comments = changeSet.getComments();
+                entry.contexts = changeSet.getContextFilter() != null
+                        ? sortedOrNull(changeSet.getContextFilter().getContexts())
+                        : null;
+                entry.labels = changeSet.getLabels() != null
+                        ? sortedOrNull(changeSet.getLabels().getLabels())
+                        : null;
                 entry.sql = generatedSql;
 
                 // Resolve the XML file path relative to the original changelog location,
@@ -157,6 +165,19 @@ public class LiquibaseBridge {
         return entries;
     }
 
+    /**
+     * Sorted copy of a context or label set, or null when empty so that Gson
+     * leaves the field out of the JSON.
+     */
+    static List<String> sortedOrNull(Collection<String> values) {
+        if (values == null || values.isEmpty()) {
+            return null;
+        }
+        List<String> sorted = new ArrayList<>(values);
+        sorted.sort(null);
+        return sorted;
+    }
+
     /**
      * Post-process entries to resolve XML line numbers by scanning the source files
      * for changeset ID attributes. Falls back to line 1 if the file cannot be read
@@ -198,6 +219,9 @@ public class LiquibaseBridge {
     static class ChangesetEntry {
         String changeset_id;
         String author;
+        String comments;
+        List<String> contexts;
+        List<String> labels;
         String sql;
         String xml_file;
         int xml_line;
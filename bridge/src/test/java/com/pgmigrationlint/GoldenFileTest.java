@@ -38,7 +38,8 @@ class GoldenFileTest {
         "skip-unsupported",
         "run-in-transaction",
         "mixed-ddl",
-        "include-directive"
+        "include-directive",
+        "changeset-metadata"
     })
     void goldenFileMatchesExpectedOutput(String fixtureName) throws Exception {
         String xmlPath = fixtureFilePath(fixtureName + ".xml");
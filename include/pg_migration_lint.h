@@ -0,0 +1,71 @@
+#ifndef PG_MIGRATION_LINT_H
+#define PG_MIGRATION_LINT_H
+
+/* Generated by cbindgen from src/ffi.rs; do not edit. Regenerate with:
+   cbindgen --config cbindgen.toml --output include/pg_migration_lint.h */
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Lint a single SQL migration and return the result as a JSON string.
+ *
+ * - `sql`: the migration source.
+ * - `file`: path reported on findings (also drives `.down.sql` detection).
+ *   Never read from disk. May be null, in which case `"migration.sql"` is used.
+ * - `config_json`: a JSON object with the same structure as
+ *   `pg-migration-lint.toml`. May be null for the default configuration.
+ *
+ * Returns `{"findings": [...]}` on success or `{"error": "..."}` on invalid
+ * input or an internal failure; a panic never crosses the boundary. The
+ * returned string is owned by the caller and must be released
+ * with [`pgml_string_free`].
+ *
+ * # Safety
+ *
+ * Every non-null pointer argument must point to a valid NUL-terminated
+ * string that stays alive for the duration of the call.
+ */
+char *pgml_lint_sql(const char *sql, const char *file, const char *config_json);
+
+/**
+ * Lint migrations on disk, as the CLI does, and return the `report.json`
+ * document as a JSON string.
+ *
+ * - `config_json`: a JSON object with the same structure as
+ *   `pg-migration-lint.toml`. May be null for the default configuration.
+ *   Relative paths resolve against the process's working directory.
+ * - `paths_json`: a JSON array of migration directories or files, replacing
+ *   `migrations.paths`. May be null to keep the configured paths.
+ * - `changed_json`: a JSON array of the changed files to lint; the rest of
+ *   the history is only replayed. May be null to lint every migration.
+ *
+ * Returns the report (`{"version": 1, "summary": {...}, "findings": [...]}`)
+ * on success or `{"error": "..."}` on invalid input, a migration that
+ * cannot be loaded, or an internal failure; a panic never crosses the
+ * boundary. The returned string is owned by the caller and must be
+ * released with [`pgml_string_free`].
+ *
+ * # Safety
+ *
+ * Every non-null pointer argument must point to a valid NUL-terminated
+ * string that stays alive for the duration of the call.
+ */
+char *pgml_lint_paths(const char *config_json, const char *paths_json, const char *changed_json);
+
+/**
+ * Release a string returned by [`pgml_lint_sql`] or [`pgml_lint_paths`].
+ *
+ * # Safety
+ *
+ * `s` must be null or a pointer previously returned by [`pgml_lint_sql`]
+ * or [`pgml_lint_paths`] that has not been freed yet.
+ */
+void pgml_string_free(char *s);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* PG_MIGRATION_LINT_H */
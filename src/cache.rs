@@ -0,0 +1,555 @@
+//! Result cache (`[cache]`).
+//!
+//! CI often runs the identical lint more than once: a retried job, or a
+//! second workflow on the same commit. With the cache enabled, the findings
+//! and replay warnings of a run are stored under a key that hashes
+//! everything they depend on, and a later run with the same key returns
+//! them without loading or replaying any migration.
+//!
+//! The key covers the crate version, the effective configuration, the rules
+//! that run, the changed-file set, and the path and contents of every file
+//! under `migrations.paths` and of every file they include (Liquibase
+//! includes and `sqlFile`s, psql `\i` targets), plus the rename-workflow
+//! state file and the messages locale file. A change to any of them is a
+//! miss. The key is a SHA-256 digest, so it is the same for every build of
+//! a release. Entries also
+//! record the crate version and are ignored when it differs, so an upgrade
+//! never reads results written by another release.
+//!
+//! Entries are not pruned; the cache directory is meant to live in a CI
+//! cache or the output directory of a single build.
+
+use std::collections::HashSet;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::catalog::ReplayWarning;
+use crate::config::Config;
+use crate::input::encoding::read_source;
+use crate::input::liquibase_bridge::changelog_files;
+use crate::input::psql::strip_meta_commands;
+use crate::input::sql::is_sql_file;
+use crate::parser::SourceSpan;
+use crate::rules::{Acknowledgment, Finding, RuleId, Severity, UnitRef};
+
+/// Version of the crate that writes and reads entries.
+const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Cache key of one lint run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheKey(String);
+
+impl CacheKey {
+    /// Hash the inputs of a run. `changed` is `None` when every migration is
+    /// linted.
+    pub fn compute(
+        config: &Config,
+        rules: &[RuleId],
+        changed: Option<&[PathBuf]>,
+    ) -> io::Result<Self> {
+        let mut hasher = KeyHasher::default();
+        hasher.field(CRATE_VERSION);
+
+        let config_json = serde_json::to_string(config).map_err(io::Error::other)?;
+        hasher.field(&config_json);
+
+        hasher.count(rules.len());
+        for rule in rules {
+            hasher.field(rule.as_str());
+        }
+
+        match changed {
+            Some(files) => {
+                let mut files: Vec<_> = files.iter().map(|f| f.to_string_lossy()).collect();
+                files.sort();
+                hasher.count(files.len());
+                for file in &files {
+                    hasher.field(file);
+                }
+            }
+            None => hasher.field("<all>"),
+        }
+
+        let mut tree = Vec::new();
+        for path in config.migrations.source_paths() {
+            tree.extend(tree_files(&path, config.migrations.follow_symlinks)?);
+        }
+        for file in &tree {
+            hasher.file(file)?;
+        }
+
+        // Read on demand, so a missing one hashes as missing instead of
+        // failing the key; the loader reports it.
+        let included = included_files(config, &tree);
+        hasher.count(included.len());
+        for file in &included {
+            hasher.optional_file(file);
+        }
+
+        let side_files = [
+            config.rename_workflow.state_file.as_deref(),
+            config.messages.locale_file.as_deref(),
+        ];
+        for file in side_files.into_iter().flatten() {
+            hasher.file(file)?;
+        }
+
+        Ok(Self(hasher.finish()))
+    }
+
+    /// Hex digest, used as the entry's file name.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// SHA-256 over length-prefixed fields. Keys name files on disk that later
+/// runs, possibly built by another toolchain, must find again, so the
+/// digest cannot depend on `std`'s unspecified `Hash` output.
+#[derive(Default)]
+struct KeyHasher(Sha256);
+
+impl KeyHasher {
+    /// Feed one field, prefixed with its length so that adjacent fields
+    /// cannot run together.
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.0.update((bytes.len() as u64).to_le_bytes());
+        self.0.update(bytes);
+    }
+
+    fn field(&mut self, text: &str) {
+        self.bytes(text.as_bytes());
+    }
+
+    fn count(&mut self, n: usize) {
+        self.0.update((n as u64).to_le_bytes());
+    }
+
+    fn file(&mut self, path: &Path) -> io::Result<()> {
+        self.field(&path.to_string_lossy());
+        self.bytes(&std::fs::read(path)?);
+        Ok(())
+    }
+
+    fn optional_file(&mut self, path: &Path) {
+        self.field(&path.to_string_lossy());
+        match std::fs::read(path) {
+            Ok(contents) => {
+                self.count(1);
+                self.bytes(&contents);
+            }
+            Err(_) => self.count(0),
+        }
+    }
+
+    fn finish(self) -> String {
+        self.0
+            .finalize()
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect()
+    }
+}
+
+/// Every file under `root`, in path order. A missing root has none, like a
+/// migrations path without files.
+fn tree_files(root: &Path, follow_symlinks: bool) -> io::Result<Vec<PathBuf>> {
+    if !root.exists() {
+        return Ok(Vec::new());
+    }
+    let walker = WalkBuilder::new(root)
+        .standard_filters(false)
+        .follow_links(follow_symlinks)
+        .sort_by_file_path(|a, b| a.cmp(b))
+        .build();
+    let mut files = Vec::new();
+    for entry in walker {
+        let entry = entry.map_err(|e| {
+            e.into_io_error()
+                .unwrap_or_else(|| io::Error::other("cannot walk migrations path"))
+        })?;
+        if entry.file_type().is_some_and(|t| t.is_file()) {
+            files.push(entry.into_path());
+        }
+    }
+    Ok(files)
+}
+
+/// Files a run reads beyond `tree`, the files under `migrations.paths`:
+/// everything Liquibase changelogs include, or with
+/// `resolve_psql_includes` the psql `\i`/`\ir` targets of the SQL files,
+/// transitively. Sorted, without duplicates.
+fn included_files(config: &Config, tree: &[PathBuf]) -> Vec<PathBuf> {
+    let mut included = Vec::new();
+    if config.migrations.strategy == "liquibase" {
+        for changelog in config.migrations.source_paths() {
+            included.extend(changelog_files(&changelog));
+        }
+    } else if config.migrations.resolve_psql_includes {
+        let mut pending: Vec<PathBuf> = tree.iter().filter(|f| is_sql_file(f)).cloned().collect();
+        let mut seen = HashSet::new();
+        while let Some(file) = pending.pop() {
+            if !seen.insert(file.clone()) {
+                continue;
+            }
+            let Ok(source) = read_source(&file) else {
+                continue;
+            };
+            let dir = file.parent().unwrap_or_else(|| Path::new(""));
+            for include in strip_meta_commands(&source).includes {
+                let target = dir.join(&include.target);
+                included.push(target.clone());
+                pending.push(target);
+            }
+        }
+    }
+    included.sort();
+    included.dedup();
+    included
+}
+
+/// Files a run reads outside `migrations.paths`: what the migrations
+/// include, plus the rename-workflow state file and the messages locale
+/// file. Reads the migrations to find their includes.
+pub(crate) fn files_outside_tree(config: &Config) -> io::Result<Vec<PathBuf>> {
+    let mut tree = Vec::new();
+    for path in config.migrations.source_paths() {
+        tree.extend(tree_files(&path, config.migrations.follow_symlinks)?);
+    }
+    let mut files = included_files(config, &tree);
+    let side_files = [
+        config.rename_workflow.state_file.as_deref(),
+        config.messages.locale_file.as_deref(),
+    ];
+    files.extend(side_files.into_iter().flatten().map(Path::to_path_buf));
+    Ok(files)
+}
+
+/// Size and modification time of every file under `migrations.paths` and of
+/// the given files outside them, for detecting changed migrations without
+/// reading them. Only for state kept in memory (the daemon's warm
+/// catalogs): modification times do not survive a checkout, so the result
+/// cache hashes contents instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FileStamps(Vec<(PathBuf, Option<(u64, SystemTime)>)>);
+
+impl FileStamps {
+    /// Stamp the files under `migrations.paths` and `outside`. A missing
+    /// file is stamped as missing.
+    pub(crate) fn take(config: &Config, outside: &[PathBuf]) -> io::Result<Self> {
+        let mut files = outside.to_vec();
+        for path in config.migrations.source_paths() {
+            files.extend(tree_files(&path, config.migrations.follow_symlinks)?);
+        }
+        files.sort();
+        files.dedup();
+        let stamps = files
+            .into_iter()
+            .map(|file| {
+                let stamp = std::fs::metadata(&file)
+                    .ok()
+                    .and_then(|meta| Some((meta.len(), meta.modified().ok()?)));
+                (file, stamp)
+            })
+            .collect();
+        Ok(Self(stamps))
+    }
+}
+
+/// Findings and replay warnings of a run, as stored in an entry.
+#[derive(Debug, Default)]
+pub struct CachedRun {
+    pub findings: Vec<Finding>,
+    pub warnings: Vec<ReplayWarning>,
+}
+
+/// On-disk layout of an entry.
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    version: String,
+    findings: Vec<EntryFinding>,
+    warnings: Vec<ReplayWarning>,
+}
+
+/// The fields of a [`Finding`] that reports use.
+#[derive(Serialize, Deserialize)]
+struct EntryFinding {
+    rule_id: RuleId,
+    severity: String,
+    message: String,
+    file: PathBuf,
+    start_line: usize,
+    end_line: usize,
+    table: Option<String>,
+    acknowledged: Option<EntryAcknowledgment>,
+    snippet: Option<String>,
+    unit: Option<UnitRef>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct EntryAcknowledgment {
+    ticket: Option<String>,
+    original_severity: String,
+}
+
+impl From<&Finding> for EntryFinding {
+    fn from(f: &Finding) -> Self {
+        Self {
+            rule_id: f.rule_id,
+            severity: f.severity.as_str().to_string(),
+            message: f.message.clone(),
+            file: f.file.clone(),
+            start_line: f.start_line,
+            end_line: f.end_line,
+            table: f.table.clone(),
+            acknowledged: f.acknowledged.as_ref().map(|a| EntryAcknowledgment {
+                ticket: a.ticket.clone(),
+                original_severity: a.original_severity.as_str().to_string(),
+            }),
+            snippet: f.snippet.clone(),
+            unit: f.unit.clone(),
+        }
+    }
+}
+
+impl EntryFinding {
+    /// Rebuild the finding, or `None` if a severity no longer parses.
+    fn into_finding(self) -> Option<Finding> {
+        let acknowledged = match self.acknowledged {
+            Some(a) => Some(Acknowledgment {
+                ticket: a.ticket,
+                original_severity: Severity::parse(&a.original_severity)?,
+            }),
+            None => None,
+        };
+        let mut finding = Finding::new(
+            self.rule_id,
+            Severity::parse(&self.severity)?,
+            self.message,
+            &self.file,
+            &SourceSpan::at(self.start_line, self.end_line),
+        );
+        finding.table = self.table;
+        finding.acknowledged = acknowledged;
+        finding.snippet = self.snippet;
+        finding.unit = self.unit;
+        Some(finding)
+    }
+}
+
+/// A directory of cached runs, one JSON file per key.
+#[derive(Debug, Clone)]
+pub struct ResultCache {
+    dir: PathBuf,
+}
+
+impl ResultCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn entry_path(&self, key: &CacheKey) -> PathBuf {
+        self.dir.join(format!("{}.json", key.as_str()))
+    }
+
+    /// The run stored under `key`. Missing, unreadable, and outdated entries
+    /// are all a miss.
+    pub fn load(&self, key: &CacheKey) -> Option<CachedRun> {
+        let text = std::fs::read_to_string(self.entry_path(key)).ok()?;
+        let entry: Entry = serde_json::from_str(&text).ok()?;
+        if entry.version != CRATE_VERSION {
+            return None;
+        }
+        let findings = entry
+            .findings
+            .into_iter()
+            .map(EntryFinding::into_finding)
+            .collect::<Option<Vec<_>>>()?;
+        Some(CachedRun {
+            findings,
+            warnings: entry.warnings,
+        })
+    }
+
+    /// Store a run under `key`. The entry is written to a temporary file and
+    /// renamed, so a concurrent reader never sees half of it.
+    pub fn store(
+        &self,
+        key: &CacheKey,
+        findings: &[Finding],
+        warnings: &[ReplayWarning],
+    ) -> io::Result<()> {
+        let entry = Entry {
+            version: CRATE_VERSION.to_string(),
+            findings: findings.iter().map(EntryFinding::from).collect(),
+            warnings: warnings.to_vec(),
+        };
+        let json = serde_json::to_string(&entry).map_err(io::Error::other)?;
+        std::fs::create_dir_all(&self.dir)?;
+        let path = self.entry_path(key);
+        let tmp = path.with_extension(format!("json.{}.tmp", std::process::id()));
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, &path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::WarningCategory;
+    use tempfile::TempDir;
+
+    fn config_for(dir: &Path) -> Config {
+        let mut config = Config::default();
+        config.migrations.paths = vec![dir.join("migrations").into()];
+        config
+    }
+
+    fn key(config: &Config, changed: Option<&[PathBuf]>) -> CacheKey {
+        CacheKey::compute(config, &[RuleId::Pgm001, RuleId::Pgm501], changed).expect("key")
+    }
+
+    #[test]
+    fn test_key_tracks_inputs() {
+        let dir = TempDir::new().expect("tempdir");
+        let migrations = dir.path().join("migrations");
+        std::fs::create_dir_all(&migrations).expect("mkdir");
+        std::fs::write(
+            migrations.join("V001__init.sql"),
+            "CREATE TABLE t (id int);",
+        )
+        .expect("write");
+        let config = config_for(dir.path());
+        let changed = [PathBuf::from("migrations/V001__init.sql")];
+
+        let base = key(&config, Some(&changed));
+        assert_eq!(base, key(&config, Some(&changed)));
+        assert_ne!(base, key(&config, None));
+
+        let mut strict = config.clone();
+        strict.catalog.strict_replay = true;
+        assert_ne!(base, key(&strict, Some(&changed)));
+
+        let only_001 = CacheKey::compute(&config, &[RuleId::Pgm001], Some(&changed)).expect("key");
+        assert_ne!(base, only_001);
+
+        std::fs::write(
+            migrations.join("V001__init.sql"),
+            "CREATE TABLE t (id bigint);",
+        )
+        .expect("write");
+        assert_ne!(base, key(&config, Some(&changed)));
+    }
+
+    #[test]
+    fn test_key_tracks_included_files() {
+        let dir = TempDir::new().expect("tempdir");
+        let migrations = dir.path().join("migrations");
+        let shared = dir.path().join("shared");
+        std::fs::create_dir_all(&migrations).expect("mkdir");
+        std::fs::create_dir_all(&shared).expect("mkdir");
+        std::fs::write(migrations.join("V001__init.sql"), "\\i ../shared/a.sql\n").expect("write");
+        std::fs::write(shared.join("a.sql"), "\\ir b.sql\n").expect("write");
+        std::fs::write(shared.join("b.sql"), "CREATE TABLE t (id int);").expect("write");
+        let mut config = config_for(dir.path());
+        config.migrations.resolve_psql_includes = true;
+
+        let base = key(&config, None);
+        assert_eq!(base.as_str().len(), 64);
+        assert!(base.as_str().chars().all(|c| c.is_ascii_hexdigit()));
+
+        std::fs::write(shared.join("b.sql"), "CREATE TABLE t (id bigint);").expect("write");
+        let edited = key(&config, None);
+        assert_ne!(base, edited);
+
+        std::fs::remove_file(shared.join("b.sql")).expect("remove");
+        assert_ne!(edited, key(&config, None));
+    }
+
+    #[test]
+    fn test_file_stamps_track_tree_and_included_files() {
+        let dir = TempDir::new().expect("tempdir");
+        let migrations = dir.path().join("migrations");
+        let shared = dir.path().join("shared");
+        std::fs::create_dir_all(&migrations).expect("mkdir");
+        std::fs::create_dir_all(&shared).expect("mkdir");
+        std::fs::write(migrations.join("V001__init.sql"), "\\i ../shared/a.sql\n").expect("write");
+        std::fs::write(shared.join("a.sql"), "CREATE TABLE t (id int);").expect("write");
+        let mut config = config_for(dir.path());
+        config.migrations.resolve_psql_includes = true;
+
+        let outside = files_outside_tree(&config).expect("outside");
+        assert_eq!(outside, vec![migrations.join("../shared/a.sql")]);
+        let base = FileStamps::take(&config, &outside).expect("stamps");
+        assert_eq!(base, FileStamps::take(&config, &outside).expect("stamps"));
+
+        std::fs::write(shared.join("a.sql"), "CREATE TABLE t (id bigint);").expect("write");
+        let edited = FileStamps::take(&config, &outside).expect("stamps");
+        assert_ne!(base, edited);
+
+        std::fs::write(migrations.join("V002__more.sql"), "SELECT 1;").expect("write");
+        assert_ne!(edited, FileStamps::take(&config, &outside).expect("stamps"));
+    }
+
+    #[test]
+    fn test_store_and_load_round_trip() {
+        let dir = TempDir::new().expect("tempdir");
+        let cache = ResultCache::new(dir.path().join("cache"));
+        let key = CacheKey("0123456789abcdef".to_string());
+        assert!(cache.load(&key).is_none());
+
+        let mut finding = Finding::new(
+            RuleId::Pgm201,
+            Severity::Info,
+            "DROP TABLE 'orders' on an existing table.".to_string(),
+            Path::new("db/migrations/V002__drop.sql"),
+            &SourceSpan::at(3, 4),
+        );
+        finding.table = Some("public.orders".to_string());
+        finding.acknowledged = Some(Acknowledgment {
+            ticket: Some("DB-1".to_string()),
+            original_severity: Severity::Minor,
+        });
+        let warning = ReplayWarning {
+            category: WarningCategory::Unparseable,
+            message: "could not parse".to_string(),
+            file: PathBuf::from("db/migrations/V001__init.sql"),
+            line: 7,
+        };
+        cache
+            .store(&key, &[finding], std::slice::from_ref(&warning))
+            .expect("store");
+
+        let run = cache.load(&key).expect("hit");
+        assert_eq!(run.findings.len(), 1);
+        let loaded = &run.findings[0];
+        assert_eq!(loaded.rule_id, RuleId::Pgm201);
+        assert_eq!(loaded.severity, Severity::Info);
+        assert_eq!((loaded.start_line, loaded.end_line), (3, 4));
+        assert_eq!(loaded.table.as_deref(), Some("public.orders"));
+        assert_eq!(
+            loaded.acknowledged.as_ref().map(|a| a.original_severity),
+            Some(Severity::Minor)
+        );
+        assert_eq!(run.warnings.len(), 1);
+        assert_eq!(run.warnings[0].line, 7);
+    }
+
+    #[test]
+    fn test_entry_from_other_version_is_a_miss() {
+        let dir = TempDir::new().expect("tempdir");
+        let cache = ResultCache::new(dir.path());
+        let key = CacheKey("fedcba9876543210".to_string());
+        std::fs::write(
+            cache.entry_path(&key),
+            r#"{"version":"0.0.1","findings":[],"warnings":[]}"#,
+        )
+        .expect("write");
+        assert!(cache.load(&key).is_none());
+    }
+}
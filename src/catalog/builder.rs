@@ -22,10 +22,12 @@
 //! ```
 
 use crate::catalog::types::{
-    Catalog, ColumnState, ConstraintState, IndexState, PartitionByInfo, TableState,
+    Catalog, ColumnState, ConstraintState, IndexState, OwningColumn, PartitionByInfo, TableState,
 };
 use crate::parser::ir::IndexColumn;
-use crate::parser::ir::{DefaultExpr, PartitionStrategy, TypeName};
+use crate::parser::ir::{
+    DefaultExpr, LiteralList, PartitionStrategy, ReplicaIdentity, Spelling, TypeName,
+};
 
 /// Heuristic: extract bare identifiers from expression text as column references.
 ///
@@ -103,6 +105,22 @@ impl CatalogBuilder {
         self
     }
 
+    /// Add a sequence by catalog key (e.g. `"billing.invoice_seq"`).
+    pub fn sequence(mut self, key: &str) -> Self {
+        self.catalog.add_sequence(key, None);
+        self
+    }
+
+    /// Add a sequence owned by `table.column` (table by catalog key).
+    pub fn owned_sequence(mut self, key: &str, table: &str, column: &str) -> Self {
+        let owner = OwningColumn {
+            table: table.to_string(),
+            column: column.to_string(),
+        };
+        self.catalog.add_sequence(key, Some(owner));
+        self
+    }
+
     pub fn build(self) -> Catalog {
         self.catalog
     }
@@ -125,14 +143,22 @@ impl TableBuilder {
             state: TableState {
                 name: name.to_string(),
                 display_name: name.to_string(),
+                sql_name: name
+                    .split('.')
+                    .map(|part| Spelling::inferred(part).to_sql())
+                    .collect::<Vec<_>>()
+                    .join("."),
                 columns: vec![],
                 indexes: vec![],
                 constraints: vec![],
                 has_primary_key: false,
                 incomplete: false,
+                unlogged: false,
+                replica_identity: ReplicaIdentity::Default,
                 is_partitioned: false,
                 partition_by: None,
                 parent_table: None,
+                inherits: vec![],
             },
         }
     }
@@ -141,6 +167,7 @@ impl TableBuilder {
     pub fn column(&mut self, name: &str, type_name: &str, nullable: bool) -> &mut Self {
         self.state.columns.push(ColumnState {
             name: name.to_string(),
+            sql_name: Spelling::inferred(name).to_sql(),
             type_name: TypeName::simple(type_name),
             nullable,
             has_default: false,
@@ -159,6 +186,7 @@ impl TableBuilder {
     ) -> &mut Self {
         self.state.columns.push(ColumnState {
             name: name.to_string(),
+            sql_name: Spelling::inferred(name).to_sql(),
             type_name: TypeName::simple(type_name),
             nullable,
             has_default: true,
@@ -326,6 +354,28 @@ impl TableBuilder {
             name: name.map(|s| s.to_string()),
             expression: expression.to_string(),
             not_valid,
+            literal_lists: vec![],
+        });
+        self
+    }
+
+    /// Add a CHECK constraint testing `column` against a list of literals:
+    /// `column IN ('a', 'b', ...)`.
+    pub fn check_in_list(
+        &mut self,
+        name: Option<&str>,
+        column: &str,
+        values: &[&str],
+    ) -> &mut Self {
+        let quoted: Vec<String> = values.iter().map(|v| format!("'{v}'")).collect();
+        self.state.constraints.push(ConstraintState::Check {
+            name: name.map(|s| s.to_string()),
+            expression: format!("{column} IN ({})", quoted.join(", ")),
+            not_valid: false,
+            literal_lists: vec![LiteralList {
+                column: column.to_string(),
+                values: values.iter().map(|v| v.to_string()).collect(),
+            }],
         });
         self
     }
@@ -344,6 +394,17 @@ impl TableBuilder {
         self
     }
 
+    /// Mark this table as `UNLOGGED`.
+    pub fn unlogged(&mut self) -> &mut Self {
+        self.state.unlogged = true;
+        self
+    }
+
+    pub fn replica_identity(&mut self, identity: ReplicaIdentity) -> &mut Self {
+        self.state.replica_identity = identity;
+        self
+    }
+
     /// Mark this table as partitioned with the given strategy and columns.
     pub fn partitioned_by(&mut self, strategy: PartitionStrategy, columns: &[&str]) -> &mut Self {
         self.state.is_partitioned = true;
@@ -360,6 +421,12 @@ impl TableBuilder {
         self
     }
 
+    /// Mark this table as inheriting (`INHERITS`) from the given parents.
+    pub fn inherits(&mut self, parent_keys: &[&str]) -> &mut Self {
+        self.state.inherits = parent_keys.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
     pub fn build(self) -> TableState {
         self.state
     }
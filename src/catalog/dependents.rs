@@ -0,0 +1,149 @@
+//! Catalog objects that depend on a column
+//!
+//! Walks the replayed catalog for everything that would be dropped with, or
+//! would stop PostgreSQL from dropping, a column: indexes and constraints on
+//! the column's own table, and foreign keys on other tables that reference it.
+//! Used by the DROP/RENAME COLUMN rules to show the blast radius of a change.
+
+use crate::catalog::{Catalog, ConstraintState, IndexState, TableState};
+
+/// One object that depends on a column.
+#[derive(Debug, Clone, Copy)]
+pub enum ColumnDependent<'c> {
+    /// An index on the column's table that uses the column, as a plain entry
+    /// or inside an expression. Indexes backing a PRIMARY KEY or UNIQUE
+    /// constraint are reported as the constraint instead.
+    Index(&'c IndexState),
+    /// A constraint on the column's table that involves the column.
+    Constraint(&'c ConstraintState),
+    /// A foreign key on `table` whose referenced columns include the column.
+    ReferencingForeignKey {
+        table: &'c TableState,
+        constraint: &'c ConstraintState,
+    },
+}
+
+/// Everything in `catalog` that depends on `column` of `table_key`: indexes
+/// and constraints in definition order, then referencing foreign keys in
+/// table-key order. Empty if the table is not in the catalog.
+pub fn column_dependents<'c>(
+    catalog: &'c Catalog,
+    table_key: &str,
+    column: &str,
+) -> Vec<ColumnDependent<'c>> {
+    let Some(table) = catalog.get_table(table_key) else {
+        return Vec::new();
+    };
+
+    let mut dependents: Vec<ColumnDependent<'c>> = table
+        .indexes_involving_column(column)
+        .into_iter()
+        .filter(|idx| !backs_constraint(table, idx))
+        .map(ColumnDependent::Index)
+        .collect();
+    let own = table.constraints_involving_column(column);
+    dependents.extend(own.iter().copied().map(ColumnDependent::Constraint));
+
+    let pk_columns = table.constraints.iter().find_map(|c| match c {
+        ConstraintState::PrimaryKey { columns, .. } => Some(columns),
+        _ => None,
+    });
+    let mut tables: Vec<&TableState> = catalog.tables().collect();
+    tables.sort_by(|a, b| a.name.cmp(&b.name));
+    for other in tables {
+        for constraint in &other.constraints {
+            let ConstraintState::ForeignKey {
+                ref_table,
+                ref_columns,
+                ..
+            } = constraint
+            else {
+                continue;
+            };
+            let references = if ref_columns.is_empty() {
+                pk_columns.is_some_and(|pk| pk.iter().any(|c| c == column))
+            } else {
+                ref_columns.iter().any(|c| c == column)
+            };
+            let already_listed = own.iter().any(|c| std::ptr::eq(*c, constraint));
+            if *ref_table == table.name && references && !already_listed {
+                dependents.push(ColumnDependent::ReferencingForeignKey {
+                    table: other,
+                    constraint,
+                });
+            }
+        }
+    }
+
+    dependents
+}
+
+/// True if `idx` is the index behind the table's PRIMARY KEY or a
+/// `UNIQUE ... USING INDEX` constraint.
+fn backs_constraint(table: &TableState, idx: &IndexState) -> bool {
+    (table.has_primary_key && idx.name.ends_with("_pkey"))
+        || table.constraints.iter().any(|c| {
+            matches!(c, ConstraintState::Unique { using_index: Some(name), .. } if *name == idx.name)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::builder::CatalogBuilder;
+
+    fn catalog() -> Catalog {
+        CatalogBuilder::new()
+            .table("public.users", |t| {
+                t.column("id", "int8", false)
+                    .column("email", "text", false)
+                    .column("nickname", "text", true)
+                    .pk(&["id"])
+                    .index("idx_users_email", &["email"], true)
+                    .check_constraint(Some("users_email_lower"), "(email = lower(email))", false);
+            })
+            .table("public.sessions", |t| {
+                t.column("user_id", "int8", false).fk(
+                    "sessions_user_fk",
+                    &["user_id"],
+                    "public.users",
+                    &["id"],
+                );
+            })
+            .build()
+    }
+
+    #[test]
+    fn test_indexes_and_constraints_on_own_table() {
+        let catalog = catalog();
+        let deps = column_dependents(&catalog, "public.users", "email");
+        assert_eq!(deps.len(), 2);
+        assert!(matches!(deps[0], ColumnDependent::Index(idx) if idx.name == "idx_users_email"));
+        assert!(matches!(
+            deps[1],
+            ColumnDependent::Constraint(ConstraintState::Check { .. })
+        ));
+    }
+
+    #[test]
+    fn test_referencing_foreign_keys_and_pk_without_pkey_index() {
+        let catalog = catalog();
+        let deps = column_dependents(&catalog, "public.users", "id");
+        assert_eq!(deps.len(), 2, "{deps:?}");
+        assert!(matches!(
+            deps[0],
+            ColumnDependent::Constraint(ConstraintState::PrimaryKey { .. })
+        ));
+        assert!(matches!(
+            deps[1],
+            ColumnDependent::ReferencingForeignKey { table, .. } if table.name == "public.sessions"
+        ));
+    }
+
+    #[test]
+    fn test_unused_column_has_no_dependents() {
+        let catalog = catalog();
+        assert!(column_dependents(&catalog, "public.users", "nickname").is_empty());
+        assert!(column_dependents(&catalog, "public.missing", "id").is_empty());
+    }
+}
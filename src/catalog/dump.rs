@@ -0,0 +1,362 @@
+//! Human-auditable catalog dump
+//!
+//! Serializable view of the replayed [`Catalog`], written by `--emit-catalog`
+//! so reviewers can see what the linter believes the schema is. Tables are
+//! sorted by catalog key; columns, indexes, and constraints keep definition
+//! order. Types and defaults are rendered as SQL-like text rather than IR
+//! structures. [`CatalogDump::from_catalog_redacted`] masks the literals in
+//! that text for `output.redact_literals`.
+
+use serde::Serialize;
+
+use crate::catalog::{Catalog, ColumnState, ConstraintState, IndexState, TableState};
+use crate::output::redact::{redact_literal_value, redact_literals};
+use crate::parser::ir::{DefaultExpr, IndexColumn, PartitionStrategy, ReplicaIdentity};
+
+/// The whole catalog: one entry per table, sorted by name, plus the
+/// extensions and sequences created by replayed migrations.
+#[derive(Debug, Serialize)]
+pub struct CatalogDump {
+    pub tables: Vec<TableDump>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<String>,
+    /// Catalog keys of sequences, sorted.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sequences: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TableDump {
+    /// Catalog key (`schema.table`).
+    pub name: String,
+    pub columns: Vec<ColumnDump>,
+    pub indexes: Vec<IndexDump>,
+    pub constraints: Vec<ConstraintDump>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partition_by: Option<PartitionDump>,
+    /// Catalog key of the partitioned parent, if this table is a partition.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partition_of: Option<String>,
+    /// Catalog keys of the `INHERITS` parents, in declaration order.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub inherits: Vec<String>,
+    /// Catalog keys of this table's partitions, sorted.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub partitions: Vec<String>,
+    /// True if the table is `UNLOGGED`.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub unlogged: bool,
+    /// `REPLICA IDENTITY` as SQL (`FULL`, `USING INDEX name`, `NOTHING`);
+    /// omitted for `DEFAULT`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replica_identity: Option<String>,
+    /// True if unparseable SQL touched this table, so its state may be
+    /// missing changes.
+    pub incomplete: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ColumnDump {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub nullable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IndexDump {
+    pub name: String,
+    /// Column names and expression text, in index order.
+    pub columns: Vec<String>,
+    pub unique: bool,
+    pub access_method: String,
+    #[serde(rename = "where", skip_serializing_if = "Option::is_none")]
+    pub where_clause: Option<String>,
+    /// `CREATE INDEX ON ONLY`: not yet propagated to partitions.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub only: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ConstraintDump {
+    PrimaryKey {
+        name: Option<String>,
+        columns: Vec<String>,
+    },
+    ForeignKey {
+        name: Option<String>,
+        columns: Vec<String>,
+        ref_table: String,
+        ref_columns: Vec<String>,
+        not_valid: bool,
+    },
+    Unique {
+        name: Option<String>,
+        columns: Vec<String>,
+    },
+    Check {
+        name: Option<String>,
+        expression: String,
+        not_valid: bool,
+    },
+    Exclude {
+        name: Option<String>,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub struct PartitionDump {
+    pub strategy: &'static str,
+    pub columns: Vec<String>,
+}
+
+impl CatalogDump {
+    /// Build the dump from a replayed catalog.
+    pub fn from_catalog(catalog: &Catalog) -> Self {
+        Self::build(catalog, false)
+    }
+
+    /// Build the dump with string and numeric literals in defaults, index
+    /// expressions, `WHERE` clauses, and CHECK expressions masked.
+    pub fn from_catalog_redacted(catalog: &Catalog) -> Self {
+        Self::build(catalog, true)
+    }
+
+    fn build(catalog: &Catalog, redact: bool) -> Self {
+        let mut tables: Vec<TableDump> = catalog
+            .tables()
+            .map(|t| table_dump(catalog, t, redact))
+            .collect();
+        tables.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut extensions: Vec<String> = catalog.extensions().map(str::to_string).collect();
+        extensions.sort();
+        let mut sequences: Vec<String> = catalog.sequences().map(str::to_string).collect();
+        sequences.sort();
+        Self {
+            tables,
+            extensions,
+            sequences,
+        }
+    }
+}
+
+/// SQL text for the dump, with literals masked when `redact` is set.
+fn sql_text(text: &str, redact: bool) -> String {
+    if redact {
+        redact_literals(text)
+    } else {
+        text.to_string()
+    }
+}
+
+fn table_dump(catalog: &Catalog, table: &TableState, redact: bool) -> TableDump {
+    let mut partitions = catalog.get_partition_children(&table.name);
+    partitions.sort();
+    TableDump {
+        name: table.name.clone(),
+        columns: table
+            .columns
+            .iter()
+            .map(|c| column_dump(c, redact))
+            .collect(),
+        indexes: table
+            .indexes
+            .iter()
+            .map(|i| index_dump(i, redact))
+            .collect(),
+        constraints: table
+            .constraints
+            .iter()
+            .map(|c| constraint_dump(c, redact))
+            .collect(),
+        partition_by: table.partition_by.as_ref().map(|p| PartitionDump {
+            strategy: match p.strategy {
+                PartitionStrategy::Range => "range",
+                PartitionStrategy::List => "list",
+                PartitionStrategy::Hash => "hash",
+            },
+            columns: p.columns.clone(),
+        }),
+        partition_of: table.parent_table.clone(),
+        inherits: table.inherits.clone(),
+        partitions,
+        unlogged: table.unlogged,
+        replica_identity: (table.replica_identity != ReplicaIdentity::Default)
+            .then(|| table.replica_identity.to_string()),
+        incomplete: table.incomplete,
+    }
+}
+
+fn column_dump(col: &ColumnState, redact: bool) -> ColumnDump {
+    ColumnDump {
+        name: col.name.clone(),
+        type_name: col.type_name.to_string(),
+        nullable: col.nullable,
+        default: col.default_expr.as_ref().map(|d| match d {
+            DefaultExpr::Literal(s) if redact => redact_literal_value(s),
+            DefaultExpr::Literal(s) => s.clone(),
+            DefaultExpr::Other(s) => sql_text(s, redact),
+            DefaultExpr::ColumnRef(name) => name.clone(),
+            DefaultExpr::FunctionCall { name, args } => {
+                let args: Vec<String> = args.iter().map(|a| sql_text(a, redact)).collect();
+                format!("{name}({})", args.join(", "))
+            }
+        }),
+    }
+}
+
+fn index_dump(idx: &IndexState, redact: bool) -> IndexDump {
+    IndexDump {
+        name: idx.name.clone(),
+        columns: idx
+            .entries
+            .iter()
+            .map(|e| match e {
+                IndexColumn::Column(name) => name.clone(),
+                IndexColumn::Expression { text, .. } => sql_text(text, redact),
+            })
+            .collect(),
+        unique: idx.unique,
+        access_method: idx.access_method.clone(),
+        where_clause: idx.where_clause.as_deref().map(|w| sql_text(w, redact)),
+        only: idx.only,
+    }
+}
+
+fn constraint_dump(c: &ConstraintState, redact: bool) -> ConstraintDump {
+    match c.clone() {
+        ConstraintState::PrimaryKey { name, columns } => {
+            ConstraintDump::PrimaryKey { name, columns }
+        }
+        ConstraintState::ForeignKey {
+            name,
+            columns,
+            ref_table,
+            ref_columns,
+            not_valid,
+            ..
+        } => ConstraintDump::ForeignKey {
+            name,
+            columns,
+            ref_table,
+            ref_columns,
+            not_valid,
+        },
+        ConstraintState::Unique { name, columns, .. } => ConstraintDump::Unique { name, columns },
+        ConstraintState::Check {
+            name,
+            expression,
+            not_valid,
+            ..
+        } => ConstraintDump::Check {
+            name,
+            expression: sql_text(&expression, redact),
+            not_valid,
+        },
+        ConstraintState::Exclude { name } => ConstraintDump::Exclude { name },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::builder::CatalogBuilder;
+
+    #[test]
+    fn test_dump_catalog() {
+        let catalog = CatalogBuilder::new()
+            .table("public.orders", |t| {
+                t.column("id", "int8", false)
+                    .column_with_default(
+                        "created_at",
+                        "timestamptz",
+                        false,
+                        DefaultExpr::FunctionCall {
+                            name: "now".to_string(),
+                            args: vec![],
+                        },
+                    )
+                    .column("customer_id", "int8", true)
+                    .pk(&["id"])
+                    .fk(
+                        "orders_customer_fk",
+                        &["customer_id"],
+                        "public.customers",
+                        &["id"],
+                    )
+                    .partial_index(
+                        "idx_orders_customer",
+                        &["customer_id"],
+                        false,
+                        "customer_id IS NOT NULL",
+                    )
+                    .expression_index("idx_orders_day", &["expr:date(created_at)"], false);
+            })
+            .table("public.customers", |t| {
+                t.column("id", "int8", false).pk(&["id"]);
+            })
+            .build();
+
+        insta::assert_json_snapshot!(CatalogDump::from_catalog(&catalog));
+    }
+
+    #[test]
+    fn test_dump_partitions() {
+        let catalog = CatalogBuilder::new()
+            .table("public.events", |t| {
+                t.column("ts", "timestamptz", false)
+                    .partitioned_by(PartitionStrategy::Range, &["ts"]);
+            })
+            .table("public.events_2024", |t| {
+                t.column("ts", "timestamptz", false)
+                    .partition_of("public.events");
+            })
+            .build();
+
+        let dump = CatalogDump::from_catalog(&catalog);
+        assert_eq!(dump.tables[0].name, "public.events");
+        assert_eq!(dump.tables[0].partitions, vec!["public.events_2024"]);
+        assert_eq!(
+            dump.tables[1].partition_of.as_deref(),
+            Some("public.events")
+        );
+    }
+
+    #[test]
+    fn test_dump_redacted_masks_literals() {
+        let catalog = CatalogBuilder::new()
+            .table("public.accounts", |t| {
+                t.column_with_default(
+                    "owner",
+                    "text",
+                    false,
+                    DefaultExpr::Literal("ops@example.com".to_string()),
+                )
+                .column_with_default(
+                    "active",
+                    "bool",
+                    false,
+                    DefaultExpr::Literal("true".to_string()),
+                )
+                .partial_index("idx_accounts_owner", &["owner"], false, "owner <> 'root'")
+                .check_constraint(Some("accounts_quota"), "(quota < 1000)", false);
+            })
+            .build();
+
+        let dump = CatalogDump::from_catalog_redacted(&catalog);
+        let table = &dump.tables[0];
+        assert_eq!(table.columns[0].default.as_deref(), Some("'?'"));
+        assert_eq!(table.columns[1].default.as_deref(), Some("true"));
+        assert_eq!(
+            table.indexes[0].where_clause.as_deref(),
+            Some("owner <> '?'")
+        );
+        assert!(matches!(
+            &table.constraints[0],
+            ConstraintDump::Check { expression, .. } if expression == "(quota < ?)"
+        ));
+    }
+}
@@ -0,0 +1,132 @@
+//! Objects provided by well-known extensions
+//!
+//! The catalog only tracks tables, so when an extension is dropped we need
+//! to know which column types and default functions it owned. This table
+//! lists the commonly used extensions; unknown extensions are assumed to
+//! own nothing the catalog can see.
+
+use crate::catalog::{Catalog, ColumnState, TableState};
+use crate::parser::ir::DefaultExpr;
+
+/// Column types and functions installed by an extension.
+#[derive(Debug)]
+pub struct KnownExtension {
+    pub name: &'static str,
+    pub types: &'static [&'static str],
+    pub functions: &'static [&'static str],
+}
+
+/// How a column depends on an extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtensionDependency {
+    /// The column's type (or array element type) belongs to the extension.
+    /// `DROP EXTENSION ... CASCADE` drops the column.
+    Type,
+    /// The column's default calls one of the extension's functions.
+    /// `DROP EXTENSION ... CASCADE` drops the default.
+    Default,
+}
+
+const KNOWN_EXTENSIONS: &[KnownExtension] = &[
+    KnownExtension {
+        name: "uuid-ossp",
+        types: &[],
+        functions: &[
+            "uuid_generate_v1",
+            "uuid_generate_v1mc",
+            "uuid_generate_v3",
+            "uuid_generate_v4",
+            "uuid_generate_v5",
+            "uuid_nil",
+        ],
+    },
+    KnownExtension {
+        name: "pgcrypto",
+        types: &[],
+        // gen_random_uuid() is built in since PostgreSQL 13, so it is not listed.
+        functions: &["gen_random_bytes", "crypt", "gen_salt", "digest", "hmac"],
+    },
+    KnownExtension {
+        name: "postgis",
+        types: &["geometry", "geography", "box2d", "box3d"],
+        functions: &[],
+    },
+    KnownExtension {
+        name: "hstore",
+        types: &["hstore"],
+        functions: &[],
+    },
+    KnownExtension {
+        name: "citext",
+        types: &["citext"],
+        functions: &[],
+    },
+    KnownExtension {
+        name: "ltree",
+        types: &["ltree", "lquery", "ltxtquery"],
+        functions: &[],
+    },
+    KnownExtension {
+        name: "cube",
+        types: &["cube"],
+        functions: &[],
+    },
+    KnownExtension {
+        name: "isn",
+        types: &[
+            "ean13", "isbn", "isbn13", "ismn", "ismn13", "issn", "issn13", "upc",
+        ],
+        functions: &[],
+    },
+];
+
+/// Look up a well-known extension by name (case-insensitive).
+pub fn known_extension(name: &str) -> Option<&'static KnownExtension> {
+    KNOWN_EXTENSIONS
+        .iter()
+        .find(|e| e.name.eq_ignore_ascii_case(name))
+}
+
+impl KnownExtension {
+    /// How `col` depends on this extension, if at all. A type dependency
+    /// wins over a default dependency because it is the more destructive one.
+    pub fn column_dependency(&self, col: &ColumnState) -> Option<ExtensionDependency> {
+        if self
+            .types
+            .iter()
+            .any(|t| col.type_name.name.eq_ignore_ascii_case(t))
+        {
+            return Some(ExtensionDependency::Type);
+        }
+        let calls_function = match col.default_expr.as_ref()? {
+            DefaultExpr::FunctionCall { name, .. } => {
+                self.functions.iter().any(|f| f.eq_ignore_ascii_case(name))
+            }
+            DefaultExpr::Other(text) => {
+                let text = text.to_ascii_lowercase();
+                self.functions
+                    .iter()
+                    .any(|f| text.contains(&format!("{f}(")))
+            }
+            DefaultExpr::Literal(_) | DefaultExpr::ColumnRef(_) => false,
+        };
+        calls_function.then_some(ExtensionDependency::Default)
+    }
+}
+
+/// Every column in `catalog` that depends on `ext`, in table-key order.
+pub fn dependent_columns<'c>(
+    catalog: &'c Catalog,
+    ext: &KnownExtension,
+) -> Vec<(&'c TableState, &'c ColumnState, ExtensionDependency)> {
+    let mut tables: Vec<&TableState> = catalog.tables().collect();
+    tables.sort_by(|a, b| a.name.cmp(&b.name));
+    tables
+        .into_iter()
+        .flat_map(|t| {
+            t.columns
+                .iter()
+                .filter_map(move |c| ext.column_dependency(c).map(|dep| (t, c, dep)))
+        })
+        .collect()
+}
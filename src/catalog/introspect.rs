@@ -0,0 +1,384 @@
+//! Catalog from a live database (`--introspect-db`, feature `db-introspect`).
+//!
+//! Teams whose migration history was squashed or lost cannot replay it into
+//! a catalog. Instead, the schema is read from a PostgreSQL instance (e.g.
+//! production or a replica) and the changed migrations are linted against
+//! it. Unchanged migrations are taken to be applied already and are not
+//! replayed.
+//!
+//! One `psql` query reads the user schemas from `pg_catalog` as JSON: tables
+//! with their columns, constraints, indexes, partitions, sequences, and
+//! extensions. The result is rendered as DDL and replayed with
+//! [`schema_catalog`], so the catalog is built exactly as if the schema had
+//! been created by a single migration. Names are schema-qualified (the
+//! query runs with an empty `search_path`).
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::Catalog;
+use crate::catalog::{ReplayWarning, schema_catalog};
+
+#[derive(Debug, Error)]
+pub enum IntrospectError {
+    #[error("Failed to execute psql at '{path}': {source}")]
+    Spawn {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("psql could not read the database schema: {message}")]
+    Psql { message: String },
+
+    #[error("unexpected schema query output: {message}")]
+    Parse { message: String },
+}
+
+/// Relations in user schemas, not created by an extension. Expects the
+/// relation as `c` and its namespace as `n`.
+const USER_RELATION: &str = "n.nspname NOT IN ('pg_catalog', 'information_schema') \
+     AND n.nspname !~ '^pg_(toast|temp_)' \
+     AND NOT EXISTS (SELECT 1 FROM pg_depend e \
+         WHERE e.classid = 'pg_class'::regclass AND e.objid = c.oid AND e.deptype = 'e')";
+
+/// The query returning the schema as one JSON document (see [`DbSchema`]).
+fn schema_query() -> String {
+    format!(
+        "SELECT json_build_object(
+  'extensions', (SELECT coalesce(json_agg(quote_ident(x.extname) ORDER BY x.extname), '[]')
+     FROM pg_extension x WHERE x.extname <> 'plpgsql'),
+  'sequences', (SELECT coalesce(json_agg(json_build_object(
+         'name', c.oid::regclass::text,
+         'owned_by', (SELECT d.refobjid::regclass::text || '.' || quote_ident(a.attname)
+            FROM pg_depend d JOIN pg_attribute a
+              ON a.attrelid = d.refobjid AND a.attnum = d.refobjsubid
+            WHERE d.classid = 'pg_class'::regclass AND d.objid = c.oid
+              AND d.refclassid = 'pg_class'::regclass AND d.deptype = 'a')
+       ) ORDER BY c.oid::regclass::text), '[]')
+     FROM pg_class c JOIN pg_namespace n ON n.oid = c.relnamespace
+     WHERE c.relkind = 'S' AND {USER_RELATION}
+       AND NOT EXISTS (SELECT 1 FROM pg_depend i WHERE i.classid = 'pg_class'::regclass
+         AND i.objid = c.oid AND i.deptype = 'i')),
+  'tables', (SELECT coalesce(json_agg(json_build_object(
+         'name', c.oid::regclass::text,
+         'unlogged', c.relpersistence = 'u',
+         'partition_key', CASE WHEN c.relkind = 'p' THEN pg_get_partkeydef(c.oid) END,
+         'parent', (SELECT h.inhparent::regclass::text FROM pg_inherits h
+            WHERE h.inhrelid = c.oid AND c.relispartition),
+         'bound', CASE WHEN c.relispartition THEN pg_get_expr(c.relpartbound, c.oid) END,
+         'columns', (SELECT coalesce(json_agg(json_build_object(
+                'name', quote_ident(a.attname),
+                'type', format_type(a.atttypid, a.atttypmod),
+                'not_null', a.attnotnull,
+                'default', pg_get_expr(d.adbin, d.adrelid),
+                'identity', a.attidentity,
+                'generated', a.attgenerated
+              ) ORDER BY a.attnum), '[]')
+            FROM pg_attribute a
+            LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum
+            WHERE a.attrelid = c.oid AND a.attnum > 0 AND NOT a.attisdropped)
+       ) ORDER BY c.oid::regclass::text), '[]')
+     FROM pg_class c JOIN pg_namespace n ON n.oid = c.relnamespace
+     WHERE c.relkind IN ('r', 'p') AND {USER_RELATION}),
+  'constraints', (SELECT coalesce(json_agg(json_build_object(
+         'table', c.oid::regclass::text,
+         'name', quote_ident(k.conname),
+         'kind', k.contype,
+         'definition', pg_get_constraintdef(k.oid)
+       ) ORDER BY c.oid::regclass::text, k.conname), '[]')
+     FROM pg_constraint k
+     JOIN pg_class c ON c.oid = k.conrelid
+     JOIN pg_namespace n ON n.oid = c.relnamespace
+     WHERE k.contype IN ('p', 'u', 'x', 'c', 'f') AND k.conislocal AND {USER_RELATION}),
+  'indexes', (SELECT coalesce(json_agg(pg_get_indexdef(i.indexrelid)
+       ORDER BY i.indexrelid::regclass::text), '[]')
+     FROM pg_index i
+     JOIN pg_class c ON c.oid = i.indrelid
+     JOIN pg_namespace n ON n.oid = c.relnamespace
+     WHERE c.relkind IN ('r', 'p') AND {USER_RELATION}
+       AND NOT EXISTS (SELECT 1 FROM pg_constraint k WHERE k.conindid = i.indexrelid
+         AND k.contype IN ('p', 'u', 'x'))
+       AND NOT EXISTS (SELECT 1 FROM pg_inherits h WHERE h.inhrelid = i.indexrelid))
+)"
+    )
+}
+
+/// The schema as the query returns it. Names are quoted where needed and
+/// schema-qualified; expressions and definitions are PostgreSQL's own
+/// deparsed text.
+#[derive(Debug, Deserialize)]
+struct DbSchema {
+    extensions: Vec<String>,
+    sequences: Vec<DbSequence>,
+    tables: Vec<DbTable>,
+    constraints: Vec<DbConstraint>,
+    /// `CREATE INDEX` statements, for indexes that do not back a constraint.
+    indexes: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DbSequence {
+    name: String,
+    /// `table.column` of the column the sequence belongs to.
+    owned_by: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DbTable {
+    name: String,
+    unlogged: bool,
+    /// `RANGE (ts)` for a partitioned table.
+    partition_key: Option<String>,
+    /// The partitioned table this table is a partition of.
+    parent: Option<String>,
+    /// `FOR VALUES ...` or `DEFAULT` for a partition.
+    bound: Option<String>,
+    columns: Vec<DbColumn>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DbColumn {
+    name: String,
+    #[serde(rename = "type")]
+    type_name: String,
+    not_null: bool,
+    default: Option<String>,
+    /// `pg_attribute.attidentity`: `a`, `d`, or empty.
+    identity: String,
+    /// `pg_attribute.attgenerated`: `s` or empty.
+    generated: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DbConstraint {
+    table: String,
+    name: String,
+    /// `pg_constraint.contype`.
+    kind: String,
+    definition: String,
+}
+
+impl DbSchema {
+    /// The schema as DDL: extensions, sequences, tables, partitions,
+    /// constraints (foreign keys last, once every key exists), indexes, and
+    /// sequence ownership.
+    fn to_sql(&self) -> String {
+        let mut sql = String::new();
+        for extension in &self.extensions {
+            sql.push_str(&format!("CREATE EXTENSION {extension};\n"));
+        }
+        for sequence in &self.sequences {
+            sql.push_str(&format!("CREATE SEQUENCE {};\n", sequence.name));
+        }
+        for table in &self.tables {
+            let columns: Vec<String> = table.columns.iter().map(DbColumn::to_sql).collect();
+            sql.push_str(&format!(
+                "CREATE {}TABLE {} (\n    {}\n)",
+                if table.unlogged { "UNLOGGED " } else { "" },
+                table.name,
+                columns.join(",\n    ")
+            ));
+            if let Some(key) = &table.partition_key {
+                sql.push_str(&format!(" PARTITION BY {key}"));
+            }
+            sql.push_str(";\n");
+        }
+        for table in &self.tables {
+            if let (Some(parent), Some(bound)) = (&table.parent, &table.bound) {
+                sql.push_str(&format!(
+                    "ALTER TABLE {parent} ATTACH PARTITION {} {bound};\n",
+                    table.name
+                ));
+            }
+        }
+        for kinds in ["pux", "c", "f"] {
+            for constraint in self.constraints.iter().filter(|c| kinds.contains(&c.kind)) {
+                sql.push_str(&format!(
+                    "ALTER TABLE {} ADD CONSTRAINT {} {};\n",
+                    constraint.table, constraint.name, constraint.definition
+                ));
+            }
+        }
+        for index in &self.indexes {
+            sql.push_str(&format!("{index};\n"));
+        }
+        for sequence in &self.sequences {
+            if let Some(owner) = &sequence.owned_by {
+                sql.push_str(&format!(
+                    "ALTER SEQUENCE {} OWNED BY {owner};\n",
+                    sequence.name
+                ));
+            }
+        }
+        sql
+    }
+}
+
+impl DbColumn {
+    fn to_sql(&self) -> String {
+        let mut sql = format!("{} {}", self.name, self.type_name);
+        match (
+            self.identity.as_str(),
+            self.generated.as_str(),
+            &self.default,
+        ) {
+            ("a", _, _) => sql.push_str(" GENERATED ALWAYS AS IDENTITY"),
+            ("d", _, _) => sql.push_str(" GENERATED BY DEFAULT AS IDENTITY"),
+            (_, "s", Some(expr)) => sql.push_str(&format!(" GENERATED ALWAYS AS ({expr}) STORED")),
+            (_, _, Some(expr)) => sql.push_str(&format!(" DEFAULT {expr}")),
+            _ => {}
+        }
+        if self.not_null {
+            sql.push_str(" NOT NULL");
+        }
+        sql
+    }
+}
+
+/// The schema of the database at `conninfo`, read with `psql`, as DDL.
+pub fn schema_sql(psql: &Path, conninfo: &str) -> Result<String, IntrospectError> {
+    let output = Command::new(psql)
+        .args([
+            "--no-psqlrc",
+            "--quiet",
+            "--tuples-only",
+            "--no-align",
+            "--set",
+            "ON_ERROR_STOP=1",
+            "--dbname",
+            conninfo,
+            "--command",
+            "SET search_path TO ''",
+            "--command",
+            &schema_query(),
+        ])
+        .output()
+        .map_err(|source| IntrospectError::Spawn {
+            path: psql.to_path_buf(),
+            source,
+        })?;
+    if !output.status.success() {
+        return Err(IntrospectError::Psql {
+            message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    let schema: DbSchema =
+        serde_json::from_slice(&output.stdout).map_err(|e| IntrospectError::Parse {
+            message: e.to_string(),
+        })?;
+    Ok(schema.to_sql())
+}
+
+/// Build the catalog from the database at `conninfo`. Statements of the
+/// generated DDL the parser does not understand are returned as warnings;
+/// the tables they touch are marked incomplete, as in a replay.
+pub fn introspect(
+    psql: &Path,
+    conninfo: &str,
+) -> Result<(Catalog, Vec<ReplayWarning>), IntrospectError> {
+    let sql = schema_sql(psql, conninfo)?;
+    Ok(schema_catalog(&sql, Path::new("<database>"), "public"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r#"{
+        "extensions": ["hstore"],
+        "sequences": [{"name": "public.order_no_seq", "owned_by": "public.orders.order_no"}],
+        "tables": [
+            {"name": "public.customers", "unlogged": false, "partition_key": null,
+             "parent": null, "bound": null, "columns": [
+                {"name": "id", "type": "bigint", "not_null": true, "default": null,
+                 "identity": "a", "generated": ""},
+                {"name": "attrs", "type": "public.hstore", "not_null": false,
+                 "default": null, "identity": "", "generated": ""}
+            ]},
+            {"name": "public.orders", "unlogged": false, "partition_key": null,
+             "parent": null, "bound": null, "columns": [
+                {"name": "id", "type": "bigint", "not_null": true, "default": null,
+                 "identity": "", "generated": ""},
+                {"name": "order_no", "type": "integer", "not_null": true,
+                 "default": "nextval('public.order_no_seq'::regclass)",
+                 "identity": "", "generated": ""},
+                {"name": "customer_id", "type": "bigint", "not_null": false,
+                 "default": null, "identity": "", "generated": ""},
+                {"name": "status", "type": "character varying(20)", "not_null": true,
+                 "default": "'new'::character varying", "identity": "", "generated": ""}
+            ]},
+            {"name": "public.events", "unlogged": false, "partition_key": "RANGE (ts)",
+             "parent": null, "bound": null, "columns": [
+                {"name": "ts", "type": "timestamp with time zone", "not_null": true,
+                 "default": null, "identity": "", "generated": ""}
+            ]},
+            {"name": "public.events_2024", "unlogged": false, "partition_key": null,
+             "parent": "public.events",
+             "bound": "FOR VALUES FROM ('2024-01-01 00:00:00+00') TO ('2025-01-01 00:00:00+00')",
+             "columns": [
+                {"name": "ts", "type": "timestamp with time zone", "not_null": true,
+                 "default": null, "identity": "", "generated": ""}
+            ]}
+        ],
+        "constraints": [
+            {"table": "public.orders", "name": "orders_customer_id_fkey", "kind": "f",
+             "definition": "FOREIGN KEY (customer_id) REFERENCES public.customers(id)"},
+            {"table": "public.customers", "name": "customers_pkey", "kind": "p",
+             "definition": "PRIMARY KEY (id)"},
+            {"table": "public.orders", "name": "orders_pkey", "kind": "p",
+             "definition": "PRIMARY KEY (id)"},
+            {"table": "public.orders", "name": "orders_status_check", "kind": "c",
+             "definition": "CHECK (((status)::text <> ''::text)) NOT VALID"}
+        ],
+        "indexes": [
+            "CREATE INDEX idx_orders_status ON public.orders USING btree (status)"
+        ]
+    }"#;
+
+    fn schema() -> DbSchema {
+        serde_json::from_str(SCHEMA).expect("schema JSON")
+    }
+
+    #[test]
+    fn test_schema_rendered_as_ddl() {
+        let sql = schema().to_sql();
+        assert!(
+            sql.starts_with("CREATE EXTENSION hstore;\nCREATE SEQUENCE public.order_no_seq;\n")
+        );
+        assert!(sql.contains("    id bigint GENERATED ALWAYS AS IDENTITY NOT NULL,\n"));
+        assert!(sql.contains(") PARTITION BY RANGE (ts);\n"));
+        assert!(
+            sql.contains(
+                "ALTER TABLE public.events ATTACH PARTITION public.events_2024 FOR VALUES"
+            )
+        );
+        // Foreign keys come after every primary key.
+        let fk = sql.find("orders_customer_id_fkey").expect("fk");
+        let pk = sql.find("customers_pkey").expect("pk");
+        assert!(pk < fk);
+        assert!(
+            sql.ends_with("ALTER SEQUENCE public.order_no_seq OWNED BY public.orders.order_no;\n")
+        );
+    }
+
+    #[test]
+    fn test_schema_replayed_into_catalog() {
+        let sql = schema().to_sql();
+        let (catalog, warnings) = schema_catalog(&sql, Path::new("<database>"), "public");
+        assert_eq!(warnings, vec![]);
+
+        let orders = catalog.get_table("public.orders").expect("orders");
+        assert_eq!(
+            orders.get_column("status").unwrap().type_name.name.as_ref(),
+            "varchar"
+        );
+        assert!(orders.has_primary_key);
+        assert!(orders.indexes.iter().any(|i| i.name == "idx_orders_status"));
+        assert!(catalog.get_table("public.events_2024").is_some());
+        assert!(catalog.has_extension("hstore"));
+    }
+}
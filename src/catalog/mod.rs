@@ -4,5 +4,12 @@ pub(crate) mod replay;
 pub mod types;
 
 pub mod builder;
+pub mod dependents;
+pub mod dump;
+pub mod extensions;
+#[cfg(feature = "db-introspect")]
+pub mod introspect;
+pub mod snapshot;
 
+pub use replay::{ReplayWarning, WarningCategory, schema_catalog};
 pub use types::{Catalog, ColumnState, ConstraintState, IndexState, PartitionByInfo, TableState};
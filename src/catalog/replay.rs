@@ -5,31 +5,214 @@
 //! single-pass replay strategy: the pipeline calls [`apply`] for each
 //! migration unit, and the catalog accumulates state over time.
 
+use std::path::{Path, PathBuf};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::catalog::extensions::{self, ExtensionDependency};
 use crate::catalog::types::*;
 use crate::input::MigrationUnit;
+use crate::input::sql::SqlLoader;
 use crate::parser::ir::*;
 
 #[cfg(test)]
 mod tests;
 
-/// Apply a single migration unit's IR nodes to mutate the catalog.
+/// Kind of problem with the migration history found during replay, as named
+/// in `warnings.fail_on`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningCategory {
+    /// `CREATE TABLE` / `CREATE INDEX` for an object the catalog already has.
+    ReplayConflict,
+    /// A statement the parser could not convert to IR.
+    Unparseable,
+    /// A statement PostgreSQL would reject, refused by strict replay
+    /// (`catalog.strict_replay`).
+    Rejected,
+}
+
+impl WarningCategory {
+    pub const ALL: [WarningCategory; 3] = [
+        WarningCategory::ReplayConflict,
+        WarningCategory::Unparseable,
+        WarningCategory::Rejected,
+    ];
+
+    /// The name used in the config and on the command line.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WarningCategory::ReplayConflict => "replay_conflict",
+            WarningCategory::Unparseable => "unparseable",
+            WarningCategory::Rejected => "rejected",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.as_str() == name)
+    }
+}
+
+/// A problem with the migration history found while replaying a unit.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ReplayWarning {
+    pub category: WarningCategory,
+    pub message: String,
+    pub file: PathBuf,
+    /// Line of the statement in `file`.
+    pub line: usize,
+}
+
+/// Lenient [`apply_with`].
+#[cfg(test)]
+pub(crate) fn apply(catalog: &mut Catalog, unit: &MigrationUnit) -> Vec<ReplayWarning> {
+    apply_with(catalog, unit, false)
+}
+
+/// Apply a single migration unit's IR nodes to mutate the catalog, and
+/// return the problems with the history that replay ran into.
 ///
 /// Called by the pipeline for each unit in order. Each statement in the
 /// unit is applied sequentially. Statements that reference tables not
 /// present in the catalog are silently skipped (the table may belong
 /// to a different schema or be managed outside the tracked migrations).
-pub(crate) fn apply(catalog: &mut Catalog, unit: &MigrationUnit) {
+///
+/// In `strict` mode (`catalog.strict_replay`) a statement PostgreSQL would
+/// reject against the replayed schema (see [`rejection`]) is not applied;
+/// it is reported as a [`WarningCategory::Rejected`] warning instead.
+pub(crate) fn apply_with(
+    catalog: &mut Catalog,
+    unit: &MigrationUnit,
+    strict: bool,
+) -> Vec<ReplayWarning> {
+    let mut warnings = Vec::new();
     for located in &unit.statements {
-        apply_node(catalog, &located.node);
+        let mut warn = |category, message| {
+            warnings.push(ReplayWarning {
+                category,
+                message,
+                file: unit.source_file.clone(),
+                line: located.span.start_line,
+            })
+        };
+        if let IrNode::Unparseable { table_hint, .. } = &located.node {
+            warn(
+                WarningCategory::Unparseable,
+                match table_hint {
+                    Some(table) => format!(
+                        "statement could not be parsed; table `{table}` is marked incomplete"
+                    ),
+                    None => "statement could not be parsed".to_string(),
+                },
+            );
+        }
+        if strict && let Some(reason) = rejection(catalog, &located.node) {
+            warn(WarningCategory::Rejected, reason);
+            continue;
+        }
+        if let Some(conflict) = apply_node(catalog, &located.node) {
+            warn(WarningCategory::ReplayConflict, conflict);
+        }
     }
+    warnings
 }
 
-/// Apply a single IR node to the catalog.
-fn apply_node(catalog: &mut Catalog, node: &IrNode) {
+/// Build a catalog from a schema written as DDL rather than from a migration
+/// history: a `pg_dump --schema-only` file for `--baseline-schema`, or the
+/// statements `--introspect-db` generates from a database.
+///
+/// The SQL is parsed as one unit read from `source` and replayed leniently;
+/// unqualified names get `default_schema`.
+pub fn schema_catalog(
+    sql: &str,
+    source: &Path,
+    default_schema: &str,
+) -> (Catalog, Vec<ReplayWarning>) {
+    let mut unit = SqlLoader::new(true).load_source(source, sql);
+    crate::normalize::normalize_unit(&mut unit, default_schema);
+    let mut catalog = Catalog::new();
+    let warnings = apply_with(&mut catalog, &unit, false);
+    (catalog, warnings)
+}
+
+/// Why PostgreSQL would reject `node` against `catalog`, if it would.
+///
+/// Covers creating a table or index that already exists, adding a column
+/// the table already has, dropping a constraint it does not have, and a
+/// foreign key to a table that does not exist. `IF [NOT] EXISTS` guards are
+/// honored. Statements on tables the catalog does not know, or on tables
+/// marked incomplete, are not checked: their state is not known.
+fn rejection(catalog: &Catalog, node: &IrNode) -> Option<String> {
+    let missing_ref = |fk_ref: &QualifiedName, own: &QualifiedName| {
+        let key = fk_ref.catalog_key();
+        (key != own.catalog_key() && !catalog.has_table(key)).then(|| {
+            format!(
+                "foreign key references `{}`, which does not exist",
+                fk_ref.display_name()
+            )
+        })
+    };
+
     match node {
-        IrNode::CreateTable(ct) => apply_create_table(catalog, ct),
+        IrNode::CreateTable(ct) => {
+            if !ct.if_not_exists && catalog.has_table(ct.name.catalog_key()) {
+                return Some(format!(
+                    "relation `{}` already exists",
+                    ct.name.display_name()
+                ));
+            }
+            ct.constraints.iter().find_map(|c| match c {
+                TableConstraint::ForeignKey { ref_table, .. } => missing_ref(ref_table, &ct.name),
+                _ => None,
+            })
+        }
+        IrNode::CreateIndex(ci) => {
+            let name = ci.index_name.as_deref()?;
+            (!ci.if_not_exists && catalog.get_index(name).is_some())
+                .then(|| format!("relation `{name}` already exists"))
+        }
+        IrNode::AlterTable(at) => {
+            let table = catalog.get_table(at.name.catalog_key())?;
+            if table.incomplete {
+                return None;
+            }
+            let mut added: Vec<&str> = Vec::new();
+            at.actions.iter().find_map(|action| match action {
+                AlterTableAction::AddColumn(col) if !col.if_not_exists => {
+                    if table.get_column(&col.name).is_some() || added.contains(&col.name.as_str()) {
+                        return Some(format!(
+                            "column `{}` of relation `{}` already exists",
+                            col.name, table.display_name
+                        ));
+                    }
+                    added.push(&col.name);
+                    None
+                }
+                AlterTableAction::DropConstraint {
+                    constraint_name,
+                    if_exists: false,
+                } if table.constraint_position(constraint_name).is_none() => Some(format!(
+                    "constraint `{constraint_name}` of relation `{}` does not exist",
+                    table.display_name
+                )),
+                AlterTableAction::AddConstraint(TableConstraint::ForeignKey {
+                    ref_table, ..
+                }) => missing_ref(ref_table, &at.name),
+                _ => None,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Apply a single IR node to the catalog. Returns a description of the
+/// conflict if the node creates an object the catalog already has.
+fn apply_node(catalog: &mut Catalog, node: &IrNode) -> Option<String> {
+    match node {
+        IrNode::CreateTable(ct) => return apply_create_table(catalog, ct),
         IrNode::AlterTable(at) => apply_alter_table(catalog, at),
-        IrNode::CreateIndex(ci) => apply_create_index(catalog, ci),
+        IrNode::CreateIndex(ci) => return apply_create_index(catalog, ci),
         IrNode::DropIndex(di) => apply_drop_index(catalog, di),
         IrNode::DropTable(dt) => apply_drop_table(catalog, dt),
         IrNode::RenameTable { name, new_name } => apply_rename_table(catalog, name, new_name),
@@ -41,10 +224,36 @@ fn apply_node(catalog: &mut Catalog, node: &IrNode) {
         IrNode::AlterIndexAttachPartition {
             parent_index_name, ..
         } => apply_alter_index_attach(catalog, parent_index_name),
+        IrNode::RenameIndex {
+            index_name,
+            new_name,
+        } => apply_rename_index(catalog, index_name, new_name),
+        IrNode::AlterIndexSetTablespace { .. } | IrNode::MoveAllInTablespace { .. } => {
+            /* tablespaces are not tracked */
+        }
+        IrNode::CreateSchema { .. } => { /* schemas are not tracked */ }
         IrNode::DropSchema(ds) => apply_drop_schema(catalog, ds),
+        IrNode::CreateExtension(ce) => catalog.add_extension(&ce.name),
+        IrNode::DropExtension(de) => apply_drop_extension(catalog, de),
+        IrNode::CreateSequence { name, owned_by, .. } => {
+            catalog.add_sequence(name.catalog_key(), owning_column(owned_by.as_ref()));
+        }
+        IrNode::SetSequenceOwner { name, owned_by } => {
+            catalog.set_sequence_owner(name.catalog_key(), owning_column(owned_by.as_ref()));
+        }
+        IrNode::DropSequence { name, .. } => {
+            catalog.remove_sequence(name.catalog_key());
+        }
+        IrNode::RenameSequence { name, new_name } => {
+            catalog.rename_sequence(name.catalog_key(), &sibling_key(name, new_name));
+        }
+        IrNode::Grant(_) | IrNode::AlterOwner(_) => { /* privileges not tracked */ }
+        IrNode::Replication(_) => { /* publications and subscriptions not tracked */ }
+        IrNode::TableAttachment { .. } => { /* comments and triggers not tracked */ }
         IrNode::TruncateTable(_)
         | IrNode::Cluster(_)
         | IrNode::VacuumFull(_)
+        | IrNode::VacuumAllTables
         | IrNode::Reindex(_) => { /* no schema state change */ }
         IrNode::InsertInto(_) | IrNode::UpdateTable(_) | IrNode::DeleteFrom(_) => {
             /* DML: no schema change */
@@ -52,6 +261,7 @@ fn apply_node(catalog: &mut Catalog, node: &IrNode) {
         IrNode::Unparseable { table_hint, .. } => apply_unparseable(catalog, table_hint),
         IrNode::Ignored { .. } => { /* no-op */ }
     }
+    None
 }
 
 /// Handle CREATE TABLE: insert a new table into the catalog with columns,
@@ -59,25 +269,25 @@ fn apply_node(catalog: &mut Catalog, node: &IrNode) {
 ///
 /// When `IF NOT EXISTS` is used and the table already exists, the statement
 /// is a no-op in PostgreSQL. We mirror that by keeping the existing catalog
-/// state and emitting a warning — the migration chain is ambiguous at that
+/// state and returning a conflict — the migration chain is ambiguous at that
 /// point (which definition is the truth?).
-fn apply_create_table(catalog: &mut Catalog, ct: &CreateTable) {
+fn apply_create_table(catalog: &mut Catalog, ct: &CreateTable) -> Option<String> {
     let table_key = ct.name.catalog_key().to_string();
 
+    let mut conflict = None;
     if catalog.has_table(&table_key) {
         if ct.if_not_exists {
-            eprintln!(
-                "warning: CREATE TABLE IF NOT EXISTS `{}` skipped — table already exists in catalog. \
+            return Some(format!(
+                "CREATE TABLE IF NOT EXISTS `{}` skipped — table already exists in catalog. \
                  The migration chain may be inconsistent.",
                 ct.name.display_name()
-            );
-            return;
+            ));
         }
-        eprintln!(
-            "warning: CREATE TABLE `{}` overwrites existing table in catalog. \
+        conflict = Some(format!(
+            "CREATE TABLE `{}` overwrites existing table in catalog. \
              The table may have been dropped outside tracked migrations, or this is a duplicate definition.",
             ct.name.display_name()
-        );
+        ));
     }
 
     let parent_key = ct
@@ -88,17 +298,25 @@ fn apply_create_table(catalog: &mut Catalog, ct: &CreateTable) {
     let mut table = TableState {
         name: table_key.clone(),
         display_name: ct.name.display_name(),
+        sql_name: ct.name.to_sql(),
         columns: Vec::new(),
         indexes: Vec::new(),
         constraints: Vec::new(),
         has_primary_key: false,
         incomplete: false,
+        unlogged: ct.persistence == TablePersistence::Unlogged,
+        replica_identity: ReplicaIdentity::Default,
         is_partitioned: ct.partition_by.is_some(),
         partition_by: ct.partition_by.as_ref().map(|pb| PartitionByInfo {
             strategy: pb.strategy,
             columns: pb.columns.clone(),
         }),
         parent_table: parent_key.clone(),
+        inherits: ct
+            .inherits
+            .iter()
+            .map(|p| p.catalog_key().to_string())
+            .collect(),
     };
 
     // For PARTITION OF, inherit columns from the parent table if it exists.
@@ -110,9 +328,44 @@ fn apply_create_table(catalog: &mut Catalog, ct: &CreateTable) {
         }
     }
 
-    // Convert columns (explicit columns on the child, or regular table columns)
+    // For INHERITS, merge in each parent's columns and CHECK constraints.
+    // PostgreSQL does not inherit primary keys, unique constraints, foreign
+    // keys, or indexes, so those are left out.
+    for parent_name in &ct.inherits {
+        let Some(parent) = catalog.get_table(parent_name.catalog_key()) else {
+            continue;
+        };
+        for col in &parent.columns {
+            if table.get_column(&col.name).is_none() {
+                table.columns.push(col.clone());
+            }
+        }
+        for constraint in &parent.constraints {
+            let ConstraintState::Check { name, .. } = constraint else {
+                continue;
+            };
+            let already_merged = name
+                .as_deref()
+                .is_some_and(|n| table.constraint_named(n).is_some());
+            if !already_merged {
+                table.constraints.push(constraint.clone());
+            }
+        }
+    }
+
+    // Convert columns (explicit columns on the child, or regular table columns).
+    // A column that is also inherited merges with the inherited one: the
+    // child's definition wins, but NOT NULL from a parent is kept.
     for col in &ct.columns {
-        table.columns.push(column_def_to_state(col));
+        let state = column_def_to_state(col);
+        match table.get_column_mut(&col.name) {
+            Some(inherited) if !ct.inherits.is_empty() => {
+                let nullable = inherited.nullable && state.nullable;
+                *inherited = state;
+                inherited.nullable = nullable;
+            }
+            _ => table.columns.push(state),
+        }
 
         // Handle inline PK on the column definition
         if col.is_inline_pk {
@@ -133,6 +386,58 @@ fn apply_create_table(catalog: &mut Catalog, ct: &CreateTable) {
     }
 
     catalog.insert_table(table);
+    add_implicit_sequences(catalog, &ct.name, &ct.columns);
+    conflict
+}
+
+/// Register the sequences PostgreSQL creates for `serial` and identity
+/// columns, named `{table}_{column}_seq` in the table's schema.
+fn add_implicit_sequences<'a>(
+    catalog: &mut Catalog,
+    table: &QualifiedName,
+    columns: impl IntoIterator<Item = &'a ColumnDef>,
+) {
+    for col in columns {
+        if col.is_serial || col.is_identity {
+            let seq_name = format!("{}_{}_seq", table.name, col.name);
+            let owner = OwningColumn {
+                table: table.catalog_key().to_string(),
+                column: col.name.clone(),
+            };
+            catalog.add_sequence(&sibling_key(table, &seq_name), Some(owner));
+        }
+    }
+}
+
+/// Catalog form of an `OWNED BY` clause; `OWNED BY NONE` is `None`.
+fn owning_column(owner: Option<&SequenceOwner>) -> Option<OwningColumn> {
+    owner.map(|owner| OwningColumn {
+        table: owner.table.catalog_key().to_string(),
+        column: owner.column.clone(),
+    })
+}
+
+/// Remove a dropped table along with the sequences its columns own.
+fn drop_table(catalog: &mut Catalog, key: &str) {
+    catalog.remove_table(key);
+    catalog.remove_owned_sequences(|owner| owner.table == key);
+}
+
+/// Catalog key for `new_name` in the same schema as `name`.
+pub(crate) fn sibling_key(name: &QualifiedName, new_name: &str) -> String {
+    match &name.schema {
+        Some(schema) => format!("{}.{}", schema, new_name),
+        None => {
+            // After normalization, schema should always be set. Fall back to
+            // extracting it from the old catalog key ("schema.name" format).
+            let old_key = name.catalog_key();
+            if let Some(dot) = old_key.find('.') {
+                format!("{}.{}", &old_key[..dot], new_name)
+            } else {
+                new_name.to_string()
+            }
+        }
+    }
 }
 
 /// Handle ALTER TABLE: apply each action to the existing table.
@@ -146,6 +451,15 @@ fn apply_alter_table(catalog: &mut Catalog, at: &AlterTable) {
         return;
     }
 
+    add_implicit_sequences(
+        catalog,
+        &at.name,
+        at.actions.iter().filter_map(|action| match action {
+            AlterTableAction::AddColumn(col) => Some(col),
+            _ => None,
+        }),
+    );
+
     // Collect indexes to register/unregister so we can update the reverse map
     // after releasing the mutable borrow on the table.
     let mut indexes_to_register: Vec<String> = Vec::new();
@@ -233,43 +547,23 @@ fn apply_alter_table(catalog: &mut Catalog, at: &AlterTable) {
                         col.default_expr = None;
                     }
                 }
-                AlterTableAction::DropConstraint { constraint_name } => {
-                    // Check if we're dropping a PK constraint.
-                    // Match by stored name, or fall back to the default
-                    // `{table}_pkey` heuristic for unnamed PKs.
+                AlterTableAction::DropConstraint {
+                    constraint_name, ..
+                } => {
                     let default_pkey_name = format!("{}_pkey", table.name);
-                    let dropping_pk = table.has_primary_key
-                        && table.constraints.iter().any(|c| match c {
-                            ConstraintState::PrimaryKey { name, .. } => {
-                                name.as_deref() == Some(constraint_name)
-                                    || (name.is_none() && *constraint_name == default_pkey_name)
-                            }
-                            _ => false,
-                        });
+                    // Match by stored name, or by the name PostgreSQL
+                    // would have generated for an unnamed constraint.
+                    let dropped = table
+                        .constraint_position(constraint_name)
+                        .map(|i| table.constraints.remove(i));
+                    let dropping_pk = matches!(dropped, Some(ConstraintState::PrimaryKey { .. }));
 
-                    // Grab `using_index` from UNIQUE constraints before retain removes them.
                     // When a UNIQUE constraint was created via USING INDEX, the backing
                     // index name may differ from the constraint name.
-                    let unique_using_index: Option<String> =
-                        table.constraints.iter().find_map(|c| match c {
-                            ConstraintState::Unique {
-                                name,
-                                using_index: Some(idx),
-                                ..
-                            } if name.as_deref() == Some(constraint_name) => Some(idx.clone()),
-                            _ => None,
-                        });
-
-                    // Remove named constraints (FK, Unique, Check, Exclude, PK).
-                    table.constraints.retain(|c| match c {
-                        ConstraintState::ForeignKey { name, .. }
-                        | ConstraintState::Unique { name, .. }
-                        | ConstraintState::Check { name, .. }
-                        | ConstraintState::Exclude { name, .. } => {
-                            name.as_deref() != Some(constraint_name)
-                        }
-                        ConstraintState::PrimaryKey { .. } => !dropping_pk,
-                    });
+                    let unique_using_index = match dropped {
+                        Some(ConstraintState::Unique { using_index, .. }) => using_index,
+                        _ => None,
+                    };
 
                     if dropping_pk {
                         table.has_primary_key = false;
@@ -325,9 +619,24 @@ fn apply_alter_table(catalog: &mut Catalog, at: &AlterTable) {
                     // Handled below, outside the table mutable borrow.
                 }
                 AlterTableAction::DisableTrigger { .. } => { /* triggers not tracked */ }
+                AlterTableAction::SetStorageParams { .. }
+                | AlterTableAction::ResetStorageParams { .. } => {
+                    /* storage parameters not tracked */
+                }
+                AlterTableAction::SetLogged => table.unlogged = false,
+                AlterTableAction::SetUnlogged => table.unlogged = true,
+                AlterTableAction::SetReplicaIdentity(identity) => {
+                    table.replica_identity = identity.clone();
+                }
+                AlterTableAction::ChangeOwner { .. } => { /* ownership not tracked */ }
                 AlterTableAction::Other { .. } => { /* ignore unmodeled actions */ }
             }
         }
+        if let ReplicaIdentity::UsingIndex(index) = &table.replica_identity
+            && indexes_to_unregister.contains(index)
+        {
+            table.replica_identity = ReplicaIdentity::Nothing;
+        }
     }
 
     // Update reverse map outside the table borrow.
@@ -338,9 +647,15 @@ fn apply_alter_table(catalog: &mut Catalog, at: &AlterTable) {
         catalog.register_index(&name, &table_key);
     }
 
-    // Handle partition attach/detach outside the table borrow scope.
+    // Handle partition attach/detach and owned sequences outside the table
+    // borrow scope.
     for action in &at.actions {
         match action {
+            AlterTableAction::DropColumn { name } => {
+                catalog.remove_owned_sequences(|owner| {
+                    owner.table == table_key && owner.column == *name
+                });
+            }
             AlterTableAction::AttachPartition { child } => {
                 let child_key = child.catalog_key().to_string();
                 if let Some(child_table) = catalog.get_table_mut(&child_key) {
@@ -362,30 +677,31 @@ fn apply_alter_table(catalog: &mut Catalog, at: &AlterTable) {
 /// If the table does not exist in the catalog, silently skip.
 ///
 /// When `IF NOT EXISTS` is used and a same-named index already exists,
-/// PostgreSQL treats it as a no-op. We keep the existing index and warn.
-fn apply_create_index(catalog: &mut Catalog, ci: &CreateIndex) {
+/// PostgreSQL treats it as a no-op. We keep the existing index and return a
+/// conflict.
+fn apply_create_index(catalog: &mut Catalog, ci: &CreateIndex) -> Option<String> {
     let table_key = ci.table_name.catalog_key().to_string();
 
     let index_name = ci.index_name.clone().unwrap_or_default();
 
+    let mut conflict = None;
     if !index_name.is_empty() && catalog.get_index(&index_name).is_some() {
         if ci.if_not_exists {
-            eprintln!(
-                "warning: CREATE INDEX IF NOT EXISTS `{}` skipped — index already exists in catalog. \
+            return Some(format!(
+                "CREATE INDEX IF NOT EXISTS `{}` skipped — index already exists in catalog. \
                  The migration chain may be inconsistent.",
                 index_name
-            );
-            return;
+            ));
         }
-        eprintln!(
-            "warning: CREATE INDEX `{}` overwrites existing index in catalog. \
+        conflict = Some(format!(
+            "CREATE INDEX `{}` overwrites existing index in catalog. \
              The index may have been dropped outside tracked migrations, or this is a duplicate definition.",
             index_name
-        );
+        ));
     }
 
     let Some(table) = catalog.get_table_mut(&table_key) else {
-        return;
+        return conflict;
     };
 
     let entries: Vec<IndexColumn> = ci.columns.to_vec();
@@ -401,6 +717,7 @@ fn apply_create_index(catalog: &mut Catalog, ci: &CreateIndex) {
 
     // Register after confirming the table exists, to avoid ghost entries.
     catalog.register_index(&index_name, &table_key);
+    conflict
 }
 
 /// Handle DROP INDEX: find and remove the named index from whichever table has it.
@@ -414,6 +731,10 @@ fn apply_drop_index(catalog: &mut Catalog, di: &DropIndex) {
 
     if let Some(table) = catalog.get_table_mut(&table_name) {
         table.indexes.retain(|idx| idx.name != di.index_name);
+        // Without its index, the replica identity behaves as NOTHING.
+        if table.replica_identity == ReplicaIdentity::UsingIndex(di.index_name.clone()) {
+            table.replica_identity = ReplicaIdentity::Nothing;
+        }
     }
 }
 
@@ -437,6 +758,47 @@ fn apply_alter_index_attach(catalog: &mut Catalog, parent_index_name: &str) {
     }
 }
 
+/// Handle ALTER INDEX ... RENAME TO: rename the index in place so later
+/// statements (`USING INDEX`, `DROP INDEX`, `ATTACH PARTITION`) find it
+/// under its new name.
+///
+/// PostgreSQL keeps a PRIMARY KEY or UNIQUE constraint's name in sync with
+/// its index, so a constraint named after the index is renamed too.
+fn apply_rename_index(catalog: &mut Catalog, index_name: &str, new_name: &str) {
+    let Some(table_key) = catalog.table_for_index(index_name).map(String::from) else {
+        return;
+    };
+    catalog.unregister_index(index_name);
+    catalog.register_index(new_name, &table_key);
+
+    let Some(table) = catalog.get_table_mut(&table_key) else {
+        return;
+    };
+    if let Some(idx) = table.indexes.iter_mut().find(|i| i.name == index_name) {
+        idx.name = new_name.to_string();
+    }
+    if table.replica_identity == ReplicaIdentity::UsingIndex(index_name.to_string()) {
+        table.replica_identity = ReplicaIdentity::UsingIndex(new_name.to_string());
+    }
+    for constraint in &mut table.constraints {
+        let (name, using_index) = match constraint {
+            ConstraintState::PrimaryKey { name, .. } => (name, None),
+            ConstraintState::Unique {
+                name, using_index, ..
+            } => (name, Some(using_index)),
+            _ => continue,
+        };
+        if name.as_deref() == Some(index_name) {
+            *name = Some(new_name.to_string());
+        }
+        if let Some(using_index) = using_index
+            && using_index.as_deref() == Some(index_name)
+        {
+            *using_index = Some(new_name.to_string());
+        }
+    }
+}
+
 /// Handle DROP TABLE: remove the table from the catalog entirely.
 ///
 /// For partitioned tables with CASCADE, recursively removes all partition
@@ -451,14 +813,21 @@ fn apply_drop_table(catalog: &mut Catalog, dt: &DropTable) {
     {
         let children_to_remove = collect_partition_subtree(catalog, &table_key);
         for child_key in children_to_remove {
-            catalog.remove_table(&child_key);
+            drop_table(catalog, &child_key);
+        }
+    }
+
+    // With CASCADE, inheritance children are dropped too.
+    if dt.cascade {
+        for child_key in collect_subtree(catalog, &table_key, Catalog::get_inheritance_children) {
+            drop_table(catalog, &child_key);
         }
     }
 
-    catalog.remove_table(&table_key);
+    drop_table(catalog, &table_key);
 }
 
-/// Handle DROP SCHEMA: remove all tables in the schema from the catalog.
+/// Handle DROP SCHEMA: remove all tables and sequences in the schema from the catalog.
 ///
 /// With CASCADE, all tables whose catalog key starts with `"{schema_name}."`
 /// are removed. Without CASCADE, PostgreSQL would error at runtime if the
@@ -476,13 +845,81 @@ fn apply_drop_schema(catalog: &mut Catalog, ds: &DropSchema) {
         .collect();
 
     for key in keys_to_remove {
-        catalog.remove_table(&key);
+        drop_table(catalog, &key);
+    }
+
+    let sequences: Vec<String> = catalog
+        .sequences()
+        .filter(|key| key.starts_with(&prefix))
+        .map(str::to_string)
+        .collect();
+    for key in sequences {
+        catalog.remove_sequence(&key);
+    }
+}
+
+/// Handle DROP EXTENSION: forget the extension, and with CASCADE remove
+/// what depended on it.
+///
+/// PostgreSQL drops columns whose type belongs to the extension and the
+/// defaults that call its functions. Only well-known extensions are
+/// modeled (see [`extensions::known_extension`]). Without CASCADE the
+/// statement would fail if anything depended on the extension, so the
+/// tables are left untouched.
+fn apply_drop_extension(catalog: &mut Catalog, de: &DropExtension) {
+    catalog.remove_extension(&de.name);
+    if !de.cascade {
+        return;
+    }
+    let Some(ext) = extensions::known_extension(&de.name) else {
+        return;
+    };
+
+    let dependents: Vec<(String, String, ExtensionDependency)> =
+        extensions::dependent_columns(catalog, ext)
+            .into_iter()
+            .map(|(t, c, dep)| (t.name.clone(), c.name.clone(), dep))
+            .collect();
+
+    let mut indexes_to_unregister = Vec::new();
+    for (table_key, column_name, dep) in dependents {
+        let Some(table) = catalog.get_table_mut(&table_key) else {
+            continue;
+        };
+        match dep {
+            ExtensionDependency::Type => {
+                for idx in &table.indexes {
+                    if idx.references_column(&column_name) {
+                        indexes_to_unregister.push(idx.name.clone());
+                    }
+                }
+                table.remove_column(&column_name);
+            }
+            ExtensionDependency::Default => {
+                if let Some(col) = table.get_column_mut(&column_name) {
+                    col.has_default = false;
+                    col.default_expr = None;
+                }
+            }
+        }
+    }
+    for name in indexes_to_unregister {
+        catalog.unregister_index(&name);
     }
 }
 
 /// Collect all partition children recursively (depth-first) for cascade removal.
 /// Uses a visited set to prevent cycles.
 fn collect_partition_subtree(catalog: &Catalog, root_key: &str) -> Vec<String> {
+    collect_subtree(catalog, root_key, Catalog::get_partition_children)
+}
+
+/// Collect all descendants of `root_key`, following `children` recursively.
+fn collect_subtree(
+    catalog: &Catalog,
+    root_key: &str,
+    children: fn(&Catalog, &str) -> Vec<String>,
+) -> Vec<String> {
     let mut result = Vec::new();
     let mut stack = vec![root_key.to_string()];
     let mut visited = std::collections::HashSet::new();
@@ -491,7 +928,7 @@ fn collect_partition_subtree(catalog: &Catalog, root_key: &str) -> Vec<String> {
         if !visited.insert(key.clone()) {
             continue;
         }
-        for child in catalog.get_partition_children(&key) {
+        for child in children(catalog, &key) {
             result.push(child.clone());
             stack.push(child);
         }
@@ -512,21 +949,11 @@ fn apply_rename_table(catalog: &mut Catalog, name: &QualifiedName, new_name: &st
 
     // Compute children before remove_table removes the table from the HashMap.
     let children = catalog.get_partition_children(&old_key);
+    let inheritance_children = catalog.get_inheritance_children(&old_key);
 
     if let Some(mut table) = catalog.remove_table(&old_key) {
         // Build the new key using the same schema as the old name.
-        let new_key = match &name.schema {
-            Some(schema) => format!("{}.{}", schema, new_name),
-            None => {
-                // After normalization, schema should always be set. Fall back to
-                // extracting it from the old catalog key ("schema.name" format).
-                if let Some(dot) = old_key.find('.') {
-                    format!("{}.{}", &old_key[..dot], new_name)
-                } else {
-                    new_name.to_string()
-                }
-            }
-        };
+        let new_key = sibling_key(name, new_name);
 
         // Update each child's parent_table to point to the new key.
         for child_key in &children {
@@ -534,9 +961,25 @@ fn apply_rename_table(catalog: &mut Catalog, name: &QualifiedName, new_name: &st
                 child.parent_table = Some(new_key.clone());
             }
         }
+        for child_key in &inheritance_children {
+            if let Some(child) = catalog.get_table_mut(child_key) {
+                for parent in &mut child.inherits {
+                    if *parent == old_key {
+                        parent.clone_from(&new_key);
+                    }
+                }
+            }
+        }
+
+        for owner in catalog.sequence_owners_mut() {
+            if owner.table == old_key {
+                owner.table.clone_from(&new_key);
+            }
+        }
 
         table.name = new_key.clone();
         table.display_name = new_name.to_string();
+        table.sql_name = Spelling::inferred(new_name).to_sql();
         catalog.insert_table(table);
     }
 }
@@ -556,6 +999,7 @@ fn apply_rename_column(
     // Rename the column itself.
     if let Some(col) = table.get_column_mut(old_name) {
         col.name = new_name.to_string();
+        col.sql_name = Spelling::inferred(new_name).to_sql();
     }
 
     // Update partition key columns if this table is partitioned.
@@ -632,6 +1076,13 @@ fn apply_rename_column(
             ConstraintState::Exclude { .. } => {}
         }
     }
+
+    // Sequences owned by the column follow it.
+    for owner in catalog.sequence_owners_mut() {
+        if owner.table == table_key && owner.column == old_name {
+            owner.column = new_name.to_string();
+        }
+    }
 }
 
 /// Replace a column name in an expression string, respecting word boundaries.
@@ -682,6 +1133,7 @@ fn apply_unparseable(catalog: &mut Catalog, table_hint: &Option<String>) {
 fn column_def_to_state(col: &ColumnDef) -> ColumnState {
     ColumnState {
         name: col.name.clone(),
+        sql_name: col.name_spelling().to_sql(),
         type_name: col.type_name.clone(),
         nullable: col.nullable,
         has_default: col.default_expr.is_some(),
@@ -785,11 +1237,13 @@ fn apply_table_constraint(table: &mut TableState, constraint: &TableConstraint)
             name,
             expression,
             not_valid,
+            deps,
         } => {
             table.constraints.push(ConstraintState::Check {
                 name: name.clone(),
                 expression: expression.clone(),
                 not_valid: *not_valid,
+                literal_lists: deps.literal_lists.clone(),
             });
         }
         TableConstraint::Exclude { name } => {
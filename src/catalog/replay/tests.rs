@@ -17,12 +17,15 @@ fn make_unit(nodes: Vec<IrNode>) -> MigrationUnit {
                     start_offset: 0,
                     end_offset: 0,
                 },
+                raw_sql: Default::default(),
             })
             .collect(),
         source_file: PathBuf::from("test.sql"),
         source_line_offset: 1,
         run_in_transaction: true,
         is_down: false,
+        suppressions: Default::default(),
+        changeset: Default::default(),
     }
 }
 
@@ -257,7 +260,7 @@ fn test_column_type_change() {
 
     let table = catalog.get_table("t").expect("table should exist");
     let column = table.get_column("x").expect("column x should exist");
-    assert_eq!(column.type_name.name, "bigint");
+    assert_eq!(&*column.type_name.name, "bigint");
 }
 
 #[test]
@@ -318,7 +321,7 @@ fn test_recreate_after_drop() {
         .get_table("t")
         .expect("table should exist after re-create");
     assert_eq!(table.columns.len(), 1);
-    assert_eq!(table.columns[0].type_name.name, "bigint");
+    assert_eq!(&*table.columns[0].type_name.name, "bigint");
 }
 
 #[test]
@@ -570,7 +573,8 @@ fn test_ignored_node_is_noop() {
             .with_columns(vec![col("id", "integer", false)])
             .into(),
         IrNode::Ignored {
-            raw_sql: "COMMENT ON TABLE t IS 'A table'".to_string(),
+            kind: StatementKind::Comment,
+            raw_sql: "COMMENT ON FUNCTION f() IS 'A function'".to_string(),
         },
     ]);
 
@@ -661,6 +665,7 @@ fn test_add_constraint_via_alter_table() {
                     name: Some("ck_email".to_string()),
                     expression: "email <> ''".to_string(),
                     not_valid: false,
+                    deps: CheckExprDeps::default(),
                 }),
                 AlterTableAction::AddConstraint(TableConstraint::Exclude {
                     name: Some("excl_email".to_string()),
@@ -993,6 +998,7 @@ fn test_drop_column_preserves_check_constraint() {
                 name: Some("chk_positive".to_string()),
                 expression: "amount > 0".to_string(),
                 not_valid: false,
+                deps: CheckExprDeps::default(),
             }])
             .into(),
         AlterTable {
@@ -1360,6 +1366,7 @@ fn test_drop_unique_using_index_removes_backing_index() {
         name: QualifiedName::qualified("public", "orders"),
         actions: vec![AlterTableAction::DropConstraint {
             constraint_name: "uq_orders_email".to_string(),
+            if_exists: false,
         }],
     })]);
     apply(&mut catalog, &drop_unit);
@@ -1576,6 +1583,48 @@ fn test_create_expression_index_stored_in_catalog() {
     assert_eq!(idx.column_names().count(), 0, "No plain column names");
 }
 
+#[test]
+fn test_sql_names_follow_source_spelling() {
+    let mut name = qname("UserAccounts");
+    name.set_spelling(
+        None,
+        Spelling {
+            quoted: true,
+            text: "UserAccounts".to_string(),
+        },
+    );
+    let mut id = col("id", "bigint", false);
+    id.spelling = Some(Spelling {
+        quoted: false,
+        text: "Id".to_string(),
+    });
+    let mut catalog = Catalog::new();
+    apply(
+        &mut catalog,
+        &make_unit(vec![
+            CreateTable::test(name)
+                .with_columns(vec![id, col("Email", "text", true)])
+                .into(),
+        ]),
+    );
+
+    let table = catalog.get_table("UserAccounts").expect("table exists");
+    assert_eq!(table.sql_name, "\"UserAccounts\"");
+    let sql_names: Vec<&str> = table.columns.iter().map(|c| c.sql_name.as_str()).collect();
+    assert_eq!(sql_names, vec!["Id", "\"Email\""]);
+
+    apply(
+        &mut catalog,
+        &make_unit(vec![IrNode::RenameColumn {
+            table: qname("UserAccounts"),
+            old_name: "id".to_string(),
+            new_name: "Key".to_string(),
+        }]),
+    );
+    let table = catalog.get_table("UserAccounts").expect("table exists");
+    assert_eq!(table.columns[0].sql_name, "\"Key\"");
+}
+
 #[test]
 fn test_rename_column_updates_index_entries() {
     let mut catalog = CatalogBuilder::new()
@@ -1757,6 +1806,96 @@ fn test_create_partitioned_table() {
     assert!(table.parent_table.is_none());
 }
 
+#[test]
+fn test_set_logged_and_unlogged_track_persistence() {
+    let mut catalog = Catalog::new();
+    let set = |action: AlterTableAction| -> IrNode {
+        AlterTable {
+            name: qname("events"),
+            actions: vec![action],
+        }
+        .into()
+    };
+
+    apply(
+        &mut catalog,
+        &make_unit(vec![
+            CreateTable::test(qname("events"))
+                .with_columns(vec![col("id", "integer", false)])
+                .with_persistence(TablePersistence::Unlogged)
+                .into(),
+        ]),
+    );
+    assert!(catalog.get_table("events").unwrap().unlogged);
+
+    apply(
+        &mut catalog,
+        &make_unit(vec![set(AlterTableAction::SetLogged)]),
+    );
+    assert!(!catalog.get_table("events").unwrap().unlogged);
+
+    apply(
+        &mut catalog,
+        &make_unit(vec![set(AlterTableAction::SetUnlogged)]),
+    );
+    assert!(catalog.get_table("events").unwrap().unlogged);
+}
+
+#[test]
+fn test_replica_identity_tracks_its_index() {
+    let mut catalog = Catalog::new();
+    let set = |identity: ReplicaIdentity| -> IrNode {
+        AlterTable {
+            name: qname("events"),
+            actions: vec![AlterTableAction::SetReplicaIdentity(identity)],
+        }
+        .into()
+    };
+    let identity = |catalog: &Catalog| {
+        catalog
+            .get_table("events")
+            .unwrap()
+            .replica_identity
+            .clone()
+    };
+
+    apply(
+        &mut catalog,
+        &make_unit(vec![
+            CreateTable::test(qname("events"))
+                .with_columns(vec![col("id", "integer", false)])
+                .into(),
+            CreateIndex::test(Some("events_id_key".to_string()), qname("events"))
+                .with_columns(vec![IndexColumn::Column("id".to_string())])
+                .with_unique(true)
+                .into(),
+            set(ReplicaIdentity::Full),
+        ]),
+    );
+    assert_eq!(identity(&catalog), ReplicaIdentity::Full);
+
+    apply(
+        &mut catalog,
+        &make_unit(vec![
+            set(ReplicaIdentity::UsingIndex("events_id_key".to_string())),
+            IrNode::RenameIndex {
+                index_name: "events_id_key".to_string(),
+                new_name: "events_id_uniq".to_string(),
+            },
+        ]),
+    );
+    assert_eq!(
+        identity(&catalog),
+        ReplicaIdentity::UsingIndex("events_id_uniq".to_string())
+    );
+
+    apply(
+        &mut catalog,
+        &make_unit(vec![DropIndex::test("events_id_uniq").into()]),
+    );
+    assert_eq!(identity(&catalog), ReplicaIdentity::Nothing);
+}
+
 #[test]
 fn test_create_partition_of_with_parent_in_catalog() {
     let mut catalog = Catalog::new();
@@ -1815,6 +1954,73 @@ fn test_create_partition_of_without_parent_in_catalog() {
     );
 }
 
+#[test]
+fn test_create_inherits_merges_columns_and_checks_but_not_pk() {
+    let mut catalog = Catalog::new();
+    let unit = make_unit(vec![
+        CreateTable::test(qname("cities"))
+            .with_columns(vec![col_pk("id", "integer"), col("name", "text", false)])
+            .with_constraints(vec![TableConstraint::Check {
+                name: Some("cities_name_not_blank".to_string()),
+                expression: "name <> ''".to_string(),
+                not_valid: false,
+                deps: CheckExprDeps::default(),
+            }])
+            .into(),
+        CreateTable::test(qname("capitals"))
+            .with_columns(vec![col("name", "text", true), col("state", "text", true)])
+            .with_inherits(vec![qname("cities")])
+            .into(),
+    ]);
+    apply(&mut catalog, &unit);
+
+    let child = catalog.get_table("capitals").expect("child should exist");
+    assert_eq!(child.inherits, vec!["cities"]);
+    assert!(child.parent_table.is_none());
+    let columns: Vec<_> = child.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(columns, vec!["id", "name", "state"]);
+    // NOT NULL from the parent survives the child's redeclaration
+    assert!(!child.get_column("name").unwrap().nullable);
+    assert!(child.constraint_named("cities_name_not_blank").is_some());
+    assert!(!child.has_primary_key);
+    assert!(child.indexes.is_empty());
+    assert_eq!(catalog.get_inheritance_children("cities"), vec!["capitals"]);
+    assert!(catalog.get_partition_children("cities").is_empty());
+}
+
+#[test]
+fn test_rename_and_drop_cascade_inheritance_parent() {
+    let mut catalog = CatalogBuilder::new()
+        .table("cities", |t| {
+            t.column("id", "integer", false);
+        })
+        .table("capitals", |t| {
+            t.column("id", "integer", false).inherits(&["cities"]);
+        })
+        .build();
+
+    apply(
+        &mut catalog,
+        &make_unit(vec![IrNode::RenameTable {
+            name: qname("cities"),
+            new_name: "towns".to_string(),
+        }]),
+    );
+    assert_eq!(
+        catalog.get_table("capitals").unwrap().inherits,
+        vec!["towns"]
+    );
+
+    apply(
+        &mut catalog,
+        &make_unit(vec![
+            DropTable::test(qname("towns")).with_cascade(true).into(),
+        ]),
+    );
+    assert!(!catalog.has_table("towns"));
+    assert!(!catalog.has_table("capitals"));
+}
+
 #[rstest]
 #[case::attach_existing_child(true, true)]
 #[case::attach_missing_child(true, false)]
@@ -2140,6 +2346,74 @@ fn test_drop_schema(
     }
 }
 
+fn extension_catalog() -> Catalog {
+    CatalogBuilder::new()
+        .table("products", |t| {
+            t.column_with_default(
+                "id",
+                "uuid",
+                false,
+                DefaultExpr::FunctionCall {
+                    name: "uuid_generate_v4".to_string(),
+                    args: vec![],
+                },
+            )
+            .column("attrs", "hstore", true)
+            .pk(&["id"])
+            .index("idx_products_attrs", &["attrs"], false);
+        })
+        .build()
+}
+
+#[test]
+fn test_create_and_drop_extension_tracked() {
+    let mut catalog = Catalog::new();
+    apply(
+        &mut catalog,
+        &make_unit(vec![CreateExtension::test("hstore").into()]),
+    );
+    assert!(catalog.has_extension("hstore"));
+
+    apply(
+        &mut catalog,
+        &make_unit(vec![DropExtension::test("hstore").into()]),
+    );
+    assert!(!catalog.has_extension("hstore"));
+}
+
+#[test]
+fn test_drop_extension_without_cascade_keeps_columns() {
+    let mut catalog = extension_catalog();
+    apply(
+        &mut catalog,
+        &make_unit(vec![DropExtension::test("hstore").into()]),
+    );
+
+    let table = catalog.get_table("products").unwrap();
+    assert!(table.get_column("attrs").is_some());
+}
+
+#[test]
+fn test_drop_extension_cascade_drops_dependents() {
+    let mut catalog = extension_catalog();
+    apply(
+        &mut catalog,
+        &make_unit(vec![
+            DropExtension::test("hstore").with_cascade(true).into(),
+            DropExtension::test("uuid-ossp").with_cascade(true).into(),
+        ]),
+    );
+
+    let table = catalog.get_table("products").unwrap();
+    assert!(table.get_column("attrs").is_none());
+    assert!(table.indexes.is_empty());
+    assert!(catalog.table_for_index("idx_products_attrs").is_none());
+
+    let id = table.get_column("id").unwrap();
+    assert!(!id.has_default);
+    assert!(id.default_expr.is_none());
+}
+
 #[test]
 fn test_rename_partitioned_parent() {
     let mut catalog = CatalogBuilder::new()
@@ -2285,6 +2559,72 @@ fn test_alter_index_attach_partition_missing_index_is_noop() {
     assert!(catalog.has_table("parent"));
 }
 
+#[test]
+fn test_rename_index_keeps_identity() {
+    let mut catalog = Catalog::new();
+
+    let unit1 = make_unit(vec![
+        CreateTable::test(qname("users"))
+            .with_columns(vec![col("email", "text", false)])
+            .into(),
+        IrNode::CreateIndex(
+            CreateIndex::test(Some("idx_email".to_string()), qname("users"))
+                .with_columns(vec![IndexColumn::Column("email".to_string())])
+                .with_unique(true),
+        ),
+        IrNode::AlterTable(AlterTable {
+            name: qname("users"),
+            actions: vec![AlterTableAction::AddConstraint(TableConstraint::Unique {
+                name: Some("uq_email".to_string()),
+                columns: vec![],
+                using_index: Some("idx_email".to_string()),
+            })],
+        }),
+    ]);
+    apply(&mut catalog, &unit1);
+
+    let unit2 = make_unit(vec![IrNode::RenameIndex {
+        index_name: "idx_email".to_string(),
+        new_name: "idx_users_email".to_string(),
+    }]);
+    apply(&mut catalog, &unit2);
+
+    assert!(catalog.get_index("idx_email").is_none());
+    let idx = catalog
+        .get_index("idx_users_email")
+        .expect("index should be found under its new name");
+    assert!(idx.unique);
+    assert_eq!(idx.column_names().collect::<Vec<_>>(), vec!["email"]);
+
+    let table = catalog.get_table("users").unwrap();
+    assert!(table.constraints.iter().any(|c| matches!(
+        c,
+        ConstraintState::Unique { using_index: Some(i), .. } if i == "idx_users_email"
+    )));
+
+    // A later DROP INDEX by the new name removes it.
+    let unit3 = make_unit(vec![IrNode::DropIndex(DropIndex {
+        index_name: "idx_users_email".to_string(),
+        concurrent: true,
+        if_exists: false,
+    })]);
+    apply(&mut catalog, &unit3);
+    assert!(catalog.get_table("users").unwrap().indexes.is_empty());
+}
+
+#[test]
+fn test_rename_missing_index_is_noop() {
+    let mut catalog = Catalog::new();
+
+    let unit = make_unit(vec![IrNode::RenameIndex {
+        index_name: "idx_nonexistent".to_string(),
+        new_name: "idx_other".to_string(),
+    }]);
+    apply(&mut catalog, &unit);
+
+    assert!(catalog.get_index("idx_other").is_none());
+}
+
 #[test]
 fn test_rename_column_updates_check_expression() {
     let mut catalog = Catalog::new();
@@ -2299,6 +2639,7 @@ fn test_rename_column_updates_check_expression() {
                 name: Some("chk_ts".to_string()),
                 expression: "(ts >= '2024-01-01')".to_string(),
                 not_valid: false,
+                deps: CheckExprDeps::default(),
             }])
             .into(),
     ]);
@@ -2512,6 +2853,25 @@ fn drop_constraint_setup_unique() -> (Catalog, &'static str, &'static str) {
     (catalog, "users", "uq_email")
 }
 
+fn drop_constraint_setup_unnamed_unique() -> (Catalog, &'static str, &'static str) {
+    let mut catalog = Catalog::new();
+    let unit1 = make_unit(vec![
+        CreateTable::test(qname("users"))
+            .with_columns(vec![
+                col("id", "integer", false),
+                col("email", "text", false),
+            ])
+            .with_constraints(vec![TableConstraint::Unique {
+                name: None,
+                columns: vec!["email".to_string()],
+                using_index: None,
+            }])
+            .into(),
+    ]);
+    apply(&mut catalog, &unit1);
+    (catalog, "users", "users_email_key")
+}
+
 fn drop_constraint_setup_check() -> (Catalog, &'static str, &'static str) {
     let mut catalog = Catalog::new();
     let unit1 = make_unit(vec![
@@ -2524,6 +2884,7 @@ fn drop_constraint_setup_check() -> (Catalog, &'static str, &'static str) {
                 name: Some("chk_positive".to_string()),
                 expression: "(amount > 0)".to_string(),
                 not_valid: false,
+                deps: CheckExprDeps::default(),
             }])
             .into(),
     ]);
@@ -2553,6 +2914,7 @@ fn drop_constraint_setup_exclude() -> (Catalog, &'static str, &'static str) {
 #[case::fk(drop_constraint_setup_fk())]
 #[case::pk(drop_constraint_setup_pk())]
 #[case::unique(drop_constraint_setup_unique())]
+#[case::unnamed_unique(drop_constraint_setup_unnamed_unique())]
 #[case::check(drop_constraint_setup_check())]
 #[case::exclude(drop_constraint_setup_exclude())]
 fn test_drop_constraint_by_type(#[case] setup: (Catalog, &'static str, &'static str)) {
@@ -2570,6 +2932,7 @@ fn test_drop_constraint_by_type(#[case] setup: (Catalog, &'static str, &'static
         name: qname(table_key),
         actions: vec![AlterTableAction::DropConstraint {
             constraint_name: constraint_name.to_string(),
+            if_exists: false,
         }],
     })]);
     apply(&mut catalog, &unit);
@@ -2616,6 +2979,7 @@ fn test_drop_custom_named_pk_constraint() {
         name: qname("orders"),
         actions: vec![AlterTableAction::DropConstraint {
             constraint_name: "pk_orders".to_string(),
+            if_exists: false,
         }],
     })]);
     apply(&mut catalog, &unit2);
@@ -2627,6 +2991,41 @@ fn test_drop_custom_named_pk_constraint() {
     );
 }
 
+#[test]
+fn test_drop_unnamed_fk_on_qualified_table_by_default_name() {
+    let mut catalog = Catalog::new();
+
+    let unit1 = make_unit(vec![
+        CreateTable::test(QualifiedName::qualified("app", "orders"))
+            .with_columns(vec![
+                col("id", "integer", false),
+                col("customer_id", "integer", false),
+            ])
+            .with_constraints(vec![TableConstraint::ForeignKey {
+                name: None,
+                columns: vec!["customer_id".to_string()],
+                ref_table: QualifiedName::qualified("app", "customers"),
+                ref_columns: vec!["id".to_string()],
+                not_valid: false,
+            }])
+            .into(),
+    ]);
+    apply(&mut catalog, &unit1);
+
+    // PostgreSQL names the FK from the bare relation name, without schema.
+    let unit2 = make_unit(vec![IrNode::AlterTable(AlterTable {
+        name: QualifiedName::qualified("app", "orders"),
+        actions: vec![AlterTableAction::DropConstraint {
+            constraint_name: "orders_customer_id_fkey".to_string(),
+            if_exists: false,
+        }],
+    })]);
+    apply(&mut catalog, &unit2);
+
+    let table = catalog.get_table("app.orders").unwrap();
+    assert!(table.constraints.is_empty());
+}
+
 #[test]
 fn test_drop_nonexistent_constraint() {
     let mut catalog = Catalog::new();
@@ -2643,6 +3042,7 @@ fn test_drop_nonexistent_constraint() {
         name: qname("orders"),
         actions: vec![AlterTableAction::DropConstraint {
             constraint_name: "ghost_constraint".to_string(),
+            if_exists: false,
         }],
     })]);
     apply(&mut catalog, &unit2);
@@ -2668,6 +3068,7 @@ fn test_drop_constraint_preserves_others() {
                     name: Some("chk_positive".to_string()),
                     expression: "(amount > 0)".to_string(),
                     not_valid: false,
+                    deps: CheckExprDeps::default(),
                 },
                 TableConstraint::Unique {
                     name: Some("uq_email".to_string()),
@@ -2691,6 +3092,7 @@ fn test_drop_constraint_preserves_others() {
         name: qname("orders"),
         actions: vec![AlterTableAction::DropConstraint {
             constraint_name: "chk_positive".to_string(),
+            if_exists: false,
         }],
     })]);
     apply(&mut catalog, &unit2);
@@ -2758,6 +3160,7 @@ fn validate_constraint_setup_check() -> (Catalog, &'static str, &'static str, bo
                 name: Some("chk_positive".to_string()),
                 expression: "(amount > 0)".to_string(),
                 not_valid: true,
+                deps: CheckExprDeps::default(),
             }])
             .into(),
     ]);
@@ -2774,6 +3177,7 @@ fn validate_constraint_setup_nonexistent() -> (Catalog, &'static str, &'static s
                 name: Some("chk_real".to_string()),
                 expression: "(id > 0)".to_string(),
                 not_valid: true,
+                deps: CheckExprDeps::default(),
             }])
             .into(),
     ]);
@@ -2794,6 +3198,7 @@ fn validate_constraint_setup_already_valid() -> (Catalog, &'static str, &'static
                 name: Some("chk_positive".to_string()),
                 expression: "(amount > 0)".to_string(),
                 not_valid: false, // already valid
+                deps: CheckExprDeps::default(),
             }])
             .into(),
     ]);
@@ -2889,6 +3294,7 @@ fn test_drop_unique_constraint_removes_backing_index() {
         name: qname("users"),
         actions: vec![AlterTableAction::DropConstraint {
             constraint_name: "uq_email".to_string(),
+            if_exists: false,
         }],
     })]);
     apply(&mut catalog, &unit4);
@@ -3206,3 +3612,338 @@ fn test_replay_preserves_access_method() {
     );
     assert!(!idx.is_btree(), "GIN index should not be btree");
 }
+
+#[test]
+fn test_sequences_tracked_through_create_rename_and_drop() {
+    let mut catalog = Catalog::new();
+    let billing_seq = QualifiedName::qualified("billing", "invoice_seq");
+    apply(
+        &mut catalog,
+        &make_unit(vec![
+            IrNode::CreateSequence {
+                name: billing_seq.clone(),
+                if_not_exists: false,
+                owned_by: None,
+            },
+            CreateTable::test(QualifiedName::qualified("public", "orders"))
+                .with_columns(vec![
+                    ColumnDef::test("id", "integer").with_serial(),
+                    ColumnDef::test("ref", "bigint").with_identity(),
+                    col("note", "text", true),
+                ])
+                .into(),
+            IrNode::RenameSequence {
+                name: billing_seq,
+                new_name: "invoice_no_seq".to_string(),
+            },
+        ]),
+    );
+    let mut sequences: Vec<&str> = catalog.sequences().collect();
+    sequences.sort_unstable();
+    assert_eq!(
+        sequences,
+        [
+            "billing.invoice_no_seq",
+            "public.orders_id_seq",
+            "public.orders_ref_seq"
+        ]
+    );
+
+    apply(
+        &mut catalog,
+        &make_unit(vec![
+            IrNode::DropSequence {
+                name: QualifiedName::qualified("public", "orders_ref_seq"),
+                if_exists: false,
+            },
+            DropSchema::test("billing").with_cascade(true).into(),
+        ]),
+    );
+    assert_eq!(
+        catalog.sequences().collect::<Vec<_>>(),
+        ["public.orders_id_seq"]
+    );
+}
+
+#[test]
+fn test_owned_sequences_follow_their_column() {
+    let mut catalog = Catalog::new();
+    let orders = QualifiedName::qualified("public", "orders");
+    let owner = |column: &str| SequenceOwner {
+        table: orders.clone(),
+        column: column.to_string(),
+    };
+    apply(
+        &mut catalog,
+        &make_unit(vec![
+            CreateTable::test(orders.clone())
+                .with_columns(vec![
+                    ColumnDef::test("id", "integer").with_serial(),
+                    col("invoice_no", "bigint", false),
+                    col("ref", "bigint", false),
+                ])
+                .into(),
+            IrNode::CreateSequence {
+                name: QualifiedName::qualified("public", "invoice_no_seq"),
+                if_not_exists: false,
+                owned_by: Some(owner("invoice_no")),
+            },
+            IrNode::CreateSequence {
+                name: QualifiedName::qualified("public", "ref_seq"),
+                if_not_exists: false,
+                owned_by: None,
+            },
+            IrNode::SetSequenceOwner {
+                name: QualifiedName::qualified("public", "ref_seq"),
+                owned_by: Some(owner("ref")),
+            },
+            IrNode::RenameTable {
+                name: orders.clone(),
+                new_name: "sales".to_string(),
+            },
+            IrNode::RenameColumn {
+                table: QualifiedName::qualified("public", "sales"),
+                old_name: "ref".to_string(),
+                new_name: "reference".to_string(),
+            },
+        ]),
+    );
+    let owner = catalog.sequence_owner("public.ref_seq").unwrap();
+    assert_eq!(
+        (owner.table.as_str(), owner.column.as_str()),
+        ("public.sales", "reference")
+    );
+    assert_eq!(
+        catalog
+            .sequence_owner("public.orders_id_seq")
+            .unwrap()
+            .column,
+        "id"
+    );
+
+    apply(
+        &mut catalog,
+        &make_unit(vec![IrNode::AlterTable(AlterTable {
+            name: QualifiedName::qualified("public", "sales"),
+            actions: vec![AlterTableAction::DropColumn {
+                name: "invoice_no".to_string(),
+            }],
+        })]),
+    );
+    assert!(!catalog.has_sequence("public.invoice_no_seq"));
+    assert!(catalog.has_sequence("public.ref_seq"));
+
+    apply(
+        &mut catalog,
+        &make_unit(vec![
+            IrNode::CreateSequence {
+                name: QualifiedName::qualified("public", "standalone_seq"),
+                if_not_exists: false,
+                owned_by: None,
+            },
+            DropTable::test(QualifiedName::qualified("public", "sales")).into(),
+        ]),
+    );
+    assert_eq!(
+        catalog.sequences().collect::<Vec<_>>(),
+        ["public.standalone_seq"]
+    );
+}
+
+fn alter_orders(actions: Vec<AlterTableAction>) -> IrNode {
+    IrNode::AlterTable(AlterTable {
+        name: qname("orders"),
+        actions,
+    })
+}
+
+fn fk_to(table: &str) -> TableConstraint {
+    TableConstraint::ForeignKey {
+        name: None,
+        columns: vec!["customer_id".to_string()],
+        ref_table: qname(table),
+        ref_columns: vec!["id".to_string()],
+        not_valid: false,
+    }
+}
+
+#[rstest]
+#[case::duplicate_table(
+    CreateTable::test(qname("orders")).into(),
+    "relation `orders` already exists"
+)]
+#[case::duplicate_index(
+    CreateIndex::test(Some("idx_orders_note".to_string()), qname("orders")).into(),
+    "relation `idx_orders_note` already exists"
+)]
+#[case::duplicate_column(
+    alter_orders(vec![AlterTableAction::AddColumn(col("note", "text", true))]),
+    "column `note` of relation `orders` already exists"
+)]
+#[case::column_added_twice(
+    alter_orders(vec![
+        AlterTableAction::AddColumn(col("status", "text", true)),
+        AlterTableAction::AddColumn(col("status", "text", true)),
+    ]),
+    "column `status` of relation `orders` already exists"
+)]
+#[case::missing_constraint(
+    alter_orders(vec![AlterTableAction::DropConstraint {
+        constraint_name: "orders_status_check".to_string(),
+        if_exists: false,
+    }]),
+    "constraint `orders_status_check` of relation `orders` does not exist"
+)]
+#[case::fk_to_missing_table(
+    alter_orders(vec![AlterTableAction::AddConstraint(fk_to("customers"))]),
+    "foreign key references `customers`, which does not exist"
+)]
+#[case::create_table_fk_to_missing_table(
+    CreateTable::test(qname("invoices"))
+        .with_constraints(vec![fk_to("customers")])
+        .into(),
+    "foreign key references `customers`, which does not exist"
+)]
+fn test_strict_replay_rejects(#[case] node: IrNode, #[case] reason: &str) {
+    let mut catalog = CatalogBuilder::new()
+        .table("orders", |t| {
+            t.column("id", "bigint", false)
+                .column("note", "text", true)
+                .pk(&["id"])
+                .index("idx_orders_note", &["note"], false);
+        })
+        .build();
+
+    let warnings = apply_with(&mut catalog, &make_unit(vec![node.clone()]), true);
+    assert_eq!(warnings.len(), 1, "{warnings:?}");
+    assert_eq!(warnings[0].category, WarningCategory::Rejected);
+    assert_eq!(warnings[0].message, reason);
+
+    // The rejected statement was not applied.
+    let orders = catalog.get_table("orders").unwrap();
+    assert_eq!(orders.columns.len(), 2);
+    assert_eq!(orders.indexes.len(), 1);
+    assert_eq!(orders.constraints.len(), 1);
+    assert!(!catalog.has_table("invoices"));
+
+    // Lenient replay never rejects.
+    let lenient = apply(&mut catalog, &make_unit(vec![node]));
+    assert!(
+        lenient
+            .iter()
+            .all(|w| w.category != WarningCategory::Rejected)
+    );
+}
+
+#[rstest]
+#[case::add_column_if_not_exists(alter_orders(vec![AlterTableAction::AddColumn(ColumnDef {
+    if_not_exists: true,
+    ..col("note", "text", true)
+})]))]
+#[case::drop_constraint_if_exists(alter_orders(vec![AlterTableAction::DropConstraint {
+    constraint_name: "orders_status_check".to_string(),
+    if_exists: true,
+}]))]
+#[case::drop_existing_constraint(alter_orders(vec![AlterTableAction::DropConstraint {
+    constraint_name: "orders_pkey".to_string(),
+    if_exists: false,
+}]))]
+#[case::fk_to_existing_table(alter_orders(vec![AlterTableAction::AddConstraint(fk_to("orders"))]))]
+#[case::self_referencing_fk(
+    CreateTable::test(qname("invoices"))
+        .with_constraints(vec![fk_to("invoices")])
+        .into()
+)]
+#[case::table_if_not_exists(CreateTable::test(qname("orders")).with_if_not_exists(true).into())]
+#[case::unknown_table(IrNode::AlterTable(AlterTable {
+    name: qname("shipments"),
+    actions: vec![AlterTableAction::AddColumn(col("note", "text", true))],
+}))]
+fn test_strict_replay_accepts(#[case] node: IrNode) {
+    let mut catalog = CatalogBuilder::new()
+        .table("orders", |t| {
+            t.column("id", "bigint", false)
+                .column("note", "text", true)
+                .pk(&["id"]);
+        })
+        .build();
+
+    let warnings = apply_with(&mut catalog, &make_unit(vec![node]), true);
+    assert!(
+        warnings
+            .iter()
+            .all(|w| w.category != WarningCategory::Rejected),
+        "{warnings:?}"
+    );
+}
+
+#[test]
+fn test_strict_replay_skips_incomplete_tables() {
+    let mut catalog = CatalogBuilder::new()
+        .table("orders", |t| {
+            t.column("note", "text", true).incomplete();
+        })
+        .build();
+
+    let unit = make_unit(vec![alter_orders(vec![AlterTableAction::AddColumn(col(
+        "note", "text", true,
+    ))])]);
+    assert!(apply_with(&mut catalog, &unit, true).is_empty());
+}
+
+#[test]
+fn test_schema_catalog_from_pg_dump() {
+    let dump = "\\restrict AbC123\n\
+SET statement_timeout = 0;\n\
+SELECT pg_catalog.set_config('search_path', '', false);\n\
+SET default_tablespace = '';\n\
+\n\
+CREATE TABLE public.customers (\n\
+    id bigint NOT NULL\n\
+);\n\
+ALTER TABLE public.customers OWNER TO app;\n\
+\n\
+CREATE TABLE public.orders (\n\
+    id bigint NOT NULL,\n\
+    customer_id bigint NOT NULL,\n\
+    status text\n\
+);\n\
+COMMENT ON TABLE public.orders IS 'Customer orders';\n\
+\n\
+CREATE SEQUENCE public.orders_id_seq\n\
+    START WITH 1\n\
+    INCREMENT BY 1\n\
+    NO MINVALUE\n\
+    NO MAXVALUE\n\
+    CACHE 1;\n\
+ALTER SEQUENCE public.orders_id_seq OWNED BY public.orders.id;\n\
+ALTER TABLE ONLY public.orders ALTER COLUMN id SET DEFAULT nextval('public.orders_id_seq'::regclass);\n\
+\n\
+ALTER TABLE ONLY public.customers\n\
+    ADD CONSTRAINT customers_pkey PRIMARY KEY (id);\n\
+ALTER TABLE ONLY public.orders\n\
+    ADD CONSTRAINT orders_pkey PRIMARY KEY (id);\n\
+CREATE INDEX idx_orders_status ON public.orders USING btree (status);\n\
+ALTER TABLE ONLY public.orders\n\
+    ADD CONSTRAINT orders_customer_id_fkey FOREIGN KEY (customer_id) REFERENCES public.customers(id);\n\
+\n\
+\\unrestrict AbC123\n";
+
+    let (catalog, warnings) = schema_catalog(dump, Path::new("schema.sql"), "public");
+    assert_eq!(warnings, vec![]);
+
+    let orders = catalog.get_table("public.orders").expect("orders");
+    assert!(orders.has_primary_key);
+    assert!(orders.get_column("id").unwrap().default_expr.is_some());
+    assert!(orders.indexes.iter().any(|i| i.name == "idx_orders_status"));
+    assert!(orders.constraints.iter().any(|c| matches!(
+        c,
+        ConstraintState::ForeignKey { ref_table, .. } if ref_table == "public.customers"
+    )));
+    assert!(
+        catalog
+            .get_table("public.customers")
+            .unwrap()
+            .has_primary_key
+    );
+}
@@ -0,0 +1,343 @@
+//! Saved catalog snapshots
+//!
+//! `--save-catalog` writes the replayed [`Catalog`] together with the
+//! migration units that built it; `--load-catalog` starts from that catalog
+//! and skips those units, so a pull request only replays and lints the
+//! migrations added since the snapshot was taken (e.g. on `main`).
+//!
+//! Each unit is recorded by id, source file, and a fingerprint of its
+//! statements. A recorded unit whose statements have changed since makes
+//! the snapshot stale: the catalog no longer reflects the history, so the
+//! run fails rather than linting against it. Snapshots are tied to the tool
+//! version that wrote them.
+//!
+//! A snapshot of a long history is large JSON. With the `zstd` feature, a
+//! path ending in `.zst` is written zstd-compressed; reading recognizes a
+//! compressed file by its magic number, whatever its name.
+
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::Catalog;
+use crate::input::MigrationUnit;
+
+/// Layout version of the snapshot file.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// The first four bytes of a zstd frame.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// zstd level for compressed snapshots: the library default, which is
+/// fast to write and already shrinks catalog JSON many times over.
+#[cfg(feature = "zstd")]
+const ZSTD_LEVEL: i32 = 3;
+
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error("could not read catalog snapshot {path}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("could not write catalog snapshot {path}: {source}")]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("{path} is not a catalog snapshot: {message}")]
+    Parse { path: PathBuf, message: String },
+
+    #[error(
+        "catalog snapshot {path} is zstd-compressed, but this pg-migration-lint \
+         was built without the zstd feature"
+    )]
+    CompressionUnsupported { path: PathBuf },
+
+    #[error(
+        "catalog snapshot {path} was written by pg-migration-lint {found}; \
+         save it again with this version ({expected})"
+    )]
+    Version {
+        path: PathBuf,
+        found: String,
+        expected: String,
+    },
+
+    #[error(
+        "catalog snapshot is stale: {file} ({id}) changed after the snapshot was saved; \
+         save it again or run without --load-catalog"
+    )]
+    Stale { file: PathBuf, id: String },
+}
+
+/// A catalog and the units replayed to build it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CatalogSnapshot {
+    pub version: u32,
+    /// Version of pg-migration-lint that wrote the snapshot.
+    pub tool_version: String,
+    /// The units in the snapshot, in replay order.
+    pub units: Vec<SnapshotUnit>,
+    pub catalog: Catalog,
+}
+
+/// A migration unit recorded in a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotUnit {
+    pub id: String,
+    /// Source file, relative to the working directory when it is inside it.
+    pub file: PathBuf,
+    /// Hash of the unit's statements and transaction mode.
+    pub fingerprint: String,
+}
+
+impl SnapshotUnit {
+    pub fn of(unit: &MigrationUnit) -> Self {
+        let mut hasher = DefaultHasher::new();
+        for statement in &unit.statements {
+            statement.raw_sql.hash(&mut hasher);
+        }
+        unit.run_in_transaction.hash(&mut hasher);
+        unit.is_down.hash(&mut hasher);
+        Self {
+            id: unit.id.clone(),
+            file: relative_to_cwd(&unit.source_file),
+            fingerprint: format!("{:016x}", hasher.finish()),
+        }
+    }
+}
+
+fn relative_to_cwd(path: &Path) -> PathBuf {
+    std::env::current_dir()
+        .ok()
+        .and_then(|cwd| path.strip_prefix(cwd).ok())
+        .unwrap_or(path)
+        .to_path_buf()
+}
+
+impl CatalogSnapshot {
+    pub fn new(catalog: Catalog, units: Vec<SnapshotUnit>) -> Self {
+        Self {
+            version: SNAPSHOT_VERSION,
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            units,
+            catalog,
+        }
+    }
+
+    /// Read a snapshot written by [`write`](Self::write) with this version
+    /// of the tool.
+    pub fn read(path: &Path) -> Result<Self, SnapshotError> {
+        let bytes = std::fs::read(path).map_err(|source| SnapshotError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let json = if bytes.starts_with(&ZSTD_MAGIC) {
+            decompress(path, &bytes)?
+        } else {
+            bytes
+        };
+        let snapshot: Self = serde_json::from_slice(&json).map_err(|e| SnapshotError::Parse {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+        let expected = env!("CARGO_PKG_VERSION");
+        if snapshot.version != SNAPSHOT_VERSION || snapshot.tool_version != expected {
+            return Err(SnapshotError::Version {
+                path: path.to_path_buf(),
+                found: snapshot.tool_version,
+                expected: expected.to_string(),
+            });
+        }
+        Ok(snapshot)
+    }
+
+    /// Write the snapshot as JSON, creating the parent directory if needed.
+    /// A path ending in `.zst` is written zstd-compressed (feature `zstd`).
+    pub fn write(&self, path: &Path) -> Result<(), SnapshotError> {
+        let write_err = |source| SnapshotError::Write {
+            path: path.to_path_buf(),
+            source,
+        };
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(write_err)?;
+        }
+        let json = serde_json::to_vec(self).map_err(|e| write_err(e.into()))?;
+        let bytes = if is_compressed_path(path) {
+            compress(path, &json)?
+        } else {
+            json
+        };
+        std::fs::write(path, bytes).map_err(write_err)
+    }
+
+    /// A lookup of the recorded units by id and file.
+    pub fn recorded(&self) -> RecordedUnits<'_> {
+        RecordedUnits(
+            self.units
+                .iter()
+                .map(|u| ((u.id.as_str(), u.file.as_path()), u.fingerprint.as_str()))
+                .collect(),
+        )
+    }
+}
+
+/// Whether `path` asks for a compressed snapshot.
+fn is_compressed_path(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "zst")
+}
+
+#[cfg(feature = "zstd")]
+fn compress(path: &Path, json: &[u8]) -> Result<Vec<u8>, SnapshotError> {
+    zstd::encode_all(json, ZSTD_LEVEL).map_err(|source| SnapshotError::Write {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(not(feature = "zstd"))]
+fn compress(path: &Path, _json: &[u8]) -> Result<Vec<u8>, SnapshotError> {
+    Err(SnapshotError::CompressionUnsupported {
+        path: path.to_path_buf(),
+    })
+}
+
+#[cfg(feature = "zstd")]
+fn decompress(path: &Path, bytes: &[u8]) -> Result<Vec<u8>, SnapshotError> {
+    zstd::decode_all(bytes).map_err(|source| SnapshotError::Read {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(not(feature = "zstd"))]
+fn decompress(path: &Path, _bytes: &[u8]) -> Result<Vec<u8>, SnapshotError> {
+    Err(SnapshotError::CompressionUnsupported {
+        path: path.to_path_buf(),
+    })
+}
+
+/// The units of a loaded snapshot, for deciding which units to skip.
+pub struct RecordedUnits<'a>(HashMap<(&'a str, &'a Path), &'a str>);
+
+impl RecordedUnits<'_> {
+    /// `Ok(true)` if the snapshot already contains `unit`, `Ok(false)` if it
+    /// is new, and an error if the snapshot recorded a different version of
+    /// it.
+    pub fn contains(&self, unit: &SnapshotUnit) -> Result<bool, SnapshotError> {
+        match self.0.get(&(unit.id.as_str(), unit.file.as_path())) {
+            None => Ok(false),
+            Some(fingerprint) if *fingerprint == unit.fingerprint => Ok(true),
+            Some(_) => Err(SnapshotError::Stale {
+                file: unit.file.clone(),
+                id: unit.id.clone(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::builder::CatalogBuilder;
+
+    fn unit(id: &str, sql: &str) -> MigrationUnit {
+        MigrationUnit {
+            id: id.to_string(),
+            statements: crate::parser::pg_query::parse_sql(sql),
+            source_file: PathBuf::from(format!("migrations/{id}.sql")),
+            source_line_offset: 1,
+            run_in_transaction: true,
+            is_down: false,
+            suppressions: Default::default(),
+            changeset: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_write_read_and_recorded_units() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("cache/catalog.json");
+        let catalog = CatalogBuilder::new()
+            .table("public.orders", |t| {
+                t.column("id", "bigint", false).pk(&["id"]);
+            })
+            .build();
+        let v1 = unit("V1", "CREATE TABLE orders (id bigint PRIMARY KEY);");
+        CatalogSnapshot::new(catalog, vec![SnapshotUnit::of(&v1)])
+            .write(&path)
+            .expect("write");
+
+        let snapshot = CatalogSnapshot::read(&path).expect("read");
+        assert!(snapshot.catalog.has_table("public.orders"));
+
+        let recorded = snapshot.recorded();
+        assert!(recorded.contains(&SnapshotUnit::of(&v1)).unwrap());
+        let v2 = unit("V2", "ALTER TABLE orders ADD COLUMN total numeric;");
+        assert!(!recorded.contains(&SnapshotUnit::of(&v2)).unwrap());
+        let edited = unit("V1", "CREATE TABLE orders (id int PRIMARY KEY);");
+        assert!(matches!(
+            recorded.contains(&SnapshotUnit::of(&edited)),
+            Err(SnapshotError::Stale { .. })
+        ));
+    }
+
+    #[test]
+    fn test_read_rejects_other_versions_and_garbage() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("catalog.json");
+        let mut snapshot = CatalogSnapshot::new(Catalog::new(), vec![]);
+        snapshot.tool_version = "0.0.1".to_string();
+        snapshot.write(&path).expect("write");
+        assert!(matches!(
+            CatalogSnapshot::read(&path),
+            Err(SnapshotError::Version { .. })
+        ));
+
+        std::fs::write(&path, "{}").expect("write");
+        assert!(matches!(
+            CatalogSnapshot::read(&path),
+            Err(SnapshotError::Parse { .. })
+        ));
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zst_path_round_trips_compressed() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("catalog.json.zst");
+        let catalog = CatalogBuilder::new()
+            .table("public.orders", |t| {
+                t.column("id", "bigint", false).pk(&["id"]);
+            })
+            .build();
+        CatalogSnapshot::new(catalog, vec![])
+            .write(&path)
+            .expect("write");
+
+        let bytes = std::fs::read(&path).expect("read");
+        assert!(bytes.starts_with(&ZSTD_MAGIC));
+        let snapshot = CatalogSnapshot::read(&path).expect("read");
+        assert!(snapshot.catalog.has_table("public.orders"));
+    }
+
+    #[cfg(not(feature = "zstd"))]
+    #[test]
+    fn test_zst_path_needs_zstd_feature() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("catalog.json.zst");
+        assert!(matches!(
+            CatalogSnapshot::new(Catalog::new(), vec![]).write(&path),
+            Err(SnapshotError::CompressionUnsupported { .. })
+        ));
+    }
+}
@@ -3,14 +3,43 @@
 //! The catalog represents the database schema state at a point in migration history.
 //! It's built by replaying migrations in order.
 
-use crate::parser::ir::{DefaultExpr, IndexColumn, PartitionStrategy, TypeName};
-use std::collections::HashMap;
-
-#[derive(Debug, Clone, Default)]
+use crate::intern::intern;
+use crate::parser::ir::{
+    DefaultExpr, IndexColumn, LiteralList, PartitionStrategy, ReplicaIdentity, TypeName,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+/// Tables are stored behind `Arc` with interned keys so that cloning the
+/// catalog (done once per linted unit for `catalog_before`) copies pointers,
+/// not table contents. Mutation goes through [`Arc::make_mut`], which only
+/// deep-copies a table when a snapshot still shares it.
+///
+/// Serializes losslessly (unlike the [`dump`](crate::catalog::dump) view),
+/// so a replayed catalog can be saved and reloaded; see
+/// [`to_json`](Self::to_json).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(into = "CatalogData", from = "CatalogData")]
 pub struct Catalog {
-    tables: HashMap<String, TableState>,
-    /// Reverse lookup: index name → owning table key.
-    index_to_table: HashMap<String, String>,
+    tables: HashMap<Arc<str>, Arc<TableState>>,
+    /// Reverse lookup: index name → owning table key. Both are interned.
+    index_to_table: HashMap<Arc<str>, Arc<str>>,
+    /// Extensions installed by `CREATE EXTENSION` in replayed migrations.
+    extensions: HashSet<String>,
+    /// Sequences created by `CREATE SEQUENCE`, `serial`, or identity columns
+    /// in replayed migrations, by catalog key, with the column that owns
+    /// each one (`OWNED BY`), if any.
+    sequences: HashMap<String, Option<OwningColumn>>,
+}
+
+/// The column a sequence is owned by. PostgreSQL drops the sequence with
+/// the column or its table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OwningColumn {
+    /// Catalog key of the table.
+    pub table: String,
+    pub column: String,
 }
 
 impl Catalog {
@@ -18,12 +47,23 @@ impl Catalog {
         Self::default()
     }
 
+    /// Serialize the whole catalog as JSON. Tables are sorted by catalog
+    /// key, so the same catalog always gives the same text.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    /// Rebuild a catalog written by [`to_json`](Self::to_json).
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
     pub fn get_table(&self, name: &str) -> Option<&TableState> {
-        self.tables.get(name)
+        self.tables.get(name).map(|t| &**t)
     }
 
     pub(crate) fn get_table_mut(&mut self, name: &str) -> Option<&mut TableState> {
-        self.tables.get_mut(name)
+        self.tables.get_mut(name).map(Arc::make_mut)
     }
 
     pub fn has_table(&self, name: &str) -> bool {
@@ -31,22 +71,23 @@ impl Catalog {
     }
 
     pub(crate) fn insert_table(&mut self, table: TableState) {
+        let key = intern(&table.name);
         // Register all indexes in the reverse lookup.
         for idx in &table.indexes {
             if !idx.name.is_empty() {
                 self.index_to_table
-                    .insert(idx.name.clone(), table.name.clone());
+                    .insert(intern(&idx.name), Arc::clone(&key));
             }
         }
-        self.tables.insert(table.name.clone(), table);
+        self.tables.insert(key, Arc::new(table));
     }
 
     pub(crate) fn remove_table(&mut self, name: &str) -> Option<TableState> {
         if let Some(table) = self.tables.remove(name) {
             for idx in &table.indexes {
-                self.index_to_table.remove(&idx.name);
+                self.index_to_table.remove(idx.name.as_str());
             }
-            Some(table)
+            Some(Arc::unwrap_or_clone(table))
         } else {
             None
         }
@@ -56,7 +97,7 @@ impl Catalog {
     pub(crate) fn register_index(&mut self, index_name: &str, table_key: &str) {
         if !index_name.is_empty() {
             self.index_to_table
-                .insert(index_name.to_string(), table_key.to_string());
+                .insert(intern(index_name), intern(table_key));
         }
     }
 
@@ -67,7 +108,7 @@ impl Catalog {
 
     /// Look up which table owns a given index. O(1).
     pub(crate) fn table_for_index(&self, index_name: &str) -> Option<&str> {
-        self.index_to_table.get(index_name).map(|s| s.as_str())
+        self.index_to_table.get(index_name).map(|s| &**s)
     }
 
     /// Look up an index by name across all tables. Returns the `IndexState` if found.
@@ -78,7 +119,77 @@ impl Catalog {
     }
 
     pub fn tables(&self) -> impl Iterator<Item = &TableState> {
-        self.tables.values()
+        self.tables.values().map(|t| &**t)
+    }
+
+    pub fn has_extension(&self, name: &str) -> bool {
+        self.extensions.contains(name)
+    }
+
+    pub fn extensions(&self) -> impl Iterator<Item = &str> {
+        self.extensions.iter().map(String::as_str)
+    }
+
+    pub(crate) fn add_extension(&mut self, name: &str) {
+        self.extensions.insert(name.to_string());
+    }
+
+    pub(crate) fn remove_extension(&mut self, name: &str) {
+        self.extensions.remove(name);
+    }
+
+    pub fn has_sequence(&self, key: &str) -> bool {
+        self.sequences.contains_key(key)
+    }
+
+    pub fn sequences(&self) -> impl Iterator<Item = &str> {
+        self.sequences.keys().map(String::as_str)
+    }
+
+    /// The column that owns the sequence, if the sequence is tracked and
+    /// owned.
+    pub fn sequence_owner(&self, key: &str) -> Option<&OwningColumn> {
+        self.sequences.get(key)?.as_ref()
+    }
+
+    pub(crate) fn add_sequence(&mut self, key: &str, owner: Option<OwningColumn>) {
+        self.sequences.insert(key.to_string(), owner);
+    }
+
+    /// Forget a sequence. Returns `true` if it was tracked.
+    pub(crate) fn remove_sequence(&mut self, key: &str) -> bool {
+        self.sequences.remove(key).is_some()
+    }
+
+    /// Move a sequence to a new key, keeping its owner. Returns `true` if it
+    /// was tracked.
+    pub(crate) fn rename_sequence(&mut self, key: &str, new_key: &str) -> bool {
+        match self.sequences.remove(key) {
+            Some(owner) => {
+                self.sequences.insert(new_key.to_string(), owner);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Set the owner of a tracked sequence (`ALTER SEQUENCE ... OWNED BY`).
+    pub(crate) fn set_sequence_owner(&mut self, key: &str, owner: Option<OwningColumn>) {
+        if let Some(current) = self.sequences.get_mut(key) {
+            *current = owner;
+        }
+    }
+
+    /// Owners of every owned sequence, for renames of tables and columns.
+    pub(crate) fn sequence_owners_mut(&mut self) -> impl Iterator<Item = &mut OwningColumn> {
+        self.sequences.values_mut().flatten()
+    }
+
+    /// Drop the sequences whose owner matches, as PostgreSQL does when the
+    /// owning column or table is dropped.
+    pub(crate) fn remove_owned_sequences(&mut self, owned: impl Fn(&OwningColumn) -> bool) {
+        self.sequences
+            .retain(|_, owner| !owner.as_ref().is_some_and(&owned));
     }
 
     /// Returns the catalog keys of all partition children of the given parent.
@@ -94,6 +205,15 @@ impl Catalog {
             .collect()
     }
 
+    /// Return the catalog keys of all tables that list `key` in `INHERITS`.
+    pub(crate) fn get_inheritance_children(&self, key: &str) -> Vec<String> {
+        self.tables
+            .values()
+            .filter(|t| t.inherits.iter().any(|p| p == key))
+            .map(|t| t.name.clone())
+            .collect()
+    }
+
     /// Returns `true` if the given table is a partition child (has a `parent_table`).
     #[cfg(test)]
     pub(crate) fn is_partition_child(&self, key: &str) -> bool {
@@ -104,18 +224,62 @@ impl Catalog {
     }
 }
 
+/// Serialized form of [`Catalog`]: the index lookup is rebuilt from the
+/// tables, and keys are re-interned, when it is read back.
+#[derive(Serialize, Deserialize)]
+struct CatalogData {
+    tables: Vec<TableState>,
+    #[serde(default)]
+    extensions: Vec<String>,
+    #[serde(default)]
+    sequences: BTreeMap<String, Option<OwningColumn>>,
+}
+
+impl From<Catalog> for CatalogData {
+    fn from(catalog: Catalog) -> Self {
+        let mut tables: Vec<TableState> = catalog
+            .tables
+            .into_values()
+            .map(Arc::unwrap_or_clone)
+            .collect();
+        tables.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut extensions: Vec<String> = catalog.extensions.into_iter().collect();
+        extensions.sort();
+        Self {
+            tables,
+            extensions,
+            sequences: catalog.sequences.into_iter().collect(),
+        }
+    }
+}
+
+impl From<CatalogData> for Catalog {
+    fn from(data: CatalogData) -> Self {
+        let mut catalog = Catalog::new();
+        for table in data.tables {
+            catalog.insert_table(table);
+        }
+        catalog.extensions = data.extensions.into_iter().collect();
+        catalog.sequences = data.sequences.into_iter().collect();
+        catalog
+    }
+}
+
 /// Partition key specification stored in the catalog.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PartitionByInfo {
     pub strategy: PartitionStrategy,
     pub columns: Vec<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TableState {
     pub name: String,
     /// User-facing name (omits synthetic schema prefix).
     pub display_name: String,
+    /// `display_name` as SQL, quoted and cased the way the migration wrote
+    /// it (`"UserAccounts"`), for reproducing the name in suggested SQL.
+    pub sql_name: String,
     pub columns: Vec<ColumnState>,
     pub indexes: Vec<IndexState>,
     pub constraints: Vec<ConstraintState>,
@@ -123,12 +287,24 @@ pub struct TableState {
     /// True if an unparseable statement referenced this table.
     /// Rules should consider lowering confidence on findings for incomplete tables.
     pub incomplete: bool,
+    /// True if the table is `UNLOGGED`, from `CREATE UNLOGGED TABLE` or the
+    /// latest `SET LOGGED` / `SET UNLOGGED`.
+    pub unlogged: bool,
+    /// `REPLICA IDENTITY` from the latest `ALTER TABLE ... REPLICA IDENTITY`.
+    /// Dropping the `USING INDEX` index leaves `Nothing`, which is how
+    /// PostgreSQL then behaves.
+    pub replica_identity: ReplicaIdentity,
     /// True if this table uses `PARTITION BY` (is a partitioned parent table).
     pub is_partitioned: bool,
     /// Partition strategy and columns, if this table is partitioned.
     pub partition_by: Option<PartitionByInfo>,
     /// Catalog key of the parent table, if this table is a partition child.
     pub parent_table: Option<String>,
+    /// Catalog keys of the `INHERITS (...)` parents, in declaration order.
+    /// Unlike partitions, inheritance children get the parents' columns and
+    /// CHECK constraints but not their primary key, unique constraints,
+    /// foreign keys, or indexes.
+    pub inherits: Vec<String>,
 }
 
 impl TableState {
@@ -228,6 +404,23 @@ impl TableState {
         })
     }
 
+    /// Find a constraint by name. Unnamed constraints match the default name
+    /// PostgreSQL would have given them (see [`ConstraintState::default_name`]).
+    pub fn constraint_named(&self, constraint_name: &str) -> Option<&ConstraintState> {
+        self.constraint_position(constraint_name)
+            .map(|i| &self.constraints[i])
+    }
+
+    /// Position of the constraint [`constraint_named`](Self::constraint_named) finds.
+    pub(crate) fn constraint_position(&self, constraint_name: &str) -> Option<usize> {
+        // PostgreSQL derives default names from the bare relation name.
+        let relname = self.name.rsplit('.').next().unwrap_or(&self.name);
+        self.constraints.iter().position(|c| match c.name() {
+            Some(name) => name == constraint_name,
+            None => c.default_name(relname).as_deref() == Some(constraint_name),
+        })
+    }
+
     /// Returns all constraints that involve the given column.
     pub fn constraints_involving_column(&self, col: &str) -> Vec<&ConstraintState> {
         self.constraints
@@ -276,22 +469,24 @@ impl TableState {
 ///
 /// Splits on non-identifier characters and checks for an exact token match.
 /// This avoids false positives like `ts` matching `timestamp`.
-fn expression_mentions_column(expression: &str, column: &str) -> bool {
+pub(crate) fn expression_mentions_column(expression: &str, column: &str) -> bool {
     expression
         .split(|c: char| !c.is_alphanumeric() && c != '_')
         .any(|token| token == column)
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ColumnState {
     pub name: String,
+    /// `name` as SQL, quoted and cased the way the migration wrote it.
+    pub sql_name: String,
     pub type_name: TypeName, // Reuses the IR type
     pub nullable: bool,
     pub has_default: bool,
     pub default_expr: Option<DefaultExpr>, // Reuses the IR type
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IndexState {
     pub name: String,
     /// Index entries in definition order. Order matters for prefix matching.
@@ -339,7 +534,7 @@ impl IndexState {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ConstraintState {
     PrimaryKey {
         name: Option<String>,
@@ -365,6 +560,8 @@ pub enum ConstraintState {
         name: Option<String>,
         expression: String,
         not_valid: bool,
+        /// Columns the expression tests against a fixed list of literals.
+        literal_lists: Vec<LiteralList>,
     },
     Exclude {
         name: Option<String>,
@@ -372,6 +569,35 @@ pub enum ConstraintState {
 }
 
 impl ConstraintState {
+    /// The explicit constraint name, if one was given.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            ConstraintState::PrimaryKey { name, .. }
+            | ConstraintState::ForeignKey { name, .. }
+            | ConstraintState::Unique { name, .. }
+            | ConstraintState::Check { name, .. }
+            | ConstraintState::Exclude { name } => name.as_deref(),
+        }
+    }
+
+    /// The name PostgreSQL generates for an unnamed constraint on `table`:
+    /// `{table}_pkey`, `{table}_{cols}_fkey`, or `{table}_{cols}_key`.
+    /// `None` for CHECK and EXCLUDE, whose generated names depend on
+    /// expression columns the catalog does not track. Names are not
+    /// truncated to PostgreSQL's 63-byte limit.
+    pub fn default_name(&self, table: &str) -> Option<String> {
+        match self {
+            ConstraintState::PrimaryKey { .. } => Some(format!("{table}_pkey")),
+            ConstraintState::ForeignKey { columns, .. } => {
+                Some(format!("{table}_{}_fkey", columns.join("_")))
+            }
+            ConstraintState::Unique { columns, .. } => {
+                Some(format!("{table}_{}_key", columns.join("_")))
+            }
+            ConstraintState::Check { .. } | ConstraintState::Exclude { .. } => None,
+        }
+    }
+
     /// Returns true if this constraint involves the given column name.
     pub fn involves_column(&self, col: &str) -> bool {
         match self {
@@ -393,6 +619,26 @@ mod tests {
     use crate::parser::ir::PartitionStrategy;
     use rstest::rstest;
 
+    #[test]
+    fn test_catalog_clone_shares_index_lookup_keys() {
+        let catalog = CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "bigint", false)
+                    .index("idx_orders_id", &["id"], false);
+            })
+            .build();
+        let copy = catalog.clone();
+        let key = |c: &Catalog| {
+            let (name, table) = c.index_to_table.iter().next().expect("one index");
+            (Arc::clone(name), Arc::clone(table))
+        };
+        let ((name, table), (copy_name, copy_table)) = (key(&catalog), key(&copy));
+        assert_eq!(&*name, "idx_orders_id");
+        assert!(Arc::ptr_eq(&name, &copy_name));
+        assert!(Arc::ptr_eq(&table, &copy_table));
+        assert!(Arc::ptr_eq(&name, &intern("idx_orders_id")));
+    }
+
     #[test]
     fn test_is_partition_child() {
         let catalog = CatalogBuilder::new()
@@ -414,6 +660,58 @@ mod tests {
         assert!(!catalog.is_partition_child("nonexistent"));
     }
 
+    #[test]
+    fn test_json_round_trip() {
+        let mut catalog = CatalogBuilder::new()
+            .table("public.orders", |t| {
+                t.column("id", "bigint", false)
+                    .column("status", "text", true)
+                    .pk(&["id"])
+                    .index("idx_orders_status", &["status"], false)
+                    .check_constraint(Some("chk_status"), "(status <> '')", false);
+            })
+            .table("public.orders_2024", |t| {
+                t.column("id", "bigint", false)
+                    .partition_of("public.orders");
+            })
+            .build();
+        catalog.add_extension("pgcrypto");
+        catalog.add_sequence(
+            "public.orders_id_seq",
+            Some(OwningColumn {
+                table: "public.orders".to_string(),
+                column: "id".to_string(),
+            }),
+        );
+
+        let json = catalog.to_json().expect("serialize");
+        let restored = Catalog::from_json(&json).expect("deserialize");
+
+        assert_eq!(restored.to_json().expect("serialize"), json);
+        let orders = restored.get_table("public.orders").expect("orders");
+        assert_eq!(
+            orders.get_column("id").unwrap().type_name.name.as_ref(),
+            "bigint"
+        );
+        assert!(orders.has_primary_key);
+        assert_eq!(
+            restored.table_for_index("idx_orders_status"),
+            Some("public.orders")
+        );
+        assert_eq!(
+            restored.get_partition_children("public.orders"),
+            vec!["public.orders_2024"]
+        );
+        assert!(restored.has_extension("pgcrypto"));
+        assert_eq!(
+            restored
+                .sequence_owner("public.orders_id_seq")
+                .map(|o| o.column.as_str()),
+            Some("id")
+        );
+        assert!(Catalog::from_json("{\"tables\": 3}").is_err());
+    }
+
     #[test]
     fn test_remove_table_returns_none_for_nonexistent() {
         let mut catalog = CatalogBuilder::new()
@@ -465,6 +763,7 @@ mod tests {
             name: Some("chk_positive".to_string()),
             expression: "(amount > 0)".to_string(),
             not_valid: false,
+            literal_lists: vec![],
         };
         assert_eq!(
             constraint.involves_column(col),
@@ -2,8 +2,11 @@
 //!
 //! Reads pg-migration-lint.toml configuration files.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::path::{Path, PathBuf};
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::LazyLock;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -19,7 +22,7 @@ pub enum ConfigError {
 }
 
 /// Main configuration structure
-#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
 pub struct Config {
     #[serde(default)]
     pub migrations: MigrationsConfig,
@@ -35,13 +38,44 @@ pub struct Config {
 
     #[serde(default)]
     pub rules: RulesConfig,
+
+    #[serde(default)]
+    pub meta: MetaConfig,
+
+    #[serde(default)]
+    pub schemas: SchemasConfig,
+
+    #[serde(default)]
+    pub messages: MessagesConfig,
+
+    #[serde(default)]
+    pub postgres: PostgresConfig,
+
+    #[serde(default)]
+    pub patterns: Vec<PatternConfig>,
+
+    #[serde(default)]
+    pub rename_workflow: RenameWorkflowConfig,
+
+    #[serde(default)]
+    pub lock_budget: LockBudgetConfig,
+
+    #[serde(default)]
+    pub warnings: WarningsConfig,
+
+    #[serde(default)]
+    pub catalog: CatalogConfig,
+
+    #[serde(default)]
+    pub cache: CacheConfig,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct MigrationsConfig {
-    /// Paths to migration directories or changelog files
+    /// Paths to migration directories or changelog files, optionally with
+    /// options for the migrations under them
     #[serde(default)]
-    pub paths: Vec<PathBuf>,
+    pub paths: Vec<MigrationPath>,
 
     /// Migration ordering strategy
     #[serde(default = "default_strategy")]
@@ -66,22 +100,134 @@ pub struct MigrationsConfig {
     /// Set to `false` for golang-migrate repos where files run outside transactions.
     #[serde(default)]
     pub run_in_transaction: Option<bool>,
+
+    /// Skip SQL files ignored by git (`.gitignore`, `.git/info/exclude`)
+    /// when scanning migration directories (default: `true`).
+    #[serde(default = "default_true")]
+    pub respect_gitignore: bool,
+
+    /// Load symlinked SQL files found in migration directories
+    /// (default: `true`). When `false`, symlinks are skipped.
+    #[serde(default = "default_true")]
+    pub follow_symlinks: bool,
+
+    /// Read and lint the files named by psql `\i`/`\ir` meta-commands
+    /// (default: `false`). Other meta-commands are always stripped.
+    #[serde(default)]
+    pub resolve_psql_includes: bool,
+
+    /// Directory that psql includes and files included by Liquibase
+    /// changelogs must stay inside, after `..` and symlinks are resolved.
+    /// Guards against a changelog from an untrusted pull request reading
+    /// files elsewhere on the CI runner. Relative to the config file, like
+    /// `paths`. Default: the config file's directory, or the working
+    /// directory without one.
+    #[serde(default)]
+    pub include_root: Option<PathBuf>,
+}
+
+/// An entry of `migrations.paths`: a bare path, or a table with the path
+/// and options for the migrations under it.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(untagged)]
+pub enum MigrationPath {
+    Path(PathBuf),
+    Detailed {
+        path: PathBuf,
+
+        /// Default schema for the migrations under `path`, overriding
+        /// `migrations.default_schema`.
+        #[serde(default)]
+        default_schema: Option<String>,
+    },
+}
+
+impl MigrationPath {
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Path(path) | Self::Detailed { path, .. } => path,
+        }
+    }
+
+    fn path_mut(&mut self) -> &mut PathBuf {
+        match self {
+            Self::Path(path) | Self::Detailed { path, .. } => path,
+        }
+    }
+
+    pub fn default_schema(&self) -> Option<&str> {
+        match self {
+            Self::Path(_) => None,
+            Self::Detailed { default_schema, .. } => default_schema.as_deref(),
+        }
+    }
+
+    /// Whether `file` is one of the migrations under this entry. An entry
+    /// naming a changelog file covers its directory, where the files it
+    /// includes usually live.
+    fn contains(&self, file: &Path) -> bool {
+        let path = without_cur_dir(self.path());
+        let root = if path.is_file() {
+            path.parent().unwrap_or(&path).to_path_buf()
+        } else {
+            path
+        };
+        without_cur_dir(file).starts_with(root)
+    }
+}
+
+impl From<PathBuf> for MigrationPath {
+    fn from(path: PathBuf) -> Self {
+        Self::Path(path)
+    }
+}
+
+impl MigrationsConfig {
+    /// The directory includes must stay inside: `include_root` (set by
+    /// [`Config::resolve_paths`] when loaded from a file), or else the
+    /// working directory. `None` only when neither is known.
+    pub fn effective_include_root(&self) -> Option<PathBuf> {
+        self.include_root
+            .clone()
+            .or_else(|| std::env::current_dir().ok())
+    }
+
+    /// The configured migration paths, without their options.
+    pub fn source_paths(&self) -> Vec<PathBuf> {
+        self.paths.iter().map(|p| p.path().to_path_buf()).collect()
+    }
+
+    /// Default schema for unqualified names in `file`: that of the most
+    /// specific `paths` entry containing it that sets one, or else
+    /// `default_schema`.
+    pub fn default_schema_for(&self, file: &Path) -> &str {
+        self.paths
+            .iter()
+            .filter(|p| p.contains(file))
+            .filter_map(|p| Some((p.path().components().count(), p.default_schema()?)))
+            .max_by_key(|(depth, _)| *depth)
+            .map_or(&self.default_schema, |(_, schema)| schema)
+    }
 }
 
 impl Default for MigrationsConfig {
     fn default() -> Self {
         Self {
-            paths: vec![PathBuf::from("db/migrations")],
+            paths: vec![PathBuf::from("db/migrations").into()],
             strategy: default_strategy(),
             include: default_include(),
             exclude: vec![],
             default_schema: default_schema(),
             run_in_transaction: None,
+            respect_gitignore: true,
+            follow_symlinks: true,
+            resolve_psql_includes: false,
+            include_root: None,
         }
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct LiquibaseConfig {
     /// Path to liquibase-bridge.jar
     pub bridge_jar_path: Option<PathBuf>,
@@ -95,6 +241,17 @@ pub struct LiquibaseConfig {
     /// Strategy: "auto", "bridge", "update-sql"
     #[serde(default = "default_liquibase_strategy")]
     pub strategy: String,
+
+    /// Contexts of the run (Liquibase `--contexts`). When set, changesets
+    /// whose `context` expression does not match are neither replayed nor
+    /// linted.
+    #[serde(default)]
+    pub contexts: Vec<String>,
+
+    /// Label expression of the run (Liquibase `--label-filter`). When set,
+    /// changesets whose `labels` do not match are neither replayed nor linted.
+    #[serde(default)]
+    pub labels: Option<String>,
 }
 
 impl Default for LiquibaseConfig {
@@ -104,13 +261,15 @@ impl Default for LiquibaseConfig {
             binary_path: Some(PathBuf::from("liquibase")),
             properties_file: None,
             strategy: default_liquibase_strategy(),
+            contexts: vec![],
+            labels: None,
         }
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct OutputConfig {
-    /// Output formats: "sarif", "sonarqube", "text"
+    /// Output formats: "sarif", "sonarqube", "text", "pr-comment", "json"
     #[serde(default = "default_formats")]
     pub formats: Vec<String>,
 
@@ -123,6 +282,63 @@ pub struct OutputConfig {
     /// Example: `strip_prefix = "impl/"` turns `impl/src/main/...` into `src/main/...`.
     #[serde(default)]
     pub strip_prefix: Option<String>,
+
+    /// Mask string and numeric literals in SQL echoed into finding messages,
+    /// `[messages]` template parameters, and the `--emit-catalog` dump, so
+    /// seed values do not end up in report artifacts.
+    #[serde(default)]
+    pub redact_literals: bool,
+
+    /// Embed the text of the statement each finding points at (`snippet` in
+    /// JSON, `region.snippet` in SARIF). Masked when `redact_literals` is set.
+    #[serde(default)]
+    pub include_snippets: bool,
+
+    /// Options for the `pr-comment` Markdown summary.
+    #[serde(default)]
+    pub pr_comment: PrCommentConfig,
+
+    /// Options for the `json` report.
+    #[serde(default)]
+    pub json: JsonOutputConfig,
+}
+
+/// Options for the `json` format (`[output.json]`).
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct JsonOutputConfig {
+    /// Add the replayed catalog (the `--emit-catalog` dump) to the report.
+    #[serde(default)]
+    pub include_catalog: bool,
+}
+
+/// Options for the `pr-comment` format (`[output.pr_comment]`).
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct PrCommentConfig {
+    /// Most findings listed in the comment; the rest are only counted.
+    /// `--max-items` overrides it.
+    #[serde(default = "default_pr_comment_max_items")]
+    pub max_items: usize,
+
+    /// Base URL that finding paths are appended to for links, e.g.
+    /// `https://github.com/acme/shop/blob/main`. Without it, locations are
+    /// plain text.
+    #[serde(default)]
+    pub source_url: Option<String>,
+
+    /// URL of the full reports (e.g. the CI job's artifacts page), linked
+    /// at the end of the comment.
+    #[serde(default)]
+    pub artifacts_url: Option<String>,
+}
+
+impl Default for PrCommentConfig {
+    fn default() -> Self {
+        Self {
+            max_items: default_pr_comment_max_items(),
+            source_url: None,
+            artifacts_url: None,
+        }
+    }
 }
 
 impl Default for OutputConfig {
@@ -131,39 +347,696 @@ impl Default for OutputConfig {
             formats: default_formats(),
             dir: default_output_dir(),
             strip_prefix: None,
+            redact_literals: false,
+            include_snippets: false,
+            pr_comment: PrCommentConfig::default(),
+            json: JsonOutputConfig::default(),
         }
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct CliConfig {
     /// Exit non-zero if findings meet or exceed this severity
     #[serde(default = "default_fail_on")]
     pub fail_on: String,
+
+    /// Lint the migration files changed since the merge base of this git ref
+    /// and `HEAD` (e.g. `"origin/main"`), as `--changed-from-git` does.
+    /// `--changed-files`, `--changed-files-from`, and `--changed-from-git`
+    /// take precedence. Default: unset, lint every migration.
+    #[serde(default)]
+    pub changed_from_git: Option<String>,
 }
 
 impl Default for CliConfig {
     fn default() -> Self {
         Self {
             fail_on: default_fail_on(),
+            changed_from_git: None,
         }
     }
 }
 
 /// Configuration for rule selection.
-#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
 pub struct RulesConfig {
     /// Rule IDs to disable globally (e.g., `["PGM006", "PGM101"]`).
     /// Findings from disabled rules are not emitted.
-    /// Invalid rule IDs cause a config-load error (exit 2).
+    /// IDs are resolved against the assembled
+    /// [`RuleRegistry`](crate::rules::RuleRegistry); unknown IDs and IDs from
+    /// rule packs that are not loaded cause a config error (exit 2).
+    #[serde(default)]
+    pub disabled: Vec<crate::rules::RuleRef>,
+
+    /// Rule families to disable globally (e.g., `["3xx", "5xx"]`), matched
+    /// against [`RuleId::family_prefix`](crate::rules::RuleId::family_prefix).
+    /// Applies in addition to `disabled`. Unknown families cause a
+    /// config-load error (exit 2).
+    #[serde(default)]
+    pub disabled_families: Vec<String>,
+
+    /// Opt-in rules to turn on (e.g., `["PGM514"]`). Rules that are off by
+    /// default (see [`RuleId::is_opt_in`](crate::rules::RuleId::is_opt_in))
+    /// only run when listed here; `disabled` still wins.
+    #[serde(default)]
+    pub enabled: Vec<crate::rules::RuleRef>,
+
+    /// Options for PGM107 (integer primary key).
     #[serde(default)]
-    pub disabled: Vec<crate::rules::RuleId>,
+    pub pgm107: Pgm107Config,
+
+    /// Options for PGM110 (UUID stored as text).
+    #[serde(default)]
+    pub pgm110: Pgm110Config,
+
+    /// Options for PGM111 (discouraged column types).
+    #[serde(default)]
+    pub pgm111: Pgm111Config,
+
+    /// Options for PGM201 (DROP TABLE on existing table).
+    #[serde(default)]
+    pub pgm201: IfExistsSeverityConfig,
+
+    /// Options for PGM202 (DROP TABLE CASCADE on existing table).
+    #[serde(default)]
+    pub pgm202: IfExistsSeverityConfig,
+
+    /// Options for PGM525 (column added to a table read with `SELECT *`).
+    #[serde(default)]
+    pub pgm525: Pgm525Config,
+
+    /// Options for PGM526 (new table or schema without a grant).
+    #[serde(default)]
+    pub pgm526: Pgm526Config,
+
+    /// Options for PGM528 (enum-like CHECK constraint).
+    #[serde(default)]
+    pub pgm528: Pgm528Config,
+
+    /// Per-rule table filters (`[[rules.overrides]]`), applied to findings
+    /// after the rules have run.
+    #[serde(default)]
+    pub overrides: Vec<RuleOverride>,
+
+    /// Also report findings on the bookkeeping tables of migration tools
+    /// (`databasechangelog`, `databasechangeloglock`, `schema_migrations`,
+    /// `flyway_schema_history`). Off by default: findings on those tables are
+    /// dropped.
+    #[serde(default)]
+    pub lint_tracking_tables: bool,
+}
+
+/// Options for PGM107 (integer primary key).
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct Pgm107Config {
+    /// Tables allowed to keep an `integer`/`smallint` primary key, typically
+    /// small lookup tables (e.g., `["countries", "ref.currencies"]`).
+    /// Unqualified names match in any schema.
+    #[serde(default)]
+    pub allow_tables: Vec<String>,
+}
+
+/// Options for PGM110 (UUID stored as text).
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct Pgm110Config {
+    /// Column-name patterns that suggest a UUID value. `*` matches any run
+    /// of characters; matching is case-insensitive.
+    #[serde(default = "default_pgm110_column_patterns")]
+    pub column_patterns: Vec<String>,
+
+    /// Default-expression functions that generate UUIDs.
+    #[serde(default = "default_pgm110_uuid_functions")]
+    pub uuid_functions: Vec<String>,
+}
+
+fn default_pgm110_column_patterns() -> Vec<String> {
+    vec!["*_id".to_string(), "*uuid*".to_string()]
+}
+
+fn default_pgm110_uuid_functions() -> Vec<String> {
+    [
+        "gen_random_uuid",
+        "uuid_generate_v1",
+        "uuid_generate_v1mc",
+        "uuid_generate_v4",
+        "uuidv4",
+        "uuidv7",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+impl Default for Pgm110Config {
+    fn default() -> Self {
+        Self {
+            column_patterns: default_pgm110_column_patterns(),
+            uuid_functions: default_pgm110_uuid_functions(),
+        }
+    }
+}
+
+/// Options for PGM111 (discouraged column types).
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct Pgm111Config {
+    /// Type names to flag, matched case-insensitively against the base type
+    /// (so `hstore` also flags `hstore[]`). Use the canonical name PostgreSQL
+    /// reports, without a schema (`timetz`, not `time with time zone`).
+    #[serde(default = "default_pgm111_types")]
+    pub types: Vec<String>,
+}
+
+fn default_pgm111_types() -> Vec<String> {
+    ["hstore", "abstime", "reltime", "timetz"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+impl Default for Pgm111Config {
+    fn default() -> Self {
+        Self {
+            types: default_pgm111_types(),
+        }
+    }
+}
+
+/// Options for PGM525 (column added to a table read with `SELECT *`).
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct Pgm525Config {
+    /// Heavily referenced tables whose consumers may use `SELECT *` or
+    /// `INSERT` without a column list (e.g., `["orders", "billing.invoices"]`).
+    /// Unqualified names match in any schema.
+    #[serde(default)]
+    pub tables: Vec<String>,
+}
+
+/// Options for PGM526 (new table or schema without a grant).
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct Pgm526Config {
+    /// Roles every new table and schema must be granted to (e.g.,
+    /// `["app_rw", "app_ro"]`). Empty means a grant to any role is enough.
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+/// Options for PGM528 (enum-like CHECK constraint).
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct Pgm528Config {
+    /// Fewest literals in a `col IN (...)` CHECK for it to be reported on
+    /// its own. Shorter lists are reported only when another table's CHECK
+    /// allows the same values.
+    #[serde(default = "default_pgm528_min_values")]
+    pub min_values: usize,
+}
+
+fn default_pgm528_min_values() -> usize {
+    5
+}
+
+impl Default for Pgm528Config {
+    fn default() -> Self {
+        Self {
+            min_values: default_pgm528_min_values(),
+        }
+    }
+}
+
+/// Restrict or exempt one rule by table (`[[rules.overrides]]`).
+///
+/// Entries are table-name patterns: `*` matches any run of characters and
+/// matching is case-insensitive. An entry with a dot matches the
+/// schema-qualified name; one without matches the table name in any schema.
+/// Findings that are not about a specific table are never filtered.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RuleOverride {
+    /// The rule the override applies to (e.g., `"PGM302"`).
+    pub rule: crate::rules::RuleRef,
+
+    /// Only report the rule on tables matching these patterns. Empty means
+    /// every table.
+    #[serde(default)]
+    pub tables: Vec<String>,
+
+    /// Never report the rule on tables matching these patterns (e.g.,
+    /// `["public.schema_migrations", "public.flyway_schema_history"]`).
+    #[serde(default)]
+    pub exclude_tables: Vec<String>,
+}
+
+/// Options for PGM201 and PGM202 (DROP TABLE on existing table).
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct IfExistsSeverityConfig {
+    /// Severity for findings on `DROP TABLE IF EXISTS`, e.g. `"info"` when
+    /// guarded drops are reviewed cleanup. Unguarded drops keep the rule's
+    /// default severity. Default: the rule's default severity.
+    #[serde(default)]
+    pub if_exists_severity: Option<String>,
+}
+
+impl IfExistsSeverityConfig {
+    /// The configured severity for guarded drops, if any.
+    pub fn severity(&self) -> Option<crate::rules::Severity> {
+        self.if_exists_severity
+            .as_deref()
+            .and_then(crate::rules::Severity::parse)
+    }
+}
+
+impl RulesConfig {
+    /// All lint rules that are not disabled by this configuration, either
+    /// individually or by family. Opt-in rules are included only when
+    /// listed in `enabled`.
+    pub fn active_rules(&self) -> Vec<crate::rules::RuleId> {
+        self.active_rules_from(crate::rules::RuleId::lint_rules())
+    }
+
+    /// [`active_rules`](Self::active_rules) over the rules of `registry`,
+    /// including the rules registered by rule packs.
+    pub fn active_rules_in(
+        &self,
+        registry: &crate::rules::RuleRegistry,
+    ) -> Vec<crate::rules::RuleId> {
+        self.active_rules_from(registry.lint_rules().into_iter())
+    }
+
+    fn active_rules_from(
+        &self,
+        rules: impl Iterator<Item = crate::rules::RuleId>,
+    ) -> Vec<crate::rules::RuleId> {
+        rules
+            .filter(|r| !r.is_opt_in() || self.enabled.iter().any(|e| e == r))
+            .filter(|r| !self.disabled.iter().any(|d| d == r))
+            .filter(|r| {
+                !self
+                    .disabled_families
+                    .iter()
+                    .any(|f| f.eq_ignore_ascii_case(r.family_prefix()))
+            })
+            .collect()
+    }
+}
+
+/// Parse a rule family prefix such as `"3xx"` (case-insensitive) into its
+/// canonical form. Only families that contain lint rules are accepted.
+pub fn parse_rule_family(s: &str) -> Result<&'static str, ConfigError> {
+    let families = crate::rules::RuleId::lint_families();
+    families
+        .iter()
+        .find(|f| f.eq_ignore_ascii_case(s))
+        .copied()
+        .ok_or_else(|| {
+            ConfigError::Validation(format!(
+                "invalid rule family '{}'. Valid values: {}",
+                s,
+                families.join(", ")
+            ))
+        })
+}
+
+/// Configuration for meta-behavior rules (PGM9xx).
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct MetaConfig {
+    #[serde(default)]
+    pub pgm901: Pgm901Config,
+}
+
+/// Configuration for PGM901 (down-migration severity cap).
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct Pgm901Config {
+    /// Rule IDs whose findings keep their original severity in down migrations
+    /// (e.g., `["PGM201", "PGM202"]`), so destructive operations in rollbacks
+    /// can still fail the build.
+    #[serde(default)]
+    pub exempt_rules: Vec<crate::rules::RuleRef>,
+}
+
+impl Pgm901Config {
+    /// The exempt rules that are built-in rules.
+    pub fn exempt_rule_ids(&self) -> Vec<crate::rules::RuleId> {
+        self.exempt_rules
+            .iter()
+            .filter_map(crate::rules::RuleRef::builtin)
+            .collect()
+    }
+}
+
+/// Schema ownership boundaries and the service schema, used by PGM511 and
+/// PGM527.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct SchemasConfig {
+    /// Schema name → teams that own it (e.g., `payments = ["payments_team"]`).
+    /// Schemas not listed here have no owner and are never flagged.
+    #[serde(default)]
+    pub ownership: BTreeMap<String, Vec<String>>,
+
+    /// Team name → directories (or files) holding that team's migrations.
+    /// A migration file belongs to every team with a path that contains it.
+    #[serde(default)]
+    pub teams: BTreeMap<String, Vec<PathBuf>>,
+
+    /// The service schema every new table and sequence belongs in, used by
+    /// PGM527 (e.g., `"billing"`). Unset means no schema is required.
+    #[serde(default)]
+    pub required: Option<String>,
+}
+
+/// Target PostgreSQL server (`[postgres]`).
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct PostgresConfig {
+    /// Major version the migrations run against (e.g., `16`). Rules whose
+    /// outcome depends on the server version use it; when unset they assume
+    /// a supported release and stay conservative where versions differ.
+    #[serde(default)]
+    pub version: Option<u32>,
+}
+
+/// Expand/contract rename workflow (`[rename_workflow]`), used by PGM519
+/// and PGM520.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct RenameWorkflowConfig {
+    /// State file listing tables and columns and their workflow phase.
+    /// See [`crate::rename_workflow`].
+    #[serde(default)]
+    pub state_file: Option<PathBuf>,
+
+    /// Contents of `state_file`, loaded by [`Config::from_file`].
+    #[serde(skip)]
+    pub state: crate::rename_workflow::RenameState,
+}
+
+impl RenameWorkflowConfig {
+    /// Read `state_file`, if set.
+    fn load_state_file(&mut self) -> Result<(), ConfigError> {
+        let Some(path) = &self.state_file else {
+            return Ok(());
+        };
+        self.state = crate::rename_workflow::RenameState::load(path).map_err(|e| {
+            ConfigError::Validation(format!(
+                "cannot read rename_workflow.state_file '{}': {e}",
+                path.display()
+            ))
+        })?;
+        Ok(())
+    }
+}
+
+/// Lock duration budget (`[lock_budget]`), used by PGM032.
+///
+/// Row counts are hints supplied by the team; the linter never connects to
+/// a database. Throughput defaults are deliberately conservative.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct LockBudgetConfig {
+    /// Longest a migration unit may hold locks that block writes, in
+    /// seconds. PGM032 does nothing while unset.
+    #[serde(default)]
+    pub max_seconds: Option<f64>,
+
+    /// Estimated row count per table. Unqualified names match in any
+    /// schema; a schema-qualified entry wins over an unqualified one.
+    #[serde(default)]
+    pub table_rows: BTreeMap<String, u64>,
+
+    /// Rows assumed for tables missing from `table_rows`.
+    #[serde(default)]
+    pub default_rows: u64,
+
+    /// Rows per second read by a validation scan (SET NOT NULL, CHECK,
+    /// FOREIGN KEY, ATTACH PARTITION).
+    #[serde(default = "default_scan_rows_per_second")]
+    pub scan_rows_per_second: u64,
+
+    /// Rows per second written by a table rewrite or index build.
+    #[serde(default = "default_rewrite_rows_per_second")]
+    pub rewrite_rows_per_second: u64,
+}
+
+impl Default for LockBudgetConfig {
+    fn default() -> Self {
+        Self {
+            max_seconds: None,
+            table_rows: BTreeMap::new(),
+            default_rows: 0,
+            scan_rows_per_second: default_scan_rows_per_second(),
+            rewrite_rows_per_second: default_rewrite_rows_per_second(),
+        }
+    }
+}
+
+impl LockBudgetConfig {
+    /// Estimated rows in the table with catalog key `table_key`
+    /// (`schema.table`), falling back to `default_rows`.
+    pub fn rows_for(&self, table_key: &str) -> u64 {
+        let name = table_key
+            .rsplit_once('.')
+            .map_or(table_key, |(_, name)| name);
+        self.table_rows
+            .get(table_key)
+            .or_else(|| self.table_rows.get(name))
+            .copied()
+            .unwrap_or(self.default_rows)
+    }
+}
+
+/// A required migration pattern (`[[patterns]]`), enforced by PGM517.
+///
+/// See [`crate::rules::patterns`] for how operations are matched.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PatternConfig {
+    /// Name shown in findings (e.g., `"not-null-via-check"`).
+    pub name: String,
+
+    /// Operation the pattern applies to.
+    pub on: crate::rules::patterns::PatternOp,
+
+    /// Options the operation itself must use.
+    #[serde(default)]
+    pub require: Vec<crate::rules::patterns::PatternFlag>,
+
+    /// Related operation that must come earlier in the migration history.
+    #[serde(default)]
+    pub preceded_by: Option<crate::rules::patterns::PatternOp>,
+
+    /// Related operation that must come later in the changed migrations.
+    #[serde(default)]
+    pub followed_by: Option<crate::rules::patterns::PatternOp>,
+
+    /// Whether `preceded_by` and `followed_by` must be in a different
+    /// migration unit than the operation (default: `true`).
+    #[serde(default = "default_true")]
+    pub separate_unit: bool,
+
+    /// Severity of this pattern's findings (default: PGM517's).
+    #[serde(default)]
+    pub severity: Option<String>,
+
+    /// Text appended to the finding message, e.g. a runbook link.
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+impl PatternConfig {
+    /// Reject patterns that can never fire or can never be satisfied.
+    fn validate(&self) -> Result<(), ConfigError> {
+        let invalid = |reason: String| {
+            ConfigError::Validation(format!("invalid pattern '{}': {reason}", self.name))
+        };
+        if self.require.is_empty() && self.preceded_by.is_none() && self.followed_by.is_none() {
+            return Err(invalid(
+                "set at least one of require, preceded_by, followed_by".to_string(),
+            ));
+        }
+        if let Some(flag) = self.require.iter().find(|f| !f.applies_to(self.on)) {
+            return Err(invalid(format!(
+                "{} does not apply to {}",
+                flag.label(),
+                self.on.label()
+            )));
+        }
+        if let Some(severity) = &self.severity
+            && crate::rules::Severity::parse(severity).is_none()
+        {
+            return Err(invalid(format!(
+                "severity '{severity}'. Valid values: blocker, critical, major, minor, info"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Replay warnings that fail the run (`[warnings]`).
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct WarningsConfig {
+    /// Warning categories that make the run a tool error (exit code 2),
+    /// even when there are no findings.
+    #[serde(default)]
+    pub fail_on: Vec<crate::catalog::WarningCategory>,
+}
+
+/// Catalog replay (`[catalog]`).
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct CatalogConfig {
+    /// Refuse statements PostgreSQL would reject against the replayed
+    /// schema (duplicate column, dropping a missing constraint, foreign key
+    /// to a missing table) and report them as PGM034 findings, instead of
+    /// replaying them leniently.
+    #[serde(default)]
+    pub strict_replay: bool,
+
+    /// Treat tables the replayed catalog does not have as existing in rules
+    /// that only fire on existing tables, for histories whose older
+    /// migrations were squashed or deleted. A PGM902 finding counts the
+    /// decisions that relied on this.
+    #[serde(default)]
+    pub assume_unknown_tables_exist: bool,
+}
+
+/// Result cache (`[cache]`). See [`crate::cache`].
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct CacheConfig {
+    /// Reuse the findings of an identical earlier run (same version,
+    /// config, changed set, and migration files) instead of linting again.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Directory holding cache entries. Defaults to `cache` under
+    /// `output.dir`.
+    #[serde(default)]
+    pub dir: Option<PathBuf>,
+}
+
+impl CacheConfig {
+    /// The cache directory: `dir`, or `cache` under `output_dir`.
+    pub fn dir_or_default(&self, output_dir: &Path) -> PathBuf {
+        self.dir.clone().unwrap_or_else(|| output_dir.join("cache"))
+    }
+}
+
+/// Oldest major version accepted for `postgres.version` (9 covers 9.x).
+const MIN_POSTGRES_VERSION: u32 = 9;
+
+/// Finding message templates (`[messages]`).
+///
+/// A template is a string with `{name}` placeholders, rendered by
+/// [`apply_message_templates`](crate::rules::apply_message_templates).
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct MessagesConfig {
+    /// Template for every rule without its own entry in `templates`
+    /// (e.g., `"{message} See https://wiki.example.com/db/{rule_id}"`).
+    #[serde(default)]
+    pub default: Option<String>,
+
+    /// Rule ID → template for that rule's findings.
+    #[serde(default)]
+    pub templates: BTreeMap<crate::rules::RuleRef, String>,
+
+    /// TOML file with `default` and `[templates]` entries, typically a
+    /// translation. Entries set directly in `[messages]` take precedence.
+    #[serde(default)]
+    pub locale_file: Option<PathBuf>,
+}
+
+/// Contents of a `[messages] locale_file`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LocaleFile {
+    #[serde(default)]
+    default: Option<String>,
+    #[serde(default)]
+    templates: BTreeMap<crate::rules::RuleRef, String>,
+}
+
+impl MessagesConfig {
+    /// Template for `rule`, falling back to `default`.
+    pub fn template_for(&self, rule: crate::rules::RuleId) -> Option<&str> {
+        self.templates
+            .get(rule.as_str())
+            .or(self.default.as_ref())
+            .map(String::as_str)
+    }
+
+    /// Returns `true` if no template is configured.
+    pub fn is_empty(&self) -> bool {
+        self.default.is_none() && self.templates.is_empty()
+    }
+
+    /// Merge templates from `locale_file`, if set. Templates already present
+    /// keep their value.
+    fn load_locale_file(&mut self) -> Result<(), ConfigError> {
+        let Some(path) = &self.locale_file else {
+            return Ok(());
+        };
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            ConfigError::Validation(format!(
+                "cannot read messages.locale_file '{}': {e}",
+                path.display()
+            ))
+        })?;
+        let locale: LocaleFile = toml::from_str(&contents)?;
+        if self.default.is_none() {
+            self.default = locale.default;
+        }
+        for (rule, template) in locale.templates {
+            self.templates.entry(rule).or_insert(template);
+        }
+        Ok(())
+    }
+
+    /// Reject templates with unbalanced braces. Template keys are checked
+    /// against the rule registry (see
+    /// [`RuleRegistry::validate_config`](crate::rules::RuleRegistry::validate_config)).
+    fn validate(&self) -> Result<(), ConfigError> {
+        let named = self
+            .default
+            .iter()
+            .map(|t| ("default".to_string(), t))
+            .chain(self.templates.iter().map(|(id, t)| (id.to_string(), t)));
+        for (name, template) in named {
+            crate::rules::check_message_template(template).map_err(|e| {
+                ConfigError::Validation(format!("invalid message template for {name}: {e}"))
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl SchemasConfig {
+    /// Teams that own `schema`, or `None` if the schema has no configured owner.
+    pub fn owners(&self, schema: &str) -> Option<&[String]> {
+        self.ownership.get(schema).map(Vec::as_slice)
+    }
+
+    /// Teams whose configured paths contain `file`.
+    ///
+    /// Paths are compared component-wise, ignoring `.` components, so
+    /// `./db/migrations/a.sql` belongs to a team configured with `db/migrations`.
+    pub fn teams_for(&self, file: &Path) -> Vec<&str> {
+        let file = without_cur_dir(file);
+        self.teams
+            .iter()
+            .filter(|(_, paths)| paths.iter().any(|p| file.starts_with(without_cur_dir(p))))
+            .map(|(team, _)| team.as_str())
+            .collect()
+    }
+}
+
+fn without_cur_dir(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| !matches!(c, Component::CurDir))
+        .collect()
 }
 
 fn default_schema() -> String {
     "public".to_string()
 }
 
+fn default_true() -> bool {
+    true
+}
+
 fn default_strategy() -> String {
     "filename_lexicographic".to_string()
 }
@@ -180,6 +1053,10 @@ fn default_formats() -> Vec<String> {
     vec!["sarif".to_string()]
 }
 
+fn default_pr_comment_max_items() -> usize {
+    20
+}
+
 fn default_output_dir() -> PathBuf {
     PathBuf::from("build/reports/migration-lint")
 }
@@ -188,8 +1065,32 @@ fn default_fail_on() -> String {
     "critical".to_string()
 }
 
+fn default_scan_rows_per_second() -> u64 {
+    2_000_000
+}
+
+fn default_rewrite_rows_per_second() -> u64 {
+    500_000
+}
+
 /// Valid section names for `--explain-config`.
-const VALID_SECTIONS: &[&str] = &["migrations", "liquibase", "output", "cli", "rules"];
+const VALID_SECTIONS: &[&str] = &[
+    "migrations",
+    "liquibase",
+    "output",
+    "cli",
+    "rules",
+    "meta",
+    "schemas",
+    "messages",
+    "postgres",
+    "patterns",
+    "rename_workflow",
+    "lock_budget",
+    "warnings",
+    "catalog",
+    "cache",
+];
 
 const SECTION_MIGRATIONS: &str = "\
 [migrations]
@@ -199,8 +1100,15 @@ const SECTION_MIGRATIONS: &str = "\
   running the tool from any directory with --config pointing at the file.
 
   paths = [\"db/migrations\"]
-    Paths to migration directories or changelog files.
-    Type: list of paths
+    Paths to migration directories or changelog files. An entry can also
+    be a table with `path` and an optional `default_schema` that overrides
+    the global one for the migrations under it (for a changelog file, its
+    directory):
+      paths = [
+        { path = \"services/billing/migrations\", default_schema = \"billing\" },
+        \"services/shop/migrations\",
+      ]
+    Type: list of paths or tables
     Default: [\"db/migrations\"]
 
   strategy = \"filename_lexicographic\"
@@ -221,7 +1129,8 @@ const SECTION_MIGRATIONS: &str = "\
 
   default_schema = \"public\"
     Schema applied to unqualified table names so that `orders` and
-    `public.orders` resolve to the same catalog entry.
+    `public.orders` resolve to the same catalog entry. A `paths` entry
+    with its own `default_schema` overrides it for its migrations.
     Type: string
     Default: \"public\"
 
@@ -230,6 +1139,38 @@ const SECTION_MIGRATIONS: &str = "\
     Set to false for golang-migrate repos where files run outside transactions.
     Type: boolean (optional)
     Default: true (when absent)
+
+  respect_gitignore = true
+    Skip .sql files ignored by git (.gitignore in the directory or a parent,
+    and .git/info/exclude) when scanning migration directories. Files listed
+    directly in `paths` are always loaded.
+    Type: boolean
+    Default: true
+
+  follow_symlinks = true
+    Load symlinked .sql files found in migration directories. Set to false
+    to skip them.
+    Type: boolean
+    Default: true
+
+  resolve_psql_includes = false
+    psql meta-commands (\\set, \\echo, \\i, ...) are stripped from plain SQL
+    files before parsing. When true, the files named by \\i and \\ir are read
+    (relative to the including file) and their statements are linted as if
+    they stood on the include line.
+    Type: boolean
+    Default: false
+
+  include_root = \".\"
+    Directory that included files must stay inside, once `..` and symlinks
+    are resolved: psql \\i/\\ir targets and the files Liquibase changelogs
+    pull in with <include>/<includeAll>/<sqlFile>. A psql include outside
+    it is not read (a warning, and the statement is unparseable); a
+    Liquibase changelog naming a file outside it fails the load (exit 2)
+    before Liquibase runs. Protects CI runs on untrusted pull requests.
+    Relative to the config file.
+    Type: path
+    Default: the config file's directory
 ";
 
 const SECTION_LIQUIBASE: &str = "\
@@ -255,6 +1196,21 @@ const SECTION_LIQUIBASE: &str = "\
     Type: string
     Values: \"auto\", \"bridge\", \"update-sql\"
     Default: \"auto\" (tries bridge -> update-sql)
+
+  contexts = []
+    Contexts the changelog is run with, as with Liquibase --contexts.
+    Changesets whose context expression does not match (e.g.
+    context=\"legacy\" when contexts = [\"prod\"]) are left out of replay
+    and linting. Changesets without a context always run.
+    Type: list of strings
+    Default: [] (every changeset runs)
+
+  labels = \"billing and !legacy\"
+    Label expression the changelog is run with, as with Liquibase
+    --label-filter. Changesets whose labels do not match are left out of
+    replay and linting. Changesets without labels always run.
+    Type: string (optional)
+    Default: none (every changeset runs)
 ";
 
 const SECTION_OUTPUT: &str = "\
@@ -263,7 +1219,7 @@ const SECTION_OUTPUT: &str = "\
   formats = [\"sarif\"]
     Output report formats to generate.
     Type: list of strings
-    Values: \"sarif\", \"sonarqube\", \"text\"
+    Values: \"sarif\", \"sonarqube\", \"text\", \"pr-comment\", \"json\"
     Default: [\"sarif\"]
 
   dir = \"build/reports/migration-lint\"
@@ -277,6 +1233,49 @@ const SECTION_OUTPUT: &str = "\
     SonarQube) expects module-relative paths.
     Type: string (optional)
     Default: none
+
+  redact_literals = false
+    Mask string and numeric literals ('...' -> '?', 42 -> ?) in SQL that is
+    echoed into finding messages, [messages] template parameters such as
+    {values}, and the --emit-catalog dump. Use when migrations seed tokens
+    or e-mail addresses and reports leave the build.
+    Type: bool
+    Default: false
+
+  include_snippets = false
+    Embed the text of the statement each finding points at: `snippet` on
+    JSON findings (daemon, bindings) and `region.snippet` in SARIF, so
+    reviewers see the SQL without opening the file. Literals are masked
+    when redact_literals is set.
+    Type: bool
+    Default: false
+
+[output.pr_comment]
+
+  max_items = 20
+    Most findings listed in the pr-comment Markdown summary (pr-comment.md);
+    the rest are only counted. --max-items overrides it.
+    Type: integer
+    Default: 20
+
+  source_url = \"https://github.com/acme/shop/blob/main\"
+    Base URL that finding paths are appended to, so each location links to
+    the file and line (#L<line>, as GitHub and GitLab expect).
+    Type: string (optional)
+    Default: none (locations are plain text)
+
+  artifacts_url = \"https://ci.example.com/jobs/123/artifacts\"
+    Link to the full reports, added at the end of the comment.
+    Type: string (optional)
+    Default: none
+
+[output.json]
+
+  include_catalog = false
+    Add the replayed catalog (the same dump as --emit-catalog, masked when
+    redact_literals is set) to report.json as `catalog`.
+    Type: bool
+    Default: false
 ";
 
 const SECTION_CLI: &str = "\
@@ -288,6 +1287,15 @@ const SECTION_CLI: &str = "\
     Type: string
     Values: \"blocker\", \"critical\", \"major\", \"minor\", \"info\", \"none\"
     Default: \"critical\"
+
+  changed_from_git = \"origin/main\"
+    Lint only the migration files git reports as added or modified since
+    the merge base of this ref and HEAD, including uncommitted and
+    untracked files. Renamed files count under their new path; deleted
+    files are skipped. Same as --changed-from-git; --changed-files,
+    --changed-files-from, and --changed-from-git take precedence.
+    Type: string
+    Default: (unset, lint every migration)
 ";
 
 const SECTION_RULES: &str = "\
@@ -295,9 +1303,420 @@ const SECTION_RULES: &str = "\
 
   disabled = []
     Rule IDs to disable globally. Findings from disabled rules are not emitted.
+    IDs here, in `enabled`, in [[rules.overrides]], in [meta.pgm901]
+    exempt_rules and in [messages.templates] are checked against every
+    registered rule; an ID from a rule pack that is not loaded is reported
+    as such.
     Example: [\"PGM006\", \"PGM101\"]
     Type: list of strings
     Default: []
+
+  disabled_families = []
+    Rule families to disable globally, by hundreds digit. Combined with
+    `disabled`.
+    Example: [\"3xx\", \"5xx\"]
+    Type: list of strings
+    Values: \"0xx\", \"1xx\", \"2xx\", \"3xx\", \"4xx\", \"5xx\"
+    Default: []
+
+  enabled = []
+    Opt-in rules to turn on. These rules are off by default; `disabled`
+    still takes precedence.
+    Values: \"PGM514\"
+    Type: list of strings
+    Default: []
+
+  lint_tracking_tables = false
+    Also report findings on the bookkeeping tables of migration tools:
+    databasechangelog, databasechangeloglock, schema_migrations and
+    flyway_schema_history, in any schema. By default, findings on them are
+    dropped.
+    Type: boolean
+    Default: false
+
+[rules.pgm107]
+
+  allow_tables = []
+    Tables allowed to keep an integer/smallint primary key (e.g. small
+    lookup tables). Unqualified names match in any schema.
+    Example: [\"countries\", \"ref.currencies\"]
+    Type: list of strings
+    Default: []
+
+[rules.pgm110]
+
+  column_patterns = [\"*_id\", \"*uuid*\"]
+    Column-name patterns that suggest a UUID value stored as text.
+    `*` matches any run of characters; matching is case-insensitive.
+    Type: list of strings
+    Default: [\"*_id\", \"*uuid*\"]
+
+  uuid_functions = [\"gen_random_uuid\", ...]
+    Default-expression functions whose presence marks a column as
+    holding UUIDs.
+    Type: list of strings
+    Default: [\"gen_random_uuid\", \"uuid_generate_v1\", \"uuid_generate_v1mc\",
+              \"uuid_generate_v4\", \"uuidv4\", \"uuidv7\"]
+
+[rules.pgm111]
+
+  types = [\"hstore\", \"abstime\", \"reltime\", \"timetz\"]
+    Column types PGM111 flags. Replacing the list drops the defaults, so
+    repeat any you want to keep. Match the canonical type name without a
+    schema (e.g. \"timetz\", \"int4\"); arrays of a listed type are flagged
+    too. `money` is covered by PGM104.
+    Example: [\"hstore\", \"timetz\", \"xml\"]
+    Type: list of strings
+    Default: [\"hstore\", \"abstime\", \"reltime\", \"timetz\"]
+
+[rules.pgm201] / [rules.pgm202]
+
+  if_exists_severity = \"info\"
+    Severity for DROP TABLE IF EXISTS findings (PGM202: with CASCADE) on
+    existing tables. Unguarded drops keep the rule's severity.
+    Values: \"blocker\", \"critical\", \"major\", \"minor\", \"info\"
+    Type: string
+    Default: unset (same as unguarded drops)
+
+[rules.pgm525]
+
+  tables = []
+    Heavily referenced tables whose readers may use SELECT * or INSERT
+    without a column list. PGM525 (opt-in) reports the first ADD COLUMN on
+    each of them once per run. Unqualified names match in any schema.
+    Example: [\"orders\", \"billing.invoices\"]
+    Type: list of strings
+    Default: []
+
+[rules.pgm526]
+
+  roles = []
+    Roles every table and schema created in the change must be granted to.
+    PGM526 (opt-in) reports objects no GRANT in the change reaches for one
+    of them. Empty means a grant to any role is enough.
+    Example: [\"app_rw\", \"app_ro\"]
+    Type: list of strings
+    Default: []
+
+[rules.pgm528]
+
+  min_values = 5
+    Fewest literals in a CHECK (col IN (...)) for PGM528 (opt-in) to
+    suggest an enum type or lookup table. Shorter lists are reported only
+    when a CHECK on another table allows the same set of values.
+    Type: integer
+    Default: 5
+
+[[rules.overrides]]
+
+  Restricts or exempts one rule by table. Repeat the table per rule.
+  Patterns use `*` for any run of characters and match case-insensitively;
+  a pattern with a dot matches schema.table, one without matches the table
+  name in any schema. Findings not tied to a table are always kept.
+
+  rule = \"<rule ID>\"
+    The rule the override applies to.
+    Type: string (required)
+
+  tables = []
+    Only report the rule on matching tables. Empty means all tables.
+    Type: list of strings
+    Default: []
+
+  exclude_tables = []
+    Never report the rule on matching tables.
+    Example: [\"public.schema_migrations\", \"public.flyway_schema_history\"]
+    Type: list of strings
+    Default: []
+";
+
+const SECTION_META: &str = "\
+[meta.pgm901]
+
+  exempt_rules = []
+    Rule IDs whose findings are NOT capped to INFO in down migrations.
+    Use this when destructive operations in rollbacks should still fail
+    the build.
+    Example: [\"PGM201\", \"PGM202\"]
+    Type: list of strings
+    Default: []
+";
+
+const SECTION_SCHEMAS: &str = "\
+[schemas]
+
+  required = \"<schema>\"
+    The service schema every table and sequence created by a migration
+    belongs in. PGM527 (opt-in) flags CREATE TABLE and CREATE SEQUENCE
+    with an unqualified name that resolves elsewhere, or a name explicitly
+    in public.
+    Example: \"billing\"
+    Type: string
+    Default: unset (PGM527 never fires)
+
+[schemas.ownership]
+
+  <schema> = [\"<team>\", ...]
+    Teams that own a schema. DDL on tables in an owned schema is flagged
+    by PGM511 unless the migration file belongs to one of the owning teams.
+    Schemas not listed have no owner and are never flagged.
+    Example: payments = [\"payments_team\"]
+    Type: table of string lists
+    Default: {} (PGM511 never fires)
+
+[schemas.teams]
+
+  <team> = [\"<path>\", ...]
+    Directories (or files) holding a team's migrations. A migration file
+    belongs to every team with a path that contains it. Relative paths
+    resolve against the config file's directory.
+    Example: payments_team = [\"db/migrations/payments\"]
+    Type: table of path lists
+    Default: {}
+";
+
+const SECTION_MESSAGES: &str = "\
+[messages]
+
+  default = \"<template>\"
+    Template for every rule's finding message. Placeholders:
+      {message}      the rule's built-in message
+      {rule_id}      e.g. PGM001
+      {severity}     e.g. CRITICAL
+      {description}  the rule's short description
+      {file}, {line} where the finding is
+    Rules also expose the values their message is built from, e.g. {table},
+    {column}, {type}. Unknown placeholders are left as written; write {{ and
+    }} for literal braces.
+    Example: \"{message} See https://wiki.example.com/db/{rule_id}\"
+    Type: string
+    Default: none (built-in messages)
+
+  locale_file = \"<path>\"
+    TOML file with the same `default` and [templates] keys, typically a
+    translation. Templates set in the config file take precedence.
+    Relative paths resolve against the config file's directory.
+    Type: path
+    Default: none
+
+[messages.templates]
+
+  <rule id> = \"<template>\"
+    Template for one rule, overriding `default`.
+    Example: PGM001 = \"{message} Runbook: https://wiki.example.com/concurrent-index\"
+    Type: table of strings
+    Default: {}
+";
+
+const SECTION_POSTGRES: &str = "\
+[postgres]
+
+  version = <major>
+    Major PostgreSQL version the migrations run against. Version-aware
+    rules use it: PGM008 flags ADD COLUMN ... NOT NULL DEFAULT on an
+    existing table when the default rewrites the table (any default
+    before 11, a volatile default from 11 on).
+    Example: 16
+    Type: integer (9 or later)
+    Default: none (PGM008 only flags a missing DEFAULT)
+";
+
+const SECTION_PATTERNS: &str = "\
+[[patterns]]
+
+  Required migration patterns, enforced by PGM517. Each entry applies to
+  one operation and says how it must be rolled out. Two operations are
+  related when they are on the same table and name the same constraint
+  or share a column. Operations on tables created in the same change are
+  not checked.
+
+  Operations: add_column, drop_column, set_not_null, alter_column_type,
+  rename_column, add_foreign_key, add_check, add_unique, add_primary_key,
+  validate_constraint, validated_check (a CHECK added without NOT VALID,
+  or validated), create_index.
+
+  name = \"<name>\"
+    Shown in findings. Must be unique.
+    Type: string (required)
+
+  on = \"<operation>\"
+    Operation the pattern applies to.
+    Type: string (required)
+
+  require = [\"not_valid\" | \"concurrently\", ...]
+    Options the operation itself must use. not_valid applies to
+    add_foreign_key and add_check, concurrently to create_index.
+    Type: list of strings
+    Default: []
+
+  preceded_by = \"<operation>\"
+    A related operation that must come earlier, in a changed or an
+    already-merged migration.
+    Type: string
+    Default: none
+
+  followed_by = \"<operation>\"
+    A related operation that must come later in the changed migrations.
+    Checked after the last migration is linted.
+    Type: string
+    Default: none
+
+  separate_unit = true | false
+    Whether preceded_by and followed_by must be in a different migration
+    than the operation.
+    Type: bool
+    Default: true
+
+  severity = \"<severity>\"
+    Severity of this pattern's findings.
+    Type: string
+    Default: major (PGM517's default)
+
+  message = \"<text>\"
+    Appended to the finding message, e.g. a runbook link.
+    Type: string
+    Default: none
+
+  Example:
+    [[patterns]]
+    name = \"not-null-via-check\"
+    on = \"set_not_null\"
+    preceded_by = \"validated_check\"
+
+    [[patterns]]
+    name = \"fk-not-valid\"
+    on = \"add_foreign_key\"
+    require = [\"not_valid\"]
+    followed_by = \"validate_constraint\"
+";
+
+const SECTION_RENAME_WORKFLOW: &str = "\
+[rename_workflow]
+
+  state_file = \"<path>\"
+    TOML file tracking tables and columns in the expand/contract rename
+    workflow. PGM519 (RENAME COLUMN) and PGM520 (RENAME TABLE) allow a
+    rename only for objects whose phase is \"contract\". Both rules are
+    opt-in: list them in [rules] enabled.
+    Relative paths resolve against the config file's directory.
+    Type: path
+    Default: none (every rename on an existing table is flagged)
+
+  State file format:
+    [[renames]]
+    table = \"orders\"        # optionally schema-qualified
+    column = \"status\"       # omit for the table itself
+    phase = \"contract\"      # expand, migrate, or contract
+";
+
+const SECTION_LOCK_BUDGET: &str = "\
+[lock_budget]
+
+  PGM032 estimates how long each migration unit holds locks that block
+  writes and flags units over the budget. The model is approximate:
+  validation scans read every row at scan_rows_per_second; table
+  rewrites, index builds, VACUUM FULL and CLUSTER write every row at
+  rewrite_rows_per_second (rewrites once more per existing index).
+  Catalog-only changes and CONCURRENTLY / NOT VALID variants cost
+  nothing. In a transaction the unit's statements add up, because locks
+  are held until commit; otherwise the slowest statement counts.
+
+  max_seconds = <seconds>
+    Budget per migration unit.
+    Type: number (greater than 0)
+    Default: none (PGM032 is inactive)
+
+  default_rows = 0
+    Rows assumed for tables not listed in [lock_budget.table_rows].
+    Type: integer
+    Default: 0 (unlisted tables cost nothing)
+
+  scan_rows_per_second = 2000000
+    Type: integer (greater than 0)
+    Default: 2000000
+
+  rewrite_rows_per_second = 500000
+    Type: integer (greater than 0)
+    Default: 500000
+
+[lock_budget.table_rows]
+  Estimated rows per table. Unqualified names match in any schema.
+  Type: table of integers
+  Default: {}
+
+  Example:
+    [lock_budget]
+    max_seconds = 5
+
+    [lock_budget.table_rows]
+    orders = 40000000
+    \"billing.invoices\" = 2000000
+";
+
+const SECTION_WARNINGS: &str = "\
+[warnings]
+
+  fail_on = []
+    Replay warning categories that fail the run with exit code 2 (tool
+    error) after reports are written, even when there are no findings.
+    Overridden by --fail-on-warnings.
+    Type: list of strings
+    Values:
+      \"replay_conflict\"  CREATE TABLE / CREATE INDEX for an object the
+                         replayed history already has
+      \"unparseable\"      a statement the parser could not convert
+      \"rejected\"         a statement strict replay refused (see
+                         [catalog] strict_replay)
+    Default: [] (warnings never fail the run)
+";
+
+const SECTION_CATALOG: &str = "\
+[catalog]
+
+  strict_replay = true | false
+    Replay migrations the way PostgreSQL would run them. A statement the
+    server would reject against the replayed schema is skipped and
+    reported as a PGM034 (BLOCKER) finding at that statement: CREATE
+    TABLE / CREATE INDEX for an existing relation, ADD COLUMN for an
+    existing column, DROP CONSTRAINT for a missing constraint, and a
+    foreign key to a missing table. IF [NOT] EXISTS is honored, and
+    tables touched by unparseable SQL are not checked. When false,
+    replay stays lenient and only logs replay conflicts.
+    Type: boolean
+    Default: false
+
+  assume_unknown_tables_exist = true | false
+    For histories whose older migrations were squashed or deleted. Rules
+    that only fire on existing tables (CREATE INDEX without CONCURRENTLY,
+    ADD COLUMN ... NOT NULL, DROP TABLE, ...) treat a table the replayed
+    catalog does not have, and that the change does not create, as
+    existing. Rules that need the table's columns or indexes still skip
+    it. One PGM902 (INFO) finding reports how many decisions relied on the
+    assumption and for which tables.
+    Type: boolean
+    Default: false (unknown tables are treated as new)
+";
+
+const SECTION_CACHE: &str = "\
+[cache]
+
+  enabled = true | false
+    Store the findings and replay warnings of each run, keyed by a hash of
+    the tool version, the effective config, the active rules, the changed
+    files, and the path and contents of every file under migrations.paths
+    (plus rename_workflow.state_file and messages.locale_file). A later run
+    with the same key reuses them without replaying the history. Runs with
+    --emit-catalog, --profile-rules, --explain-changed-files,
+    --verify-against-db, or output.json.include_catalog are not cached.
+    --no-cache skips the cache for one run.
+    Type: boolean
+    Default: false
+
+  dir = \"<path>\"
+    Directory holding cache entries, one JSON file per key. Entries from
+    another tool version are ignored; nothing is pruned.
+    Type: path
+    Default: \"<output.dir>/cache\"
 ";
 
 /// Print configuration reference for a specific section, or all sections.
@@ -311,6 +1730,16 @@ pub fn explain_config(section: &str) -> Result<(), ConfigError> {
         ("output", SECTION_OUTPUT),
         ("cli", SECTION_CLI),
         ("rules", SECTION_RULES),
+        ("meta", SECTION_META),
+        ("schemas", SECTION_SCHEMAS),
+        ("messages", SECTION_MESSAGES),
+        ("postgres", SECTION_POSTGRES),
+        ("patterns", SECTION_PATTERNS),
+        ("rename_workflow", SECTION_RENAME_WORKFLOW),
+        ("lock_budget", SECTION_LOCK_BUDGET),
+        ("warnings", SECTION_WARNINGS),
+        ("catalog", SECTION_CATALOG),
+        ("cache", SECTION_CACHE),
     ];
 
     if section == "all" {
@@ -334,6 +1763,13 @@ pub fn explain_config(section: &str) -> Result<(), ConfigError> {
 }
 
 impl Config {
+    /// Shared default instance, for callers that need a `'static` reference
+    /// (e.g., a [`LintContext`](crate::rules::LintContext) built outside the pipeline).
+    pub fn default_ref() -> &'static Config {
+        static DEFAULT: LazyLock<Config> = LazyLock::new(Config::default);
+        &DEFAULT
+    }
+
     /// Load configuration from a file.
     ///
     /// Relative paths in the config are resolved relative to the config file's
@@ -352,6 +1788,9 @@ impl Config {
             _ => Path::new("."),
         };
         config.resolve_paths(config_dir);
+        config.messages.load_locale_file()?;
+        config.messages.validate()?;
+        config.rename_workflow.load_state_file()?;
 
         Ok(config)
     }
@@ -363,11 +1802,20 @@ impl Config {
     pub fn resolve_paths(&mut self, config_dir: &Path) {
         // migrations.paths
         for p in &mut self.migrations.paths {
+            let p = p.path_mut();
             if p.is_relative() {
                 *p = config_dir.join(&*p);
             }
         }
 
+        // migrations.include_root — defaults to the config directory rather
+        // than the working directory, so it shares a base with paths.
+        self.migrations.include_root = Some(match self.migrations.include_root.take() {
+            Some(p) if p.is_relative() => config_dir.join(p),
+            Some(p) => p,
+            None => config_dir.to_path_buf(),
+        });
+
         // liquibase.bridge_jar_path
         if let Some(ref mut p) = self.liquibase.bridge_jar_path
             && p.is_relative()
@@ -375,29 +1823,61 @@ impl Config {
             *p = config_dir.join(&*p);
         }
 
-        // liquibase.binary_path — skip if it looks like a bare command name
-        // (no path separators), since those are resolved via $PATH.
-        if let Some(ref mut p) = self.liquibase.binary_path
+        // liquibase.binary_path — skip if it looks like a bare command name
+        // (no path separators), since those are resolved via $PATH.
+        if let Some(ref mut p) = self.liquibase.binary_path
+            && p.is_relative()
+            && p.components().count() > 1
+        {
+            *p = config_dir.join(&*p);
+        }
+
+        // liquibase.properties_file
+        if let Some(ref mut p) = self.liquibase.properties_file
+            && p.is_relative()
+        {
+            *p = config_dir.join(&*p);
+        }
+
+        // output.dir
+        if self.output.dir.is_relative() {
+            self.output.dir = config_dir.join(&self.output.dir);
+        }
+
+        // cache.dir
+        if let Some(ref mut p) = self.cache.dir
+            && p.is_relative()
+        {
+            *p = config_dir.join(&*p);
+        }
+
+        // schemas.teams
+        for p in self.schemas.teams.values_mut().flatten() {
+            if p.is_relative() {
+                *p = config_dir.join(&*p);
+            }
+        }
+
+        // messages.locale_file
+        if let Some(ref mut p) = self.messages.locale_file
             && p.is_relative()
-            && p.components().count() > 1
         {
             *p = config_dir.join(&*p);
         }
 
-        // liquibase.properties_file
-        if let Some(ref mut p) = self.liquibase.properties_file
+        // rename_workflow.state_file
+        if let Some(ref mut p) = self.rename_workflow.state_file
             && p.is_relative()
         {
             *p = config_dir.join(&*p);
         }
-
-        // output.dir
-        if self.output.dir.is_relative() {
-            self.output.dir = config_dir.join(&self.output.dir);
-        }
     }
 
     /// Validate configuration values.
+    ///
+    /// Rule IDs are not checked here: they resolve against the assembled
+    /// rule registry (see
+    /// [`RuleRegistry::validate_config`](crate::rules::RuleRegistry::validate_config)).
     fn validate(&self) -> Result<(), ConfigError> {
         let fail_on = &self.cli.fail_on;
         if !fail_on.eq_ignore_ascii_case("none") && crate::rules::Severity::parse(fail_on).is_none()
@@ -407,6 +1887,64 @@ impl Config {
                 fail_on
             )));
         }
+        for family in &self.rules.disabled_families {
+            parse_rule_family(family)?;
+        }
+        for (section, options) in [
+            ("rules.pgm201", &self.rules.pgm201),
+            ("rules.pgm202", &self.rules.pgm202),
+        ] {
+            if let Some(severity) = &options.if_exists_severity
+                && crate::rules::Severity::parse(severity).is_none()
+            {
+                return Err(ConfigError::Validation(format!(
+                    "invalid {section}.if_exists_severity '{severity}'. Valid values: blocker, critical, major, minor, info"
+                )));
+            }
+        }
+        if let Some(version) = self.postgres.version
+            && version < MIN_POSTGRES_VERSION
+        {
+            return Err(ConfigError::Validation(format!(
+                "invalid postgres.version {}: expected a major version of {} or later (e.g. 16)",
+                version, MIN_POSTGRES_VERSION
+            )));
+        }
+        if let Some(max) = self.lock_budget.max_seconds
+            && !(max.is_finite() && max > 0.0)
+        {
+            return Err(ConfigError::Validation(format!(
+                "invalid lock_budget.max_seconds {}: expected a number of seconds greater than 0",
+                max
+            )));
+        }
+        for (key, rate) in [
+            (
+                "scan_rows_per_second",
+                self.lock_budget.scan_rows_per_second,
+            ),
+            (
+                "rewrite_rows_per_second",
+                self.lock_budget.rewrite_rows_per_second,
+            ),
+        ] {
+            if rate == 0 {
+                return Err(ConfigError::Validation(format!(
+                    "invalid lock_budget.{key} 0: expected a rate greater than 0"
+                )));
+            }
+        }
+        let mut names = std::collections::HashSet::new();
+        for pattern in &self.patterns {
+            pattern.validate()?;
+            if !names.insert(pattern.name.as_str()) {
+                return Err(ConfigError::Validation(format!(
+                    "duplicate pattern name '{}'",
+                    pattern.name
+                )));
+            }
+        }
+        self.messages.validate()?;
         Ok(())
     }
 }
@@ -415,10 +1953,12 @@ impl Config {
 mod tests {
     use super::*;
 
-    /// Helper: parse TOML into Config and run validation.
+    /// Helper: parse TOML into Config and run validation, including the
+    /// rule ID checks against the registry.
     fn parse_and_validate(toml_str: &str) -> Result<Config, ConfigError> {
         let config: Config = toml::from_str(toml_str)?;
         config.validate()?;
+        crate::rules::RuleRegistry::assemble(&config).validate_config(&config)?;
         Ok(config)
     }
 
@@ -458,12 +1998,183 @@ mod tests {
         assert_eq!(
             config.rules.disabled,
             vec![
-                "PGM006".parse::<crate::rules::RuleId>().unwrap(),
-                "PGM101".parse::<crate::rules::RuleId>().unwrap(),
+                crate::rules::RuleRef::from(crate::rules::RuleId::Pgm006),
+                crate::rules::RuleRef::from(crate::rules::RuleId::Pgm101),
             ]
         );
     }
 
+    #[test]
+    fn test_active_rules_excludes_disabled() {
+        let toml = "[rules]\ndisabled = [\"PGM006\"]";
+        let config = parse_and_validate(toml).unwrap();
+        let active = config.rules.active_rules();
+        assert!(!active.contains(&crate::rules::RuleId::Pgm006));
+        assert!(active.contains(&crate::rules::RuleId::Pgm001));
+        assert!(!active.iter().any(|r| r.is_meta()));
+    }
+
+    #[test]
+    fn test_active_rules_excludes_disabled_families() {
+        let toml = "[rules]\ndisabled_families = [\"3xx\", \"5XX\"]";
+        let config = parse_and_validate(toml).unwrap();
+        let active = config.rules.active_rules();
+        assert!(!active.contains(&crate::rules::RuleId::Pgm301));
+        assert!(!active.contains(&crate::rules::RuleId::Pgm501));
+        assert!(active.contains(&crate::rules::RuleId::Pgm001));
+        assert!(active.contains(&crate::rules::RuleId::Pgm201));
+    }
+
+    #[test]
+    fn test_opt_in_rules_off_until_enabled() {
+        let config = parse_and_validate("").unwrap();
+        assert!(
+            !config
+                .rules
+                .active_rules()
+                .contains(&crate::rules::RuleId::Pgm514)
+        );
+
+        let config = parse_and_validate("[rules]\nenabled = [\"PGM514\"]").unwrap();
+        assert!(
+            config
+                .rules
+                .active_rules()
+                .contains(&crate::rules::RuleId::Pgm514)
+        );
+
+        let config =
+            parse_and_validate("[rules]\nenabled = [\"PGM514\"]\ndisabled = [\"PGM514\"]").unwrap();
+        assert!(
+            !config
+                .rules
+                .active_rules()
+                .contains(&crate::rules::RuleId::Pgm514)
+        );
+    }
+
+    #[test]
+    fn test_enabled_rejects_default_on_rule() {
+        let err = parse_and_validate("[rules]\nenabled = [\"PGM001\"]")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("rule 'PGM001' is on by default"), "{err}");
+    }
+
+    #[test]
+    fn test_invalid_disabled_family_rejected() {
+        let toml = "[rules]\ndisabled_families = [\"7xx\"]";
+        let err = parse_and_validate(toml).unwrap_err().to_string();
+        assert!(err.contains("invalid rule family '7xx'"), "{err}");
+        assert!(err.contains("0xx, 1xx, 2xx, 3xx, 4xx, 5xx"), "{err}");
+    }
+
+    #[test]
+    fn test_parse_rule_family() {
+        assert_eq!(parse_rule_family("2xx").unwrap(), "2xx");
+        assert_eq!(parse_rule_family("2XX").unwrap(), "2xx");
+        assert!(
+            parse_rule_family("9xx").is_err(),
+            "meta family has no lint rules"
+        );
+        assert!(parse_rule_family("PGM2").is_err());
+    }
+
+    #[test]
+    fn test_rules_if_exists_severity() {
+        let toml = "[rules.pgm201]\nif_exists_severity = \"info\"";
+        let config = parse_and_validate(toml).unwrap();
+        assert_eq!(
+            config.rules.pgm201.severity(),
+            Some(crate::rules::Severity::Info)
+        );
+        assert_eq!(config.rules.pgm202.severity(), None);
+
+        let toml = "[rules.pgm202]\nif_exists_severity = \"low\"";
+        let err = parse_and_validate(toml).unwrap_err().to_string();
+        assert!(
+            err.contains("invalid rules.pgm202.if_exists_severity 'low'"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_rules_pgm107_allow_tables_deserialization() {
+        let toml = "[rules.pgm107]\nallow_tables = [\"countries\", \"ref.currencies\"]";
+        let config = parse_and_validate(toml).unwrap();
+        assert_eq!(
+            config.rules.pgm107.allow_tables,
+            vec!["countries".to_string(), "ref.currencies".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_rules_pgm110_partial_override_keeps_other_defaults() {
+        let toml = "[rules.pgm110]\ncolumn_patterns = [\"*_ref\"]";
+        let config = parse_and_validate(toml).unwrap();
+        assert_eq!(
+            config.rules.pgm110.column_patterns,
+            vec!["*_ref".to_string()]
+        );
+        assert_eq!(
+            config.rules.pgm110.uuid_functions,
+            Pgm110Config::default().uuid_functions
+        );
+    }
+
+    #[test]
+    fn test_rules_pgm111_types_replace_defaults() {
+        let config = parse_and_validate("").unwrap();
+        assert!(config.rules.pgm111.types.contains(&"hstore".to_string()));
+
+        let toml = "[rules.pgm111]\ntypes = [\"xml\"]";
+        let config = parse_and_validate(toml).unwrap();
+        assert_eq!(config.rules.pgm111.types, vec!["xml".to_string()]);
+    }
+
+    #[test]
+    fn test_schemas_deserialization() {
+        let toml = "[schemas.ownership]\npayments = [\"payments_team\"]\n\n\
+                    [schemas.teams]\npayments_team = [\"db/migrations/payments\"]";
+        let config = parse_and_validate(toml).unwrap();
+        assert_eq!(
+            config.schemas.owners("payments"),
+            Some(&["payments_team".to_string()][..])
+        );
+        assert_eq!(config.schemas.owners("public"), None);
+        assert_eq!(
+            config.schemas.teams["payments_team"],
+            vec![PathBuf::from("db/migrations/payments")]
+        );
+    }
+
+    #[test]
+    fn test_schemas_teams_for_matches_path_prefix() {
+        let toml = "[schemas.teams]\n\
+                    payments_team = [\"./db/migrations/payments\"]\n\
+                    platform = [\"db/migrations\"]";
+        let config = parse_and_validate(toml).unwrap();
+        assert_eq!(
+            config
+                .schemas
+                .teams_for(Path::new("db/migrations/payments/V1__a.sql")),
+            vec!["payments_team", "platform"]
+        );
+        assert_eq!(
+            config
+                .schemas
+                .teams_for(Path::new("./db/migrations/orders/V1__a.sql")),
+            vec!["platform"]
+        );
+        // Component-wise: `payments_v2` is not inside `payments`.
+        assert_eq!(
+            config
+                .schemas
+                .teams_for(Path::new("db/migrations/payments_v2/V1__a.sql")),
+            vec!["platform"]
+        );
+    }
+
     #[test]
     fn test_rules_section_defaults_to_empty() {
         let config = Config::default();
@@ -477,6 +2188,272 @@ mod tests {
         assert!(config.rules.disabled.is_empty());
     }
 
+    #[test]
+    fn test_meta_pgm901_exempt_rules_deserialization() {
+        let toml = "[meta.pgm901]\nexempt_rules = [\"PGM201\", \"PGM202\"]";
+        let config = parse_and_validate(toml).unwrap();
+        assert_eq!(
+            config.meta.pgm901.exempt_rule_ids(),
+            vec![crate::rules::RuleId::Pgm201, crate::rules::RuleId::Pgm202]
+        );
+    }
+
+    #[test]
+    fn test_meta_pgm901_exempt_rules_rejects_meta_rule() {
+        let toml = "[meta.pgm901]\nexempt_rules = [\"PGM901\"]";
+        let err = parse_and_validate(toml).unwrap_err();
+        assert!(
+            err.to_string().contains("meta.pgm901.exempt_rules"),
+            "Expected validation error, got: {}",
+            err
+        );
+    }
+
+    // --- postgres tests ---
+
+    #[test]
+    fn test_postgres_version_deserialization() {
+        let config = parse_and_validate("[postgres]\nversion = 10").unwrap();
+        assert_eq!(config.postgres.version, Some(10));
+        assert_eq!(Config::default().postgres.version, None);
+    }
+
+    #[test]
+    fn test_postgres_version_too_old_rejected() {
+        let err = parse_and_validate("[postgres]\nversion = 8").unwrap_err();
+        assert!(
+            err.to_string().contains("invalid postgres.version 8"),
+            "{err}"
+        );
+    }
+
+    // --- patterns tests ---
+
+    #[test]
+    fn test_patterns_deserialization() {
+        let toml = "[[patterns]]\nname = \"not-null-via-check\"\non = \"set_not_null\"\n\
+                    preceded_by = \"validated_check\"\n\
+                    [[patterns]]\nname = \"fk-not-valid\"\non = \"add_foreign_key\"\n\
+                    require = [\"not_valid\"]\nfollowed_by = \"validate_constraint\"\n\
+                    severity = \"critical\"";
+        let config = parse_and_validate(toml).unwrap();
+        assert_eq!(config.patterns.len(), 2);
+        assert_eq!(
+            config.patterns[0].preceded_by,
+            Some(crate::rules::patterns::PatternOp::ValidatedCheck)
+        );
+        assert!(config.patterns[0].separate_unit);
+        assert_eq!(
+            config.patterns[1].require,
+            vec![crate::rules::patterns::PatternFlag::NotValid]
+        );
+        assert!(Config::default().patterns.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_patterns_rejected() {
+        for (toml, expected) in [
+            (
+                "[[patterns]]\nname = \"p\"\non = \"set_not_null\"",
+                "set at least one of require",
+            ),
+            (
+                "[[patterns]]\nname = \"p\"\non = \"set_not_null\"\nrequire = [\"concurrently\"]",
+                "CONCURRENTLY does not apply to SET NOT NULL",
+            ),
+            (
+                "[[patterns]]\nname = \"p\"\non = \"create_index\"\nrequire = [\"concurrently\"]\n\
+                 severity = \"loud\"",
+                "severity 'loud'",
+            ),
+            (
+                "[[patterns]]\nname = \"p\"\non = \"create_index\"\nrequire = [\"concurrently\"]\n\
+                 [[patterns]]\nname = \"p\"\non = \"add_check\"\nrequire = [\"not_valid\"]",
+                "duplicate pattern name 'p'",
+            ),
+        ] {
+            let err = parse_and_validate(toml).unwrap_err();
+            assert!(err.to_string().contains(expected), "{err}");
+        }
+        assert!(parse_and_validate("[[patterns]]\nname = \"p\"\non = \"drop_table\"").is_err());
+    }
+
+    // --- messages tests ---
+
+    #[test]
+    fn test_messages_deserialization() {
+        let toml = "[messages]\ndefault = \"{message} See {rule_id}\"\n\
+                    [messages.templates]\nPGM001 = \"Index on {table}\"";
+        let config = parse_and_validate(toml).unwrap();
+        assert_eq!(
+            config.messages.template_for(crate::rules::RuleId::Pgm001),
+            Some("Index on {table}")
+        );
+        assert_eq!(
+            config.messages.template_for(crate::rules::RuleId::Pgm002),
+            Some("{message} See {rule_id}")
+        );
+    }
+
+    #[test]
+    fn test_messages_rejects_unbalanced_template() {
+        let toml = "[messages.templates]\nPGM001 = \"{message\"";
+        let err = parse_and_validate(toml).unwrap_err().to_string();
+        assert!(err.contains("message template for PGM001"), "{err}");
+    }
+
+    #[test]
+    fn test_messages_rejects_meta_rule() {
+        let toml = "[messages.templates]\nPGM901 = \"{message}\"";
+        let err = parse_and_validate(toml).unwrap_err().to_string();
+        assert!(err.contains("meta rule 'PGM901'"), "{err}");
+    }
+
+    #[test]
+    fn test_messages_locale_file_merged_under_config() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("messages.de.toml"),
+            "default = \"{message} (Regel {rule_id})\"\n\
+             [templates]\nPGM001 = \"Index auf {table}\"\nPGM002 = \"Index entfernt\"",
+        )
+        .unwrap();
+        let config_path = dir.path().join("pg-migration-lint.toml");
+        std::fs::write(
+            &config_path,
+            "[messages]\nlocale_file = \"messages.de.toml\"\n\
+             [messages.templates]\nPGM002 = \"{message} Runbook: https://wiki/drop-index\"",
+        )
+        .unwrap();
+
+        let config = Config::from_file(&config_path).unwrap();
+        let messages = &config.messages;
+        assert_eq!(
+            messages.template_for(crate::rules::RuleId::Pgm001),
+            Some("Index auf {table}")
+        );
+        assert_eq!(
+            messages.template_for(crate::rules::RuleId::Pgm002),
+            Some("{message} Runbook: https://wiki/drop-index")
+        );
+        assert_eq!(
+            messages.template_for(crate::rules::RuleId::Pgm101),
+            Some("{message} (Regel {rule_id})")
+        );
+    }
+
+    #[test]
+    fn test_messages_missing_locale_file_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("pg-migration-lint.toml");
+        std::fs::write(&config_path, "[messages]\nlocale_file = \"missing.toml\"").unwrap();
+        let err = Config::from_file(&config_path).unwrap_err().to_string();
+        assert!(err.contains("messages.locale_file"), "{err}");
+    }
+
+    #[test]
+    fn test_rename_workflow_state_file_loaded_relative_to_config() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("db")).unwrap();
+        std::fs::write(
+            dir.path().join("db/renames.toml"),
+            "[[renames]]\ntable = \"orders\"\ncolumn = \"status\"\nphase = \"contract\"",
+        )
+        .unwrap();
+        let config_path = dir.path().join("pg-migration-lint.toml");
+        std::fs::write(
+            &config_path,
+            "[rename_workflow]\nstate_file = \"db/renames.toml\"",
+        )
+        .unwrap();
+
+        let config = Config::from_file(&config_path).unwrap();
+        let orders = crate::parser::ir::QualifiedName::qualified("public", "orders");
+        assert_eq!(
+            config.rename_workflow.state.phase(&orders, Some("status")),
+            Some(crate::rename_workflow::RenamePhase::Contract)
+        );
+
+        std::fs::write(
+            &config_path,
+            "[rename_workflow]\nstate_file = \"missing.toml\"",
+        )
+        .unwrap();
+        let err = Config::from_file(&config_path).unwrap_err().to_string();
+        assert!(err.contains("rename_workflow.state_file"), "{err}");
+    }
+
+    #[test]
+    fn test_lock_budget_rows_and_validation() {
+        let toml = "[lock_budget]\nmax_seconds = 5\ndefault_rows = 1000\n\
+                    [lock_budget.table_rows]\norders = 40000000\n\"billing.orders\" = 10";
+        let config = parse_and_validate(toml).unwrap();
+        let budget = &config.lock_budget;
+        assert_eq!(budget.max_seconds, Some(5.0));
+        assert_eq!(budget.scan_rows_per_second, 2_000_000);
+        assert_eq!(budget.rows_for("public.orders"), 40_000_000);
+        assert_eq!(budget.rows_for("billing.orders"), 10);
+        assert_eq!(budget.rows_for("public.users"), 1000);
+
+        let err = parse_and_validate("[lock_budget]\nmax_seconds = 0")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("invalid lock_budget.max_seconds 0"), "{err}");
+        let err = parse_and_validate("[lock_budget]\nrewrite_rows_per_second = 0")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("invalid lock_budget.rewrite_rows_per_second 0"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_warnings_fail_on_categories() {
+        use crate::catalog::WarningCategory;
+
+        assert!(Config::default().warnings.fail_on.is_empty());
+        let config = parse_and_validate(
+            "[warnings]\nfail_on = [\"replay_conflict\", \"unparseable\", \"rejected\"]",
+        )
+        .unwrap();
+        assert_eq!(config.warnings.fail_on, WarningCategory::ALL);
+
+        let err = parse_and_validate("[warnings]\nfail_on = [\"typo\"]")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown variant `typo`"), "{err}");
+    }
+
+    #[test]
+    fn test_liquibase_contexts_and_labels() {
+        let config = parse_and_validate("[liquibase]").unwrap();
+        assert!(config.liquibase.contexts.is_empty());
+        assert_eq!(config.liquibase.labels, None);
+
+        let toml = "[liquibase]\ncontexts = [\"prod\"]\nlabels = \"billing and !legacy\"";
+        let config = parse_and_validate(toml).unwrap();
+        assert_eq!(config.liquibase.contexts, vec!["prod"]);
+        assert_eq!(
+            config.liquibase.labels.as_deref(),
+            Some("billing and !legacy")
+        );
+    }
+
+    #[test]
+    fn test_catalog_strict_replay() {
+        assert!(!Config::default().catalog.strict_replay);
+        let config = parse_and_validate("[catalog]\nstrict_replay = true").unwrap();
+        assert!(config.catalog.strict_replay);
+    }
+
+    #[test]
+    fn test_catalog_assume_unknown_tables_exist() {
+        assert!(!Config::default().catalog.assume_unknown_tables_exist);
+        let config = parse_and_validate("[catalog]\nassume_unknown_tables_exist = true").unwrap();
+        assert!(config.catalog.assume_unknown_tables_exist);
+    }
+
     #[test]
     fn test_run_in_transaction_defaults_to_none() {
         let config = Config::default();
@@ -497,6 +2474,19 @@ mod tests {
         assert_eq!(config.migrations.run_in_transaction, Some(false));
     }
 
+    #[test]
+    fn test_loader_toggles_default_on() {
+        let config = parse_and_validate("[migrations]").unwrap();
+        assert!(config.migrations.respect_gitignore);
+        assert!(config.migrations.follow_symlinks);
+
+        let toml = "[migrations]\nrespect_gitignore = false\nfollow_symlinks = false";
+        let config = parse_and_validate(toml).unwrap();
+        assert!(!config.migrations.respect_gitignore);
+        assert!(!config.migrations.follow_symlinks);
+        assert!(!config.migrations.resolve_psql_includes);
+    }
+
     #[test]
     fn test_run_in_transaction_absent_is_none() {
         let toml = "[migrations]\nstrategy = \"filename_lexicographic\"";
@@ -509,7 +2499,7 @@ mod tests {
     #[test]
     fn test_resolve_paths_prepends_config_dir() {
         let mut config = Config::default();
-        config.migrations.paths = vec![PathBuf::from("db/migrations")];
+        config.migrations.paths = vec![PathBuf::from("db/migrations").into()];
         config.liquibase.bridge_jar_path = Some(PathBuf::from("tools/bridge.jar"));
         config.liquibase.properties_file = Some(PathBuf::from("liquibase.properties"));
         config.output.dir = PathBuf::from("build/reports");
@@ -517,9 +2507,13 @@ mod tests {
         config.resolve_paths(Path::new("/project/impl"));
 
         assert_eq!(
-            config.migrations.paths,
+            config.migrations.source_paths(),
             vec![PathBuf::from("/project/impl/db/migrations")]
         );
+        assert_eq!(
+            config.migrations.include_root,
+            Some(PathBuf::from("/project/impl"))
+        );
         assert_eq!(
             config.liquibase.bridge_jar_path,
             Some(PathBuf::from("/project/impl/tools/bridge.jar"))
@@ -537,14 +2531,14 @@ mod tests {
     #[test]
     fn test_resolve_paths_leaves_absolute_paths_unchanged() {
         let mut config = Config::default();
-        config.migrations.paths = vec![PathBuf::from("/abs/db/migrations")];
+        config.migrations.paths = vec![PathBuf::from("/abs/db/migrations").into()];
         config.liquibase.bridge_jar_path = Some(PathBuf::from("/abs/tools/bridge.jar"));
         config.output.dir = PathBuf::from("/abs/build/reports");
 
         config.resolve_paths(Path::new("/project/impl"));
 
         assert_eq!(
-            config.migrations.paths,
+            config.migrations.source_paths(),
             vec![PathBuf::from("/abs/db/migrations")]
         );
         assert_eq!(
@@ -554,6 +2548,39 @@ mod tests {
         assert_eq!(config.output.dir, PathBuf::from("/abs/build/reports"));
     }
 
+    #[test]
+    fn test_default_schema_per_path() {
+        let mut config: Config = toml::from_str(
+            r#"
+[migrations]
+default_schema = "shop"
+paths = [
+  { path = "services/billing", default_schema = "billing" },
+  { path = "services/billing/legacy", default_schema = "billing_old" },
+  "services/shop",
+  { path = "services/audit" },
+]
+"#,
+        )
+        .unwrap();
+        config.resolve_paths(Path::new("/repo"));
+
+        let migrations = &config.migrations;
+        assert_eq!(
+            migrations.source_paths()[0],
+            PathBuf::from("/repo/services/billing")
+        );
+        let schema_for = |file: &str| migrations.default_schema_for(Path::new(file));
+        assert_eq!(schema_for("/repo/services/billing/V1.sql"), "billing");
+        assert_eq!(
+            schema_for("/repo/services/billing/legacy/V1.sql"),
+            "billing_old"
+        );
+        assert_eq!(schema_for("/repo/services/shop/V1.sql"), "shop");
+        assert_eq!(schema_for("/repo/services/audit/V1.sql"), "shop");
+        assert_eq!(schema_for("/repo/services/billing_v2/V1.sql"), "shop");
+    }
+
     #[test]
     fn test_resolve_paths_bare_binary_name_unchanged() {
         let mut config = Config::default();
@@ -585,18 +2612,50 @@ mod tests {
     #[test]
     fn test_resolve_paths_dot_config_dir_is_identity() {
         let mut config = Config::default();
-        config.migrations.paths = vec![PathBuf::from("db/migrations")];
+        config.migrations.paths = vec![PathBuf::from("db/migrations").into()];
         config.output.dir = PathBuf::from("build/reports");
 
         config.resolve_paths(Path::new("."));
 
         assert_eq!(
-            config.migrations.paths,
+            config.migrations.source_paths(),
             vec![PathBuf::from("./db/migrations")]
         );
         assert_eq!(config.output.dir, PathBuf::from("./build/reports"));
     }
 
+    #[test]
+    fn test_resolve_paths_schema_team_paths() {
+        let mut config = Config::default();
+        config.schemas.teams.insert(
+            "payments_team".to_string(),
+            vec![PathBuf::from("db/payments"), PathBuf::from("/abs/payments")],
+        );
+
+        config.resolve_paths(Path::new("/project"));
+
+        assert_eq!(
+            config.schemas.teams["payments_team"],
+            vec![
+                PathBuf::from("/project/db/payments"),
+                PathBuf::from("/abs/payments"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_paths_messages_locale_file() {
+        let mut config = Config::default();
+        config.messages.locale_file = Some(PathBuf::from("i18n/messages.de.toml"));
+
+        config.resolve_paths(Path::new("/project"));
+
+        assert_eq!(
+            config.messages.locale_file,
+            Some(PathBuf::from("/project/i18n/messages.de.toml"))
+        );
+    }
+
     // --- strip_prefix tests ---
 
     #[test]
@@ -619,6 +2678,25 @@ mod tests {
         assert_eq!(config.output.strip_prefix, None);
     }
 
+    #[test]
+    fn test_redact_literals_deserialization() {
+        let toml = "[output]\nredact_literals = true";
+        let config = parse_and_validate(toml).unwrap();
+        assert!(config.output.redact_literals);
+    }
+
+    #[test]
+    fn test_pr_comment_deserialization() {
+        let toml = "[output.pr_comment]\nmax_items = 5\nsource_url = \"https://github.com/acme/shop/blob/main\"";
+        let config = parse_and_validate(toml).unwrap();
+        assert_eq!(config.output.pr_comment.max_items, 5);
+        assert_eq!(
+            config.output.pr_comment.source_url.as_deref(),
+            Some("https://github.com/acme/shop/blob/main")
+        );
+        assert_eq!(config.output.pr_comment.artifacts_url, None);
+    }
+
     // --- config defaults tests ---
 
     /// Assert that every field in the config has its expected default value.
@@ -626,7 +2704,7 @@ mod tests {
     fn assert_defaults_sane(config: &Config) {
         // migrations
         assert_eq!(
-            config.migrations.paths,
+            config.migrations.source_paths(),
             vec![PathBuf::from("db/migrations")],
             "migrations.paths"
         );
@@ -681,6 +2759,20 @@ mod tests {
             "output.dir"
         );
         assert_eq!(config.output.strip_prefix, None, "output.strip_prefix");
+        assert!(!config.output.redact_literals, "output.redact_literals");
+        assert!(!config.output.include_snippets, "output.include_snippets");
+        assert_eq!(
+            config.output.pr_comment.max_items, 20,
+            "output.pr_comment.max_items"
+        );
+        assert_eq!(
+            config.output.pr_comment.source_url, None,
+            "output.pr_comment.source_url"
+        );
+        assert_eq!(
+            config.output.pr_comment.artifacts_url, None,
+            "output.pr_comment.artifacts_url"
+        );
 
         // cli
         assert_eq!(config.cli.fail_on, "critical", "cli.fail_on");
@@ -690,6 +2782,29 @@ mod tests {
             config.rules.disabled.is_empty(),
             "rules.disabled should be empty"
         );
+        assert!(
+            config.rules.pgm107.allow_tables.is_empty(),
+            "rules.pgm107.allow_tables should be empty"
+        );
+        assert_eq!(
+            config.rules.pgm110.column_patterns,
+            vec!["*_id".to_string(), "*uuid*".to_string()],
+            "rules.pgm110.column_patterns"
+        );
+        assert!(
+            config
+                .rules
+                .pgm110
+                .uuid_functions
+                .contains(&"gen_random_uuid".to_string()),
+            "rules.pgm110.uuid_functions should include gen_random_uuid"
+        );
+
+        // meta
+        assert!(
+            config.meta.pgm901.exempt_rules.is_empty(),
+            "meta.pgm901.exempt_rules should be empty"
+        );
     }
 
     #[test]
@@ -733,7 +2848,7 @@ mod tests {
 
     #[test]
     fn test_explain_config_each_section_ok() {
-        for section in &["migrations", "liquibase", "output", "cli", "rules"] {
+        for section in VALID_SECTIONS {
             assert!(
                 explain_config(section).is_ok(),
                 "explain_config({section:?}) should return Ok",
@@ -169,8 +169,8 @@ pub fn build_context(examples_dir: &Path) -> Result<DocsContext, DocgenError> {
         }
     }
 
-    // PGM901 is a meta-behavior, not a standalone rule — exclude from count
-    let rule_count = all_rules.iter().filter(|r| r.id != "PGM901").count();
+    // Meta-behaviors (PGM9xx) are not standalone rules — exclude from count
+    let rule_count = RuleId::lint_rules().count();
 
     Ok(DocsContext {
         rule_count,
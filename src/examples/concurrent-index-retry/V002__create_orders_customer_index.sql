@@ -0,0 +1,7 @@
+-- CREATE INDEX CONCURRENTLY does not block writes, but it cannot run inside
+-- a transaction (see run_in_transaction in pg-migration-lint.toml). If it
+-- fails or is cancelled, it leaves an INVALID index behind, and a retried
+-- CREATE INDEX IF NOT EXISTS would silently keep it. Dropping the index
+-- first makes the migration safe to run again.
+DROP INDEX CONCURRENTLY IF EXISTS idx_orders_customer_id;
+CREATE INDEX CONCURRENTLY IF NOT EXISTS idx_orders_customer_id ON orders (customer_id);
@@ -0,0 +1,235 @@
+//! Blessed end-to-end migration scenarios.
+//!
+//! Each [`Example`] is a small migration history that shows a safe rollout
+//! pattern the rules push towards: a zero-downtime column rename, a NOT NULL
+//! rollout that avoids the full-table scan, and a retryable concurrent index
+//! build. The SQL and config are embedded in the binary, so
+//! `pg-migration-lint examples show <name>` prints (or writes) a template
+//! that teams can copy, and tests can lint the same files.
+//!
+//! The first migration of every example is the starting schema. It is
+//! replayed but not linted, like the baseline of an existing repository.
+
+use std::path::{Path, PathBuf};
+
+use crate::config::{Config, ConfigError};
+use crate::input::MigrationUnit;
+use crate::input::sql::SqlLoader;
+use crate::normalize;
+use crate::pipeline::LintPipeline;
+use crate::rules::{self, Finding};
+
+/// Name of the config file written next to an example's migrations.
+pub const CONFIG_FILE: &str = "pg-migration-lint.toml";
+
+/// Directory the migrations are written to, relative to the config file.
+pub const MIGRATIONS_DIR: &str = "migrations";
+
+/// One migration file of an example.
+#[derive(Debug, Clone, Copy)]
+pub struct ExampleFile {
+    /// File name, e.g. `V002__expand_add_full_name.sql`.
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+/// An embedded migration scenario.
+#[derive(Debug, Clone, Copy)]
+pub struct Example {
+    /// Name used on the command line, e.g. `rename-column`.
+    pub name: &'static str,
+    /// One-line summary.
+    pub title: &'static str,
+    /// Contents of the example's `pg-migration-lint.toml`.
+    pub config: &'static str,
+    /// Migrations in the order they run. The first is the starting schema.
+    pub files: &'static [ExampleFile],
+}
+
+macro_rules! example_file {
+    ($dir:literal, $name:literal) => {
+        ExampleFile {
+            name: $name,
+            sql: include_str!(concat!($dir, "/", $name)),
+        }
+    };
+}
+
+/// Every shipped example, in the order `examples list` prints them.
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        name: "rename-column",
+        title: "Zero-downtime column rename (expand, migrate, contract)",
+        config: include_str!("rename-column/pg-migration-lint.toml"),
+        files: &[
+            example_file!("rename-column", "V001__create_users.sql"),
+            example_file!("rename-column", "V002__expand_add_full_name.sql"),
+            example_file!("rename-column", "V003__migrate_backfill_full_name.sql"),
+            example_file!("rename-column", "V004__contract_drop_display_name.sql"),
+        ],
+    },
+    Example {
+        name: "not-null-rollout",
+        title: "SET NOT NULL through a validated CHECK constraint",
+        config: include_str!("not-null-rollout/pg-migration-lint.toml"),
+        files: &[
+            example_file!("not-null-rollout", "V001__create_orders.sql"),
+            example_file!("not-null-rollout", "V002__add_customer_ref_check.sql"),
+            example_file!("not-null-rollout", "V003__validate_customer_ref_check.sql"),
+            example_file!("not-null-rollout", "V004__set_customer_ref_not_null.sql"),
+            example_file!("not-null-rollout", "V005__drop_customer_ref_check.sql"),
+        ],
+    },
+    Example {
+        name: "concurrent-index-retry",
+        title: "CREATE INDEX CONCURRENTLY that is safe to retry after a failure",
+        config: include_str!("concurrent-index-retry/pg-migration-lint.toml"),
+        files: &[
+            example_file!("concurrent-index-retry", "V001__create_orders.sql"),
+            example_file!(
+                "concurrent-index-retry",
+                "V002__create_orders_customer_index.sql"
+            ),
+        ],
+    },
+];
+
+/// The example called `name`, if there is one.
+pub fn find(name: &str) -> Option<&'static Example> {
+    EXAMPLES.iter().find(|e| e.name == name)
+}
+
+impl Example {
+    /// Parse the example's configuration.
+    pub fn parse_config(&self) -> Result<Config, ConfigError> {
+        Ok(toml::from_str(self.config)?)
+    }
+
+    /// Parse every migration into a unit, as if read from
+    /// `migrations/<file>`.
+    pub fn units(&self, config: &Config) -> Vec<MigrationUnit> {
+        let run_in_tx = config.migrations.run_in_transaction.unwrap_or(true);
+        let loader = SqlLoader::new(run_in_tx);
+        let mut units: Vec<MigrationUnit> = self
+            .files
+            .iter()
+            .map(|file| loader.load_source(&Path::new(MIGRATIONS_DIR).join(file.name), file.sql))
+            .collect();
+        for unit in &mut units {
+            let default_schema = config.migrations.default_schema_for(&unit.source_file);
+            normalize::normalize_unit(unit, default_schema);
+        }
+        units
+    }
+
+    /// Lint the example with its own configuration: the first migration is
+    /// replayed, the rest are linted as changed files. Inline suppressions
+    /// are applied and findings come back in report order.
+    pub fn lint(&self) -> Result<Vec<Finding>, ConfigError> {
+        let config = self.parse_config()?;
+        let units = self.units(&config);
+        let mut pipeline = LintPipeline::new()
+            .with_down_cap_exempt(&config.meta.pgm901.exempt_rule_ids())
+            .with_config(config.clone());
+        let rules = config.rules.active_rules();
+
+        let mut findings = Vec::new();
+        let Some((baseline, changed)) = units.split_first() else {
+            return Ok(findings);
+        };
+        pipeline.replay(baseline);
+        for unit in changed {
+            let mut unit_findings = pipeline.lint(unit, &rules);
+            let suppressions = &unit.suppressions;
+            unit_findings.retain(|f| !suppressions.is_suppressed(f.rule_id, f.start_line));
+            suppressions.apply_acknowledgments(&mut unit_findings);
+            findings.append(&mut unit_findings);
+        }
+        findings.extend(pipeline.finish(&rules));
+        rules::dedup_findings(&mut findings);
+        rules::sort_findings(&mut findings);
+        Ok(findings)
+    }
+
+    /// Write the config to `dir/pg-migration-lint.toml` and the migrations
+    /// to `dir/migrations/`, creating directories as needed. Returns the
+    /// written paths.
+    pub fn write_to(&self, dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+        let migrations = dir.join(MIGRATIONS_DIR);
+        std::fs::create_dir_all(&migrations)?;
+
+        let config_path = dir.join(CONFIG_FILE);
+        std::fs::write(&config_path, self.config)?;
+        let mut written = vec![config_path];
+        for file in self.files {
+            let path = migrations.join(file.name);
+            std::fs::write(&path, file.sql)?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Severity;
+
+    #[test]
+    fn test_names_are_unique_and_found() {
+        for example in EXAMPLES {
+            assert_eq!(find(example.name).unwrap().name, example.name);
+        }
+        let mut names: Vec<_> = EXAMPLES.iter().map(|e| e.name).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), EXAMPLES.len());
+        assert!(find("no-such-example").is_none());
+    }
+
+    #[test]
+    fn test_configs_parse() {
+        for example in EXAMPLES {
+            let config = example
+                .parse_config()
+                .unwrap_or_else(|e| panic!("{}: {e}", example.name));
+            assert_eq!(
+                config.migrations.source_paths(),
+                [PathBuf::from(MIGRATIONS_DIR)]
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_to_lays_out_config_and_migrations() {
+        let dir = tempfile::tempdir().unwrap();
+        let example = find("rename-column").unwrap();
+        let written = example.write_to(dir.path()).unwrap();
+
+        assert_eq!(written.len(), example.files.len() + 1);
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join(CONFIG_FILE)).unwrap(),
+            example.config
+        );
+        let first = dir.path().join("migrations/V001__create_users.sql");
+        assert_eq!(
+            std::fs::read_to_string(first).unwrap(),
+            example.files[0].sql
+        );
+    }
+
+    #[test]
+    fn test_examples_lint_clean() {
+        // A template the linter flags would not be worth copying. Info
+        // findings (e.g. PGM009 on the contract step) are reminders only.
+        for example in EXAMPLES {
+            let findings = example.lint().unwrap();
+            let flagged: Vec<_> = findings
+                .iter()
+                .filter(|f| f.severity >= Severity::Minor)
+                .map(|f| format!("{} {}:{}", f.rule_id, f.file.display(), f.start_line))
+                .collect();
+            assert!(flagged.is_empty(), "{}: {flagged:?}", example.name);
+        }
+    }
+}
@@ -0,0 +1,7 @@
+-- Step 1: promise that new rows have a customer_ref. NOT VALID skips the
+-- scan of existing rows, so the ACCESS EXCLUSIVE lock is held only briefly.
+--
+-- Backfill any NULLs before the next migration.
+ALTER TABLE orders
+    ADD CONSTRAINT orders_customer_ref_not_null
+    CHECK (customer_ref IS NOT NULL) NOT VALID;
@@ -0,0 +1,3 @@
+-- Step 2: check the existing rows. VALIDATE CONSTRAINT scans the table under
+-- a SHARE UPDATE EXCLUSIVE lock, which does not block reads or writes.
+ALTER TABLE orders VALIDATE CONSTRAINT orders_customer_ref_not_null;
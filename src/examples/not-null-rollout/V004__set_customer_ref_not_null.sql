@@ -0,0 +1,5 @@
+-- Step 3: PostgreSQL 12+ sees the validated CHECK (customer_ref IS NOT NULL)
+-- and sets NOT NULL without scanning the table. PGM013 cannot tell that the
+-- CHECK was validated in an earlier migration, so it is suppressed here.
+-- pgm-lint:suppress PGM013
+ALTER TABLE orders ALTER COLUMN customer_ref SET NOT NULL;
@@ -0,0 +1,4 @@
+-- Step 4: the CHECK is now redundant. It is dropped in its own migration:
+-- in a combined ALTER TABLE, PostgreSQL drops constraints before it sets
+-- NOT NULL, and would scan the table after all.
+ALTER TABLE orders DROP CONSTRAINT orders_customer_ref_not_null;
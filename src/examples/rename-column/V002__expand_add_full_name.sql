@@ -0,0 +1,6 @@
+-- Expand: add the new column next to the old one. Nullable and without a
+-- default, so adding it only touches the catalog.
+--
+-- Before the next migration, deploy application code that writes both
+-- display_name and full_name and still reads display_name.
+ALTER TABLE users ADD COLUMN full_name text;
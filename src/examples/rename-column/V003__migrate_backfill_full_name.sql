@@ -0,0 +1,7 @@
+-- Migrate: copy the rows written before the dual-write deploy. On a large
+-- table, run the same UPDATE in batches of primary key ranges instead.
+--
+-- After it has run, deploy code that reads full_name and stops writing
+-- display_name.
+-- pgm-lint:suppress PGM302
+UPDATE users SET full_name = display_name WHERE full_name IS NULL;
@@ -0,0 +1,4 @@
+-- Contract: no deployed code reads or writes display_name any more, so it
+-- can go. DROP COLUMN only marks the column dropped; it does not rewrite
+-- the table.
+ALTER TABLE users DROP COLUMN display_name;
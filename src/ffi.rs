@@ -0,0 +1,334 @@
+//! C ABI for embedding the linter from other languages (feature `ffi`).
+//!
+//! Exposes [`pgml_lint_sql`], [`pgml_lint_paths`], and [`pgml_string_free`]
+//! so that C, Python (`ctypes`/`cffi`), and other FFI-capable runtimes can
+//! lint without spawning the CLI. Everything crosses the boundary as
+//! NUL-terminated UTF-8 JSON strings, so callers never see Rust types.
+//!
+//! `cargo build --release --features ffi` builds the shared library next to
+//! the rlib. The C declarations are in `include/pg_migration_lint.h`,
+//! generated from this file with
+//! `cbindgen --config cbindgen.toml --output include/pg_migration_lint.h`.
+
+use std::ffi::{CStr, CString, c_char};
+use std::panic;
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use crate::Config;
+use crate::output::{JsonReporter, Reporter};
+use crate::pipeline::{lint_paths, lint_sql};
+
+/// Lint a single SQL migration and return the result as a JSON string.
+///
+/// - `sql`: the migration source.
+/// - `file`: path reported on findings (also drives `.down.sql` detection).
+///   Never read from disk. May be null, in which case `"migration.sql"` is used.
+/// - `config_json`: a JSON object with the same structure as
+///   `pg-migration-lint.toml`. May be null for the default configuration.
+///
+/// Returns `{"findings": [...]}` on success or `{"error": "..."}` on invalid
+/// input or an internal failure; a panic never crosses the boundary. The
+/// returned string is owned by the caller and must be released
+/// with [`pgml_string_free`].
+///
+/// # Safety
+///
+/// Every non-null pointer argument must point to a valid NUL-terminated
+/// string that stays alive for the duration of the call.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pgml_lint_sql(
+    sql: *const c_char,
+    file: *const c_char,
+    config_json: *const c_char,
+) -> *mut c_char {
+    // A panic must not unwind into the caller's frames.
+    let result = panic::catch_unwind(|| {
+        // SAFETY: the caller guarantees the pointers are valid C strings or null.
+        unsafe { lint_sql_json(sql, file, config_json) }
+            .map(|findings| json!({ "findings": findings }).to_string())
+    });
+    respond(result)
+}
+
+/// Lint migrations on disk, as the CLI does, and return the `report.json`
+/// document as a JSON string.
+///
+/// - `config_json`: a JSON object with the same structure as
+///   `pg-migration-lint.toml`. May be null for the default configuration.
+///   Relative paths resolve against the process's working directory.
+/// - `paths_json`: a JSON array of migration directories or files, replacing
+///   `migrations.paths`. May be null to keep the configured paths.
+/// - `changed_json`: a JSON array of the changed files to lint; the rest of
+///   the history is only replayed. May be null to lint every migration.
+///
+/// Returns the report (`{"version": 1, "summary": {...}, "findings": [...]}`)
+/// on success or `{"error": "..."}` on invalid input, a migration that
+/// cannot be loaded, or an internal failure; a panic never crosses the
+/// boundary. The returned string is owned by the caller and must be
+/// released with [`pgml_string_free`].
+///
+/// # Safety
+///
+/// Every non-null pointer argument must point to a valid NUL-terminated
+/// string that stays alive for the duration of the call.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pgml_lint_paths(
+    config_json: *const c_char,
+    paths_json: *const c_char,
+    changed_json: *const c_char,
+) -> *mut c_char {
+    // A panic must not unwind into the caller's frames.
+    let result = panic::catch_unwind(|| {
+        // SAFETY: the caller guarantees the pointers are valid C strings or null.
+        unsafe { lint_paths_json(config_json, paths_json, changed_json) }
+    });
+    respond(result)
+}
+
+/// Turn the outcome of a call into the string handed to the caller.
+fn respond(result: std::thread::Result<Result<String, String>>) -> *mut c_char {
+    let body = match result {
+        Ok(Ok(body)) => body,
+        Ok(Err(error)) => json!({ "error": error }).to_string(),
+        Err(_) => json!({ "error": "internal error: the linter panicked" }).to_string(),
+    };
+    // serde_json escapes NUL bytes, so the rendered JSON should never
+    // contain one; answer with a fixed error rather than trust that.
+    CString::new(body)
+        .unwrap_or_else(|_| {
+            CString::from(c"{\"error\":\"internal error: output contains a NUL byte\"}")
+        })
+        .into_raw()
+}
+
+/// Release a string returned by [`pgml_lint_sql`] or [`pgml_lint_paths`].
+///
+/// # Safety
+///
+/// `s` must be null or a pointer previously returned by [`pgml_lint_sql`]
+/// or [`pgml_lint_paths`] that has not been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pgml_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        // A panic must not unwind into the caller's frames; there is nothing
+        // to report it to, so it is dropped.
+        let _ = panic::catch_unwind(|| {
+            // SAFETY: `s` was produced by `CString::into_raw` in `respond`.
+            drop(unsafe { CString::from_raw(s) });
+        });
+    }
+}
+
+/// Decode the C arguments and run [`lint_sql`].
+///
+/// # Safety
+///
+/// Same contract as [`pgml_lint_sql`].
+unsafe fn lint_sql_json(
+    sql: *const c_char,
+    file: *const c_char,
+    config_json: *const c_char,
+) -> Result<Vec<crate::Finding>, String> {
+    // SAFETY: forwarded from the caller.
+    let sql = unsafe { c_str_arg(sql, "sql")? }.ok_or("sql must not be null")?;
+    let file = unsafe { c_str_arg(file, "file")? }.unwrap_or("migration.sql");
+    let config = unsafe { config_arg(config_json)? };
+    Ok(lint_sql(sql, Path::new(file), &config))
+}
+
+/// Decode the C arguments, run [`lint_paths`], and render the report.
+///
+/// # Safety
+///
+/// Same contract as [`pgml_lint_paths`].
+unsafe fn lint_paths_json(
+    config_json: *const c_char,
+    paths_json: *const c_char,
+    changed_json: *const c_char,
+) -> Result<String, String> {
+    // SAFETY: forwarded from the caller.
+    let mut config = unsafe { config_arg(config_json)? };
+    if let Some(paths) = unsafe { path_list_arg(paths_json, "paths_json")? } {
+        config.migrations.paths = paths.into_iter().map(Into::into).collect();
+    }
+    let changed = unsafe { path_list_arg(changed_json, "changed_json")? };
+    let findings = lint_paths(&config, changed.as_deref()).map_err(|e| e.to_string())?;
+    JsonReporter::new()
+        .render(&findings)
+        .map_err(|e| e.to_string())
+}
+
+/// Parse and validate the nullable `config_json` argument.
+///
+/// # Safety
+///
+/// `ptr` must be null or a valid NUL-terminated string.
+unsafe fn config_arg(ptr: *const c_char) -> Result<Config, String> {
+    // SAFETY: forwarded from the caller.
+    let config = match unsafe { c_str_arg(ptr, "config_json")? } {
+        Some(text) => {
+            serde_json::from_str::<Config>(text).map_err(|e| format!("invalid config: {e}"))?
+        }
+        None => Config::default(),
+    };
+    crate::rules::RuleRegistry::assemble(&config)
+        .validate_config(&config)
+        .map_err(|e| format!("invalid config: {e}"))?;
+    Ok(config)
+}
+
+/// Parse a nullable JSON array of paths.
+///
+/// # Safety
+///
+/// `ptr` must be null or a valid NUL-terminated string.
+unsafe fn path_list_arg(ptr: *const c_char, name: &str) -> Result<Option<Vec<PathBuf>>, String> {
+    // SAFETY: forwarded from the caller.
+    unsafe { c_str_arg(ptr, name)? }
+        .map(|text| {
+            serde_json::from_str::<Vec<PathBuf>>(text)
+                .map_err(|e| format!("{name} must be a JSON array of paths: {e}"))
+        })
+        .transpose()
+}
+
+/// Borrow a nullable C string as `&str`, rejecting invalid UTF-8.
+///
+/// # Safety
+///
+/// `ptr` must be null or a valid NUL-terminated string outliving `'a`.
+unsafe fn c_str_arg<'a>(ptr: *const c_char, name: &str) -> Result<Option<&'a str>, String> {
+    if ptr.is_null() {
+        return Ok(None);
+    }
+    // SAFETY: non-null and NUL-terminated per the caller's contract.
+    let s = unsafe { CStr::from_ptr(ptr) };
+    s.to_str()
+        .map(Some)
+        .map_err(|_| format!("{name} is not valid UTF-8"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Call `pgml_lint_sql` with Rust strings and parse the JSON reply.
+    fn call(sql: Option<&str>, config: Option<&str>) -> serde_json::Value {
+        let sql = sql.map(|s| CString::new(s).unwrap());
+        let file = CString::new("V001__test.sql").unwrap();
+        let config = config.map(|c| CString::new(c).unwrap());
+        unsafe {
+            let out = pgml_lint_sql(
+                sql.as_ref().map_or(std::ptr::null(), |s| s.as_ptr()),
+                file.as_ptr(),
+                config.as_ref().map_or(std::ptr::null(), |c| c.as_ptr()),
+            );
+            let value = serde_json::from_str(CStr::from_ptr(out).to_str().unwrap()).unwrap();
+            pgml_string_free(out);
+            value
+        }
+    }
+
+    #[test]
+    fn test_lint_sql_returns_findings_array() {
+        let value = call(
+            Some("CREATE TABLE events (id bigint PRIMARY KEY, created_at timestamp);"),
+            None,
+        );
+        let findings = value["findings"].as_array().expect("findings array");
+        assert!(findings.iter().any(|f| f["rule_id"] == "PGM101"));
+    }
+
+    #[test]
+    fn test_invalid_config_returns_error() {
+        let value = call(
+            Some("SELECT 1;"),
+            Some("{\"rules\": {\"disabled\": [\"BOGUS\"]}}"),
+        );
+        let error = value["error"].as_str().expect("error string");
+        assert!(error.starts_with("invalid config"), "got: {error}");
+    }
+
+    #[test]
+    fn test_null_sql_returns_error() {
+        let value = call(None, None);
+        assert_eq!(value["error"], "sql must not be null");
+    }
+
+    /// Call `pgml_lint_paths` with Rust strings and parse the JSON reply.
+    fn call_paths(
+        config: Option<&str>,
+        paths: Option<&str>,
+        changed: Option<&str>,
+    ) -> serde_json::Value {
+        let args = [config, paths, changed].map(|arg| arg.map(|a| CString::new(a).unwrap()));
+        let [config, paths, changed] = args
+            .each_ref()
+            .map(|arg| arg.as_ref().map_or(std::ptr::null(), |a| a.as_ptr()));
+        unsafe {
+            let out = pgml_lint_paths(config, paths, changed);
+            let value = serde_json::from_str(CStr::from_ptr(out).to_str().unwrap()).unwrap();
+            pgml_string_free(out);
+            value
+        }
+    }
+
+    #[test]
+    fn test_lint_paths_returns_report_for_changed_files() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        std::fs::write(
+            dir.path().join("V001__events.sql"),
+            "CREATE TABLE events (id bigint PRIMARY KEY, created_at timestamp);",
+        )
+        .expect("write");
+        std::fs::write(
+            dir.path().join("V002__index.sql"),
+            "CREATE INDEX idx_events_created ON events (created_at);",
+        )
+        .expect("write");
+        let paths = json!([dir.path()]).to_string();
+        let changed = json!([dir.path().join("V002__index.sql")]).to_string();
+
+        let value = call_paths(None, Some(&paths), Some(&changed));
+        assert_eq!(value["version"], 1);
+        let findings = value["findings"].as_array().expect("findings array");
+        assert!(findings.iter().any(|f| f["rule_id"] == "PGM001"));
+        assert!(findings.iter().all(|f| f["rule_id"] != "PGM101"));
+        assert_eq!(value["summary"]["total"], findings.len());
+    }
+
+    #[test]
+    fn test_lint_paths_rejects_malformed_paths() {
+        let value = call_paths(None, Some("\"db/migrations\""), None);
+        let error = value["error"].as_str().expect("error string");
+        assert!(
+            error.starts_with("paths_json must be a JSON array"),
+            "got: {error}"
+        );
+    }
+
+    #[test]
+    fn test_header_declares_every_export() {
+        let header = include_str!("../include/pg_migration_lint.h");
+        let source = include_str!("ffi.rs");
+        let exports: Vec<&str> = source
+            .split("pub unsafe extern \"C\" fn ")
+            .skip(1)
+            .filter_map(|rest| rest.split('(').next())
+            .collect();
+        assert_eq!(exports.len(), 3);
+        for name in exports {
+            assert!(
+                header.contains(&format!("{name}(")),
+                "{name} missing from the header"
+            );
+        }
+    }
+
+    #[test]
+    fn test_string_free_accepts_null() {
+        unsafe { pgml_string_free(std::ptr::null_mut()) };
+    }
+}
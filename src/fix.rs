@@ -0,0 +1,567 @@
+//! Machine-applied fixes for the idempotency rules (`--fix`, `--fix-dry-run`)
+//!
+//! PGM401 and PGM402 have one obvious repair: put `IF EXISTS` after
+//! `DROP TABLE` / `DROP INDEX [CONCURRENTLY]`, or `IF NOT EXISTS` after
+//! `CREATE [TEMP | UNLOGGED] TABLE`, `CREATE [UNIQUE] INDEX [CONCURRENTLY]`,
+//! and `CREATE EXTENSION`. The fixer edits the migration text in place:
+//! a small lexer splits the file into statements the way pg_query does
+//! (string literals, quoted identifiers, dollar-quoted bodies, and comments
+//! are skipped), finds the statement a finding points at by its start line,
+//! and inserts the clause after the keyword. Nothing else in the file
+//! changes, so line numbers stay the same.
+//!
+//! Only `.sql` files are edited. Findings reported against Liquibase XML,
+//! YAML, or JSON changelogs are left alone, as are acknowledged findings,
+//! unnamed `CREATE INDEX` (which cannot take `IF NOT EXISTS`), and
+//! statements whose keywords do not match the expected shape.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::input::encoding;
+use crate::parser::lex::{dollar_tag, is_ident_byte, skip_block_comment, skip_string};
+use crate::rules::{Finding, RuleId};
+
+/// Rules whose findings `--fix` can repair.
+pub const FIXABLE_RULES: [RuleId; 2] = [RuleId::Pgm401, RuleId::Pgm402];
+
+/// Lines of unchanged context around each hunk of `FileFix::diff`.
+const DIFF_CONTEXT: usize = 3;
+
+#[derive(Debug, Error)]
+pub enum FixError {
+    #[error("Failed to read {path}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Failed to write {path}: {source}")]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// The fixed text of one migration file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileFix {
+    pub path: PathBuf,
+    pub original: String,
+    pub fixed: String,
+    /// Indices into the findings passed to [`plan`] that this fix repairs.
+    pub findings: Vec<usize>,
+    /// The file is not valid UTF-8 and was read as Latin-1, like the loader
+    /// reads it; it is written back as Latin-1.
+    pub latin1: bool,
+}
+
+impl FileFix {
+    /// Overwrite the file with the fixed text, in the file's own encoding.
+    pub fn write(&self) -> Result<(), FixError> {
+        let bytes = if self.latin1 {
+            encoding::encode_latin1(&self.fixed).ok_or_else(|| FixError::Write {
+                path: self.path.clone(),
+                source: std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "fixed text is not representable in Latin-1",
+                ),
+            })?
+        } else {
+            self.fixed.clone().into_bytes()
+        };
+        std::fs::write(&self.path, bytes).map_err(|source| FixError::Write {
+            path: self.path.clone(),
+            source,
+        })
+    }
+
+    /// The change as a unified diff against `a/<path>` and `b/<path>`.
+    pub fn diff(&self) -> String {
+        unified_diff(&self.path, &self.original, &self.fixed)
+    }
+}
+
+/// Work out the fixes for `findings`, reading each affected file once.
+///
+/// Files where no finding could be fixed are not returned.
+pub fn plan(findings: &[Finding]) -> Result<Vec<FileFix>, FixError> {
+    let mut by_file: BTreeMap<&Path, Vec<usize>> = BTreeMap::new();
+    for (idx, finding) in findings.iter().enumerate() {
+        if FIXABLE_RULES.contains(&finding.rule_id)
+            && finding.acknowledged.is_none()
+            && is_sql_file(&finding.file)
+        {
+            by_file.entry(&finding.file).or_default().push(idx);
+        }
+    }
+
+    let mut fixes = Vec::new();
+    for (path, indices) in by_file {
+        let bytes = std::fs::read(path).map_err(|source| FixError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        // Non-UTF-8 files are decoded as Latin-1, as the loader does. The
+        // inserted clauses are ASCII, so they round-trip.
+        let (original, latin1) = match String::from_utf8(bytes) {
+            Ok(original) => (original, false),
+            Err(e) => (encoding::decode_latin1(e.as_bytes()), true),
+        };
+        let targets: Vec<(RuleId, usize)> = indices
+            .iter()
+            .map(|&i| (findings[i].rule_id, findings[i].start_line))
+            .collect();
+        let (fixed, applied) = fix_source(&original, &targets);
+        let fixed_findings: Vec<usize> = indices
+            .into_iter()
+            .zip(applied)
+            .filter_map(|(idx, applied)| applied.then_some(idx))
+            .collect();
+        if !fixed_findings.is_empty() {
+            fixes.push(FileFix {
+                path: path.to_path_buf(),
+                original,
+                fixed,
+                findings: fixed_findings,
+                latin1,
+            });
+        }
+    }
+    Ok(fixes)
+}
+
+/// Apply the fix for each `(rule, start line)` target to `source`.
+///
+/// Returns the fixed text and, per target, whether it was fixed. A target
+/// is fixed at most once, in the first statement starting on its line that
+/// the rule's fix applies to.
+pub fn fix_source(source: &str, targets: &[(RuleId, usize)]) -> (String, Vec<bool>) {
+    let statements = split_statements(source);
+    let mut used = vec![false; statements.len()];
+    let mut inserts: Vec<(usize, String)> = Vec::new();
+    let mut applied = Vec::with_capacity(targets.len());
+
+    for &(rule, line) in targets {
+        let found = statements.iter().enumerate().find_map(|(i, stmt)| {
+            if used[i] || stmt.start_line != line {
+                return None;
+            }
+            insertion(rule, source, &stmt.tokens).map(|insert| (i, insert))
+        });
+        match found {
+            Some((i, insert)) => {
+                used[i] = true;
+                inserts.push(insert);
+                applied.push(true);
+            }
+            None => applied.push(false),
+        }
+    }
+
+    let mut fixed = source.to_string();
+    inserts.sort_by_key(|insert| std::cmp::Reverse(insert.0));
+    for (offset, text) in inserts {
+        fixed.insert_str(offset, &text);
+    }
+    (fixed, applied)
+}
+
+/// Where to insert the clause that fixes `rule` in a statement, and the
+/// text to insert, or `None` when the statement does not have the shape
+/// the fix expects.
+fn insertion(rule: RuleId, source: &str, tokens: &[Token]) -> Option<(usize, String)> {
+    let word = |i: usize| {
+        tokens
+            .get(i)
+            .filter(|t| t.kind == TokenKind::Word)
+            .map(|t| &source[t.start..t.end])
+    };
+    let is = |i: usize, keyword: &str| word(i).is_some_and(|w| w.eq_ignore_ascii_case(keyword));
+
+    let (after, clause) = match rule {
+        RuleId::Pgm401 if is(0, "DROP") && is(1, "TABLE") => (1, "IF EXISTS"),
+        RuleId::Pgm401 if is(0, "DROP") && is(1, "INDEX") => {
+            (if is(2, "CONCURRENTLY") { 2 } else { 1 }, "IF EXISTS")
+        }
+        RuleId::Pgm402 if is(0, "CREATE") => {
+            let mut i = 1;
+            if is(i, "EXTENSION") {
+                (i, "IF NOT EXISTS")
+            } else if is(i, "UNIQUE") || is(i, "INDEX") {
+                if is(i, "UNIQUE") {
+                    i += 1;
+                }
+                if !is(i, "INDEX") {
+                    return None;
+                }
+                if is(i + 1, "CONCURRENTLY") {
+                    i += 1;
+                }
+                // IF NOT EXISTS needs an index name.
+                if is(i + 1, "ON") {
+                    return None;
+                }
+                (i, "IF NOT EXISTS")
+            } else {
+                if is(i, "GLOBAL") || is(i, "LOCAL") {
+                    i += 1;
+                }
+                if is(i, "TEMP") || is(i, "TEMPORARY") || is(i, "UNLOGGED") {
+                    i += 1;
+                }
+                if !is(i, "TABLE") {
+                    return None;
+                }
+                (i, "IF NOT EXISTS")
+            }
+        }
+        _ => return None,
+    };
+
+    // The clause must be missing and something must follow the keyword.
+    if tokens.get(after + 1).is_none() || is(after + 1, "IF") {
+        return None;
+    }
+    let keyword = word(after)?;
+    let clause = if keyword.bytes().all(|b| b.is_ascii_lowercase()) {
+        clause.to_ascii_lowercase()
+    } else {
+        clause.to_string()
+    };
+    Some((tokens[after].end, format!(" {clause}")))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+    /// Keyword or unquoted identifier.
+    Word,
+    /// Anything else: quoted identifiers, literals, operators.
+    Other,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token {
+    kind: TokenKind,
+    start: usize,
+    end: usize,
+}
+
+/// A `;`-terminated statement of the source.
+#[derive(Debug)]
+struct Statement {
+    /// 1-based line of the statement's first byte that is not whitespace
+    /// or a `--` comment, matching the parser's `SourceSpan::start_line`.
+    start_line: usize,
+    tokens: Vec<Token>,
+}
+
+/// Split `source` into statements. psql meta-command lines are skipped,
+/// as the loader blanks them before parsing.
+fn split_statements(source: &str) -> Vec<Statement> {
+    let bytes = source.as_bytes();
+    let mut statements = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut line_start = 0;
+    let mut i = 0;
+
+    let mut push = |start: &mut Option<usize>, tokens: &mut Vec<Token>| {
+        if let Some(start_line) = start.take() {
+            statements.push(Statement {
+                start_line,
+                tokens: std::mem::take(tokens),
+            });
+        }
+    };
+
+    while i < bytes.len() {
+        let token_start = i;
+        let kind = match bytes[i] {
+            b'\n' => {
+                i += 1;
+                line += 1;
+                line_start = i;
+                continue;
+            }
+            b' ' | b'\t' | b'\r' | b'\x0c' => {
+                i += 1;
+                continue;
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = source[i..].find('\n').map_or(bytes.len(), |n| i + n);
+                continue;
+            }
+            b'\\' if source[line_start..i].trim().is_empty() => {
+                i = source[i..].find('\n').map_or(bytes.len(), |n| i + n);
+                continue;
+            }
+            b';' => {
+                push(&mut start, &mut tokens);
+                i += 1;
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                start.get_or_insert(line);
+                i = skip_block_comment(bytes, i + 2);
+                line += source[token_start..i].matches('\n').count();
+                if let Some(n) = source[token_start..i].rfind('\n') {
+                    line_start = token_start + n + 1;
+                }
+                continue;
+            }
+            b'\'' => {
+                let backslash_escapes = i > 0 && matches!(bytes[i - 1], b'E' | b'e');
+                i = skip_string(bytes, i + 1, backslash_escapes);
+                TokenKind::Other
+            }
+            b'"' => {
+                i = source[i + 1..].find('"').map_or(bytes.len(), |n| i + n + 2);
+                TokenKind::Other
+            }
+            b'$' => match dollar_tag(&source[i..]) {
+                Some(tag) => {
+                    let body = i + tag.len();
+                    i = source[body..]
+                        .find(tag)
+                        .map_or(bytes.len(), |n| body + n + tag.len());
+                    TokenKind::Other
+                }
+                None => {
+                    i += word_len(bytes, i);
+                    TokenKind::Word
+                }
+            },
+            b if is_ident_byte(b) => {
+                i += word_len(bytes, i);
+                TokenKind::Word
+            }
+            _ => {
+                // `i` only ever advances by whole tokens, so it sits on a
+                // char boundary and `source[i..]` is not empty.
+                let Some(ch) = source[i..].chars().next() else {
+                    break;
+                };
+                i += ch.len_utf8();
+                if ch == '\u{feff}' {
+                    continue;
+                }
+                TokenKind::Other
+            }
+        };
+        start.get_or_insert(line);
+        let newlines = source[token_start..i].matches('\n').count();
+        if newlines > 0 {
+            line += newlines;
+            line_start = token_start + source[token_start..i].rfind('\n').unwrap_or(0) + 1;
+        }
+        tokens.push(Token {
+            kind,
+            start: token_start,
+            end: i,
+        });
+    }
+    push(&mut start, &mut tokens);
+    statements
+}
+
+fn is_sql_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("sql"))
+}
+
+/// Length of the run of word bytes starting at `i`.
+fn word_len(bytes: &[u8], i: usize) -> usize {
+    bytes[i..].iter().take_while(|&&b| is_ident_byte(b)).count()
+}
+
+/// A unified diff of two texts with the same number of lines, which is
+/// all the fixer produces: clauses are inserted within a line.
+fn unified_diff(path: &Path, original: &str, fixed: &str) -> String {
+    let old: Vec<&str> = original.lines().collect();
+    let new: Vec<&str> = fixed.lines().collect();
+    let changed: Vec<usize> = (0..old.len().max(new.len()))
+        .filter(|&i| old.get(i) != new.get(i))
+        .collect();
+    if changed.is_empty() {
+        return String::new();
+    }
+
+    let name = path.to_string_lossy().replace('\\', "/");
+    let mut out = format!("--- a/{name}\n+++ b/{name}\n");
+
+    // Group changed lines whose context would overlap into one hunk.
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &i in &changed {
+        match hunks.last_mut() {
+            Some((_, last)) if i <= *last + 2 * DIFF_CONTEXT + 1 => *last = i,
+            _ => hunks.push((i, i)),
+        }
+    }
+
+    for (first, last) in hunks {
+        let start = first.saturating_sub(DIFF_CONTEXT);
+        let end = (last + DIFF_CONTEXT + 1).min(old.len());
+        let len = end - start;
+        out.push_str(&format!(
+            "@@ -{},{len} +{},{len} @@\n",
+            start + 1,
+            start + 1
+        ));
+        let mut i = start;
+        while i < end {
+            if old[i] == new[i] {
+                out.push_str(&format!(" {}\n", old[i]));
+                i += 1;
+                continue;
+            }
+            let run_end = (i..end).find(|&j| old[j] == new[j]).unwrap_or(end);
+            for line in &old[i..run_end] {
+                out.push_str(&format!("-{line}\n"));
+            }
+            for line in &new[i..run_end] {
+                out.push_str(&format!("+{line}\n"));
+            }
+            i = run_end;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fix(source: &str, targets: &[(RuleId, usize)]) -> String {
+        fix_source(source, targets).0
+    }
+
+    #[test]
+    fn test_drop_table_and_index_get_if_exists() {
+        let sql = "DROP TABLE orders;\nDROP INDEX CONCURRENTLY idx_orders_status;\n";
+        assert_eq!(
+            fix(sql, &[(RuleId::Pgm401, 1), (RuleId::Pgm401, 2)]),
+            "DROP TABLE IF EXISTS orders;\n\
+             DROP INDEX CONCURRENTLY IF EXISTS idx_orders_status;\n"
+        );
+    }
+
+    #[test]
+    fn test_create_statements_get_if_not_exists() {
+        let sql = "CREATE UNLOGGED TABLE orders (id bigint);\n\
+                   CREATE UNIQUE INDEX CONCURRENTLY idx_orders_id ON orders (id);\n\
+                   CREATE EXTENSION pgcrypto;\n";
+        assert_eq!(
+            fix(
+                sql,
+                &[
+                    (RuleId::Pgm402, 1),
+                    (RuleId::Pgm402, 2),
+                    (RuleId::Pgm402, 3)
+                ]
+            ),
+            "CREATE UNLOGGED TABLE IF NOT EXISTS orders (id bigint);\n\
+             CREATE UNIQUE INDEX CONCURRENTLY IF NOT EXISTS idx_orders_id ON orders (id);\n\
+             CREATE EXTENSION IF NOT EXISTS pgcrypto;\n"
+        );
+    }
+
+    #[test]
+    fn test_clause_follows_keyword_case() {
+        assert_eq!(
+            fix("drop table orders;", &[(RuleId::Pgm401, 1)]),
+            "drop table if exists orders;"
+        );
+    }
+
+    #[test]
+    fn test_unnamed_index_is_not_fixed() {
+        let (fixed, applied) =
+            fix_source("CREATE INDEX ON orders (status);", &[(RuleId::Pgm402, 1)]);
+        assert_eq!(fixed, "CREATE INDEX ON orders (status);");
+        assert_eq!(applied, vec![false]);
+    }
+
+    #[test]
+    fn test_statement_found_by_line_past_strings_and_comments() {
+        let sql = "-- DROP TABLE decoy;\n\
+                   INSERT INTO notes VALUES ('DROP TABLE x;');\n\
+                   CREATE FUNCTION f() RETURNS void AS $$ BEGIN DROP TABLE y; END $$ LANGUAGE plpgsql;\n\
+                   /* cleanup */ DROP TABLE orders;\n";
+        let (fixed, applied) = fix_source(sql, &[(RuleId::Pgm401, 4)]);
+        assert_eq!(applied, vec![true]);
+        assert!(fixed.ends_with("/* cleanup */ DROP TABLE IF EXISTS orders;\n"));
+        assert!(fixed.contains("BEGIN DROP TABLE y; END"));
+    }
+
+    #[test]
+    fn test_two_statements_on_one_line() {
+        assert_eq!(
+            fix(
+                "DROP TABLE a; DROP TABLE b;",
+                &[(RuleId::Pgm401, 1), (RuleId::Pgm401, 1)]
+            ),
+            "DROP TABLE IF EXISTS a; DROP TABLE IF EXISTS b;"
+        );
+    }
+
+    #[test]
+    fn test_rule_must_match_statement() {
+        let (fixed, applied) = fix_source("DROP TABLE orders;", &[(RuleId::Pgm402, 1)]);
+        assert_eq!(fixed, "DROP TABLE orders;");
+        assert_eq!(applied, vec![false]);
+    }
+
+    #[test]
+    fn test_diff_shows_changed_lines_with_context() {
+        let fix = FileFix {
+            path: PathBuf::from("migrations/V2__drop.sql"),
+            original: "-- cleanup\nDROP TABLE orders;\n".to_string(),
+            fixed: "-- cleanup\nDROP TABLE IF EXISTS orders;\n".to_string(),
+            findings: vec![0],
+            latin1: false,
+        };
+        assert_eq!(
+            fix.diff(),
+            "--- a/migrations/V2__drop.sql\n\
+             +++ b/migrations/V2__drop.sql\n\
+             @@ -1,2 +1,2 @@\n \
+             -- cleanup\n\
+             -DROP TABLE orders;\n\
+             +DROP TABLE IF EXISTS orders;\n"
+        );
+    }
+
+    #[test]
+    fn test_latin1_file_written_back_as_latin1() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let path = dir.path().join("V2__drop.sql");
+        std::fs::write(&path, b"-- r\xE9sum\xE9\nDROP TABLE orders;\n").expect("write");
+        let span = crate::parser::ir::SourceSpan {
+            start_line: 2,
+            end_line: 2,
+            start_offset: 0,
+            end_offset: 0,
+        };
+        let finding = Finding::new(
+            RuleId::Pgm401,
+            crate::rules::Severity::Minor,
+            String::new(),
+            &path,
+            &span,
+        );
+
+        let fixes = plan(&[finding]).expect("plan failed");
+        assert_eq!(fixes.len(), 1);
+        assert!(fixes[0].latin1);
+        fixes[0].write().expect("write failed");
+        assert_eq!(
+            std::fs::read(&path).expect("read"),
+            b"-- r\xE9sum\xE9\nDROP TABLE IF EXISTS orders;\n"
+        );
+    }
+}
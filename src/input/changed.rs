@@ -0,0 +1,307 @@
+//! Matching `--changed-files` paths to migration units
+//!
+//! CI passes changed paths in whatever form its diff produced (relative to
+//! the repository root, relative to the working directory, or absolute),
+//! while each migration unit carries the path the loader found it under.
+//! A unit counts as changed when, for some provided path:
+//!
+//! 1. the canonicalized paths are equal,
+//! 2. the provided path equals the unit's path as written, or
+//! 3. one path ends with the other, compared by components, and the shorter
+//!    one has a directory component, so a bare `V001.sql` never matches
+//!    across directories.
+//!
+//! Provided paths are canonicalized where they exist on disk. Paths are
+//! compared in a normalized form that does not depend on the host: `\` and
+//! `/` are both separators, the verbatim prefixes `canonicalize` adds on
+//! Windows (`\\?\C:\`, `\\?\UNC\server\share`) are removed, and drive
+//! letters compare case-insensitively. Every match is recorded so
+//! `--explain-changed-files` can show which provided path selected which
+//! unit and why, and which paths selected nothing.
+
+use std::path::{Path, PathBuf};
+
+/// Why a unit matched a provided path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchReason {
+    /// Both paths canonicalize to the same file.
+    SameFile,
+    /// The unit's path is exactly the provided path.
+    SamePath,
+    /// The provided path ends with the unit's path.
+    UnitPathIsSuffix,
+    /// The unit's path ends with the provided path.
+    ProvidedPathIsSuffix,
+}
+
+impl MatchReason {
+    /// Human-readable explanation for `--explain-changed-files`.
+    pub fn describe(self) -> &'static str {
+        match self {
+            MatchReason::SameFile => "same file after canonicalizing",
+            MatchReason::SamePath => "identical path",
+            MatchReason::UnitPathIsSuffix => "provided path ends with the unit's path",
+            MatchReason::ProvidedPathIsSuffix => "unit's path ends with the provided path",
+        }
+    }
+}
+
+/// A migration unit selected by a provided path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnitMatch {
+    pub unit_id: String,
+    pub source_file: PathBuf,
+    pub reason: MatchReason,
+}
+
+/// One path from `--changed-files` / `--changed-files-from`.
+#[derive(Debug, Clone)]
+pub struct ChangedFile {
+    /// The path as provided.
+    pub path: PathBuf,
+    /// The canonical path, if the provided path exists on disk.
+    pub canonical: Option<PathBuf>,
+    /// Units this path selected, in replay order.
+    pub matches: Vec<UnitMatch>,
+    /// Normalized canonical path, or the provided path when not on disk.
+    key: Vec<String>,
+}
+
+impl ChangedFile {
+    fn reason_for(
+        &self,
+        source_file: &[String],
+        canonical_source: &[String],
+    ) -> Option<MatchReason> {
+        let key = self.key.as_slice();
+        if self.canonical.is_some() && key == canonical_source {
+            Some(MatchReason::SameFile)
+        } else if key == source_file {
+            Some(MatchReason::SamePath)
+        } else if key.ends_with(source_file) && source_file.len() > 1 {
+            Some(MatchReason::UnitPathIsSuffix)
+        } else if source_file.ends_with(key) && key.len() > 1 {
+            Some(MatchReason::ProvidedPathIsSuffix)
+        } else {
+            None
+        }
+    }
+}
+
+/// Split `path` into comparable components, the same way on every host.
+///
+/// A leading root is kept as its own component (`/`, or `//` for a UNC
+/// share) so absolute and relative paths stay distinct, and a drive letter
+/// is upper-cased. `.` and empty components are dropped.
+fn normalize(path: &Path) -> Vec<String> {
+    let raw = path.to_string_lossy().replace('\\', "/");
+    let (root, rest) = if let Some(rest) = raw.strip_prefix("//?/UNC/") {
+        (Some("//"), rest)
+    } else if let Some(rest) = raw.strip_prefix("//?/") {
+        (None, rest)
+    } else if let Some(rest) = raw.strip_prefix("//") {
+        (Some("//"), rest)
+    } else if let Some(rest) = raw.strip_prefix('/') {
+        (Some("/"), rest)
+    } else {
+        (None, raw.as_str())
+    };
+
+    let mut components: Vec<String> = root.map(str::to_string).into_iter().collect();
+    for (i, part) in rest
+        .split('/')
+        .filter(|c| !c.is_empty() && *c != ".")
+        .enumerate()
+    {
+        let is_drive = i == 0
+            && root.is_none()
+            && part.len() == 2
+            && part.ends_with(':')
+            && part.as_bytes()[0].is_ascii_alphabetic();
+        components.push(if is_drive {
+            part.to_ascii_uppercase()
+        } else {
+            part.to_string()
+        });
+    }
+    components
+}
+
+/// The provided changed paths and the units each one matched so far.
+#[derive(Debug, Clone, Default)]
+pub struct ChangedFiles {
+    files: Vec<ChangedFile>,
+}
+
+impl ChangedFiles {
+    /// Canonicalize `paths` where they exist on disk. Repeated paths are
+    /// kept once.
+    pub fn new(paths: &[PathBuf]) -> Self {
+        let mut files: Vec<ChangedFile> = Vec::with_capacity(paths.len());
+        for path in paths {
+            if files.iter().any(|f| f.path == *path) {
+                continue;
+            }
+            let canonical = std::fs::canonicalize(path).ok();
+            let key = normalize(canonical.as_deref().unwrap_or(path));
+            files.push(ChangedFile {
+                path: path.clone(),
+                canonical,
+                matches: Vec::new(),
+                key,
+            });
+        }
+        Self { files }
+    }
+
+    /// Whether the unit is changed, recording a match for every provided
+    /// path that selects it.
+    pub fn match_unit(&mut self, unit_id: &str, source_file: &Path) -> bool {
+        let canonical_source = normalize(
+            &std::fs::canonicalize(source_file).unwrap_or_else(|_| source_file.to_path_buf()),
+        );
+        let source = normalize(source_file);
+        let mut matched = false;
+        for file in &mut self.files {
+            if let Some(reason) = file.reason_for(&source, &canonical_source) {
+                file.matches.push(UnitMatch {
+                    unit_id: unit_id.to_string(),
+                    source_file: source_file.to_path_buf(),
+                    reason,
+                });
+                matched = true;
+            }
+        }
+        matched
+    }
+
+    /// All provided paths, in the order given.
+    pub fn files(&self) -> &[ChangedFile] {
+        &self.files
+    }
+
+    /// Provided paths that matched no unit.
+    pub fn unmatched(&self) -> impl Iterator<Item = &ChangedFile> {
+        self.files.iter().filter(|f| f.matches.is_empty())
+    }
+
+    /// Report for `--explain-changed-files`: each provided path, how it was
+    /// compared, and the units it selected.
+    pub fn explain(&self) -> String {
+        let matched = self.files.iter().filter(|f| !f.matches.is_empty()).count();
+        let mut out = format!(
+            "Changed-file matching: {} path(s) provided, {} matched at least one unit\n",
+            self.files.len(),
+            matched
+        );
+        for file in &self.files {
+            out.push_str(&format!("\n  {}\n", file.path.display()));
+            match &file.canonical {
+                Some(canonical) => {
+                    out.push_str(&format!("    compared as {}\n", canonical.display()));
+                }
+                None => out.push_str("    not found on disk; compared as written\n"),
+            }
+            if file.matches.is_empty() {
+                out.push_str("    matched no units\n");
+            }
+            for m in &file.matches {
+                out.push_str(&format!(
+                    "    -> {} ({}): {}\n",
+                    m.unit_id,
+                    m.source_file.display(),
+                    m.reason.describe()
+                ));
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changed(paths: &[&str]) -> ChangedFiles {
+        ChangedFiles::new(&paths.iter().map(PathBuf::from).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_same_path_and_suffix_matches() {
+        let mut files = changed(&[
+            "no/such/dir/db/migrations/V002__b.sql",
+            "migrations/V003__c.sql",
+        ]);
+        assert!(files.match_unit("V002__b.sql", Path::new("db/migrations/V002__b.sql")));
+        assert!(files.match_unit("V003__c.sql", Path::new("/repo/db/migrations/V003__c.sql")));
+        assert!(!files.match_unit("V004__d.sql", Path::new("db/migrations/V004__d.sql")));
+
+        let reasons: Vec<_> = files.files().iter().map(|f| f.matches[0].reason).collect();
+        assert_eq!(
+            reasons,
+            [
+                MatchReason::UnitPathIsSuffix,
+                MatchReason::ProvidedPathIsSuffix
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bare_file_name_does_not_match_across_directories() {
+        let mut files = changed(&["V001__a.sql"]);
+        assert!(!files.match_unit("V001__a.sql", Path::new("db/other/V001__a.sql")));
+        assert!(files.match_unit("V001__a.sql", Path::new("V001__a.sql")));
+        assert_eq!(files.files()[0].matches[0].reason, MatchReason::SamePath);
+    }
+
+    #[test]
+    fn test_windows_separators_drive_letters_and_verbatim_prefixes() {
+        assert_eq!(
+            normalize(Path::new(r"\\?\c:\agent\_work\1\s\db\V001__a.sql")),
+            normalize(Path::new("C:/agent/_work/1/s/db/V001__a.sql"))
+        );
+        assert_eq!(
+            normalize(Path::new(r"\\?\UNC\build01\src\db\V001__a.sql")),
+            normalize(Path::new(r"\\build01\src\db\V001__a.sql"))
+        );
+        assert_ne!(
+            normalize(Path::new("/db/V001__a.sql")),
+            normalize(Path::new("db/V001__a.sql"))
+        );
+
+        let mut files = changed(&[
+            r"db\migrations\V001__a.sql",
+            r"c:\repo\db\migrations\V002__b.sql",
+        ]);
+        assert!(files.match_unit(
+            "V001__a.sql",
+            Path::new(r"\\?\C:\repo\db\migrations\V001__a.sql")
+        ));
+        assert!(files.match_unit(
+            "V002__b.sql",
+            Path::new("C:/repo/db/migrations/V002__b.sql")
+        ));
+        assert_eq!(
+            files.files()[0].matches[0].reason,
+            MatchReason::ProvidedPathIsSuffix
+        );
+        assert_eq!(files.files()[1].matches[0].reason, MatchReason::SamePath);
+    }
+
+    #[test]
+    fn test_unmatched_paths_and_explain() {
+        let mut files = changed(&["db/migrations/V001__a.sql", "db/migrations/V009__typo.sql"]);
+        files.match_unit("V001__a.sql", Path::new("db/migrations/V001__a.sql"));
+
+        let unmatched: Vec<_> = files.unmatched().map(|f| f.path.clone()).collect();
+        assert_eq!(unmatched, [PathBuf::from("db/migrations/V009__typo.sql")]);
+
+        let report = files.explain();
+        assert!(report.contains("2 path(s) provided, 1 matched"), "{report}");
+        assert!(
+            report.contains("V009__typo.sql\n    not found on disk"),
+            "{report}"
+        );
+        assert!(report.contains("matched no units"), "{report}");
+    }
+}
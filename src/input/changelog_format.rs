@@ -0,0 +1,737 @@
+//! Liquibase changelog serializations
+//!
+//! Liquibase reads changelogs written in XML, YAML (`databaseChangeLog:`
+//! documents), JSON, or formatted SQL (`--changeset author:id` comments).
+//! Both loading strategies hand the changelog to Liquibase, so
+//! `createTable`, `addColumn`, `createIndex`, and every other change type
+//! become the same SQL whatever the serialization. What the
+//! loaders read themselves is the changelog text around each changeset: the
+//! line it starts on, for suppressions and finding locations, and the
+//! attributes `update-sql` output does not carry, and the files it includes,
+//! to hold them to `include_root` before Liquibase reads them. This module
+//! finds these in YAML, JSON, and formatted SQL changelogs; XML is handled
+//! by [`changeset_line`](crate::suppress::changeset_line) and the bridge
+//! loader.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use yaml_rust2::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust2::scanner::Marker;
+
+use crate::input::ChangesetMeta;
+
+/// Serialization of a changelog file, from its extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangelogFormat {
+    Xml,
+    Yaml,
+    Json,
+    FormattedSql,
+}
+
+impl ChangelogFormat {
+    /// `.yaml` / `.yml`, `.json`, and `.sql` files; anything else is read
+    /// as XML.
+    pub fn from_path(path: &Path) -> Self {
+        match path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase)
+            .as_deref()
+        {
+            Some("yaml" | "yml") => Self::Yaml,
+            Some("json") => Self::Json,
+            Some("sql") => Self::FormattedSql,
+            _ => Self::Xml,
+        }
+    }
+}
+
+/// A changeset found in a YAML, JSON, or formatted SQL changelog.
+struct Changeset {
+    /// 1-based line of the `changeSet` key or `--changeset` comment.
+    line: usize,
+    /// Scalar attributes in source order, values unquoted.
+    attributes: Vec<(String, String)>,
+}
+
+impl Changeset {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn meta(&self) -> ChangesetMeta {
+        let list = |key: &str| -> Vec<String> {
+            let mut items: Vec<String> = self
+                .get(key)
+                .map(|v| {
+                    v.split(',')
+                        .map(|item| item.trim().to_string())
+                        .filter(|item| !item.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default();
+            items.sort();
+            items
+        };
+        let contexts = match list("contextFilter") {
+            contexts if contexts.is_empty() => list("context"),
+            contexts => contexts,
+        };
+        let non_empty = |key: &str| self.get(key).filter(|v| !v.is_empty()).map(String::from);
+        ChangesetMeta {
+            author: non_empty("author"),
+            comments: non_empty("comment"),
+            contexts,
+            labels: list("labels"),
+        }
+    }
+}
+
+/// 1-based line of the changeset `id` by `author` in a YAML, JSON, or
+/// formatted SQL changelog. Without an author, the first changeset with the
+/// id is taken.
+pub fn changeset_line(
+    format: ChangelogFormat,
+    source: &str,
+    id: &str,
+    author: Option<&str>,
+) -> Option<usize> {
+    find_changeset(format, source, id, author).map(|cs| cs.line)
+}
+
+/// Attributes of the changeset `id` by `author` in a YAML, JSON, or
+/// formatted SQL changelog: `author`, `comment`, `context` /
+/// `contextFilter`, and `labels`.
+pub fn changeset_attributes(
+    format: ChangelogFormat,
+    source: &str,
+    id: &str,
+    author: Option<&str>,
+) -> Option<ChangesetMeta> {
+    find_changeset(format, source, id, author).map(|cs| cs.meta())
+}
+
+/// Liquibase identifies a changeset by its id, author, and file; the file
+/// is `source`.
+fn find_changeset(
+    format: ChangelogFormat,
+    source: &str,
+    id: &str,
+    author: Option<&str>,
+) -> Option<Changeset> {
+    changesets(format, source).into_iter().find(|cs| {
+        cs.get("id") == Some(id) && author.is_none_or(|author| cs.get("author") == Some(author))
+    })
+}
+
+fn changesets(format: ChangelogFormat, source: &str) -> Vec<Changeset> {
+    match format {
+        ChangelogFormat::Yaml | ChangelogFormat::Json => document_changesets(source),
+        ChangelogFormat::FormattedSql => formatted_sql_changesets(source),
+        ChangelogFormat::Xml => Vec::new(),
+    }
+}
+
+/// YAML and JSON: each `changeSet` entry of `databaseChangeLog`, in any
+/// YAML style (block, flow, multi-line scalars, anchors and aliases). JSON
+/// is read as the YAML subset it is, so both get the line of each
+/// `changeSet` key from the parser.
+fn document_changesets(source: &str) -> Vec<Changeset> {
+    let Some(document) = parse_document(source) else {
+        return Vec::new();
+    };
+    document
+        .get("databaseChangeLog")
+        .map(Node::items)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|entry| entry.entry("changeSet"))
+        .map(|(line, changeset)| Changeset {
+            line,
+            attributes: changeset
+                .entries()
+                .iter()
+                .filter_map(|(key, _, value)| Some((key.clone(), value.scalar()?.to_string())))
+                .collect(),
+        })
+        .collect()
+}
+
+/// What a changelog [`Reference`] names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceKind {
+    /// Another changelog, from `include`.
+    Changelog,
+    /// A directory of changelogs, from `includeAll`.
+    Directory,
+    /// A SQL file a `sqlFile` change reads.
+    SqlFile,
+}
+
+/// A file or directory a changelog names, as written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    pub kind: ReferenceKind,
+    pub path: String,
+    /// `relativeToChangelogFile`: `path` is relative to the naming
+    /// changelog rather than the root one.
+    pub relative_to_changelog: bool,
+}
+
+impl Reference {
+    /// Build from an element's name and attribute lookup; `None` for
+    /// elements that name no file.
+    pub fn from_attributes<'a>(
+        element: &str,
+        attribute: impl Fn(&str) -> Option<&'a str>,
+    ) -> Option<Self> {
+        let (kind, key) = match element {
+            "include" => (ReferenceKind::Changelog, "file"),
+            "includeAll" => (ReferenceKind::Directory, "path"),
+            "sqlFile" => (ReferenceKind::SqlFile, "path"),
+            _ => return None,
+        };
+        Some(Self {
+            kind,
+            path: attribute(key).filter(|p| !p.is_empty())?.to_string(),
+            relative_to_changelog: attribute("relativeToChangelogFile")
+                .is_some_and(|v| v.eq_ignore_ascii_case("true")),
+        })
+    }
+}
+
+/// The files and directories a YAML, JSON, or formatted SQL changelog
+/// names with `include`, `includeAll`, and `sqlFile`.
+pub fn references(format: ChangelogFormat, source: &str) -> Vec<Reference> {
+    match format {
+        ChangelogFormat::Yaml | ChangelogFormat::Json => {
+            let mut found = Vec::new();
+            if let Some(document) = parse_document(source) {
+                document_references(&document, &mut found);
+            }
+            found
+        }
+        ChangelogFormat::FormattedSql => formatted_sql_references(source),
+        ChangelogFormat::Xml => Vec::new(),
+    }
+}
+
+/// Every `include`, `includeAll`, and `sqlFile` mapping, at any depth:
+/// `sqlFile` sits under a changeset's `changes`.
+fn document_references(node: &Node, found: &mut Vec<Reference>) {
+    for (key, _, value) in node.entries() {
+        let attribute = |name: &str| value.get(name).and_then(Node::scalar);
+        match Reference::from_attributes(key, attribute) {
+            Some(reference) => found.push(reference),
+            None => document_references(value, found),
+        }
+    }
+    for item in node.items() {
+        document_references(item, found);
+    }
+}
+
+/// Formatted SQL: `--include file:path` and `--includeAll path:dir`
+/// comments.
+fn formatted_sql_references(source: &str) -> Vec<Reference> {
+    source
+        .lines()
+        .filter_map(|line| {
+            let comment = line.trim_start().strip_prefix("--")?.trim_start();
+            let mut words = sql_attribute_words(comment).into_iter();
+            let element = words.next()?;
+            let attributes: Vec<(&str, &str)> = words
+                .filter_map(|word| {
+                    let (key, value) = word.split_once(':')?;
+                    Some((key, value.trim_matches('"')))
+                })
+                .collect();
+            Reference::from_attributes(element, |name| {
+                attributes
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| *value)
+            })
+        })
+        .collect()
+}
+
+/// A node of a YAML or JSON document, with the 1-based line it starts on.
+#[derive(Debug, Clone)]
+pub(crate) struct Node {
+    pub line: usize,
+    value: Value,
+}
+
+#[derive(Debug, Clone)]
+enum Value {
+    Scalar(String),
+    Sequence(Vec<Node>),
+    /// Entries in source order: key, the line of the key, and the value.
+    Mapping(Vec<(String, usize, Node)>),
+}
+
+impl Node {
+    /// The scalar text, unquoted; `None` for a collection.
+    pub fn scalar(&self) -> Option<&str> {
+        match &self.value {
+            Value::Scalar(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// The items of a sequence; empty for anything else.
+    pub fn items(&self) -> &[Node] {
+        match &self.value {
+            Value::Sequence(items) => items,
+            _ => &[],
+        }
+    }
+
+    /// The entries of a mapping; empty for anything else.
+    fn entries(&self) -> &[(String, usize, Node)] {
+        match &self.value {
+            Value::Mapping(entries) => entries,
+            _ => &[],
+        }
+    }
+
+    /// The value of `key` in a mapping, with the line of the key.
+    pub fn entry(&self, key: &str) -> Option<(usize, &Node)> {
+        self.entries()
+            .iter()
+            .find(|(k, _, _)| k == key)
+            .map(|(_, line, value)| (*line, value))
+    }
+
+    /// The value of `key` in a mapping.
+    pub fn get(&self, key: &str) -> Option<&Node> {
+        self.entry(key).map(|(_, value)| value)
+    }
+}
+
+/// Parse the first document of a YAML or JSON file; `None` if it is not
+/// well-formed.
+pub(crate) fn parse_document(source: &str) -> Option<Node> {
+    let mut builder = TreeBuilder::default();
+    Parser::new_from_str(source)
+        .load(&mut builder, false)
+        .ok()?;
+    builder.root
+}
+
+/// Builds [`Node`]s from parser events, resolving aliases to a copy of the
+/// anchored node.
+#[derive(Default)]
+struct TreeBuilder {
+    /// Open collections: the node so far, its anchor id, and for a mapping
+    /// the key (with its line) waiting for a value.
+    open: Vec<(Node, usize, Option<(String, usize)>)>,
+    anchors: HashMap<usize, Node>,
+    root: Option<Node>,
+}
+
+impl TreeBuilder {
+    fn open(&mut self, value: Value, anchor: usize, mark: Marker) {
+        let node = Node {
+            line: mark.line(),
+            value,
+        };
+        self.open.push((node, anchor, None));
+    }
+
+    fn close(&mut self) {
+        if let Some((node, anchor, _)) = self.open.pop() {
+            self.add(node, anchor);
+        }
+    }
+
+    /// Attach a finished node to the open collection, or make it the root.
+    fn add(&mut self, node: Node, anchor: usize) {
+        if anchor > 0 {
+            self.anchors.insert(anchor, node.clone());
+        }
+        let Some((parent, _, pending_key)) = self.open.last_mut() else {
+            self.root.get_or_insert(node);
+            return;
+        };
+        match &mut parent.value {
+            Value::Sequence(items) => items.push(node),
+            Value::Mapping(entries) => match pending_key.take() {
+                Some((key, line)) => entries.push((key, line, node)),
+                None => {
+                    let key = node.scalar().unwrap_or_default().to_string();
+                    *pending_key = Some((key, node.line));
+                }
+            },
+            Value::Scalar(_) => {}
+        }
+    }
+}
+
+impl MarkedEventReceiver for TreeBuilder {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        match event {
+            Event::MappingStart(anchor, _) => self.open(Value::Mapping(Vec::new()), anchor, mark),
+            Event::SequenceStart(anchor, _) => self.open(Value::Sequence(Vec::new()), anchor, mark),
+            Event::MappingEnd | Event::SequenceEnd => self.close(),
+            Event::Scalar(text, _, anchor, _) => {
+                let node = Node {
+                    line: mark.line(),
+                    value: Value::Scalar(text),
+                };
+                self.add(node, anchor);
+            }
+            Event::Alias(anchor) => {
+                if let Some(node) = self.anchors.get(&anchor).cloned() {
+                    self.add(node, 0);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Formatted SQL: each `--changeset author:id key:value ...` comment, with
+/// the `--comment:` line that follows it. Values may be double-quoted to
+/// hold spaces (`context:"prod and eu"`).
+fn formatted_sql_changesets(source: &str) -> Vec<Changeset> {
+    let mut found: Vec<Changeset> = Vec::new();
+    for (idx, line) in source.lines().enumerate() {
+        let Some(comment) = line.trim_start().strip_prefix("--").map(str::trim_start) else {
+            continue;
+        };
+        if let Some(text) = comment.strip_prefix("comment:") {
+            let text = text.trim();
+            if let Some(changeset) = found.last_mut()
+                && !text.is_empty()
+                && changeset.get("comment").is_none()
+            {
+                changeset
+                    .attributes
+                    .push(("comment".to_string(), text.to_string()));
+            }
+            continue;
+        }
+        let Some(rest) = comment.strip_prefix("changeset") else {
+            continue;
+        };
+        if !rest.starts_with(char::is_whitespace) {
+            continue;
+        }
+        let mut words = sql_attribute_words(rest).into_iter();
+        let Some((author, id)) = words.next().and_then(|w| {
+            let (author, id) = w.split_once(':')?;
+            Some((author.to_string(), id.trim_matches('"').to_string()))
+        }) else {
+            continue;
+        };
+        let mut attributes = vec![("author".to_string(), author), ("id".to_string(), id)];
+        attributes.extend(words.filter_map(|word| {
+            let (key, value) = word.split_once(':')?;
+            Some((key.to_string(), value.trim_matches('"').to_string()))
+        }));
+        found.push(Changeset {
+            line: idx + 1,
+            attributes,
+        });
+    }
+    found
+}
+
+/// Split a `--changeset` line on whitespace outside double quotes.
+fn sql_attribute_words(line: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = None;
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if let Some(s) = start.take() {
+                    words.push(&line[s..i]);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(i);
+    }
+    if let Some(s) = start {
+        words.push(&line[s..]);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const YAML: &str = "\
+databaseChangeLog:
+  - changeSet:
+      id: 20240315-1
+      author: robert
+      # the orders table
+      labels: billing, core
+      changes:
+        - createTable:
+            tableName: orders
+            columns:
+              - column:
+                  name: id
+                  type: bigint
+  - changeSet:
+      id: \"2\"
+      author: 'anna'
+      context: prod
+      comment: Index on customer # reviewed
+      changes:
+        - createIndex:
+            indexName: idx_orders_customer
+";
+
+    const JSON: &str = r#"{
+  "databaseChangeLog": [
+    {
+      "changeSet": {
+        "id": "20240315-1",
+        "author": "robert",
+        "changes": [{"createTable": {"tableName": "orders"}}]
+      }
+    },
+    {
+      "changeSet": {
+        "id": 2,
+        "author": "anna",
+        "contextFilter": "prod, staging",
+        "comment": "Index on customer"
+      }
+    }
+  ]
+}"#;
+
+    const FORMATTED_SQL: &str = "\
+--liquibase formatted sql
+
+--changeset robert:20240315-1 labels:billing,core
+--comment: Create orders
+CREATE TABLE orders (id bigint PRIMARY KEY);
+
+-- changeset anna:2 context:\"legacy and !prod\" runInTransaction:false
+CREATE INDEX CONCURRENTLY idx_orders_id ON orders (id);
+";
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            ChangelogFormat::from_path(Path::new("db/changelog.YAML")),
+            ChangelogFormat::Yaml
+        );
+        assert_eq!(
+            ChangelogFormat::from_path(Path::new("db/changelog.yml")),
+            ChangelogFormat::Yaml
+        );
+        assert_eq!(
+            ChangelogFormat::from_path(Path::new("db/changelog.json")),
+            ChangelogFormat::Json
+        );
+        assert_eq!(
+            ChangelogFormat::from_path(Path::new("db/changelog.sql")),
+            ChangelogFormat::FormattedSql
+        );
+        assert_eq!(
+            ChangelogFormat::from_path(Path::new("db/changelog.xml")),
+            ChangelogFormat::Xml
+        );
+    }
+
+    #[test]
+    fn test_yaml_changesets() {
+        assert_eq!(
+            changeset_line(ChangelogFormat::Yaml, YAML, "20240315-1", None),
+            Some(2)
+        );
+        assert_eq!(
+            changeset_line(ChangelogFormat::Yaml, YAML, "2", None),
+            Some(14)
+        );
+        assert_eq!(changeset_line(ChangelogFormat::Yaml, YAML, "3", None), None);
+
+        let first =
+            changeset_attributes(ChangelogFormat::Yaml, YAML, "20240315-1", None).expect("found");
+        assert_eq!(first.author.as_deref(), Some("robert"));
+        assert_eq!(first.labels, vec!["billing", "core"]);
+        let second = changeset_attributes(ChangelogFormat::Yaml, YAML, "2", None).expect("found");
+        assert_eq!(second.author.as_deref(), Some("anna"));
+        assert_eq!(second.comments.as_deref(), Some("Index on customer"));
+        assert_eq!(second.contexts, vec!["prod"]);
+    }
+
+    #[test]
+    fn test_json_changesets() {
+        assert_eq!(
+            changeset_line(ChangelogFormat::Json, JSON, "20240315-1", None),
+            Some(4)
+        );
+        assert_eq!(
+            changeset_line(ChangelogFormat::Json, JSON, "2", None),
+            Some(11)
+        );
+
+        let second = changeset_attributes(ChangelogFormat::Json, JSON, "2", None).expect("found");
+        assert_eq!(second.author.as_deref(), Some("anna"));
+        assert_eq!(second.comments.as_deref(), Some("Index on customer"));
+        assert_eq!(second.contexts, vec!["prod", "staging"]);
+        assert!(changeset_attributes(ChangelogFormat::Json, "not json", "2", None).is_none());
+    }
+
+    #[test]
+    fn test_changeset_matched_by_id_and_author() {
+        let yaml = "\
+databaseChangeLog:
+  - changeSet:
+      id: 1
+      author: robert
+  - changeSet:
+      id: 1
+      author: anna
+      comment: Second
+";
+        let format = ChangelogFormat::Yaml;
+        assert_eq!(changeset_line(format, yaml, "1", Some("robert")), Some(2));
+        assert_eq!(changeset_line(format, yaml, "1", Some("anna")), Some(5));
+        assert_eq!(changeset_line(format, yaml, "1", Some("erik")), None);
+        assert_eq!(changeset_line(format, yaml, "1", None), Some(2));
+        let anna = changeset_attributes(format, yaml, "1", Some("anna")).expect("found");
+        assert_eq!(anna.comments.as_deref(), Some("Second"));
+
+        let sql = "--changeset robert:1\nSELECT 1;\n--changeset anna:1\nSELECT 2;\n";
+        let format = ChangelogFormat::FormattedSql;
+        assert_eq!(changeset_line(format, sql, "1", Some("anna")), Some(3));
+    }
+
+    #[test]
+    fn test_yaml_styles() {
+        let yaml = "\
+databaseChangeLog:
+  - changeSet: {id: flow-1, author: robert, labels: \"a, b\"}
+  - changeSet:
+      id: multi
+      author: anna
+      comment: >
+        Folded comment
+        over two lines
+  - changeSet:
+      id: \"quoted: id\"
+      author: anna
+      context: |
+        prod
+";
+        let format = ChangelogFormat::Yaml;
+        assert_eq!(changeset_line(format, yaml, "flow-1", None), Some(2));
+        let flow = changeset_attributes(format, yaml, "flow-1", None).expect("found");
+        assert_eq!(flow.labels, vec!["a", "b"]);
+        assert_eq!(changeset_line(format, yaml, "multi", Some("anna")), Some(3));
+        let multi = changeset_attributes(format, yaml, "multi", None).expect("found");
+        assert_eq!(
+            multi.comments.as_deref(),
+            Some("Folded comment over two lines\n")
+        );
+        assert_eq!(changeset_line(format, yaml, "quoted: id", None), Some(9));
+        let quoted = changeset_attributes(format, yaml, "quoted: id", None).expect("found");
+        assert_eq!(quoted.contexts, vec!["prod"]);
+
+        let aliased = "\
+databaseChangeLog:
+  - changeSet:
+      id: 1
+      author: &who robert
+  - changeSet:
+      id: 2
+      author: *who
+";
+        assert_eq!(
+            changeset_line(format, aliased, "2", Some("robert")),
+            Some(5)
+        );
+    }
+
+    #[test]
+    fn test_references() {
+        let yaml = "\
+databaseChangeLog:
+  - include:
+      file: common/base.yaml
+      relativeToChangelogFile: true
+  - includeAll: {path: /etc/changelogs/}
+  - changeSet:
+      id: 1
+      author: robert
+      changes:
+        - sqlFile:
+            path: ../sql/seed.sql
+";
+        assert_eq!(
+            references(ChangelogFormat::Yaml, yaml),
+            vec![
+                Reference {
+                    kind: ReferenceKind::Changelog,
+                    path: "common/base.yaml".to_string(),
+                    relative_to_changelog: true,
+                },
+                Reference {
+                    kind: ReferenceKind::Directory,
+                    path: "/etc/changelogs/".to_string(),
+                    relative_to_changelog: false,
+                },
+                Reference {
+                    kind: ReferenceKind::SqlFile,
+                    path: "../sql/seed.sql".to_string(),
+                    relative_to_changelog: false,
+                },
+            ]
+        );
+
+        let json = r#"{"databaseChangeLog": [{"include": {"file": "a.json"}}]}"#;
+        assert_eq!(references(ChangelogFormat::Json, json).len(), 1);
+
+        let sql = "\
+--liquibase formatted sql
+--include file:\"../shared/base.sql\" relativeToChangelogFile:true
+--includeAll path:more/
+--changeset robert:1
+SELECT 1;
+";
+        let found = references(ChangelogFormat::FormattedSql, sql);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].path, "../shared/base.sql");
+        assert!(found[0].relative_to_changelog);
+        assert_eq!(found[1].kind, ReferenceKind::Directory);
+    }
+
+    #[test]
+    fn test_formatted_sql_changesets() {
+        let format = ChangelogFormat::FormattedSql;
+        assert_eq!(
+            changeset_line(format, FORMATTED_SQL, "20240315-1", None),
+            Some(3)
+        );
+        assert_eq!(changeset_line(format, FORMATTED_SQL, "2", None), Some(7));
+
+        let first = changeset_attributes(format, FORMATTED_SQL, "20240315-1", None).expect("found");
+        assert_eq!(first.author.as_deref(), Some("robert"));
+        assert_eq!(first.comments.as_deref(), Some("Create orders"));
+        assert_eq!(first.labels, vec!["billing", "core"]);
+        assert!(first.contexts.is_empty());
+        let second = changeset_attributes(format, FORMATTED_SQL, "2", None).expect("found");
+        assert_eq!(second.author.as_deref(), Some("anna"));
+        assert_eq!(second.comments, None);
+        assert_eq!(second.contexts, vec!["legacy and !prod"]);
+    }
+}
@@ -0,0 +1,90 @@
+//! Decoding migration files
+//!
+//! Migration files are expected to be UTF-8. A leading UTF-8 byte order mark
+//! is dropped. A file that is not valid UTF-8 is decoded as Latin-1 (every
+//! byte maps to one character), with a warning, rather than aborting the run:
+//! legacy migrations written by Windows editors are the usual cause, and the
+//! SQL keywords and identifiers the rules look at are ASCII either way.
+
+use std::path::Path;
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Read `path` and decode it, falling back to Latin-1 for non-UTF-8 files.
+pub fn read_source(path: &Path) -> std::io::Result<String> {
+    let bytes = std::fs::read(path)?;
+    let (source, lossy) = decode(bytes);
+    if lossy {
+        eprintln!(
+            "WARNING: {} is not valid UTF-8; decoding it as Latin-1",
+            path.display()
+        );
+    }
+    Ok(source)
+}
+
+/// Decode file contents. Returns the text and whether the Latin-1 fallback
+/// was used.
+fn decode(mut bytes: Vec<u8>) -> (String, bool) {
+    if bytes.starts_with(UTF8_BOM) {
+        bytes.drain(..UTF8_BOM.len());
+    }
+    match String::from_utf8(bytes) {
+        Ok(source) => (source, false),
+        Err(e) => (decode_latin1(e.as_bytes()), true),
+    }
+}
+
+/// Decode Latin-1 bytes: every byte is the character with the same value.
+pub(crate) fn decode_latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|&b| char::from(b)).collect()
+}
+
+/// Encode `text` as Latin-1, or `None` if it has a character above U+00FF.
+pub(crate) fn encode_latin1(text: &str) -> Option<Vec<u8>> {
+    text.chars().map(|c| u8::try_from(c).ok()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_utf8_passes_through() {
+        let (source, lossy) = decode("CREATE TABLE café (id int);".as_bytes().to_vec());
+        assert_eq!(source, "CREATE TABLE café (id int);");
+        assert!(!lossy);
+    }
+
+    #[test]
+    fn test_utf8_bom_stripped() {
+        let (source, lossy) = decode(b"\xEF\xBB\xBFSELECT 1;".to_vec());
+        assert_eq!(source, "SELECT 1;");
+        assert!(!lossy);
+    }
+
+    #[test]
+    fn test_latin1_fallback() {
+        // 'é' in Latin-1 is the single byte 0xE9, which is invalid UTF-8 here.
+        let (source, lossy) = decode(b"-- caf\xE9\nSELECT 1;".to_vec());
+        assert_eq!(source, "-- café\nSELECT 1;");
+        assert!(lossy);
+    }
+
+    #[test]
+    fn test_latin1_round_trip() {
+        let bytes = b"-- r\xE9sum\xE9\nSELECT 1;";
+        let text = decode_latin1(bytes);
+        assert_eq!(encode_latin1(&text).as_deref(), Some(&bytes[..]));
+        assert_eq!(encode_latin1("snowman \u{2603}"), None);
+    }
+
+    #[test]
+    fn test_read_source_latin1_file() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let path = dir.path().join("V001__legacy.sql");
+        std::fs::write(&path, b"COMMENT ON TABLE t IS 'r\xE9sum\xE9';").expect("write");
+        let source = read_source(&path).expect("read");
+        assert_eq!(source, "COMMENT ON TABLE t IS 'résumé';");
+    }
+}
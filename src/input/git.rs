@@ -0,0 +1,170 @@
+//! Changed migration files from git (`--changed-from-git`)
+//!
+//! Instead of computing `--changed-files` in a CI script, the linter can ask
+//! git which files differ from a base ref. The comparison starts at the merge
+//! base of the ref and `HEAD`, like `git diff base...HEAD`, so commits that
+//! landed on the base branch after the branch point do not count. It runs
+//! against the working tree, so uncommitted edits count too, and untracked
+//! files that are not ignored are added.
+//!
+//! Added, copied, modified, and type-changed files are kept. A renamed file
+//! counts under its new path: a migration renamed into place (say, to change
+//! its version) runs again under the new name. Deleted files have no unit to
+//! lint and are dropped. The result is limited to files under the configured
+//! migration paths; a path that is a file (a Liquibase changelog) stands for
+//! its directory, so included changelogs next to or below it are kept.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum GitError {
+    #[error("Failed to execute git: {0}")]
+    Spawn(#[from] std::io::Error),
+
+    #[error("git {command} failed: {message}")]
+    Command { command: String, message: String },
+}
+
+/// One entry of `git diff --name-status -z`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DiffEntry {
+    status: char,
+    /// The path after the change (the new path of a rename or copy).
+    path: String,
+}
+
+/// Files under `migration_paths` that were added or modified since the merge
+/// base of `base_ref` and `HEAD`, as absolute paths, sorted and deduplicated.
+///
+/// `workdir` is any directory inside the repository.
+pub fn changed_from_git(
+    base_ref: &str,
+    workdir: &Path,
+    migration_paths: &[PathBuf],
+) -> Result<Vec<PathBuf>, GitError> {
+    let root = PathBuf::from(git(workdir, &["rev-parse", "--show-toplevel"])?.trim_end());
+    let base = git(&root, &["merge-base", base_ref, "HEAD"]).map_err(|e| match e {
+        GitError::Command { message, .. } => GitError::Command {
+            command: format!("merge-base {base_ref} HEAD"),
+            message: format!(
+                "{message} (is '{base_ref}' fetched? Shallow CI checkouts need enough \
+                 history to reach the merge base)"
+            ),
+        },
+        other => other,
+    })?;
+    let diff = git(
+        &root,
+        &[
+            "diff",
+            "--name-status",
+            "-z",
+            "--find-renames",
+            "--no-ext-diff",
+            base.trim_end(),
+        ],
+    )?;
+    let untracked = git(&root, &["ls-files", "-z", "--others", "--exclude-standard"])?;
+
+    let dirs: Vec<PathBuf> = migration_paths
+        .iter()
+        .map(|p| {
+            let p = if p.is_relative() {
+                workdir.join(p)
+            } else {
+                p.clone()
+            };
+            let p = p.canonicalize().unwrap_or(p);
+            match p.parent() {
+                Some(parent) if p.is_file() => parent.to_path_buf(),
+                _ => p,
+            }
+        })
+        .collect();
+
+    let mut files: Vec<PathBuf> = parse_name_status(&diff)
+        .into_iter()
+        .filter(|e| matches!(e.status, 'A' | 'C' | 'M' | 'R' | 'T'))
+        .map(|e| e.path)
+        .chain(
+            untracked
+                .split('\0')
+                .filter(|p| !p.is_empty())
+                .map(String::from),
+        )
+        .map(|p| root.join(p))
+        .filter(|p| dirs.iter().any(|dir| p.starts_with(dir)))
+        .collect();
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// Run git in `dir` and return its stdout.
+fn git(dir: &Path, args: &[&str]) -> Result<String, GitError> {
+    let output = Command::new("git").arg("-C").arg(dir).args(args).output()?;
+    if !output.status.success() {
+        return Err(GitError::Command {
+            command: args.join(" "),
+            message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Parse `git diff --name-status -z` output. Renames and copies carry a
+/// similarity score and two paths; every other status carries one.
+fn parse_name_status(output: &str) -> Vec<DiffEntry> {
+    let mut fields = output.split('\0').filter(|f| !f.is_empty());
+    let mut entries = Vec::new();
+    while let Some(status) = fields.next() {
+        let Some(code) = status.chars().next() else {
+            continue;
+        };
+        let path = match code {
+            'R' | 'C' => fields.nth(1),
+            _ => fields.next(),
+        };
+        let Some(path) = path else {
+            break;
+        };
+        entries.push(DiffEntry {
+            status: code,
+            path: path.to_string(),
+        });
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_name_status_takes_new_path_of_renames() {
+        let output = "M\0db/V001__init.sql\0R087\0db/V002__old.sql\0db/V003__new.sql\0\
+                      D\0db/V004__gone.sql\0A\0db/V005__add.sql\0";
+        let entries = parse_name_status(output);
+        let pairs: Vec<_> = entries
+            .iter()
+            .map(|e| (e.status, e.path.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ('M', "db/V001__init.sql"),
+                ('R', "db/V003__new.sql"),
+                ('D', "db/V004__gone.sql"),
+                ('A', "db/V005__add.sql"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_name_status_empty() {
+        assert!(parse_name_status("").is_empty());
+    }
+}
@@ -7,9 +7,14 @@
 //! JSON with exact changeset-to-SQL-to-line mapping.
 
 use crate::config::LiquibaseConfig;
-use crate::input::LoadError;
-use crate::input::RawMigrationUnit;
+use crate::input::changelog_format::{self, ChangelogFormat, Reference, ReferenceKind};
+use crate::input::encoding::read_source;
+use crate::input::liquibase_filter::retain_applied;
+use crate::input::psql::replace_batch_separators;
+use crate::input::{ChangesetMeta, LoadError, RawMigrationUnit, is_within_root};
+use crate::suppress::{Suppressions, changeset_line, parse_suppressions};
 use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
@@ -32,6 +37,14 @@ struct BridgeChangeset {
     xml_line: usize,
     #[serde(default = "default_run_in_transaction")]
     run_in_transaction: bool,
+    #[serde(default)]
+    author: Option<String>,
+    #[serde(default)]
+    comments: Option<String>,
+    #[serde(default)]
+    contexts: Vec<String>,
+    #[serde(default)]
+    labels: Vec<String>,
 }
 
 fn default_xml_line() -> usize {
@@ -93,7 +106,9 @@ impl BridgeLoader {
 /// Parse the JSON output from the bridge JAR into `RawMigrationUnit`s.
 ///
 /// The JSON is expected to be an array of changeset objects, each containing
-/// the changeset ID, SQL text, source file, line number, and transaction mode.
+/// the changeset ID, SQL text, source file, line number, and transaction mode,
+/// plus the optional author, comments, contexts, and labels. Batch separator
+/// lines left by an `endDelimiter` (`GO`, `/`) become `;`.
 pub fn parse_bridge_json(json_str: &str) -> Result<Vec<RawMigrationUnit>, LoadError> {
     let changesets: Vec<BridgeChangeset> =
         serde_json::from_str(json_str).map_err(|e| LoadError::BridgeError {
@@ -104,11 +119,18 @@ pub fn parse_bridge_json(json_str: &str) -> Result<Vec<RawMigrationUnit>, LoadEr
         .into_iter()
         .map(|cs| RawMigrationUnit {
             id: cs.changeset_id,
-            sql: cs.sql,
+            sql: replace_batch_separators(&cs.sql),
             source_file: PathBuf::from(cs.xml_file),
             source_line_offset: cs.xml_line,
             run_in_transaction: cs.run_in_transaction,
             is_down: false,
+            suppressions: Suppressions::default(),
+            changeset: ChangesetMeta {
+                author: cs.author.filter(|a| !a.is_empty()),
+                comments: cs.comments.filter(|c| !c.is_empty()),
+                contexts: cs.contexts,
+                labels: cs.labels,
+            },
         })
         .collect();
 
@@ -129,6 +151,344 @@ pub fn resolve_source_paths(units: &mut [RawMigrationUnit], base_dir: &Path) {
     }
 }
 
+/// Fail when `changelog`, or any file it names, lies outside
+/// `include_root`: `<include>`/`<includeAll>` of another changelog or
+/// `<sqlFile>` through `..`, an absolute path, or a symlink. Walks the
+/// includes itself, in every serialization, so Liquibase never reads a file
+/// outside the root. Files that cannot be read are left for Liquibase to
+/// report.
+pub fn check_changelog_includes(
+    changelog: &Path,
+    include_root: Option<&Path>,
+) -> Result<(), LoadError> {
+    let Some(root) = include_root else {
+        return Ok(());
+    };
+    walk_includes(changelog, |path| {
+        if is_within_root(root, path) {
+            Ok(())
+        } else {
+            Err(LoadError::OutsideIncludeRoot {
+                path: path.to_path_buf(),
+                root: root.to_path_buf(),
+            })
+        }
+    })
+    .map(drop)
+}
+
+/// `changelog` and every file it names, directly or through the changelogs
+/// it includes: the inputs Liquibase reads for it.
+pub fn changelog_files(changelog: &Path) -> Vec<PathBuf> {
+    walk_includes(changelog, |_| Ok(())).unwrap_or_default()
+}
+
+/// Walk the include graph of `changelog`, calling `visit` on each file and
+/// directory before it is read. A path without `relativeToChangelogFile` is
+/// resolved against the root changelog's directory, as
+/// [`resolve_source_paths`] does. Returns the files visited, in walk order.
+fn walk_includes(
+    changelog: &Path,
+    visit: impl Fn(&Path) -> Result<(), LoadError>,
+) -> Result<Vec<PathBuf>, LoadError> {
+    let base_dir = changelog.parent().unwrap_or_else(|| Path::new("."));
+    let mut files = Vec::new();
+    let mut pending = vec![changelog.to_path_buf()];
+    let mut seen = HashSet::new();
+    while let Some(file) = pending.pop() {
+        visit(&file)?;
+        if !seen.insert(file.clone()) {
+            continue;
+        }
+        files.push(file.clone());
+        let Ok(source) = read_source(&file) else {
+            continue;
+        };
+        let dir = file.parent().unwrap_or_else(|| Path::new("."));
+        for reference in changelog_references(&file, &source) {
+            let path = if reference.relative_to_changelog {
+                dir.join(&reference.path)
+            } else {
+                base_dir.join(&reference.path)
+            };
+            match reference.kind {
+                ReferenceKind::Changelog => pending.push(path),
+                ReferenceKind::SqlFile => {
+                    visit(&path)?;
+                    files.push(path);
+                }
+                ReferenceKind::Directory => {
+                    visit(&path)?;
+                    pending.extend(changelogs_under(&path, &visit)?);
+                }
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// The changelogs under `dir`, at any depth, as `includeAll` reads them.
+/// Each one, and each directory, is passed to `visit` first.
+fn changelogs_under(
+    dir: &Path,
+    visit: &impl Fn(&Path) -> Result<(), LoadError>,
+) -> Result<Vec<PathBuf>, LoadError> {
+    let mut changelogs = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for path in entries.flatten().map(|e| e.path()) {
+            let is_changelog = path.extension().and_then(|e| e.to_str()).is_some_and(|e| {
+                ["xml", "yaml", "yml", "json", "sql"]
+                    .iter()
+                    .any(|ext| e.eq_ignore_ascii_case(ext))
+            });
+            if path.is_dir() {
+                visit(&path)?;
+                dirs.push(path);
+            } else if is_changelog {
+                visit(&path)?;
+                changelogs.push(path);
+            }
+        }
+    }
+    Ok(changelogs)
+}
+
+/// The files and directories a changelog of any serialization names.
+fn changelog_references(path: &Path, source: &str) -> Vec<Reference> {
+    match ChangelogFormat::from_path(path) {
+        ChangelogFormat::Xml => xml_references(source),
+        format => changelog_format::references(format, source),
+    }
+}
+
+/// `<include>`, `<includeAll>`, and `<sqlFile>` elements of an XML
+/// changelog, outside comments.
+fn xml_references(source: &str) -> Vec<Reference> {
+    let mut found = Vec::new();
+    let mut rest = source;
+    while let Some(i) = rest.find('<') {
+        rest = &rest[i + 1..];
+        if let Some(comment) = rest.strip_prefix("!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let tag = &rest[..rest.find('>').unwrap_or(rest.len())];
+        let name = tag
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+        let attributes: Vec<(&str, Option<String>)> = ["file", "path", "relativeToChangelogFile"]
+            .into_iter()
+            .map(|key| (key, xml_attribute(tag, key)))
+            .collect();
+        let reference = Reference::from_attributes(name, |key| {
+            attributes
+                .iter()
+                .find(|(k, _)| *k == key)
+                .and_then(|(_, value)| value.as_deref())
+        });
+        found.extend(reference);
+    }
+    found
+}
+
+/// Fail when a unit comes from a file outside `include_root`. Liquibase
+/// can resolve an include through its search path where
+/// [`check_changelog_includes`] did not look, so the files it actually read
+/// are checked too. Call after [`resolve_source_paths`], before any unit's
+/// file is read.
+pub fn check_include_root(
+    units: &[RawMigrationUnit],
+    include_root: Option<&Path>,
+) -> Result<(), LoadError> {
+    let Some(root) = include_root else {
+        return Ok(());
+    };
+    match units.iter().find(|u| !is_within_root(root, &u.source_file)) {
+        Some(unit) => Err(LoadError::OutsideIncludeRoot {
+            path: unit.source_file.clone(),
+            root: root.to_path_buf(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Read each unit's source changelog and attach the suppression directives
+/// that apply to it.
+///
+/// Each distinct source file is read and parsed once. A unit is matched to
+/// its `<changeSet>` by `source_line_offset` when the loader provides it
+/// (bridge JAR), and otherwise by changeset id (`update-sql`, which has no
+/// line information). Call after [`resolve_source_paths`].
+pub fn attach_suppressions(units: &mut [RawMigrationUnit]) {
+    let mut files: HashMap<PathBuf, Option<(String, Suppressions)>> = HashMap::new();
+    for unit in units {
+        let parsed = files.entry(unit.source_file.clone()).or_insert_with(|| {
+            match read_source(&unit.source_file) {
+                Ok(source) => {
+                    let suppressions = parse_suppressions(&source);
+                    Some((source, suppressions))
+                }
+                Err(e) => {
+                    eprintln!(
+                        "Warning: could not read '{}' for suppression comments: {}",
+                        unit.source_file.display(),
+                        e
+                    );
+                    None
+                }
+            }
+        });
+        let Some((source, suppressions)) = parsed else {
+            continue;
+        };
+        let unit_line = if unit.source_line_offset > 1 {
+            unit.source_line_offset
+        } else {
+            unit_changeset_line(unit, source).unwrap_or(1)
+        };
+        unit.suppressions = suppressions.for_unit(unit_line);
+    }
+}
+
+/// 1-based line of the unit's changeset in a changelog of any
+/// serialization.
+fn unit_changeset_line(unit: &RawMigrationUnit, source: &str) -> Option<usize> {
+    match ChangelogFormat::from_path(&unit.source_file) {
+        ChangelogFormat::Xml => changeset_line(source, &unit.id),
+        format => changelog_format::changeset_line(
+            format,
+            source,
+            &unit.id,
+            unit.changeset.author.as_deref(),
+        ),
+    }
+}
+
+/// Fill in the changeset attributes `update-sql` output does not carry
+/// (comments, contexts, labels) from each unit's `<changeSet>` element, or
+/// its `changeSet` entry in a YAML or JSON changelog, so that both strategies
+/// produce the same units. Attributes the loader already reported are kept.
+/// Call after [`resolve_source_paths`].
+pub fn attach_changeset_attributes(units: &mut [RawMigrationUnit]) {
+    let mut files: HashMap<PathBuf, Option<String>> = HashMap::new();
+    for unit in units {
+        // Unreadable files were already reported by `attach_suppressions`.
+        let source = files
+            .entry(unit.source_file.clone())
+            .or_insert_with(|| read_source(&unit.source_file).ok());
+        let Some(source) = source else {
+            continue;
+        };
+        let from_source = match ChangelogFormat::from_path(&unit.source_file) {
+            ChangelogFormat::Xml => {
+                let line = if unit.source_line_offset > 1 {
+                    Some(unit.source_line_offset)
+                } else {
+                    changeset_line(source, &unit.id)
+                };
+                line.map(|line| changeset_attributes(source, line))
+            }
+            format => changelog_format::changeset_attributes(
+                format,
+                source,
+                &unit.id,
+                unit.changeset.author.as_deref(),
+            ),
+        };
+        let Some(from_source) = from_source else {
+            continue;
+        };
+        let meta = &mut unit.changeset;
+        meta.author = meta.author.take().or(from_source.author);
+        meta.comments = meta.comments.take().or(from_source.comments);
+        if meta.contexts.is_empty() {
+            meta.contexts = from_source.contexts;
+        }
+        if meta.labels.is_empty() {
+            meta.labels = from_source.labels;
+        }
+    }
+}
+
+/// Read the attributes of the `<changeSet>` element starting on the 1-based
+/// `line` of an XML changelog, and the text of its `<comment>` child.
+fn changeset_attributes(source: &str, line: usize) -> ChangesetMeta {
+    let start = source
+        .split_inclusive('\n')
+        .take(line - 1)
+        .map(str::len)
+        .sum::<usize>();
+    let element = &source[start..];
+    let element = &element[..element.find("</changeSet>").unwrap_or(element.len())];
+    let tag = &element[..element.find('>').map_or(element.len(), |i| i + 1)];
+
+    let list = |name: &str| -> Vec<String> {
+        let mut items: Vec<String> = xml_attribute(tag, name)
+            .map(|v| {
+                v.split(',')
+                    .map(|item| item.trim().to_string())
+                    .filter(|item| !item.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        items.sort();
+        items
+    };
+    let contexts = match list("contextFilter") {
+        contexts if contexts.is_empty() => list("context"),
+        contexts => contexts,
+    };
+    let comments = element
+        .find("<comment>")
+        .map(|i| &element[i + "<comment>".len()..])
+        .and_then(|rest| rest.find("</comment>").map(|end| &rest[..end]))
+        .map(|text| unescape_xml(text.trim()))
+        .filter(|text| !text.is_empty());
+
+    ChangesetMeta {
+        author: xml_attribute(tag, "author").filter(|a| !a.is_empty()),
+        comments,
+        contexts,
+        labels: list("labels"),
+    }
+}
+
+/// The unescaped value of attribute `name` in an XML start tag.
+fn xml_attribute(tag: &str, name: &str) -> Option<String> {
+    let mut rest = tag;
+    while let Some(i) = rest.find(name) {
+        let preceded_by_space = rest[..i].ends_with(char::is_whitespace);
+        let after = rest[i + name.len()..].trim_start();
+        rest = &rest[i + name.len()..];
+        let Some(value) = after.strip_prefix('=').map(str::trim_start) else {
+            continue;
+        };
+        if !preceded_by_space {
+            continue;
+        }
+        let quote = value.chars().next()?;
+        if quote != '"' && quote != '\'' {
+            continue;
+        }
+        let value = &value[1..];
+        return value.find(quote).map(|end| unescape_xml(&value[..end]));
+    }
+    None
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
 /// Load Liquibase migrations using the configured strategy.
 ///
 /// Strategy selection:
@@ -136,40 +496,49 @@ pub fn resolve_source_paths(units: &mut [RawMigrationUnit], base_dir: &Path) {
 /// - `"update-sql"`: Use `liquibase update-sql` only.
 /// - `"auto"` (default): Try bridge -> update-sql in order.
 ///
-/// The `paths` parameter should contain paths to changelog files.
+/// The `paths` parameter should contain paths to changelog files. A
+/// changelog that names a file outside `include_root` fails the load before
+/// Liquibase runs. Changesets excluded by
+/// the configured `contexts` and `labels` are dropped.
 pub fn load_liquibase(
     config: &LiquibaseConfig,
     paths: &[PathBuf],
+    include_root: Option<&Path>,
 ) -> Result<Vec<RawMigrationUnit>, LoadError> {
     let strategy = config.strategy.as_str();
 
-    match strategy {
-        "bridge" => load_with_bridge(config, paths),
-        "update-sql" => load_with_updatesql(config, paths),
-        "auto" => load_auto(config, paths),
+    let mut units = match strategy {
+        "bridge" => load_with_bridge(config, paths, include_root),
+        "update-sql" => load_with_updatesql(config, paths, include_root),
+        "auto" => load_auto(config, paths, include_root),
         other => Err(LoadError::Config {
             message: format!("Unknown liquibase strategy: '{}'", other),
         }),
-    }
+    }?;
+    retain_applied(&mut units, config);
+    Ok(units)
 }
 
 /// Try bridge -> update-sql in order.
 fn load_auto(
     config: &LiquibaseConfig,
     paths: &[PathBuf],
+    include_root: Option<&Path>,
 ) -> Result<Vec<RawMigrationUnit>, LoadError> {
     // Try bridge first
     if config.bridge_jar_path.is_some() {
-        match load_with_bridge(config, paths) {
+        match load_with_bridge(config, paths, include_root) {
             Ok(units) => return Ok(units),
+            Err(e @ LoadError::OutsideIncludeRoot { .. }) => return Err(e),
             Err(_) => { /* fall through to next strategy */ }
         }
     }
 
     // Try update-sql
     if config.binary_path.is_some() {
-        match load_with_updatesql(config, paths) {
+        match load_with_updatesql(config, paths, include_root) {
             Ok(units) => return Ok(units),
+            Err(e @ LoadError::OutsideIncludeRoot { .. }) => return Err(e),
             Err(_) => { /* fall through to error */ }
         }
     }
@@ -185,6 +554,7 @@ fn load_auto(
 fn load_with_bridge(
     config: &LiquibaseConfig,
     paths: &[PathBuf],
+    include_root: Option<&Path>,
 ) -> Result<Vec<RawMigrationUnit>, LoadError> {
     let jar_path = config
         .bridge_jar_path
@@ -197,9 +567,12 @@ fn load_with_bridge(
     let mut all_units = Vec::new();
 
     for path in paths {
+        check_changelog_includes(path, include_root)?;
         let mut units = loader.load(path)?;
         let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
         resolve_source_paths(&mut units, base_dir);
+        check_include_root(&units, include_root)?;
+        attach_suppressions(&mut units);
         all_units.extend(units);
     }
 
@@ -210,6 +583,7 @@ fn load_with_bridge(
 fn load_with_updatesql(
     config: &LiquibaseConfig,
     paths: &[PathBuf],
+    include_root: Option<&Path>,
 ) -> Result<Vec<RawMigrationUnit>, LoadError> {
     let binary_path = config
         .binary_path
@@ -225,9 +599,13 @@ fn load_with_updatesql(
     let mut all_units = Vec::new();
 
     for path in paths {
+        check_changelog_includes(path, include_root)?;
         let mut units = loader.load(path)?;
         let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
         resolve_source_paths(&mut units, base_dir);
+        check_include_root(&units, include_root)?;
+        attach_suppressions(&mut units);
+        attach_changeset_attributes(&mut units);
         all_units.extend(units);
     }
 
@@ -327,9 +705,10 @@ mod tests {
             binary_path: None,
             properties_file: None,
             strategy: "invalid-strategy".to_string(),
+            ..Default::default()
         };
 
-        let result = load_liquibase(&config, &[]);
+        let result = load_liquibase(&config, &[], None);
         assert!(result.is_err());
         match result {
             Err(LoadError::Config { message }) => {
@@ -370,6 +749,8 @@ mod tests {
                 source_line_offset: 1,
                 run_in_transaction: true,
                 is_down: false,
+                suppressions: Default::default(),
+                changeset: Default::default(),
             },
             RawMigrationUnit {
                 id: "2".into(),
@@ -378,6 +759,8 @@ mod tests {
                 source_line_offset: 1,
                 run_in_transaction: true,
                 is_down: false,
+                suppressions: Default::default(),
+                changeset: Default::default(),
             },
         ];
         resolve_source_paths(&mut units, Path::new("db/changelog"));
@@ -389,6 +772,81 @@ mod tests {
         assert_eq!(units[1].source_file, PathBuf::from("/absolute/bar.xml"));
     }
 
+    #[test]
+    fn test_include_outside_root_rejected() {
+        let unit = |file: &str| RawMigrationUnit {
+            id: "1".into(),
+            sql: "SELECT 1;".into(),
+            source_file: PathBuf::from(file),
+            source_line_offset: 1,
+            run_in_transaction: true,
+            is_down: false,
+            suppressions: Default::default(),
+            changeset: Default::default(),
+        };
+        let root = Path::new("/repo");
+        let inside = [unit("/repo/db/changelog/master.xml")];
+        assert!(check_include_root(&inside, Some(root)).is_ok());
+
+        let escaping = [
+            unit("/repo/db/changelog/master.xml"),
+            unit("/repo/db/changelog/../../../etc/shadow.xml"),
+        ];
+        let err = check_include_root(&escaping, Some(root)).expect_err("outside root");
+        assert!(matches!(err, LoadError::OutsideIncludeRoot { .. }));
+        assert!(check_include_root(&escaping, None).is_ok());
+    }
+
+    #[test]
+    fn test_changelog_includes_checked_before_loading() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("repo");
+        let changelogs = root.join("db");
+        std::fs::create_dir_all(changelogs.join("more")).unwrap();
+        let write = |name: &str, text: &str| std::fs::write(changelogs.join(name), text).unwrap();
+        let check = |name: &str| check_changelog_includes(&changelogs.join(name), Some(&root));
+        let escaped = |name: &str| match check(name) {
+            Err(LoadError::OutsideIncludeRoot { path, .. }) => path,
+            other => panic!("expected OutsideIncludeRoot, got {other:?}"),
+        };
+
+        write(
+            "master.xml",
+            r#"<databaseChangeLog>
+  <!-- <include file="/etc/passwd"/> -->
+  <include file="child.xml" relativeToChangelogFile="true"/>
+  <includeAll path="more/" relativeToChangelogFile="true"/>
+</databaseChangeLog>"#,
+        );
+        write(
+            "child.xml",
+            r#"<changeSet id="1" author="a"><sqlFile path="seed.sql"/></changeSet>"#,
+        );
+        write("more/V1.sql", "--liquibase formatted sql\n");
+        assert!(check("master.xml").is_ok());
+        assert!(check_changelog_includes(&changelogs.join("master.xml"), None).is_ok());
+
+        write(
+            "child.xml",
+            r#"<changeSet id="1" author="a">
+  <sqlFile path="../../secret.sql" relativeToChangelogFile="true"/>
+</changeSet>"#,
+        );
+        assert!(escaped("master.xml").ends_with("secret.sql"));
+
+        write("absolute.xml", r#"<include file="/etc/passwd.xml"/>"#);
+        assert_eq!(escaped("absolute.xml"), PathBuf::from("/etc/passwd.xml"));
+
+        write(
+            "master.yaml",
+            "databaseChangeLog:\n  - includeAll:\n      path: ../../elsewhere/\n",
+        );
+        assert!(escaped("master.yaml").ends_with("elsewhere/"));
+
+        write("master.sql", "--include file:../../../x.sql\n");
+        assert!(escaped("master.sql").ends_with("x.sql"));
+    }
+
     #[test]
     fn test_resolve_paths_empty_base() {
         let mut units = vec![RawMigrationUnit {
@@ -398,6 +856,8 @@ mod tests {
             source_line_offset: 1,
             run_in_transaction: true,
             is_down: false,
+            suppressions: Default::default(),
+            changeset: Default::default(),
         }];
         // Empty base dir (changelog at repo root) should leave path unchanged
         resolve_source_paths(&mut units, Path::new(""));
@@ -413,6 +873,8 @@ mod tests {
             source_line_offset: 1,
             run_in_transaction: true,
             is_down: false,
+            suppressions: Default::default(),
+            changeset: Default::default(),
         }];
         resolve_source_paths(&mut units, Path::new("."));
         assert_eq!(units[0].source_file, PathBuf::from("./foo.xml"));
@@ -436,4 +898,175 @@ mod tests {
         assert!(units[0].sql.contains("CREATE TABLE a"));
         assert!(units[0].sql.contains("CREATE TABLE b"));
     }
+
+    #[test]
+    fn test_attach_suppressions_scopes_to_changeset() {
+        use crate::rules::RuleId;
+
+        let dir = tempfile::tempdir().unwrap();
+        let changelog = dir.path().join("changelog.xml");
+        std::fs::write(
+            &changelog,
+            r#"<databaseChangeLog>
+    <changeSet id="1" author="dev">
+        <!-- pgm-lint:suppress PGM001 -->
+        <sql>CREATE INDEX idx_a ON a (x);</sql>
+        <sql>CREATE INDEX idx_b ON b (x);</sql>
+    </changeSet>
+    <changeSet id="2" author="dev">
+        <sql>CREATE INDEX idx_c ON c (x);</sql>
+    </changeSet>
+</databaseChangeLog>
+"#,
+        )
+        .unwrap();
+
+        let unit = |id: &str, line: usize| RawMigrationUnit {
+            id: id.into(),
+            sql: String::new(),
+            source_file: changelog.clone(),
+            source_line_offset: line,
+            run_in_transaction: true,
+            is_down: false,
+            suppressions: Default::default(),
+            changeset: Default::default(),
+        };
+        // Bridge units carry the <changeSet> line; update-sql units only the id.
+        let mut units = vec![unit("1", 2), unit("2", 7), unit("1", 1), unit("2", 1)];
+        attach_suppressions(&mut units);
+
+        // Suppressed on every line of changeset 1, however the unit was matched.
+        assert!(units[0].suppressions.is_suppressed(RuleId::Pgm001, 3));
+        assert!(units[0].suppressions.is_suppressed(RuleId::Pgm001, 4));
+        assert!(units[2].suppressions.is_suppressed(RuleId::Pgm001, 2));
+        // Not in changeset 2.
+        assert!(!units[1].suppressions.is_suppressed(RuleId::Pgm001, 8));
+        assert!(!units[3].suppressions.is_suppressed(RuleId::Pgm001, 2));
+    }
+
+    #[test]
+    fn test_parse_json_changeset_metadata() {
+        let json = r#"[
+            {
+                "changeset_id": "1",
+                "author": "robert",
+                "comments": "Create orders",
+                "contexts": ["prod", "staging"],
+                "labels": ["billing"],
+                "sql": "SELECT 1;",
+                "xml_file": "test.xml"
+            },
+            {
+                "changeset_id": "2",
+                "author": "",
+                "sql": "SELECT 2;",
+                "xml_file": "test.xml"
+            }
+        ]"#;
+
+        let units = parse_bridge_json(json).expect("Should parse metadata");
+        assert_eq!(
+            units[0].changeset,
+            ChangesetMeta {
+                author: Some("robert".to_string()),
+                comments: Some("Create orders".to_string()),
+                contexts: vec!["prod".to_string(), "staging".to_string()],
+                labels: vec!["billing".to_string()],
+            }
+        );
+        assert_eq!(units[1].changeset, ChangesetMeta::default());
+    }
+
+    #[test]
+    fn test_attach_changeset_attributes_from_xml() {
+        let dir = tempfile::tempdir().unwrap();
+        let changelog = dir.path().join("changelog.xml");
+        std::fs::write(
+            &changelog,
+            r#"<databaseChangeLog>
+    <changeSet id="1" author="dev"
+               context="staging, prod" labels='billing'>
+        <comment>Orders &amp; items</comment>
+        <sql>CREATE TABLE orders (id int);</sql>
+    </changeSet>
+    <changeSet id="2" author="ops" contextFilter="prod">
+        <sql>CREATE TABLE audit (id int);</sql>
+    </changeSet>
+</databaseChangeLog>
+"#,
+        )
+        .unwrap();
+
+        let unit = |id: &str, author: &str| RawMigrationUnit {
+            id: id.into(),
+            sql: String::new(),
+            source_file: changelog.clone(),
+            source_line_offset: 1,
+            run_in_transaction: true,
+            is_down: false,
+            suppressions: Default::default(),
+            changeset: ChangesetMeta {
+                author: Some(author.to_string()),
+                ..Default::default()
+            },
+        };
+        let mut units = vec![unit("1", "dev"), unit("2", "ops")];
+        attach_changeset_attributes(&mut units);
+
+        assert_eq!(
+            units[0].changeset,
+            ChangesetMeta {
+                author: Some("dev".to_string()),
+                comments: Some("Orders & items".to_string()),
+                contexts: vec!["prod".to_string(), "staging".to_string()],
+                labels: vec!["billing".to_string()],
+            }
+        );
+        assert_eq!(units[1].changeset.contexts, vec!["prod"]);
+        assert_eq!(units[1].changeset.comments, None);
+        assert!(units[1].changeset.labels.is_empty());
+    }
+
+    #[test]
+    fn test_attach_changeset_attributes_from_yaml() {
+        let dir = tempfile::tempdir().unwrap();
+        let changelog = dir.path().join("changelog.yaml");
+        std::fs::write(
+            &changelog,
+            "databaseChangeLog:
+  - changeSet:
+      id: 1
+      author: dev
+      context: staging, prod
+      comment: Create orders
+      changes:
+        - createTable:
+            tableName: orders
+",
+        )
+        .unwrap();
+
+        let mut units = vec![RawMigrationUnit {
+            id: "1".into(),
+            sql: String::new(),
+            source_file: changelog,
+            source_line_offset: 1,
+            run_in_transaction: true,
+            is_down: false,
+            suppressions: Default::default(),
+            changeset: ChangesetMeta::default(),
+        }];
+        attach_suppressions(&mut units);
+        attach_changeset_attributes(&mut units);
+
+        assert_eq!(
+            units[0].changeset,
+            ChangesetMeta {
+                author: Some("dev".to_string()),
+                comments: Some("Create orders".to_string()),
+                contexts: vec!["prod".to_string(), "staging".to_string()],
+                labels: vec![],
+            }
+        );
+    }
 }
@@ -0,0 +1,353 @@
+//! Comparing the two Liquibase loading strategies
+//!
+//! `pg-migration-lint liquibase compare-strategies` loads the changelog with
+//! the bridge JAR and with `update-sql` and reports where the units differ,
+//! so a team can check that dropping one strategy leaves the findings alone.
+//! Units are paired by changeset id and author (in order, when a pair occurs
+//! more than once) and compared on:
+//!
+//! - presence: a changeset only one strategy produced;
+//! - order: the shared changesets replay in a different order;
+//! - statements: the SQL parses to different IR (formatting, comments, and
+//!   statement terminators do not count);
+//! - transaction mode: `runInTransaction`, which `update-sql` cannot see.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::input::RawMigrationUnit;
+use crate::parser::ir::{IrNode, Located};
+use crate::parser::pg_query::parse_sql;
+
+/// A Liquibase loading strategy, as named in `[liquibase] strategy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    Bridge,
+    UpdateSql,
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Bridge => "bridge",
+            Self::UpdateSql => "update-sql",
+        })
+    }
+}
+
+/// One difference between the bridge and `update-sql` units.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Divergence {
+    /// Only one strategy produced this changeset.
+    Missing {
+        id: String,
+        source_file: PathBuf,
+        only_in: Strategy,
+    },
+    /// The shared changesets diverge in order from this one on.
+    Order {
+        position: usize,
+        bridge_id: String,
+        update_sql_id: String,
+    },
+    /// The changeset's SQL parses to different statements. Holds the first
+    /// statement that differs on each side (empty when one side has fewer).
+    Statements {
+        id: String,
+        source_file: PathBuf,
+        bridge_sql: String,
+        update_sql_sql: String,
+    },
+    /// The changeset runs inside a transaction under one strategy only.
+    Transaction {
+        id: String,
+        source_file: PathBuf,
+        bridge: bool,
+    },
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing {
+                id,
+                source_file,
+                only_in,
+            } => write!(
+                f,
+                "{}: changeset '{id}' is only loaded by {only_in}",
+                source_file.display()
+            ),
+            Self::Order {
+                position,
+                bridge_id,
+                update_sql_id,
+            } => write!(
+                f,
+                "order differs at changeset #{}: bridge has '{bridge_id}', update-sql has '{update_sql_id}'",
+                position + 1
+            ),
+            Self::Statements {
+                id,
+                source_file,
+                bridge_sql,
+                update_sql_sql,
+            } => write!(
+                f,
+                "{}: changeset '{id}' produces different statements\n    bridge:     {}\n    update-sql: {}",
+                source_file.display(),
+                or_none(bridge_sql),
+                or_none(update_sql_sql),
+            ),
+            Self::Transaction {
+                id,
+                source_file,
+                bridge,
+            } => write!(
+                f,
+                "{}: changeset '{id}' runs {} a transaction with bridge but {} with update-sql",
+                source_file.display(),
+                if *bridge { "inside" } else { "outside" },
+                if *bridge { "outside" } else { "inside" },
+            ),
+        }
+    }
+}
+
+fn or_none(sql: &str) -> &str {
+    if sql.is_empty() { "(none)" } else { sql }
+}
+
+/// Compare the units the bridge JAR and `update-sql` loaded from the same
+/// changelog. An empty result means both strategies replay and lint the
+/// same statements.
+pub fn compare_strategies(
+    bridge: &[RawMigrationUnit],
+    update_sql: &[RawMigrationUnit],
+) -> Vec<Divergence> {
+    let pairs = pair_units(bridge, update_sql);
+    let mut divergences = Vec::new();
+
+    for (index, unit) in bridge.iter().enumerate() {
+        if !pairs.iter().any(|&(b, _)| b == index) {
+            divergences.push(missing(unit, Strategy::Bridge));
+        }
+    }
+    for (index, unit) in update_sql.iter().enumerate() {
+        if !pairs.iter().any(|&(_, u)| u == index) {
+            divergences.push(missing(unit, Strategy::UpdateSql));
+        }
+    }
+
+    // `pairs` follows the bridge order; the update-sql side must ascend too.
+    if let Some(position) = pairs.windows(2).position(|w| w[0].1 > w[1].1) {
+        let mut update_sql_order: Vec<usize> = pairs.iter().map(|&(_, u)| u).collect();
+        update_sql_order.sort_unstable();
+        let position = (0..pairs.len())
+            .find(|&i| pairs[i].1 != update_sql_order[i])
+            .unwrap_or(position);
+        divergences.push(Divergence::Order {
+            position,
+            bridge_id: bridge[pairs[position].0].id.clone(),
+            update_sql_id: update_sql[update_sql_order[position]].id.clone(),
+        });
+    }
+
+    for &(b, u) in &pairs {
+        let (b, u) = (&bridge[b], &update_sql[u]);
+        if let Some((bridge_sql, update_sql_sql)) = first_statement_difference(&b.sql, &u.sql) {
+            divergences.push(Divergence::Statements {
+                id: b.id.clone(),
+                source_file: b.source_file.clone(),
+                bridge_sql,
+                update_sql_sql,
+            });
+        }
+        if b.run_in_transaction != u.run_in_transaction {
+            divergences.push(Divergence::Transaction {
+                id: b.id.clone(),
+                source_file: b.source_file.clone(),
+                bridge: b.run_in_transaction,
+            });
+        }
+    }
+
+    divergences
+}
+
+fn missing(unit: &RawMigrationUnit, only_in: Strategy) -> Divergence {
+    Divergence::Missing {
+        id: unit.id.clone(),
+        source_file: unit.source_file.clone(),
+        only_in,
+    }
+}
+
+/// Indices of matching (bridge, update-sql) units, in bridge order. Units
+/// match on changeset id and author; repeated keys pair up in order.
+fn pair_units(bridge: &[RawMigrationUnit], update_sql: &[RawMigrationUnit]) -> Vec<(usize, usize)> {
+    let mut by_key: HashMap<(&str, Option<&str>), Vec<usize>> = HashMap::new();
+    for (index, unit) in update_sql.iter().enumerate().rev() {
+        by_key.entry(key(unit)).or_default().push(index);
+    }
+    bridge
+        .iter()
+        .enumerate()
+        .filter_map(|(index, unit)| Some((index, by_key.get_mut(&key(unit))?.pop()?)))
+        .collect()
+}
+
+fn key(unit: &RawMigrationUnit) -> (&str, Option<&str>) {
+    (&unit.id, unit.changeset.author.as_deref())
+}
+
+/// The first statement whose IR differs between the two SQL texts, as
+/// written on each side, or `None` when both parse to the same IR.
+fn first_statement_difference(bridge: &str, update_sql: &str) -> Option<(String, String)> {
+    let bridge = parse_sql(bridge);
+    let update_sql = parse_sql(update_sql);
+    let nodes = |statements: &[Located<IrNode>]| -> Vec<IrNode> {
+        statements.iter().map(|s| comparable(&s.node)).collect()
+    };
+    let (bridge_nodes, update_sql_nodes) = (nodes(&bridge), nodes(&update_sql));
+    let index = (0..bridge_nodes.len().max(update_sql_nodes.len()))
+        .find(|&i| bridge_nodes.get(i) != update_sql_nodes.get(i))?;
+    let sql = |statements: &[Located<IrNode>]| {
+        statements
+            .get(index)
+            .map(|s| s.raw_sql.to_string())
+            .unwrap_or_default()
+    };
+    Some((sql(&bridge), sql(&update_sql)))
+}
+
+/// `node` with the SQL text of statements the linter does not model reduced
+/// to single spaces, so that a difference in layout alone is not reported.
+fn comparable(node: &IrNode) -> IrNode {
+    let collapse = |sql: &str| sql.split_whitespace().collect::<Vec<_>>().join(" ");
+    match node {
+        IrNode::Ignored { kind, raw_sql } => IrNode::Ignored {
+            kind: *kind,
+            raw_sql: collapse(raw_sql),
+        },
+        IrNode::Unparseable {
+            raw_sql,
+            table_hint,
+        } => IrNode::Unparseable {
+            raw_sql: collapse(raw_sql),
+            table_hint: table_hint.clone(),
+        },
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::ChangesetMeta;
+
+    fn unit(id: &str, sql: &str, run_in_transaction: bool) -> RawMigrationUnit {
+        RawMigrationUnit {
+            id: id.to_string(),
+            sql: sql.to_string(),
+            source_file: PathBuf::from("db/changelog.xml"),
+            source_line_offset: 1,
+            run_in_transaction,
+            is_down: false,
+            suppressions: Default::default(),
+            changeset: ChangesetMeta {
+                author: Some("robert".to_string()),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_identical_units_do_not_diverge() {
+        let bridge = vec![
+            unit("1", "CREATE TABLE orders (id bigint PRIMARY KEY);", true),
+            unit("2", "ALTER TABLE orders ADD COLUMN total numeric;", true),
+        ];
+        // update-sql formats the same statements differently.
+        let update_sql = vec![
+            unit(
+                "1",
+                "CREATE TABLE orders (\n  id bigint PRIMARY KEY\n)",
+                true,
+            ),
+            unit(
+                "2",
+                "-- add total\nALTER TABLE orders\n  ADD COLUMN total numeric;",
+                true,
+            ),
+        ];
+        assert_eq!(compare_strategies(&bridge, &update_sql), vec![]);
+    }
+
+    #[test]
+    fn test_missing_and_reordered_units() {
+        let bridge = vec![
+            unit("1", "SELECT 1;", true),
+            unit("2", "SELECT 2;", true),
+            unit("3", "SELECT 3;", true),
+        ];
+        let update_sql = vec![
+            unit("3", "SELECT 3;", true),
+            unit("1", "SELECT 1;", true),
+            unit("4", "SELECT 4;", true),
+        ];
+        let divergences = compare_strategies(&bridge, &update_sql);
+        assert_eq!(
+            divergences,
+            vec![
+                Divergence::Missing {
+                    id: "2".to_string(),
+                    source_file: PathBuf::from("db/changelog.xml"),
+                    only_in: Strategy::Bridge,
+                },
+                Divergence::Missing {
+                    id: "4".to_string(),
+                    source_file: PathBuf::from("db/changelog.xml"),
+                    only_in: Strategy::UpdateSql,
+                },
+                Divergence::Order {
+                    position: 0,
+                    bridge_id: "1".to_string(),
+                    update_sql_id: "3".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            divergences[0].to_string(),
+            "db/changelog.xml: changeset '2' is only loaded by bridge"
+        );
+    }
+
+    #[test]
+    fn test_statement_and_transaction_divergence() {
+        let bridge = vec![unit(
+            "1",
+            "CREATE INDEX CONCURRENTLY idx_orders_total ON orders (total);",
+            false,
+        )];
+        let update_sql = vec![unit(
+            "1",
+            "CREATE INDEX idx_orders_total ON orders (total);",
+            true,
+        )];
+        let divergences = compare_strategies(&bridge, &update_sql);
+        assert_eq!(divergences.len(), 2);
+        assert_eq!(
+            divergences[0].to_string(),
+            "db/changelog.xml: changeset '1' produces different statements\n    \
+             bridge:     CREATE INDEX CONCURRENTLY idx_orders_total ON orders (total)\n    \
+             update-sql: CREATE INDEX idx_orders_total ON orders (total)"
+        );
+        assert_eq!(
+            divergences[1].to_string(),
+            "db/changelog.xml: changeset '1' runs outside a transaction with bridge but \
+             inside with update-sql"
+        );
+    }
+}
@@ -0,0 +1,226 @@
+//! Liquibase context and label filtering
+//!
+//! Liquibase skips a changeset whose `context` expression does not match the
+//! contexts of the run, or whose `labels` do not match the run's label
+//! expression. [`retain_applied`] applies `[liquibase] contexts` and
+//! `labels` the same way, so that replay and linting only see the changesets
+//! an `update` with those settings would apply.
+//!
+//! Expressions use Liquibase's syntax: names combined with `and`, `or`,
+//! `!` / `not`, and parentheses; a comma is `or`. Names are compared
+//! case-insensitively.
+
+use crate::config::LiquibaseConfig;
+use crate::input::{ChangesetMeta, RawMigrationUnit};
+
+/// Drop the units Liquibase would not apply with the configured contexts and
+/// labels. With neither configured every unit is kept.
+pub fn retain_applied(units: &mut Vec<RawMigrationUnit>, config: &LiquibaseConfig) {
+    let contexts: Vec<String> = config
+        .contexts
+        .iter()
+        .map(|c| c.trim().to_lowercase())
+        .filter(|c| !c.is_empty())
+        .collect();
+    let labels = config.labels.as_deref().filter(|l| !l.trim().is_empty());
+    if contexts.is_empty() && labels.is_none() {
+        return;
+    }
+    units.retain(|unit| is_applied(&unit.changeset, &contexts, labels));
+}
+
+/// Whether a changeset runs with the given (lowercase) `contexts` and label
+/// expression. A changeset without contexts runs in every context, and one
+/// without labels under every label expression.
+fn is_applied(meta: &ChangesetMeta, contexts: &[String], labels: Option<&str>) -> bool {
+    let context_ok = contexts.is_empty()
+        || meta.contexts.is_empty()
+        || meta
+            .contexts
+            .iter()
+            .any(|expr| evaluate(expr, |name| contexts.iter().any(|c| *c == name)));
+    let labels_ok = match labels {
+        None => true,
+        Some(_) if meta.labels.is_empty() => true,
+        Some(expr) => evaluate(expr, |name| {
+            meta.labels.iter().any(|l| l.to_lowercase() == name)
+        }),
+    };
+    context_ok && labels_ok
+}
+
+/// Evaluate `expr`, with `is_set` deciding each (lowercased) name. An
+/// expression that does not parse matches, so a typo never hides a
+/// changeset.
+fn evaluate(expr: &str, is_set: impl Fn(&str) -> bool) -> bool {
+    let tokens = tokenize(expr);
+    let mut parser = Parser {
+        tokens: &tokens,
+        pos: 0,
+        is_set: &is_set,
+    };
+    match parser.or_expr() {
+        Some(value) if parser.pos == tokens.len() => value,
+        _ => true,
+    }
+}
+
+fn tokenize(expr: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    for c in expr.chars() {
+        if c.is_whitespace() || matches!(c, '(' | ')' | '!' | ',') {
+            if !word.is_empty() {
+                tokens.push(std::mem::take(&mut word).to_lowercase());
+            }
+            if !c.is_whitespace() {
+                tokens.push(c.to_string());
+            }
+        } else {
+            word.push(c);
+        }
+    }
+    if !word.is_empty() {
+        tokens.push(word.to_lowercase());
+    }
+    tokens
+}
+
+/// Recursive descent over `or` > `and` > `not` > name / parenthesized.
+struct Parser<'a> {
+    tokens: &'a [String],
+    pos: usize,
+    is_set: &'a dyn Fn(&str) -> bool,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn or_expr(&mut self) -> Option<bool> {
+        let mut value = self.and_expr()?;
+        while matches!(self.peek(), Some("or" | ",")) {
+            self.pos += 1;
+            value |= self.and_expr()?;
+        }
+        Some(value)
+    }
+
+    fn and_expr(&mut self) -> Option<bool> {
+        let mut value = self.unary()?;
+        while self.peek() == Some("and") {
+            self.pos += 1;
+            value &= self.unary()?;
+        }
+        Some(value)
+    }
+
+    fn unary(&mut self) -> Option<bool> {
+        let token = self.peek()?;
+        self.pos += 1;
+        match token {
+            "!" | "not" => self.unary().map(|v| !v),
+            "(" => {
+                let value = self.or_expr()?;
+                (self.peek() == Some(")")).then(|| {
+                    self.pos += 1;
+                    value
+                })
+            }
+            ")" | "," | "and" | "or" => None,
+            name => Some((self.is_set)(name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn unit(id: &str, contexts: &[&str], labels: &[&str]) -> RawMigrationUnit {
+        RawMigrationUnit {
+            id: id.to_string(),
+            sql: "SELECT 1;".to_string(),
+            source_file: PathBuf::from("db/changelog.xml"),
+            source_line_offset: 1,
+            run_in_transaction: true,
+            is_down: false,
+            suppressions: Default::default(),
+            changeset: ChangesetMeta {
+                contexts: contexts.iter().map(|c| c.to_string()).collect(),
+                labels: labels.iter().map(|l| l.to_string()).collect(),
+                ..Default::default()
+            },
+        }
+    }
+
+    fn applied(
+        units: Vec<RawMigrationUnit>,
+        contexts: &[&str],
+        labels: Option<&str>,
+    ) -> Vec<String> {
+        let config = LiquibaseConfig {
+            contexts: contexts.iter().map(|c| c.to_string()).collect(),
+            labels: labels.map(String::from),
+            ..Default::default()
+        };
+        let mut units = units;
+        retain_applied(&mut units, &config);
+        units.into_iter().map(|u| u.id).collect()
+    }
+
+    #[test]
+    fn test_expressions() {
+        let set = |name: &str| matches!(name, "prod" | "eu");
+        assert!(evaluate("prod", set));
+        assert!(evaluate("PROD", set));
+        assert!(!evaluate("legacy", set));
+        assert!(evaluate("!legacy", set));
+        assert!(evaluate("not legacy and prod", set));
+        assert!(!evaluate("prod and legacy", set));
+        assert!(evaluate("legacy or eu", set));
+        assert!(evaluate("legacy, eu", set));
+        assert!(!evaluate("!(prod or legacy)", set));
+        assert!(evaluate("(legacy or prod) and eu", set));
+        // Unparseable expressions keep the changeset.
+        assert!(evaluate("prod and", set));
+        assert!(evaluate("(legacy", set));
+    }
+
+    #[test]
+    fn test_no_filter_keeps_everything() {
+        let units = vec![unit("1", &["legacy"], &[]), unit("2", &[], &["billing"])];
+        assert_eq!(applied(units, &[], None), vec!["1", "2"]);
+    }
+
+    #[test]
+    fn test_contexts_filter() {
+        let units = vec![
+            unit("plain", &[], &[]),
+            unit("legacy", &["legacy"], &[]),
+            unit("prod-or-staging", &["prod", "staging"], &[]),
+            unit("not-legacy", &["!legacy"], &[]),
+            unit("prod-eu", &["prod and eu"], &[]),
+        ];
+        assert_eq!(
+            applied(units, &["Prod"], None),
+            vec!["plain", "prod-or-staging", "not-legacy"]
+        );
+    }
+
+    #[test]
+    fn test_labels_filter() {
+        let units = vec![
+            unit("plain", &[], &[]),
+            unit("billing", &[], &["billing"]),
+            unit("billing-legacy", &[], &["billing", "legacy"]),
+            unit("core", &[], &["core"]),
+        ];
+        assert_eq!(
+            applied(units, &[], Some("billing and !legacy")),
+            vec!["plain", "billing"]
+        );
+    }
+}
@@ -11,8 +11,9 @@
 //!
 //! This module parses those markers and extracts the SQL between them.
 
-use crate::input::LoadError;
-use crate::input::RawMigrationUnit;
+use crate::input::psql::replace_batch_separators;
+use crate::input::{ChangesetMeta, LoadError, RawMigrationUnit};
+use crate::suppress::Suppressions;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -137,6 +138,7 @@ impl UpdateSqlLoader {
 #[derive(Debug)]
 struct ParsedChangeset {
     id: String,
+    author: Option<String>,
     source_file: String,
     sql_lines: Vec<String>,
 }
@@ -150,18 +152,21 @@ struct ParsedChangeset {
 /// ```
 ///
 /// This function extracts SQL between consecutive changeset markers.
+/// Liquibase writes a changeset's `endDelimiter` after each statement; `GO`
+/// and `/` separator lines are turned into `;` so the SQL parses.
 pub fn parse_updatesql_output(output: &str) -> Result<Vec<RawMigrationUnit>, LoadError> {
     let mut changesets: Vec<ParsedChangeset> = Vec::new();
     let mut current: Option<ParsedChangeset> = None;
 
     for line in output.lines() {
-        if let Some((file, id)) = parse_changeset_marker(line) {
+        if let Some((file, id, author)) = parse_changeset_marker(line) {
             // Save previous changeset if any
             if let Some(cs) = current.take() {
                 changesets.push(cs);
             }
             current = Some(ParsedChangeset {
                 id,
+                author,
                 source_file: file,
                 sql_lines: Vec::new(),
             });
@@ -184,7 +189,7 @@ pub fn parse_updatesql_output(output: &str) -> Result<Vec<RawMigrationUnit>, Loa
         .into_iter()
         .filter(|cs| !cs.sql_lines.is_empty())
         .map(|cs| {
-            let sql = cs.sql_lines.join("\n");
+            let sql = replace_batch_separators(&cs.sql_lines.join("\n"));
             RawMigrationUnit {
                 id: cs.id,
                 sql,
@@ -192,6 +197,13 @@ pub fn parse_updatesql_output(output: &str) -> Result<Vec<RawMigrationUnit>, Loa
                 source_line_offset: 1,
                 run_in_transaction: true, // update-sql doesn't reliably expose this
                 is_down: false,
+                suppressions: Suppressions::default(),
+                // Comments, contexts, and labels are not in the output; the
+                // loader fills them in from the changelog.
+                changeset: ChangesetMeta {
+                    author: cs.author,
+                    ..Default::default()
+                },
             }
         })
         .collect();
@@ -204,8 +216,8 @@ pub fn parse_updatesql_output(output: &str) -> Result<Vec<RawMigrationUnit>, Loa
 /// Expected format: `-- Changeset <file>::<id>::<author>`
 /// Also handles: `-- Changeset <file>::<id>::<author> (with extra info)`
 ///
-/// Returns `Some((file, id))` if the line matches, `None` otherwise.
-fn parse_changeset_marker(line: &str) -> Option<(String, String)> {
+/// Returns `Some((file, id, author))` if the line matches, `None` otherwise.
+fn parse_changeset_marker(line: &str) -> Option<(String, String, Option<String>)> {
     let trimmed = line.trim();
 
     // Match "-- Changeset " prefix (case-insensitive on "Changeset")
@@ -223,8 +235,12 @@ fn parse_changeset_marker(line: &str) -> Option<(String, String)> {
 
     let file = parts[0].trim().to_string();
     let id = parts[1].trim().to_string();
+    let author = parts
+        .get(2)
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty());
 
-    Some((file, id))
+    Some((file, id, author))
 }
 
 /// Check if a line is a Liquibase internal comment that should be skipped.
@@ -271,7 +287,11 @@ mod tests {
         let result = parse_changeset_marker(line);
         assert_eq!(
             result,
-            Some(("changelog.xml".to_string(), "20240315-1".to_string()))
+            Some((
+                "changelog.xml".to_string(),
+                "20240315-1".to_string(),
+                Some("robert".to_string())
+            ))
         );
     }
 
@@ -281,7 +301,11 @@ mod tests {
         let result = parse_changeset_marker(line);
         assert_eq!(
             result,
-            Some(("db/changelog.xml".to_string(), "create-table".to_string()))
+            Some((
+                "db/changelog.xml".to_string(),
+                "create-table".to_string(),
+                Some("admin".to_string())
+            ))
         );
     }
 
@@ -343,6 +367,7 @@ INSERT INTO DATABASECHANGELOG (ID, AUTHOR) VALUES ('create-orders', 'bob');
         assert_eq!(units.len(), 2);
 
         assert_eq!(units[0].id, "create-users");
+        assert_eq!(units[0].changeset.author.as_deref(), Some("alice"));
         assert!(units[0].sql.contains("CREATE TABLE users"));
 
         assert_eq!(units[1].id, "create-orders");
@@ -402,4 +427,22 @@ ALTER TABLE a ADD COLUMN name text;
         assert!(units[0].sql.contains("CREATE TABLE b"));
         assert!(units[0].sql.contains("ALTER TABLE a"));
     }
+
+    #[test]
+    fn test_parse_updatesql_go_end_delimiter() {
+        let output = r#"-- Changeset changelog.xml::fn::dev
+CREATE FUNCTION touch() RETURNS trigger AS $$
+BEGIN
+    NEW.updated_at := now();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql
+GO
+"#;
+
+        let units = parse_updatesql_output(output).expect("Should parse GO delimiter");
+        assert_eq!(units.len(), 1);
+        assert!(units[0].sql.ends_with("$$ LANGUAGE plpgsql\n; "));
+        assert!(!units[0].sql.contains("GO"));
+    }
 }
@@ -3,11 +3,19 @@
 //! Supports SQL files and Liquibase XML changesets.
 
 use crate::parser::ir::{IrNode, Located};
-use std::path::PathBuf;
+use crate::suppress::Suppressions;
+use std::path::{Component, Path, PathBuf};
 use thiserror::Error;
 
+pub mod changed;
+pub mod changelog_format;
+pub mod encoding;
+pub mod git;
 pub mod liquibase_bridge;
+pub mod liquibase_compare;
+pub mod liquibase_filter;
 pub mod liquibase_updatesql;
+pub mod psql;
 pub mod sql;
 
 /// A single migration unit: one changeset (Liquibase) or one file (go-migrate).
@@ -33,6 +41,27 @@ pub struct MigrationUnit {
 
     /// Is this a down/rollback migration?
     pub is_down: bool,
+
+    /// Inline suppression and acknowledgment directives that apply to this
+    /// unit, read from its source file by the loader.
+    pub suppressions: Suppressions,
+
+    /// Liquibase changeset attributes. Empty for SQL-file units.
+    pub changeset: ChangesetMeta,
+}
+
+/// Attributes of a Liquibase `<changeSet>` that are not needed to parse its
+/// SQL, passed through so that both Liquibase loaders report the same unit.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChangesetMeta {
+    /// The `author` attribute.
+    pub author: Option<String>,
+    /// Text of the `<comment>` element.
+    pub comments: Option<String>,
+    /// Context expressions from the `context` / `contextFilter` attribute.
+    pub contexts: Vec<String>,
+    /// Label expressions from the `labels` attribute.
+    pub labels: Vec<String>,
 }
 
 /// An ordered sequence of migration units representing the full history.
@@ -62,6 +91,13 @@ pub struct RawMigrationUnit {
 
     /// Is this a down/rollback migration?
     pub is_down: bool,
+
+    /// Inline suppression and acknowledgment directives that apply to this
+    /// unit, read from its source file by the loader.
+    pub suppressions: Suppressions,
+
+    /// Liquibase changeset attributes, carried through to the `MigrationUnit`.
+    pub changeset: ChangesetMeta,
 }
 
 impl RawMigrationUnit {
@@ -93,10 +129,54 @@ impl RawMigrationUnit {
             source_line_offset: self.source_line_offset,
             run_in_transaction: self.run_in_transaction,
             is_down: self.is_down,
+            suppressions: self.suppressions,
+            changeset: self.changeset,
         }
     }
 }
 
+/// Whether `path` lies inside `root` once `..` components and symlinks are
+/// resolved. For a path that does not exist, the nearest existing ancestor
+/// is resolved and the rest is applied lexically.
+pub fn is_within_root(root: &Path, path: &Path) -> bool {
+    resolve_path(path).starts_with(resolve_path(root))
+}
+
+fn resolve_path(path: &Path) -> PathBuf {
+    if let Ok(canonical) = path.canonicalize() {
+        return canonical;
+    }
+    let path = normalize_lexically(&std::path::absolute(path).unwrap_or_else(|_| path.into()));
+    let mut existing = path.as_path();
+    let mut rest = Vec::new();
+    while let (Some(parent), Some(name)) = (existing.parent(), existing.file_name()) {
+        rest.push(name);
+        existing = parent;
+        if let Ok(canonical) = existing.canonicalize() {
+            return rest
+                .iter()
+                .rev()
+                .fold(canonical, |acc, name| acc.join(name));
+        }
+    }
+    path
+}
+
+/// `path` with `.` removed and each `..` applied to the component before it.
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,6 +190,8 @@ mod tests {
             source_line_offset: offset,
             run_in_transaction: true,
             is_down: false,
+            suppressions: Default::default(),
+            changeset: Default::default(),
         }
     }
 
@@ -220,6 +302,13 @@ mod tests {
             source_line_offset: 7,
             run_in_transaction: false,
             is_down: true,
+            suppressions: Default::default(),
+            changeset: ChangesetMeta {
+                author: Some("robert".to_string()),
+                comments: Some("Create orders".to_string()),
+                contexts: vec!["prod".to_string()],
+                labels: vec!["billing".to_string()],
+            },
         };
         let unit = raw.into_migration_unit();
         assert_eq!(unit.id, "cs-42");
@@ -227,6 +316,23 @@ mod tests {
         assert_eq!(unit.source_line_offset, 7);
         assert!(!unit.run_in_transaction);
         assert!(unit.is_down);
+        assert_eq!(unit.changeset.author.as_deref(), Some("robert"));
+        assert_eq!(unit.changeset.labels, vec!["billing"]);
+    }
+
+    #[test]
+    fn test_is_within_root() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path().join("repo");
+        std::fs::create_dir_all(root.join("db")).expect("mkdir");
+        std::fs::write(root.join("db/child.xml"), "").expect("write");
+        std::fs::write(dir.path().join("secret.txt"), "").expect("write");
+
+        assert!(is_within_root(&root, &root.join("db/child.xml")));
+        assert!(is_within_root(&root, &root.join("db/../db/missing.xml")));
+        assert!(!is_within_root(&root, &root.join("db/../../secret.txt")));
+        assert!(!is_within_root(&root, &dir.path().join("secret.txt")));
+        assert!(!is_within_root(&root, Path::new("/etc/passwd")));
     }
 }
 
@@ -247,4 +353,10 @@ pub enum LoadError {
 
     #[error("Configuration error: {message}")]
     Config { message: String },
+
+    #[error(
+        "{path} is outside the include root {root}; files included by a changelog must \
+         stay inside it (migrations.include_root)"
+    )]
+    OutsideIncludeRoot { path: PathBuf, root: PathBuf },
 }
@@ -0,0 +1,262 @@
+//! psql meta-command handling for plain SQL migrations
+//!
+//! Migrations written for `psql -f` may contain backslash meta-commands
+//! (`\set`, `\echo`, `\i other.sql`, ...) that are not SQL. pg_query rejects
+//! them, which would turn the whole file into `Unparseable`. The loader
+//! blanks these lines out before parsing and reports `\i`/`\ir` includes so
+//! they can be resolved separately.
+//!
+//! Lines are blanked byte-for-byte, so statement line numbers and byte
+//! offsets in the remaining SQL are unchanged.
+//!
+//! The same lexer finds batch separator lines (`GO`, `/`) in Liquibase
+//! changesets that set `endDelimiter`, often together with
+//! `splitStatements="false"` so function bodies are not split at their
+//! semicolons. Those lines become statement terminators.
+
+use crate::parser::lex::dollar_tag;
+
+/// A `\i`, `\include`, `\ir`, or `\include_relative` meta-command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PsqlInclude {
+    /// 1-based line of the meta-command.
+    pub line: usize,
+    /// Byte offsets of the meta-command line (without the newline).
+    pub start_offset: usize,
+    pub end_offset: usize,
+    /// The file argument, unquoted.
+    pub target: String,
+}
+
+/// SQL text with psql meta-commands removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrippedSql {
+    pub sql: String,
+    /// Include meta-commands, in source order.
+    pub includes: Vec<PsqlInclude>,
+}
+
+/// Lexer state carried across lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum State {
+    Normal,
+    SingleQuote,
+    DoubleQuote,
+    BlockComment(usize),
+    DollarQuote(String),
+}
+
+/// Blank out every line whose first non-blank character is a backslash,
+/// unless the line starts inside a string, quoted identifier, dollar-quoted
+/// body, or block comment.
+pub fn strip_meta_commands(source: &str) -> StrippedSql {
+    let mut sql = String::with_capacity(source.len());
+    let mut includes = Vec::new();
+    let mut state = State::Normal;
+    let mut offset = 0;
+
+    for (idx, line) in source.split_inclusive('\n').enumerate() {
+        let content = line.trim_end_matches(['\n', '\r']);
+        if state == State::Normal && content.trim_start().starts_with('\\') {
+            if let Some(target) = include_target(content.trim_start()) {
+                includes.push(PsqlInclude {
+                    line: idx + 1,
+                    start_offset: offset,
+                    end_offset: offset + content.len(),
+                    target,
+                });
+            }
+            sql.extend(std::iter::repeat_n(' ', content.len()));
+            sql.push_str(&line[content.len()..]);
+        } else {
+            state = scan_line(line, state);
+            sql.push_str(line);
+        }
+        offset += line.len();
+    }
+
+    StrippedSql { sql, includes }
+}
+
+/// Turn every line that holds only a batch separator (`GO` in any case, or
+/// `/`) into a `;` padded to the same length, unless the line starts inside
+/// a string, quoted identifier, dollar-quoted body, or block comment.
+///
+/// Line numbers and byte offsets are unchanged. A separator after a
+/// statement that already ends in `;` leaves an empty statement, which
+/// PostgreSQL accepts.
+pub fn replace_batch_separators(source: &str) -> String {
+    let mut sql = String::with_capacity(source.len());
+    let mut state = State::Normal;
+
+    for line in source.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
+        let word = content.trim();
+        if state == State::Normal && (word.eq_ignore_ascii_case("go") || word == "/") {
+            sql.push(';');
+            sql.extend(std::iter::repeat_n(' ', content.len() - 1));
+            sql.push_str(&line[content.len()..]);
+        } else {
+            state = scan_line(line, state);
+            sql.push_str(line);
+        }
+    }
+
+    sql
+}
+
+/// The file argument of an include meta-command, if `command` is one.
+fn include_target(command: &str) -> Option<String> {
+    let mut parts = command.splitn(2, char::is_whitespace);
+    let name = parts.next()?;
+    if !matches!(name, "\\i" | "\\include" | "\\ir" | "\\include_relative") {
+        return None;
+    }
+    let arg = parts.next()?.trim();
+    let arg = arg
+        .strip_prefix('\'')
+        .and_then(|a| a.strip_suffix('\''))
+        .unwrap_or(arg);
+    (!arg.is_empty()).then(|| arg.to_string())
+}
+
+/// Advance the lexer state over one line of SQL.
+fn scan_line(line: &str, mut state: State) -> State {
+    let bytes = line.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match &state {
+            State::Normal => match bytes[i] {
+                b'-' if bytes.get(i + 1) == Some(&b'-') => return State::Normal,
+                b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                    state = State::BlockComment(1);
+                    i += 1;
+                }
+                b'\'' => state = State::SingleQuote,
+                b'"' => state = State::DoubleQuote,
+                b'$' => {
+                    if let Some(tag) = dollar_tag(&line[i..]) {
+                        i += tag.len() - 1;
+                        state = State::DollarQuote(tag.to_string());
+                    }
+                }
+                _ => {}
+            },
+            State::SingleQuote => {
+                if bytes[i] == b'\'' {
+                    state = State::Normal;
+                }
+            }
+            State::DoubleQuote => {
+                if bytes[i] == b'"' {
+                    state = State::Normal;
+                }
+            }
+            State::BlockComment(depth) => {
+                let depth = *depth;
+                if bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/') {
+                    state = if depth == 1 {
+                        State::Normal
+                    } else {
+                        State::BlockComment(depth - 1)
+                    };
+                    i += 1;
+                } else if bytes[i] == b'/' && bytes.get(i + 1) == Some(&b'*') {
+                    state = State::BlockComment(depth + 1);
+                    i += 1;
+                }
+            }
+            State::DollarQuote(tag) => {
+                if line[i..].starts_with(tag.as_str()) {
+                    i += tag.len() - 1;
+                    state = State::Normal;
+                }
+            }
+        }
+        i += 1;
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meta_commands_blanked_preserving_offsets() {
+        let source = "\\set ON_ERROR_STOP on\nCREATE TABLE t (id int);\n  \\echo done\n";
+        let stripped = strip_meta_commands(source);
+        assert_eq!(stripped.sql.len(), source.len());
+        assert_eq!(
+            stripped.sql,
+            format!(
+                "{}\nCREATE TABLE t (id int);\n{}\n",
+                " ".repeat(21),
+                " ".repeat(12)
+            )
+        );
+        assert!(stripped.includes.is_empty());
+    }
+
+    #[test]
+    fn test_includes_reported() {
+        let source =
+            "SELECT 1;\n\\i common/roles.sql\n\\ir 'helpers.sql'\n\\include_relative x.sql";
+        let stripped = strip_meta_commands(source);
+        let targets: Vec<_> = stripped
+            .includes
+            .iter()
+            .map(|i| (i.line, i.target.as_str()))
+            .collect();
+        assert_eq!(
+            targets,
+            vec![(2, "common/roles.sql"), (3, "helpers.sql"), (4, "x.sql")]
+        );
+        let first = &stripped.includes[0];
+        assert_eq!(
+            &source[first.start_offset..first.end_offset],
+            "\\i common/roles.sql"
+        );
+    }
+
+    #[test]
+    fn test_backslash_inside_dollar_quote_kept() {
+        let source = "CREATE FUNCTION f() RETURNS text AS $fn$\n\\echo not a command\n$fn$ LANGUAGE sql;\n\\echo after\n";
+        let stripped = strip_meta_commands(source);
+        assert!(stripped.sql.contains("\\echo not a command"));
+        assert!(!stripped.sql.contains("\\echo after"));
+    }
+
+    #[test]
+    fn test_backslash_inside_string_and_comment_kept() {
+        let source = "INSERT INTO t VALUES ('a\n\\b');\n/* note\n\\c db\n*/\n\\c db\n";
+        let stripped = strip_meta_commands(source);
+        assert!(stripped.sql.contains("\\b');"));
+        assert_eq!(stripped.sql.matches("\\c db").count(), 1);
+    }
+
+    #[test]
+    fn test_batch_separators_terminate_statements() {
+        let source = "CREATE FUNCTION f() RETURNS int AS $$\nBEGIN\n  RETURN 1;\nEND;\n$$ LANGUAGE plpgsql\nGO\nCREATE INDEX i ON t (a)\n  /\n";
+        let replaced = replace_batch_separators(source);
+        assert_eq!(replaced.len(), source.len());
+        assert!(replaced.contains("LANGUAGE plpgsql\n; \n"));
+        assert!(replaced.ends_with("ON t (a)\n;  \n"));
+    }
+
+    #[test]
+    fn test_batch_separator_inside_dollar_quote_kept() {
+        let source =
+            "CREATE FUNCTION f() RETURNS text AS $fn$\nSELECT 'x'\ngo\n$fn$ LANGUAGE sql;\nGO\n";
+        let replaced = replace_batch_separators(source);
+        assert!(replaced.contains("\ngo\n$fn$"));
+        assert!(replaced.ends_with("LANGUAGE sql;\n; \n"));
+    }
+
+    #[test]
+    fn test_line_comment_does_not_open_quote() {
+        let source = "SELECT 1; -- don't\n\\echo x\n";
+        let stripped = strip_meta_commands(source);
+        assert!(!stripped.sql.contains("\\echo"));
+    }
+}
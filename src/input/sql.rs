@@ -2,11 +2,18 @@
 //!
 //! Reads `.sql` migration files from disk, parses them into IR using the
 //! pg_query parser, and returns `MigrationUnit`s ready for catalog replay
-//! and linting.
+//! and linting. psql meta-commands are stripped before parsing (see
+//! [`crate::input::psql`]).
 
-use crate::input::{LoadError, MigrationHistory, MigrationUnit};
+use crate::input::encoding::read_source;
+use crate::input::psql::{PsqlInclude, strip_meta_commands};
+use crate::input::{LoadError, MigrationHistory, MigrationUnit, is_within_root};
+use crate::parser::ir::{IrNode, Located, SourceSpan};
 use crate::parser::pg_query::parse_sql;
+use crate::suppress::parse_suppressions;
+use ignore::WalkBuilder;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Loader for plain SQL migration files.
 ///
@@ -15,14 +22,65 @@ use std::path::{Path, PathBuf};
 ///
 /// Down migrations are detected by filename suffix: the stem (minus `.sql`)
 /// must end with `.down` or `_down`.
+///
+/// When scanning a directory, files matched by `.gitignore` (in the directory
+/// or any parent) and `.git/info/exclude` are skipped, and symlinked files
+/// are followed. Both can be turned off. Files passed explicitly are always
+/// loaded.
+///
+/// psql `\i`/`\ir` includes are dropped unless include resolution is
+/// enabled, in which case the included file's statements are linted as if
+/// they stood on the include line. With an include root set, includes
+/// outside it are not read.
+#[derive(Debug, Clone)]
 pub struct SqlLoader {
     run_in_transaction: bool,
+    respect_gitignore: bool,
+    follow_symlinks: bool,
+    resolve_includes: bool,
+    include_root: Option<PathBuf>,
 }
 
+/// Maximum nesting of resolved psql includes.
+const MAX_INCLUDE_DEPTH: usize = 16;
+
 impl SqlLoader {
     /// Create a new `SqlLoader` with the given default `run_in_transaction` value.
     pub fn new(run_in_transaction: bool) -> Self {
-        Self { run_in_transaction }
+        Self {
+            run_in_transaction,
+            respect_gitignore: true,
+            follow_symlinks: true,
+            resolve_includes: false,
+            include_root: None,
+        }
+    }
+
+    /// Whether directory scans skip files ignored by git (default: `true`).
+    pub fn with_respect_gitignore(mut self, respect_gitignore: bool) -> Self {
+        self.respect_gitignore = respect_gitignore;
+        self
+    }
+
+    /// Whether directory scans load symlinked files (default: `true`).
+    /// When `false`, symlinks are skipped.
+    pub fn with_follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = follow_symlinks;
+        self
+    }
+
+    /// Whether psql `\i`/`\ir` includes are read and parsed (default:
+    /// `false`). Paths are resolved relative to the including file.
+    pub fn with_resolve_includes(mut self, resolve_includes: bool) -> Self {
+        self.resolve_includes = resolve_includes;
+        self
+    }
+
+    /// Directory resolved includes must stay inside (default: none, any
+    /// readable file). An include outside it is treated like a missing one.
+    pub fn with_include_root(mut self, include_root: Option<PathBuf>) -> Self {
+        self.include_root = include_root;
+        self
     }
 
     /// Load migrations from the given paths.
@@ -35,54 +93,49 @@ impl SqlLoader {
     /// collects all SQL files across all paths, sorts them, and returns the
     /// complete migration history.
     pub fn load(&self, paths: &[PathBuf]) -> Result<MigrationHistory, LoadError> {
-        let mut sql_files: Vec<PathBuf> = Vec::new();
-
-        for path in paths {
-            if path.is_dir() {
-                let entries = collect_sql_files(path)?;
-                sql_files.extend(entries);
-            } else if path.is_file() {
-                if is_sql_file(path) {
-                    sql_files.push(path.clone());
-                }
-            } else {
-                return Err(LoadError::Io {
-                    path: path.clone(),
-                    source: std::io::Error::new(
-                        std::io::ErrorKind::NotFound,
-                        format!("Path does not exist: {}", path.display()),
-                    ),
-                });
-            }
-        }
-
-        // Sort lexicographically by filename to ensure deterministic ordering
-        sql_files.sort_by(|a, b| {
-            let a_name = a.file_name().unwrap_or_default();
-            let b_name = b.file_name().unwrap_or_default();
-            a_name.cmp(b_name)
-        });
-
-        let mut units = Vec::new();
-        for file in &sql_files {
-            let unit = self.load_file(file)?;
-            units.push(unit);
-        }
-
+        let units = self.units(paths)?.collect::<Result<Vec<_>, _>>()?;
         Ok(MigrationHistory { units })
     }
 
+    /// Stream migrations from the given paths, one unit at a time.
+    ///
+    /// Resolves and sorts the file list eagerly (so missing paths fail
+    /// up front), but reads and parses each file only when the iterator
+    /// reaches it. Callers that replay and drop each unit keep at most one
+    /// parsed unit in memory, regardless of history size.
+    pub fn units(
+        &self,
+        paths: &[PathBuf],
+    ) -> Result<impl Iterator<Item = Result<MigrationUnit, LoadError>> + use<>, LoadError> {
+        let sql_files = self.sorted_sql_files(paths)?;
+        let loader = self.clone();
+        Ok(sql_files
+            .into_iter()
+            .map(move |file| loader.load_file(&file)))
+    }
+
     /// Load a single SQL file and parse it into a `MigrationUnit`.
     ///
-    /// The file is read entirely into memory, parsed into IR nodes, and
-    /// wrapped in a `MigrationUnit` with metadata derived from the filename.
+    /// The file is read entirely into memory (decoded as described in
+    /// [`crate::input::encoding`]), parsed into IR nodes, and wrapped in a
+    /// `MigrationUnit` with metadata derived from the filename.
     pub fn load_file(&self, path: &Path) -> Result<MigrationUnit, LoadError> {
-        let source = std::fs::read_to_string(path).map_err(|e| LoadError::Io {
+        let source = read_source(path).map_err(|e| LoadError::Io {
             path: path.to_path_buf(),
             source: e,
         })?;
 
-        let statements = parse_sql(&source);
+        Ok(self.load_source(path, &source))
+    }
+
+    /// Parse in-memory SQL `source` into a `MigrationUnit` as if it had been
+    /// read from `path`.
+    ///
+    /// Used by embedders (bindings, daemons) that receive SQL text rather
+    /// than files. `path` only provides the unit id, the down-migration
+    /// suffix check, and the file reported on findings.
+    pub fn load_source(&self, path: &Path, source: &str) -> MigrationUnit {
+        let statements = self.parse_with_includes(path, source, &mut vec![path.to_path_buf()]);
 
         let filename = path
             .file_name()
@@ -91,51 +144,208 @@ impl SqlLoader {
 
         let is_down = is_down_migration(&filename);
 
-        Ok(MigrationUnit {
+        MigrationUnit {
             id: filename,
             statements,
             source_file: path.to_path_buf(),
             source_line_offset: 1,
             run_in_transaction: self.run_in_transaction,
             is_down,
-        })
+            suppressions: parse_suppressions(source),
+            changeset: Default::default(),
+        }
     }
 }
 
 impl Default for SqlLoader {
     fn default() -> Self {
-        Self {
-            run_in_transaction: true,
-        }
+        Self::new(true)
     }
 }
 
-/// Collect all `.sql` files from a directory (non-recursive).
-fn collect_sql_files(dir: &Path) -> Result<Vec<PathBuf>, LoadError> {
-    let entries = std::fs::read_dir(dir).map_err(|e| LoadError::Io {
-        path: dir.to_path_buf(),
-        source: e,
-    })?;
+impl SqlLoader {
+    /// Strip psql meta-commands from `source`, parse it, and splice in the
+    /// statements of resolved includes. `stack` holds the files currently
+    /// being included, to break cycles.
+    fn parse_with_includes(
+        &self,
+        path: &Path,
+        source: &str,
+        stack: &mut Vec<PathBuf>,
+    ) -> Vec<Located<IrNode>> {
+        let stripped = strip_meta_commands(source);
+        let mut statements = parse_sql(&stripped.sql);
+        if !self.resolve_includes || stripped.includes.is_empty() {
+            return statements;
+        }
+        for include in &stripped.includes {
+            statements.extend(self.include_statements(path, include, stack));
+        }
+        statements.sort_by_key(|s| s.span.start_line);
+        statements
+    }
 
-    let mut files: Vec<PathBuf> = Vec::new();
+    /// Statements of the file named by `include`, all located on the
+    /// include line. An include that cannot be read (missing, cyclic,
+    /// nested too deep, or outside the include root) becomes one
+    /// `Unparseable` statement.
+    fn include_statements(
+        &self,
+        from: &Path,
+        include: &PsqlInclude,
+        stack: &mut Vec<PathBuf>,
+    ) -> Vec<Located<IrNode>> {
+        let span = SourceSpan {
+            start_line: include.line,
+            end_line: include.line,
+            start_offset: include.start_offset,
+            end_offset: include.end_offset,
+        };
+        let target = from
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join(&include.target);
+
+        let source = if let Some(root) = self
+            .include_root
+            .as_deref()
+            .filter(|root| !is_within_root(root, &target))
+        {
+            Err(format!("outside the include root {}", root.display()))
+        } else if stack.contains(&target) {
+            Err("include cycle".to_string())
+        } else if stack.len() > MAX_INCLUDE_DEPTH {
+            Err(format!("more than {MAX_INCLUDE_DEPTH} nested includes"))
+        } else {
+            read_source(&target).map_err(|e| e.to_string())
+        };
+        let source = match source {
+            Ok(source) => source,
+            Err(reason) => {
+                eprintln!(
+                    "WARNING: {}:{}: cannot include '{}': {reason}",
+                    from.display(),
+                    include.line,
+                    target.display()
+                );
+                return vec![Located {
+                    node: IrNode::Unparseable {
+                        raw_sql: format!("\\i {}", include.target),
+                        table_hint: None,
+                    },
+                    span,
+                    raw_sql: Arc::from(format!("\\i {}", include.target)),
+                }];
+            }
+        };
 
-    for entry in entries {
-        let entry = entry.map_err(|e| LoadError::Io {
-            path: dir.to_path_buf(),
-            source: e,
-        })?;
+        stack.push(target.clone());
+        let mut statements = self.parse_with_includes(&target, &source, stack);
+        stack.pop();
+        for stmt in &mut statements {
+            stmt.span = span.clone();
+        }
+        statements
+    }
+
+    /// Collect every SQL file under `paths`, sorted lexicographically by filename.
+    fn sorted_sql_files(&self, paths: &[PathBuf]) -> Result<Vec<PathBuf>, LoadError> {
+        let mut sql_files: Vec<PathBuf> = Vec::new();
 
-        let path = entry.path();
-        if path.is_file() && is_sql_file(&path) {
-            files.push(path);
+        for path in paths {
+            if path.is_dir() {
+                let entries = self.collect_sql_files(path)?;
+                sql_files.extend(entries);
+            } else if path.is_file() {
+                if is_sql_file(path) {
+                    sql_files.push(path.clone());
+                }
+            } else {
+                return Err(LoadError::Io {
+                    path: path.clone(),
+                    source: std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        format!("Path does not exist: {}", path.display()),
+                    ),
+                });
+            }
         }
+
+        // Sort lexicographically by filename to ensure deterministic ordering;
+        // the same filename in two directories falls back to the full path.
+        sql_files.sort_by(|a, b| {
+            let a_name = a.file_name().unwrap_or_default();
+            let b_name = b.file_name().unwrap_or_default();
+            a_name.cmp(b_name).then_with(|| a.cmp(b))
+        });
+
+        Ok(sql_files)
     }
 
-    Ok(files)
+    /// Collect all `.sql` files from a directory (non-recursive).
+    ///
+    /// Fails if two files differ only in case: on a case-insensitive
+    /// filesystem (macOS, Windows) only one of them survives a checkout, so
+    /// the migration history would depend on where it is linted.
+    fn collect_sql_files(&self, dir: &Path) -> Result<Vec<PathBuf>, LoadError> {
+        let walker = WalkBuilder::new(dir)
+            .standard_filters(false)
+            .max_depth(Some(1))
+            .git_ignore(self.respect_gitignore)
+            .git_exclude(self.respect_gitignore)
+            .parents(self.respect_gitignore)
+            .require_git(false)
+            .build();
+
+        let mut files: Vec<PathBuf> = Vec::new();
+
+        for entry in walker {
+            let entry = entry.map_err(|e| LoadError::Io {
+                path: dir.to_path_buf(),
+                source: e
+                    .into_io_error()
+                    .unwrap_or_else(|| std::io::Error::other("invalid .gitignore pattern")),
+            })?;
+
+            if entry.depth() == 0 || (!self.follow_symlinks && entry.path_is_symlink()) {
+                continue;
+            }
+            let path = entry.into_path();
+            if path.is_file() && is_sql_file(&path) {
+                files.push(path);
+            }
+        }
+
+        let mut names: Vec<(String, &PathBuf)> = files
+            .iter()
+            .map(|f| {
+                (
+                    f.file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .to_lowercase(),
+                    f,
+                )
+            })
+            .collect();
+        names.sort();
+        if let Some(pair) = names.windows(2).find(|w| w[0].0 == w[1].0) {
+            return Err(LoadError::Config {
+                message: format!(
+                    "'{}' and '{}' differ only in case; rename one so the history is the \
+                     same on case-insensitive filesystems",
+                    pair[0].1.display(),
+                    pair[1].1.display()
+                ),
+            });
+        }
+
+        Ok(files)
+    }
 }
 
 /// Check if a path has a `.sql` extension.
-fn is_sql_file(path: &Path) -> bool {
+pub(crate) fn is_sql_file(path: &Path) -> bool {
     path.extension()
         .map(|ext| ext.eq_ignore_ascii_case("sql"))
         .unwrap_or(false)
@@ -288,6 +498,29 @@ mod tests {
         assert_eq!(unit.statements.len(), 3);
     }
 
+    #[test]
+    fn test_units_streams_in_filename_order() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        fs::write(dir.path().join("V002__b.sql"), "SELECT 1;").expect("write");
+        fs::write(dir.path().join("V001__a.sql"), "SELECT 1;").expect("write");
+
+        let loader = SqlLoader::default();
+        let mut units = loader
+            .units(&[dir.path().to_path_buf()])
+            .expect("Failed to open stream");
+
+        assert_eq!(units.next().unwrap().unwrap().id, "V001__a.sql");
+        assert_eq!(units.next().unwrap().unwrap().id, "V002__b.sql");
+        assert!(units.next().is_none());
+    }
+
+    #[test]
+    fn test_units_missing_path_fails_before_streaming() {
+        let loader = SqlLoader::default();
+        let result = loader.units(&[PathBuf::from("/nonexistent/path/to/migrations")]);
+        assert!(matches!(result, Err(LoadError::Io { .. })));
+    }
+
     #[test]
     fn test_loader_directory() {
         let dir = tempfile::tempdir().expect("Failed to create temp dir");
@@ -405,8 +638,159 @@ mod tests {
         fs::write(dir.path().join("changelog.xml"), "<xml/>").expect("write");
         fs::write(dir.path().join("notes.txt"), "notes").expect("write");
 
-        let files = collect_sql_files(dir.path()).expect("collect failed");
+        let files = SqlLoader::default()
+            .collect_sql_files(dir.path())
+            .expect("collect failed");
         assert_eq!(files.len(), 1);
         assert!(files[0].to_string_lossy().contains("migration.sql"));
     }
+
+    #[test]
+    fn test_load_source_strips_psql_meta_commands() {
+        let source = "\\set ON_ERROR_STOP on\nCREATE TABLE t (id int);\n\\echo done\n";
+        let unit = SqlLoader::default().load_source(Path::new("V001__t.sql"), source);
+        assert_eq!(unit.statements.len(), 1);
+        assert!(matches!(unit.statements[0].node, IrNode::CreateTable(_)));
+        assert_eq!(unit.statements[0].span.start_line, 2);
+    }
+
+    #[test]
+    fn test_resolved_include_located_on_include_line() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        fs::write(dir.path().join("roles.sql"), "CREATE TABLE roles (id int);").expect("write");
+        let main = dir.path().join("V001__init.sql");
+        fs::write(&main, "CREATE TABLE users (id int);\n\\ir roles.sql\n").expect("write");
+
+        let unit = SqlLoader::default().load_file(&main).expect("load");
+        assert_eq!(unit.statements.len(), 1, "includes are dropped by default");
+
+        let unit = SqlLoader::default()
+            .with_resolve_includes(true)
+            .load_file(&main)
+            .expect("load");
+        assert_eq!(unit.statements.len(), 2);
+        assert_eq!(unit.statements[1].span.start_line, 2);
+    }
+
+    #[test]
+    fn test_unresolvable_include_is_unparseable() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let main = dir.path().join("V001__init.sql");
+        fs::write(&main, "\\i V001__init.sql\n\\i missing.sql\n").expect("write");
+
+        let unit = SqlLoader::default()
+            .with_resolve_includes(true)
+            .load_file(&main)
+            .expect("load");
+        let lines: Vec<usize> = unit
+            .statements
+            .iter()
+            .filter(|s| matches!(s.node, IrNode::Unparseable { .. }))
+            .map(|s| s.span.start_line)
+            .collect();
+        assert_eq!(lines, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_include_outside_root_not_read() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let repo = dir.path().join("repo");
+        fs::create_dir_all(&repo).expect("mkdir");
+        fs::write(
+            dir.path().join("outside.sql"),
+            "CREATE TABLE leak (id int);",
+        )
+        .expect("write");
+        fs::write(repo.join("inside.sql"), "CREATE TABLE roles (id int);").expect("write");
+        let main = repo.join("V001__init.sql");
+        fs::write(&main, "\\ir inside.sql\n\\ir ../outside.sql\n").expect("write");
+
+        let unit = SqlLoader::default()
+            .with_resolve_includes(true)
+            .with_include_root(Some(repo.clone()))
+            .load_file(&main)
+            .expect("load");
+        let unparseable: Vec<usize> = unit
+            .statements
+            .iter()
+            .filter(|s| matches!(s.node, IrNode::Unparseable { .. }))
+            .map(|s| s.span.start_line)
+            .collect();
+        assert_eq!(unparseable, vec![2]);
+    }
+
+    #[test]
+    fn test_collect_sql_files_respects_gitignore() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        fs::write(dir.path().join(".gitignore"), "generated_*.sql\n").expect("write");
+        fs::write(dir.path().join("V001__create.sql"), "SELECT 1;").expect("write");
+        fs::write(dir.path().join("generated_dump.sql"), "SELECT 2;").expect("write");
+
+        let files = SqlLoader::default()
+            .collect_sql_files(dir.path())
+            .expect("collect failed");
+        assert_eq!(files, vec![dir.path().join("V001__create.sql")]);
+
+        let files = SqlLoader::default()
+            .with_respect_gitignore(false)
+            .collect_sql_files(dir.path())
+            .expect("collect failed");
+        assert_eq!(files.len(), 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_collect_sql_files_symlinks() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let shared = tempfile::tempdir().expect("Failed to create temp dir");
+        fs::write(shared.path().join("common.sql"), "SELECT 1;").expect("write");
+        std::os::unix::fs::symlink(
+            shared.path().join("common.sql"),
+            dir.path().join("V001__common.sql"),
+        )
+        .expect("symlink");
+
+        let files = SqlLoader::default()
+            .collect_sql_files(dir.path())
+            .expect("collect failed");
+        assert_eq!(files.len(), 1);
+
+        let files = SqlLoader::default()
+            .with_follow_symlinks(false)
+            .collect_sql_files(dir.path())
+            .expect("collect failed");
+        assert!(files.is_empty());
+    }
+
+    #[test]
+    fn test_collect_sql_files_rejects_case_collision() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        fs::write(dir.path().join("V001__create.sql"), "SELECT 1;").expect("write");
+        // Skip on case-insensitive filesystems, where this overwrites the first file.
+        fs::write(dir.path().join("v001__CREATE.sql"), "SELECT 2;").expect("write");
+        if fs::read_dir(dir.path()).expect("read_dir").count() < 2 {
+            return;
+        }
+
+        let result = SqlLoader::default().collect_sql_files(dir.path());
+        assert!(matches!(result, Err(LoadError::Config { .. })));
+    }
+
+    #[test]
+    fn test_same_filename_in_two_directories_sorted_by_path() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let (b, a) = (dir.path().join("b"), dir.path().join("a"));
+        for d in [&b, &a] {
+            fs::create_dir(d).expect("mkdir");
+            fs::write(d.join("V001__init.sql"), "SELECT 1;").expect("write");
+        }
+
+        let files = SqlLoader::default()
+            .sorted_sql_files(&[b.clone(), a.clone()])
+            .expect("collect failed");
+        assert_eq!(
+            files,
+            vec![a.join("V001__init.sql"), b.join("V001__init.sql")]
+        );
+    }
 }
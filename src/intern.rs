@@ -0,0 +1,142 @@
+//! String interning for identifiers.
+//!
+//! Large histories repeat the same table keys and type names thousands of
+//! times across IR nodes and catalog snapshots. Interning hands out shared
+//! `Arc<str>` handles so each distinct string is allocated once, and clones
+//! (e.g. the per-unit `catalog_before` snapshot) are reference-count bumps.
+//!
+//! Interned: `QualifiedName`'s catalog key, `TypeName::name`, and the
+//! catalog's table keys and index-name lookup keys. `QualifiedName`'s `name`
+//! and `schema`, index names in `IndexState`, and column names stay plain
+//! `String`s.
+//!
+//! Each thread interns into its own table, so there is no lock to contend
+//! on, and a thread's table goes away with it (one daemon connection, one
+//! table). Strings no longer held outside the table are dropped as it grows,
+//! so a long-lived thread does not keep every identifier it ever saw.
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Entries a table holds before its first sweep.
+const MIN_SWEEP_AT: usize = 1024;
+
+thread_local! {
+    static INTERNER: RefCell<Interner> = RefCell::new(Interner::default());
+}
+
+struct Interner {
+    set: HashSet<Arc<str>>,
+    /// Size at which the next insert sweeps unused entries first: twice the
+    /// size left by the last sweep, so sweeping stays amortized O(1).
+    sweep_at: usize,
+}
+
+impl Default for Interner {
+    fn default() -> Self {
+        Self {
+            set: HashSet::new(),
+            sweep_at: MIN_SWEEP_AT,
+        }
+    }
+}
+
+impl Interner {
+    fn intern(&mut self, s: &str) -> Arc<str> {
+        if let Some(existing) = self.set.get(s) {
+            return Arc::clone(existing);
+        }
+        if self.set.len() >= self.sweep_at {
+            // Only the table holds these, so nothing can share them again.
+            self.set.retain(|handle| Arc::strong_count(handle) > 1);
+            self.sweep_at = (self.set.len() * 2).max(MIN_SWEEP_AT);
+        }
+        let handle: Arc<str> = Arc::from(s);
+        self.set.insert(Arc::clone(&handle));
+        handle
+    }
+}
+
+/// Return the shared handle for `s`, allocating it on first use on this
+/// thread.
+///
+/// Only use this for identifiers (table keys, type names), not for
+/// arbitrary SQL text.
+pub(crate) fn intern(s: &str) -> Arc<str> {
+    INTERNER.with_borrow_mut(|interner| interner.intern(s))
+}
+
+/// Serde for interned `Arc<str>` fields (`#[serde(with = "crate::intern::serde_interned")]`):
+/// written as a plain string and interned again when read back, so a
+/// deserialized catalog shares allocations like a replayed one.
+pub(crate) mod serde_interned {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::sync::Arc;
+
+    pub fn serialize<S: Serializer>(value: &Arc<str>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(value)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Arc<str>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(super::intern(&s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interned_len() -> usize {
+        INTERNER.with_borrow(|interner| interner.set.len())
+    }
+
+    #[test]
+    fn test_intern_returns_shared_allocation() {
+        let a = intern("public.orders");
+        let b = intern(&String::from("public.orders"));
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(&*a, "public.orders");
+    }
+
+    #[test]
+    fn test_intern_distinct_strings_differ() {
+        let a = intern("int4");
+        let b = intern("int8");
+        assert!(!Arc::ptr_eq(&a, &b));
+    }
+
+    #[test]
+    fn test_sweep_keeps_only_held_entries() {
+        // A fresh thread starts with an empty table of its own.
+        std::thread::spawn(|| {
+            assert_eq!(interned_len(), 0);
+            let held: Vec<Arc<str>> = (0..10)
+                .map(|i| intern(&format!("public.held{i}")))
+                .collect();
+            for i in held.len()..MIN_SWEEP_AT {
+                intern(&format!("public.t{i}"));
+            }
+            assert_eq!(interned_len(), MIN_SWEEP_AT);
+
+            // The next new string sweeps the entries nothing else holds.
+            let next = intern("public.next");
+            assert_eq!(interned_len(), held.len() + 1);
+            assert!(held.iter().all(|h| Arc::ptr_eq(h, &intern(h))));
+            assert!(Arc::ptr_eq(&next, &intern("public.next")));
+        })
+        .join()
+        .expect("interning thread panicked");
+    }
+
+    #[test]
+    fn test_unused_strings_are_dropped() {
+        let kept = intern("public.kept");
+        for i in 0..10 * MIN_SWEEP_AT {
+            intern(&format!("public.t{i}"));
+        }
+        assert!(interned_len() <= MIN_SWEEP_AT, "{}", interned_len());
+        assert!(Arc::ptr_eq(&kept, &intern("public.kept")));
+    }
+}
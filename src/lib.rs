@@ -4,22 +4,36 @@
 //! It parses SQL and Liquibase changesets, builds a table catalog by replaying
 //! migration history, and runs safety rules against changed files.
 
+pub mod cache;
 pub mod catalog;
 pub mod config;
 #[cfg(feature = "docgen")]
 pub mod docgen;
+pub mod examples;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod fix;
 pub mod input;
+mod intern;
 pub mod normalize;
 pub mod output;
 pub mod parser;
 pub mod pipeline;
+pub mod rename_workflow;
 pub mod rules;
+#[cfg(feature = "docgen")]
+pub mod scaffold;
+pub mod schema;
+pub mod server;
 pub mod suppress;
+pub mod term;
+#[cfg(feature = "verify-db")]
+pub mod verify;
 
 // Re-export commonly used types
 pub use catalog::{Catalog, TableState};
 pub use config::Config;
-pub use output::RuleInfo;
+pub use output::{RenderOptions, RuleInfo};
 pub use parser::ir::{IrNode, Located};
-pub use pipeline::LintPipeline;
+pub use pipeline::{LintPipeline, RuleTiming, RunEvent, UnitEvent};
 pub use rules::{Finding, Rule, RuleId, Severity};
@@ -5,24 +5,36 @@
 //! Exit codes:
 //! - 0: No findings at or above the configured severity threshold
 //! - 1: One or more findings at or above the threshold
-//! - 2: Tool error (config error, parse failure, I/O error, etc.)
+//! - 2: Tool error (config error, parse failure, I/O error, etc.), or a
+//!   catalog replay warning in a `--fail-on-warnings` / `warnings.fail_on`
+//!   category
 
 use anyhow::{Context, Result};
-use clap::Parser;
-use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use clap::{Parser, Subcommand};
+use indicatif::{ProgressBar, ProgressFinish, ProgressStyle};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use pg_migration_lint::input::MigrationHistory;
+use pg_migration_lint::cache::{CacheKey, ResultCache};
+use pg_migration_lint::catalog::dump::CatalogDump;
+use pg_migration_lint::catalog::snapshot::{CatalogSnapshot, SnapshotUnit};
+use pg_migration_lint::catalog::{ReplayWarning, WarningCategory, schema_catalog};
+use pg_migration_lint::fix;
+use pg_migration_lint::input::changed::ChangedFiles;
+use pg_migration_lint::input::encoding::read_source;
 use pg_migration_lint::input::liquibase_bridge::load_liquibase;
 use pg_migration_lint::input::sql::SqlLoader;
+use pg_migration_lint::input::{LoadError, MigrationUnit};
 use pg_migration_lint::normalize;
 use pg_migration_lint::output::{
-    Reporter, RuleInfo, SarifReporter, SonarQubeReporter, TextReporter,
+    JsonReporter, PrCommentReporter, Reporter, RuleInfo, SarifReporter, SonarQubeReporter,
+    TextReporter,
 };
-use pg_migration_lint::rules::dedup_findings;
-use pg_migration_lint::rules::{Rule, RuleId};
-use pg_migration_lint::suppress::parse_suppressions;
-use pg_migration_lint::{Config, Finding, LintPipeline, Severity};
+use pg_migration_lint::rules::{Rule, RuleId, RuleRegistry};
+use pg_migration_lint::rules::{dedup_findings, sort_findings};
+use pg_migration_lint::term::{Stream, Style, TermCaps};
+use pg_migration_lint::{Config, Finding, LintPipeline, RuleTiming, Severity};
 
 /// Default config file name used when --config is not explicitly provided.
 const DEFAULT_CONFIG_FILE: &str = "pg-migration-lint.toml";
@@ -43,14 +55,24 @@ struct Args {
     #[arg(long)]
     changed_files_from: Option<PathBuf>,
 
+    /// Lint the migration files git reports as changed since the merge base
+    /// of this ref and HEAD (e.g. origin/main)
+    #[arg(long, value_name = "BASE_REF", conflicts_with_all = ["changed_files", "changed_files_from"])]
+    changed_from_git: Option<String>,
+
     /// Explain a specific rule (e.g., --explain PGM001)
     #[arg(long)]
     explain: Option<String>,
 
-    /// Override output format (text, sarif, sonarqube)
+    /// Override output format (text, sarif, sonarqube, pr-comment, json)
     #[arg(long)]
     format: Option<String>,
 
+    /// Most findings listed in the pr-comment summary (overrides
+    /// output.pr_comment.max_items)
+    #[arg(long, value_name = "N")]
+    max_items: Option<usize>,
+
     /// Show configuration reference. Optionally specify a section name.
     #[arg(long, num_args = 0..=1, default_missing_value = "all")]
     explain_config: Option<String>,
@@ -59,9 +81,204 @@ struct Args {
     #[arg(long)]
     fail_on: Option<String>,
 
+    /// Exit with code 2 if catalog replay reports warnings in these
+    /// categories (comma-separated: replay_conflict, unparseable, rejected;
+    /// all when no value is given). Overrides warnings.fail_on.
+    #[arg(long, value_name = "CATEGORIES", num_args = 0..=1, default_missing_value = "all")]
+    fail_on_warnings: Option<String>,
+
+    /// Lint only these rule families (comma-separated, e.g. 0xx,2xx).
+    /// Rules disabled in the config stay disabled.
+    #[arg(long, value_name = "FAMILIES")]
+    only: Option<String>,
+
     /// Validate configuration and check that paths and tools exist, then exit
     #[arg(long)]
     validate_config: bool,
+
+    /// Write the final replayed catalog (tables, columns, indexes, constraints,
+    /// partitions) as JSON to this path
+    #[arg(long, value_name = "PATH")]
+    emit_catalog: Option<PathBuf>,
+
+    /// Save the replayed catalog and the migrations it was built from to
+    /// this path, for --load-catalog in later runs
+    #[arg(long, value_name = "PATH")]
+    save_catalog: Option<PathBuf>,
+
+    /// Start from a catalog saved with --save-catalog and only replay and
+    /// lint the migrations it does not contain
+    #[arg(long, value_name = "PATH")]
+    load_catalog: Option<PathBuf>,
+
+    /// Seed the catalog from this schema dump (`pg_dump --schema-only`)
+    /// before replaying the migrations
+    #[arg(long, value_name = "PATH", conflicts_with = "load_catalog")]
+    baseline_schema: Option<PathBuf>,
+
+    /// Record per-rule execution time and finding counts and print them
+    /// after the summary
+    #[arg(long)]
+    profile_rules: bool,
+
+    /// Show which migration units each --changed-files path matched and why
+    #[arg(long)]
+    explain_changed_files: bool,
+
+    /// Do not show progress while loading and replaying migrations
+    #[arg(short, long)]
+    quiet: bool,
+
+    /// Lint without reading or writing the result cache (`[cache]`)
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Insert the missing IF EXISTS / IF NOT EXISTS (PGM401, PGM402) into
+    /// the changed .sql migrations and report only what is left
+    #[arg(long)]
+    fix: bool,
+
+    /// Print the changes --fix would make as a unified diff on stdout,
+    /// without writing any file
+    #[arg(long, conflicts_with = "fix")]
+    fix_dry_run: bool,
+
+    /// Apply the migrations with psql to this disposable database
+    /// (connection string or URI), inside a transaction that is rolled back,
+    /// and report statements of changed units that fail (PGM036)
+    #[cfg(feature = "verify-db")]
+    #[arg(long, value_name = "CONN")]
+    verify_against_db: Option<String>,
+
+    /// Build the catalog from the schema of this database (connection string
+    /// or URI) instead of replaying the migration history, and lint the
+    /// changed migrations against it. Unchanged migrations are not replayed
+    #[cfg(feature = "db-introspect")]
+    #[arg(long, value_name = "CONN", conflicts_with_all = ["load_catalog", "baseline_schema"])]
+    introspect_db: Option<String>,
+
+    /// psql binary used by --verify-against-db and --introspect-db
+    #[cfg(any(feature = "verify-db", feature = "db-introspect"))]
+    #[arg(long, value_name = "PATH", default_value = "psql")]
+    psql_path: PathBuf,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run as a long-lived HTTP/JSON daemon (POST /lint, GET /health)
+    Serve {
+        /// Address to listen on. A non-loopback address requires --token
+        #[arg(long, default_value = "127.0.0.1:7878")]
+        listen: String,
+        /// Directory whose repositories requests may lint against with
+        /// `repo` (repeatable). Without one, `repo` is rejected
+        #[arg(long = "allow-repo", value_name = "DIR")]
+        allow_repos: Vec<PathBuf>,
+        /// Token clients must send as `Authorization: Bearer <token>`
+        #[arg(long, env = "PG_MIGRATION_LINT_TOKEN", hide_env_values = true)]
+        token: Option<String>,
+    },
+    /// Browse the embedded rollout scenarios (zero-downtime rename, safe NOT
+    /// NULL, retryable concurrent index)
+    Examples {
+        #[command(subcommand)]
+        command: ExamplesCommand,
+    },
+    /// Merge SARIF or SonarQube reports from several runs into one, tagging
+    /// each finding with the report it came from
+    MergeReports {
+        /// Reports to merge, as PATH or NAME=PATH. Without a name, the file
+        /// stem names the source.
+        #[arg(required = true, value_name = "REPORT")]
+        reports: Vec<String>,
+
+        /// Format of the inputs and the merged report (sarif, sonarqube)
+        #[arg(long, default_value = "sarif")]
+        format: String,
+
+        /// Write the merged report here instead of to stdout
+        #[arg(short, long, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+    /// Liquibase loading tools
+    Liquibase {
+        #[command(subcommand)]
+        command: LiquibaseCommand,
+    },
+    /// Machine-readable rule metadata
+    Rules {
+        #[command(subcommand)]
+        command: RulesCommand,
+    },
+    /// Tools for developing pg-migration-lint itself (requires --features docgen)
+    #[cfg(feature = "docgen")]
+    Dev {
+        #[command(subcommand)]
+        command: DevCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ExamplesCommand {
+    /// List the available examples
+    List,
+    /// Print an example's config and migrations
+    Show {
+        /// Example name, e.g. rename-column
+        name: String,
+
+        /// Write the config and migrations into this directory instead of
+        /// printing them
+        #[arg(long, value_name = "DIR")]
+        out: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum LiquibaseCommand {
+    /// Load the changelog with both the bridge JAR and `update-sql` and
+    /// report changesets whose units differ (exit 1 if any do)
+    CompareStrategies,
+}
+
+#[derive(Subcommand, Debug)]
+enum RulesCommand {
+    /// Print a JSON Schema for pg-migration-lint.toml, with every rule's
+    /// family, default severity, and config keys
+    Schema {
+        /// Write the schema here instead of to stdout
+        #[arg(short, long, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+}
+
+#[cfg(feature = "docgen")]
+#[derive(Subcommand, Debug)]
+enum DevCommand {
+    /// Scaffold a new rule: module, tests, docs body, and registration
+    NewRule {
+        /// Rule ID, e.g. PGM031. Defaults to the next free ID in --family.
+        id: Option<String>,
+
+        /// Rule family, e.g. 0xx
+        #[arg(long)]
+        family: Option<String>,
+
+        /// One-line description
+        #[arg(long, default_value = "TODO: describe the rule")]
+        description: String,
+
+        /// Default severity (blocker, critical, major, minor, info)
+        #[arg(long, default_value = "minor")]
+        severity: String,
+
+        /// Project root containing src/rules/
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+    },
 }
 
 fn main() {
@@ -81,6 +298,179 @@ fn main() {
     }
 }
 
+/// Run a `dev` subcommand.
+#[cfg(feature = "docgen")]
+fn run_dev_command(command: &DevCommand) -> Result<()> {
+    use pg_migration_lint::scaffold;
+
+    let DevCommand::NewRule {
+        id,
+        family,
+        description,
+        severity,
+        root,
+    } = command;
+    let severity = Severity::parse(severity).with_context(|| {
+        format!(
+            "Invalid --severity '{severity}'. Valid values: blocker, critical, major, minor, info"
+        )
+    })?;
+    if let Some(family) = family {
+        pg_migration_lint::config::parse_rule_family(family).context("Invalid --family")?;
+    }
+    let id = scaffold::resolve_id(root, id.as_deref(), family.as_deref())?;
+    let created = scaffold::new_rule(root, &id, description, severity)?;
+
+    println!("Scaffolded {}:", created.id);
+    for path in &created.created {
+        println!("  created {}", path.display());
+    }
+    for path in &created.updated {
+        println!("  updated {}", path.display());
+    }
+    println!(
+        "\nNext: resolve the TODO({id}) markers, review the new snapshots with \
+         `cargo insta review`, then update the rule counts in docs/rules.md, \
+         README.md, and CLAUDE.md, add a SPEC.md section, and add a violation \
+         to tests/fixtures/repos/all-rules."
+    );
+    Ok(())
+}
+
+/// Run an `examples` subcommand.
+fn run_examples_command(command: &ExamplesCommand) -> Result<()> {
+    use pg_migration_lint::examples::{self, EXAMPLES};
+
+    match command {
+        ExamplesCommand::List => {
+            let width = EXAMPLES.iter().map(|e| e.name.len()).max().unwrap_or(0);
+            for example in EXAMPLES {
+                println!("{:width$}  {}", example.name, example.title);
+            }
+        }
+        ExamplesCommand::Show { name, out } => {
+            let example = examples::find(name).with_context(|| {
+                let names: Vec<_> = EXAMPLES.iter().map(|e| e.name).collect();
+                format!(
+                    "Unknown example '{name}'. Available examples: {}",
+                    names.join(", ")
+                )
+            })?;
+            match out {
+                Some(dir) => {
+                    let written = example
+                        .write_to(dir)
+                        .with_context(|| format!("Failed to write example to {}", dir.display()))?;
+                    for path in written {
+                        println!("  created {}", path.display());
+                    }
+                }
+                None => {
+                    // Same separators as `head` on several files.
+                    println!("{}: {}\n", example.name, example.title);
+                    println!("==> {} <==\n{}", examples::CONFIG_FILE, example.config);
+                    for file in example.files {
+                        println!(
+                            "==> {}/{} <==\n{}",
+                            examples::MIGRATIONS_DIR,
+                            file.name,
+                            file.sql
+                        );
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Run a `rules` subcommand.
+fn run_rules_command(command: &RulesCommand) -> Result<()> {
+    let RulesCommand::Schema { output } = command;
+    let schema = serde_json::to_string_pretty(&pg_migration_lint::schema::rules_schema())
+        .context("Failed to serialize schema")?;
+    match output {
+        Some(path) => write_output_file(path, &schema)?,
+        None => println!("{schema}"),
+    }
+    Ok(())
+}
+
+/// Run a `liquibase` subcommand.
+///
+/// Returns `Ok(true)` if the strategies diverge.
+fn run_liquibase_command(command: &LiquibaseCommand, config: &Config) -> Result<bool> {
+    use pg_migration_lint::input::liquibase_compare::compare_strategies;
+
+    let LiquibaseCommand::CompareStrategies = command;
+    let paths = config.migrations.source_paths();
+    let include_root = config.migrations.effective_include_root();
+    let load = |strategy: &str| {
+        let liquibase = pg_migration_lint::config::LiquibaseConfig {
+            strategy: strategy.to_string(),
+            ..config.liquibase.clone()
+        };
+        load_liquibase(&liquibase, &paths, include_root.as_deref())
+            .with_context(|| format!("Failed to load Liquibase migrations with '{strategy}'"))
+    };
+    let bridge = load("bridge")?;
+    let update_sql = load("update-sql")?;
+
+    let divergences = compare_strategies(&bridge, &update_sql);
+    println!(
+        "bridge: {} changesets, update-sql: {} changesets",
+        bridge.len(),
+        update_sql.len()
+    );
+    for divergence in &divergences {
+        println!("{divergence}");
+    }
+    match divergences.len() {
+        0 => println!("No divergences."),
+        1 => println!("1 divergence."),
+        n => println!("{n} divergences."),
+    }
+    Ok(!divergences.is_empty())
+}
+
+/// Write `contents` to `path`, creating its parent directory if needed.
+fn write_output_file(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    std::fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Run `merge-reports`.
+fn run_merge_reports(reports: &[String], format: &str, output: Option<&PathBuf>) -> Result<()> {
+    use pg_migration_lint::output::merge::{self, MergeFormat, ReportSource};
+
+    let format = MergeFormat::parse(format).with_context(|| {
+        format!("Invalid --format '{format}' for merge-reports. Valid values: sarif, sonarqube")
+    })?;
+    let sources = reports
+        .iter()
+        .map(|arg| {
+            let (name, path) = ReportSource::parse_arg(arg);
+            let contents = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read report '{path}'"))?;
+            Ok(ReportSource {
+                name,
+                path,
+                contents,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let merged = merge::merge_reports(&sources, format).context("Failed to merge reports")?;
+    match output {
+        Some(path) => write_output_file(path, &merged)?,
+        None => println!("{merged}"),
+    }
+    Ok(())
+}
+
 /// Run the main lint pipeline.
 ///
 /// Returns `Ok(true)` if findings at or above the severity threshold were found,
@@ -98,71 +488,272 @@ fn run(args: Args) -> Result<bool> {
         return Ok(false);
     }
 
+    if let Some(Command::MergeReports {
+        ref reports,
+        ref format,
+        ref output,
+    }) = args.command
+    {
+        run_merge_reports(reports, format, output.as_ref())?;
+        return Ok(false);
+    }
+
+    if let Some(Command::Examples { ref command }) = args.command {
+        run_examples_command(command)?;
+        return Ok(false);
+    }
+
+    if let Some(Command::Rules { ref command }) = args.command {
+        run_rules_command(command)?;
+        return Ok(false);
+    }
+
+    #[cfg(feature = "docgen")]
+    if let Some(Command::Dev { ref command }) = args.command {
+        run_dev_command(command)?;
+        return Ok(false);
+    }
+
     // Load configuration.
     // If --config is explicitly provided and the file doesn't exist, that's a tool error.
     // If using the default path and it doesn't exist, warn and use defaults.
     let config = load_config(&args.config)?;
 
+    // Rule IDs in the config resolve against the assembled rule registry,
+    // so they are checked here rather than when the file is parsed.
+    RuleRegistry::assemble(&config)
+        .validate_config(&config)
+        .context("Failed to load configuration")?;
+
     // Handle --validate-config early exit
     if args.validate_config {
         return print_config_validation(&config);
     }
 
-    // Parse changed files
-    let changed_files = parse_changed_files(&args)?;
+    if let Some(Command::Liquibase { ref command }) = args.command {
+        return run_liquibase_command(command, &config);
+    }
 
-    // --- Step 1: Load migration files ---
-    let mut history = load_migrations(&config)?;
+    if let Some(Command::Serve {
+        ref listen,
+        ref allow_repos,
+        ref token,
+    }) = args.command
+    {
+        let listener = std::net::TcpListener::bind(listen)
+            .with_context(|| format!("Failed to listen on {listen}"))?;
+        let options = pg_migration_lint::server::ServeOptions {
+            allowed_repos: allow_repos.clone(),
+            token: token.clone(),
+        };
+        eprintln!("pg-migration-lint: serving on http://{listen}");
+        pg_migration_lint::server::serve(listener, config, options).context("Server error")?;
+        return Ok(false);
+    }
 
-    // --- Step 1b: Normalize schemas ---
-    // Assign the configured default schema to every unqualified QualifiedName
-    // so that catalog keys are always schema-qualified.
-    normalize::normalize_schemas(&mut history.units, &config.migrations.default_schema);
+    // Parse changed files. `None` lints every migration.
+    let changed_files = parse_changed_files(&args, &config)?;
 
-    // --- Step 2: Build changed files set for O(1) lookup ---
-    // Convert the Vec<PathBuf> into a HashSet<PathBuf>.
-    // Canonicalize paths where possible for reliable matching.
-    let changed_files_set: HashSet<PathBuf> = changed_files
-        .iter()
-        .map(|p| std::fs::canonicalize(p).unwrap_or_else(|_| p.clone()))
-        .collect();
+    let fail_on_warnings = match args.fail_on_warnings.as_deref() {
+        Some(list) => parse_warning_categories(list)?,
+        None => config.warnings.fail_on.clone(),
+    };
 
-    // Selective mode: if the user passed --changed-files or --changed-files-from,
-    // we only lint the files they named — even if the resulting set is empty.
-    // An empty set in selective mode means "lint nothing, but still write reports"
-    // so that CI consumers (e.g. SonarQube) always find the expected report file.
-    let selective_mode = args.changed_files.is_some() || args.changed_files_from.is_some();
+    let only_families = args
+        .only
+        .as_deref()
+        .map(|list| {
+            list.split(',')
+                .map(|f| pg_migration_lint::config::parse_rule_family(f.trim()))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()
+        .context("Invalid --only")?;
+
+    // Build active rules list, filtering out any disabled via config and
+    // any outside the --only families.
+    let mut active_rules: Vec<RuleId> = config.rules.active_rules();
+    if let Some(ref families) = only_families {
+        active_rules.retain(|r| families.contains(&r.family_prefix()));
+    }
+
+    // Selective mode: if the user passed --changed-files, --changed-files-from,
+    // or a git base ref, we only lint the files they named — even if the
+    // resulting set is empty. An empty set in selective mode means "lint
+    // nothing, but still write reports" so that CI consumers (e.g. SonarQube)
+    // always find the expected report file.
+    let selective_mode = changed_files.is_some();
     let lint_all = !selective_mode;
+    let changed_files = changed_files.unwrap_or_default();
+
+    // --- Step 0: Result cache ---
+    // An identical earlier run is reused as is; see pg_migration_lint::cache.
+    let cache = result_cache(
+        &args,
+        &config,
+        &active_rules,
+        selective_mode.then_some(changed_files.as_slice()),
+    );
+    if let Some((cache, key)) = &cache
+        && let Some(run) = cache.load(key)
+    {
+        eprintln!("pg-migration-lint: reusing cached result {}", key.as_str());
+        return finish_run(
+            &args,
+            &config,
+            &fail_on_warnings,
+            &run.findings,
+            &run.warnings,
+            None,
+        );
+    }
+
+    // --- Step 1: Open the migration stream ---
+    // Units are parsed lazily and dropped after replay, so memory stays
+    // bounded by the catalog rather than the size of the history.
+    let progress = replay_progress(args.quiet);
+    progress.set_message("Loading migrations");
+    let units = load_migrations(&config)?;
+    if let (len, Some(upper)) = units.size_hint()
+        && len == upper
+    {
+        progress.set_length(len as u64);
+        progress.set_style(progress_style(
+            "{msg} {wide_bar} {pos}/{len} units [{elapsed}]",
+        ));
+    }
+    progress.set_message("Replaying migrations");
+
+    // --- Step 2: Prepare changed-file matching ---
+    // Paths are canonicalized where possible; every match is recorded for
+    // --explain-changed-files and the zero-match warning.
+    let mut changed_files = ChangedFiles::new(&changed_files);
 
     // --- Step 3: Single-pass replay and lint ---
-    let mut pipeline = LintPipeline::new();
+    let mut pipeline = LintPipeline::new()
+        .with_down_cap_exempt(&config.meta.pgm901.exempt_rule_ids())
+        .with_config(config.clone())
+        .with_rule_profiling(args.profile_rules);
 
-    // Build active rules list, filtering out any disabled via config.
-    let disabled: HashSet<RuleId> = config.rules.disabled.iter().copied().collect();
-    let active_rules: Vec<RuleId> = RuleId::lint_rules()
-        .filter(|r| !disabled.contains(r))
-        .collect();
+    // With --load-catalog, units already in the snapshot are skipped.
+    let mut snapshot = args
+        .load_catalog
+        .as_deref()
+        .map(CatalogSnapshot::read)
+        .transpose()?;
+    if let Some(snapshot) = snapshot.as_mut() {
+        pipeline = pipeline.with_catalog(std::mem::take(&mut snapshot.catalog));
+    }
+    let recorded = snapshot.as_ref().map(CatalogSnapshot::recorded);
+
+    // With --baseline-schema the history is replayed on top of the dumped
+    // schema instead of an empty catalog.
+    if let Some(ref path) = args.baseline_schema {
+        progress.set_message("Reading the baseline schema");
+        let sql = read_source(path)
+            .with_context(|| format!("Failed to read baseline schema {}", path.display()))?;
+        let (catalog, warnings) = schema_catalog(&sql, path, &config.migrations.default_schema);
+        if let Some(first) = warnings.first() {
+            progress.suspend(|| {
+                eprintln!(
+                    "Warning: --baseline-schema: {} statement(s) of {} could not be applied \
+                     to the catalog (first at line {}); the tables they touch are incomplete",
+                    warnings.len(),
+                    path.display(),
+                    first.line
+                )
+            });
+        }
+        pipeline = pipeline.with_catalog(catalog);
+        progress.set_message("Replaying migrations");
+    }
+
+    // With --introspect-db the catalog comes from the database, which already
+    // reflects the unchanged units; only changed units are applied.
+    #[cfg(feature = "db-introspect")]
+    let introspected = if let Some(conninfo) = &args.introspect_db {
+        if !selective_mode {
+            anyhow::bail!(
+                "--introspect-db needs the changed migrations (--changed-files or a base ref); \
+                 every other migration is taken to be applied to the database already"
+            );
+        }
+        progress.set_message("Reading the database schema");
+        let (catalog, warnings) =
+            pg_migration_lint::catalog::introspect::introspect(&args.psql_path, conninfo)
+                .context("--introspect-db failed")?;
+        if !warnings.is_empty() {
+            progress.suspend(|| {
+                eprintln!(
+                    "Warning: --introspect-db: {} statement(s) of the database schema could \
+                     not be applied to the catalog; the tables they touch are incomplete",
+                    warnings.len()
+                )
+            });
+        }
+        pipeline = pipeline.with_catalog(catalog);
+        progress.set_message("Replaying migrations");
+        true
+    } else {
+        false
+    };
+    #[cfg(not(feature = "db-introspect"))]
+    let introspected = false;
+    let mut snapshot_units: Vec<SnapshotUnit> = Vec::new();
+    let mut skipped_changed = 0usize;
 
     let mut all_findings: Vec<Finding> = Vec::new();
     let mut changed_units_per_file: HashMap<PathBuf, usize> = HashMap::new();
 
-    for unit in &history.units {
+    // With --verify-against-db every unit is also collected into a psql
+    // script that is applied after the static pass.
+    #[cfg(feature = "verify-db")]
+    let mut verify_script = args.verify_against_db.as_ref().map(|_| {
+        pg_migration_lint::verify::VerifyScript::new()
+            .with_redact_literals(config.output.redact_literals)
+    });
+
+    for unit in units {
+        let mut unit = unit.context("Failed to load migrations")?;
+
+        // --- Step 1b: Normalize schemas ---
+        // Assign the default schema configured for the unit's path to every
+        // unqualified QualifiedName so that catalog keys are always
+        // schema-qualified.
+        let default_schema = config.migrations.default_schema_for(&unit.source_file);
+        normalize::normalize_unit(&mut unit, default_schema);
+        let unit = &unit;
+
         // Determine if this unit is in the changed set.
-        // Try canonicalized comparison first, then fall back to direct and ends_with matching.
-        let is_changed = if lint_all {
-            true
-        } else {
-            let canonical = std::fs::canonicalize(&unit.source_file)
-                .unwrap_or_else(|_| unit.source_file.clone());
-            changed_files_set.contains(&canonical)
-                || changed_files_set.contains(&unit.source_file)
-                || changed_files_set.iter().any(|cf| {
-                    // Only allow suffix matching when the shorter path includes a directory
-                    // component, to prevent bare filenames from matching across directories.
-                    (cf.ends_with(&unit.source_file) && unit.source_file.components().count() > 1)
-                        || (unit.source_file.ends_with(cf) && cf.components().count() > 1)
-                })
-        };
+        let is_changed = lint_all || changed_files.match_unit(&unit.id, &unit.source_file);
+
+        if recorded.is_some() || args.save_catalog.is_some() {
+            let snapshot_unit = SnapshotUnit::of(unit);
+            let in_snapshot = match &recorded {
+                Some(recorded) => recorded.contains(&snapshot_unit)?,
+                None => false,
+            };
+            snapshot_units.push(snapshot_unit);
+            if in_snapshot {
+                if is_changed && selective_mode {
+                    skipped_changed += 1;
+                }
+                progress.inc(1);
+                continue;
+            }
+        }
+
+        if introspected && !is_changed {
+            progress.inc(1);
+            continue;
+        }
+
+        #[cfg(feature = "verify-db")]
+        if let Some(script) = verify_script.as_mut() {
+            script
+                .push_unit(unit, is_changed)
+                .context("--verify-against-db failed")?;
+        }
 
         if is_changed {
             *changed_units_per_file
@@ -171,32 +762,24 @@ fn run(args: Args) -> Result<bool> {
 
             let mut unit_findings = pipeline.lint(unit, &active_rules);
 
-            // Parse suppressions from source file and filter findings.
-            // Read the raw SQL source for suppression comments.
-            let source = match std::fs::read_to_string(&unit.source_file) {
-                Ok(s) => s,
-                Err(e) => {
-                    eprintln!(
-                        "Warning: could not read '{}' for suppression comments: {}",
-                        unit.source_file.display(),
-                        e
-                    );
-                    String::new()
-                }
-            };
-            let suppressions = parse_suppressions(&source);
+            // Filter findings by the unit's inline suppressions (read from
+            // its source file by the loader).
+            let suppressions = &unit.suppressions;
 
             for id in suppressions.rule_ids() {
                 if id.is_meta() {
-                    eprintln!(
-                        "WARNING: meta rule '{}' in suppression comment in {} (meta rules cannot be suppressed)",
-                        id,
-                        unit.source_file.display()
-                    );
+                    progress.suspend(|| {
+                        eprintln!(
+                            "WARNING: meta rule '{}' in suppression comment in {} (meta rules cannot be suppressed)",
+                            id,
+                            unit.source_file.display()
+                        )
+                    });
                 }
             }
 
             unit_findings.retain(|f| !suppressions.is_suppressed(f.rule_id, f.start_line));
+            suppressions.apply_acknowledgments(&mut unit_findings);
             dedup_findings(&mut unit_findings);
 
             all_findings.append(&mut unit_findings);
@@ -204,6 +787,42 @@ fn run(args: Args) -> Result<bool> {
             // Not a changed file -- just replay to build catalog
             pipeline.replay(unit);
         }
+        progress.inc(1);
+    }
+    progress.finish_and_clear();
+
+    if skipped_changed > 0 {
+        eprintln!(
+            "Warning: {skipped_changed} changed migration unit(s) are already in the \
+             --load-catalog snapshot and were not linted"
+        );
+    }
+    if let Some(ref path) = args.save_catalog {
+        CatalogSnapshot::new(pipeline.catalog().clone(), snapshot_units).write(path)?;
+    }
+
+    // Required follow-ups from [[patterns]] that no later changed unit provided
+    let mut follow_up_findings = pipeline.finish(&active_rules);
+    dedup_findings(&mut follow_up_findings);
+    all_findings.append(&mut follow_up_findings);
+
+    #[cfg(feature = "verify-db")]
+    if let (Some(script), Some(conninfo)) = (&verify_script, &args.verify_against_db)
+        && active_rules.contains(&RuleId::Pgm036)
+        && script.has_changed_statements()
+    {
+        let outcome = script
+            .run(&RuleId::Pgm036, &args.psql_path, conninfo)
+            .context("--verify-against-db failed")?;
+        for (file, line, error) in &outcome.history_failures {
+            eprintln!(
+                "Warning: --verify-against-db: unchanged migration {}:{} failed: {}",
+                file.display(),
+                line,
+                error
+            );
+        }
+        all_findings.extend(outcome.findings);
     }
 
     // Warn when a single file contributes many changesets (likely a single-file changelog)
@@ -223,6 +842,32 @@ fn run(args: Args) -> Result<bool> {
         }
     }
 
+    if selective_mode {
+        if args.explain_changed_files {
+            eprint!("{}", changed_files.explain());
+        }
+        for file in changed_files.unmatched() {
+            eprintln!(
+                "Warning: changed file '{}' did not match any migration unit. \
+                 Run with --explain-changed-files to see how paths were compared.",
+                file.path.display()
+            );
+        }
+    } else if args.explain_changed_files {
+        eprintln!(
+            "--explain-changed-files has no effect without --changed-files, \
+             --changed-files-from, or --changed-from-git"
+        );
+    }
+
+    if let Some(ref path) = args.emit_catalog {
+        write_catalog(pipeline.catalog(), path, config.output.redact_literals)?;
+    }
+
+    if args.fix || args.fix_dry_run {
+        apply_fixes(&mut all_findings, args.fix_dry_run)?;
+    }
+
     // --- Step 4: Strip path prefix (if configured) ---
     if let Some(ref prefix) = config.output.strip_prefix {
         for finding in &mut all_findings {
@@ -232,6 +877,117 @@ fn run(args: Args) -> Result<bool> {
         }
     }
 
+    // Every reporter sees the same, platform-independent order.
+    sort_findings(&mut all_findings);
+
+    if let Some((cache, key)) = &cache
+        && let Err(e) = cache.store(key, &all_findings, pipeline.warnings())
+    {
+        eprintln!("Warning: could not write the result cache: {e}");
+    }
+
+    finish_run(
+        &args,
+        &config,
+        &fail_on_warnings,
+        &all_findings,
+        pipeline.warnings(),
+        Some(&pipeline),
+    )
+}
+
+/// The result cache and this run's key, or `None` when the run is not
+/// cached: the cache is off, `--no-cache` is given, the run needs more than
+/// findings (the catalog, rule timings, changed-file matching, a database),
+/// or the key cannot be computed.
+fn result_cache(
+    args: &Args,
+    config: &Config,
+    active_rules: &[RuleId],
+    changed: Option<&[PathBuf]>,
+) -> Option<(ResultCache, CacheKey)> {
+    #[cfg(feature = "verify-db")]
+    if args.verify_against_db.is_some() {
+        return None;
+    }
+    #[cfg(feature = "db-introspect")]
+    if args.introspect_db.is_some() {
+        return None;
+    }
+    if !config.cache.enabled
+        || args.no_cache
+        || args.emit_catalog.is_some()
+        || args.save_catalog.is_some()
+        || args.load_catalog.is_some()
+        || args.baseline_schema.is_some()
+        || args.profile_rules
+        || args.explain_changed_files
+        || args.fix
+        || args.fix_dry_run
+        || config.output.json.include_catalog
+    {
+        return None;
+    }
+    match CacheKey::compute(config, active_rules, changed) {
+        Ok(key) => Some((
+            ResultCache::new(config.cache.dir_or_default(&config.output.dir)),
+            key,
+        )),
+        Err(e) => {
+            eprintln!("Warning: result cache disabled: {e}");
+            None
+        }
+    }
+}
+
+/// `--fix` / `--fix-dry-run`: repair the PGM401/PGM402 findings in `.sql`
+/// files. Writing the fixes removes the repaired findings from the report;
+/// a dry run prints the diff and reports every finding.
+fn apply_fixes(all_findings: &mut Vec<Finding>, dry_run: bool) -> Result<()> {
+    let fixes = fix::plan(all_findings)?;
+    let count: usize = fixes.iter().map(|f| f.findings.len()).sum();
+
+    if dry_run {
+        for file_fix in &fixes {
+            print!("{}", file_fix.diff());
+        }
+        eprintln!(
+            "pg-migration-lint: --fix-dry-run: {count} finding(s) in {} file(s) can be fixed",
+            fixes.len()
+        );
+        return Ok(());
+    }
+
+    for file_fix in &fixes {
+        file_fix.write()?;
+    }
+    let fixed: HashSet<usize> = fixes
+        .iter()
+        .flat_map(|f| f.findings.iter().copied())
+        .collect();
+    *all_findings = std::mem::take(all_findings)
+        .into_iter()
+        .enumerate()
+        .filter(|(idx, _)| !fixed.contains(idx))
+        .map(|(_, finding)| finding)
+        .collect();
+    eprintln!(
+        "pg-migration-lint: fixed {count} finding(s) in {} file(s)",
+        fixes.len()
+    );
+    Ok(())
+}
+
+/// Emit reports, print the summary, and decide the exit status. `pipeline`
+/// is `None` when the findings come from the result cache.
+fn finish_run(
+    args: &Args,
+    config: &Config,
+    fail_on_warnings: &[WarningCategory],
+    all_findings: &[Finding],
+    warnings: &[ReplayWarning],
+    pipeline: Option<&LintPipeline>,
+) -> Result<bool> {
     // --- Step 5: Emit reports ---
     let formats: Vec<String> = if let Some(ref fmt) = args.format {
         vec![fmt.clone()]
@@ -244,6 +1000,25 @@ fn run(args: Args) -> Result<bool> {
             "text" => Box::new(TextReporter::new(true)),
             "sarif" => Box::new(SarifReporter::new()),
             "sonarqube" => Box::new(SonarQubeReporter::new(RuleInfo::all())),
+            "pr-comment" => {
+                let mut reporter = PrCommentReporter::new(&config.output.pr_comment);
+                if let Some(max_items) = args.max_items {
+                    reporter.max_items = max_items;
+                }
+                Box::new(reporter)
+            }
+            "json" => {
+                let mut reporter = JsonReporter::new();
+                if let Some(pipeline) = pipeline
+                    && config.output.json.include_catalog
+                {
+                    reporter = reporter.with_catalog(catalog_dump(
+                        pipeline.catalog(),
+                        config.output.redact_literals,
+                    ));
+                }
+                Box::new(reporter)
+            }
             other => {
                 eprintln!("Warning: Unknown output format '{other}', skipping",);
                 continue;
@@ -251,12 +1026,39 @@ fn run(args: Args) -> Result<bool> {
         };
 
         reporter
-            .emit(&all_findings, &config.output.dir)
+            .emit(all_findings, &config.output.dir)
             .context(format!("Failed to write {format} report",))?;
     }
 
     // --- Step 6: Summary and exit code ---
     eprintln!("pg-migration-lint: {} finding(s)", all_findings.len());
+    if let Some(profile) = pipeline.and_then(LintPipeline::rule_profile) {
+        print_rule_profile(profile);
+    }
+
+    let failing_warnings: Vec<_> = warnings
+        .iter()
+        .filter(|w| fail_on_warnings.contains(&w.category))
+        .collect();
+    if !failing_warnings.is_empty() {
+        let list: Vec<String> = failing_warnings
+            .iter()
+            .map(|w| {
+                format!(
+                    "  {}:{}: [{}] {}",
+                    w.file.display(),
+                    w.line,
+                    w.category.as_str(),
+                    w.message
+                )
+            })
+            .collect();
+        anyhow::bail!(
+            "{} catalog replay warning(s) in failing categories:\n{}",
+            failing_warnings.len(),
+            list.join("\n")
+        );
+    }
 
     let fail_on_str = args.fail_on.as_deref().unwrap_or(&config.cli.fail_on);
     let fail_on = if fail_on_str.eq_ignore_ascii_case("none") {
@@ -278,6 +1080,81 @@ fn run(args: Args) -> Result<bool> {
     Ok(false)
 }
 
+/// Parse the `--fail-on-warnings` value: `all`, or a comma-separated list of
+/// warning categories.
+fn parse_warning_categories(list: &str) -> Result<Vec<WarningCategory>> {
+    if list == "all" {
+        return Ok(WarningCategory::ALL.to_vec());
+    }
+    list.split(',')
+        .map(|name| {
+            let name = name.trim();
+            WarningCategory::parse(name).with_context(|| {
+                format!(
+                    "Unknown warning category '{name}' for --fail-on-warnings. \
+                     Valid values: replay_conflict, unparseable, rejected, all"
+                )
+            })
+        })
+        .collect()
+}
+
+/// Print the `--profile-rules` table to stderr, slowest rule first.
+///
+/// Finding counts are raw: they include findings later removed by
+/// suppressions or dedup.
+fn print_rule_profile(profile: &BTreeMap<RuleId, RuleTiming>) {
+    let mut rows: Vec<_> = profile.iter().collect();
+    rows.sort_by(|a, b| b.1.elapsed.cmp(&a.1.elapsed).then(a.0.cmp(b.0)));
+
+    let total: Duration = rows.iter().map(|(_, t)| t.elapsed).sum();
+    eprintln!(
+        "pg-migration-lint: rule profile ({} rules, {:.3} ms total)",
+        rows.len(),
+        total.as_secs_f64() * 1000.0
+    );
+    eprintln!(
+        "  {:<8} {:>12} {:>8} {:>10}",
+        "rule", "time (ms)", "units", "findings"
+    );
+    for (id, timing) in rows {
+        eprintln!(
+            "  {:<8} {:>12.3} {:>8} {:>10}",
+            id.as_str(),
+            timing.elapsed.as_secs_f64() * 1000.0,
+            timing.units,
+            timing.findings
+        );
+    }
+}
+
+/// Write the replayed catalog as pretty-printed JSON for `--emit-catalog`.
+fn write_catalog(
+    catalog: &pg_migration_lint::Catalog,
+    path: &std::path::Path,
+    redact_literals: bool,
+) -> Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    let dump = catalog_dump(catalog, redact_literals);
+    let json = serde_json::to_string_pretty(&dump).context("Failed to serialize catalog")?;
+    std::fs::write(path, json + "\n")
+        .with_context(|| format!("Failed to write catalog to {}", path.display()))
+}
+
+/// The catalog as dumped by `--emit-catalog` and the `json` report.
+fn catalog_dump(catalog: &pg_migration_lint::Catalog, redact_literals: bool) -> CatalogDump {
+    if redact_literals {
+        CatalogDump::from_catalog_redacted(catalog)
+    } else {
+        CatalogDump::from_catalog(catalog)
+    }
+}
+
 /// Load configuration from file.
 ///
 /// If `config_path` is `Some`, the user explicitly passed `--config` and the file
@@ -314,16 +1191,45 @@ fn explain_rule(rule_id: &str) -> Result<()> {
         .parse()
         .map_err(|_| anyhow::anyhow!("Unknown rule: {}", rule_id))?;
 
-    println!("Rule: {}", parsed);
-    println!("Severity: {}", parsed.default_severity());
-    println!("Description: {}", parsed.description());
+    let term = TermCaps::detect(Stream::Stdout);
+    println!("{} {}", term.paint(Style::Bold, "Rule:"), parsed);
+    println!(
+        "{} {}",
+        term.paint(Style::Bold, "Severity:"),
+        parsed.default_severity()
+    );
+    println!(
+        "{} {}",
+        term.paint(Style::Bold, "Description:"),
+        parsed.description()
+    );
     println!();
     println!("{}", parsed.explain());
 
     Ok(())
 }
 
-fn parse_changed_files(args: &Args) -> Result<Vec<PathBuf>> {
+/// The changed files to lint, or `None` to lint every migration. Explicit
+/// lists win over a git base ref, and `--changed-from-git` over
+/// `cli.changed_from_git`.
+fn parse_changed_files(args: &Args, config: &Config) -> Result<Option<Vec<PathBuf>>> {
+    if args.changed_files.is_none() && args.changed_files_from.is_none() {
+        let Some(base_ref) = args
+            .changed_from_git
+            .as_deref()
+            .or(config.cli.changed_from_git.as_deref())
+        else {
+            return Ok(None);
+        };
+        let files = pg_migration_lint::input::git::changed_from_git(
+            base_ref,
+            Path::new("."),
+            &config.migrations.source_paths(),
+        )
+        .with_context(|| format!("Failed to list files changed since '{base_ref}'"))?;
+        return Ok(Some(files));
+    }
+
     let mut files = Vec::new();
 
     if let Some(ref file_list) = args.changed_files {
@@ -346,56 +1252,91 @@ fn parse_changed_files(args: &Args) -> Result<Vec<PathBuf>> {
         }
     }
 
-    Ok(files)
+    Ok(Some(files))
 }
 
-/// Load migration files using the strategy configured in `config.migrations.strategy`.
+/// Progress indicator for the load and replay phases: a spinner while
+/// migrations are discovered, then a bar counting replayed units.
+///
+/// Drawn on stderr only when it is an interactive terminal, `CI` is unset
+/// and `--quiet` is not given; otherwise the indicator is hidden.
+fn replay_progress(quiet: bool) -> ProgressBar {
+    let visible =
+        !quiet && TermCaps::detect(Stream::Stderr).interactive && std::env::var_os("CI").is_none();
+    if !visible {
+        return ProgressBar::hidden();
+    }
+    let progress = ProgressBar::new_spinner()
+        .with_style(progress_style("{spinner} {msg} {pos} units [{elapsed}]"))
+        .with_finish(ProgressFinish::AndClear);
+    progress.enable_steady_tick(Duration::from_millis(100));
+    progress
+}
+
+fn progress_style(template: &str) -> ProgressStyle {
+    ProgressStyle::with_template(template).expect("progress template is valid")
+}
+
+/// Stream of parsed migration units, in replay order.
+type UnitStream = Box<dyn Iterator<Item = Result<MigrationUnit, LoadError>>>;
+
+/// Open a stream of migration units using the strategy configured in
+/// `config.migrations.strategy`.
 ///
 /// - `"filename_lexicographic"` (default): Load `.sql` files sorted by filename.
 /// - `"liquibase"`: Use the Liquibase two-tier fallback (bridge JAR -> update-sql).
 ///
 /// For the Liquibase strategy, the sub-strategy is controlled by `config.liquibase.strategy`
 /// (`"auto"`, `"bridge"`, `"update-sql"`).
-fn load_migrations(config: &Config) -> Result<MigrationHistory> {
+///
+/// Units are parsed on demand as the stream is consumed. Liquibase still
+/// extracts all raw SQL up front (the bridge/update-sql run is one process),
+/// but IR for each changeset is only built when it is reached.
+fn load_migrations(config: &Config) -> Result<UnitStream> {
     match config.migrations.strategy.as_str() {
         "liquibase" => {
             eprintln!(
                 "pg-migration-lint: using liquibase strategy (sub-strategy: {})",
                 config.liquibase.strategy
             );
-            let raw_units = load_liquibase(&config.liquibase, &config.migrations.paths)
-                .context("Failed to load Liquibase migrations")?;
+            let raw_units = load_liquibase(
+                &config.liquibase,
+                &config.migrations.source_paths(),
+                config.migrations.effective_include_root().as_deref(),
+            )
+            .context("Failed to load Liquibase migrations")?;
 
-            let units = raw_units
-                .into_iter()
-                .map(|r| r.into_migration_unit())
-                .collect();
-
-            Ok(MigrationHistory { units })
+            Ok(Box::new(
+                raw_units.into_iter().map(|r| Ok(r.into_migration_unit())),
+            ))
         }
         "filename_lexicographic" => {
             eprintln!("pg-migration-lint: using filename_lexicographic strategy");
-            let run_in_tx = config.migrations.run_in_transaction.unwrap_or(true);
-            let loader = SqlLoader::new(run_in_tx);
-            let history = loader
-                .load(&config.migrations.paths)
-                .context("Failed to load migrations")?;
-            Ok(history)
+            load_sql_migrations(config)
         }
         other => {
             eprintln!(
                 "pg-migration-lint: unknown strategy '{other}', falling back to filename_lexicographic",
             );
-            let run_in_tx = config.migrations.run_in_transaction.unwrap_or(true);
-            let loader = SqlLoader::new(run_in_tx);
-            let history = loader
-                .load(&config.migrations.paths)
-                .context("Failed to load migrations")?;
-            Ok(history)
+            load_sql_migrations(config)
         }
     }
 }
 
+/// Open a stream over plain `.sql` migration files.
+fn load_sql_migrations(config: &Config) -> Result<UnitStream> {
+    let run_in_tx = config.migrations.run_in_transaction.unwrap_or(true);
+    let loader = SqlLoader::new(run_in_tx)
+        .with_respect_gitignore(config.migrations.respect_gitignore)
+        .with_follow_symlinks(config.migrations.follow_symlinks)
+        .with_resolve_includes(config.migrations.resolve_psql_includes)
+        .with_include_root(config.migrations.effective_include_root());
+    let units = loader
+        .units(&config.migrations.source_paths())
+        .context("Failed to load migrations")?;
+    Ok(Box::new(units))
+}
+
 fn print_config_validation(config: &Config) -> Result<bool> {
     use std::process::Command;
 
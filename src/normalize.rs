@@ -15,9 +15,15 @@ use crate::parser::ir::*;
 /// all catalog keys are schema-qualified.
 pub fn normalize_schemas(units: &mut [MigrationUnit], default_schema: &str) {
     for unit in units.iter_mut() {
-        for located in &mut unit.statements {
-            normalize_node(&mut located.node, default_schema);
-        }
+        normalize_unit(unit, default_schema);
+    }
+}
+
+/// Assign the default schema to every unqualified `QualifiedName` in a
+/// single unit. Used when units are streamed rather than loaded up front.
+pub fn normalize_unit(unit: &mut MigrationUnit, default_schema: &str) {
+    for located in &mut unit.statements {
+        normalize_node(&mut located.node, default_schema);
     }
 }
 
@@ -30,6 +36,9 @@ fn normalize_node(node: &mut IrNode, default_schema: &str) {
             if let Some(ref mut parent) = ct.partition_of {
                 parent.set_default_schema(default_schema);
             }
+            for parent in &mut ct.inherits {
+                parent.set_default_schema(default_schema);
+            }
             for constraint in &mut ct.constraints {
                 normalize_constraint(constraint, default_schema);
             }
@@ -73,6 +82,9 @@ fn normalize_node(node: &mut IrNode, default_schema: &str) {
         IrNode::RenameColumn { table, .. } => {
             table.set_default_schema(default_schema);
         }
+        IrNode::TableAttachment { table, .. } => {
+            table.set_default_schema(default_schema);
+        }
         IrNode::InsertInto(ii) => {
             ii.table_name.set_default_schema(default_schema);
         }
@@ -100,10 +112,40 @@ fn normalize_node(node: &mut IrNode, default_schema: &str) {
         } => {
             child_index_name.set_default_schema(default_schema);
         }
+        IrNode::CreateSequence { name, owned_by, .. }
+        | IrNode::SetSequenceOwner { name, owned_by } => {
+            name.set_default_schema(default_schema);
+            if let Some(owner) = owned_by {
+                owner.table.set_default_schema(default_schema);
+            }
+        }
+        IrNode::DropSequence { name, .. } | IrNode::RenameSequence { name, .. } => {
+            name.set_default_schema(default_schema);
+        }
+        IrNode::Replication(rc) => {
+            for table in &mut rc.tables {
+                table.set_default_schema(default_schema);
+            }
+        }
         // DropIndex only has index_name: String — no QualifiedName to normalize.
-        // DropSchema only has schema_name: String — no QualifiedName to normalize.
+        // CreateSchema and DropSchema only carry a schema name.
+        // Extensions are database-wide; their names are not schema-qualified.
+        // Grant and AlterOwner carry object names as written, for messages only.
         // AlterIndexAttachPartition parent_index_name is a plain String (like DropIndex).
-        IrNode::DropIndex(_) | IrNode::DropSchema(_) | IrNode::Ignored { .. } => {}
+        // RenameIndex and AlterIndexSetTablespace only carry index names.
+        // MoveAllInTablespace and VacuumAllTables name no table.
+        IrNode::DropIndex(_)
+        | IrNode::CreateSchema { .. }
+        | IrNode::DropSchema(_)
+        | IrNode::CreateExtension(_)
+        | IrNode::DropExtension(_)
+        | IrNode::Grant(_)
+        | IrNode::AlterOwner(_)
+        | IrNode::RenameIndex { .. }
+        | IrNode::AlterIndexSetTablespace { .. }
+        | IrNode::MoveAllInTablespace { .. }
+        | IrNode::VacuumAllTables
+        | IrNode::Ignored { .. } => {}
     }
 }
 
@@ -133,12 +175,15 @@ mod tests {
                         start_offset: 0,
                         end_offset: 0,
                     },
+                    raw_sql: Default::default(),
                 })
                 .collect(),
             source_file: PathBuf::from("test.sql"),
             source_line_offset: 1,
             run_in_transaction: true,
             is_down: false,
+            suppressions: Default::default(),
+            changeset: Default::default(),
         }
     }
 
@@ -212,6 +257,9 @@ mod tests {
                 default_expr: None,
                 is_inline_pk: false,
                 is_serial: false,
+                is_identity: false,
+                if_not_exists: false,
+                spelling: None,
             })],
         })])];
 
@@ -364,7 +412,8 @@ mod tests {
     #[test]
     fn test_normalize_ignored_untouched() {
         let mut units = vec![make_unit(vec![IrNode::Ignored {
-            raw_sql: "GRANT SELECT ON orders TO user".to_string(),
+            kind: StatementKind::Comment,
+            raw_sql: "COMMENT ON FUNCTION f() IS 'Helper'".to_string(),
         }])];
 
         normalize_schemas(&mut units, "public");
@@ -463,6 +512,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_normalize_inherits_names() {
+        let mut units = vec![make_unit(vec![
+            CreateTable::test(QualifiedName::unqualified("child"))
+                .with_inherits(vec![
+                    QualifiedName::unqualified("parent"),
+                    QualifiedName::qualified("audit", "base"),
+                ])
+                .into(),
+        ])];
+
+        normalize_schemas(&mut units, "public");
+
+        let IrNode::CreateTable(ct) = &units[0].statements[0].node else {
+            panic!("Expected CreateTable");
+        };
+        let keys: Vec<_> = ct.inherits.iter().map(|p| p.catalog_key()).collect();
+        assert_eq!(keys, vec!["public.parent", "audit.base"]);
+    }
+
     #[test]
     fn test_normalize_attach_partition_child() {
         let mut units = vec![make_unit(vec![IrNode::AlterTable(AlterTable {
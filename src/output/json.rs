@@ -0,0 +1,201 @@
+//! Plain JSON reporter
+//!
+//! Writes `report.json`: a versioned document with the findings, counts by
+//! rule and by severity, and optionally the replayed catalog. Meant for CI
+//! scripts that would otherwise have to dig through SARIF. Fields are only
+//! added within a version; renaming or removing one bumps
+//! [`JSON_REPORT_VERSION`].
+
+use crate::catalog::dump::CatalogDump;
+use crate::output::{JsonReporter, ReportError, Reporter};
+use crate::rules::{Finding, Severity, UnitRef};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Version of the `report.json` layout.
+pub const JSON_REPORT_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    version: u32,
+    tool: JsonTool<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timestamp: Option<&'a str>,
+    summary: JsonSummary,
+    findings: Vec<JsonFinding<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    catalog: Option<&'a CatalogDump>,
+}
+
+#[derive(Serialize)]
+struct JsonTool<'a> {
+    name: &'static str,
+    version: &'a str,
+}
+
+#[derive(Serialize)]
+struct JsonSummary {
+    total: usize,
+    by_severity: SeverityCounts,
+    /// Findings per rule ID, sorted by ID.
+    by_rule: BTreeMap<&'static str, usize>,
+}
+
+/// Finding counts for every severity, most severe first.
+#[derive(Serialize, Default)]
+struct SeverityCounts {
+    blocker: usize,
+    critical: usize,
+    major: usize,
+    minor: usize,
+    info: usize,
+}
+
+impl SeverityCounts {
+    fn add(&mut self, severity: Severity) {
+        let count = match severity {
+            Severity::Blocker => &mut self.blocker,
+            Severity::Critical => &mut self.critical,
+            Severity::Major => &mut self.major,
+            Severity::Minor => &mut self.minor,
+            Severity::Info => &mut self.info,
+        };
+        *count += 1;
+    }
+}
+
+#[derive(Serialize)]
+struct JsonFinding<'a> {
+    rule_id: &'static str,
+    severity: &'static str,
+    message: &'a str,
+    file: String,
+    start_line: usize,
+    end_line: usize,
+    /// Catalog key of the table the finding is about, when known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    table: Option<&'a str>,
+    /// The migration unit (changeset id and author, or file name).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unit: Option<&'a UnitRef>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    acknowledged: Option<JsonAcknowledgment<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snippet: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct JsonAcknowledgment<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ticket: Option<&'a str>,
+    original_severity: &'static str,
+}
+
+impl Reporter for JsonReporter {
+    /// Render findings as a `report.json` document.
+    fn render(&self, findings: &[Finding]) -> Result<String, ReportError> {
+        let mut by_severity = SeverityCounts::default();
+        let mut by_rule: BTreeMap<&'static str, usize> = BTreeMap::new();
+        for f in findings {
+            by_severity.add(f.severity);
+            *by_rule.entry(f.rule_id.as_str()).or_default() += 1;
+        }
+
+        let report = JsonReport {
+            version: JSON_REPORT_VERSION,
+            tool: JsonTool {
+                name: "pg-migration-lint",
+                version: self.options.version(),
+            },
+            timestamp: self.options.timestamp.as_deref(),
+            summary: JsonSummary {
+                total: findings.len(),
+                by_severity,
+                by_rule,
+            },
+            findings: findings
+                .iter()
+                .map(|f| JsonFinding {
+                    rule_id: f.rule_id.as_str(),
+                    severity: f.severity.as_str(),
+                    message: &f.message,
+                    file: self.options.path(&f.file),
+                    start_line: f.start_line,
+                    end_line: f.end_line,
+                    table: f.table.as_deref(),
+                    unit: f.unit.as_ref(),
+                    acknowledged: f.acknowledged.as_ref().map(|a| JsonAcknowledgment {
+                        ticket: a.ticket.as_deref(),
+                        original_severity: a.original_severity.as_str(),
+                    }),
+                    snippet: f.snippet.as_deref(),
+                })
+                .collect(),
+            catalog: self.catalog.as_ref(),
+        };
+
+        serde_json::to_string_pretty(&report).map_err(|e| ReportError::Serialization(e.to_string()))
+    }
+
+    /// The output filename for the JSON report.
+    fn filename(&self) -> &str {
+        "report.json"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::output::RenderOptions;
+    use crate::output::test_helpers::test_finding;
+    use crate::parser::SourceSpan;
+    use crate::rules::{Acknowledgment, RuleId};
+    use std::path::Path;
+
+    fn render(reporter: JsonReporter, findings: &[Finding]) -> serde_json::Value {
+        let options = RenderOptions {
+            tool_version: Some("0.0.0-test".to_string()),
+            ..RenderOptions::default()
+        };
+        let json = reporter
+            .with_options(options)
+            .render(findings)
+            .expect("render");
+        serde_json::from_str(&json).expect("parse json")
+    }
+
+    #[test]
+    fn findings_and_counts() {
+        let mut acknowledged = Finding::new(
+            RuleId::Pgm201,
+            Severity::Info,
+            "DROP TABLE 'legacy_orders' on an existing table.".to_string(),
+            Path::new("db/migrations/V043__drop.sql"),
+            &SourceSpan::at(2, 2),
+        );
+        acknowledged.table = Some("public.legacy_orders".to_string());
+        acknowledged.acknowledged = Some(Acknowledgment {
+            ticket: Some("DB-123".to_string()),
+            original_severity: Severity::Minor,
+        });
+        let findings = vec![test_finding(), test_finding(), acknowledged];
+
+        insta::assert_json_snapshot!(render(JsonReporter::new(), &findings));
+    }
+
+    #[test]
+    fn catalog_included_when_set() {
+        let catalog = CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "bigint", false).pk(&["id"]);
+            })
+            .build();
+        let reporter = JsonReporter::new().with_catalog(CatalogDump::from_catalog(&catalog));
+
+        let parsed = render(reporter, &[]);
+        assert_eq!(parsed["summary"]["total"], 0);
+        assert_eq!(parsed["catalog"]["tables"][0]["name"], "orders");
+        assert!(render(JsonReporter::new(), &[]).get("catalog").is_none());
+    }
+}
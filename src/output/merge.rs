@@ -0,0 +1,343 @@
+//! Merging reports from several runs into one
+//!
+//! `pg-migration-lint merge-reports` combines the SARIF or SonarQube reports
+//! of separate runs (one per service or repository) into a single artifact
+//! for a cross-repository dashboard. Each input is a named source; the name
+//! travels with every finding taken from it:
+//!
+//! - SARIF: every input run is kept as its own run, tagged with
+//!   `automationDetails.id` = `"<source>/"` and a `properties.source` object
+//!   holding the name and the report it came from.
+//! - SonarQube: the issue format has no room for metadata, so each issue's
+//!   `filePath` is prefixed with `<source>/`. Rules are merged by ID, keeping
+//!   the highest severity.
+//!
+//! Reports are merged as JSON, so fields this version does not write (from
+//! newer or older releases) are carried through unchanged.
+
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value, json};
+use thiserror::Error;
+
+use crate::output::sarif::SARIF_SCHEMA;
+
+#[derive(Debug, Error)]
+pub enum MergeError {
+    #[error("report '{source_name}' is not valid JSON: {message}")]
+    Parse {
+        source_name: String,
+        message: String,
+    },
+
+    #[error("report '{source_name}' is a {found} report, expected {expected}")]
+    FormatMismatch {
+        source_name: String,
+        expected: MergeFormat,
+        found: MergeFormat,
+    },
+
+    #[error("report '{0}' is neither a SARIF log nor a SonarQube Generic Issue Import report")]
+    UnknownFormat(String),
+
+    #[error("source name '{0}' is used for more than one report")]
+    DuplicateSource(String),
+
+    #[error("Serialization error: {0}")]
+    Serialization(String),
+}
+
+/// Report formats that can be merged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeFormat {
+    Sarif,
+    SonarQube,
+}
+
+impl MergeFormat {
+    /// Parse a `--format` value. Only the JSON formats can be merged.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "sarif" => Some(Self::Sarif),
+            "sonarqube" => Some(Self::SonarQube),
+            _ => None,
+        }
+    }
+
+    /// Recognize a parsed report by its top-level keys.
+    fn detect(report: &Value) -> Option<Self> {
+        if report.get("runs").is_some_and(Value::is_array) {
+            Some(Self::Sarif)
+        } else if report.get("issues").is_some_and(Value::is_array) {
+            Some(Self::SonarQube)
+        } else {
+            None
+        }
+    }
+}
+
+impl std::fmt::Display for MergeFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Sarif => "sarif",
+            Self::SonarQube => "sonarqube",
+        })
+    }
+}
+
+/// One report to merge.
+#[derive(Debug, Clone)]
+pub struct ReportSource {
+    /// Name findings from this report are tagged with (e.g. the repository).
+    pub name: String,
+    /// Where the report was read from, recorded in SARIF output.
+    pub path: String,
+    /// The report's JSON text.
+    pub contents: String,
+}
+
+impl ReportSource {
+    /// Parse a command-line argument: `NAME=PATH`, or a bare `PATH` named
+    /// after its file stem (`reports/billing.sarif` is `billing`).
+    pub fn parse_arg(arg: &str) -> (String, String) {
+        if let Some((name, path)) = arg.split_once('=')
+            && !name.is_empty()
+        {
+            return (name.to_string(), path.to_string());
+        }
+        let name = std::path::Path::new(arg)
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| arg.to_string());
+        (name, arg.to_string())
+    }
+}
+
+/// Merge `sources` into one report in `format`. Every source must already
+/// be a report in that format.
+pub fn merge_reports(sources: &[ReportSource], format: MergeFormat) -> Result<String, MergeError> {
+    let mut seen = std::collections::HashSet::new();
+    let mut reports = Vec::with_capacity(sources.len());
+    for source in sources {
+        if !seen.insert(source.name.as_str()) {
+            return Err(MergeError::DuplicateSource(source.name.clone()));
+        }
+        let report: Value =
+            serde_json::from_str(&source.contents).map_err(|e| MergeError::Parse {
+                source_name: source.name.clone(),
+                message: e.to_string(),
+            })?;
+        let found = MergeFormat::detect(&report)
+            .ok_or_else(|| MergeError::UnknownFormat(source.name.clone()))?;
+        if found != format {
+            return Err(MergeError::FormatMismatch {
+                source_name: source.name.clone(),
+                expected: format,
+                found,
+            });
+        }
+        reports.push((source, report));
+    }
+
+    let merged = match format {
+        MergeFormat::Sarif => merge_sarif(reports),
+        MergeFormat::SonarQube => merge_sonarqube(reports),
+    };
+    serde_json::to_string_pretty(&merged).map_err(|e| MergeError::Serialization(e.to_string()))
+}
+
+/// Keep every run, tagged with the source it came from.
+fn merge_sarif(reports: Vec<(&ReportSource, Value)>) -> Value {
+    let mut runs = Vec::new();
+    for (source, mut report) in reports {
+        let Some(Value::Array(source_runs)) = report.get_mut("runs").map(Value::take) else {
+            continue;
+        };
+        for mut run in source_runs {
+            let Some(run_obj) = run.as_object_mut() else {
+                continue;
+            };
+            run_obj.insert(
+                "automationDetails".to_string(),
+                json!({ "id": format!("{}/", source.name) }),
+            );
+            let properties = run_obj
+                .entry("properties")
+                .or_insert_with(|| Value::Object(Map::new()));
+            if let Some(properties) = properties.as_object_mut() {
+                properties.insert(
+                    "source".to_string(),
+                    json!({ "name": source.name, "report": source.path }),
+                );
+            }
+            runs.push(run);
+        }
+    }
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": runs,
+    })
+}
+
+/// SonarQube rule severities, lowest first.
+const SONAR_SEVERITIES: [&str; 5] = ["INFO", "MINOR", "MAJOR", "CRITICAL", "BLOCKER"];
+
+fn sonar_severity_rank(rule: &Value) -> Option<usize> {
+    let severity = rule.get("severity")?.as_str()?;
+    SONAR_SEVERITIES.iter().position(|s| *s == severity)
+}
+
+/// Merge rules by ID and prefix every issue's file path with its source.
+fn merge_sonarqube(reports: Vec<(&ReportSource, Value)>) -> Value {
+    let mut rules: BTreeMap<String, Value> = BTreeMap::new();
+    let mut issues = Vec::new();
+    for (source, mut report) in reports {
+        if let Some(Value::Array(source_rules)) = report.get_mut("rules").map(Value::take) {
+            for rule in source_rules {
+                let Some(id) = rule.get("id").and_then(Value::as_str).map(str::to_string) else {
+                    continue;
+                };
+                match rules.get(&id) {
+                    Some(existing)
+                        if sonar_severity_rank(existing) >= sonar_severity_rank(&rule) => {}
+                    _ => {
+                        rules.insert(id, rule);
+                    }
+                }
+            }
+        }
+        if let Some(Value::Array(source_issues)) = report.get_mut("issues").map(Value::take) {
+            for mut issue in source_issues {
+                if let Some(file_path) = issue.pointer_mut("/primaryLocation/filePath")
+                    && let Some(path) = file_path.as_str()
+                {
+                    *file_path = Value::String(format!("{}/{path}", source.name));
+                }
+                issues.push(issue);
+            }
+        }
+    }
+    json!({
+        "rules": rules.into_values().collect::<Vec<_>>(),
+        "issues": issues,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::test_helpers::test_finding;
+    use crate::output::{Reporter, RuleInfo, SarifReporter, SonarQubeReporter};
+    use crate::rules::Severity;
+
+    fn source(name: &str, contents: String) -> ReportSource {
+        ReportSource {
+            name: name.to_string(),
+            path: format!("reports/{name}.json"),
+            contents,
+        }
+    }
+
+    fn sarif() -> String {
+        SarifReporter::new().render(&[test_finding()]).unwrap()
+    }
+
+    fn sonarqube(severity: Severity) -> String {
+        let mut finding = test_finding();
+        finding.severity = severity;
+        SonarQubeReporter::new(RuleInfo::all())
+            .render(&[finding])
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_arg() {
+        assert_eq!(
+            ReportSource::parse_arg("billing=out/findings.sarif"),
+            ("billing".to_string(), "out/findings.sarif".to_string())
+        );
+        assert_eq!(
+            ReportSource::parse_arg("reports/orders.sarif"),
+            ("orders".to_string(), "reports/orders.sarif".to_string())
+        );
+    }
+
+    #[test]
+    fn test_sarif_runs_are_kept_per_source() {
+        let merged = merge_reports(
+            &[source("billing", sarif()), source("orders", sarif())],
+            MergeFormat::Sarif,
+        )
+        .unwrap();
+        let merged: Value = serde_json::from_str(&merged).unwrap();
+        insta::assert_json_snapshot!(merged, {
+            ".runs[].tool.driver.version" => "[version]",
+        });
+    }
+
+    #[test]
+    fn test_sonarqube_issues_prefixed_and_rules_merged() {
+        let merged = merge_reports(
+            &[
+                source("billing", sonarqube(Severity::Minor)),
+                source("orders", sonarqube(Severity::Critical)),
+            ],
+            MergeFormat::SonarQube,
+        )
+        .unwrap();
+        let merged: Value = serde_json::from_str(&merged).unwrap();
+
+        let rules = merged["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0]["severity"], "CRITICAL");
+
+        let paths: Vec<_> = merged["issues"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|i| i["primaryLocation"]["filePath"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                "billing/db/migrations/V042__add_index.sql",
+                "orders/db/migrations/V042__add_index.sql"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_inputs_rejected() {
+        let err = merge_reports(
+            &[
+                source("billing", sarif()),
+                source("orders", sonarqube(Severity::Major)),
+            ],
+            MergeFormat::Sarif,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "report 'orders' is a sonarqube report, expected sarif"
+        );
+
+        let err = merge_reports(
+            &[source("billing", sarif()), source("billing", sarif())],
+            MergeFormat::Sarif,
+        )
+        .unwrap_err();
+        assert!(matches!(err, MergeError::DuplicateSource(_)), "{err}");
+
+        let err =
+            merge_reports(&[source("billing", "{}".to_string())], MergeFormat::Sarif).unwrap_err();
+        assert!(matches!(err, MergeError::UnknownFormat(_)), "{err}");
+
+        let err = merge_reports(
+            &[source("billing", "not json".to_string())],
+            MergeFormat::Sarif,
+        )
+        .unwrap_err();
+        assert!(matches!(err, MergeError::Parse { .. }), "{err}");
+    }
+}
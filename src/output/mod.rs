@@ -1,9 +1,18 @@
 //! Output reporters for different formats
 //!
-//! Supports SARIF 2.1.0, SonarQube Generic Issue Import JSON, and text output.
+//! Supports SARIF 2.1.0, SonarQube Generic Issue Import JSON, text output, a
+//! Markdown summary for pull request comments, and a plain versioned JSON
+//! report for scripts. [`merge`] combines SARIF or SonarQube reports from
+//! several runs into one.
+//!
+//! Every reporter takes [`RenderOptions`] through `with_options`, so library
+//! users can pin the tool version, the run timestamp, and the base directory
+//! of finding paths, and snapshot-test the rendered reports.
 
-use crate::rules::{Finding, Rule, RuleId, Severity};
-use std::path::Path;
+use crate::catalog::dump::CatalogDump;
+use crate::config::PrCommentConfig;
+use crate::rules::{Finding, Rule, RuleId, RuleRegistry, Severity};
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -54,22 +63,78 @@ pub(crate) fn emit_to_file(
     Ok(())
 }
 
+/// Settings that control run-specific details of rendered reports.
+///
+/// The defaults are what the CLI writes: the crate version, no timestamp,
+/// and finding paths as they were discovered. Fixing all three makes the
+/// output identical across machines and releases.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// Tool version recorded in reports that carry one (SARIF). `None` uses
+    /// the crate version.
+    pub tool_version: Option<String>,
+    /// Start time of the run, recorded as-is (SARIF `startTimeUtc`, so use
+    /// RFC 3339 UTC). Omitted when `None`.
+    pub timestamp: Option<String>,
+    /// Finding paths under this directory are rendered relative to it.
+    pub base_dir: Option<PathBuf>,
+}
+
+impl RenderOptions {
+    /// The tool version to record.
+    pub(crate) fn version(&self) -> &str {
+        self.tool_version
+            .as_deref()
+            .unwrap_or(env!("CARGO_PKG_VERSION"))
+    }
+
+    /// A finding path relative to `base_dir`, with forward slashes.
+    pub(crate) fn path(&self, path: &Path) -> String {
+        let relative = self
+            .base_dir
+            .as_deref()
+            .and_then(|base| path.strip_prefix(base).ok())
+            .unwrap_or(path);
+        normalize_path(relative)
+    }
+}
+
 /// Text reporter also supports writing to stdout (for --format text).
 pub struct TextReporter {
     pub use_stdout: bool,
+    options: RenderOptions,
 }
 
 impl TextReporter {
     pub fn new(use_stdout: bool) -> Self {
-        Self { use_stdout }
+        Self {
+            use_stdout,
+            options: RenderOptions::default(),
+        }
+    }
+
+    /// Render with `options` instead of the defaults.
+    pub fn with_options(mut self, options: RenderOptions) -> Self {
+        self.options = options;
+        self
     }
 }
 
-pub struct SarifReporter;
+pub struct SarifReporter {
+    options: RenderOptions,
+}
 
 impl SarifReporter {
     pub fn new() -> Self {
-        Self
+        Self {
+            options: RenderOptions::default(),
+        }
+    }
+
+    /// Render with `options` instead of the defaults.
+    pub fn with_options(mut self, options: RenderOptions) -> Self {
+        self.options = options;
+        self
     }
 }
 
@@ -94,28 +159,113 @@ pub struct RuleInfo {
 impl RuleInfo {
     /// Build rule metadata for all non-meta rules.
     pub fn all() -> Vec<Self> {
-        RuleId::lint_rules()
-            .map(|r| RuleInfo {
-                id: r,
-                name: r.description().to_string(),
-                description: r.explain().to_string(),
-                default_severity: r.default_severity(),
-            })
-            .collect()
+        RuleId::lint_rules().map(Self::of).collect()
+    }
+
+    /// Build rule metadata for every rule of `registry`, including the rules
+    /// registered by rule packs.
+    pub fn registered(registry: &RuleRegistry) -> Vec<Self> {
+        registry.lint_rules().into_iter().map(Self::of).collect()
+    }
+
+    fn of(r: RuleId) -> Self {
+        RuleInfo {
+            id: r,
+            name: r.description().to_string(),
+            description: r.explain().to_string(),
+            default_severity: r.default_severity(),
+        }
     }
 }
 
 pub struct SonarQubeReporter {
     rules: Vec<RuleInfo>,
+    options: RenderOptions,
 }
 
 impl SonarQubeReporter {
     /// Create a new SonarQube reporter with rule metadata for the 10.3+ format.
     pub fn new(rules: Vec<RuleInfo>) -> Self {
-        Self { rules }
+        Self {
+            rules,
+            options: RenderOptions::default(),
+        }
+    }
+
+    /// Render with `options` instead of the defaults.
+    pub fn with_options(mut self, options: RenderOptions) -> Self {
+        self.options = options;
+        self
     }
 }
 
+/// Markdown summary for posting as a pull/merge request comment.
+pub struct PrCommentReporter {
+    /// Most findings listed; the rest are only counted.
+    pub max_items: usize,
+    /// Base URL that finding paths are appended to for links.
+    pub source_url: Option<String>,
+    /// Link to the full reports, added at the end.
+    pub artifacts_url: Option<String>,
+    options: RenderOptions,
+}
+
+impl PrCommentReporter {
+    /// Create a reporter from `[output.pr_comment]`.
+    pub fn new(config: &PrCommentConfig) -> Self {
+        Self {
+            max_items: config.max_items,
+            source_url: config.source_url.clone(),
+            artifacts_url: config.artifacts_url.clone(),
+            options: RenderOptions::default(),
+        }
+    }
+
+    /// Render with `options` instead of the defaults.
+    pub fn with_options(mut self, options: RenderOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+/// Plain JSON report for CI scripts: findings, counts by rule and severity,
+/// and optionally the replayed catalog. The layout is versioned
+/// ([`JSON_REPORT_VERSION`]).
+pub struct JsonReporter {
+    catalog: Option<CatalogDump>,
+    options: RenderOptions,
+}
+
+impl JsonReporter {
+    pub fn new() -> Self {
+        Self {
+            catalog: None,
+            options: RenderOptions::default(),
+        }
+    }
+
+    /// Add the catalog state to the report.
+    pub fn with_catalog(mut self, catalog: CatalogDump) -> Self {
+        self.catalog = Some(catalog);
+        self
+    }
+
+    /// Render with `options` instead of the defaults.
+    pub fn with_options(mut self, options: RenderOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+impl Default for JsonReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Base URL for the hosted rule documentation (GitHub Pages).
+pub(crate) const DOCS_BASE_URL: &str = "https://robert-sjoblom.github.io/pg-migration-lint/rules";
+
 /// Normalize a path to use forward slashes for cross-platform output.
 pub(crate) fn normalize_path(path: &Path) -> String {
     path.to_string_lossy().replace('\\', "/")
@@ -124,10 +274,16 @@ pub(crate) fn normalize_path(path: &Path) -> String {
 #[cfg(test)]
 pub mod test_helpers;
 
+pub(crate) mod json;
+pub mod merge;
+pub(crate) mod pr_comment;
+pub mod redact;
 pub(crate) mod sarif;
 pub(crate) mod sonarqube;
 pub(crate) mod text;
 
+pub use json::JSON_REPORT_VERSION;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -139,7 +295,7 @@ mod tests {
     #[test]
     fn emit_creates_file_on_disk() {
         let dir = tempfile::tempdir().expect("tempdir");
-        let reporter = SarifReporter;
+        let reporter = SarifReporter::new();
         reporter.emit(&[test_finding()], dir.path()).expect("emit");
         let path = dir.path().join("findings.sarif");
         assert!(path.exists(), "findings.sarif should exist");
@@ -151,7 +307,7 @@ mod tests {
     fn emit_creates_output_directory() {
         let dir = tempfile::tempdir().expect("tempdir");
         let nested = dir.path().join("a").join("b").join("c");
-        let reporter = SarifReporter;
+        let reporter = SarifReporter::new();
         reporter.emit(&[test_finding()], &nested).expect("emit");
         let path = nested.join("findings.sarif");
         assert!(path.exists(), "findings.sarif should exist in nested dir");
@@ -160,7 +316,7 @@ mod tests {
     #[test]
     fn emit_overwrites_existing_file() {
         let dir = tempfile::tempdir().expect("tempdir");
-        let reporter = SarifReporter;
+        let reporter = SarifReporter::new();
 
         let first = vec![Finding::new(
             RuleId::Pgm001,
@@ -191,7 +347,7 @@ mod tests {
     #[test]
     fn text_emit_stdout_does_not_create_file() {
         let dir = tempfile::tempdir().expect("tempdir");
-        let reporter = TextReporter { use_stdout: true };
+        let reporter = TextReporter::new(true);
         reporter.emit(&[test_finding()], dir.path()).expect("emit");
         let path = dir.path().join("findings.txt");
         assert!(
@@ -202,7 +358,7 @@ mod tests {
 
     #[test]
     fn sarif_filename() {
-        assert_eq!(SarifReporter.filename(), "findings.sarif");
+        assert_eq!(SarifReporter::new().filename(), "findings.sarif");
     }
 
     #[test]
@@ -211,12 +367,58 @@ mod tests {
         assert_eq!(reporter.filename(), "findings.json");
     }
 
+    #[test]
+    fn json_filename() {
+        assert_eq!(JsonReporter::new().filename(), "report.json");
+    }
+
+    #[test]
+    fn pr_comment_filename() {
+        let reporter = PrCommentReporter::new(&crate::config::PrCommentConfig::default());
+        assert_eq!(reporter.filename(), "pr-comment.md");
+    }
+
     #[test]
     fn text_filename() {
         let reporter = TextReporter::new(false);
         assert_eq!(reporter.filename(), "findings.txt");
     }
 
+    #[test]
+    fn render_options_path_relative_to_base_dir() {
+        let options = RenderOptions {
+            base_dir: Some("/work/repo".into()),
+            ..RenderOptions::default()
+        };
+        assert_eq!(
+            options.path(Path::new("/work/repo/db/V001.sql")),
+            "db/V001.sql"
+        );
+        assert_eq!(
+            options.path(Path::new("/elsewhere/V001.sql")),
+            "/elsewhere/V001.sql"
+        );
+        assert_eq!(
+            RenderOptions::default().version(),
+            env!("CARGO_PKG_VERSION")
+        );
+    }
+
+    #[test]
+    fn text_render_uses_render_options() {
+        let mut finding = test_finding();
+        finding.file = Path::new("/work/repo/db/V001.sql").to_path_buf();
+        let reporter = TextReporter::new(false).with_options(RenderOptions {
+            base_dir: Some("/work/repo".into()),
+            ..RenderOptions::default()
+        });
+        let content = reporter.render(&[finding]).expect("render");
+        assert!(
+            content.starts_with("CRITICAL PGM001 db/V001.sql:3\n"),
+            "{content}"
+        );
+    }
+
     #[test]
     fn emit_to_file_writes_text_report() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -0,0 +1,270 @@
+//! Pull request comment reporter
+//!
+//! Produces a Markdown summary meant to be posted as a GitHub or GitLab
+//! pull/merge request comment: finding counts per severity, a table of the
+//! most severe findings, their full messages in a collapsible block, and a
+//! link to the full reports.
+//!
+//! ```text
+//! ## pg-migration-lint
+//!
+//! **2 findings** in 1 file: 1 critical, 1 major.
+//!
+//! | Severity | Rule | Location | Message |
+//! | --- | --- | --- | --- |
+//! | CRITICAL | [PGM001](…#pgm001) | `db/migrations/V042__add_index.sql:3` | CREATE INDEX on … |
+//! ```
+
+use crate::output::{DOCS_BASE_URL, PrCommentReporter, ReportError, Reporter};
+use crate::rules::{Finding, Severity};
+use std::collections::HashSet;
+use std::fmt::Write as _;
+
+/// Severities in the order they are counted and listed.
+const SEVERITY_ORDER: [Severity; 5] = [
+    Severity::Blocker,
+    Severity::Critical,
+    Severity::Major,
+    Severity::Minor,
+    Severity::Info,
+];
+
+/// Longest message shown in the table; the full text is in the details.
+const TABLE_MESSAGE_CHARS: usize = 120;
+
+impl PrCommentReporter {
+    /// `file:line`, linked to the line under `source_url` when configured,
+    /// then the changeset when the finding's unit is not the whole file.
+    fn location(&self, finding: &Finding) -> String {
+        let path = self.options.path(&finding.file);
+        let label = format!("`{path}:{}`", finding.start_line);
+        let location = match &self.source_url {
+            Some(base) => format!(
+                "[{label}]({}/{path}#L{})",
+                base.trim_end_matches('/'),
+                finding.start_line
+            ),
+            None => label,
+        };
+        match finding.unit_label() {
+            Some(unit) => format!("{location} ({})", table_cell(&unit)),
+            None => location,
+        }
+    }
+}
+
+/// Link a rule id to its section in the hosted rule docs.
+fn rule_link(finding: &Finding) -> String {
+    let id = finding.rule_id.as_str();
+    format!("[{id}]({DOCS_BASE_URL}#{})", id.to_lowercase())
+}
+
+/// Escape text so Markdown and HTML in a message render literally.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// A message fitted into one table cell: single line, no pipes, truncated.
+fn table_cell(message: &str) -> String {
+    let line = message.split_whitespace().collect::<Vec<_>>().join(" ");
+    let line = match line.char_indices().nth(TABLE_MESSAGE_CHARS) {
+        Some((cut, _)) => format!("{}…", line[..cut].trim_end()),
+        None => line,
+    };
+    escape(&line).replace('|', "\\|")
+}
+
+/// A code fence longer than any backtick run in `text`.
+fn fence_for(text: &str) -> String {
+    let longest = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+fn plural(count: usize, word: &str) -> String {
+    if count == 1 {
+        format!("{count} {word}")
+    } else {
+        format!("{count} {word}s")
+    }
+}
+
+impl Reporter for PrCommentReporter {
+    fn render(&self, findings: &[Finding]) -> Result<String, ReportError> {
+        let mut out = String::from("## pg-migration-lint\n\n");
+
+        if findings.is_empty() {
+            out.push_str("No findings.\n");
+        } else {
+            let files: HashSet<_> = findings.iter().map(|f| &f.file).collect();
+            let counts: Vec<String> = SEVERITY_ORDER
+                .iter()
+                .filter_map(|severity| {
+                    let n = findings.iter().filter(|f| f.severity == *severity).count();
+                    (n > 0).then(|| format!("{n} {}", severity.title_case().to_lowercase()))
+                })
+                .collect();
+            let _ = writeln!(
+                out,
+                "**{}** in {}: {}.",
+                plural(findings.len(), "finding"),
+                plural(files.len(), "file"),
+                counts.join(", ")
+            );
+
+            // Most severe first; the stable sort keeps the file/line order
+            // reporters receive within each severity.
+            let mut top: Vec<&Finding> = findings.iter().collect();
+            top.sort_by_key(|f| std::cmp::Reverse(f.severity));
+            top.truncate(self.max_items);
+
+            if !top.is_empty() {
+                out.push_str("\n| Severity | Rule | Location | Message |\n");
+                out.push_str("| --- | --- | --- | --- |\n");
+                for f in &top {
+                    let _ = writeln!(
+                        out,
+                        "| {} | {} | {} | {} |",
+                        f.severity,
+                        rule_link(f),
+                        self.location(f),
+                        table_cell(&f.message)
+                    );
+                }
+            }
+            if top.len() < findings.len() {
+                let _ = writeln!(
+                    out,
+                    "\nShowing the {} most severe of {} findings.",
+                    top.len(),
+                    findings.len()
+                );
+            }
+
+            if !top.is_empty() {
+                out.push_str("\n<details>\n<summary>Details</summary>\n");
+                for f in &top {
+                    let _ = write!(
+                        out,
+                        "\n#### {} {} — {}\n\n{}\n",
+                        f.severity,
+                        f.rule_id,
+                        self.location(f),
+                        escape(&f.message)
+                    );
+                    if let Some(ack) = &f.acknowledged {
+                        let _ = write!(out, "\nAcknowledged (was {})", ack.original_severity);
+                        if let Some(ticket) = &ack.ticket {
+                            let _ = write!(out, ", ticket {}", escape(ticket));
+                        }
+                        out.push_str(".\n");
+                    }
+                    if let Some(snippet) = &f.snippet {
+                        let fence = fence_for(snippet);
+                        let _ = write!(out, "\n{fence}sql\n{snippet}\n{fence}\n");
+                    }
+                }
+                out.push_str("\n</details>\n");
+            }
+        }
+
+        if let Some(url) = &self.artifacts_url {
+            let _ = write!(out, "\nFull reports: {url}\n");
+        }
+
+        Ok(out)
+    }
+
+    fn filename(&self) -> &str {
+        "pr-comment.md"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PrCommentConfig;
+    use crate::output::test_helpers::test_finding;
+    use crate::parser::SourceSpan;
+    use crate::rules::{Acknowledgment, RuleId};
+    use std::path::Path;
+
+    fn reporter(max_items: usize) -> PrCommentReporter {
+        PrCommentReporter::new(&PrCommentConfig {
+            max_items,
+            source_url: Some("https://github.com/acme/shop/blob/main/".to_string()),
+            artifacts_url: Some("https://ci.example.com/jobs/42/artifacts".to_string()),
+        })
+    }
+
+    fn findings() -> Vec<Finding> {
+        let mut fk = Finding::new(
+            RuleId::Pgm501,
+            Severity::Major,
+            "Foreign key on 'order_items(order_id)' has no covering index.".to_string(),
+            Path::new("db/migrations/V042__add_index.sql"),
+            &SourceSpan::at(7, 7),
+        );
+        fk.snippet = Some(
+            "ALTER TABLE order_items ADD CONSTRAINT fk_order FOREIGN KEY (order_id) REFERENCES orders (id)"
+                .to_string(),
+        );
+        let mut acked = Finding::new(
+            RuleId::Pgm006,
+            Severity::Info,
+            "Column 'status' on 'orders' uses function 'a | b()' as default.".to_string(),
+            Path::new("db/migrations/V043__defaults.sql"),
+            &SourceSpan::at(2, 2),
+        );
+        acked.acknowledged = Some(Acknowledgment {
+            ticket: Some("DB-17".to_string()),
+            original_severity: Severity::Minor,
+        });
+        vec![test_finding(), fk, acked]
+    }
+
+    #[test]
+    fn summary_table_and_details() {
+        let content = reporter(20).render(&findings()).expect("render");
+        insta::assert_snapshot!(content);
+    }
+
+    #[test]
+    fn max_items_caps_table_by_severity() {
+        let content = reporter(1).render(&findings()).expect("render");
+        assert!(content.contains("**3 findings** in 2 files: 1 critical, 1 major, 1 info."));
+        assert!(content.contains("| CRITICAL | [PGM001]"));
+        assert!(!content.contains("| MAJOR |"));
+        assert!(content.contains("Showing the 1 most severe of 3 findings."));
+        assert!(content.ends_with("Full reports: https://ci.example.com/jobs/42/artifacts\n"));
+    }
+
+    #[test]
+    fn no_findings() {
+        let reporter = PrCommentReporter::new(&PrCommentConfig::default());
+        let content = reporter.render(&[]).expect("render");
+        assert_eq!(content, "## pg-migration-lint\n\nNo findings.\n");
+    }
+
+    #[test]
+    fn long_message_truncated_in_table_only() {
+        let mut finding = test_finding();
+        finding.message = "word ".repeat(60);
+        let content = PrCommentReporter::new(&PrCommentConfig::default())
+            .render(&[finding])
+            .expect("render");
+        let row = content
+            .lines()
+            .find(|l| l.starts_with("| CRITICAL"))
+            .unwrap();
+        assert!(row.ends_with("word… |"), "{row}");
+        assert!(content.contains(&"word ".repeat(60).trim_end().to_string()));
+    }
+
+    #[test]
+    fn fence_longer_than_backticks_in_snippet() {
+        assert_eq!(fence_for("SELECT 1"), "```");
+        assert_eq!(fence_for("SELECT '````'"), "`````");
+    }
+}
@@ -0,0 +1,213 @@
+//! Literal redaction for SQL echoed into reports
+//!
+//! With `output.redact_literals = true`, SQL fragments copied from migrations
+//! into finding messages, template parameters, or the `--emit-catalog` dump
+//! have their string and numeric literals masked, so seed values (tokens,
+//! e-mail addresses) do not leave the build in report artifacts.
+//! Identifiers, keywords, and operators are kept so the fragment still shows
+//! what the rule is talking about. Rules record the text they quote on the
+//! finding (`Finding::with_quoted_sql`), and the pipeline masks it before
+//! `[messages]` templates are applied.
+
+use crate::parser::lex::{dollar_tag, is_ident_byte, skip_block_comment, skip_string};
+
+/// Mask for a string literal (single-quoted, escape, or dollar-quoted).
+const STRING_MASK: &str = "'?'";
+
+/// Mask for a numeric literal.
+const NUMBER_MASK: &str = "?";
+
+/// Replace every string and numeric literal in `sql` with a placeholder.
+///
+/// `'...'` (including `E'...'`, `B'...'`, `X'...'` bodies) and `$tag$...$tag$`
+/// become `'?'`; numbers become `?`. Quoted identifiers, positional
+/// parameters (`$1`), and digits inside identifiers (`col2`) are left alone.
+/// Comments are copied as-is.
+pub fn redact_literals(sql: &str) -> String {
+    let mut out = String::with_capacity(sql.len());
+    let bytes = sql.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let prev_is_ident = i > 0 && is_ident_byte(bytes[i - 1]);
+        match bytes[i] {
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                let end = sql[i..].find('\n').map_or(sql.len(), |n| i + n);
+                out.push_str(&sql[i..end]);
+                i = end;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let end = skip_block_comment(bytes, i + 2);
+                out.push_str(&sql[i..end]);
+                i = end;
+            }
+            b'"' => {
+                let end = sql[i + 1..].find('"').map_or(sql.len(), |n| i + n + 2);
+                out.push_str(&sql[i..end]);
+                i = end;
+            }
+            b'\'' => {
+                let backslash_escapes = i > 0 && matches!(bytes[i - 1], b'E' | b'e');
+                i = skip_string(bytes, i + 1, backslash_escapes);
+                out.push_str(STRING_MASK);
+            }
+            b'$' if !prev_is_ident => match dollar_tag(&sql[i..]) {
+                Some(tag) => {
+                    let body = i + tag.len();
+                    i = sql[body..]
+                        .find(tag)
+                        .map_or(sql.len(), |n| body + n + tag.len());
+                    out.push_str(STRING_MASK);
+                }
+                None => {
+                    // Positional parameter ($1) or stray `$`: keep verbatim.
+                    let end = i
+                        + 1
+                        + bytes[i + 1..]
+                            .iter()
+                            .take_while(|b| b.is_ascii_digit())
+                            .count();
+                    out.push_str(&sql[i..end]);
+                    i = end;
+                }
+            },
+            b'0'..=b'9' if !prev_is_ident => {
+                i = skip_number(bytes, i);
+                out.push_str(NUMBER_MASK);
+            }
+            _ => {
+                // Copy one whole character: advance to the next char boundary.
+                let end = (i + 1..=sql.len())
+                    .find(|&end| sql.is_char_boundary(end))
+                    .unwrap_or(sql.len());
+                out.push_str(sql.get(i..end).unwrap_or_default());
+                i = end;
+            }
+        }
+    }
+
+    out
+}
+
+/// Redact a value that is a literal in its entirety, such as a
+/// `DefaultExpr::Literal`. Booleans and `NULL` carry no data and are kept.
+pub fn redact_literal_value(value: &str) -> String {
+    if ["true", "false", "null"]
+        .iter()
+        .any(|kw| value.eq_ignore_ascii_case(kw))
+    {
+        value.to_string()
+    } else if !value.is_empty()
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_digit() || b == b'.' || b == b'-')
+    {
+        NUMBER_MASK.to_string()
+    } else {
+        STRING_MASK.to_string()
+    }
+}
+
+/// Redact a PostgreSQL error message. Besides the literals
+/// [`redact_literals`] masks, a double-quoted value the server reports after
+/// a colon (`invalid input syntax for type integer: "x"`) is masked; other
+/// double-quoted names (`relation "orders"`) are kept.
+pub fn redact_error_message(message: &str) -> String {
+    let redacted = redact_literals(message);
+    let mut out = String::with_capacity(redacted.len());
+    let mut rest = redacted.as_str();
+    while let Some(pos) = rest.find(": \"") {
+        out.push_str(&rest[..pos + 2]);
+        out.push_str(STRING_MASK);
+        let value = &rest[pos + 3..];
+        rest = value.find('"').map_or("", |end| &value[end + 1..]);
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Index just past a numeric literal starting at `i`: digits, an optional
+/// fraction, and an optional exponent.
+fn skip_number(bytes: &[u8], mut i: usize) -> usize {
+    let digits = |i: usize| i + bytes[i..].iter().take_while(|b| b.is_ascii_digit()).count();
+    i = digits(i);
+    if bytes.get(i) == Some(&b'.') && bytes.get(i + 1).is_some_and(u8::is_ascii_digit) {
+        i = digits(i + 1);
+    }
+    if matches!(bytes.get(i), Some(b'e' | b'E')) {
+        let sign = usize::from(matches!(bytes.get(i + 1), Some(b'+' | b'-')));
+        if bytes.get(i + 1 + sign).is_some_and(u8::is_ascii_digit) {
+            i = digits(i + 1 + sign);
+        }
+    }
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_masks_strings_and_numbers() {
+        assert_eq!(
+            redact_literals("status IN ('pending', 'shipped') AND qty > 10.5e3"),
+            "status IN ('?', '?') AND qty > ?"
+        );
+    }
+
+    #[test]
+    fn test_escaped_quotes_and_casts() {
+        assert_eq!(
+            redact_literals("note <> 'it''s' AND ts >= '2024-01-01'::date"),
+            "note <> '?' AND ts >= '?'::date"
+        );
+        assert_eq!(
+            redact_literals(r"v = E'a\'b' OR v = 'c'"),
+            "v = E'?' OR v = '?'"
+        );
+    }
+
+    #[test]
+    fn test_error_message_values_masked_names_kept() {
+        assert_eq!(
+            redact_error_message("invalid input syntax for type integer: \"4111-1111\""),
+            "invalid input syntax for type integer: '?'"
+        );
+        assert_eq!(
+            redact_error_message("relation \"orders\" already exists"),
+            "relation \"orders\" already exists"
+        );
+    }
+
+    #[test]
+    fn test_dollar_quotes_and_parameters() {
+        assert_eq!(
+            redact_literals("token = $x$s3cr3t$x$ OR id = $1"),
+            "token = '?' OR id = $1"
+        );
+    }
+
+    #[test]
+    fn test_identifiers_are_kept() {
+        assert_eq!(
+            redact_literals(r#"col2 > 0 AND "Weird 7" = 'x' AND email LIKE '%@corp.example'"#),
+            r#"col2 > ? AND "Weird 7" = '?' AND email LIKE '?'"#
+        );
+    }
+
+    #[test]
+    fn test_multibyte_characters_are_kept() {
+        assert_eq!(
+            redact_literals("prix ≥ 5 AND «x» = 'é' AND café → 1"),
+            "prix ≥ ? AND «x» = '?' AND café → ?"
+        );
+    }
+
+    #[test]
+    fn test_literal_value() {
+        assert_eq!(redact_literal_value("ops@example.com"), "'?'");
+        assert_eq!(redact_literal_value("42"), "?");
+        assert_eq!(redact_literal_value("true"), "true");
+        assert_eq!(redact_literal_value("NULL"), "NULL");
+    }
+}
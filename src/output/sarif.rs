@@ -4,10 +4,13 @@
 //! compatible with GitHub Code Scanning. Upload via `github/codeql-action/upload-sarif@v3`.
 
 use crate::output::{ReportError, Reporter, SarifReporter};
-use crate::rules::{Finding, RuleId, Severity};
+use crate::rules::{Acknowledgment, Finding, RuleId, Severity};
 use serde::Serialize;
 use std::collections::BTreeMap;
 
+/// JSON schema URI of SARIF 2.1.0 logs.
+pub(crate) const SARIF_SCHEMA: &str = "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/main/sarif-2.1/schema/sarif-schema-2.1.0.json";
+
 /// Top-level SARIF envelope.
 #[derive(Serialize)]
 struct SarifLog {
@@ -21,9 +24,19 @@ struct SarifLog {
 #[derive(Serialize)]
 struct SarifRun {
     tool: SarifTool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    invocations: Vec<SarifInvocation>,
     results: Vec<SarifResult>,
 }
 
+/// A SARIF invocation. Only written when a run timestamp is given.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifInvocation {
+    execution_successful: bool,
+    start_time_utc: String,
+}
+
 /// SARIF tool descriptor.
 #[derive(Serialize)]
 struct SarifTool {
@@ -35,7 +48,7 @@ struct SarifTool {
 #[serde(rename_all = "camelCase")]
 struct SarifDriver {
     name: &'static str,
-    version: &'static str,
+    version: String,
     information_uri: &'static str,
     rules: Vec<SarifRuleDescriptor>,
 }
@@ -63,6 +76,14 @@ struct SarifResult {
     level: &'static str,
     message: SarifMessage,
     locations: Vec<SarifLocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    properties: Option<SarifResultProperties>,
+}
+
+/// SARIF result property bag. Only present for acknowledged findings.
+#[derive(Serialize)]
+struct SarifResultProperties {
+    acknowledged: Acknowledgment,
 }
 
 /// SARIF message wrapper.
@@ -76,6 +97,18 @@ struct SarifMessage {
 #[serde(rename_all = "camelCase")]
 struct SarifLocation {
     physical_location: SarifPhysicalLocation,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    logical_locations: Vec<SarifLogicalLocation>,
+}
+
+/// The changeset a result belongs to, when it is not the whole file.
+/// `fullyQualifiedName` follows Liquibase's `file::id::author` identity.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLogicalLocation {
+    name: String,
+    fully_qualified_name: String,
+    kind: &'static str,
 }
 
 /// SARIF physical location (file + region).
@@ -92,12 +125,20 @@ struct SarifArtifactLocation {
     uri: String,
 }
 
-/// SARIF region (line range).
+/// SARIF region (line range, plus the statement text when snippets are on).
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 struct SarifRegion {
     start_line: usize,
     end_line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snippet: Option<SarifArtifactContent>,
+}
+
+/// SARIF artifact content (the `region.snippet` text).
+#[derive(Serialize)]
+struct SarifArtifactContent {
+    text: String,
 }
 
 /// Map a finding severity to a SARIF level string.
@@ -109,11 +150,6 @@ fn sarif_level(severity: &Severity) -> &'static str {
     }
 }
 
-/// Convert a file path to a SARIF-compatible URI with forward slashes.
-fn path_to_uri(path: &std::path::Path) -> String {
-    super::normalize_path(path)
-}
-
 /// Collect unique rules from findings, keyed by rule_id.
 ///
 /// Returns a map from rule_id to the highest severity seen for that rule,
@@ -163,29 +199,59 @@ impl Reporter for SarifReporter {
                 locations: vec![SarifLocation {
                     physical_location: SarifPhysicalLocation {
                         artifact_location: SarifArtifactLocation {
-                            uri: path_to_uri(&f.file),
+                            uri: self.options.path(&f.file),
                         },
                         region: SarifRegion {
                             start_line: f.start_line,
                             end_line: f.end_line,
+                            snippet: f.snippet.clone().map(|text| SarifArtifactContent { text }),
                         },
                     },
+                    logical_locations: f
+                        .changeset()
+                        .map(|unit| {
+                            let mut fully_qualified_name =
+                                format!("{}::{}", self.options.path(&f.file), unit.id);
+                            if let Some(author) = &unit.author {
+                                fully_qualified_name.push_str(&format!("::{author}"));
+                            }
+                            SarifLogicalLocation {
+                                name: unit.id.clone(),
+                                fully_qualified_name,
+                                kind: "module",
+                            }
+                        })
+                        .into_iter()
+                        .collect(),
                 }],
+                properties: f
+                    .acknowledged
+                    .clone()
+                    .map(|acknowledged| SarifResultProperties { acknowledged }),
             })
             .collect();
 
         let log = SarifLog {
-            schema: "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/main/sarif-2.1/schema/sarif-schema-2.1.0.json",
+            schema: SARIF_SCHEMA,
             version: "2.1.0",
             runs: vec![SarifRun {
                 tool: SarifTool {
                     driver: SarifDriver {
                         name: "pg-migration-lint",
-                        version: env!("CARGO_PKG_VERSION"),
+                        version: self.options.version().to_string(),
                         information_uri: "https://github.com/robert-sjoblom/pg-migration-lint",
                         rules,
                     },
                 },
+                invocations: self
+                    .options
+                    .timestamp
+                    .iter()
+                    .map(|start_time_utc| SarifInvocation {
+                        execution_successful: true,
+                        start_time_utc: start_time_utc.clone(),
+                    })
+                    .collect(),
                 results,
             }],
         };
@@ -202,14 +268,15 @@ impl Reporter for SarifReporter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::output::RenderOptions;
     use crate::output::test_helpers::test_finding;
     use crate::parser::SourceSpan;
-    use crate::rules::{Finding, Severity};
+    use crate::rules::{Finding, Severity, UnitRef};
     use std::path::Path;
 
     /// Helper: render findings via SarifReporter and parse the resulting JSON.
     fn emit_and_parse(findings: &[Finding]) -> serde_json::Value {
-        let reporter = SarifReporter;
+        let reporter = SarifReporter::new();
         let json = reporter.render(findings).expect("render");
         serde_json::from_str(&json).expect("parse json")
     }
@@ -223,6 +290,48 @@ mod tests {
         });
     }
 
+    #[test]
+    fn snippet_emitted_as_region_snippet() {
+        let mut finding = test_finding();
+        let parsed = emit_and_parse(std::slice::from_ref(&finding));
+        let region = &parsed["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["region"];
+        assert!(region.get("snippet").is_none());
+
+        finding.snippet = Some("CREATE INDEX idx_orders_status ON orders (status)".to_string());
+        let parsed = emit_and_parse(&[finding]);
+        let region = &parsed["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(
+            region["snippet"]["text"],
+            "CREATE INDEX idx_orders_status ON orders (status)"
+        );
+    }
+
+    #[test]
+    fn changeset_emitted_as_logical_location() {
+        let mut finding = test_finding();
+        finding.unit = Some(UnitRef {
+            id: "V042__add_index.sql".to_string(),
+            author: None,
+        });
+        let parsed = emit_and_parse(std::slice::from_ref(&finding));
+        let location = &parsed["runs"][0]["results"][0]["locations"][0];
+        assert!(location.get("logicalLocations").is_none());
+
+        finding.file = Path::new("db/changelog.xml").to_path_buf();
+        finding.unit = Some(UnitRef {
+            id: "42-add-index".to_string(),
+            author: Some("alice".to_string()),
+        });
+        let parsed = emit_and_parse(&[finding]);
+        let logical = &parsed["runs"][0]["results"][0]["locations"][0]["logicalLocations"][0];
+        assert_eq!(logical["name"], "42-add-index");
+        assert_eq!(
+            logical["fullyQualifiedName"],
+            "db/changelog.xml::42-add-index::alice"
+        );
+        assert_eq!(logical["kind"], "module");
+    }
+
     #[test]
     fn no_findings_produces_empty_results() {
         let findings: Vec<Finding> = vec![];
@@ -471,4 +580,22 @@ mod tests {
             ".runs[0].tool.driver.version" => "[version]",
         });
     }
+
+    #[test]
+    fn render_options_pin_version_timestamp_and_paths() {
+        let mut finding = test_finding();
+        finding.file = Path::new("/work/repo/db/migrations/V042__add_index.sql").to_path_buf();
+        let options = RenderOptions {
+            tool_version: Some("0.0.0-test".to_string()),
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            base_dir: Some("/work/repo".into()),
+        };
+        let json = SarifReporter::new()
+            .with_options(options)
+            .render(&[finding])
+            .expect("render");
+        let parsed: serde_json::Value = serde_json::from_str(&json).expect("parse json");
+
+        insta::assert_json_snapshot!(parsed);
+    }
 }
@@ -4,14 +4,11 @@
 //! with a top-level `rules` array containing clean-code attributes and impacts.
 //! See: <https://docs.sonarsource.com/sonarqube-server/10.3/analyzing-source-code/importing-external-issues/generic-issue-import-format/>
 
-use crate::output::{ReportError, Reporter, SonarQubeReporter};
+use crate::output::{DOCS_BASE_URL, ReportError, Reporter, RuleInfo, SonarQubeReporter};
 use crate::rules::{Finding, RuleId};
 use serde::Serialize;
 use std::collections::HashSet;
 
-/// Base URL for the hosted rule documentation (GitHub Pages).
-const DOCS_BASE_URL: &str = "https://robert-sjoblom.github.io/pg-migration-lint/rules";
-
 /// Build a concise SonarQube rule description with a link to full documentation.
 ///
 /// SonarQube's Generic Issue Import format only supports plain strings (no
@@ -51,12 +48,51 @@ fn sonarqube_meta(rule_id: RuleId) -> SonarQubeRuleMeta {
         | RuleId::Pgm018
         | RuleId::Pgm019
         | RuleId::Pgm021
-        | RuleId::Pgm022 => SonarQubeRuleMeta {
+        | RuleId::Pgm022
+        | RuleId::Pgm024
+        | RuleId::Pgm029
+        | RuleId::Pgm031 => SonarQubeRuleMeta {
+            clean_code_attribute: "COMPLETE",
+            issue_type: "BUG",
+            software_quality: "RELIABILITY",
+            impact_severity: "HIGH",
+        },
+        // Out-of-order migrations: the earlier one fails at deploy
+        RuleId::Pgm027 => SonarQubeRuleMeta {
+            clean_code_attribute: "LOGICAL",
+            issue_type: "BUG",
+            software_quality: "RELIABILITY",
+            impact_severity: "HIGH",
+        },
+        // CHECK with a subquery fails at deploy; non-immutable CHECKs break later writes
+        RuleId::Pgm028 => SonarQubeRuleMeta {
+            clean_code_attribute: "LOGICAL",
+            issue_type: "BUG",
+            software_quality: "RELIABILITY",
+            impact_severity: "HIGH",
+        },
+        // References to missing columns and rejected or failing statements fail at deploy
+        RuleId::Pgm033 | RuleId::Pgm034 | RuleId::Pgm036 => SonarQubeRuleMeta {
+            clean_code_attribute: "LOGICAL",
+            issue_type: "BUG",
+            software_quality: "RELIABILITY",
+            impact_severity: "HIGH",
+        },
+        // REPLICA IDENTITY FULL inflates WAL; a dropped identity index breaks CDC
+        RuleId::Pgm035 => SonarQubeRuleMeta {
             clean_code_attribute: "COMPLETE",
             issue_type: "BUG",
             software_quality: "RELIABILITY",
             impact_severity: "HIGH",
         },
+        // Several constraints under one lock: longer lock, all-or-nothing retry
+        // Estimated lock duration over budget: an approximation, not a failure
+        RuleId::Pgm030 | RuleId::Pgm032 => SonarQubeRuleMeta {
+            clean_code_attribute: "EFFICIENT",
+            issue_type: "CODE_SMELL",
+            software_quality: "RELIABILITY",
+            impact_severity: "MEDIUM",
+        },
         // Combinable ALTER TABLEs: minor lock contention improvement
         RuleId::Pgm023 => SonarQubeRuleMeta {
             clean_code_attribute: "EFFICIENT",
@@ -79,7 +115,12 @@ fn sonarqube_meta(rule_id: RuleId) -> SonarQubeRuleMeta {
             impact_severity: "MEDIUM",
         },
         // Silent constraint drops / trigger disables: risk data integrity
-        RuleId::Pgm010 | RuleId::Pgm011 | RuleId::Pgm012 | RuleId::Pgm020 => SonarQubeRuleMeta {
+        RuleId::Pgm010
+        | RuleId::Pgm011
+        | RuleId::Pgm012
+        | RuleId::Pgm020
+        | RuleId::Pgm025
+        | RuleId::Pgm026 => SonarQubeRuleMeta {
             clean_code_attribute: "COMPLETE",
             issue_type: "BUG",
             software_quality: "RELIABILITY",
@@ -93,14 +134,14 @@ fn sonarqube_meta(rule_id: RuleId) -> SonarQubeRuleMeta {
             impact_severity: "MEDIUM",
         },
         // Performance: missing FK index
-        RuleId::Pgm501 => SonarQubeRuleMeta {
+        RuleId::Pgm501 | RuleId::Pgm522 => SonarQubeRuleMeta {
             clean_code_attribute: "EFFICIENT",
             issue_type: "CODE_SMELL",
             software_quality: "MAINTAINABILITY",
             impact_severity: "MEDIUM",
         },
         // Schema quality: table without PK, rename operations
-        RuleId::Pgm502 | RuleId::Pgm504 | RuleId::Pgm505 => SonarQubeRuleMeta {
+        RuleId::Pgm502 | RuleId::Pgm504 | RuleId::Pgm505 | RuleId::Pgm512 => SonarQubeRuleMeta {
             clean_code_attribute: "COMPLETE",
             issue_type: "CODE_SMELL",
             software_quality: "MAINTAINABILITY",
@@ -120,13 +161,101 @@ fn sonarqube_meta(rule_id: RuleId) -> SonarQubeRuleMeta {
             software_quality: "MAINTAINABILITY",
             impact_severity: "MEDIUM",
         },
-        // CREATE UNLOGGED TABLE, DROP NOT NULL, mixed-case/reserved identifiers
-        RuleId::Pgm506 | RuleId::Pgm507 | RuleId::Pgm509 => SonarQubeRuleMeta {
+        // Schema boundaries: another team's tables changed, or objects
+        // created outside the service schema
+        RuleId::Pgm511 | RuleId::Pgm527 => SonarQubeRuleMeta {
+            clean_code_attribute: "MODULAR",
+            issue_type: "CODE_SMELL",
+            software_quality: "MAINTAINABILITY",
+            impact_severity: "MEDIUM",
+        },
+        // Unlogged tables, DROP NOT NULL, mixed-case/reserved identifiers,
+        // boolean NOT NULL without default, enum-like CHECK lists
+        RuleId::Pgm506 | RuleId::Pgm507 | RuleId::Pgm509 | RuleId::Pgm510 | RuleId::Pgm528 => {
+            SonarQubeRuleMeta {
+                clean_code_attribute: "CONVENTIONAL",
+                issue_type: "CODE_SMELL",
+                software_quality: "MAINTAINABILITY",
+                impact_severity: "LOW",
+            }
+        }
+        // Access-control and replication policy: changes outside their pipeline
+        RuleId::Pgm514 | RuleId::Pgm524 => SonarQubeRuleMeta {
+            clean_code_attribute: "MODULAR",
+            issue_type: "CODE_SMELL",
+            software_quality: "MAINTAINABILITY",
+            impact_severity: "LOW",
+        },
+        // Reminder to check SELECT * and positional INSERT consumers
+        RuleId::Pgm525 => SonarQubeRuleMeta {
+            clean_code_attribute: "COMPLETE",
+            issue_type: "CODE_SMELL",
+            software_quality: "RELIABILITY",
+            impact_severity: "LOW",
+        },
+        // Missing grants: the application is denied access after deploy
+        RuleId::Pgm526 => SonarQubeRuleMeta {
+            clean_code_attribute: "COMPLETE",
+            issue_type: "BUG",
+            software_quality: "RELIABILITY",
+            impact_severity: "MEDIUM",
+        },
+        // Hand-rolled table rewrite: advisory pointer to the view-swap pattern
+        RuleId::Pgm515 => SonarQubeRuleMeta {
             clean_code_attribute: "CONVENTIONAL",
             issue_type: "CODE_SMELL",
             software_quality: "MAINTAINABILITY",
             impact_severity: "LOW",
         },
+        // Legacy inheritance: advisory pointer to declarative partitioning
+        RuleId::Pgm516 => SonarQubeRuleMeta {
+            clean_code_attribute: "CONVENTIONAL",
+            issue_type: "CODE_SMELL",
+            software_quality: "MAINTAINABILITY",
+            impact_severity: "LOW",
+        },
+        // Team policy: configured rollout patterns
+        RuleId::Pgm517 => SonarQubeRuleMeta {
+            clean_code_attribute: "CONVENTIONAL",
+            issue_type: "CODE_SMELL",
+            software_quality: "RELIABILITY",
+            impact_severity: "MEDIUM",
+        },
+        // Session-scoped scratch table outliving its migration
+        RuleId::Pgm518 => SonarQubeRuleMeta {
+            clean_code_attribute: "CLEAR",
+            issue_type: "CODE_SMELL",
+            software_quality: "RELIABILITY",
+            impact_severity: "LOW",
+        },
+        // Team policy: expand/contract rename workflow
+        RuleId::Pgm519 | RuleId::Pgm520 => SonarQubeRuleMeta {
+            clean_code_attribute: "CONVENTIONAL",
+            issue_type: "CODE_SMELL",
+            software_quality: "RELIABILITY",
+            impact_severity: "MEDIUM",
+        },
+        // Sequence default that fails to apply or breaks per-schema restores
+        RuleId::Pgm521 => SonarQubeRuleMeta {
+            clean_code_attribute: "COMPLETE",
+            issue_type: "BUG",
+            software_quality: "RELIABILITY",
+            impact_severity: "HIGH",
+        },
+        // Standalone sequence left behind when its table is dropped
+        RuleId::Pgm523 => SonarQubeRuleMeta {
+            clean_code_attribute: "COMPLETE",
+            issue_type: "CODE_SMELL",
+            software_quality: "MAINTAINABILITY",
+            impact_severity: "LOW",
+        },
+        // Autovacuum disabled: bloat and stale statistics
+        RuleId::Pgm513 => SonarQubeRuleMeta {
+            clean_code_attribute: "COMPLETE",
+            issue_type: "CODE_SMELL",
+            software_quality: "RELIABILITY",
+            impact_severity: "MEDIUM",
+        },
         // Destructive: DROP TABLE, TRUNCATE TABLE
         RuleId::Pgm201 | RuleId::Pgm203 => SonarQubeRuleMeta {
             clean_code_attribute: "COMPLETE",
@@ -134,8 +263,9 @@ fn sonarqube_meta(rule_id: RuleId) -> SonarQubeRuleMeta {
             software_quality: "MAINTAINABILITY",
             impact_severity: "MEDIUM",
         },
-        // Destructive: DROP TABLE CASCADE, TRUNCATE TABLE CASCADE
-        RuleId::Pgm202 | RuleId::Pgm204 => SonarQubeRuleMeta {
+        // Destructive: DROP TABLE CASCADE, TRUNCATE TABLE CASCADE, DROP EXTENSION,
+        // column re-added with another type
+        RuleId::Pgm202 | RuleId::Pgm204 | RuleId::Pgm206 | RuleId::Pgm207 => SonarQubeRuleMeta {
             clean_code_attribute: "COMPLETE",
             issue_type: "BUG",
             software_quality: "RELIABILITY",
@@ -162,7 +292,7 @@ fn sonarqube_meta(rule_id: RuleId) -> SonarQubeRuleMeta {
             software_quality: "MAINTAINABILITY",
             impact_severity: "MEDIUM",
         },
-        // Type anti-pattern rules (PGM101-106, PGM108-109)
+        // Type anti-pattern rules (PGM101-106, PGM108-111)
         RuleId::Pgm101
         | RuleId::Pgm102
         | RuleId::Pgm103
@@ -170,7 +300,9 @@ fn sonarqube_meta(rule_id: RuleId) -> SonarQubeRuleMeta {
         | RuleId::Pgm105
         | RuleId::Pgm106
         | RuleId::Pgm108
-        | RuleId::Pgm109 => SonarQubeRuleMeta {
+        | RuleId::Pgm109
+        | RuleId::Pgm110
+        | RuleId::Pgm111 => SonarQubeRuleMeta {
             clean_code_attribute: "CONVENTIONAL",
             issue_type: "CODE_SMELL",
             software_quality: "MAINTAINABILITY",
@@ -183,8 +315,10 @@ fn sonarqube_meta(rule_id: RuleId) -> SonarQubeRuleMeta {
             software_quality: "MAINTAINABILITY",
             impact_severity: "MEDIUM",
         },
-        // Meta-behavior (PGM901) — should not appear in findings, but handle gracefully
-        RuleId::Pgm901 => SonarQubeRuleMeta {
+        // Meta-behavior: PGM901 never appears in findings; PGM902 is an
+        // informational summary. Rule pack rules carry no SonarQube metadata
+        // of their own.
+        RuleId::Pgm901 | RuleId::Pgm902 | RuleId::Custom(_) => SonarQubeRuleMeta {
             clean_code_attribute: "CONVENTIONAL",
             issue_type: "CODE_SMELL",
             software_quality: "MAINTAINABILITY",
@@ -258,10 +392,22 @@ fn effort_minutes(rule_id: RuleId) -> u32 {
     match rule_id {
         // Combining ALTER TABLE statements: trivial refactor
         RuleId::Pgm023 => 5,
+        // Fixing a misspelled column name or quoting
+        RuleId::Pgm033 => 5,
+        // Adding an IF [NOT] EXISTS guard or reconciling the history
+        RuleId::Pgm034 | RuleId::Pgm036 => 10,
+        // Splitting constraints into separate migrations
+        RuleId::Pgm030 => 10,
+        // Choosing a unique index for the replica identity
+        RuleId::Pgm035 => 15,
         // Concurrently fixes are usually quick
         RuleId::Pgm001 | RuleId::Pgm002 | RuleId::Pgm003 | RuleId::Pgm004 => 5,
         // Index/constraint additions
-        RuleId::Pgm016 | RuleId::Pgm017 | RuleId::Pgm501 => 15,
+        RuleId::Pgm016 | RuleId::Pgm017 | RuleId::Pgm501 | RuleId::Pgm522 => 15,
+        // Reordering migrations
+        RuleId::Pgm027 => 15,
+        // Moving a cross-table rule to a FK or trigger
+        RuleId::Pgm028 => 30,
         // Table rewrites / schema changes need more thought
         RuleId::Pgm005
         | RuleId::Pgm006
@@ -273,19 +419,43 @@ fn effort_minutes(rule_id: RuleId) -> u32 {
         | RuleId::Pgm018
         | RuleId::Pgm019
         | RuleId::Pgm021
-        | RuleId::Pgm022 => 30,
+        | RuleId::Pgm022
+        | RuleId::Pgm024
+        | RuleId::Pgm029
+        | RuleId::Pgm031
+        | RuleId::Pgm032 => 30,
         // Schema quality / side-effect warnings
-        RuleId::Pgm009 | RuleId::Pgm010 | RuleId::Pgm011 | RuleId::Pgm012 | RuleId::Pgm020 => 10,
+        RuleId::Pgm009
+        | RuleId::Pgm010
+        | RuleId::Pgm011
+        | RuleId::Pgm012
+        | RuleId::Pgm020
+        | RuleId::Pgm025
+        | RuleId::Pgm026 => 10,
         RuleId::Pgm502
         | RuleId::Pgm503
         | RuleId::Pgm504
         | RuleId::Pgm505
+        | RuleId::Pgm512
         | RuleId::Pgm506
         | RuleId::Pgm507
-        | RuleId::Pgm509 => 10,
+        | RuleId::Pgm509
+        | RuleId::Pgm510 => 10,
         RuleId::Pgm508 => 5,
+        RuleId::Pgm511 => 15,
+        RuleId::Pgm513 => 5,
+        RuleId::Pgm514 | RuleId::Pgm524 => 5,
+        RuleId::Pgm515 => 30,
+        // Moving a value list into an enum type or lookup table
+        RuleId::Pgm528 => 30,
+        RuleId::Pgm516 => 60,
+        RuleId::Pgm517 => 30,
+        RuleId::Pgm518 | RuleId::Pgm523 => 5,
+        RuleId::Pgm519 | RuleId::Pgm520 => 60,
+        RuleId::Pgm521 => 10,
+        RuleId::Pgm525 | RuleId::Pgm526 | RuleId::Pgm527 => 5,
         RuleId::Pgm201 | RuleId::Pgm203 => 10,
-        RuleId::Pgm202 | RuleId::Pgm204 => 15,
+        RuleId::Pgm202 | RuleId::Pgm204 | RuleId::Pgm206 | RuleId::Pgm207 => 15,
         RuleId::Pgm205 => 30,
         RuleId::Pgm301 | RuleId::Pgm302 | RuleId::Pgm303 => 10,
         RuleId::Pgm401 | RuleId::Pgm402 | RuleId::Pgm403 => 10,
@@ -298,9 +468,11 @@ fn effort_minutes(rule_id: RuleId) -> u32 {
         | RuleId::Pgm106
         | RuleId::Pgm107
         | RuleId::Pgm108
-        | RuleId::Pgm109 => 10,
-        // Meta-behavior
-        RuleId::Pgm901 => 10,
+        | RuleId::Pgm109
+        | RuleId::Pgm110
+        | RuleId::Pgm111 => 10,
+        // Meta-behavior and rule pack rules
+        RuleId::Pgm901 | RuleId::Pgm902 | RuleId::Custom(_) => 10,
     }
 }
 
@@ -313,11 +485,21 @@ impl Reporter for SonarQubeReporter {
         // Collect the set of rule IDs that appear in findings
         let fired_rules: HashSet<RuleId> = findings.iter().map(|f| f.rule_id).collect();
 
+        // Meta rules (the PGM902 summary) are not among the stored rules but
+        // still need a definition for their issues to import.
+        let mut meta_rules: Vec<RuleInfo> = fired_rules
+            .iter()
+            .filter(|id| id.is_meta() && !self.rules.iter().any(|r| r.id == **id))
+            .map(|&id| RuleInfo::of(id))
+            .collect();
+        meta_rules.sort_by_key(|r| r.id);
+
         // Build rules array from stored RuleInfo, filtered to only rules that fired
         let rules: Vec<SonarQubeRule> = self
             .rules
             .iter()
             .filter(|r| fired_rules.contains(&r.id))
+            .chain(&meta_rules)
             .map(|r| {
                 let meta = sonarqube_meta(r.id);
                 SonarQubeRule {
@@ -343,8 +525,11 @@ impl Reporter for SonarQubeReporter {
                 rule_id: f.rule_id.to_string(),
                 effort_minutes: effort_minutes(f.rule_id),
                 primary_location: SonarQubePrimaryLocation {
-                    message: f.message.clone(),
-                    file_path: super::normalize_path(&f.file),
+                    message: match f.unit_label() {
+                        Some(unit) => format!("{} ({unit})", f.message),
+                        None => f.message.clone(),
+                    },
+                    file_path: self.options.path(&f.file),
                     text_range: SonarQubeTextRange {
                         start_line: f.start_line,
                         end_line: f.end_line,
@@ -473,6 +658,27 @@ mod tests {
         insta::assert_json_snapshot!(parsed);
     }
 
+    #[test]
+    fn changeset_appended_to_message() {
+        let mut finding = Finding::new(
+            RuleId::Pgm001,
+            Severity::Critical,
+            "CREATE INDEX should use CONCURRENTLY.".to_string(),
+            Path::new("db/changelog.xml"),
+            &SourceSpan::at(12, 12),
+        );
+        finding.unit = Some(crate::rules::UnitRef {
+            id: "42-add-index".to_string(),
+            author: None,
+        });
+
+        let parsed = emit_and_parse(&[finding]);
+        assert_eq!(
+            parsed["issues"][0]["primaryLocation"]["message"],
+            "CREATE INDEX should use CONCURRENTLY. (changeset 42-add-index)"
+        );
+    }
+
     #[test]
     fn round_trip_sonarqube_all_fields_verified() {
         let findings = vec![
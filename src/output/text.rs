@@ -6,9 +6,13 @@
 //! CRITICAL PGM001 db/migrations/V042__add_order_index.sql:3
 //!   CREATE INDEX on existing table 'orders' should use CONCURRENTLY.
 //! ```
+//!
+//! When printed to a terminal, the severity is colored according to
+//! [`TermCaps`]; `findings.txt` is always plain.
 
-use crate::output::{ReportError, Reporter, TextReporter};
-use crate::rules::Finding;
+use crate::output::{RenderOptions, ReportError, Reporter, TextReporter};
+use crate::rules::{Finding, Severity};
+use crate::term::{Stream, Style, TermCaps};
 use std::fmt::Write as _;
 use std::io::Write;
 use std::path::Path;
@@ -20,25 +24,53 @@ use std::path::Path;
 /// SEVERITY RULE_ID file:line
 ///   message
 /// ```
-fn format_finding(finding: &Finding) -> String {
-    let file_str = super::normalize_path(&finding.file);
+/// followed by the changeset when the finding's unit is not the whole file,
+/// and the acknowledgment when there is one.
+fn format_finding(finding: &Finding, caps: TermCaps, options: &RenderOptions) -> String {
+    let file_str = options.path(&finding.file);
+    let severity = finding.severity.to_string();
     let mut buf = String::new();
     // Using write! on String is infallible, but we handle the result properly.
     let _ = write!(
         buf,
         "{} {} {}:{}\n  {}\n",
-        finding.severity, finding.rule_id, file_str, finding.start_line, finding.message
+        caps.paint(severity_style(finding.severity), &severity),
+        finding.rule_id,
+        file_str,
+        finding.start_line,
+        finding.message
     );
+    if let Some(unit) = finding.unit_label() {
+        buf.push_str(&caps.paint(Style::Dim, &format!("  {unit}")));
+        buf.push('\n');
+    }
+    if let Some(ack) = &finding.acknowledged {
+        let mut line = format!("  acknowledged (was {})", ack.original_severity);
+        if let Some(ticket) = &ack.ticket {
+            let _ = write!(line, ", ticket {ticket}");
+        }
+        buf.push_str(&caps.paint(Style::Dim, &line));
+        buf.push('\n');
+    }
     buf
 }
 
+/// Style of a severity label.
+fn severity_style(severity: Severity) -> Style {
+    match severity {
+        Severity::Blocker | Severity::Critical => Style::Error,
+        Severity::Major => Style::Warning,
+        Severity::Minor | Severity::Info => Style::Note,
+    }
+}
+
 /// Format all findings into a single text string.
 ///
 /// Each finding is separated by a blank line for readability.
-fn format_all(findings: &[Finding]) -> String {
+fn format_all(findings: &[Finding], caps: TermCaps, options: &RenderOptions) -> String {
     let mut output = String::new();
     for (i, finding) in findings.iter().enumerate() {
-        output.push_str(&format_finding(finding));
+        output.push_str(&format_finding(finding, caps, options));
         if i < findings.len() - 1 {
             output.push('\n');
         }
@@ -48,7 +80,7 @@ fn format_all(findings: &[Finding]) -> String {
 
 impl Reporter for TextReporter {
     fn render(&self, findings: &[Finding]) -> Result<String, ReportError> {
-        Ok(format_all(findings))
+        Ok(format_all(findings, TermCaps::PLAIN, &self.options))
     }
 
     fn filename(&self) -> &str {
@@ -57,12 +89,13 @@ impl Reporter for TextReporter {
 
     /// Emit findings as human-readable text.
     ///
-    /// If `use_stdout` is true, writes to stdout. Otherwise writes
+    /// If `use_stdout` is true, writes to stdout, colored when stdout
+    /// supports it. Otherwise writes
     /// `findings.txt` to the given `output_dir`. Creates the directory
     /// if it does not exist.
     fn emit(&self, findings: &[Finding], output_dir: &Path) -> Result<(), ReportError> {
         if self.use_stdout {
-            let text = self.render(findings)?;
+            let text = format_all(findings, TermCaps::detect(Stream::Stdout), &self.options);
             let stdout = std::io::stdout();
             let mut handle = stdout.lock();
             handle.write_all(text.as_bytes())?;
@@ -84,7 +117,7 @@ mod tests {
 
     #[test]
     fn single_finding_correct_format() {
-        let reporter = TextReporter { use_stdout: false };
+        let reporter = TextReporter::new(false);
         let findings = vec![test_finding()];
         let content = reporter.render(&findings).expect("render");
         insta::assert_snapshot!(content);
@@ -92,7 +125,7 @@ mod tests {
 
     #[test]
     fn multiple_findings_separated_by_blank_line() {
-        let reporter = TextReporter { use_stdout: false };
+        let reporter = TextReporter::new(false);
 
         let findings = vec![
             Finding::new(
@@ -117,7 +150,7 @@ mod tests {
 
     #[test]
     fn no_findings_produces_empty_output() {
-        let reporter = TextReporter { use_stdout: false };
+        let reporter = TextReporter::new(false);
         let findings: Vec<Finding> = vec![];
         let content = reporter.render(&findings).expect("render");
         assert!(content.is_empty());
@@ -133,8 +166,74 @@ mod tests {
             &SourceSpan::at(1, 1),
         );
 
-        let formatted = format_finding(&finding);
+        let formatted = format_finding(&finding, TermCaps::PLAIN, &RenderOptions::default());
         assert!(formatted.contains("db/migrations/V042__add_index.sql"));
         assert!(!formatted.contains('\\'));
     }
+
+    #[test]
+    fn format_finding_shows_acknowledgment() {
+        let mut finding = Finding::new(
+            RuleId::Pgm201,
+            Severity::Info,
+            "test".to_string(),
+            Path::new("a.sql"),
+            &SourceSpan::at(3, 3),
+        );
+        finding.acknowledged = Some(crate::rules::Acknowledgment {
+            ticket: Some("DB-123".to_string()),
+            original_severity: Severity::Minor,
+        });
+
+        let formatted = format_finding(&finding, TermCaps::PLAIN, &RenderOptions::default());
+        assert!(formatted.ends_with("  acknowledged (was MINOR), ticket DB-123\n"));
+    }
+
+    #[test]
+    fn format_finding_shows_changeset() {
+        let mut finding = Finding::new(
+            RuleId::Pgm001,
+            Severity::Critical,
+            "test".to_string(),
+            Path::new("db/changelog.xml"),
+            &SourceSpan::at(12, 12),
+        );
+        finding.unit = Some(crate::rules::UnitRef {
+            id: "42-add-index".to_string(),
+            author: Some("alice".to_string()),
+        });
+
+        let formatted = format_finding(&finding, TermCaps::PLAIN, &RenderOptions::default());
+        assert_eq!(
+            formatted,
+            "CRITICAL PGM001 db/changelog.xml:12\n  test\n  changeset 42-add-index by alice\n"
+        );
+
+        finding.file = Path::new("db/42-add-index").to_path_buf();
+        let formatted = format_finding(&finding, TermCaps::PLAIN, &RenderOptions::default());
+        assert!(!formatted.contains("changeset"));
+    }
+
+    #[test]
+    fn format_finding_colors_severity_when_enabled() {
+        let mut finding = Finding::new(
+            RuleId::Pgm001,
+            Severity::Critical,
+            "test".to_string(),
+            Path::new("a.sql"),
+            &SourceSpan::at(3, 3),
+        );
+        finding.acknowledged = Some(crate::rules::Acknowledgment {
+            ticket: None,
+            original_severity: Severity::Major,
+        });
+        let caps = TermCaps {
+            color: true,
+            interactive: true,
+        };
+
+        let formatted = format_finding(&finding, caps, &RenderOptions::default());
+        assert!(formatted.starts_with("\x1b[1;31mCRITICAL\x1b[0m PGM001 a.sql:3\n"));
+        assert!(formatted.ends_with("\x1b[2m  acknowledged (was MAJOR)\x1b[0m\n"));
+    }
 }
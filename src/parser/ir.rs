@@ -5,6 +5,11 @@
 
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::intern::intern;
 
 /// A parsed SQL statement mapped to a high-level operation.
 /// Each variant carries only the fields rules need — not the full AST.
@@ -15,7 +20,53 @@ pub enum IrNode {
     CreateIndex(CreateIndex),
     DropIndex(DropIndex),
     DropTable(DropTable),
+    /// `CREATE SCHEMA name`. `CREATE SCHEMA AUTHORIZATION role` without a
+    /// name is `Ignored`.
+    CreateSchema {
+        name: String,
+        if_not_exists: bool,
+    },
     DropSchema(DropSchema),
+    /// `CREATE EXTENSION`.
+    CreateExtension(CreateExtension),
+    /// `DROP EXTENSION`. One node per extension, like `DropSchema`.
+    DropExtension(DropExtension),
+    /// `CREATE SEQUENCE`.
+    CreateSequence {
+        name: QualifiedName,
+        if_not_exists: bool,
+        /// Column from `OWNED BY table.column`. `None` without the option
+        /// and for `OWNED BY NONE`.
+        owned_by: Option<SequenceOwner>,
+    },
+    /// `ALTER SEQUENCE name OWNED BY table.column | NONE`. `ALTER SEQUENCE`
+    /// without `OWNED BY` is `Ignored`.
+    SetSequenceOwner {
+        name: QualifiedName,
+        /// `None` for `OWNED BY NONE`.
+        owned_by: Option<SequenceOwner>,
+    },
+    /// `DROP SEQUENCE`. One node per sequence, like `DropTable`.
+    DropSequence {
+        name: QualifiedName,
+        if_exists: bool,
+    },
+    /// `ALTER SEQUENCE name RENAME TO new_name`. pg_query emits `RenameStmt`.
+    RenameSequence {
+        name: QualifiedName,
+        new_name: String,
+    },
+    /// `GRANT` / `REVOKE` of privileges or role membership, and
+    /// `ALTER DEFAULT PRIVILEGES`.
+    Grant(Grant),
+    /// `ALTER <object> OWNER TO role` for non-table objects. Tables (and
+    /// relations pg_query routes through `AlterTableStmt`) use
+    /// [`AlterTableAction::ChangeOwner`].
+    AlterOwner(AlterOwner),
+    /// Logical replication DDL: `CREATE`/`ALTER`/`DROP PUBLICATION` and
+    /// `CREATE`/`ALTER`/`DROP SUBSCRIPTION`. One node per publication or
+    /// subscription, like `DropTable`.
+    Replication(ReplicationChange),
     TruncateTable(TruncateTable),
     /// DML: INSERT INTO a table.
     InsertInto(InsertInto),
@@ -35,6 +86,33 @@ pub enum IrNode {
         parent_index_name: String,
         child_index_name: QualifiedName,
     },
+    /// `ALTER INDEX name SET TABLESPACE tablespace` — moves the index to
+    /// another tablespace, rewriting it.
+    AlterIndexSetTablespace {
+        index_name: String,
+        tablespace: String,
+    },
+    /// `ALTER TABLE|INDEX|MATERIALIZED VIEW ALL IN TABLESPACE name
+    /// [OWNED BY role, ...] SET TABLESPACE new_name` — moves every matching
+    /// relation in the tablespace, rewriting each one. pg_query emits
+    /// `AlterTableMoveAllStmt`.
+    MoveAllInTablespace {
+        /// `TABLE`, `INDEX`, or `MATERIALIZED VIEW`.
+        object_kind: String,
+        tablespace: String,
+        new_tablespace: String,
+        /// Roles from `OWNED BY`; empty when every relation is moved.
+        owned_by: Vec<String>,
+    },
+    /// `VACUUM` without a table list — vacuums every table in the database.
+    /// `VACUUM FULL` without a table list is `VacuumFull { table: None }`.
+    VacuumAllTables,
+    /// `ALTER INDEX name RENAME TO new_name`. pg_query emits `RenameStmt`.
+    /// Index names are unqualified, like `DropIndex`.
+    RenameIndex {
+        index_name: String,
+        new_name: String,
+    },
     /// Rename an existing table. pg_query emits `RenameStmt`, not `AlterTableStmt`.
     RenameTable {
         name: QualifiedName,
@@ -46,9 +124,19 @@ pub enum IrNode {
         old_name: String,
         new_name: String,
     },
-    /// SQL that parsed successfully but has no IR mapping (e.g., GRANT, COMMENT ON).
-    /// Not an error — just not relevant to linting.
+    /// `COMMENT ON` a table or one of its columns, constraints, or triggers,
+    /// and `CREATE TRIGGER` / `DROP TRIGGER`: changes attached to a table that
+    /// leave its shape alone. Comments on other objects are `Ignored`.
+    TableAttachment {
+        /// `COMMENT ON`, `CREATE TRIGGER`, or `DROP TRIGGER`.
+        statement: String,
+        table: QualifiedName,
+    },
+    /// SQL that parsed successfully but has no IR mapping (e.g., CREATE VIEW).
+    /// Not an error — just not relevant to linting. `kind` says what the
+    /// statement was, so callers need not re-parse `raw_sql`.
     Ignored {
+        kind: StatementKind,
         raw_sql: String,
     },
     /// SQL that failed to parse or is inherently opaque (DO $$ blocks, dynamic SQL).
@@ -59,8 +147,39 @@ pub enum IrNode {
     },
 }
 
+impl IrNode {
+    /// The table the statement creates, changes, or reads, for statements
+    /// that target exactly one table.
+    pub fn target_table(&self) -> Option<&QualifiedName> {
+        match self {
+            IrNode::CreateTable(ct) => Some(&ct.name),
+            IrNode::AlterTable(at) => Some(&at.name),
+            IrNode::CreateIndex(ci) => Some(&ci.table_name),
+            IrNode::DropTable(dt) => Some(&dt.name),
+            IrNode::TruncateTable(tt) => Some(&tt.name),
+            IrNode::InsertInto(ii) => Some(&ii.table_name),
+            IrNode::UpdateTable(ut) => Some(&ut.table_name),
+            IrNode::DeleteFrom(df) => Some(&df.table_name),
+            IrNode::Cluster(c) => Some(&c.table),
+            IrNode::VacuumFull(v) => v.table.as_ref(),
+            IrNode::RenameTable { name, .. } => Some(name),
+            IrNode::RenameColumn { table, .. } => Some(table),
+            IrNode::TableAttachment { table, .. } => Some(table),
+            _ => None,
+        }
+    }
+}
+
+/// The column a sequence is owned by (`OWNED BY table.column`). PostgreSQL
+/// drops an owned sequence together with its column or table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequenceOwner {
+    pub table: QualifiedName,
+    pub column: String,
+}
+
 /// Partition strategy for `CREATE TABLE ... PARTITION BY`.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PartitionStrategy {
     Range,
     List,
@@ -74,6 +193,79 @@ pub struct PartitionBy {
     pub columns: Vec<String>,
 }
 
+/// Classification of a statement the IR does not model (`IrNode::Ignored`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum StatementKind {
+    /// `COMMENT ON` an object other than a table, column, table constraint,
+    /// or trigger.
+    Comment,
+    /// `CREATE FUNCTION` / `CREATE PROCEDURE`.
+    CreateFunction,
+    /// `CREATE VIEW`.
+    CreateView,
+    /// `CREATE TABLE ... AS`, `SELECT ... INTO`, `CREATE MATERIALIZED VIEW`.
+    CreateTableAs,
+    /// `REFRESH MATERIALIZED VIEW`.
+    RefreshMaterializedView,
+    /// `ALTER SEQUENCE` other than `RENAME`.
+    AlterSequence,
+    /// `CREATE TYPE` (enum, composite, base) and `CREATE DOMAIN`.
+    CreateType,
+    /// `ALTER TYPE ... ADD/RENAME VALUE` and `ALTER DOMAIN`.
+    AlterType,
+    /// `CREATE SCHEMA`.
+    CreateSchema,
+    /// `SET` / `RESET` of a session setting.
+    Set,
+    /// `BEGIN`, `COMMIT`, `ROLLBACK`, `SAVEPOINT`.
+    Transaction,
+    /// `SELECT` (other than `SELECT ... INTO`).
+    Select,
+    /// `LOCK TABLE`.
+    Lock,
+    /// Plain `VACUUM` or `ANALYZE` (not `VACUUM FULL`).
+    Vacuum,
+    /// `ALTER TABLE` with no subcommand the IR models.
+    AlterTable,
+    /// `ALTER INDEX` other than `ATTACH PARTITION` / `SET TABLESPACE`.
+    AlterIndex,
+    /// `ALTER ... RENAME` of an object other than a table, column, index, or
+    /// sequence.
+    Rename,
+    /// `DROP` of an object other than a table, index, schema, extension, or
+    /// sequence.
+    Drop,
+    /// Anything else.
+    Unknown,
+}
+
+impl StatementKind {
+    /// Statement keyword(s) for messages and summaries, e.g. `"COMMENT ON"`.
+    pub fn label(self) -> &'static str {
+        match self {
+            StatementKind::Comment => "COMMENT ON",
+            StatementKind::CreateFunction => "CREATE FUNCTION",
+            StatementKind::CreateView => "CREATE VIEW",
+            StatementKind::CreateTableAs => "CREATE TABLE AS",
+            StatementKind::RefreshMaterializedView => "REFRESH MATERIALIZED VIEW",
+            StatementKind::AlterSequence => "ALTER SEQUENCE",
+            StatementKind::CreateType => "CREATE TYPE",
+            StatementKind::AlterType => "ALTER TYPE",
+            StatementKind::CreateSchema => "CREATE SCHEMA",
+            StatementKind::Set => "SET",
+            StatementKind::Transaction => "transaction control",
+            StatementKind::Select => "SELECT",
+            StatementKind::Lock => "LOCK",
+            StatementKind::Vacuum => "VACUUM",
+            StatementKind::AlterTable => "ALTER TABLE",
+            StatementKind::AlterIndex => "ALTER INDEX",
+            StatementKind::Rename => "RENAME",
+            StatementKind::Drop => "DROP",
+            StatementKind::Unknown => "other",
+        }
+    }
+}
+
 /// Table persistence mode, mapping 1:1 to PostgreSQL's `relpersistence`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TablePersistence {
@@ -109,6 +301,36 @@ pub struct CreateTable {
     pub partition_by: Option<PartitionBy>,
     /// Parent table for `CREATE TABLE child PARTITION OF parent ...`.
     pub partition_of: Option<QualifiedName>,
+    /// Parent tables from `INHERITS (parent, ...)` — legacy table
+    /// inheritance, not declarative partitioning.
+    pub inherits: Vec<QualifiedName>,
+    /// Storage parameters from `WITH (...)`.
+    pub storage_params: Vec<StorageParam>,
+    /// `ON COMMIT` action. Only temporary tables accept the clause.
+    pub on_commit: OnCommit,
+}
+
+/// `ON COMMIT` action of a temporary table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnCommit {
+    /// No clause, or `ON COMMIT PRESERVE ROWS`: the table lives until the
+    /// end of the session.
+    PreserveRows,
+    /// `ON COMMIT DELETE ROWS`: emptied at every commit, kept for the session.
+    DeleteRows,
+    /// `ON COMMIT DROP`: dropped when the transaction commits.
+    Drop,
+}
+
+/// A table storage parameter from `WITH (...)` or `ALTER TABLE ... SET (...)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StorageParam {
+    /// Parameter name, prefixed by its namespace when present:
+    /// `"fillfactor"`, `"toast.autovacuum_enabled"`.
+    pub name: String,
+    /// Value as written (`"false"`, `"70"`). `None` for a bare name, which
+    /// PostgreSQL treats as `true` for boolean parameters.
+    pub value: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -151,6 +373,8 @@ pub enum AlterTableAction {
     /// DROP CONSTRAINT by name.
     DropConstraint {
         constraint_name: String,
+        /// `DROP CONSTRAINT IF EXISTS`.
+        if_exists: bool,
     },
     /// VALIDATE CONSTRAINT by name — clears the `not_valid` flag on FK/CHECK.
     ValidateConstraint {
@@ -169,12 +393,56 @@ pub enum AlterTableAction {
     DisableTrigger {
         scope: TriggerDisableScope,
     },
+    /// `ALTER TABLE ... SET (param = value, ...)`
+    SetStorageParams {
+        params: Vec<StorageParam>,
+    },
+    /// `ALTER TABLE ... RESET (param, ...)`
+    ResetStorageParams {
+        names: Vec<String>,
+    },
+    /// `ALTER TABLE ... SET LOGGED` — rewrites the table into the WAL.
+    SetLogged,
+    /// `ALTER TABLE ... SET UNLOGGED` — the table stops being WAL-logged.
+    SetUnlogged,
+    /// `ALTER TABLE ... OWNER TO role`
+    ChangeOwner {
+        new_owner: String,
+    },
+    /// `ALTER TABLE ... REPLICA IDENTITY { DEFAULT | FULL | USING INDEX | NOTHING }`
+    SetReplicaIdentity(ReplicaIdentity),
     /// Catch-all for ALTER TABLE actions we parse but don't model.
     Other {
         description: String,
     },
 }
 
+/// The old-row values logical decoding records for UPDATE and DELETE
+/// (`REPLICA IDENTITY`).
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ReplicaIdentity {
+    /// The primary key columns, if the table has one.
+    #[default]
+    Default,
+    /// Every column of the old row.
+    Full,
+    /// The columns of the named unique index.
+    UsingIndex(String),
+    /// No old-row values.
+    Nothing,
+}
+
+impl std::fmt::Display for ReplicaIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplicaIdentity::Default => f.write_str("DEFAULT"),
+            ReplicaIdentity::Full => f.write_str("FULL"),
+            ReplicaIdentity::UsingIndex(index) => write!(f, "USING INDEX {index}"),
+            ReplicaIdentity::Nothing => f.write_str("NOTHING"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct CreateIndex {
     pub index_name: Option<String>,
@@ -213,10 +481,72 @@ pub struct DropSchema {
     pub cascade: bool,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateExtension {
+    /// Extension name as written, e.g. `"uuid-ossp"`, `"postgis"`.
+    pub name: String,
+    pub if_not_exists: bool,
+    /// Target schema from `SCHEMA name`, if given.
+    pub schema: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DropExtension {
+    pub name: String,
+    pub if_exists: bool,
+    pub cascade: bool,
+}
+
+/// A permission change. Only what rules need to describe it; privileges and
+/// grant options are not modeled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grant {
+    /// `false` for `REVOKE`.
+    pub is_grant: bool,
+    /// Object kind in SQL spelling: `"TABLE"`, `"SCHEMA"`, `"FUNCTION"`, ...;
+    /// `"ROLE"` for membership (`GRANT role TO user`) and
+    /// `"DEFAULT PRIVILEGES"` for `ALTER DEFAULT PRIVILEGES`.
+    pub object_kind: String,
+    /// Target names as written. Schema names for `ALL TABLES IN SCHEMA`,
+    /// role names for membership grants; empty for default privileges.
+    pub objects: Vec<String>,
+    /// `ON ALL TABLES|SEQUENCES|... IN SCHEMA`: `objects` are schemas, and
+    /// the grant covers the objects that exist when it runs.
+    pub all_in_schema: bool,
+    /// Grantee role names; `PUBLIC` for the public pseudo-role.
+    pub grantees: Vec<String>,
+}
+
+/// `ALTER <object> OWNER TO role`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlterOwner {
+    /// Object kind in SQL spelling, like [`Grant::object_kind`].
+    pub object_kind: String,
+    /// Object name as written.
+    pub name: String,
+    pub new_owner: String,
+}
+
+/// A publication or subscription statement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplicationChange {
+    /// Statement keywords, e.g. `"ALTER PUBLICATION"`.
+    pub statement: String,
+    /// Publication or subscription name.
+    pub name: String,
+    /// Tables named by a publication's `FOR TABLE` or `ADD`/`SET`/`DROP
+    /// TABLE`. Empty for subscriptions and `FOR ALL TABLES`.
+    pub tables: Vec<QualifiedName>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TruncateTable {
     pub name: QualifiedName,
     pub cascade: bool,
+    /// `RESTART IDENTITY`: sequences owned by the table's columns are reset.
+    pub restart_identity: bool,
+    /// `TRUNCATE ONLY`: descendant tables are not truncated.
+    pub only: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -227,6 +557,8 @@ pub struct InsertInto {
 #[derive(Debug, Clone, PartialEq)]
 pub struct UpdateTable {
     pub table_name: QualifiedName,
+    /// Columns assigned in the SET clause, in source order.
+    pub set_columns: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -287,20 +619,73 @@ pub struct Reindex {
 
 // --- Supporting types ---
 
+/// How an identifier was written in the migration source.
+///
+/// PostgreSQL folds unquoted identifiers to lowercase and keeps quoted ones
+/// as written, so the parsed name alone cannot tell `"users"` from `users`
+/// or `UserAccounts` from `useraccounts`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Spelling {
+    /// True if the identifier was double-quoted.
+    pub quoted: bool,
+    /// The identifier as written, without quotes. For an unquoted identifier
+    /// this keeps the case PostgreSQL folded away.
+    pub text: String,
+}
+
+impl Spelling {
+    /// Spelling of a parsed name whose source is not known: quoted exactly
+    /// when the name cannot be written without quotes.
+    pub fn inferred(name: &str) -> Self {
+        Self {
+            quoted: requires_quoting(name),
+            text: name.to_string(),
+        }
+    }
+
+    /// SQL text that reproduces the identifier as written.
+    pub fn to_sql(&self) -> String {
+        if self.quoted {
+            format!("\"{}\"", self.text.replace('"', "\"\""))
+        } else {
+            self.text.clone()
+        }
+    }
+}
+
+/// True if a parsed (case-folded) name can only be written double-quoted:
+/// it is empty, contains uppercase or characters not allowed in a bare
+/// identifier, starts with a digit or `$`, or is a reserved word.
+pub fn requires_quoting(name: &str) -> bool {
+    let mut chars = name.chars();
+    let Some(first) = chars.next() else {
+        return true;
+    };
+    let bare_start = |c: char| c.is_ascii_lowercase() || c == '_' || !c.is_ascii();
+    !bare_start(first)
+        || !chars.all(|c| bare_start(c) || c.is_ascii_digit() || c == '$')
+        || crate::rules::reserved_keywords::is_reserved(name)
+}
+
 /// Schema-qualified name. `schema` is None for unqualified references.
 ///
 /// `PartialEq`, `Eq`, and `Hash` are implemented manually on `schema` + `name`
-/// only, excluding the pre-computed `catalog_key` cache and `schema_is_default`.
+/// only, excluding the pre-computed `catalog_key` cache, `schema_is_default`,
+/// and the source spelling.
 #[derive(Debug, Clone)]
 pub struct QualifiedName {
     pub schema: Option<String>,
     pub name: String,
     /// Pre-computed lookup key: `"schema.name"` when qualified, `"name"` when not.
-    /// Updated by constructors and `set_default_schema()`.
-    catalog_key: String,
+    /// Updated by constructors and `set_default_schema()`. Interned, so the
+    /// many references to the same table share one allocation.
+    catalog_key: Arc<str>,
     /// True when the schema was assigned by normalization, not by the user.
     /// Used to suppress the schema prefix in user-facing messages.
     schema_is_default: bool,
+    /// How the schema and name were written, when the parser saw the source.
+    schema_spelling: Option<Spelling>,
+    name_spelling: Option<Spelling>,
 }
 
 impl PartialEq for QualifiedName {
@@ -321,24 +706,28 @@ impl Hash for QualifiedName {
 impl QualifiedName {
     pub fn unqualified(name: impl Into<String>) -> Self {
         let name = name.into();
-        let catalog_key = name.clone();
+        let catalog_key = intern(&name);
         Self {
             schema: None,
             name,
             catalog_key,
             schema_is_default: false,
+            schema_spelling: None,
+            name_spelling: None,
         }
     }
 
     pub fn qualified(schema: impl Into<String>, name: impl Into<String>) -> Self {
         let schema = schema.into();
         let name = name.into();
-        let catalog_key = format!("{}.{}", schema, name);
+        let catalog_key = intern(&format!("{}.{}", schema, name));
         Self {
             schema: Some(schema),
             name,
             catalog_key,
             schema_is_default: false,
+            schema_spelling: None,
+            name_spelling: None,
         }
     }
 
@@ -357,11 +746,56 @@ impl QualifiedName {
     pub fn set_default_schema(&mut self, default: &str) {
         if self.schema.is_none() {
             self.schema = Some(default.to_string());
-            self.catalog_key = format!("{}.{}", default, self.name);
+            self.catalog_key = intern(&format!("{}.{}", default, self.name));
             self.schema_is_default = true;
         }
     }
 
+    /// True when the schema was assigned by normalization rather than written
+    /// in the migration. Always false for unqualified names.
+    pub fn schema_is_default(&self) -> bool {
+        self.schema_is_default
+    }
+
+    /// Record how the schema and name were written in the source.
+    pub fn set_spelling(&mut self, schema: Option<Spelling>, name: Spelling) {
+        self.schema_spelling = schema;
+        self.name_spelling = Some(name);
+    }
+
+    /// How the name was written, or the inferred spelling if the source was
+    /// not seen (Liquibase XML, catalog builders).
+    pub fn name_spelling(&self) -> Spelling {
+        self.name_spelling
+            .clone()
+            .unwrap_or_else(|| Spelling::inferred(&self.name))
+    }
+
+    /// How the schema was written. `None` when there is no schema or it was
+    /// assigned by normalization.
+    pub fn schema_spelling(&self) -> Option<Spelling> {
+        if self.schema_is_default {
+            return None;
+        }
+        let schema = self.schema.as_deref()?;
+        Some(
+            self.schema_spelling
+                .clone()
+                .unwrap_or_else(|| Spelling::inferred(schema)),
+        )
+    }
+
+    /// The name as SQL, spelled and quoted the way the migration wrote it.
+    /// Like [`display_name`](Self::display_name), a schema assigned by
+    /// normalization is left out.
+    pub fn to_sql(&self) -> String {
+        let name = self.name_spelling().to_sql();
+        match self.schema_spelling() {
+            Some(schema) => format!("{}.{name}", schema.to_sql()),
+            None => name,
+        }
+    }
+
     /// Returns the user-facing name: just `name` if the schema was synthesized
     /// by normalization, or `schema.name` if the user wrote it explicitly.
     pub fn display_name(&self) -> String {
@@ -395,29 +829,166 @@ pub struct ColumnDef {
     pub is_inline_pk: bool,
     /// True if this column was declared as `serial`, `bigserial`, or `smallserial`.
     pub is_serial: bool,
+    /// True if this column was declared `GENERATED { ALWAYS | BY DEFAULT } AS IDENTITY`.
+    pub is_identity: bool,
+    /// True for `ADD COLUMN IF NOT EXISTS`. Always false in `CREATE TABLE`.
+    pub if_not_exists: bool,
+    /// How the name was written, when the parser saw the source.
+    pub spelling: Option<Spelling>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl ColumnDef {
+    /// How the name was written, or the inferred spelling if unknown.
+    pub fn name_spelling(&self) -> Spelling {
+        self.spelling
+            .clone()
+            .unwrap_or_else(|| Spelling::inferred(&self.name))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TypeName {
     /// The base type name, lowercased: "integer", "varchar", "numeric", etc.
-    pub name: String,
+    /// For an array type this is the element type: `text[]` has name "text".
+    /// Interned: every column of the same type shares one allocation.
+    #[serde(with = "crate::intern::serde_interned")]
+    pub name: Arc<str>,
     /// Type modifiers. For varchar(100): modifiers = [100].
     /// For numeric(10,2): modifiers = [10, 2].
     pub modifiers: Vec<i64>,
+    /// Number of array dimensions: 0 for `text`, 1 for `text[]` or
+    /// `text ARRAY`, 2 for `int[][]`. PostgreSQL ignores declared bounds
+    /// (`int[3]`), so only the count is kept.
+    pub array_dims: usize,
 }
 
+/// Broad category of a column type, derived from its base name.
+///
+/// The catalog does not track `CREATE TYPE`, so composite, enum, and domain
+/// types, and types from extensions (`hstore`, `citext`), all land in
+/// [`TypeKind::UserDefined`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeKind {
+    /// A built-in scalar type: numbers, text, time, json, uuid, network, etc.
+    Base,
+    /// A built-in range or multirange type (`tstzrange`, `int8multirange`).
+    Range,
+    /// Any type not built into PostgreSQL.
+    UserDefined,
+}
+
+/// Built-in range and multirange types.
+const RANGE_TYPES: &[&str] = &[
+    "int4range",
+    "int8range",
+    "numrange",
+    "tsrange",
+    "tstzrange",
+    "daterange",
+    "int4multirange",
+    "int8multirange",
+    "nummultirange",
+    "tsmultirange",
+    "tstzmultirange",
+    "datemultirange",
+];
+
+/// Built-in scalar types, by the canonical names pg_query produces plus the
+/// SQL spellings used in tests and hand-built IR.
+const BASE_TYPES: &[&str] = &[
+    "int2",
+    "int4",
+    "int8",
+    "smallint",
+    "integer",
+    "int",
+    "bigint",
+    "float4",
+    "float8",
+    "real",
+    "numeric",
+    "decimal",
+    "money",
+    "bool",
+    "boolean",
+    "text",
+    "varchar",
+    "bpchar",
+    "char",
+    "name",
+    "bytea",
+    "date",
+    "time",
+    "timetz",
+    "timestamp",
+    "timestamptz",
+    "interval",
+    "uuid",
+    "json",
+    "jsonb",
+    "jsonpath",
+    "xml",
+    "inet",
+    "cidr",
+    "macaddr",
+    "macaddr8",
+    "bit",
+    "varbit",
+    "point",
+    "line",
+    "lseg",
+    "box",
+    "path",
+    "polygon",
+    "circle",
+    "tsvector",
+    "tsquery",
+    "oid",
+    "regclass",
+    "regtype",
+    "regproc",
+    "pg_lsn",
+    "pg_snapshot",
+    "txid_snapshot",
+    "unknown",
+];
+
 impl TypeName {
     pub fn simple(name: impl Into<String>) -> Self {
         Self {
-            name: name.into().to_lowercase(),
+            name: intern(&name.into().to_lowercase()),
             modifiers: vec![],
+            array_dims: 0,
         }
     }
 
     pub fn with_modifiers(name: impl Into<String>, modifiers: Vec<i64>) -> Self {
         Self {
-            name: name.into().to_lowercase(),
+            name: intern(&name.into().to_lowercase()),
             modifiers,
+            array_dims: 0,
+        }
+    }
+
+    /// Make this an array type with `dims` dimensions.
+    pub fn with_array_dims(mut self, dims: usize) -> Self {
+        self.array_dims = dims;
+        self
+    }
+
+    /// True for array types (`text[]`).
+    pub fn is_array(&self) -> bool {
+        self.array_dims > 0
+    }
+
+    /// The category of the base (element) type.
+    pub fn kind(&self) -> TypeKind {
+        if RANGE_TYPES.contains(&&*self.name) {
+            TypeKind::Range
+        } else if BASE_TYPES.contains(&&*self.name) {
+            TypeKind::Base
+        } else {
+            TypeKind::UserDefined
         }
     }
 }
@@ -429,16 +1000,23 @@ impl fmt::Display for TypeName {
             let mods: Vec<String> = self.modifiers.iter().map(|m| m.to_string()).collect();
             write!(f, "({})", mods.join(","))?;
         }
+        for _ in 0..self.array_dims {
+            write!(f, "[]")?;
+        }
         Ok(())
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DefaultExpr {
     /// A constant literal: 0, 'active', TRUE, etc.
     Literal(String),
     /// A function call: now(), gen_random_uuid(), my_func(), etc.
     FunctionCall { name: String, args: Vec<String> },
+    /// A bare column reference, e.g. a misspelled keyword or a value written
+    /// in double quotes (`DEFAULT "active"`). Holds the column name.
+    /// PostgreSQL rejects column references in defaults (PGM033).
+    ColumnRef(String),
     /// An expression we parsed but can't categorize. Treated as opaque.
     Other(String),
 }
@@ -470,6 +1048,8 @@ pub enum TableConstraint {
         name: Option<String>,
         expression: String,
         not_valid: bool,
+        /// The columns, subqueries, and functions the expression uses.
+        deps: CheckExprDeps,
     },
     /// EXCLUDE constraint. Only captures the constraint name — the element list
     /// (columns and operators, e.g. `room WITH =, period WITH &&`) is not tracked.
@@ -478,11 +1058,49 @@ pub enum TableConstraint {
     Exclude { name: Option<String> },
 }
 
+/// Column references, subqueries, function calls, and SQL value functions
+/// found in a CHECK expression. Filled in by the parser for PGM028 and PGM033.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CheckExprDeps {
+    /// Referenced column names (the last part of each column reference),
+    /// sorted and deduplicated. Columns inside a subquery are not included.
+    pub columns: Vec<String>,
+    /// The expression contains a subquery (`EXISTS (...)`, `x IN (SELECT ...)`).
+    pub has_subquery: bool,
+    /// Called functions, unqualified and lowercased.
+    pub functions: Vec<String>,
+    /// SQL value functions such as `CURRENT_USER` or `CURRENT_DATE`.
+    pub session_values: Vec<String>,
+    /// `column IN (...)` and `column = ANY (ARRAY[...])` tests whose right
+    /// side holds only literals (PGM528).
+    pub literal_lists: Vec<LiteralList>,
+}
+
+/// A column tested against a fixed list of literals in a CHECK expression,
+/// e.g. `status IN ('draft', 'sent', 'paid')`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LiteralList {
+    /// The tested column (the last part of the column reference).
+    pub column: String,
+    /// The literal values in source order, without quotes or casts.
+    pub values: Vec<String>,
+}
+
+impl LiteralList {
+    /// The values sorted and deduplicated, for comparing two lists as sets.
+    pub fn value_set(&self) -> Vec<&str> {
+        let mut values: Vec<&str> = self.values.iter().map(String::as_str).collect();
+        values.sort_unstable();
+        values.dedup();
+        values
+    }
+}
+
 /// An element in an index's column list.
 ///
 /// Most indexes reference plain column names, but expression indexes
 /// (e.g. `CREATE INDEX idx ON t (LOWER(email))`) store the deparsed SQL text.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum IndexColumn {
     /// Simple column reference by name.
     Column(String),
@@ -520,6 +1138,10 @@ impl IndexColumn {
 pub struct Located<T> {
     pub node: T,
     pub span: SourceSpan,
+    /// Statement text from its first keyword to its end, as written in the
+    /// source. Shared by every node a single statement expands into. Empty
+    /// for nodes that were not parsed from SQL text.
+    pub raw_sql: Arc<str>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -546,7 +1168,7 @@ impl SourceSpan {
 
 #[cfg(test)]
 impl ColumnDef {
-    /// Minimal column: nullable, no default, not a PK, not serial.
+    /// Minimal column: nullable, no default, not a PK, not serial or identity.
     pub fn test(name: impl Into<String>, type_name: impl Into<String>) -> Self {
         Self {
             name: name.into(),
@@ -555,6 +1177,9 @@ impl ColumnDef {
             default_expr: None,
             is_inline_pk: false,
             is_serial: false,
+            is_identity: false,
+            if_not_exists: false,
+            spelling: None,
         }
     }
 
@@ -583,6 +1208,11 @@ impl ColumnDef {
         self
     }
 
+    pub fn with_identity(mut self) -> Self {
+        self.is_identity = true;
+        self
+    }
+
     pub fn with_type(mut self, type_name: TypeName) -> Self {
         self.type_name = type_name;
         self
@@ -601,6 +1231,9 @@ impl CreateTable {
             if_not_exists: false,
             partition_by: None,
             partition_of: None,
+            inherits: vec![],
+            storage_params: vec![],
+            on_commit: OnCommit::PreserveRows,
         }
     }
 
@@ -633,6 +1266,31 @@ impl CreateTable {
         self.partition_of = Some(parent);
         self
     }
+
+    pub fn with_inherits(mut self, parents: Vec<QualifiedName>) -> Self {
+        self.inherits = parents;
+        self
+    }
+
+    pub fn with_storage_params(mut self, storage_params: Vec<StorageParam>) -> Self {
+        self.storage_params = storage_params;
+        self
+    }
+
+    pub fn with_on_commit(mut self, on_commit: OnCommit) -> Self {
+        self.on_commit = on_commit;
+        self
+    }
+}
+
+#[cfg(test)]
+impl StorageParam {
+    pub fn test(name: &str, value: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            value: Some(value.to_string()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -754,13 +1412,100 @@ impl DropSchema {
     }
 }
 
+#[cfg(test)]
+impl CreateExtension {
+    /// Minimal CREATE EXTENSION: if_not_exists false, no schema.
+    pub fn test(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            if_not_exists: false,
+            schema: None,
+        }
+    }
+
+    pub fn with_if_not_exists(mut self, if_not_exists: bool) -> Self {
+        self.if_not_exists = if_not_exists;
+        self
+    }
+}
+
+#[cfg(test)]
+impl DropExtension {
+    /// Minimal DROP EXTENSION: if_exists false, cascade false.
+    pub fn test(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            if_exists: false,
+            cascade: false,
+        }
+    }
+
+    pub fn with_if_exists(mut self, if_exists: bool) -> Self {
+        self.if_exists = if_exists;
+        self
+    }
+
+    pub fn with_cascade(mut self, cascade: bool) -> Self {
+        self.cascade = cascade;
+        self
+    }
+}
+
+#[cfg(test)]
+impl Grant {
+    /// Minimal `GRANT ... ON TABLE objects TO grantees`.
+    pub fn test(objects: &[&str], grantees: &[&str]) -> Self {
+        Self {
+            is_grant: true,
+            object_kind: "TABLE".to_string(),
+            objects: objects.iter().map(|s| s.to_string()).collect(),
+            all_in_schema: false,
+            grantees: grantees.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    pub fn with_all_in_schema(mut self, all_in_schema: bool) -> Self {
+        self.all_in_schema = all_in_schema;
+        self
+    }
+
+    pub fn with_is_grant(mut self, is_grant: bool) -> Self {
+        self.is_grant = is_grant;
+        self
+    }
+
+    pub fn with_object_kind(mut self, object_kind: &str) -> Self {
+        self.object_kind = object_kind.to_string();
+        self
+    }
+}
+
+#[cfg(test)]
+impl ReplicationChange {
+    /// `statement name` with no tables, e.g. `("ALTER SUBSCRIPTION", "sub")`.
+    pub fn test(statement: &str, name: &str) -> Self {
+        Self {
+            statement: statement.to_string(),
+            name: name.to_string(),
+            tables: vec![],
+        }
+    }
+
+    pub fn with_tables(mut self, tables: Vec<QualifiedName>) -> Self {
+        self.tables = tables;
+        self
+    }
+}
+
 #[cfg(test)]
 impl TruncateTable {
-    /// Minimal TRUNCATE TABLE: cascade false.
+    /// Minimal TRUNCATE TABLE: cascade, restart_identity, and only false.
     pub fn test(name: QualifiedName) -> Self {
         Self {
             name,
             cascade: false,
+            restart_identity: false,
+            only: false,
         }
     }
 
@@ -768,6 +1513,16 @@ impl TruncateTable {
         self.cascade = cascade;
         self
     }
+
+    pub fn with_restart_identity(mut self, restart_identity: bool) -> Self {
+        self.restart_identity = restart_identity;
+        self
+    }
+
+    pub fn with_only(mut self, only: bool) -> Self {
+        self.only = only;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -782,7 +1537,15 @@ impl InsertInto {
 impl UpdateTable {
     /// Minimal UPDATE.
     pub fn test(table_name: QualifiedName) -> Self {
-        Self { table_name }
+        Self {
+            table_name,
+            set_columns: vec![],
+        }
+    }
+
+    pub fn with_set_columns(mut self, columns: &[&str]) -> Self {
+        self.set_columns = columns.iter().map(|c| c.to_string()).collect();
+        self
     }
 }
 
@@ -908,6 +1671,41 @@ impl From<DropSchema> for IrNode {
     }
 }
 
+#[cfg(test)]
+impl From<CreateExtension> for IrNode {
+    fn from(value: CreateExtension) -> Self {
+        IrNode::CreateExtension(value)
+    }
+}
+
+#[cfg(test)]
+impl From<DropExtension> for IrNode {
+    fn from(value: DropExtension) -> Self {
+        IrNode::DropExtension(value)
+    }
+}
+
+#[cfg(test)]
+impl From<Grant> for IrNode {
+    fn from(value: Grant) -> Self {
+        IrNode::Grant(value)
+    }
+}
+
+#[cfg(test)]
+impl From<AlterOwner> for IrNode {
+    fn from(value: AlterOwner) -> Self {
+        IrNode::AlterOwner(value)
+    }
+}
+
+#[cfg(test)]
+impl From<ReplicationChange> for IrNode {
+    fn from(value: ReplicationChange) -> Self {
+        IrNode::Replication(value)
+    }
+}
+
 #[cfg(test)]
 impl From<TruncateTable> for IrNode {
     fn from(value: TruncateTable) -> Self {
@@ -0,0 +1,90 @@
+//! SQL lexing helpers
+//!
+//! The fixer, literal redaction, and the psql meta-command scanner walk raw
+//! SQL text without parsing it. They share these helpers for the spans whose
+//! end is not obvious: string literals, dollar quotes, and nested block
+//! comments.
+
+/// Whether `b` can continue an identifier or keyword. Non-ASCII bytes are
+/// identifier bytes, so a multi-byte character is never split.
+pub(crate) fn is_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'$' || b >= 0x80
+}
+
+/// Index just past the closing quote of a string literal whose body starts
+/// at `i`. `''` is an escaped quote; with `backslash_escapes`, so is `\'`.
+pub(crate) fn skip_string(bytes: &[u8], mut i: usize, backslash_escapes: bool) -> usize {
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if backslash_escapes => i += 2,
+            b'\'' if bytes.get(i + 1) == Some(&b'\'') => i += 2,
+            b'\'' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Index just past the `*/` that closes a block comment whose body starts
+/// at `i`. Block comments nest.
+pub(crate) fn skip_block_comment(bytes: &[u8], mut i: usize) -> usize {
+    let mut depth = 1;
+    while i < bytes.len() {
+        if bytes[i] == b'/' && bytes.get(i + 1) == Some(&b'*') {
+            depth += 1;
+            i += 2;
+        } else if bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/') {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    bytes.len()
+}
+
+/// The dollar-quote opener (`$$` or `$tag$`) at the start of `s`, if any.
+pub(crate) fn dollar_tag(s: &str) -> Option<&str> {
+    let rest = s.strip_prefix('$')?;
+    let end = rest.find('$')?;
+    let tag = &rest[..end];
+    let valid = tag
+        .chars()
+        .enumerate()
+        .all(|(i, c)| c == '_' || c.is_alphabetic() || (i > 0 && c.is_ascii_digit()));
+    valid.then(|| &s[..end + 2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skip_string() {
+        let sql = b"'it''s' x";
+        assert_eq!(skip_string(sql, 1, false), 7);
+        let escaped = br"'a\'b' x";
+        assert_eq!(skip_string(escaped, 1, true), 6);
+        assert_eq!(skip_string(escaped, 1, false), 4);
+        assert_eq!(skip_string(b"'open", 1, false), 5);
+    }
+
+    #[test]
+    fn test_skip_block_comment_nests() {
+        let sql = b"/* a /* b */ c */ SELECT";
+        assert_eq!(skip_block_comment(sql, 2), 17);
+        assert_eq!(skip_block_comment(b"/* open", 2), 7);
+    }
+
+    #[test]
+    fn test_dollar_tag() {
+        assert_eq!(dollar_tag("$$ body $$"), Some("$$"));
+        assert_eq!(dollar_tag("$fn$ body $fn$"), Some("$fn$"));
+        assert_eq!(dollar_tag("$1"), None);
+        assert_eq!(dollar_tag("$1$"), None);
+        assert_eq!(dollar_tag("plain"), None);
+    }
+}
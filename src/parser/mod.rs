@@ -1,11 +1,14 @@
 //! SQL parsing and IR generation
 
 pub mod ir;
+pub(crate) mod lex;
 pub(crate) mod pg_query;
+mod spelling;
 
 pub use ir::{
-    AlterTable, AlterTableAction, Cluster, ColumnDef, CreateIndex, CreateTable, DefaultExpr,
-    DeleteFrom, DropIndex, DropSchema, DropTable, IndexColumn, InsertInto, IrNode, Located,
-    PartitionBy, PartitionStrategy, QualifiedName, SourceSpan, TableConstraint, TablePersistence,
-    TriggerDisableScope, TruncateTable, TypeName, UpdateTable,
+    AlterTable, AlterTableAction, Cluster, ColumnDef, CreateExtension, CreateIndex, CreateTable,
+    DefaultExpr, DeleteFrom, DropExtension, DropIndex, DropSchema, DropTable, IndexColumn,
+    InsertInto, IrNode, Located, PartitionBy, PartitionStrategy, QualifiedName, SourceSpan,
+    Spelling, StorageParam, TableConstraint, TablePersistence, TriggerDisableScope, TruncateTable,
+    TypeName, UpdateTable,
 };
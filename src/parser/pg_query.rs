@@ -6,13 +6,16 @@
 
 use crate::catalog::types::IndexState;
 use crate::parser::ir::{
-    AlterTable, AlterTableAction, Cluster, ColumnDef, CreateIndex, CreateTable, DefaultExpr,
-    DeleteFrom, DropIndex, DropSchema, DropTable, IndexColumn, InsertInto, IrNode, Located,
-    PartitionBy, PartitionStrategy, QualifiedName, Reindex, ReindexObjectKind, ReindexTarget,
-    SourceSpan, TableConstraint, TablePersistence, TriggerDisableScope, TruncateTable, TypeName,
-    UpdateTable, VacuumFull,
+    AlterOwner, AlterTable, AlterTableAction, CheckExprDeps, Cluster, ColumnDef, CreateExtension,
+    CreateIndex, CreateTable, DefaultExpr, DeleteFrom, DropExtension, DropIndex, DropSchema,
+    DropTable, Grant, IndexColumn, InsertInto, IrNode, LiteralList, Located, OnCommit, PartitionBy,
+    PartitionStrategy, QualifiedName, Reindex, ReindexObjectKind, ReindexTarget, ReplicaIdentity,
+    ReplicationChange, SequenceOwner, SourceSpan, StatementKind, StorageParam, TableConstraint,
+    TablePersistence, TriggerDisableScope, TruncateTable, TypeName, UpdateTable, VacuumFull,
 };
+use crate::parser::spelling;
 use pg_query::NodeEnum;
+use std::sync::Arc;
 
 /// Sentinel type name used when the actual type cannot be determined.
 const UNKNOWN_TYPE: &str = "unknown";
@@ -25,7 +28,7 @@ mod tests;
 /// Each SQL statement in the source is converted to the most specific IR node
 /// possible. Statements that fail to parse entirely are returned as a single
 /// `Unparseable` node. Statements that parse but have no IR mapping (e.g.,
-/// GRANT, COMMENT ON) are returned as `Ignored`.
+/// CREATE VIEW) are returned as `Ignored`, classified by `StatementKind`.
 ///
 /// Line numbers in the returned `SourceSpan`s are 1-based.
 pub fn parse_sql(source: &str) -> Vec<Located<IrNode>> {
@@ -45,6 +48,7 @@ pub fn parse_sql(source: &str) -> Vec<Located<IrNode>> {
                     start_offset: 0,
                     end_offset: source.len(),
                 },
+                raw_sql: Arc::from(source.trim()),
             }];
         }
     };
@@ -74,12 +78,17 @@ pub fn parse_sql(source: &str) -> Vec<Located<IrNode>> {
 
         let stmt_node = raw_stmt.stmt.as_ref().and_then(|s| s.node.as_ref());
 
-        let ir_nodes = match stmt_node {
+        let mut ir_nodes = match stmt_node {
             Some(node_enum) => convert_node(node_enum, &raw_sql),
             None => vec![IrNode::Ignored {
+                kind: StatementKind::Unknown,
                 raw_sql: raw_sql.clone(),
             }],
         };
+        let idents = spelling::identifiers(&raw_sql);
+        for ir_node in &mut ir_nodes {
+            spelling::apply(ir_node, &idents);
+        }
 
         let span = SourceSpan {
             start_line,
@@ -87,11 +96,20 @@ pub fn parse_sql(source: &str) -> Vec<Located<IrNode>> {
             start_offset,
             end_offset,
         };
+        let statement_sql: Arc<str> = Arc::from(
+            source
+                .get(token_start..end_offset)
+                .unwrap_or("")
+                .trim_end()
+                .trim_end_matches(';')
+                .trim_end(),
+        );
 
         for ir_node in ir_nodes {
             nodes.push(Located {
                 node: ir_node,
                 span: span.clone(),
+                raw_sql: Arc::clone(&statement_sql),
             });
         }
     }
@@ -142,6 +160,8 @@ fn convert_node(node: &NodeEnum, raw_sql: &str) -> Vec<IrNode> {
         NodeEnum::AlterTableStmt(alter) => {
             if alter.objtype() == pg_query::protobuf::ObjectType::ObjectIndex {
                 convert_alter_index(alter, raw_sql)
+            } else if let Some(owner) = convert_relation_owner(alter) {
+                vec![owner]
             } else {
                 vec![convert_alter_table(alter, raw_sql)]
             }
@@ -156,16 +176,112 @@ fn convert_node(node: &NodeEnum, raw_sql: &str) -> Vec<IrNode> {
         NodeEnum::ClusterStmt(cluster) => vec![convert_cluster_stmt(cluster)],
         NodeEnum::VacuumStmt(vacuum) => convert_vacuum_stmt(vacuum),
         NodeEnum::ReindexStmt(reindex) => vec![convert_reindex_stmt(reindex)],
+        NodeEnum::CommentStmt(comment) => vec![convert_comment_stmt(comment, raw_sql)],
+        NodeEnum::CreateTrigStmt(trigger) => vec![IrNode::TableAttachment {
+            statement: "CREATE TRIGGER".to_string(),
+            table: relation_to_qualified_name(trigger.relation.as_ref()),
+        }],
+        NodeEnum::AlterTableMoveAllStmt(move_all) => vec![convert_move_all_stmt(move_all)],
+        NodeEnum::CreateExtensionStmt(ext) => vec![convert_create_extension(ext)],
+        NodeEnum::CreateSeqStmt(seq) => vec![IrNode::CreateSequence {
+            name: relation_to_qualified_name(seq.sequence.as_ref()),
+            if_not_exists: seq.if_not_exists,
+            owned_by: sequence_owned_by(&seq.options).flatten(),
+        }],
+        NodeEnum::AlterSeqStmt(seq) => match sequence_owned_by(&seq.options) {
+            Some(owned_by) => vec![IrNode::SetSequenceOwner {
+                name: relation_to_qualified_name(seq.sequence.as_ref()),
+                owned_by,
+            }],
+            None => vec![IrNode::Ignored {
+                kind: StatementKind::AlterSequence,
+                raw_sql: raw_sql.to_string(),
+            }],
+        },
+        NodeEnum::CreateSchemaStmt(schema) if !schema.schemaname.is_empty() => {
+            vec![IrNode::CreateSchema {
+                name: schema.schemaname.clone(),
+                if_not_exists: schema.if_not_exists,
+            }]
+        }
+        NodeEnum::GrantStmt(grant) => vec![IrNode::Grant(convert_grant_stmt(grant))],
+        NodeEnum::GrantRoleStmt(grant) => vec![convert_grant_role_stmt(grant)],
+        NodeEnum::AlterDefaultPrivilegesStmt(adp) => {
+            vec![convert_alter_default_privileges(adp, raw_sql)]
+        }
+        NodeEnum::AlterOwnerStmt(owner) => vec![convert_alter_owner_stmt(owner)],
+        NodeEnum::CreatePublicationStmt(publication) => vec![replication_change(
+            "CREATE PUBLICATION",
+            &publication.pubname,
+            publication_tables(&publication.pubobjects),
+        )],
+        NodeEnum::AlterPublicationStmt(publication) => vec![replication_change(
+            "ALTER PUBLICATION",
+            &publication.pubname,
+            publication_tables(&publication.pubobjects),
+        )],
+        NodeEnum::CreateSubscriptionStmt(sub) => {
+            vec![replication_change(
+                "CREATE SUBSCRIPTION",
+                &sub.subname,
+                vec![],
+            )]
+        }
+        NodeEnum::AlterSubscriptionStmt(sub) => {
+            vec![replication_change(
+                "ALTER SUBSCRIPTION",
+                &sub.subname,
+                vec![],
+            )]
+        }
+        NodeEnum::DropSubscriptionStmt(sub) => {
+            vec![replication_change(
+                "DROP SUBSCRIPTION",
+                &sub.subname,
+                vec![],
+            )]
+        }
         NodeEnum::DoStmt(_) => vec![IrNode::Unparseable {
             raw_sql: raw_sql.to_string(),
             table_hint: None,
         }],
         _ => vec![IrNode::Ignored {
+            kind: classify_statement(node),
             raw_sql: raw_sql.to_string(),
         }],
     }
 }
 
+/// Classify a statement that `convert_node` has no IR mapping for.
+fn classify_statement(node: &NodeEnum) -> StatementKind {
+    match node {
+        NodeEnum::CommentStmt(_) => StatementKind::Comment,
+        NodeEnum::CreateFunctionStmt(_) => StatementKind::CreateFunction,
+        NodeEnum::ViewStmt(_) => StatementKind::CreateView,
+        NodeEnum::CreateTableAsStmt(_) => StatementKind::CreateTableAs,
+        NodeEnum::SelectStmt(select) if select.into_clause.is_some() => {
+            StatementKind::CreateTableAs
+        }
+        NodeEnum::SelectStmt(_) => StatementKind::Select,
+        NodeEnum::RefreshMatViewStmt(_) => StatementKind::RefreshMaterializedView,
+        NodeEnum::AlterSeqStmt(_) => StatementKind::AlterSequence,
+        NodeEnum::CreateEnumStmt(_)
+        | NodeEnum::CompositeTypeStmt(_)
+        | NodeEnum::CreateDomainStmt(_) => StatementKind::CreateType,
+        NodeEnum::DefineStmt(define)
+            if define.kind() == pg_query::protobuf::ObjectType::ObjectType =>
+        {
+            StatementKind::CreateType
+        }
+        NodeEnum::AlterEnumStmt(_) | NodeEnum::AlterDomainStmt(_) => StatementKind::AlterType,
+        NodeEnum::CreateSchemaStmt(_) => StatementKind::CreateSchema,
+        NodeEnum::VariableSetStmt(_) => StatementKind::Set,
+        NodeEnum::TransactionStmt(_) => StatementKind::Transaction,
+        NodeEnum::LockStmt(_) => StatementKind::Lock,
+        _ => StatementKind::Unknown,
+    }
+}
+
 /// Convert a pg_query `CreateStmt` to `IrNode::CreateTable`.
 fn convert_create_table(create: &pg_query::protobuf::CreateStmt, _raw_sql: &str) -> IrNode {
     let name = relation_to_qualified_name(create.relation.as_ref());
@@ -249,17 +365,20 @@ fn convert_create_table(create: &pg_query::protobuf::CreateStmt, _raw_sql: &str)
         })
     });
 
-    // Extract PARTITION OF parent (when partbound is present, inh_relations[0] is the parent)
-    let partition_of = if create.partbound.is_some() {
-        create
-            .inh_relations
-            .first()
-            .and_then(|node| match node.node.as_ref() {
-                Some(NodeEnum::RangeVar(rv)) => Some(relation_to_qualified_name(Some(rv))),
-                _ => None,
-            })
+    // inh_relations holds the PARTITION OF parent when partbound is present,
+    // and the INHERITS (...) parents otherwise.
+    let parents: Vec<QualifiedName> = create
+        .inh_relations
+        .iter()
+        .filter_map(|node| match node.node.as_ref() {
+            Some(NodeEnum::RangeVar(rv)) => Some(relation_to_qualified_name(Some(rv))),
+            _ => None,
+        })
+        .collect();
+    let (partition_of, inherits) = if create.partbound.is_some() {
+        (parents.into_iter().next(), Vec::new())
     } else {
-        None
+        (None, parents)
     };
 
     IrNode::CreateTable(CreateTable {
@@ -270,9 +389,59 @@ fn convert_create_table(create: &pg_query::protobuf::CreateStmt, _raw_sql: &str)
         if_not_exists: create.if_not_exists,
         partition_by,
         partition_of,
+        inherits,
+        storage_params: convert_storage_params(&create.options),
+        on_commit: match create.oncommit() {
+            pg_query::protobuf::OnCommitAction::OncommitDrop => OnCommit::Drop,
+            pg_query::protobuf::OnCommitAction::OncommitDeleteRows => OnCommit::DeleteRows,
+            _ => OnCommit::PreserveRows,
+        },
     })
 }
 
+/// Convert reloption `DefElem` nodes (`WITH (...)`, `SET (...)`) to storage
+/// parameters. Namespaced options like `toast.autovacuum_enabled` keep
+/// their prefix.
+fn convert_storage_params(options: &[pg_query::protobuf::Node]) -> Vec<StorageParam> {
+    options
+        .iter()
+        .filter_map(|n| match n.node.as_ref() {
+            Some(NodeEnum::DefElem(d)) => Some(StorageParam {
+                name: if d.defnamespace.is_empty() {
+                    d.defname.clone()
+                } else {
+                    format!("{}.{}", d.defnamespace, d.defname)
+                },
+                value: d
+                    .arg
+                    .as_ref()
+                    .and_then(|a| a.node.as_ref())
+                    .and_then(|a| match a {
+                        NodeEnum::String(s) => Some(s.sval.clone()),
+                        NodeEnum::Integer(i) => Some(i.ival.to_string()),
+                        NodeEnum::Float(f) => Some(f.fval.clone()),
+                        NodeEnum::Boolean(b) => Some(b.boolval.to_string()),
+                        // Unquoted keywords such as `off` parse as a type name.
+                        NodeEnum::TypeName(t) => t.names.last().and_then(|n| match &n.node {
+                            Some(NodeEnum::String(s)) => Some(s.sval.clone()),
+                            _ => None,
+                        }),
+                        _ => None,
+                    }),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Storage parameters from an `AlterTableCmd.def` holding a `List` of `DefElem`.
+fn storage_params_from_cmd(cmd: &pg_query::protobuf::AlterTableCmd) -> Vec<StorageParam> {
+    match cmd.def.as_ref().and_then(|d| d.node.as_ref()) {
+        Some(NodeEnum::List(list)) => convert_storage_params(&list.items),
+        _ => Vec::new(),
+    }
+}
+
 /// Convert a constraint name to `Option<String>`, treating empty strings as `None`.
 fn optional_name(name: &str) -> Option<String> {
     if name.is_empty() {
@@ -296,6 +465,7 @@ fn convert_column_def(col: &pg_query::protobuf::ColumnDef) -> (ColumnDef, Vec<Ta
     let mut nullable = true;
     let mut default_expr = None;
     let mut is_inline_pk = false;
+    let mut is_identity = false;
     let mut constraints = Vec::new();
 
     // serial/bigserial implies a nextval() default
@@ -321,6 +491,9 @@ fn convert_column_def(col: &pg_query::protobuf::ColumnDef) -> (ColumnDef, Vec<Ta
                     default_expr = Some(convert_default_expr(expr));
                 }
             }
+            pg_query::protobuf::ConstrType::ConstrIdentity => {
+                is_identity = true;
+            }
             pg_query::protobuf::ConstrType::ConstrPrimary => {
                 is_inline_pk = true;
                 nullable = false;
@@ -358,6 +531,11 @@ fn convert_column_def(col: &pg_query::protobuf::ColumnDef) -> (ColumnDef, Vec<Ta
                     name: optional_name(&con.conname),
                     expression,
                     not_valid: con.skip_validation,
+                    deps: con
+                        .raw_expr
+                        .as_deref()
+                        .map(check_expr_deps)
+                        .unwrap_or_default(),
                 });
             }
             _ => {}
@@ -371,6 +549,9 @@ fn convert_column_def(col: &pg_query::protobuf::ColumnDef) -> (ColumnDef, Vec<Ta
         default_expr,
         is_inline_pk,
         is_serial,
+        is_identity,
+        if_not_exists: false,
+        spelling: None,
     };
 
     (col_def, constraints)
@@ -383,6 +564,10 @@ fn convert_column_def(col: &pg_query::protobuf::ColumnDef) -> (ColumnDef, Vec<Ta
 ///
 /// Canonical name extraction: use the LAST element of `TypeName.names[]`.
 /// This normalizes all PostgreSQL type aliases automatically.
+///
+/// Array types keep the element type as the name; each entry in
+/// `TypeName.array_bounds` (`-1` for `[]`, the bound for `[3]`) is one
+/// dimension.
 fn extract_type_name(tn: Option<&pg_query::protobuf::TypeName>) -> (TypeName, bool) {
     let tn = match tn {
         Some(t) => t,
@@ -419,7 +604,7 @@ fn extract_type_name(tn: Option<&pg_query::protobuf::TypeName>) -> (TypeName, bo
         TypeName::with_modifiers(mapped_name, modifiers)
     };
 
-    (type_name, is_serial)
+    (type_name.with_array_dims(tn.array_bounds.len()), is_serial)
 }
 
 /// Extract integer modifiers from `TypeName.typmods[]`.
@@ -452,6 +637,7 @@ fn extract_type_modifiers(typmods: &[pg_query::protobuf::Node]) -> Vec<i64> {
 /// Mapping:
 /// - `AConst` (Integer, String, Boolean) -> `DefaultExpr::Literal`
 /// - `FuncCall` -> `DefaultExpr::FunctionCall`
+/// - `ColumnRef` -> `DefaultExpr::ColumnRef`
 /// - Everything else -> `DefaultExpr::Other`
 fn convert_default_expr(node: &pg_query::protobuf::Node) -> DefaultExpr {
     match node.node.as_ref() {
@@ -493,6 +679,18 @@ fn convert_default_expr_from_node(node: &NodeEnum) -> DefaultExpr {
             let args: Vec<String> = fc.args.iter().map(deparse_node).collect();
             DefaultExpr::FunctionCall { name, args }
         }
+        NodeEnum::ColumnRef(cr) => {
+            let name = cr
+                .fields
+                .iter()
+                .filter_map(|f| match f.node.as_ref() {
+                    Some(NodeEnum::String(s)) => Some(s.sval.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join(".");
+            DefaultExpr::ColumnRef(name)
+        }
         _ => {
             let wrapper = pg_query::protobuf::Node {
                 node: Some(node.clone()),
@@ -519,6 +717,7 @@ fn convert_alter_table(alter: &pg_query::protobuf::AlterTableStmt, raw_sql: &str
 
     if actions.is_empty() {
         return IrNode::Ignored {
+            kind: StatementKind::AlterTable,
             raw_sql: raw_sql.to_string(),
         };
     }
@@ -535,7 +734,8 @@ fn convert_alter_table_cmd(cmd: &pg_query::protobuf::AlterTableCmd) -> Vec<Alter
         pg_query::protobuf::AlterTableType::AtAddColumn => {
             match cmd.def.as_ref().and_then(|d| d.node.as_ref()) {
                 Some(NodeEnum::ColumnDef(col)) => {
-                    let (col_def, inline_constraints) = convert_column_def(col);
+                    let (mut col_def, inline_constraints) = convert_column_def(col);
+                    col_def.if_not_exists = cmd.missing_ok;
                     let mut result = vec![AlterTableAction::AddColumn(col_def)];
                     result.extend(
                         inline_constraints
@@ -670,6 +870,7 @@ fn convert_alter_table_cmd(cmd: &pg_query::protobuf::AlterTableCmd) -> Vec<Alter
         pg_query::protobuf::AlterTableType::AtDropConstraint => {
             vec![AlterTableAction::DropConstraint {
                 constraint_name: cmd.name.clone(),
+                if_exists: cmd.missing_ok,
             }]
         }
         pg_query::protobuf::AlterTableType::AtValidateConstraint => {
@@ -677,6 +878,48 @@ fn convert_alter_table_cmd(cmd: &pg_query::protobuf::AlterTableCmd) -> Vec<Alter
                 constraint_name: cmd.name.clone(),
             }]
         }
+        pg_query::protobuf::AlterTableType::AtSetRelOptions => {
+            vec![AlterTableAction::SetStorageParams {
+                params: storage_params_from_cmd(cmd),
+            }]
+        }
+        pg_query::protobuf::AlterTableType::AtChangeOwner => {
+            vec![AlterTableAction::ChangeOwner {
+                new_owner: cmd
+                    .newowner
+                    .as_ref()
+                    .map(role_spec_name)
+                    .unwrap_or_default(),
+            }]
+        }
+        pg_query::protobuf::AlterTableType::AtReplicaIdentity => {
+            let identity = match cmd.def.as_ref().and_then(|d| d.node.as_ref()) {
+                Some(NodeEnum::ReplicaIdentityStmt(ri)) => match ri.identity_type.as_str() {
+                    "f" => Some(ReplicaIdentity::Full),
+                    "i" => Some(ReplicaIdentity::UsingIndex(ri.name.clone())),
+                    "n" => Some(ReplicaIdentity::Nothing),
+                    "d" => Some(ReplicaIdentity::Default),
+                    _ => None,
+                },
+                _ => None,
+            };
+            match identity {
+                Some(identity) => vec![AlterTableAction::SetReplicaIdentity(identity)],
+                None => vec![AlterTableAction::Other {
+                    description: format!("{:?}", cmd.subtype()),
+                }],
+            }
+        }
+        pg_query::protobuf::AlterTableType::AtSetLogged => vec![AlterTableAction::SetLogged],
+        pg_query::protobuf::AlterTableType::AtSetUnLogged => vec![AlterTableAction::SetUnlogged],
+        pg_query::protobuf::AlterTableType::AtResetRelOptions => {
+            vec![AlterTableAction::ResetStorageParams {
+                names: storage_params_from_cmd(cmd)
+                    .into_iter()
+                    .map(|p| p.name)
+                    .collect(),
+            }]
+        }
         // ENABLE TRIGGER variants — not flagged, no schema state change.
         pg_query::protobuf::AlterTableType::AtEnableTrig
         | pg_query::protobuf::AlterTableType::AtEnableTrigAll
@@ -702,6 +945,7 @@ fn convert_alter_index(alter: &pg_query::protobuf::AlterTableStmt, raw_sql: &str
         Some(r) => r.relname.clone(),
         None => {
             return vec![IrNode::Ignored {
+                kind: StatementKind::AlterIndex,
                 raw_sql: raw_sql.to_string(),
             }];
         }
@@ -731,10 +975,18 @@ fn convert_alter_index(alter: &pg_query::protobuf::AlterTableStmt, raw_sql: &str
                 }];
             }
         }
+
+        if cmd.subtype() == pg_query::protobuf::AlterTableType::AtSetTableSpace {
+            return vec![IrNode::AlterIndexSetTablespace {
+                index_name: parent_name,
+                tablespace: cmd.name.clone(),
+            }];
+        }
     }
 
-    // All other ALTER INDEX subtypes (SET, RESET, SET TABLESPACE, etc.)
+    // All other ALTER INDEX subtypes (SET, RESET, etc.)
     vec![IrNode::Ignored {
+        kind: StatementKind::AlterIndex,
         raw_sql: raw_sql.to_string(),
     }]
 }
@@ -743,6 +995,8 @@ fn convert_alter_index(alter: &pg_query::protobuf::AlterTableStmt, raw_sql: &str
 ///
 /// - `ObjectType::ObjectTable` with no `subname` → `IrNode::RenameTable`
 /// - `ObjectType::ObjectColumn` → `IrNode::RenameColumn`
+/// - `ObjectType::ObjectIndex` → `IrNode::RenameIndex`
+/// - `ObjectType::ObjectSequence` → `IrNode::RenameSequence`
 /// - Everything else → `IrNode::Ignored`
 fn convert_rename_stmt(rename: &pg_query::protobuf::RenameStmt, raw_sql: &str) -> IrNode {
     match rename.rename_type() {
@@ -761,7 +1015,22 @@ fn convert_rename_stmt(rename: &pg_query::protobuf::RenameStmt, raw_sql: &str) -
                 new_name: rename.newname.clone(),
             }
         }
+        pg_query::protobuf::ObjectType::ObjectSequence => IrNode::RenameSequence {
+            name: relation_to_qualified_name(rename.relation.as_ref()),
+            new_name: rename.newname.clone(),
+        },
+        pg_query::protobuf::ObjectType::ObjectIndex => match rename.relation.as_ref() {
+            Some(r) => IrNode::RenameIndex {
+                index_name: r.relname.clone(),
+                new_name: rename.newname.clone(),
+            },
+            None => IrNode::Ignored {
+                kind: StatementKind::Rename,
+                raw_sql: raw_sql.to_string(),
+            },
+        },
         _ => IrNode::Ignored {
+            kind: StatementKind::Rename,
             raw_sql: raw_sql.to_string(),
         },
     }
@@ -832,6 +1101,11 @@ fn convert_table_constraint(
                 name,
                 expression,
                 not_valid: con.skip_validation,
+                deps: con
+                    .raw_expr
+                    .as_deref()
+                    .map(check_expr_deps)
+                    .unwrap_or_default(),
             })
         }
         pg_query::protobuf::ConstrType::ConstrExclusion => Some(TableConstraint::Exclude { name }),
@@ -904,6 +1178,9 @@ fn convert_create_index(idx: &pg_query::protobuf::IndexStmt) -> IrNode {
 ///
 /// - `ObjectType::ObjectIndex` -> `IrNode::DropIndex` (one per index)
 /// - `ObjectType::ObjectTable` -> `IrNode::DropTable` (one per table)
+/// - `ObjectType::ObjectSchema` -> `IrNode::DropSchema` (one per schema)
+/// - `ObjectType::ObjectExtension` -> `IrNode::DropExtension` (one per extension)
+/// - `ObjectType::ObjectSequence` -> `IrNode::DropSequence` (one per sequence)
 /// - Everything else -> `IrNode::Ignored`
 fn convert_drop_stmt(drop: &pg_query::protobuf::DropStmt, raw_sql: &str) -> Vec<IrNode> {
     match drop.remove_type() {
@@ -911,6 +1188,7 @@ fn convert_drop_stmt(drop: &pg_query::protobuf::DropStmt, raw_sql: &str) -> Vec<
             let names = extract_all_names_from_drop_objects(&drop.objects);
             if names.is_empty() {
                 return vec![IrNode::Ignored {
+                    kind: StatementKind::Drop,
                     raw_sql: raw_sql.to_string(),
                 }];
             }
@@ -929,6 +1207,7 @@ fn convert_drop_stmt(drop: &pg_query::protobuf::DropStmt, raw_sql: &str) -> Vec<
             let qualified_names = extract_all_qualified_names_from_drop_objects(&drop.objects);
             if qualified_names.is_empty() {
                 return vec![IrNode::Ignored {
+                    kind: StatementKind::Drop,
                     raw_sql: raw_sql.to_string(),
                 }];
             }
@@ -944,9 +1223,10 @@ fn convert_drop_stmt(drop: &pg_query::protobuf::DropStmt, raw_sql: &str) -> Vec<
                 .collect()
         }
         pg_query::protobuf::ObjectType::ObjectSchema => {
-            let names = extract_schema_names_from_drop_objects(&drop.objects);
+            let names = extract_bare_names_from_drop_objects(&drop.objects);
             if names.is_empty() {
                 return vec![IrNode::Ignored {
+                    kind: StatementKind::Drop,
                     raw_sql: raw_sql.to_string(),
                 }];
             }
@@ -961,18 +1241,393 @@ fn convert_drop_stmt(drop: &pg_query::protobuf::DropStmt, raw_sql: &str) -> Vec<
                 })
                 .collect()
         }
+        pg_query::protobuf::ObjectType::ObjectExtension => {
+            let names = extract_bare_names_from_drop_objects(&drop.objects);
+            if names.is_empty() {
+                return vec![IrNode::Ignored {
+                    kind: StatementKind::Drop,
+                    raw_sql: raw_sql.to_string(),
+                }];
+            }
+            names
+                .into_iter()
+                .map(|name| {
+                    IrNode::DropExtension(DropExtension {
+                        name,
+                        if_exists: drop.missing_ok,
+                        cascade: drop.behavior() == pg_query::protobuf::DropBehavior::DropCascade,
+                    })
+                })
+                .collect()
+        }
+        pg_query::protobuf::ObjectType::ObjectSequence => {
+            let qualified_names = extract_all_qualified_names_from_drop_objects(&drop.objects);
+            if qualified_names.is_empty() {
+                return vec![IrNode::Ignored {
+                    kind: StatementKind::Drop,
+                    raw_sql: raw_sql.to_string(),
+                }];
+            }
+            qualified_names
+                .into_iter()
+                .map(|name| IrNode::DropSequence {
+                    name,
+                    if_exists: drop.missing_ok,
+                })
+                .collect()
+        }
+        pg_query::protobuf::ObjectType::ObjectTrigger => {
+            // Each object is `[schema, ]table, trigger`.
+            let tables: Vec<QualifiedName> = drop
+                .objects
+                .iter()
+                .filter_map(|obj| match obj.node.as_ref() {
+                    Some(NodeEnum::List(list)) => {
+                        let parts = extract_string_list(&list.items);
+                        parts
+                            .split_last()
+                            .and_then(|(_, table)| table_from_name_parts(table))
+                    }
+                    _ => None,
+                })
+                .collect();
+            if tables.is_empty() {
+                return vec![IrNode::Ignored {
+                    kind: StatementKind::Drop,
+                    raw_sql: raw_sql.to_string(),
+                }];
+            }
+            tables
+                .into_iter()
+                .map(|table| IrNode::TableAttachment {
+                    statement: "DROP TRIGGER".to_string(),
+                    table,
+                })
+                .collect()
+        }
+        pg_query::protobuf::ObjectType::ObjectPublication => {
+            let names = extract_bare_names_from_drop_objects(&drop.objects);
+            if names.is_empty() {
+                return vec![IrNode::Ignored {
+                    kind: StatementKind::Drop,
+                    raw_sql: raw_sql.to_string(),
+                }];
+            }
+            names
+                .iter()
+                .map(|name| replication_change("DROP PUBLICATION", name, vec![]))
+                .collect()
+        }
         _ => vec![IrNode::Ignored {
+            kind: StatementKind::Drop,
             raw_sql: raw_sql.to_string(),
         }],
     }
 }
 
+/// Convert a pg_query `CommentStmt`. Comments on a table, its columns, its
+/// constraints, or its triggers become `IrNode::TableAttachment`; comments on
+/// anything else are `Ignored`.
+fn convert_comment_stmt(comment: &pg_query::protobuf::CommentStmt, raw_sql: &str) -> IrNode {
+    use pg_query::protobuf::ObjectType;
+
+    let parts = match comment.object.as_ref().and_then(|o| o.node.as_ref()) {
+        Some(NodeEnum::List(list)) => extract_string_list(&list.items),
+        _ => vec![],
+    };
+    // The table name is the whole list for a table, and the list minus the
+    // trailing column, constraint, or trigger name otherwise.
+    let table_parts = match comment.objtype() {
+        ObjectType::ObjectTable => Some(&parts[..]),
+        ObjectType::ObjectColumn | ObjectType::ObjectTabconstraint | ObjectType::ObjectTrigger => {
+            parts.split_last().map(|(_, table)| table)
+        }
+        _ => None,
+    };
+    match table_parts.and_then(table_from_name_parts) {
+        Some(table) => IrNode::TableAttachment {
+            statement: "COMMENT ON".to_string(),
+            table,
+        },
+        None => IrNode::Ignored {
+            kind: StatementKind::Comment,
+            raw_sql: raw_sql.to_string(),
+        },
+    }
+}
+
+/// The table named by `[[catalog, ]schema, ]table` name parts.
+fn table_from_name_parts(parts: &[String]) -> Option<QualifiedName> {
+    match parts {
+        [] => None,
+        [table] => Some(QualifiedName::unqualified(table)),
+        [.., schema, table] => Some(QualifiedName::qualified(schema, table)),
+    }
+}
+
+fn replication_change(statement: &str, name: &str, tables: Vec<QualifiedName>) -> IrNode {
+    IrNode::Replication(ReplicationChange {
+        statement: statement.to_string(),
+        name: name.to_string(),
+        tables,
+    })
+}
+
+/// Tables named in a publication's object list (`FOR TABLE`, `ADD TABLE`,
+/// ...). `TABLES IN SCHEMA` entries name no table and are skipped.
+fn publication_tables(objects: &[pg_query::protobuf::Node]) -> Vec<QualifiedName> {
+    objects
+        .iter()
+        .filter_map(|n| match n.node.as_ref() {
+            Some(NodeEnum::PublicationObjSpec(spec)) => {
+                let relation = spec.pubtable.as_ref()?.relation.as_ref()?;
+                Some(relation_to_qualified_name(Some(relation)))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// The `OWNED BY` option of `CREATE SEQUENCE` / `ALTER SEQUENCE`: `None`
+/// when absent, `Some(None)` for `OWNED BY NONE`.
+///
+/// pg_query stores the target as a `DefElem` whose argument is the list of
+/// name parts (`[schema,] table, column`, or the single part `none`).
+fn sequence_owned_by(options: &[pg_query::protobuf::Node]) -> Option<Option<SequenceOwner>> {
+    options.iter().find_map(|n| match n.node.as_ref() {
+        Some(NodeEnum::DefElem(d)) if d.defname == "owned_by" => {
+            let parts = match d.arg.as_ref().and_then(|a| a.node.as_ref()) {
+                Some(NodeEnum::List(list)) => extract_string_list(&list.items),
+                _ => Vec::new(),
+            };
+            Some(match parts.as_slice() {
+                [table, column] => Some(SequenceOwner {
+                    table: QualifiedName::unqualified(table),
+                    column: column.clone(),
+                }),
+                [schema, table, column] => Some(SequenceOwner {
+                    table: QualifiedName::qualified(schema, table),
+                    column: column.clone(),
+                }),
+                _ => None,
+            })
+        }
+        _ => None,
+    })
+}
+
+/// Convert a pg_query `CreateExtensionStmt` to `IrNode::CreateExtension`.
+///
+/// The target schema comes from the `SCHEMA name` option, which pg_query
+/// stores as a `DefElem` with a `String` argument.
+fn convert_create_extension(ext: &pg_query::protobuf::CreateExtensionStmt) -> IrNode {
+    let schema = ext.options.iter().find_map(|n| match n.node.as_ref() {
+        Some(NodeEnum::DefElem(d)) if d.defname == "schema" => {
+            match d.arg.as_ref().and_then(|a| a.node.as_ref()) {
+                Some(NodeEnum::String(s)) => Some(s.sval.clone()),
+                _ => None,
+            }
+        }
+        _ => None,
+    });
+
+    IrNode::CreateExtension(CreateExtension {
+        name: ext.extname.clone(),
+        if_not_exists: ext.if_not_exists,
+        schema,
+    })
+}
+
+/// SQL keyword for an object kind, as used in `GRANT ... ON <kind>` and
+/// `ALTER <kind> ... OWNER TO`.
+fn object_kind_keyword(objtype: pg_query::protobuf::ObjectType) -> &'static str {
+    use pg_query::protobuf::ObjectType;
+    match objtype {
+        ObjectType::ObjectTable => "TABLE",
+        ObjectType::ObjectSequence => "SEQUENCE",
+        ObjectType::ObjectView => "VIEW",
+        ObjectType::ObjectMatview => "MATERIALIZED VIEW",
+        ObjectType::ObjectForeignTable => "FOREIGN TABLE",
+        ObjectType::ObjectSchema => "SCHEMA",
+        ObjectType::ObjectDatabase => "DATABASE",
+        ObjectType::ObjectFunction => "FUNCTION",
+        ObjectType::ObjectProcedure => "PROCEDURE",
+        ObjectType::ObjectRoutine => "ROUTINE",
+        ObjectType::ObjectType => "TYPE",
+        ObjectType::ObjectDomain => "DOMAIN",
+        ObjectType::ObjectLanguage => "LANGUAGE",
+        ObjectType::ObjectLargeobject => "LARGE OBJECT",
+        ObjectType::ObjectTablespace => "TABLESPACE",
+        ObjectType::ObjectForeignServer => "SERVER",
+        ObjectType::ObjectFdw => "FOREIGN DATA WRAPPER",
+        _ => "OBJECT",
+    }
+}
+
+/// Role name from a `RoleSpec`, spelling the pseudo-roles as SQL keywords.
+fn role_spec_name(role: &pg_query::protobuf::RoleSpec) -> String {
+    use pg_query::protobuf::RoleSpecType;
+    match role.roletype() {
+        RoleSpecType::RolespecPublic => "PUBLIC".to_string(),
+        RoleSpecType::RolespecCurrentRole => "CURRENT_ROLE".to_string(),
+        RoleSpecType::RolespecCurrentUser => "CURRENT_USER".to_string(),
+        RoleSpecType::RolespecSessionUser => "SESSION_USER".to_string(),
+        _ => role.rolename.clone(),
+    }
+}
+
+/// Role names from a list of `RoleSpec` nodes.
+fn role_spec_names(nodes: &[pg_query::protobuf::Node]) -> Vec<String> {
+    nodes
+        .iter()
+        .filter_map(|n| match n.node.as_ref() {
+            Some(NodeEnum::RoleSpec(role)) => Some(role_spec_name(role)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Name of a GRANT or ALTER OWNER target as written.
+///
+/// Relations are `RangeVar`s; schemas, databases, and languages bare
+/// `String`s; functions `ObjectWithArgs`; types and domains a `List` of
+/// name parts.
+fn object_name(node: &pg_query::protobuf::Node) -> Option<String> {
+    match node.node.as_ref()? {
+        NodeEnum::RangeVar(rv) => Some(relation_to_qualified_name(Some(rv)).display_name()),
+        NodeEnum::String(s) => Some(s.sval.clone()),
+        NodeEnum::ObjectWithArgs(owa) => Some(extract_string_list(&owa.objname).join(".")),
+        NodeEnum::List(list) => Some(extract_string_list(&list.items).join(".")),
+        NodeEnum::TypeName(tn) => Some(extract_string_list(&tn.names).join(".")),
+        _ => None,
+    }
+}
+
+/// Convert a pg_query `GrantStmt` (GRANT/REVOKE privileges) to a [`Grant`].
+///
+/// For `ALL TABLES IN SCHEMA s`, `objects` holds the schema names, which
+/// pg_query stores as bare `String` nodes.
+fn convert_grant_stmt(grant: &pg_query::protobuf::GrantStmt) -> Grant {
+    Grant {
+        is_grant: grant.is_grant,
+        object_kind: object_kind_keyword(grant.objtype()).to_string(),
+        objects: grant.objects.iter().filter_map(object_name).collect(),
+        all_in_schema: grant.targtype()
+            == pg_query::protobuf::GrantTargetType::AclTargetAllInSchema,
+        grantees: role_spec_names(&grant.grantees),
+    }
+}
+
+/// Convert a pg_query `GrantRoleStmt` (`GRANT role TO user`) to `IrNode::Grant`.
+///
+/// Granted roles are `AccessPriv` nodes whose `priv_name` is the role name.
+fn convert_grant_role_stmt(grant: &pg_query::protobuf::GrantRoleStmt) -> IrNode {
+    let objects = grant
+        .granted_roles
+        .iter()
+        .filter_map(|n| match n.node.as_ref() {
+            Some(NodeEnum::AccessPriv(p)) => Some(p.priv_name.clone()),
+            _ => None,
+        })
+        .collect();
+
+    IrNode::Grant(Grant {
+        is_grant: grant.is_grant,
+        object_kind: "ROLE".to_string(),
+        objects,
+        all_in_schema: false,
+        grantees: role_spec_names(&grant.grantee_roles),
+    })
+}
+
+/// Convert a pg_query `AlterDefaultPrivilegesStmt` to `IrNode::Grant`.
+///
+/// The wrapped GRANT/REVOKE applies to objects created in the future, so no
+/// objects are recorded.
+fn convert_alter_default_privileges(
+    adp: &pg_query::protobuf::AlterDefaultPrivilegesStmt,
+    raw_sql: &str,
+) -> IrNode {
+    let Some(action) = adp.action.as_ref() else {
+        return IrNode::Ignored {
+            kind: StatementKind::Unknown,
+            raw_sql: raw_sql.to_string(),
+        };
+    };
+
+    IrNode::Grant(Grant {
+        object_kind: "DEFAULT PRIVILEGES".to_string(),
+        objects: Vec::new(),
+        ..convert_grant_stmt(action)
+    })
+}
+
+/// Convert a pg_query `AlterOwnerStmt` (`ALTER SCHEMA/FUNCTION/TYPE/...
+/// OWNER TO`) to `IrNode::AlterOwner`.
+fn convert_alter_owner_stmt(owner: &pg_query::protobuf::AlterOwnerStmt) -> IrNode {
+    let name = match owner.relation.as_ref() {
+        Some(rv) => relation_to_qualified_name(Some(rv)).display_name(),
+        None => owner
+            .object
+            .as_deref()
+            .and_then(object_name)
+            .unwrap_or_default(),
+    };
+
+    IrNode::AlterOwner(AlterOwner {
+        object_kind: object_kind_keyword(owner.object_type()).to_string(),
+        name,
+        new_owner: owner
+            .newowner
+            .as_ref()
+            .map(role_spec_name)
+            .unwrap_or_default(),
+    })
+}
+
+/// Convert `ALTER SEQUENCE/VIEW/MATERIALIZED VIEW ... OWNER TO` to
+/// `IrNode::AlterOwner`.
+///
+/// pg_query routes these through `AlterTableStmt`. Returns `None` for any
+/// other statement, which is then converted as an ALTER TABLE.
+fn convert_relation_owner(alter: &pg_query::protobuf::AlterTableStmt) -> Option<IrNode> {
+    use pg_query::protobuf::ObjectType;
+    let objtype = alter.objtype();
+    if !matches!(
+        objtype,
+        ObjectType::ObjectSequence | ObjectType::ObjectView | ObjectType::ObjectMatview
+    ) {
+        return None;
+    }
+    let [cmd_node] = alter.cmds.as_slice() else {
+        return None;
+    };
+    let Some(NodeEnum::AlterTableCmd(cmd)) = cmd_node.node.as_ref() else {
+        return None;
+    };
+    if cmd.subtype() != pg_query::protobuf::AlterTableType::AtChangeOwner {
+        return None;
+    }
+
+    Some(IrNode::AlterOwner(AlterOwner {
+        object_kind: object_kind_keyword(objtype).to_string(),
+        name: relation_to_qualified_name(alter.relation.as_ref()).display_name(),
+        new_owner: cmd
+            .newowner
+            .as_ref()
+            .map(role_spec_name)
+            .unwrap_or_default(),
+    }))
+}
+
 /// Convert a pg_query `TruncateStmt` to one IR node per target table.
 ///
 /// `TRUNCATE t1, t2, t3 CASCADE` produces three `TruncateTable` nodes,
-/// all sharing the same `cascade` flag.
+/// all sharing the same `cascade` and `restart_identity` flags. `ONLY` is
+/// per relation (`TRUNCATE ONLY t1, t2` applies it to `t1` alone).
 fn convert_truncate_stmt(trunc: &pg_query::protobuf::TruncateStmt) -> Vec<IrNode> {
     let cascade = trunc.behavior() == pg_query::protobuf::DropBehavior::DropCascade;
+    let restart_identity = trunc.restart_seqs;
 
     trunc
         .relations
@@ -982,6 +1637,8 @@ fn convert_truncate_stmt(trunc: &pg_query::protobuf::TruncateStmt) -> Vec<IrNode
                 NodeEnum::RangeVar(rv) => Some(IrNode::TruncateTable(TruncateTable {
                     name: relation_to_qualified_name(Some(rv)),
                     cascade,
+                    restart_identity,
+                    only: !rv.inh,
                 })),
                 _ => None,
             })
@@ -998,7 +1655,18 @@ fn convert_insert_stmt(insert: &pg_query::protobuf::InsertStmt) -> IrNode {
 /// Convert an `UpdateStmt` to `IrNode::UpdateTable`.
 fn convert_update_stmt(update: &pg_query::protobuf::UpdateStmt) -> IrNode {
     let table_name = relation_to_qualified_name(update.relation.as_ref());
-    IrNode::UpdateTable(UpdateTable { table_name })
+    let set_columns = update
+        .target_list
+        .iter()
+        .filter_map(|n| match n.node.as_ref() {
+            Some(NodeEnum::ResTarget(rt)) => Some(rt.name.clone()),
+            _ => None,
+        })
+        .collect();
+    IrNode::UpdateTable(UpdateTable {
+        table_name,
+        set_columns,
+    })
 }
 
 /// Convert a `DeleteStmt` to `IrNode::DeleteFrom`.
@@ -1019,8 +1687,10 @@ fn convert_cluster_stmt(cluster: &pg_query::protobuf::ClusterStmt) -> IrNode {
 
 /// Convert a `VacuumStmt` to IR.
 ///
-/// Only `VACUUM FULL` is relevant — it rewrites the table under ACCESS EXCLUSIVE.
-/// Plain `VACUUM` (no `FULL` option) is mapped to `Ignored`.
+/// `VACUUM FULL` rewrites the table under ACCESS EXCLUSIVE. Plain `VACUUM`
+/// without a table list processes the whole database and maps to
+/// `VacuumAllTables`; plain `VACUUM` of named tables and `ANALYZE` map to
+/// `Ignored`.
 fn convert_vacuum_stmt(vacuum: &pg_query::protobuf::VacuumStmt) -> Vec<IrNode> {
     let is_full = vacuum.options.iter().any(|n| {
         matches!(
@@ -1030,7 +1700,11 @@ fn convert_vacuum_stmt(vacuum: &pg_query::protobuf::VacuumStmt) -> Vec<IrNode> {
     });
 
     if !is_full {
+        if vacuum.is_vacuumcmd && vacuum.rels.is_empty() {
+            return vec![IrNode::VacuumAllTables];
+        }
         return vec![IrNode::Ignored {
+            kind: StatementKind::Vacuum,
             raw_sql: String::new(),
         }];
     }
@@ -1053,6 +1727,23 @@ fn convert_vacuum_stmt(vacuum: &pg_query::protobuf::VacuumStmt) -> Vec<IrNode> {
         .collect()
 }
 
+/// Convert an `AlterTableMoveAllStmt` (`ALTER ... ALL IN TABLESPACE`) to IR.
+fn convert_move_all_stmt(move_all: &pg_query::protobuf::AlterTableMoveAllStmt) -> IrNode {
+    use pg_query::protobuf::ObjectType;
+
+    let object_kind = match move_all.objtype() {
+        ObjectType::ObjectIndex => "INDEX",
+        ObjectType::ObjectMatview => "MATERIALIZED VIEW",
+        _ => "TABLE",
+    };
+    IrNode::MoveAllInTablespace {
+        object_kind: object_kind.to_string(),
+        tablespace: move_all.orig_tablespacename.clone(),
+        new_tablespace: move_all.new_tablespacename.clone(),
+        owned_by: role_spec_names(&move_all.roles),
+    }
+}
+
 /// Convert a `ReindexStmt` to IR.
 ///
 /// `REINDEX` without `CONCURRENTLY` acquires ACCESS EXCLUSIVE lock on the
@@ -1124,11 +1815,13 @@ fn extract_all_names_from_drop_objects(objects: &[pg_query::protobuf::Node]) ->
         .collect()
 }
 
-/// Extract schema names from `DropStmt.objects[]` for `DROP SCHEMA`.
+/// Extract object names from `DropStmt.objects[]` for `DROP SCHEMA` and
+/// `DROP EXTENSION`.
 ///
 /// Unlike tables/indexes (where objects are wrapped in `List` nodes), schema
-/// names are stored as bare `String` nodes directly in the objects array.
-fn extract_schema_names_from_drop_objects(objects: &[pg_query::protobuf::Node]) -> Vec<String> {
+/// and extension names are stored as bare `String` nodes directly in the
+/// objects array.
+fn extract_bare_names_from_drop_objects(objects: &[pg_query::protobuf::Node]) -> Vec<String> {
     objects
         .iter()
         .filter_map(|obj| {
@@ -1220,95 +1913,165 @@ fn extract_string_list(nodes: &[pg_query::protobuf::Node]) -> Vec<String> {
 
 /// Extract column references from a pg_query expression node.
 ///
-/// Recursively walks the AST collecting `ColumnRef` field names. For `ColumnRef`
-/// nodes, the last `String` field is taken as the column name (earlier fields
-/// are schema/table qualifiers). Constant nodes are skipped.
-///
-/// Covers: `ColumnRef`, `FuncCall`, `TypeCast`, `A_Expr`, `BoolExpr`,
-/// `CaseExpr`, `CaseWhen`, `CoalesceExpr`, `NullTest`, `MinMaxExpr`.
+/// Walks the expression with [`walk_expr`] collecting `ColumnRef` field names.
+/// For `ColumnRef` nodes, the last `String` field is taken as the column name
+/// (earlier fields are schema/table qualifiers).
 fn extract_column_refs(node: &pg_query::protobuf::Node) -> Vec<String> {
     let mut refs = Vec::new();
-    walk_node_for_column_refs(node, &mut refs);
+    walk_expr(node, &mut |inner| {
+        // Take the last String field as the column name.
+        // Earlier fields are schema/table qualifiers.
+        if let NodeEnum::ColumnRef(cr) = inner
+            && let Some(last) = cr.fields.last()
+            && let Some(NodeEnum::String(s)) = &last.node
+        {
+            refs.push(s.sval.clone());
+        }
+    });
     refs.sort();
     refs.dedup();
     refs
 }
 
-/// Recursive helper for [`extract_column_refs`].
-fn walk_node_for_column_refs(node: &pg_query::protobuf::Node, refs: &mut Vec<String>) {
-    let Some(inner) = &node.node else {
-        return;
+/// Summarize the columns, subqueries, and functions a CHECK expression uses.
+fn check_expr_deps(node: &pg_query::protobuf::Node) -> CheckExprDeps {
+    let mut deps = CheckExprDeps {
+        columns: extract_column_refs(node),
+        ..CheckExprDeps::default()
     };
-
-    match inner {
-        NodeEnum::ColumnRef(cr) => {
-            // Take the last String field as the column name.
-            // Earlier fields are schema/table qualifiers.
-            if let Some(last) = cr.fields.last()
-                && let Some(NodeEnum::String(s)) = &last.node
-            {
-                refs.push(s.sval.clone());
-            }
-        }
+    walk_expr(node, &mut |inner| match inner {
+        NodeEnum::SubLink(_) => deps.has_subquery = true,
         NodeEnum::FuncCall(fc) => {
-            for arg in &fc.args {
-                walk_node_for_column_refs(arg, refs);
+            if let Some(name) = fc
+                .funcname
+                .iter()
+                .rev()
+                .find_map(|n| match n.node.as_ref() {
+                    Some(NodeEnum::String(s)) => Some(s.sval.to_lowercase()),
+                    _ => None,
+                })
+                && !deps.functions.contains(&name)
+            {
+                deps.functions.push(name);
             }
         }
-        NodeEnum::TypeCast(tc) => {
-            if let Some(arg) = &tc.arg {
-                walk_node_for_column_refs(arg, refs);
+        NodeEnum::SqlvalueFunction(_) => {
+            let wrapper = pg_query::protobuf::Node {
+                node: Some(inner.clone()),
+            };
+            let value = deparse_node(&wrapper).to_uppercase();
+            if !deps.session_values.contains(&value) {
+                deps.session_values.push(value);
             }
         }
         NodeEnum::AExpr(expr) => {
-            if let Some(lexpr) = &expr.lexpr {
-                walk_node_for_column_refs(lexpr, refs);
-            }
-            if let Some(rexpr) = &expr.rexpr {
-                walk_node_for_column_refs(rexpr, refs);
+            if let Some(list) = literal_list(expr) {
+                deps.literal_lists.push(list);
             }
         }
-        NodeEnum::BoolExpr(be) => {
-            for arg in &be.args {
-                walk_node_for_column_refs(arg, refs);
-            }
-        }
-        NodeEnum::CaseExpr(ce) => {
-            if let Some(arg) = &ce.arg {
-                walk_node_for_column_refs(arg, refs);
-            }
-            for when in &ce.args {
-                walk_node_for_column_refs(when, refs);
-            }
-            if let Some(def) = &ce.defresult {
-                walk_node_for_column_refs(def, refs);
-            }
-        }
-        NodeEnum::CaseWhen(cw) => {
-            if let Some(expr) = &cw.expr {
-                walk_node_for_column_refs(expr, refs);
-            }
-            if let Some(result) = &cw.result {
-                walk_node_for_column_refs(result, refs);
-            }
-        }
-        NodeEnum::CoalesceExpr(ce) => {
-            for arg in &ce.args {
-                walk_node_for_column_refs(arg, refs);
-            }
-        }
-        NodeEnum::NullTest(nt) => {
-            if let Some(arg) = &nt.arg {
-                walk_node_for_column_refs(arg, refs);
-            }
-        }
-        NodeEnum::MinMaxExpr(mm) => {
-            for arg in &mm.args {
-                walk_node_for_column_refs(arg, refs);
-            }
-        }
-        // Constants and other nodes — no column references.
         _ => {}
+    });
+    deps
+}
+
+/// The column and values of `column IN (literal, ...)` or
+/// `column = ANY (ARRAY[literal, ...])`, the form PostgreSQL deparses an IN
+/// list to. `NOT IN`, non-literal elements, and an expression on the left
+/// side yield `None`. Casts on either side are looked through.
+fn literal_list(expr: &pg_query::protobuf::AExpr) -> Option<LiteralList> {
+    use pg_query::protobuf::AExprKind;
+
+    let is_equality = expr
+        .name
+        .iter()
+        .any(|n| matches!(n.node.as_ref(), Some(NodeEnum::String(s)) if s.sval == "="));
+    if !is_equality {
+        return None;
+    }
+    let elements = match (expr.kind(), without_casts(expr.rexpr.as_deref()?)?) {
+        (AExprKind::AexprIn, NodeEnum::List(list)) => &list.items,
+        (AExprKind::AexprOpAny, NodeEnum::AArrayExpr(array)) => &array.elements,
+        _ => return None,
+    };
+    let NodeEnum::ColumnRef(cr) = without_casts(expr.lexpr.as_deref()?)? else {
+        return None;
+    };
+    let Some(NodeEnum::String(column)) = cr.fields.last().and_then(|f| f.node.as_ref()) else {
+        return None;
+    };
+    let values = elements
+        .iter()
+        .map(|element| match without_casts(element)? {
+            NodeEnum::AConst(ac) => match ac.val.as_ref()? {
+                pg_query::protobuf::a_const::Val::Ival(i) => Some(i.ival.to_string()),
+                pg_query::protobuf::a_const::Val::Sval(s) => Some(s.sval.clone()),
+                pg_query::protobuf::a_const::Val::Fval(f) => Some(f.fval.clone()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(LiteralList {
+        column: column.sval.clone(),
+        values,
+    })
+}
+
+/// The node under any number of `TypeCast`s (`'paid'::text`).
+fn without_casts(mut node: &pg_query::protobuf::Node) -> Option<&NodeEnum> {
+    loop {
+        match node.node.as_ref()? {
+            NodeEnum::TypeCast(tc) => node = tc.arg.as_deref()?,
+            inner => return Some(inner),
+        }
+    }
+}
+
+/// Visit every node of an expression tree, parents before children.
+///
+/// Descends into `FuncCall`, `TypeCast`, `A_Expr`, `BoolExpr`, `CaseExpr`,
+/// `CaseWhen`, `CoalesceExpr`, `NullTest`, `BooleanTest`, `MinMaxExpr`,
+/// `A_ArrayExpr`, and the test expression of a `SubLink` (not its subselect).
+fn walk_expr(node: &pg_query::protobuf::Node, visit: &mut dyn FnMut(&NodeEnum)) {
+    let Some(inner) = &node.node else {
+        return;
+    };
+    visit(inner);
+
+    let children: Vec<&pg_query::protobuf::Node> = match inner {
+        NodeEnum::FuncCall(fc) => fc.args.iter().collect(),
+        NodeEnum::TypeCast(tc) => tc.arg.as_deref().into_iter().collect(),
+        NodeEnum::AExpr(expr) => expr
+            .lexpr
+            .as_deref()
+            .into_iter()
+            .chain(expr.rexpr.as_deref())
+            .collect(),
+        NodeEnum::BoolExpr(be) => be.args.iter().collect(),
+        NodeEnum::CaseExpr(ce) => ce
+            .arg
+            .as_deref()
+            .into_iter()
+            .chain(&ce.args)
+            .chain(ce.defresult.as_deref())
+            .collect(),
+        NodeEnum::CaseWhen(cw) => cw
+            .expr
+            .as_deref()
+            .into_iter()
+            .chain(cw.result.as_deref())
+            .collect(),
+        NodeEnum::CoalesceExpr(ce) => ce.args.iter().collect(),
+        NodeEnum::NullTest(nt) => nt.arg.as_deref().into_iter().collect(),
+        NodeEnum::BooleanTest(bt) => bt.arg.as_deref().into_iter().collect(),
+        NodeEnum::MinMaxExpr(mm) => mm.args.iter().collect(),
+        NodeEnum::AArrayExpr(ae) => ae.elements.iter().collect(),
+        NodeEnum::SubLink(sl) => sl.testexpr.as_deref().into_iter().collect(),
+        // Constants and other nodes — no children we track.
+        _ => Vec::new(),
+    };
+    for child in children {
+        walk_expr(child, visit);
     }
 }
 
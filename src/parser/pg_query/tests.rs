@@ -1,4 +1,5 @@
 use super::*;
+use crate::parser::ir::TypeKind;
 use rstest::rstest;
 
 /// Helper: parse a CREATE INDEX with the given expression and return the
@@ -20,39 +21,43 @@ fn expr_index_refs(expr: &str) -> Vec<String> {
 }
 
 #[rstest]
-#[case::grant("GRANT SELECT ON orders TO readonly;")]
-#[case::comment_on("COMMENT ON TABLE orders IS 'Order table';")]
-#[case::alter_index_rename("ALTER INDEX idx_foo RENAME TO idx_bar;")]
-#[case::drop_view("DROP VIEW my_view;")]
-#[case::drop_sequence("DROP SEQUENCE my_seq;")]
-#[case::drop_type("DROP TYPE my_type;")]
-#[case::create_view("CREATE VIEW v AS SELECT 1;")]
+#[case::comment_on_function("COMMENT ON FUNCTION f(int) IS 'Helper';", StatementKind::Comment)]
+#[case::drop_view("DROP VIEW my_view;", StatementKind::Drop)]
+#[case::drop_type("DROP TYPE my_type;", StatementKind::Drop)]
+#[case::create_view("CREATE VIEW v AS SELECT 1;", StatementKind::CreateView)]
 #[case::create_function(
-    "CREATE FUNCTION add(a int, b int) RETURNS int AS 'SELECT a + b' LANGUAGE sql;"
+    "CREATE FUNCTION add(a int, b int) RETURNS int AS 'SELECT a + b' LANGUAGE sql;",
+    StatementKind::CreateFunction
 )]
-#[case::create_sequence("CREATE SEQUENCE order_seq START 1;")]
-#[case::create_extension("CREATE EXTENSION IF NOT EXISTS pgcrypto;")]
-#[case::create_type("CREATE TYPE mood AS ENUM ('sad', 'ok', 'happy');")]
-#[case::select("SELECT * FROM foo;")]
-#[case::set("SET search_path TO myschema;")]
-#[case::revoke("REVOKE SELECT ON orders FROM readonly;")]
-#[case::create_trigger(
-    "CREATE TRIGGER trg BEFORE INSERT ON foo FOR EACH ROW EXECUTE FUNCTION bar();"
+#[case::create_type(
+    "CREATE TYPE mood AS ENUM ('sad', 'ok', 'happy');",
+    StatementKind::CreateType
+)]
+#[case::select("SELECT * FROM foo;", StatementKind::Select)]
+#[case::select_into("SELECT * INTO foo_copy FROM foo;", StatementKind::CreateTableAs)]
+#[case::set("SET search_path TO myschema;", StatementKind::Set)]
+#[case::drop_function("DROP FUNCTION my_func(int);", StatementKind::Drop)]
+#[case::create_schema_authorization(
+    "CREATE SCHEMA AUTHORIZATION app_owner;",
+    StatementKind::CreateSchema
+)]
+#[case::alter_sequence(
+    "ALTER SEQUENCE order_seq RESTART WITH 1000;",
+    StatementKind::AlterSequence
 )]
-#[case::alter_sequence_rename("ALTER SEQUENCE my_seq RENAME TO new_seq;")]
-#[case::drop_function("DROP FUNCTION my_func(int);")]
-#[case::create_schema("CREATE SCHEMA myschema;")]
-#[case::alter_sequence("ALTER SEQUENCE order_seq RESTART WITH 1000;")]
-#[case::vacuum_plain("VACUUM orders;")]
-#[case::alter_index_set("ALTER INDEX idx_foo SET (fillfactor = 70);")]
-fn test_parse_ignored_statements(#[case] sql: &str) {
+#[case::vacuum_plain("VACUUM orders;", StatementKind::Vacuum)]
+#[case::alter_index_set(
+    "ALTER INDEX idx_foo SET (fillfactor = 70);",
+    StatementKind::AlterIndex
+)]
+#[case::begin("BEGIN;", StatementKind::Transaction)]
+fn test_parse_ignored_statements(#[case] sql: &str, #[case] expected: StatementKind) {
     let nodes = parse_sql(sql);
     assert_eq!(nodes.len(), 1, "Expected exactly 1 node for: {sql}");
-    assert!(
-        matches!(nodes[0].node, IrNode::Ignored { .. }),
-        "Expected IrNode::Ignored for: {sql}, got: {:?}",
-        nodes[0].node
-    );
+    match &nodes[0].node {
+        IrNode::Ignored { kind, .. } => assert_eq!(*kind, expected, "for: {sql}"),
+        other => panic!("Expected IrNode::Ignored for: {sql}, got: {:?}", other),
+    }
 }
 
 #[test]
@@ -98,11 +103,11 @@ fn test_parse_create_table() {
             assert_eq!(ct.name, QualifiedName::unqualified("orders"));
             assert_eq!(ct.columns.len(), 2);
             assert_eq!(ct.columns[0].name, "id");
-            assert_eq!(ct.columns[0].type_name.name, "int4");
+            assert_eq!(&*ct.columns[0].type_name.name, "int4");
             assert!(ct.columns[0].is_inline_pk);
             assert!(!ct.columns[0].nullable);
             assert_eq!(ct.columns[1].name, "status");
-            assert_eq!(ct.columns[1].type_name.name, "text");
+            assert_eq!(&*ct.columns[1].type_name.name, "text");
             assert!(!ct.columns[1].nullable);
             assert!(!ct.if_not_exists);
         }
@@ -129,7 +134,7 @@ fn test_parse_serial_type() {
     let nodes = parse_sql(sql);
     match &nodes[0].node {
         IrNode::CreateTable(ct) => {
-            assert_eq!(ct.columns[0].type_name.name, "int4");
+            assert_eq!(&*ct.columns[0].type_name.name, "int4");
             assert!(
                 matches!(ct.columns[0].default_expr, Some(DefaultExpr::FunctionCall { ref name, .. }) if name == "nextval")
             );
@@ -144,7 +149,7 @@ fn test_parse_bigserial_type() {
     let nodes = parse_sql(sql);
     match &nodes[0].node {
         IrNode::CreateTable(ct) => {
-            assert_eq!(ct.columns[0].type_name.name, "int8");
+            assert_eq!(&*ct.columns[0].type_name.name, "int8");
             assert!(ct.columns[0].default_expr.is_some());
         }
         other => panic!("Expected CreateTable, got: {:?}", other),
@@ -157,7 +162,7 @@ fn test_parse_smallserial_type() {
     let nodes = parse_sql(sql);
     match &nodes[0].node {
         IrNode::CreateTable(ct) => {
-            assert_eq!(ct.columns[0].type_name.name, "int2");
+            assert_eq!(&*ct.columns[0].type_name.name, "int2");
             assert!(
                 matches!(ct.columns[0].default_expr, Some(DefaultExpr::FunctionCall { ref name, .. }) if name == "nextval")
             );
@@ -172,7 +177,7 @@ fn test_parse_varchar_with_modifier() {
     let nodes = parse_sql(sql);
     match &nodes[0].node {
         IrNode::CreateTable(ct) => {
-            assert_eq!(ct.columns[0].type_name.name, "varchar");
+            assert_eq!(&*ct.columns[0].type_name.name, "varchar");
             assert_eq!(ct.columns[0].type_name.modifiers, vec![100]);
         }
         other => panic!("Expected CreateTable, got: {:?}", other),
@@ -185,13 +190,50 @@ fn test_parse_numeric_with_modifiers() {
     let nodes = parse_sql(sql);
     match &nodes[0].node {
         IrNode::CreateTable(ct) => {
-            assert_eq!(ct.columns[0].type_name.name, "numeric");
+            assert_eq!(&*ct.columns[0].type_name.name, "numeric");
             assert_eq!(ct.columns[0].type_name.modifiers, vec![10, 2]);
         }
         other => panic!("Expected CreateTable, got: {:?}", other),
     }
 }
 
+#[test]
+fn test_parse_array_types() {
+    let sql = "CREATE TABLE t (tags text[], grid int[][], ids bigint ARRAY, codes varchar(10)[3]);";
+    let nodes = parse_sql(sql);
+    match &nodes[0].node {
+        IrNode::CreateTable(ct) => {
+            let types: Vec<String> = ct.columns.iter().map(|c| c.type_name.to_string()).collect();
+            assert_eq!(types, ["text[]", "int4[][]", "int8[]", "varchar(10)[]"]);
+            assert_eq!(&*ct.columns[0].type_name.name, "text");
+            assert!(ct.columns[0].type_name.is_array());
+        }
+        other => panic!("Expected CreateTable, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_range_and_user_defined_types() {
+    let sql = "CREATE TABLE t (during tstzrange, attrs hstore, addr public.address, n int);";
+    let nodes = parse_sql(sql);
+    match &nodes[0].node {
+        IrNode::CreateTable(ct) => {
+            let kinds: Vec<TypeKind> = ct.columns.iter().map(|c| c.type_name.kind()).collect();
+            assert_eq!(
+                kinds,
+                [
+                    TypeKind::Range,
+                    TypeKind::UserDefined,
+                    TypeKind::UserDefined,
+                    TypeKind::Base
+                ]
+            );
+            assert!(!ct.columns[0].type_name.is_array());
+        }
+        other => panic!("Expected CreateTable, got: {:?}", other),
+    }
+}
+
 #[test]
 fn test_parse_type_canonicalization() {
     // All integer aliases should map to int4
@@ -203,7 +245,11 @@ fn test_parse_type_canonicalization() {
         let nodes = parse_sql(sql);
         match &nodes[0].node {
             IrNode::CreateTable(ct) => {
-                assert_eq!(ct.columns[0].type_name.name, "int4", "Failed for: {}", sql);
+                assert_eq!(
+                    &*ct.columns[0].type_name.name, "int4",
+                    "Failed for: {}",
+                    sql
+                );
             }
             other => panic!("Expected CreateTable for {}, got: {:?}", sql, other),
         }
@@ -362,7 +408,7 @@ fn test_parse_alter_table_add_column() {
             match &at.actions[0] {
                 AlterTableAction::AddColumn(col) => {
                     assert_eq!(col.name, "status");
-                    assert_eq!(col.type_name.name, "text");
+                    assert_eq!(&*col.type_name.name, "text");
                     assert!(!col.nullable);
                     assert_eq!(
                         col.default_expr,
@@ -438,7 +484,7 @@ fn test_parse_alter_table_alter_column_type() {
                     old_type,
                 } => {
                     assert_eq!(column_name, "status");
-                    assert_eq!(new_type.name, "varchar");
+                    assert_eq!(&*new_type.name, "varchar");
                     assert_eq!(new_type.modifiers, vec![100]);
                     assert!(old_type.is_none());
                 }
@@ -577,6 +623,142 @@ fn test_parse_drop_schema_multiple_schemas() {
     }
 }
 
+#[rstest]
+#[case::plain("CREATE EXTENSION pgcrypto;", "pgcrypto", false, None)]
+#[case::if_not_exists("CREATE EXTENSION IF NOT EXISTS pgcrypto;", "pgcrypto", true, None)]
+#[case::quoted_with_schema(
+    "CREATE EXTENSION \"uuid-ossp\" SCHEMA extensions;",
+    "uuid-ossp",
+    false,
+    Some("extensions")
+)]
+fn test_parse_create_extension(
+    #[case] sql: &str,
+    #[case] expected_name: &str,
+    #[case] expected_if_not_exists: bool,
+    #[case] expected_schema: Option<&str>,
+) {
+    let nodes = parse_sql(sql);
+    assert_eq!(nodes.len(), 1);
+    match &nodes[0].node {
+        IrNode::CreateExtension(ce) => {
+            assert_eq!(ce.name, expected_name);
+            assert_eq!(ce.if_not_exists, expected_if_not_exists);
+            assert_eq!(ce.schema.as_deref(), expected_schema);
+        }
+        other => panic!("Expected CreateExtension, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_drop_extension_multiple() {
+    let sql = "DROP EXTENSION IF EXISTS hstore, \"uuid-ossp\" CASCADE;";
+    let nodes = parse_sql(sql);
+    assert_eq!(nodes.len(), 2);
+    match &nodes[0].node {
+        IrNode::DropExtension(de) => {
+            assert_eq!(de.name, "hstore");
+            assert!(de.if_exists);
+            assert!(de.cascade);
+        }
+        other => panic!("Expected DropExtension, got: {:?}", other),
+    }
+    match &nodes[1].node {
+        IrNode::DropExtension(de) => assert_eq!(de.name, "uuid-ossp"),
+        other => panic!("Expected DropExtension, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_sequence_statements() {
+    let sql = "CREATE SEQUENCE IF NOT EXISTS billing.invoice_seq START 1;\n\
+               ALTER SEQUENCE billing.invoice_seq RENAME TO invoice_no_seq;\n\
+               DROP SEQUENCE IF EXISTS billing.invoice_no_seq, order_seq;";
+    let nodes: Vec<IrNode> = parse_sql(sql).into_iter().map(|l| l.node).collect();
+    assert_eq!(
+        nodes,
+        vec![
+            IrNode::CreateSequence {
+                name: QualifiedName::qualified("billing", "invoice_seq"),
+                if_not_exists: true,
+                owned_by: None,
+            },
+            IrNode::RenameSequence {
+                name: QualifiedName::qualified("billing", "invoice_seq"),
+                new_name: "invoice_no_seq".to_string(),
+            },
+            IrNode::DropSequence {
+                name: QualifiedName::qualified("billing", "invoice_no_seq"),
+                if_exists: true,
+            },
+            IrNode::DropSequence {
+                name: QualifiedName::unqualified("order_seq"),
+                if_exists: true,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_parse_create_schema() {
+    let sql = "CREATE SCHEMA billing;\n\
+               CREATE SCHEMA IF NOT EXISTS reporting AUTHORIZATION analyst;";
+    let nodes: Vec<IrNode> = parse_sql(sql).into_iter().map(|l| l.node).collect();
+    assert_eq!(
+        nodes,
+        vec![
+            IrNode::CreateSchema {
+                name: "billing".to_string(),
+                if_not_exists: false,
+            },
+            IrNode::CreateSchema {
+                name: "reporting".to_string(),
+                if_not_exists: true,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_parse_sequence_owned_by() {
+    let sql = "CREATE SEQUENCE invoice_seq OWNED BY billing.invoices.invoice_no;\n\
+               ALTER SEQUENCE invoice_seq OWNED BY NONE;\n\
+               ALTER SEQUENCE order_seq OWNED BY orders.id;\n\
+               ALTER SEQUENCE order_seq RESTART WITH 100;";
+    let nodes: Vec<IrNode> = parse_sql(sql).into_iter().map(|l| l.node).collect();
+    assert_eq!(
+        nodes[..3],
+        [
+            IrNode::CreateSequence {
+                name: QualifiedName::unqualified("invoice_seq"),
+                if_not_exists: false,
+                owned_by: Some(SequenceOwner {
+                    table: QualifiedName::qualified("billing", "invoices"),
+                    column: "invoice_no".to_string(),
+                }),
+            },
+            IrNode::SetSequenceOwner {
+                name: QualifiedName::unqualified("invoice_seq"),
+                owned_by: None,
+            },
+            IrNode::SetSequenceOwner {
+                name: QualifiedName::unqualified("order_seq"),
+                owned_by: Some(SequenceOwner {
+                    table: QualifiedName::unqualified("orders"),
+                    column: "id".to_string(),
+                }),
+            },
+        ]
+    );
+    assert!(matches!(
+        nodes[3],
+        IrNode::Ignored {
+            kind: StatementKind::AlterSequence,
+            ..
+        }
+    ));
+}
+
 #[test]
 fn test_parse_do_block_as_unparseable() {
     let sql = "DO $$ BEGIN RAISE NOTICE 'hello'; END $$;";
@@ -645,6 +827,16 @@ fn test_source_span_statement_after_leading_comment() {
     );
 }
 
+#[test]
+fn test_raw_sql_retained_per_statement() {
+    let sql = "-- backfill\nCREATE INDEX idx_foo ON foo (id);\n\nDROP TABLE bar, baz;\n";
+    let nodes = parse_sql(sql);
+    assert_eq!(nodes.len(), 3);
+    assert_eq!(&*nodes[0].raw_sql, "CREATE INDEX idx_foo ON foo (id)");
+    assert_eq!(&*nodes[1].raw_sql, "DROP TABLE bar, baz");
+    assert_eq!(&*nodes[2].raw_sql, "DROP TABLE bar, baz");
+}
+
 #[test]
 fn test_source_span_statement_after_multiple_comment_lines() {
     // Multiple comment lines before the statement — all must be skipped.
@@ -769,7 +961,7 @@ fn test_parse_alter_add_column_serial() {
     match &nodes[0].node {
         IrNode::AlterTable(at) => match &at.actions[0] {
             AlterTableAction::AddColumn(col) => {
-                assert_eq!(col.type_name.name, "int4");
+                assert_eq!(&*col.type_name.name, "int4");
                 assert!(matches!(
                     col.default_expr,
                     Some(DefaultExpr::FunctionCall { ref name, .. }) if name == "nextval"
@@ -781,6 +973,23 @@ fn test_parse_alter_add_column_serial() {
     }
 }
 
+#[rstest]
+#[case::always("GENERATED ALWAYS AS IDENTITY", true)]
+#[case::by_default("GENERATED BY DEFAULT AS IDENTITY", true)]
+#[case::plain("NOT NULL", false)]
+fn test_parse_add_column_identity(#[case] constraint: &str, #[case] is_identity: bool) {
+    let sql = format!("ALTER TABLE t ADD COLUMN id bigint {constraint};");
+    let nodes = parse_sql(&sql);
+    let IrNode::AlterTable(at) = &nodes[0].node else {
+        panic!("Expected AlterTable, got: {:?}", nodes[0].node);
+    };
+    let AlterTableAction::AddColumn(col) = &at.actions[0] else {
+        panic!("Expected AddColumn, got: {:?}", at.actions[0]);
+    };
+    assert_eq!(col.is_identity, is_identity);
+    assert!(!col.is_serial);
+}
+
 #[test]
 fn test_parse_boolean_default() {
     let sql = "CREATE TABLE t (active bool DEFAULT TRUE);";
@@ -1004,6 +1213,59 @@ fn test_parse_add_check_not_valid() {
     }
 }
 
+#[rstest]
+#[case::plain("CHECK (amount > 0)", &["amount"], false, &[], &[])]
+#[case::subquery(
+    "CHECK (customer_id IN (SELECT id FROM customers))",
+    &["customer_id"],
+    true,
+    &[],
+    &[]
+)]
+#[case::exists_in_case(
+    "CHECK (CASE WHEN kind = 'x' THEN EXISTS (SELECT 1 FROM kinds) ELSE true END)",
+    &["kind"],
+    true,
+    &[],
+    &[]
+)]
+#[case::functions(
+    "CHECK (created_at <= now() AND length(code) = 3)",
+    &["code", "created_at"],
+    false,
+    &["now", "length"],
+    &[]
+)]
+#[case::session_value("CHECK (owner = CURRENT_USER)", &["owner"], false, &[], &["CURRENT_USER"])]
+#[case::qualified_and_quoted(
+    "CHECK (orders.amount > 0 AND \"Status\" <> 'x')",
+    &["Status", "amount"],
+    false,
+    &[],
+    &[]
+)]
+fn test_parse_check_expr_deps(
+    #[case] check: &str,
+    #[case] columns: &[&str],
+    #[case] has_subquery: bool,
+    #[case] functions: &[&str],
+    #[case] session_values: &[&str],
+) {
+    let sql = format!("ALTER TABLE orders ADD CONSTRAINT chk {check};");
+    let nodes = parse_sql(&sql);
+    let IrNode::AlterTable(at) = &nodes[0].node else {
+        panic!("Expected AlterTable, got: {:?}", nodes[0].node);
+    };
+    let AlterTableAction::AddConstraint(TableConstraint::Check { deps, .. }) = &at.actions[0]
+    else {
+        panic!("Expected AddConstraint Check, got: {:?}", at.actions[0]);
+    };
+    assert_eq!(deps.columns, columns);
+    assert_eq!(deps.has_subquery, has_subquery);
+    assert_eq!(deps.functions, functions);
+    assert_eq!(deps.session_values, session_values);
+}
+
 #[test]
 fn test_parse_add_check_without_not_valid() {
     let sql = "ALTER TABLE orders ADD CONSTRAINT chk_amount CHECK (amount > 0);";
@@ -1058,6 +1320,46 @@ fn test_parse_rename_column() {
 
 // Schema-qualified DROP INDEX: see test_parse_drop_index_flags rstest.
 
+#[rstest]
+#[case::comment_on_table(
+    "COMMENT ON TABLE billing.orders IS 'Orders';",
+    "COMMENT ON",
+    Some("billing")
+)]
+#[case::comment_on_column(
+    "COMMENT ON COLUMN orders.status IS 'Lifecycle state';",
+    "COMMENT ON",
+    None
+)]
+#[case::comment_on_constraint(
+    "COMMENT ON CONSTRAINT orders_pkey ON billing.orders IS 'Key';",
+    "COMMENT ON",
+    Some("billing")
+)]
+#[case::comment_on_trigger("COMMENT ON TRIGGER trg ON orders IS 'Audit';", "COMMENT ON", None)]
+#[case::create_trigger(
+    "CREATE TRIGGER trg BEFORE INSERT ON billing.orders FOR EACH ROW EXECUTE FUNCTION bar();",
+    "CREATE TRIGGER",
+    Some("billing")
+)]
+#[case::drop_trigger("DROP TRIGGER trg ON billing.orders;", "DROP TRIGGER", Some("billing"))]
+fn test_parse_table_attachment(
+    #[case] sql: &str,
+    #[case] expected_statement: &str,
+    #[case] expected_schema: Option<&str>,
+) {
+    let nodes = parse_sql(sql);
+    assert_eq!(nodes.len(), 1, "Expected exactly 1 node for: {sql}");
+    match &nodes[0].node {
+        IrNode::TableAttachment { statement, table } => {
+            assert_eq!(statement, expected_statement, "for: {sql}");
+            assert_eq!(table.name, "orders", "for: {sql}");
+            assert_eq!(table.schema.as_deref(), expected_schema, "for: {sql}");
+        }
+        other => panic!("Expected TableAttachment for: {sql}, got: {:?}", other),
+    }
+}
+
 #[rstest]
 #[case::temp("CREATE TEMP TABLE scratch (id int);", TablePersistence::Temporary)]
 #[case::temporary(
@@ -1079,6 +1381,28 @@ fn test_table_persistence(#[case] sql: &str, #[case] expected: TablePersistence)
     }
 }
 
+#[rstest]
+#[case::none("CREATE TEMP TABLE scratch (id int);", OnCommit::PreserveRows)]
+#[case::drop("CREATE TEMP TABLE scratch (id int) ON COMMIT DROP;", OnCommit::Drop)]
+#[case::delete_rows(
+    "CREATE TEMP TABLE scratch (id int) ON COMMIT DELETE ROWS;",
+    OnCommit::DeleteRows
+)]
+#[case::preserve_rows(
+    "CREATE TEMP TABLE scratch (id int) ON COMMIT PRESERVE ROWS;",
+    OnCommit::PreserveRows
+)]
+fn test_create_table_on_commit(#[case] sql: &str, #[case] expected: OnCommit) {
+    let nodes = parse_sql(sql);
+    match &nodes[0].node {
+        IrNode::CreateTable(ct) => {
+            assert_eq!(ct.on_commit, expected);
+            assert_eq!(ct.persistence, TablePersistence::Temporary);
+        }
+        other => panic!("Expected CreateTable, got: {:?}", other),
+    }
+}
+
 #[test]
 fn test_parse_create_table_if_not_exists() {
     let sql = "CREATE TABLE IF NOT EXISTS orders (id int);";
@@ -1151,6 +1475,26 @@ fn test_parse_truncate_multi_table() {
     assert_eq!(names, vec!["t1", "t2", "t3"]);
 }
 
+#[test]
+fn test_parse_truncate_restart_identity_only() {
+    let sql = "TRUNCATE ONLY t1, t2 RESTART IDENTITY;";
+    let nodes = parse_sql(sql);
+    let flags: Vec<(&str, bool, bool)> = nodes
+        .iter()
+        .map(|n| match &n.node {
+            IrNode::TruncateTable(tt) => (tt.name.name.as_str(), tt.restart_identity, tt.only),
+            other => panic!("Expected TruncateTable, got: {:?}", other),
+        })
+        .collect();
+    assert_eq!(flags, vec![("t1", true, true), ("t2", true, false)]);
+
+    let nodes = parse_sql("TRUNCATE foo CONTINUE IDENTITY;");
+    match &nodes[0].node {
+        IrNode::TruncateTable(tt) => assert!(!tt.restart_identity && !tt.only),
+        other => panic!("Expected TruncateTable, got: {:?}", other),
+    }
+}
+
 #[test]
 fn test_parse_drop_table_multi() {
     let sql = "DROP TABLE t1, t2;";
@@ -1407,6 +1751,19 @@ fn test_parse_update() {
     match &nodes[0].node {
         IrNode::UpdateTable(ut) => {
             assert_eq!(ut.table_name.name, "foo");
+            assert_eq!(ut.set_columns, vec!["bar".to_string()]);
+        }
+        other => panic!("Expected UpdateTable, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_update_multiple_set_columns() {
+    let sql = "UPDATE foo SET bar = 1, baz = now();";
+    let nodes = parse_sql(sql);
+    match &nodes[0].node {
+        IrNode::UpdateTable(ut) => {
+            assert_eq!(ut.set_columns, vec!["bar".to_string(), "baz".to_string()]);
         }
         other => panic!("Expected UpdateTable, got: {:?}", other),
     }
@@ -1442,19 +1799,211 @@ fn test_parse_alter_table_enable_trigger_as_other() {
 }
 
 #[test]
-fn test_parse_alter_table_owner_as_other() {
+fn test_parse_alter_table_owner() {
     let sql = "ALTER TABLE foo OWNER TO new_owner;";
     let nodes = parse_sql(sql);
     assert_eq!(nodes.len(), 1);
-    // OWNER TO is parsed as AlterTableStmt by pg_query but maps to Other action
     match &nodes[0].node {
         IrNode::AlterTable(at) => {
             assert_eq!(at.actions.len(), 1);
-            assert!(matches!(at.actions[0], AlterTableAction::Other { .. }));
+            match &at.actions[0] {
+                AlterTableAction::ChangeOwner { new_owner } => assert_eq!(new_owner, "new_owner"),
+                other => panic!("Expected ChangeOwner, got: {:?}", other),
+            }
+        }
+        other => panic!("Expected AlterTable, got: {:?}", other),
+    }
+}
+
+#[rstest]
+#[case::logged("ALTER TABLE events SET LOGGED;", AlterTableAction::SetLogged)]
+#[case::unlogged("ALTER TABLE events SET UNLOGGED;", AlterTableAction::SetUnlogged)]
+fn test_parse_alter_table_set_persistence(#[case] sql: &str, #[case] expected: AlterTableAction) {
+    let nodes = parse_sql(sql);
+    assert_eq!(nodes.len(), 1);
+    match &nodes[0].node {
+        IrNode::AlterTable(at) => assert_eq!(at.actions, vec![expected]),
+        other => panic!("Expected AlterTable, got: {:?}", other),
+    }
+}
+
+#[rstest]
+#[case::default(
+    "ALTER TABLE events REPLICA IDENTITY DEFAULT;",
+    ReplicaIdentity::Default
+)]
+#[case::full("ALTER TABLE events REPLICA IDENTITY FULL;", ReplicaIdentity::Full)]
+#[case::nothing(
+    "ALTER TABLE events REPLICA IDENTITY NOTHING;",
+    ReplicaIdentity::Nothing
+)]
+#[case::using_index(
+    "ALTER TABLE events REPLICA IDENTITY USING INDEX events_id_key;",
+    ReplicaIdentity::UsingIndex("events_id_key".to_string())
+)]
+fn test_parse_alter_table_replica_identity(#[case] sql: &str, #[case] expected: ReplicaIdentity) {
+    let nodes = parse_sql(sql);
+    assert_eq!(nodes.len(), 1);
+    match &nodes[0].node {
+        IrNode::AlterTable(at) => assert_eq!(
+            at.actions,
+            vec![AlterTableAction::SetReplicaIdentity(expected)]
+        ),
+        other => panic!("Expected AlterTable, got: {:?}", other),
+    }
+}
+
+#[rstest]
+#[case::grant_table(
+    "GRANT SELECT, INSERT ON orders, billing.invoices TO app_rw;",
+    true,
+    "TABLE",
+    &["orders", "billing.invoices"],
+    &["app_rw"]
+)]
+#[case::revoke_public(
+    "REVOKE ALL ON orders FROM PUBLIC;",
+    false,
+    "TABLE",
+    &["orders"],
+    &["PUBLIC"]
+)]
+#[case::all_tables_in_schema(
+    "GRANT SELECT ON ALL TABLES IN SCHEMA reporting TO analyst;",
+    true,
+    "TABLE",
+    &["reporting"],
+    &["analyst"]
+)]
+#[case::schema("GRANT USAGE ON SCHEMA billing TO app;", true, "SCHEMA", &["billing"], &["app"])]
+#[case::function(
+    "GRANT EXECUTE ON FUNCTION billing.charge(bigint) TO app;",
+    true,
+    "FUNCTION",
+    &["billing.charge"],
+    &["app"]
+)]
+#[case::role_membership("GRANT app_rw TO alice, bob;", true, "ROLE", &["app_rw"], &["alice", "bob"])]
+#[case::default_privileges(
+    "ALTER DEFAULT PRIVILEGES IN SCHEMA billing GRANT SELECT ON TABLES TO analyst;",
+    true,
+    "DEFAULT PRIVILEGES",
+    &[],
+    &["analyst"]
+)]
+fn test_parse_grant(
+    #[case] sql: &str,
+    #[case] expected_is_grant: bool,
+    #[case] expected_kind: &str,
+    #[case] expected_objects: &[&str],
+    #[case] expected_grantees: &[&str],
+) {
+    let nodes = parse_sql(sql);
+    assert_eq!(nodes.len(), 1);
+    match &nodes[0].node {
+        IrNode::Grant(g) => {
+            assert_eq!(g.is_grant, expected_is_grant);
+            assert_eq!(g.object_kind, expected_kind);
+            assert_eq!(g.objects, expected_objects);
+            assert_eq!(g.grantees, expected_grantees);
+        }
+        other => panic!("Expected Grant, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_grant_all_in_schema() {
+    let sql = "GRANT SELECT ON ALL TABLES IN SCHEMA reporting TO analyst;\n\
+               GRANT SELECT ON reporting TO analyst;";
+    let nodes = parse_sql(sql);
+    let all_in_schema: Vec<bool> = nodes
+        .iter()
+        .map(|l| match &l.node {
+            IrNode::Grant(g) => g.all_in_schema,
+            other => panic!("Expected Grant, got: {:?}", other),
+        })
+        .collect();
+    assert_eq!(all_in_schema, vec![true, false]);
+}
+
+#[rstest]
+#[case::schema("ALTER SCHEMA billing OWNER TO billing_owner;", "SCHEMA", "billing")]
+#[case::function(
+    "ALTER FUNCTION billing.charge(bigint) OWNER TO billing_owner;",
+    "FUNCTION",
+    "billing.charge"
+)]
+#[case::sequence(
+    "ALTER SEQUENCE orders_id_seq OWNER TO billing_owner;",
+    "SEQUENCE",
+    "orders_id_seq"
+)]
+#[case::view(
+    "ALTER VIEW active_orders OWNER TO billing_owner;",
+    "VIEW",
+    "active_orders"
+)]
+fn test_parse_alter_owner(
+    #[case] sql: &str,
+    #[case] expected_kind: &str,
+    #[case] expected_name: &str,
+) {
+    let nodes = parse_sql(sql);
+    assert_eq!(nodes.len(), 1);
+    match &nodes[0].node {
+        IrNode::AlterOwner(ao) => {
+            assert_eq!(ao.object_kind, expected_kind);
+            assert_eq!(ao.name, expected_name);
+            assert_eq!(ao.new_owner, "billing_owner");
+        }
+        other => panic!("Expected AlterOwner, got: {:?}", other),
+    }
+}
+
+#[rstest]
+#[case::create_publication(
+    "CREATE PUBLICATION orders_pub FOR TABLE orders, billing.invoices;",
+    "CREATE PUBLICATION",
+    "orders_pub",
+    &["orders", "billing.invoices"]
+)]
+#[case::all_tables("CREATE PUBLICATION all_pub FOR ALL TABLES;", "CREATE PUBLICATION", "all_pub", &[])]
+#[case::alter_publication(
+    "ALTER PUBLICATION orders_pub ADD TABLE refunds;",
+    "ALTER PUBLICATION",
+    "orders_pub",
+    &["refunds"]
+)]
+#[case::drop_publication("DROP PUBLICATION orders_pub;", "DROP PUBLICATION", "orders_pub", &[])]
+#[case::create_subscription(
+    "CREATE SUBSCRIPTION orders_sub CONNECTION 'host=primary' PUBLICATION orders_pub;",
+    "CREATE SUBSCRIPTION",
+    "orders_sub",
+    &[]
+)]
+#[case::alter_subscription(
+    "ALTER SUBSCRIPTION orders_sub REFRESH PUBLICATION;",
+    "ALTER SUBSCRIPTION",
+    "orders_sub",
+    &[]
+)]
+#[case::drop_subscription("DROP SUBSCRIPTION orders_sub;", "DROP SUBSCRIPTION", "orders_sub", &[])]
+fn test_parse_replication_statements(
+    #[case] sql: &str,
+    #[case] expected_statement: &str,
+    #[case] expected_name: &str,
+    #[case] expected_tables: &[&str],
+) {
+    let nodes = parse_sql(sql);
+    assert_eq!(nodes.len(), 1);
+    match &nodes[0].node {
+        IrNode::Replication(rc) => {
+            assert_eq!(rc.statement, expected_statement);
+            assert_eq!(rc.name, expected_name);
+            let tables: Vec<String> = rc.tables.iter().map(|t| t.display_name()).collect();
+            assert_eq!(tables, expected_tables);
         }
-        // Some pg_query versions may route OWNER TO elsewhere, accept Ignored too
-        IrNode::Ignored { .. } => {}
-        other => panic!("Expected AlterTable or Ignored, got: {:?}", other),
+        other => panic!("Expected Replication, got: {:?}", other),
     }
 }
 
@@ -1487,7 +2036,9 @@ fn test_parse_alter_table_drop_constraint() {
             assert_eq!(at.name.name, "orders");
             assert_eq!(at.actions.len(), 1);
             match &at.actions[0] {
-                AlterTableAction::DropConstraint { constraint_name } => {
+                AlterTableAction::DropConstraint {
+                    constraint_name, ..
+                } => {
                     assert_eq!(constraint_name, "fk_customer");
                 }
                 other => panic!("Expected DropConstraint action, got: {:?}", other),
@@ -1517,6 +2068,47 @@ fn test_parse_alter_table_validate_constraint() {
     }
 }
 
+#[test]
+fn test_parse_create_table_storage_params() {
+    let sql =
+        "CREATE TABLE events (id int) WITH (fillfactor = 70, toast.autovacuum_enabled = false);";
+    let nodes = parse_sql(sql);
+    match &nodes[0].node {
+        IrNode::CreateTable(ct) => {
+            assert_eq!(
+                ct.storage_params,
+                vec![
+                    StorageParam::test("fillfactor", "70"),
+                    StorageParam::test("toast.autovacuum_enabled", "false"),
+                ]
+            );
+        }
+        other => panic!("Expected CreateTable, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_alter_table_set_and_reset_storage_params() {
+    let sql = "ALTER TABLE events SET (autovacuum_enabled = off), RESET (fillfactor);";
+    let nodes = parse_sql(sql);
+    match &nodes[0].node {
+        IrNode::AlterTable(at) => {
+            assert_eq!(
+                at.actions,
+                vec![
+                    AlterTableAction::SetStorageParams {
+                        params: vec![StorageParam::test("autovacuum_enabled", "off")],
+                    },
+                    AlterTableAction::ResetStorageParams {
+                        names: vec!["fillfactor".to_string()],
+                    },
+                ]
+            );
+        }
+        other => panic!("Expected AlterTable, got: {:?}", other),
+    }
+}
+
 #[test]
 fn test_parse_alter_table_drop_constraint_if_exists() {
     let sql = "ALTER TABLE orders DROP CONSTRAINT IF EXISTS fk_customer;";
@@ -1527,7 +2119,9 @@ fn test_parse_alter_table_drop_constraint_if_exists() {
             assert_eq!(at.name.name, "orders");
             assert_eq!(at.actions.len(), 1);
             match &at.actions[0] {
-                AlterTableAction::DropConstraint { constraint_name } => {
+                AlterTableAction::DropConstraint {
+                    constraint_name, ..
+                } => {
                     assert_eq!(constraint_name, "fk_customer");
                 }
                 other => panic!("Expected DropConstraint action, got: {:?}", other),
@@ -1590,6 +2184,21 @@ fn test_parse_default_expression() {
     }
 }
 
+#[test]
+fn test_parse_default_column_ref() {
+    let sql = "CREATE TABLE t (status text DEFAULT \"active\");";
+    let nodes = parse_sql(sql);
+    match &nodes[0].node {
+        IrNode::CreateTable(ct) => {
+            assert_eq!(
+                ct.columns[0].default_expr,
+                Some(DefaultExpr::ColumnRef("active".to_string()))
+            );
+        }
+        other => panic!("Expected CreateTable, got: {:?}", other),
+    }
+}
+
 #[test]
 fn test_parse_create_index_without_name() {
     // Anonymous indexes should have index_name == None
@@ -1835,6 +2444,54 @@ fn test_parse_vacuum_full_no_table() {
     }
 }
 
+#[test]
+fn test_parse_vacuum_no_table() {
+    let nodes = parse_sql("VACUUM (ANALYZE);");
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(nodes[0].node, IrNode::VacuumAllTables);
+
+    let nodes = parse_sql("ANALYZE;");
+    assert!(
+        matches!(
+            nodes[0].node,
+            IrNode::Ignored {
+                kind: StatementKind::Vacuum,
+                ..
+            }
+        ),
+        "{:?}",
+        nodes[0].node
+    );
+}
+
+#[test]
+fn test_parse_move_all_in_tablespace() {
+    let nodes =
+        parse_sql("ALTER TABLE ALL IN TABLESPACE pg_default OWNED BY app SET TABLESPACE fast_ssd;");
+    assert_eq!(
+        nodes[0].node,
+        IrNode::MoveAllInTablespace {
+            object_kind: "TABLE".to_string(),
+            tablespace: "pg_default".to_string(),
+            new_tablespace: "fast_ssd".to_string(),
+            owned_by: vec!["app".to_string()],
+        }
+    );
+
+    let nodes = parse_sql("ALTER INDEX ALL IN TABLESPACE pg_default SET TABLESPACE fast_ssd;");
+    match &nodes[0].node {
+        IrNode::MoveAllInTablespace {
+            object_kind,
+            owned_by,
+            ..
+        } => {
+            assert_eq!(object_kind, "INDEX");
+            assert!(owned_by.is_empty());
+        }
+        other => panic!("Expected MoveAllInTablespace, got: {other:?}"),
+    }
+}
+
 #[test]
 fn test_parse_expression_index() {
     let sql = "CREATE INDEX idx_email_lower ON users (LOWER(email));";
@@ -2009,11 +2666,24 @@ fn test_partition_of() {
                 .expect("partition_of should be set");
             assert_eq!(parent.name, "measurements");
             assert!(parent.schema.is_none());
+            assert!(ct.inherits.is_empty());
         }
         other => panic!("Expected CreateTable, got {:?}", other),
     }
 }
 
+#[test]
+fn test_inherits() {
+    let nodes =
+        parse_sql("CREATE TABLE cities_capital (state char(2)) INHERITS (cities, audit.tracked);");
+    let IrNode::CreateTable(ct) = &nodes[0].node else {
+        panic!("Expected CreateTable, got {:?}", nodes[0].node);
+    };
+    assert!(ct.partition_of.is_none());
+    let parents: Vec<_> = ct.inherits.iter().map(|p| p.display_name()).collect();
+    assert_eq!(parents, vec!["cities", "audit.tracked"]);
+}
+
 #[test]
 fn test_attach_partition() {
     let nodes = parse_sql(
@@ -2178,6 +2848,32 @@ fn test_partition_by_expression_arithmetic() {
     }
 }
 
+#[test]
+fn test_parse_alter_index_rename() {
+    let nodes = parse_sql("ALTER INDEX myschema.idx_foo RENAME TO idx_bar;");
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(
+        nodes[0].node,
+        IrNode::RenameIndex {
+            index_name: "idx_foo".to_string(),
+            new_name: "idx_bar".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_parse_alter_index_set_tablespace() {
+    let nodes = parse_sql("ALTER INDEX idx_foo SET TABLESPACE fast_ssd;");
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(
+        nodes[0].node,
+        IrNode::AlterIndexSetTablespace {
+            index_name: "idx_foo".to_string(),
+            tablespace: "fast_ssd".to_string(),
+        }
+    );
+}
+
 #[test]
 fn test_alter_index_attach_partition() {
     let sql = "ALTER INDEX idx_parent ATTACH PARTITION idx_child;";
@@ -0,0 +1,213 @@
+//! Identifier spelling recovered from the statement text
+//!
+//! pg_query hands back identifiers after PostgreSQL's case folding, so
+//! `"UserAccounts"`, `UserAccounts`, and `useraccounts` differ only in the
+//! source. After a statement is converted, its text is scanned for
+//! identifier tokens and each table and column name in the IR is matched
+//! to the first token that folds to it: a quoted token matches its exact
+//! text, an unquoted one its lowercased text.
+//!
+//! Names that match no token (keywords the grammar turns into names, or
+//! names built by the converter) keep no spelling and fall back to
+//! [`Spelling::inferred`].
+
+use crate::parser::ir::{AlterTableAction, ColumnDef, IrNode, QualifiedName, Spelling};
+
+/// Identifier tokens of `sql` in source order. Keywords are included; string
+/// literals, dollar-quoted bodies, and comments are skipped.
+pub(crate) fn identifiers(sql: &str) -> Vec<Spelling> {
+    let mut idents = Vec::new();
+    let mut chars = sql.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        match c {
+            '"' => {
+                let mut text = String::new();
+                while let Some((_, c)) = chars.next() {
+                    if c == '"' && chars.next_if(|&(_, c)| c == '"').is_none() {
+                        break;
+                    }
+                    text.push(c);
+                }
+                idents.push(Spelling { quoted: true, text });
+            }
+            '\'' => {
+                while let Some((_, c)) = chars.next() {
+                    if c == '\'' && chars.next_if(|&(_, c)| c == '\'').is_none() {
+                        break;
+                    }
+                }
+            }
+            '-' if chars.next_if(|&(_, c)| c == '-').is_some() => {
+                while chars.next_if(|&(_, c)| c != '\n').is_some() {}
+            }
+            '/' if chars.next_if(|&(_, c)| c == '*').is_some() => {
+                let mut prev = '\0';
+                for (_, c) in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            '$' => {
+                // `$tag$ ... $tag$`; a lone `$` or `$1` parameter is skipped.
+                let mut end = start + 1;
+                while let Some((i, c)) = chars.next_if(|&(_, c)| c.is_alphanumeric() || c == '_') {
+                    end = i + c.len_utf8();
+                }
+                if chars.next_if(|&(_, c)| c == '$').is_some() {
+                    let tag = &sql[start..=end];
+                    let body_start = end + 1;
+                    let close = sql[body_start..]
+                        .find(tag)
+                        .map_or(sql.len(), |i| body_start + i + tag.len());
+                    while chars.next_if(|&(i, _)| i < close).is_some() {}
+                }
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = start + c.len_utf8();
+                while let Some((i, c)) =
+                    chars.next_if(|&(_, c)| c.is_alphanumeric() || c == '_' || c == '$')
+                {
+                    end = i + c.len_utf8();
+                }
+                idents.push(Spelling {
+                    quoted: false,
+                    text: sql[start..end].to_string(),
+                });
+            }
+            c if c.is_ascii_digit() => {
+                // Skip numbers so `1e10` or `10abc` do not yield identifiers.
+                while chars
+                    .next_if(|&(_, c)| c.is_alphanumeric() || c == '.' || c == '_')
+                    .is_some()
+                {}
+            }
+            _ => {}
+        }
+    }
+    idents
+}
+
+/// The first token that PostgreSQL folds to `name`.
+fn find(idents: &[Spelling], name: &str) -> Option<Spelling> {
+    idents
+        .iter()
+        .find(|ident| {
+            if ident.quoted {
+                ident.text == name
+            } else {
+                ident.text.to_ascii_lowercase() == name
+            }
+        })
+        .cloned()
+}
+
+fn spell_name(name: &mut QualifiedName, idents: &[Spelling]) {
+    let Some(spelling) = find(idents, &name.name) else {
+        return;
+    };
+    let schema = name.schema.as_deref().and_then(|s| find(idents, s));
+    name.set_spelling(schema, spelling);
+}
+
+fn spell_column(col: &mut ColumnDef, idents: &[Spelling]) {
+    col.spelling = find(idents, &col.name);
+}
+
+/// Record the spelling of the table, sequence, and column names `node`
+/// creates or changes, using the identifier tokens of its statement.
+pub(crate) fn apply(node: &mut IrNode, idents: &[Spelling]) {
+    match node {
+        IrNode::CreateTable(ct) => {
+            spell_name(&mut ct.name, idents);
+            for col in &mut ct.columns {
+                spell_column(col, idents);
+            }
+        }
+        IrNode::AlterTable(at) => {
+            spell_name(&mut at.name, idents);
+            for action in &mut at.actions {
+                if let AlterTableAction::AddColumn(col) = action {
+                    spell_column(col, idents);
+                }
+            }
+        }
+        IrNode::CreateIndex(ci) => spell_name(&mut ci.table_name, idents),
+        IrNode::DropTable(dt) => spell_name(&mut dt.name, idents),
+        IrNode::CreateSequence { name, .. }
+        | IrNode::SetSequenceOwner { name, .. }
+        | IrNode::DropSequence { name, .. }
+        | IrNode::RenameSequence { name, .. }
+        | IrNode::RenameTable { name, .. } => spell_name(name, idents),
+        IrNode::RenameColumn { table, .. } => spell_name(table, idents),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::ir::CreateTable;
+
+    fn spelled(text: &str, quoted: bool) -> Spelling {
+        Spelling {
+            quoted,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_identifiers_skip_literals_and_comments() {
+        let idents = identifiers(
+            "-- \"Ignored\"\nINSERT INTO \"My\"\"Table\" /* x */ VALUES ('it''s', $f$ \"No\" $f$, 1e5)",
+        );
+        assert_eq!(
+            idents,
+            vec![
+                spelled("INSERT", false),
+                spelled("INTO", false),
+                spelled("My\"Table", true),
+                spelled("VALUES", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_records_quoting_and_case() {
+        let sql =
+            "CREATE TABLE Billing.\"UserAccounts\" (Id bigint, \"Email\" text, \"note\" text)";
+        let mut node = IrNode::CreateTable(
+            CreateTable::test(QualifiedName::qualified("billing", "UserAccounts")).with_columns(
+                vec![
+                    ColumnDef::test("id", "bigint"),
+                    ColumnDef::test("Email", "text"),
+                    ColumnDef::test("note", "text"),
+                ],
+            ),
+        );
+        apply(&mut node, &identifiers(sql));
+
+        let IrNode::CreateTable(ct) = node else {
+            unreachable!();
+        };
+        assert_eq!(ct.name.to_sql(), "Billing.\"UserAccounts\"");
+        let columns: Vec<Spelling> = ct.columns.iter().map(ColumnDef::name_spelling).collect();
+        assert_eq!(
+            columns,
+            vec![
+                spelled("Id", false),
+                spelled("Email", true),
+                spelled("note", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unmatched_name_falls_back_to_inferred() {
+        let mut name = QualifiedName::unqualified("Orders");
+        spell_name(&mut name, &identifiers("ALTER TABLE orders ADD x int"));
+        assert_eq!(name.name_spelling(), Spelling::inferred("Orders"));
+        assert_eq!(name.to_sql(), "\"Orders\"");
+    }
+}
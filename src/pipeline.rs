@@ -4,13 +4,24 @@
 //! catalog state, table-creation tracking, and the clone → replay → lint → cap
 //! sequence that was previously duplicated across `main.rs` and integration tests.
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use crate::Catalog;
-use crate::catalog::replay;
-use crate::input::MigrationUnit;
-use crate::parser::ir::IrNode;
-use crate::rules::{self, Finding, LintContext, Rule, RuleId};
+use crate::Config;
+use crate::catalog::{ReplayWarning, WarningCategory, replay};
+use crate::input::changed::ChangedFiles;
+use crate::input::liquibase_bridge::load_liquibase;
+use crate::input::sql::SqlLoader;
+use crate::input::{LoadError, MigrationUnit};
+use crate::normalize;
+use crate::parser::ir::{IrNode, SourceSpan};
+use crate::rules::{
+    self, ChangeContext, ChangedUnit, Finding, LintContext, NewGrants, NewTables, PatternHistory,
+    PendingReferences, Rule, RuleId, RuleRegistry, UnitRef, patterns,
+};
 
 /// Encapsulates the single-pass replay + lint pipeline.
 ///
@@ -21,45 +32,475 @@ use crate::rules::{self, Finding, LintContext, Rule, RuleId};
 pub struct LintPipeline {
     catalog: Catalog,
     tables_created_in_change: HashSet<String>,
+    down_cap_exempt: Vec<RuleId>,
+    config: Config,
+    pending_references: PendingReferences,
+    /// Operations relevant to `[[patterns]]` (PGM517), from every unit so far.
+    pattern_history: PatternHistory,
+    /// Tables created by the changed units so far (PGM522).
+    new_tables: NewTables,
+    /// Tables and schemas created and grants issued by the changed units so
+    /// far (PGM526).
+    new_grants: NewGrants,
+    /// Tables PGM525 has reported on in this run, by catalog key.
+    noticed_tables: HashSet<String>,
+    /// Where the units linted so far are, for attributing findings made
+    /// after the last unit.
+    linted_units: Vec<LintedUnit>,
+    /// The units linted so far, for [`Rule::check_change`]. Kept only when
+    /// an active rule implements it; otherwise empty.
+    changed_units: Vec<ChangedUnit>,
+    /// The catalog before the first linted unit, when `changed_units` is
+    /// kept.
+    change_catalog_before: Option<Catalog>,
+    /// Rule implementations built from `config`.
+    rules: RuleRegistry,
+    /// Per-rule timings, collected only when profiling is enabled.
+    profile: Option<BTreeMap<RuleId, RuleTiming>>,
+    /// Callbacks registered by library consumers.
+    hooks: Hooks,
+    /// Problems with the history found during replay, in unit order.
+    warnings: Vec<ReplayWarning>,
+    /// Linted units whose rules assumed unknown tables exist (PGM902).
+    assumed_tables: Vec<AssumedTables>,
+}
+
+/// Tables missing from the catalog that the rules treated as existing while
+/// linting one unit (`catalog.assume_unknown_tables_exist`).
+struct AssumedTables {
+    file: PathBuf,
+    span: SourceSpan,
+    tables: BTreeSet<String>,
+}
+
+/// A linted unit as far as attributing findings needs it: its identity, its
+/// file, and the lines its statements cover. Kept instead of the unit itself
+/// so a long run does not hold every linted unit in memory.
+struct LintedUnit {
+    unit: UnitRef,
+    file: PathBuf,
+    lines: Option<RangeInclusive<usize>>,
+}
+
+impl LintedUnit {
+    fn of(unit: &MigrationUnit) -> Self {
+        let lines = match (unit.statements.first(), unit.statements.last()) {
+            (Some(first), Some(last)) => Some(first.span.start_line..=last.span.end_line),
+            _ => None,
+        };
+        Self {
+            unit: UnitRef::from(unit),
+            file: unit.source_file.clone(),
+            lines,
+        }
+    }
+}
+
+/// What a unit hook sees: the unit, the catalog around it, and its findings.
+pub struct UnitEvent<'a> {
+    pub unit: &'a MigrationUnit,
+    /// The catalog before the unit was applied.
+    pub catalog_before: &'a Catalog,
+    /// The catalog after the unit was applied; `None` in pre-unit hooks.
+    pub catalog_after: Option<&'a Catalog>,
+    /// Raw findings (before suppression). Empty in pre-unit hooks and for
+    /// replayed units.
+    pub findings: &'a [Finding],
+    /// `true` for [`lint`](LintPipeline::lint), `false` for
+    /// [`replay`](LintPipeline::replay).
+    pub linted: bool,
+}
+
+/// What a post-run hook sees, once [`finish`](LintPipeline::finish) is done.
+pub struct RunEvent<'a> {
+    /// The catalog after the last unit.
+    pub catalog: &'a Catalog,
+    /// Raw findings from `finish` itself; per-unit findings were passed to
+    /// the post-unit hooks.
+    pub findings: &'a [Finding],
+}
+
+type UnitHook = Box<dyn FnMut(&UnitEvent<'_>) + Send>;
+type RunHook = Box<dyn FnMut(&RunEvent<'_>) + Send>;
+
+#[derive(Default)]
+struct Hooks {
+    pre_unit: Vec<UnitHook>,
+    post_unit: Vec<UnitHook>,
+    post_run: Vec<RunHook>,
+}
+
+impl Hooks {
+    fn has_unit_hooks(&self) -> bool {
+        !self.pre_unit.is_empty() || !self.post_unit.is_empty()
+    }
+
+    fn run(hooks: &mut [UnitHook], event: &UnitEvent<'_>) {
+        for hook in hooks {
+            hook(event);
+        }
+    }
+}
+
+/// Cumulative cost of one rule across a run (`--profile-rules`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RuleTiming {
+    /// Total time spent in the rule's `check`.
+    pub elapsed: Duration,
+    /// Number of units the rule was run on.
+    pub units: usize,
+    /// Raw findings the rule produced, before suppression and dedup.
+    pub findings: usize,
+}
+
+/// PGM034 findings for the statements of `unit` that strict replay refused,
+/// given the warnings replay reported for the unit.
+fn rejected_statements(
+    rule: &dyn Rule,
+    unit: &MigrationUnit,
+    warnings: &[ReplayWarning],
+) -> Vec<Finding> {
+    warnings
+        .iter()
+        .filter(|w| w.category == WarningCategory::Rejected)
+        .filter_map(|warning| {
+            let stmt = unit
+                .statements
+                .iter()
+                .find(|stmt| stmt.span.start_line == warning.line)?;
+            Some(rule.make_finding(
+                format!(
+                    "PostgreSQL would reject this statement: {}. The migration fails \
+                     at deploy; strict replay skipped the statement.",
+                    warning.message
+                ),
+                &unit.source_file,
+                &stmt.span,
+            ))
+        })
+        .collect()
+}
+
+/// Index of the linted unit `finding` belongs to: the unit in its file whose
+/// statements cover its line, or else the first unit in its file.
+fn owning_unit(units: &[LintedUnit], finding: &Finding) -> Option<usize> {
+    let mut in_file = units
+        .iter()
+        .enumerate()
+        .filter(|(_, linted)| linted.file == finding.file);
+    in_file
+        .clone()
+        .find(|(_, linted)| {
+            linted
+                .lines
+                .as_ref()
+                .is_some_and(|lines| lines.contains(&finding.start_line))
+        })
+        .or_else(|| in_file.next())
+        .map(|(i, _)| i)
+}
+
+/// Fill in `snippet` on each finding that points at exactly one of the
+/// unit's statements (`output.include_snippets`).
+///
+/// Findings whose lines span several statements, or that were reported
+/// against another file, are left without a snippet.
+fn attach_snippets(findings: &mut [Finding], unit: &MigrationUnit, redact: bool) {
+    for finding in findings.iter_mut() {
+        if finding.snippet.is_some() || finding.file != unit.source_file {
+            continue;
+        }
+        let statement = unit.statements.iter().find(|stmt| {
+            stmt.span.start_line == finding.start_line
+                && stmt.span.end_line == finding.end_line
+                && !stmt.raw_sql.is_empty()
+        });
+        if let Some(stmt) = statement {
+            finding.snippet = Some(if redact {
+                crate::output::redact::redact_literals(&stmt.raw_sql)
+            } else {
+                stmt.raw_sql.to_string()
+            });
+        }
+    }
+}
+
+/// Record `unit` on each finding reported against its file.
+fn attach_unit(findings: &mut [Finding], unit: &MigrationUnit) {
+    for finding in findings.iter_mut() {
+        if finding.unit.is_none() && finding.file == unit.source_file {
+            finding.unit = Some(UnitRef::from(unit));
+        }
+    }
 }
 
 impl LintPipeline {
     /// Create a new pipeline with an empty catalog.
     pub fn new() -> Self {
+        let config = Config::default();
         Self {
             catalog: Catalog::new(),
             tables_created_in_change: HashSet::new(),
+            down_cap_exempt: Vec::new(),
+            rules: RuleRegistry::assemble(&config),
+            config,
+            pending_references: PendingReferences::new(),
+            pattern_history: PatternHistory::new(),
+            new_tables: NewTables::new(),
+            new_grants: NewGrants::new(),
+            noticed_tables: HashSet::new(),
+            linted_units: Vec::new(),
+            changed_units: Vec::new(),
+            change_catalog_before: None,
+            profile: None,
+            hooks: Hooks::default(),
+            warnings: Vec::new(),
+            assumed_tables: Vec::new(),
+        }
+    }
+
+    /// Use the given configuration for rule options (per-rule settings such
+    /// as allowlists) and schema ownership.
+    ///
+    /// Rules are rebuilt here, so their options are resolved once rather
+    /// than on every [`lint`](Self::lint) call.
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.rules.reassemble(&config);
+        self.config = config;
+        self
+    }
+
+    /// Start from `catalog` instead of an empty one, e.g. a catalog saved
+    /// from an earlier run, and replay or lint the remaining units on top.
+    pub fn with_catalog(mut self, catalog: Catalog) -> Self {
+        self.catalog = catalog;
+        self
+    }
+
+    /// Run the rules of `registry`, including the rules rule packs
+    /// registered with it. The built-in rules are rebuilt for the pipeline's
+    /// configuration, before or after [`with_config`](Self::with_config).
+    /// Pass [`RulesConfig::active_rules_in`](crate::config::RulesConfig::active_rules_in)
+    /// to [`lint`](Self::lint) so the pack rules run.
+    pub fn with_registry(mut self, mut registry: RuleRegistry) -> Self {
+        registry.reassemble(&self.config);
+        self.rules = registry;
+        self
+    }
+
+    /// The rules this pipeline runs.
+    pub fn registry(&self) -> &RuleRegistry {
+        &self.rules
+    }
+
+    /// Exempt rules from the down-migration severity cap (PGM901).
+    ///
+    /// Findings from these rules keep their original severity even when the
+    /// unit is a down migration.
+    pub fn with_down_cap_exempt(mut self, exempt: &[RuleId]) -> Self {
+        self.down_cap_exempt = exempt.to_vec();
+        self
+    }
+
+    /// Record how long each rule takes and how many findings it produces.
+    ///
+    /// Off by default; the timing calls are skipped entirely when disabled.
+    /// Read the results with [`rule_profile`](Self::rule_profile).
+    pub fn with_rule_profiling(mut self, enabled: bool) -> Self {
+        self.profile = enabled.then(BTreeMap::new);
+        self
+    }
+
+    /// Call `hook` before each unit is linted or replayed.
+    ///
+    /// Hooks run in registration order. They see the unit and the catalog
+    /// before it, so a UI can report progress without driving the loop.
+    pub fn with_pre_unit_hook(mut self, hook: impl FnMut(&UnitEvent<'_>) + Send + 'static) -> Self {
+        self.hooks.pre_unit.push(Box::new(hook));
+        self
+    }
+
+    /// Call `hook` after each unit is linted or replayed, with the catalog
+    /// before and after it and the unit's raw findings.
+    ///
+    /// Comparing the two catalogs gives the unit's schema delta.
+    pub fn with_post_unit_hook(
+        mut self,
+        hook: impl FnMut(&UnitEvent<'_>) + Send + 'static,
+    ) -> Self {
+        self.hooks.post_unit.push(Box::new(hook));
+        self
+    }
+
+    /// Call `hook` at the end of [`finish`](Self::finish) with the final
+    /// catalog and the findings `finish` produced.
+    pub fn with_post_run_hook(mut self, hook: impl FnMut(&RunEvent<'_>) + Send + 'static) -> Self {
+        self.hooks.post_run.push(Box::new(hook));
+        self
+    }
+
+    /// Per-rule timings collected so far, or `None` if profiling is off.
+    ///
+    /// Only rules that actually ran appear in the map.
+    pub fn rule_profile(&self) -> Option<&BTreeMap<RuleId, RuleTiming>> {
+        self.profile.as_ref()
+    }
+
+    /// The catalog as replayed so far.
+    pub fn catalog(&self) -> &Catalog {
+        &self.catalog
+    }
+
+    /// Problems with the migration history found while replaying so far:
+    /// conflicting `CREATE` statements, statements that could not be
+    /// parsed, and statements strict replay refused. Conflicts and refused
+    /// statements are also printed to stderr as they are found.
+    pub fn warnings(&self) -> &[ReplayWarning] {
+        &self.warnings
+    }
+
+    /// Apply `unit` to the catalog and keep the warnings replay reports.
+    fn apply(&mut self, unit: &MigrationUnit) {
+        let strict = self.config.catalog.strict_replay;
+        for warning in replay::apply_with(&mut self.catalog, unit, strict) {
+            if warning.category != WarningCategory::Unparseable {
+                eprintln!(
+                    "warning: {}:{}: {}",
+                    warning.file.display(),
+                    warning.line,
+                    warning.message
+                );
+            }
+            self.warnings.push(warning);
+        }
+    }
+
+    /// Lint `new_units` against a pre-built catalog `snapshot`, without
+    /// replaying the history that produced it.
+    ///
+    /// The pipeline's catalog is replaced by the snapshot and its change
+    /// tracking reset, so every table in the snapshot counts as existing.
+    /// Each unit is then linted in order with the configured active rules,
+    /// exactly as [`lint`](Self::lint) would. A migration service can keep
+    /// the snapshot from a nightly full replay ([`catalog`](Self::catalog))
+    /// and check a candidate changeset in milliseconds.
+    ///
+    /// Each unit's inline suppressions and acknowledgments are applied to
+    /// its findings, then the findings from [`finish`](Self::finish) are
+    /// added, duplicates removed, and the result returned in report order
+    /// ([`rules::sort_findings`]), as [`lint_sql`] does.
+    pub fn lint_incremental(
+        &mut self,
+        snapshot: &Catalog,
+        new_units: &[MigrationUnit],
+    ) -> Vec<Finding> {
+        self.catalog = snapshot.clone();
+        self.tables_created_in_change.clear();
+        self.pending_references = PendingReferences::new();
+        self.pattern_history = PatternHistory::new();
+        self.new_tables = NewTables::new();
+        self.new_grants = NewGrants::new();
+        self.noticed_tables.clear();
+        self.linted_units.clear();
+        self.changed_units.clear();
+        self.change_catalog_before = None;
+        self.warnings.clear();
+        self.assumed_tables.clear();
+
+        let rules = self.config.rules.active_rules_in(&self.rules);
+        let mut findings = Vec::new();
+        for unit in new_units {
+            let mut unit_findings = self.lint(unit, &rules);
+            let suppressions = &unit.suppressions;
+            unit_findings.retain(|f| !suppressions.is_suppressed(f.rule_id, f.start_line));
+            suppressions.apply_acknowledgments(&mut unit_findings);
+            findings.append(&mut unit_findings);
         }
+        findings.extend(self.finish(&rules));
+        rules::dedup_findings(&mut findings);
+        rules::sort_findings(&mut findings);
+        findings
     }
 
     /// Replay a unit without linting (for non-changed migration files).
     ///
     /// Applies the unit's statements to the catalog so that subsequent
-    /// units see the correct schema state.
+    /// units see the correct schema state. Unit hooks still run, with no
+    /// findings.
     pub fn replay(&mut self, unit: &MigrationUnit) {
-        replay::apply(&mut self.catalog, unit);
+        // Only hooks need the catalog before the unit; skip the clone otherwise.
+        let catalog_before = self.hooks.has_unit_hooks().then(|| self.catalog.clone());
+        if let Some(before) = &catalog_before {
+            Hooks::run(
+                &mut self.hooks.pre_unit,
+                &UnitEvent {
+                    unit,
+                    catalog_before: before,
+                    catalog_after: None,
+                    findings: &[],
+                    linted: false,
+                },
+            );
+        }
+
+        self.apply(unit);
+        self.pending_references.resolve(&self.catalog);
+        self.record_patterns(unit, None, None);
+
+        if let Some(before) = &catalog_before {
+            Hooks::run(
+                &mut self.hooks.post_unit,
+                &UnitEvent {
+                    unit,
+                    catalog_before: before,
+                    catalog_after: Some(&self.catalog),
+                    findings: &[],
+                    linted: false,
+                },
+            );
+        }
     }
 
     /// Replay AND lint a changed unit. Returns raw findings (before suppression).
     ///
     /// Handles: catalog clone, replay, track created tables (with IF NOT EXISTS
-    /// guard), build [`LintContext`], run rules, and cap severity for down
-    /// migrations.
+    /// guard), build [`LintContext`], run rules, cap severity for down
+    /// migrations, apply message templates, and record references to tables
+    /// or columns that do not exist yet, operations that `[[patterns]]`
+    /// refer to, and the tables the unit creates.
     pub fn lint(&mut self, unit: &MigrationUnit, rules: &[RuleId]) -> Vec<Finding> {
         // Clone catalog BEFORE applying this unit
         let catalog_before = self.catalog.clone();
+        let keep_for_change = self.rules.has_change_checks(rules);
+        if keep_for_change && self.change_catalog_before.is_none() {
+            self.change_catalog_before = Some(catalog_before.clone());
+        }
+
+        Hooks::run(
+            &mut self.hooks.pre_unit,
+            &UnitEvent {
+                unit,
+                catalog_before: &catalog_before,
+                catalog_after: None,
+                findings: &[],
+                linted: true,
+            },
+        );
 
         // Apply unit to catalog
-        replay::apply(&mut self.catalog, unit);
+        let first_warning = self.warnings.len();
+        self.apply(unit);
 
         // Track tables created in this change (for PGM001/002 "new table" detection).
         // Skip IF NOT EXISTS when the table already existed — that is a no-op,
         // not a genuine creation, and must not mask rules on later statements.
+        let mut tables_created = Vec::new();
         for stmt in &unit.statements {
             if let IrNode::CreateTable(ct) = &stmt.node {
                 let key = ct.name.catalog_key().to_string();
                 if !(ct.if_not_exists && catalog_before.has_table(&key)) {
-                    self.tables_created_in_change.insert(key);
+                    self.tables_created_in_change.insert(key.clone());
+                    tables_created.push(key);
                 }
             }
         }
@@ -72,21 +513,310 @@ impl LintPipeline {
             run_in_transaction: unit.run_in_transaction,
             is_down: unit.is_down,
             file: &unit.source_file,
+            config: &self.config,
+            pending_references: &self.pending_references,
+            pattern_history: &self.pattern_history,
+            assumed_tables: Default::default(),
+            active_rules: rules,
+            suppressions: &unit.suppressions,
         };
 
         // Run active rules
         let mut findings: Vec<Finding> = Vec::new();
-        for rule in rules {
-            findings.extend(rule.check(&unit.statements, &ctx));
+        for id in rules {
+            let started = self.profile.is_some().then(Instant::now);
+            let found = match self.rules.get(id) {
+                Some(rule) => rule.check(&unit.statements, &ctx),
+                None => id.check(&unit.statements, &ctx),
+            };
+            if let (Some(profile), Some(started)) = (self.profile.as_mut(), started) {
+                let timing = profile.entry(*id).or_default();
+                timing.elapsed += started.elapsed();
+                timing.units += 1;
+                timing.findings += found.len();
+            }
+            findings.extend(found);
+        }
+
+        // Unknown tables the rules assumed to exist (PGM902)
+        let assumed = ctx.assumed_tables.take();
+        if !assumed.is_empty()
+            && let Some(first) = unit.statements.first()
+        {
+            self.assumed_tables.push(AssumedTables {
+                file: unit.source_file.clone(),
+                span: first.span.clone(),
+                tables: assumed,
+            });
+        }
+
+        // Statements strict replay refused (PGM034)
+        if rules.contains(&RuleId::Pgm034)
+            && let Some(rule) = self.rules.get(&RuleId::Pgm034)
+        {
+            findings.extend(rejected_statements(
+                rule,
+                unit,
+                &self.warnings[first_warning..],
+            ));
+        }
+
+        // Per-rule table filters ([[rules.overrides]]) and tracking tables
+        rules::attach_tables(&mut findings, &unit.statements);
+        rules::apply_overrides(&mut findings, &self.config.rules.overrides);
+        if !self.config.rules.lint_tracking_tables {
+            rules::exempt_tracking_tables(&mut findings);
         }
 
+        // PGM525 is a once-per-run notice: drop tables an earlier unit reported
+        let noticed: Vec<String> = findings
+            .iter()
+            .filter(|f| f.rule_id == RuleId::Pgm525)
+            .filter_map(|f| f.dedup_key.clone())
+            .filter(|key| !self.noticed_tables.contains(key))
+            .collect();
+        findings.retain(|f| {
+            f.rule_id != RuleId::Pgm525
+                || f.dedup_key
+                    .as_ref()
+                    .is_none_or(|key| !self.noticed_tables.contains(key))
+        });
+        self.noticed_tables.extend(noticed);
+
         // Cap severity for down migrations (PGM901)
         if unit.is_down {
-            rules::cap_for_down_migration(&mut findings);
+            rules::cap_for_down_migration(&mut findings, &self.down_cap_exempt);
+        }
+
+        // Mask quoted literals, then rewrite messages from configured
+        // templates ([messages])
+        if self.config.output.redact_literals {
+            findings.iter_mut().for_each(Finding::redact);
+        }
+        rules::apply_message_templates(&mut findings, &self.config.messages);
+
+        if self.config.output.include_snippets {
+            attach_snippets(&mut findings, unit, self.config.output.redact_literals);
+        }
+        attach_unit(&mut findings, unit);
+
+        // Forget references this unit satisfied, then remember the ones it
+        // makes to objects that do not exist yet (PGM027)
+        self.pending_references.resolve(&self.catalog);
+        self.pending_references.record(
+            &unit.statements,
+            &unit.source_file,
+            &catalog_before,
+            &self.catalog,
+        );
+
+        let suppressions = (!unit.is_down).then_some(&unit.suppressions);
+        self.record_patterns(unit, Some(&catalog_before), suppressions);
+        if let Some(suppressions) = suppressions {
+            self.new_tables.record(
+                &unit.statements,
+                &unit.source_file,
+                &catalog_before,
+                suppressions,
+            );
+            self.new_grants.record(
+                &unit.statements,
+                &unit.source_file,
+                &catalog_before,
+                suppressions,
+                self.config.migrations.default_schema_for(&unit.source_file),
+            );
+        }
+
+        self.linted_units.push(LintedUnit::of(unit));
+        if keep_for_change {
+            self.changed_units.push(ChangedUnit {
+                unit: unit.clone(),
+                tables_created,
+            });
+        }
+
+        Hooks::run(
+            &mut self.hooks.post_unit,
+            &UnitEvent {
+                unit,
+                catalog_before: &catalog_before,
+                catalog_after: Some(&self.catalog),
+                findings: &findings,
+                linted: true,
+            },
+        );
+
+        findings
+    }
+
+    /// Report what can only be judged once the whole change is known:
+    /// operations whose required follow-up (`followed_by` in `[[patterns]]`)
+    /// no later changed unit provided (PGM517), tables created in the
+    /// change whose foreign keys no index covers (PGM522), tables and
+    /// schemas created in the change that no grant reaches (PGM526), and
+    /// whatever rules report from [`Rule::check_change`].
+    ///
+    /// Call once, after the last unit. Returns raw findings with message
+    /// templates applied; operations and tables on lines that suppress the
+    /// rule are already skipped, and `check_change` findings have been
+    /// through the suppressions of the unit they point at. Post-run hooks
+    /// are called last.
+    pub fn finish(&mut self, rules: &[RuleId]) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        if !self.config.patterns.is_empty()
+            && rules.contains(&RuleId::Pgm517)
+            && let Some(rule) = self.rules.get(&RuleId::Pgm517)
+        {
+            findings.extend(patterns::check_follow_ups(
+                rule,
+                &self.config.patterns,
+                &self.pattern_history,
+            ));
+        }
+        if rules.contains(&RuleId::Pgm522)
+            && let Some(rule) = self.rules.get(&RuleId::Pgm522)
+        {
+            findings.extend(
+                self.new_tables
+                    .check_foreign_key_coverage(rule, &self.catalog),
+            );
+        }
+        if rules.contains(&RuleId::Pgm526)
+            && let Some(rule) = self.rules.get(&RuleId::Pgm526)
+        {
+            findings.extend(self.new_grants.check_grants(
+                rule,
+                &self.config.rules.pgm526.roles,
+                &self.catalog,
+            ));
+        }
+        findings.extend(self.check_change(rules));
+        findings.extend(self.assumption_summary());
+        rules::apply_overrides(&mut findings, &self.config.rules.overrides);
+        if !self.config.rules.lint_tracking_tables {
+            rules::exempt_tracking_tables(&mut findings);
+        }
+        if self.config.output.redact_literals {
+            findings.iter_mut().for_each(Finding::redact);
+        }
+        rules::apply_message_templates(&mut findings, &self.config.messages);
+        for finding in findings.iter_mut().filter(|f| f.unit.is_none()) {
+            finding.unit =
+                owning_unit(&self.linted_units, finding).map(|i| self.linted_units[i].unit.clone());
         }
 
+        let event = RunEvent {
+            catalog: &self.catalog,
+            findings: &findings,
+        };
+        for hook in &mut self.hooks.post_run {
+            hook(&event);
+        }
         findings
     }
+
+    /// The PGM902 summary of the decisions that relied on
+    /// `catalog.assume_unknown_tables_exist`, reported at the first unit
+    /// that relied on it. A decision is one unknown table in one unit.
+    fn assumption_summary(&self) -> Option<Finding> {
+        const LISTED: usize = 5;
+        let first = self.assumed_tables.first()?;
+        let decisions: usize = self.assumed_tables.iter().map(|a| a.tables.len()).sum();
+        let tables: BTreeSet<&str> = self
+            .assumed_tables
+            .iter()
+            .flat_map(|a| a.tables.iter().map(String::as_str))
+            .collect();
+        let mut list = tables
+            .iter()
+            .take(LISTED)
+            .copied()
+            .collect::<Vec<_>>()
+            .join(", ");
+        if tables.len() > LISTED {
+            list.push_str(&format!(" and {} more", tables.len() - LISTED));
+        }
+        Some(
+            RuleId::Pgm902
+                .make_finding(
+                    format!(
+                        "{decisions} lint decision(s) in {units} unit(s) treated tables missing \
+                         from the catalog as existing because catalog.assume_unknown_tables_exist \
+                         is set: {list}. Findings on these tables rest on that assumption.",
+                        units = self.assumed_tables.len(),
+                    ),
+                    &first.file,
+                    &first.span,
+                )
+                .with_param("decisions", decisions.to_string())
+                .with_param("tables", tables.len().to_string()),
+        )
+    }
+
+    /// Run [`Rule::check_change`] for `rules` over the units linted so far,
+    /// and filter the findings by the suppressions of the unit each one
+    /// points at.
+    fn check_change(&self, rules: &[RuleId]) -> Vec<Finding> {
+        let Some(catalog_before) = self.change_catalog_before.as_ref() else {
+            return Vec::new();
+        };
+        let ctx = ChangeContext {
+            catalog_before,
+            catalog_after: &self.catalog,
+            tables_created_in_change: &self.tables_created_in_change,
+            config: &self.config,
+        };
+        let mut findings = Vec::new();
+        for rule in rules.iter().filter_map(|id| self.rules.get(id)) {
+            for mut finding in rule.check_change(&self.changed_units, &ctx) {
+                let owner = owning_unit(&self.linted_units, &finding)
+                    .and_then(|i| self.changed_units.get(i));
+                let Some(ChangedUnit { unit, .. }) = owner else {
+                    findings.push(finding);
+                    continue;
+                };
+                if unit
+                    .suppressions
+                    .is_suppressed(finding.rule_id, finding.start_line)
+                {
+                    continue;
+                }
+                let one = std::slice::from_mut(&mut finding);
+                unit.suppressions.apply_acknowledgments(one);
+                if unit.is_down {
+                    rules::cap_for_down_migration(one, &self.down_cap_exempt);
+                }
+                findings.push(finding);
+            }
+        }
+        findings
+    }
+
+    /// Remember the operations in `unit` that `[[patterns]]` refer to.
+    ///
+    /// `catalog_before` defaults to the current catalog, which is good
+    /// enough for replayed units.
+    fn record_patterns(
+        &mut self,
+        unit: &MigrationUnit,
+        catalog_before: Option<&Catalog>,
+        suppressions: Option<&crate::suppress::Suppressions>,
+    ) {
+        if self.config.patterns.is_empty() {
+            return;
+        }
+        let operations = patterns::operations(
+            &unit.statements,
+            &unit.source_file,
+            self.pattern_history.units(),
+            catalog_before.unwrap_or(&self.catalog),
+            &self.catalog,
+            &self.tables_created_in_change,
+        );
+        self.pattern_history
+            .record(&self.config.patterns, operations, suppressions);
+    }
 }
 
 impl Default for LintPipeline {
@@ -94,3 +824,984 @@ impl Default for LintPipeline {
         Self::new()
     }
 }
+
+/// Lint a single SQL migration held in memory, against an empty catalog.
+///
+/// This is the self-contained entry point for embedders (language bindings,
+/// editor integrations) that have SQL text and a [`Config`] but no migration
+/// directory. `file` is only used for the unit id, down-migration detection,
+/// and the path reported on findings; it is never read.
+///
+/// Applies the default schema configured for `file`, disabled rules, PGM901 exemptions,
+/// inline suppressions and acknowledgments, deduplication, and the stable
+/// report order ([`rules::sort_findings`]) — the same steps the CLI performs.
+pub fn lint_sql(source: &str, file: &Path, config: &Config) -> Vec<Finding> {
+    lint_sql_on(Catalog::new(), source, file, config)
+}
+
+/// [`lint_sql`] against `catalog`, e.g. one replayed from a repository's
+/// migration history, instead of an empty catalog.
+pub fn lint_sql_on(catalog: Catalog, source: &str, file: &Path, config: &Config) -> Vec<Finding> {
+    let run_in_tx = config.migrations.run_in_transaction.unwrap_or(true);
+    let mut units = vec![SqlLoader::new(run_in_tx).load_source(file, source)];
+    normalize::normalize_schemas(&mut units, config.migrations.default_schema_for(file));
+
+    let mut pipeline = LintPipeline::new()
+        .with_catalog(catalog)
+        .with_down_cap_exempt(&config.meta.pgm901.exempt_rule_ids())
+        .with_config(config.clone());
+    let rules = config.rules.active_rules();
+    let mut findings = pipeline.lint(&units[0], &rules);
+    findings.extend(pipeline.finish(&rules));
+
+    let suppressions = &units[0].suppressions;
+    findings.retain(|f| !suppressions.is_suppressed(f.rule_id, f.start_line));
+    suppressions.apply_acknowledgments(&mut findings);
+    rules::dedup_findings(&mut findings);
+    rules::sort_findings(&mut findings);
+    findings
+}
+
+/// Load every migration unit `config` describes, in replay order.
+///
+/// Paths in `config` are used as given; see [`Config::resolve_paths`].
+pub fn load_units(config: &Config) -> Result<Vec<MigrationUnit>, LoadError> {
+    let paths = config.migrations.source_paths();
+    let include_root = config.migrations.effective_include_root();
+    if config.migrations.strategy == "liquibase" {
+        let raw_units = load_liquibase(&config.liquibase, &paths, include_root.as_deref())?;
+        return Ok(raw_units
+            .into_iter()
+            .map(|raw| raw.into_migration_unit())
+            .collect());
+    }
+    SqlLoader::new(config.migrations.run_in_transaction.unwrap_or(true))
+        .with_respect_gitignore(config.migrations.respect_gitignore)
+        .with_follow_symlinks(config.migrations.follow_symlinks)
+        .with_resolve_includes(config.migrations.resolve_psql_includes)
+        .with_include_root(include_root)
+        .units(&paths)?
+        .collect()
+}
+
+/// Lint the migrations `config` describes on disk, as the CLI does: every
+/// unit is replayed in order, and the units whose file (or changeset id) is
+/// in `changed` are linted. With `changed` `None`, every unit is linted.
+///
+/// Applies inline suppressions and acknowledgments, deduplication, the
+/// follow-up checks of [`LintPipeline::finish`], and the stable report
+/// order ([`rules::sort_findings`]).
+pub fn lint_paths(config: &Config, changed: Option<&[PathBuf]>) -> Result<Vec<Finding>, LoadError> {
+    let mut changed = changed.map(ChangedFiles::new);
+    let mut pipeline = LintPipeline::new()
+        .with_down_cap_exempt(&config.meta.pgm901.exempt_rule_ids())
+        .with_config(config.clone());
+    let rules = config.rules.active_rules();
+    let mut findings = Vec::new();
+    for mut unit in load_units(config)? {
+        normalize::normalize_unit(
+            &mut unit,
+            config.migrations.default_schema_for(&unit.source_file),
+        );
+        let is_changed = changed
+            .as_mut()
+            .is_none_or(|changed| changed.match_unit(&unit.id, &unit.source_file));
+        if !is_changed {
+            pipeline.replay(&unit);
+            continue;
+        }
+        let mut unit_findings = pipeline.lint(&unit, &rules);
+        let suppressions = &unit.suppressions;
+        unit_findings.retain(|f| !suppressions.is_suppressed(f.rule_id, f.start_line));
+        suppressions.apply_acknowledgments(&mut unit_findings);
+        rules::dedup_findings(&mut unit_findings);
+        findings.append(&mut unit_findings);
+    }
+    let mut follow_ups = pipeline.finish(&rules);
+    rules::dedup_findings(&mut follow_ups);
+    findings.append(&mut follow_ups);
+    rules::sort_findings(&mut findings);
+    Ok(findings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::{
+        AlterTable, AlterTableAction, CheckExprDeps, ColumnDef, CreateIndex, CreateTable,
+        DropTable, LiteralList, QualifiedName, TableConstraint,
+    };
+    use std::path::PathBuf;
+
+    const SQL: &str = "CREATE TABLE events (id bigint PRIMARY KEY, created_at timestamp);\n";
+
+    #[test]
+    fn test_lint_sql_reports_findings() {
+        let findings = lint_sql(SQL, Path::new("V001__events.sql"), &Config::default());
+        assert!(findings.iter().any(|f| f.rule_id == RuleId::Pgm101));
+    }
+
+    #[test]
+    fn test_lint_sql_respects_disabled_rules() {
+        let mut config = Config::default();
+        config.rules.disabled = vec![RuleId::Pgm101.into()];
+        let findings = lint_sql(SQL, Path::new("V001__events.sql"), &config);
+        assert!(!findings.iter().any(|f| f.rule_id == RuleId::Pgm101));
+    }
+
+    fn unit(file: &str, nodes: Vec<IrNode>) -> MigrationUnit {
+        MigrationUnit {
+            id: file.to_string(),
+            statements: nodes
+                .into_iter()
+                .map(|node| crate::Located {
+                    node,
+                    span: crate::parser::SourceSpan::at(1, 1),
+                    raw_sql: Default::default(),
+                })
+                .collect(),
+            source_file: PathBuf::from(file),
+            source_line_offset: 1,
+            run_in_transaction: true,
+            is_down: false,
+            suppressions: Default::default(),
+            changeset: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_snippets_attached_when_enabled_and_redacted() {
+        let mut migration = unit("V001.sql", vec![create_products("json")]);
+        migration.statements[0].raw_sql =
+            r#"CREATE TABLE products (attrs json DEFAULT '{"api_token": "s3cr3t"}')"#.into();
+
+        let findings = LintPipeline::new().lint(&migration, &[RuleId::Pgm106]);
+        assert!(findings.iter().all(|f| f.snippet.is_none()));
+
+        let mut config = Config::default();
+        config.output.include_snippets = true;
+        let findings = LintPipeline::new()
+            .with_config(config.clone())
+            .lint(&migration, &[RuleId::Pgm106]);
+        assert_eq!(
+            findings[0].snippet.as_deref(),
+            Some(r#"CREATE TABLE products (attrs json DEFAULT '{"api_token": "s3cr3t"}')"#)
+        );
+
+        config.output.redact_literals = true;
+        let findings = LintPipeline::new()
+            .with_config(config)
+            .lint(&migration, &[RuleId::Pgm106]);
+        assert_eq!(
+            findings[0].snippet.as_deref(),
+            Some("CREATE TABLE products (attrs json DEFAULT '?')")
+        );
+    }
+
+    #[test]
+    fn test_lint_paths_lints_changed_units_against_history() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        std::fs::write(dir.path().join("V001__events.sql"), SQL).expect("write");
+        std::fs::write(
+            dir.path().join("V002__index.sql"),
+            "CREATE INDEX idx_events_created ON events (created_at);\n",
+        )
+        .expect("write");
+        let mut config = Config::default();
+        config.migrations.paths = vec![dir.path().to_path_buf().into()];
+
+        let changed = [dir.path().join("V002__index.sql")];
+        let findings = lint_paths(&config, Some(&changed[..])).expect("lint_paths");
+        assert!(findings.iter().all(|f| f.file.ends_with("V002__index.sql")));
+        assert!(findings.iter().any(|f| f.rule_id == RuleId::Pgm001));
+
+        let findings = lint_paths(&config, None).expect("lint_paths");
+        assert!(findings.iter().any(|f| f.rule_id == RuleId::Pgm101));
+        assert!(findings.iter().all(|f| f.rule_id != RuleId::Pgm001));
+    }
+
+    #[test]
+    fn test_redacted_literals_stay_masked_in_message_templates() {
+        let values = ["ops@example.com", "4111-1111", "s3cr3t", "hunter2", "42"];
+        let quoted: Vec<String> = values.iter().map(|v| format!("'{v}'")).collect();
+        let migration = unit(
+            "V001.sql",
+            vec![IrNode::CreateTable(
+                CreateTable::test(QualifiedName::unqualified("accounts")).with_constraints(vec![
+                    TableConstraint::Check {
+                        name: None,
+                        expression: format!("owner IN ({})", quoted.join(", ")),
+                        not_valid: false,
+                        deps: CheckExprDeps {
+                            columns: vec!["owner".to_string()],
+                            literal_lists: vec![LiteralList {
+                                column: "owner".to_string(),
+                                values: values.iter().map(|v| v.to_string()).collect(),
+                            }],
+                            ..CheckExprDeps::default()
+                        },
+                    },
+                ]),
+            )],
+        );
+        let mut config = Config::default();
+        config.messages.default = Some("{message} Allowed: {values}.".to_string());
+
+        let findings = LintPipeline::new()
+            .with_config(config.clone())
+            .lint(&migration, &[RuleId::Pgm528]);
+        assert!(
+            findings[0]
+                .message
+                .contains("Allowed: 4111-1111, 42, hunter2,")
+        );
+
+        config.output.redact_literals = true;
+        let findings = LintPipeline::new()
+            .with_config(config)
+            .lint(&migration, &[RuleId::Pgm528]);
+        assert_eq!(findings.len(), 1);
+        assert!(
+            findings[0]
+                .message
+                .ends_with("Allowed: ?, ?, '?', '?', '?'."),
+            "{}",
+            findings[0].message
+        );
+        for value in values {
+            assert!(!findings[0].message.contains(value), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_findings_carry_their_unit() {
+        let mut migration = unit("changelog.xml", vec![create_products("json")]);
+        migration.id = "7-products".to_string();
+        migration.changeset.author = Some("alice".to_string());
+
+        let findings = LintPipeline::new().lint(&migration, &[RuleId::Pgm106]);
+        assert_eq!(
+            findings[0].unit,
+            Some(UnitRef {
+                id: "7-products".to_string(),
+                author: Some("alice".to_string()),
+            })
+        );
+        assert_eq!(
+            findings[0].unit_label().as_deref(),
+            Some("changeset 7-products by alice")
+        );
+    }
+
+    fn create_shipments() -> IrNode {
+        IrNode::CreateTable(CreateTable::test(QualifiedName::qualified(
+            "public",
+            "shipments",
+        )))
+    }
+
+    fn index_shipments() -> IrNode {
+        IrNode::CreateIndex(
+            CreateIndex::test(
+                Some("idx_shipments_order".to_string()),
+                QualifiedName::qualified("public", "shipments"),
+            )
+            .with_concurrent(true),
+        )
+    }
+
+    #[test]
+    fn test_reference_before_creation_across_changed_units() {
+        let mut pipeline = LintPipeline::new();
+        let first = pipeline.lint(
+            &unit("V010.sql", vec![index_shipments()]),
+            &[RuleId::Pgm027],
+        );
+        assert!(first.is_empty());
+
+        let second = pipeline.lint(
+            &unit("V011.sql", vec![create_shipments()]),
+            &[RuleId::Pgm027],
+        );
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].file, PathBuf::from("V011.sql"));
+        assert!(second[0].message.contains("V010.sql:1 (CREATE INDEX)"));
+    }
+
+    #[test]
+    fn test_reference_resolved_by_replayed_unit_not_reported() {
+        let mut pipeline = LintPipeline::new();
+        pipeline.lint(
+            &unit("V010.sql", vec![index_shipments()]),
+            &[RuleId::Pgm027],
+        );
+        pipeline.replay(&unit("V011.sql", vec![create_shipments()]));
+
+        let findings = pipeline.lint(
+            &unit("V012.sql", vec![create_shipments()]),
+            &[RuleId::Pgm027],
+        );
+        assert!(findings.is_empty());
+    }
+
+    fn create_shipments_with_fk() -> IrNode {
+        IrNode::CreateTable(
+            CreateTable::test(QualifiedName::qualified("public", "shipments"))
+                .with_columns(vec![ColumnDef::test("order_id", "bigint")])
+                .with_constraints(vec![TableConstraint::ForeignKey {
+                    name: Some("fk_shipments_order".to_string()),
+                    columns: vec!["order_id".to_string()],
+                    ref_table: QualifiedName::qualified("public", "orders"),
+                    ref_columns: vec!["id".to_string()],
+                    not_valid: false,
+                }]),
+        )
+    }
+
+    #[test]
+    fn test_new_table_fk_index_from_sibling_changed_unit() {
+        let rules = [RuleId::Pgm501, RuleId::Pgm522];
+        let mut index = CreateIndex::test(
+            Some("idx_shipments_order".to_string()),
+            QualifiedName::qualified("public", "shipments"),
+        );
+        index.columns = vec![crate::parser::ir::IndexColumn::Column(
+            "order_id".to_string(),
+        )];
+
+        let mut pipeline = LintPipeline::new();
+        let first = pipeline.lint(&unit("V010.sql", vec![create_shipments_with_fk()]), &rules);
+        assert_eq!(first[0].rule_id, RuleId::Pgm501);
+        pipeline.lint(&unit("V011.sql", vec![IrNode::CreateIndex(index)]), &rules);
+        assert!(pipeline.finish(&rules).is_empty());
+
+        let mut pipeline = LintPipeline::new();
+        pipeline.lint(&unit("V010.sql", vec![create_shipments_with_fk()]), &rules);
+        let findings = pipeline.finish(&rules);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule_id, RuleId::Pgm522);
+        assert_eq!(findings[0].file, PathBuf::from("V010.sql"));
+    }
+
+    #[test]
+    fn test_select_star_notice_once_per_table_per_run() {
+        let rules = [RuleId::Pgm525];
+        let mut config = Config::default();
+        config.rules.pgm525.tables = vec!["orders".to_string()];
+        let add_column = |column: &str| {
+            IrNode::AlterTable(AlterTable {
+                name: QualifiedName::qualified("public", "orders"),
+                actions: vec![AlterTableAction::AddColumn(ColumnDef::test(
+                    column, "bigint",
+                ))],
+            })
+        };
+
+        let mut pipeline = LintPipeline::new().with_config(config);
+        pipeline.replay(&unit(
+            "V001.sql",
+            vec![IrNode::CreateTable(CreateTable::test(
+                QualifiedName::qualified("public", "orders"),
+            ))],
+        ));
+        let first = pipeline.lint(&unit("V002.sql", vec![add_column("discount")]), &rules);
+        assert_eq!(first.len(), 1);
+        let second = pipeline.lint(&unit("V003.sql", vec![add_column("coupon")]), &rules);
+        assert!(second.is_empty());
+    }
+
+    #[test]
+    fn test_overrides_exclude_tables_from_rule() {
+        let mut config = Config::default();
+        config.rules.overrides = toml::from_str::<Config>(
+            "[[rules.overrides]]\nrule = \"PGM201\"\nexclude_tables = [\"public.schema_*\"]",
+        )
+        .unwrap()
+        .rules
+        .overrides;
+        let table = |name: &str| QualifiedName::qualified("public", name);
+
+        let mut pipeline = LintPipeline::new().with_config(config);
+        pipeline.replay(&unit(
+            "V001.sql",
+            vec![
+                IrNode::CreateTable(CreateTable::test(table("orders"))),
+                IrNode::CreateTable(CreateTable::test(table("schema_migrations"))),
+            ],
+        ));
+        let findings = pipeline.lint(
+            &unit(
+                "V002.sql",
+                vec![
+                    DropTable::test(table("schema_migrations")).into(),
+                    DropTable::test(table("orders")).into(),
+                ],
+            ),
+            &[RuleId::Pgm201],
+        );
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].table.as_deref(), Some("public.orders"));
+    }
+
+    #[test]
+    fn test_tracking_tables_exempt_unless_configured() {
+        let table = QualifiedName::qualified("public", "schema_migrations");
+        let created = unit(
+            "V001.sql",
+            vec![IrNode::CreateTable(CreateTable::test(table.clone()))],
+        );
+        let dropped = unit("V002.sql", vec![DropTable::test(table).into()]);
+
+        let mut pipeline = LintPipeline::new();
+        pipeline.replay(&created);
+        assert!(pipeline.lint(&dropped, &[RuleId::Pgm201]).is_empty());
+
+        let mut config = Config::default();
+        config.rules.lint_tracking_tables = true;
+        let mut pipeline = LintPipeline::new().with_config(config);
+        pipeline.replay(&created);
+        assert_eq!(pipeline.lint(&dropped, &[RuleId::Pgm201]).len(), 1);
+    }
+
+    #[test]
+    fn test_registered_rule_pack_rule_runs_and_can_be_disabled() {
+        use crate::rules::test_helpers::{TENANT_ID, TenantIdRule};
+
+        let pack = || {
+            let mut registry = RuleRegistry::assemble(&Config::default());
+            registry
+                .register(Box::new(TenantIdRule(&TENANT_ID)))
+                .unwrap();
+            registry
+        };
+        let created = unit(
+            "V001.sql",
+            vec![IrNode::CreateTable(CreateTable::test(
+                QualifiedName::qualified("public", "orders"),
+            ))],
+        );
+
+        let mut pipeline = LintPipeline::new().with_registry(pack());
+        let rules = pipeline.config.rules.active_rules_in(pipeline.registry());
+        let findings = pipeline.lint(&created, &rules);
+        let tenant: Vec<&Finding> = findings
+            .iter()
+            .filter(|f| f.rule_id.as_str() == "ORG001")
+            .collect();
+        assert_eq!(tenant.len(), 1);
+        assert_eq!(tenant[0].severity, crate::rules::Severity::Major);
+
+        let mut config = Config::default();
+        config.rules.disabled = vec![crate::rules::RuleRef::new("ORG001")];
+        let pipeline = LintPipeline::new()
+            .with_registry(pack())
+            .with_config(config);
+        let rules = pipeline.config.rules.active_rules_in(pipeline.registry());
+        assert!(!rules.iter().any(|r| r.as_str() == "ORG001"));
+        assert!(rules.contains(&RuleId::Pgm001));
+    }
+
+    /// Reports tables created in the change that no changed unit indexes.
+    struct UnindexedNewTable;
+
+    static UNINDEXED: crate::rules::CustomRuleMeta = crate::rules::CustomRuleMeta {
+        id: "ORG002",
+        description: "Table created without an index",
+        explain: "ORG002 — Table created without an index",
+        default_severity: crate::rules::Severity::Minor,
+    };
+
+    impl Rule for UnindexedNewTable {
+        fn id(&self) -> RuleId {
+            RuleId::Custom(&UNINDEXED)
+        }
+
+        fn default_severity(&self) -> crate::rules::Severity {
+            UNINDEXED.default_severity
+        }
+
+        fn description(&self) -> &'static str {
+            UNINDEXED.description
+        }
+
+        fn explain(&self) -> &'static str {
+            UNINDEXED.explain
+        }
+
+        fn check(&self, _: &[crate::Located<IrNode>], _: &LintContext<'_>) -> Vec<Finding> {
+            Vec::new()
+        }
+
+        fn checks_change(&self) -> bool {
+            true
+        }
+
+        fn check_change(&self, units: &[ChangedUnit], _: &ChangeContext<'_>) -> Vec<Finding> {
+            let indexed: HashSet<&str> = units
+                .iter()
+                .flat_map(|changed| &changed.unit.statements)
+                .filter_map(|stmt| match &stmt.node {
+                    IrNode::CreateIndex(ci) => Some(ci.table_name.catalog_key()),
+                    _ => None,
+                })
+                .collect();
+            let mut findings = Vec::new();
+            for changed in units {
+                for table in &changed.tables_created {
+                    if !indexed.contains(table.as_str()) {
+                        let stmt = &changed.unit.statements[0];
+                        findings.push(self.make_finding(
+                            format!("{table} has no index"),
+                            &changed.unit.source_file,
+                            &stmt.span,
+                        ));
+                    }
+                }
+            }
+            findings
+        }
+    }
+
+    #[test]
+    fn test_check_change_sees_every_changed_unit() {
+        let mut registry = RuleRegistry::assemble(&Config::default());
+        registry.register(Box::new(UnindexedNewTable)).unwrap();
+        let rules = [RuleId::Custom(&UNINDEXED)];
+        let table = |name: &str| QualifiedName::qualified("public", name);
+        let create = |file: &str, name: &str| {
+            unit(
+                file,
+                vec![IrNode::CreateTable(CreateTable::test(table(name)))],
+            )
+        };
+
+        let mut pipeline = LintPipeline::new().with_registry(registry);
+        pipeline.replay(&create("V001.sql", "legacy"));
+        pipeline.lint(&create("V002.sql", "orders"), &rules);
+        pipeline.lint(
+            &unit(
+                "V003.sql",
+                vec![IrNode::CreateIndex(CreateIndex::test(
+                    Some("idx_orders".to_string()),
+                    table("orders"),
+                ))],
+            ),
+            &rules,
+        );
+        pipeline.lint(&create("V004.sql", "audit"), &rules);
+        let mut suppressed = create("V005.sql", "events");
+        suppressed.suppressions =
+            crate::suppress::parse_suppressions("-- pgm-lint:suppress-file ORG002\n");
+        pipeline.lint(&suppressed, &rules);
+        let mut down = create("V006.sql", "scratch");
+        down.is_down = true;
+        pipeline.lint(&down, &rules);
+
+        let findings = pipeline.finish(&rules);
+        let reported: Vec<(&str, crate::rules::Severity)> = findings
+            .iter()
+            .map(|f| (f.message.as_str(), f.severity))
+            .collect();
+        assert_eq!(
+            reported,
+            vec![
+                ("public.audit has no index", crate::rules::Severity::Minor),
+                ("public.scratch has no index", crate::rules::Severity::Info),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_units_kept_only_for_active_change_checks() {
+        let table = QualifiedName::qualified("public", "orders");
+        let create = unit(
+            "V001.sql",
+            vec![IrNode::CreateTable(CreateTable::test(table))],
+        );
+
+        let mut pipeline = LintPipeline::new();
+        pipeline.lint(&create, &[RuleId::Pgm106]);
+        assert_eq!(pipeline.linted_units.len(), 1);
+        assert!(pipeline.changed_units.is_empty());
+        assert!(pipeline.change_catalog_before.is_none());
+
+        let mut pipeline = LintPipeline::new();
+        pipeline.lint(&create, &[RuleId::Pgm106, RuleId::Pgm207]);
+        assert_eq!(pipeline.changed_units.len(), 1);
+        assert!(pipeline.change_catalog_before.is_some());
+    }
+
+    #[test]
+    fn test_hooks_see_units_catalog_delta_and_findings() {
+        use std::sync::{Arc, Mutex};
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let (pre, post, run) = (events.clone(), events.clone(), events.clone());
+        let mut pipeline = LintPipeline::new()
+            .with_pre_unit_hook(move |e| {
+                pre.lock().unwrap().push(format!(
+                    "pre {} after={}",
+                    e.unit.id,
+                    e.catalog_after.is_some()
+                ));
+            })
+            .with_post_unit_hook(move |e| {
+                let created = e.catalog_after.is_some_and(|after| {
+                    after.has_table("public.products")
+                        && !e.catalog_before.has_table("public.products")
+                });
+                post.lock().unwrap().push(format!(
+                    "post {} linted={} findings={} created={created}",
+                    e.unit.id,
+                    e.linted,
+                    e.findings.len()
+                ));
+            })
+            .with_post_run_hook(move |e| {
+                run.lock()
+                    .unwrap()
+                    .push(format!("run tables={}", e.catalog.tables().count()));
+            });
+
+        pipeline.replay(&unit("V001.sql", vec![create_shipments()]));
+        pipeline.lint(
+            &unit("V002.sql", vec![create_products("json")]),
+            &[RuleId::Pgm106],
+        );
+        pipeline.finish(&[RuleId::Pgm106]);
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                "pre V001.sql after=false",
+                "post V001.sql linted=false findings=0 created=false",
+                "pre V002.sql after=false",
+                "post V002.sql linted=true findings=1 created=true",
+                "run tables=2",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_replay_collects_conflicts_and_unparseable_statements() {
+        let mut pipeline = LintPipeline::new();
+        pipeline.replay(&unit("V001.sql", vec![create_shipments()]));
+        pipeline.lint(
+            &unit(
+                "V002.sql",
+                vec![
+                    create_shipments(),
+                    IrNode::Unparseable {
+                        raw_sql: "ALTER TABLE shipments FROBNICATE".to_string(),
+                        table_hint: Some("public.shipments".to_string()),
+                    },
+                ],
+            ),
+            &[],
+        );
+
+        let warnings: Vec<_> = pipeline
+            .warnings()
+            .iter()
+            .map(|w| (w.category, w.file.to_str().unwrap()))
+            .collect();
+        assert_eq!(
+            warnings,
+            vec![
+                (WarningCategory::ReplayConflict, "V002.sql"),
+                (WarningCategory::Unparseable, "V002.sql"),
+            ]
+        );
+        assert!(pipeline.warnings()[1].message.contains("public.shipments"));
+    }
+
+    #[test]
+    fn test_strict_replay_reports_rejected_statements() {
+        let rules = [RuleId::Pgm034];
+        let lenient = {
+            let mut pipeline = LintPipeline::new();
+            pipeline.replay(&unit("V001.sql", vec![create_shipments()]));
+            pipeline.lint(&unit("V002.sql", vec![create_shipments()]), &rules)
+        };
+        assert!(lenient.is_empty());
+
+        let mut config = Config::default();
+        config.catalog.strict_replay = true;
+        let mut pipeline = LintPipeline::new().with_config(config);
+        pipeline.replay(&unit("V001.sql", vec![create_shipments()]));
+        let findings = pipeline.lint(&unit("V002.sql", vec![create_shipments()]), &rules);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule_id, RuleId::Pgm034);
+        assert_eq!(findings[0].severity, crate::rules::Severity::Blocker);
+        assert_eq!(findings[0].file, PathBuf::from("V002.sql"));
+        assert!(
+            findings[0]
+                .message
+                .contains("relation `public.shipments` already exists"),
+            "{}",
+            findings[0].message
+        );
+        assert_eq!(pipeline.warnings()[0].category, WarningCategory::Rejected);
+    }
+
+    #[test]
+    fn test_assume_unknown_tables_exist() {
+        let create_index = |name: &str, table: &str| {
+            IrNode::CreateIndex(CreateIndex::test(
+                Some(name.to_string()),
+                QualifiedName::unqualified(table),
+            ))
+        };
+        let rules = [RuleId::Pgm001];
+        let units = [
+            unit(
+                "V002.sql",
+                vec![create_index("idx_orders_status", "orders")],
+            ),
+            unit(
+                "V003.sql",
+                vec![
+                    create_index("idx_orders_total", "orders"),
+                    create_index("idx_invoices_due", "invoices"),
+                ],
+            ),
+        ];
+
+        let mut pipeline = LintPipeline::new();
+        assert!(units.iter().all(|u| pipeline.lint(u, &rules).is_empty()));
+        assert!(pipeline.finish(&rules).is_empty());
+
+        let mut config = Config::default();
+        config.catalog.assume_unknown_tables_exist = true;
+        let mut pipeline = LintPipeline::new().with_config(config);
+        let findings: Vec<Finding> = units
+            .iter()
+            .flat_map(|u| pipeline.lint(u, &rules))
+            .collect();
+        assert_eq!(findings.len(), 3);
+        assert!(findings.iter().all(|f| f.rule_id == RuleId::Pgm001));
+
+        let summary = pipeline.finish(&rules);
+        assert_eq!(summary.len(), 1);
+        assert_eq!(summary[0].rule_id, RuleId::Pgm902);
+        assert_eq!(summary[0].severity, crate::rules::Severity::Info);
+        assert_eq!(summary[0].file, PathBuf::from("V002.sql"));
+        assert_eq!(summary[0].params["decisions"], "3");
+        assert_eq!(summary[0].params["tables"], "2");
+        assert!(
+            summary[0].message.contains(": invoices, orders."),
+            "{}",
+            summary[0].message
+        );
+    }
+
+    fn create_products(type_name: &str) -> IrNode {
+        IrNode::CreateTable(
+            CreateTable::test(QualifiedName::qualified("public", "products"))
+                .with_columns(vec![ColumnDef::test("attrs", type_name)]),
+        )
+    }
+
+    #[test]
+    fn test_configured_rule_uses_pipeline_config() {
+        let mut config = Config::default();
+        config.rules.pgm111.types = vec!["xml".to_string()];
+        let mut pipeline = LintPipeline::new().with_config(config);
+
+        let findings = pipeline.lint(
+            &unit("V001.sql", vec![create_products("xml")]),
+            &[RuleId::Pgm111],
+        );
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule_id, RuleId::Pgm111);
+
+        let findings = pipeline.lint(
+            &unit("V002.sql", vec![create_products("hstore")]),
+            &[RuleId::Pgm111],
+        );
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_rule_profiling_counts_units_and_findings() {
+        let mut pipeline = LintPipeline::new();
+        pipeline.lint(
+            &unit("V001.sql", vec![create_shipments()]),
+            &[RuleId::Pgm001],
+        );
+        assert!(pipeline.rule_profile().is_none());
+
+        let mut pipeline = LintPipeline::new().with_rule_profiling(true);
+        pipeline.lint(
+            &unit("V010.sql", vec![index_shipments()]),
+            &[RuleId::Pgm001, RuleId::Pgm027],
+        );
+        pipeline.lint(
+            &unit("V011.sql", vec![create_shipments()]),
+            &[RuleId::Pgm001, RuleId::Pgm027],
+        );
+
+        let profile = pipeline.rule_profile().unwrap();
+        assert_eq!(profile.len(), 2);
+        assert_eq!(profile[&RuleId::Pgm001].units, 2);
+        assert_eq!(profile[&RuleId::Pgm001].findings, 0);
+        assert_eq!(profile[&RuleId::Pgm027].units, 2);
+        assert_eq!(profile[&RuleId::Pgm027].findings, 1);
+    }
+
+    #[test]
+    fn test_lint_incremental_uses_snapshot_as_existing_schema() {
+        let snapshot = CatalogBuilder::new()
+            .table("public.shipments", |t| {
+                t.column("id", "bigint", false).pk(&["id"]);
+            })
+            .build();
+        let index = IrNode::CreateIndex(CreateIndex::test(
+            Some("idx_shipments_order".to_string()),
+            QualifiedName::qualified("public", "shipments"),
+        ));
+
+        let units = [unit("V100.sql", vec![index])];
+        let mut pipeline = LintPipeline::new();
+        let findings = pipeline.lint_incremental(&snapshot, &units);
+        assert!(findings.iter().any(|f| f.rule_id == RuleId::Pgm001));
+
+        // A second call starts from its own snapshot, not the previous run.
+        let findings = pipeline.lint_incremental(&Catalog::new(), &units);
+        assert!(!findings.iter().any(|f| f.rule_id == RuleId::Pgm001));
+    }
+
+    #[test]
+    fn test_lint_incremental_applies_unit_suppressions() {
+        let snapshot = CatalogBuilder::new()
+            .table("public.shipments", |t| {
+                t.column("id", "bigint", false).pk(&["id"]);
+            })
+            .build();
+        let index = IrNode::CreateIndex(CreateIndex::test(
+            Some("idx_shipments_order".to_string()),
+            QualifiedName::qualified("public", "shipments"),
+        ));
+
+        let mut migration = unit("V100.sql", vec![index]);
+        migration.suppressions =
+            crate::suppress::parse_suppressions("-- pgm-lint:suppress-file PGM001\n");
+        let findings = LintPipeline::new().lint_incremental(&snapshot, &[migration]);
+        assert!(!findings.iter().any(|f| f.rule_id == RuleId::Pgm001));
+    }
+
+    fn patterns_config(toml: &str) -> Config {
+        toml::from_str(toml).unwrap()
+    }
+
+    fn alter_orders(action: AlterTableAction) -> IrNode {
+        IrNode::AlterTable(AlterTable {
+            name: QualifiedName::qualified("public", "orders"),
+            actions: vec![action],
+        })
+    }
+
+    fn create_orders() -> IrNode {
+        IrNode::CreateTable(
+            CreateTable::test(QualifiedName::qualified("public", "orders")).with_columns(vec![
+                ColumnDef::test("status", "text"),
+                ColumnDef::test("customer_id", "bigint"),
+            ]),
+        )
+    }
+
+    fn add_fk(not_valid: bool) -> IrNode {
+        alter_orders(AlterTableAction::AddConstraint(
+            TableConstraint::ForeignKey {
+                name: Some("orders_customer_fk".to_string()),
+                columns: vec!["customer_id".to_string()],
+                ref_table: QualifiedName::qualified("public", "customers"),
+                ref_columns: vec!["id".to_string()],
+                not_valid,
+            },
+        ))
+    }
+
+    #[test]
+    fn test_pattern_follow_up_checked_at_finish() {
+        let config = patterns_config(
+            "[[patterns]]\nname = \"fk-validated\"\non = \"add_foreign_key\"\n\
+             require = [\"not_valid\"]\nfollowed_by = \"validate_constraint\"",
+        );
+        let rules = [RuleId::Pgm517];
+
+        let mut pipeline = LintPipeline::new().with_config(config.clone());
+        pipeline.replay(&unit("V001.sql", vec![create_orders()]));
+        assert!(
+            pipeline
+                .lint(&unit("V010.sql", vec![add_fk(true)]), &rules)
+                .is_empty()
+        );
+        let validate = alter_orders(AlterTableAction::ValidateConstraint {
+            constraint_name: "orders_customer_fk".to_string(),
+        });
+        assert!(
+            pipeline
+                .lint(&unit("V011.sql", vec![validate]), &rules)
+                .is_empty()
+        );
+        assert!(pipeline.finish(&rules).is_empty());
+
+        let mut pipeline = LintPipeline::new().with_config(config);
+        pipeline.replay(&unit("V001.sql", vec![create_orders()]));
+        assert!(
+            pipeline
+                .lint(&unit("V010.sql", vec![add_fk(true)]), &rules)
+                .is_empty()
+        );
+        let findings = pipeline.finish(&rules);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].file, PathBuf::from("V010.sql"));
+        assert!(
+            findings[0]
+                .message
+                .contains("must be followed by VALIDATE CONSTRAINT")
+        );
+    }
+
+    #[test]
+    fn test_pattern_prerequisite_from_replayed_unit() {
+        let config = patterns_config(
+            "[[patterns]]\nname = \"not-null-via-check\"\non = \"set_not_null\"\n\
+             preceded_by = \"validated_check\"",
+        );
+        let rules = [RuleId::Pgm517];
+        let check = alter_orders(AlterTableAction::AddConstraint(TableConstraint::Check {
+            name: Some("orders_status_nn".to_string()),
+            expression: "status IS NOT NULL".to_string(),
+            not_valid: false,
+            deps: Default::default(),
+        }));
+        let set_not_null = || {
+            alter_orders(AlterTableAction::SetNotNull {
+                column_name: "status".to_string(),
+            })
+        };
+
+        let mut pipeline = LintPipeline::new().with_config(config.clone());
+        pipeline.replay(&unit("V001.sql", vec![create_orders()]));
+        let findings = pipeline.lint(&unit("V010.sql", vec![set_not_null()]), &rules);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule_id, RuleId::Pgm517);
+
+        let mut pipeline = LintPipeline::new().with_config(config);
+        pipeline.replay(&unit("V001.sql", vec![create_orders()]));
+        pipeline.replay(&unit("V002.sql", vec![check]));
+        let findings = pipeline.lint(&unit("V010.sql", vec![set_not_null()]), &rules);
+        assert!(findings.is_empty(), "{findings:?}");
+    }
+
+    #[test]
+    fn test_lint_sql_respects_suppressions() {
+        let sql = format!("-- pgm-lint:suppress-file PGM101\n{SQL}");
+        let findings = lint_sql(&sql, Path::new("V001__events.sql"), &Config::default());
+        assert!(!findings.iter().any(|f| f.rule_id == RuleId::Pgm101));
+    }
+}
@@ -0,0 +1,194 @@
+//! Expand/contract rename workflow state
+//!
+//! Teams that rename columns and tables without downtime do it in phases:
+//! add the new name and dual-write (expand), move readers over (migrate),
+//! and only then drop or rename the old object (contract). The state file
+//! records which objects are in which phase; PGM519 and PGM520 allow a
+//! `RENAME` only for objects in the contract phase.
+//!
+//! The file is TOML, checked into the repository next to the migrations:
+//!
+//! ```toml
+//! [[renames]]
+//! table = "orders"
+//! column = "status"
+//! phase = "contract"
+//!
+//! [[renames]]
+//! table = "billing.invoices"
+//! phase = "expand"
+//! ```
+//!
+//! An entry without `column` is for the table itself. Unqualified tables
+//! match in any schema, like other table lists in the config.
+
+use crate::parser::ir::QualifiedName;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use thiserror::Error;
+
+/// Errors reading or writing a rename workflow state file.
+#[derive(Debug, Error)]
+pub enum RenameStateError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("TOML parse error: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    #[error("TOML serialize error: {0}")]
+    Serialize(#[from] toml::ser::Error),
+}
+
+/// Phase of an object in the expand/contract rename workflow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RenamePhase {
+    /// The new object exists and writes go to both.
+    Expand,
+    /// Readers are moving to the new object.
+    Migrate,
+    /// Nothing uses the old name any more; it may be dropped or renamed.
+    Contract,
+}
+
+impl RenamePhase {
+    /// Lowercase name as written in the state file.
+    pub fn label(self) -> &'static str {
+        match self {
+            RenamePhase::Expand => "expand",
+            RenamePhase::Migrate => "migrate",
+            RenamePhase::Contract => "contract",
+        }
+    }
+}
+
+/// One tracked table or column.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RenameEntry {
+    /// Table name, optionally schema-qualified.
+    pub table: String,
+    /// Column name; `None` for the table itself.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+    pub phase: RenamePhase,
+}
+
+impl RenameEntry {
+    fn matches(&self, table: &QualifiedName, column: Option<&str>) -> bool {
+        let table_matches = if self.table.contains('.') {
+            self.table == table.catalog_key()
+        } else {
+            self.table == table.name
+        };
+        table_matches && self.column.as_deref() == column
+    }
+}
+
+/// Contents of a rename workflow state file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RenameState {
+    #[serde(default)]
+    pub renames: Vec<RenameEntry>,
+}
+
+impl RenameState {
+    /// Read a state file.
+    pub fn load(path: &Path) -> Result<Self, RenameStateError> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(toml::from_str(&contents)?)
+    }
+
+    /// Write the state file, replacing its contents.
+    pub fn save(&self, path: &Path) -> Result<(), RenameStateError> {
+        std::fs::write(path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Phase of `column` of `table` (or of the table itself when `column` is
+    /// `None`), if tracked.
+    pub fn phase(&self, table: &QualifiedName, column: Option<&str>) -> Option<RenamePhase> {
+        self.renames
+            .iter()
+            .find(|e| e.matches(table, column))
+            .map(|e| e.phase)
+    }
+
+    /// Track an object in `phase`, updating an existing entry for the same
+    /// table and column as written.
+    pub fn set_phase(&mut self, table: &str, column: Option<&str>, phase: RenamePhase) {
+        match self
+            .renames
+            .iter_mut()
+            .find(|e| e.table == table && e.column.as_deref() == column)
+        {
+            Some(entry) => entry.phase = phase,
+            None => self.renames.push(RenameEntry {
+                table: table.to_string(),
+                column: column.map(str::to_string),
+                phase,
+            }),
+        }
+    }
+
+    /// Stop tracking an object, e.g. once its rename has shipped. Returns
+    /// `true` if an entry was removed.
+    pub fn remove(&mut self, table: &str, column: Option<&str>) -> bool {
+        let len = self.renames.len();
+        self.renames
+            .retain(|e| !(e.table == table && e.column.as_deref() == column));
+        self.renames.len() != len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_and_phase_lookup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("renames.toml");
+
+        let mut state = RenameState::default();
+        state.set_phase("orders", Some("status"), RenamePhase::Expand);
+        state.set_phase("billing.invoices", None, RenamePhase::Migrate);
+        state.set_phase("orders", Some("status"), RenamePhase::Contract);
+        state.save(&path).unwrap();
+
+        let loaded = RenameState::load(&path).unwrap();
+        assert_eq!(loaded, state);
+        assert_eq!(loaded.renames.len(), 2);
+
+        let orders = QualifiedName::qualified("public", "orders");
+        assert_eq!(
+            loaded.phase(&orders, Some("status")),
+            Some(RenamePhase::Contract)
+        );
+        assert_eq!(loaded.phase(&orders, None), None);
+        assert_eq!(
+            loaded.phase(&QualifiedName::qualified("billing", "invoices"), None),
+            Some(RenamePhase::Migrate)
+        );
+        assert_eq!(
+            loaded.phase(&QualifiedName::qualified("public", "invoices"), None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_remove_and_unknown_fields() {
+        let mut state = RenameState::default();
+        state.set_phase("orders", Some("status"), RenamePhase::Contract);
+        assert!(state.remove("orders", Some("status")));
+        assert!(!state.remove("orders", Some("status")));
+
+        let err = toml::from_str::<RenameState>(
+            "[[renames]]\ntable = \"orders\"\nphase = \"contract\"\nowner = \"me\"",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("owner"), "{err}");
+    }
+}
@@ -15,7 +15,8 @@ use crate::rules::{Finding, LintContext, TableScope};
 /// - `stmt`: the Located wrapper (for source span)
 /// - `ctx`: the lint context
 ///
-/// Return a `Vec<Finding>` to emit findings, or an empty vec to skip.
+/// Return a `Vec<Finding>` to emit findings, or an empty vec to skip. Each
+/// finding gets a `table` template parameter unless the callback set one.
 pub fn check_alter_actions<F>(
     statements: &[Located<IrNode>],
     ctx: &LintContext<'_>,
@@ -33,7 +34,11 @@ where
                 continue;
             }
             for action in &at.actions {
-                findings.extend(check_action(at, action, stmt, ctx));
+                findings.extend(
+                    check_action(at, action, stmt, ctx)
+                        .into_iter()
+                        .map(|f| f.with_table(&at.name)),
+                );
             }
         }
     }
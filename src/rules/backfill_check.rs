@@ -0,0 +1,128 @@
+//! Shared detection of the add-column / backfill / `SET NOT NULL` sequence.
+//!
+//! Used by PGM024, which reports the whole sequence as one finding, and by
+//! PGM013 and PGM302, which skip the statements PGM024 reports.
+
+use crate::parser::ir::{AlterTableAction, IrNode, Located, QualifiedName, SourceSpan};
+use crate::rules::{LintContext, RuleId};
+
+/// One `ADD COLUMN` → `UPDATE ... SET col` → `SET NOT NULL` sequence on a
+/// pre-existing table within a single migration unit.
+pub struct Backfill<'a> {
+    pub table: &'a QualifiedName,
+    pub column: &'a str,
+    pub add_column: &'a Located<IrNode>,
+    pub update: &'a Located<IrNode>,
+    pub set_not_null: &'a Located<IrNode>,
+}
+
+impl Backfill<'_> {
+    /// Span from the `ADD COLUMN` statement through the `SET NOT NULL` statement.
+    pub fn span(&self) -> SourceSpan {
+        SourceSpan {
+            start_line: self.add_column.span.start_line,
+            end_line: self.set_not_null.span.end_line,
+            start_offset: self.add_column.span.start_offset,
+            end_offset: self.set_not_null.span.end_offset,
+        }
+    }
+
+    /// Returns `true` if `node` is this sequence's `UPDATE` statement.
+    pub fn is_update(&self, node: &IrNode) -> bool {
+        std::ptr::eq(&self.update.node, node)
+    }
+
+    /// Returns `true` if `stmt` is this sequence's `SET NOT NULL` for `column`.
+    pub fn is_set_not_null(&self, stmt: &Located<IrNode>, column: &str) -> bool {
+        std::ptr::eq(self.set_not_null, stmt) && self.column == column
+    }
+}
+
+/// Find every backfill sequence on pre-existing tables in `statements`.
+///
+/// The column must be added nullable, assigned by a later `UPDATE` on the same
+/// table, and then set `NOT NULL` by a later `ALTER TABLE`. Other statements may
+/// appear in between.
+pub fn find_backfills<'a>(
+    statements: &'a [Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Backfill<'a>> {
+    let mut backfills = Vec::new();
+
+    for (i, add_stmt) in statements.iter().enumerate() {
+        let IrNode::AlterTable(at) = &add_stmt.node else {
+            continue;
+        };
+        if !ctx.is_existing_table(at.name.catalog_key()) {
+            continue;
+        }
+        for action in &at.actions {
+            let AlterTableAction::AddColumn(col) = action else {
+                continue;
+            };
+            if !col.nullable {
+                continue;
+            }
+            let key = at.name.catalog_key();
+
+            let Some(j) = statements[i + 1..]
+                .iter()
+                .position(|s| updates_column(&s.node, key, &col.name))
+            else {
+                continue;
+            };
+            let j = i + 1 + j;
+
+            let Some(set_not_null) = statements[j + 1..]
+                .iter()
+                .find(|s| sets_not_null(&s.node, key, &col.name))
+            else {
+                continue;
+            };
+
+            backfills.push(Backfill {
+                table: &at.name,
+                column: &col.name,
+                add_column: add_stmt,
+                update: &statements[j],
+                set_not_null,
+            });
+        }
+    }
+
+    backfills
+}
+
+/// The backfill sequences PGM024 reports for this unit: none when PGM024 is
+/// not run, and none it is suppressed at.
+pub fn reported_backfills<'a>(
+    statements: &'a [Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Backfill<'a>> {
+    let mut backfills = find_backfills(statements, ctx);
+    backfills.retain(|b| ctx.reports(RuleId::Pgm024, b.span().start_line));
+    backfills
+}
+
+/// Returns `true` if `node` is an `UPDATE` on `table_key` that assigns `column`.
+fn updates_column(node: &IrNode, table_key: &str, column: &str) -> bool {
+    match node {
+        IrNode::UpdateTable(ut) => {
+            ut.table_name.catalog_key() == table_key && ut.set_columns.iter().any(|c| c == column)
+        }
+        _ => false,
+    }
+}
+
+/// Returns `true` if `node` is an `ALTER TABLE` on `table_key` that sets `column` NOT NULL.
+fn sets_not_null(node: &IrNode, table_key: &str, column: &str) -> bool {
+    match node {
+        IrNode::AlterTable(at) => {
+            at.name.catalog_key() == table_key
+                && at.actions.iter().any(|a| {
+                    matches!(a, AlterTableAction::SetNotNull { column_name } if column_name == column)
+                })
+        }
+        _ => false,
+    }
+}
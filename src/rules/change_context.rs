@@ -0,0 +1,31 @@
+use std::collections::HashSet;
+
+use crate::Catalog;
+use crate::config::Config;
+use crate::input::MigrationUnit;
+
+/// A unit linted in this run, as [`Rule::check_change`](crate::rules::Rule::check_change)
+/// sees it. Units are kept in the order they were linted.
+#[derive(Debug, Clone)]
+pub struct ChangedUnit {
+    pub unit: MigrationUnit,
+
+    /// Catalog keys of the tables the unit created (not counting
+    /// `CREATE TABLE IF NOT EXISTS` for a table that already existed).
+    pub tables_created: Vec<String>,
+}
+
+/// Context available to rules that look at the whole change.
+pub struct ChangeContext<'a> {
+    /// The catalog state before the first changed unit was applied.
+    pub catalog_before: &'a Catalog,
+
+    /// The catalog state after the last unit, changed or replayed.
+    pub catalog_after: &'a Catalog,
+
+    /// Tables created by any of the changed units.
+    pub tables_created_in_change: &'a HashSet<String>,
+
+    /// The loaded configuration (per-rule options, schema ownership).
+    pub config: &'a Config,
+}
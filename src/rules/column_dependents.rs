@@ -0,0 +1,69 @@
+//! Shared helper for rules that report what depends on a dropped or renamed column.
+//!
+//! Used by PGM009 and PGM505: look the column up in `catalog_before` and label
+//! each dependent index, constraint, and referencing foreign key for the
+//! finding message.
+
+use crate::catalog::ConstraintState;
+use crate::catalog::dependents::{ColumnDependent, column_dependents};
+use crate::rules::{Finding, LintContext};
+
+/// What depends on a column, labelled for the finding message.
+pub(super) struct Dependents {
+    /// e.g. `index 'idx_users_email'` or
+    /// `FOREIGN KEY 'sessions_user_fk' on 'sessions'`.
+    pub labels: Vec<String>,
+    /// CHECK expressions the labels quote.
+    quoted_sql: Vec<String>,
+}
+
+impl Dependents {
+    /// Record the CHECK expressions the labels quote on `finding`, so they
+    /// are masked under `output.redact_literals`.
+    pub fn quoted_on(&self, finding: Finding) -> Finding {
+        self.quoted_sql
+            .iter()
+            .fold(finding, |finding, sql| finding.with_quoted_sql(sql))
+    }
+}
+
+/// Everything that depends on `column` of `table_key` before this unit ran.
+pub(super) fn dependent_labels(ctx: &LintContext<'_>, table_key: &str, column: &str) -> Dependents {
+    let mut dependents = Dependents {
+        labels: Vec::new(),
+        quoted_sql: Vec::new(),
+    };
+    for dep in column_dependents(ctx.catalog_before, table_key, column) {
+        let label = match dep {
+            ColumnDependent::Index(idx) => format!("index '{}'", idx.name),
+            ColumnDependent::Constraint(c) => constraint_label(c, &mut dependents.quoted_sql),
+            ColumnDependent::ReferencingForeignKey { table, constraint } => format!(
+                "{} on '{}'",
+                constraint_label(constraint, &mut dependents.quoted_sql),
+                table.display_name
+            ),
+        };
+        dependents.labels.push(label);
+    }
+    dependents
+}
+
+fn constraint_label(c: &ConstraintState, quoted_sql: &mut Vec<String>) -> String {
+    let (kind, detail) = match c {
+        ConstraintState::PrimaryKey { columns, .. } => ("PRIMARY KEY", columns.join(", ")),
+        ConstraintState::ForeignKey { columns, .. } => ("FOREIGN KEY", columns.join(", ")),
+        ConstraintState::Unique { columns, .. } => ("UNIQUE", columns.join(", ")),
+        ConstraintState::Check { expression, .. } => ("CHECK", expression.to_string()),
+        ConstraintState::Exclude { .. } => ("EXCLUDE", String::new()),
+    };
+    match c.name() {
+        Some(name) => format!("{kind} '{name}'"),
+        None if detail.is_empty() => kind.to_string(),
+        None => {
+            if let ConstraintState::Check { expression, .. } = c {
+                quoted_sql.push(expression.to_string());
+            }
+            format!("{kind} ({detail})")
+        }
+    }
+}
@@ -1,6 +1,6 @@
 //! Shared column type checking logic for rules that flag specific column types.
 //!
-//! Used by PGM101-104, which all follow the same pattern: flag columns whose type
+//! Used by PGM101-104 and PGM111, which all follow the same pattern: flag columns whose type
 //! matches a predicate, across `CreateTable`, `AddColumn`, and `AlterColumnType`.
 
 use crate::Rule;
@@ -10,7 +10,8 @@ use crate::rules::{Finding, LintContext};
 /// Check all columns in CREATE TABLE and ALTER TABLE statements against a type predicate.
 ///
 /// For each column whose type matches `predicate`, a finding is emitted with a message
-/// produced by `message_fn(column_name, table_name, type_name)`.
+/// produced by `message_fn(column_name, table_name, type_name)`. The same three
+/// values are attached as the `column`, `table`, and `type` template parameters.
 pub fn check_column_types(
     statements: &[Located<IrNode>],
     ctx: &LintContext<'_>,
@@ -18,6 +19,12 @@ pub fn check_column_types(
     predicate: impl Fn(&TypeName) -> bool,
     message_fn: impl Fn(&str, &QualifiedName, &TypeName) -> String,
 ) -> Vec<Finding> {
+    let make = |col: &str, table: &QualifiedName, tn: &TypeName, stmt: &Located<IrNode>| {
+        rule.make_finding(message_fn(col, table, tn), ctx.file, &stmt.span)
+            .with_param("column", col)
+            .with_table(table)
+            .with_param("type", tn.to_string())
+    };
     let mut findings = Vec::new();
 
     for stmt in statements {
@@ -25,13 +32,7 @@ pub fn check_column_types(
             IrNode::CreateTable(ct) => {
                 for col in &ct.columns {
                     if predicate(&col.type_name) {
-                        findings.push(Finding::new(
-                            rule.id(),
-                            rule.default_severity(),
-                            message_fn(&col.name, &ct.name, &col.type_name),
-                            ctx.file,
-                            &stmt.span,
-                        ));
+                        findings.push(make(&col.name, &ct.name, &col.type_name, stmt));
                     }
                 }
             }
@@ -39,26 +40,14 @@ pub fn check_column_types(
                 for action in &at.actions {
                     match action {
                         AlterTableAction::AddColumn(col) if predicate(&col.type_name) => {
-                            findings.push(Finding::new(
-                                rule.id(),
-                                rule.default_severity(),
-                                message_fn(&col.name, &at.name, &col.type_name),
-                                ctx.file,
-                                &stmt.span,
-                            ));
+                            findings.push(make(&col.name, &at.name, &col.type_name, stmt));
                         }
                         AlterTableAction::AlterColumnType {
                             column_name,
                             new_type,
                             ..
                         } if predicate(new_type) => {
-                            findings.push(Finding::new(
-                                rule.id(),
-                                rule.default_severity(),
-                                message_fn(column_name, &at.name, new_type),
-                                ctx.file,
-                                &stmt.span,
-                            ));
+                            findings.push(make(column_name, &at.name, new_type, stmt));
                         }
                         _ => {}
                     }
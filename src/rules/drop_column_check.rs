@@ -36,6 +36,9 @@ where
             };
 
             check(name, at, table, stmt, ctx)
+                .into_iter()
+                .map(|f| f.with_param("column", name.as_str()))
+                .collect()
         },
     )
 }
@@ -1,6 +1,6 @@
 //! Shared helper for rules that flag statements targeting pre-existing tables.
 //!
-//! Used by PGM201, PGM203, PGM301, PGM302, PGM303, and PGM505, which all follow the same
+//! Used by PGM301, PGM302, and PGM303, which all follow the same
 //! pattern: iterate statements, extract a table name from a specific IR variant, check
 //! `is_existing_table`, and emit a finding.
 
@@ -8,7 +8,8 @@ use crate::parser::ir::{IrNode, Located, QualifiedName};
 use crate::rules::{Finding, LintContext, Rule};
 
 /// Iterate statements, calling `extract` on each `IrNode`. When the closure returns
-/// `Some((table_name, message))` and the table is pre-existing, a finding is emitted.
+/// `Some((table_name, message))` and the table is pre-existing, a finding is emitted
+/// with the table as its `table` template parameter.
 ///
 /// The closure should pattern-match the specific `IrNode` variant it cares about and
 /// return `None` for all other variants.
@@ -25,7 +26,8 @@ pub fn check_existing_table(
         {
             let finding = rule
                 .make_finding(message, ctx.file, &stmt.span)
-                .with_dedup_key(table_name.catalog_key().to_string());
+                .with_dedup_key(table_name.catalog_key().to_string())
+                .with_table(table_name);
             findings.push(finding);
         }
     }
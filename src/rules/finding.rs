@@ -1,8 +1,11 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::path::{Path, PathBuf};
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
+use crate::input::MigrationUnit;
+use crate::output::redact::{redact_literal_value, redact_literals};
+use crate::parser::ir::QualifiedName;
 use crate::{RuleId, Severity, parser::SourceSpan};
 
 #[derive(Debug, Clone, Serialize)]
@@ -21,6 +24,61 @@ pub struct Finding {
     /// collapse multiple DML findings on the same table into one.
     #[serde(skip)]
     pub dedup_key: Option<String>,
+    /// Structured values the message was built from (e.g. `table`, `column`),
+    /// available as `{name}` placeholders in `[messages]` templates.
+    #[serde(skip)]
+    pub params: BTreeMap<&'static str, String>,
+    /// Text from the migration that the message or parameters quote, as
+    /// `(as written, literals masked)`. See [`redact`](Self::redact).
+    #[serde(skip)]
+    pub quoted: Vec<(String, String)>,
+    /// Schema-qualified name (catalog key) of the table the finding is
+    /// about, when there is one. Set by the rule, or by the pipeline from
+    /// the statement the finding points at. `[[rules.overrides]]` match it.
+    #[serde(skip)]
+    pub table: Option<String>,
+    /// Set when a `pgm-lint:acknowledge` comment downgraded this finding.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acknowledged: Option<Acknowledgment>,
+    /// Text of the statement the finding points at, when
+    /// `output.include_snippets` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+    /// The migration unit the finding belongs to. Set by the pipeline;
+    /// reporters show it so a changeset in a single-file changelog can be
+    /// found by id.
+    #[serde(skip)]
+    pub unit: Option<UnitRef>,
+}
+
+/// Identity of the migration unit a finding belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnitRef {
+    /// Changeset id (Liquibase) or file name (SQL migrations).
+    pub id: String,
+    /// Changeset author, when the changelog names one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+}
+
+impl From<&MigrationUnit> for UnitRef {
+    fn from(unit: &MigrationUnit) -> Self {
+        Self {
+            id: unit.id.clone(),
+            author: unit.changeset.author.clone(),
+        }
+    }
+}
+
+/// Record of an acknowledged finding: the finding is kept but reported as
+/// INFO.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Acknowledgment {
+    /// Ticket reference from `ticket=...`, if given.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ticket: Option<String>,
+    /// Severity the rule reported before acknowledgment.
+    pub original_severity: Severity,
 }
 
 #[allow(clippy::ptr_arg)] // serde serialize_with requires &PathBuf, not &Path
@@ -48,6 +106,12 @@ impl Finding {
             start_line: span.start_line,
             end_line: span.end_line,
             dedup_key: None,
+            params: BTreeMap::new(),
+            quoted: Vec::new(),
+            table: None,
+            acknowledged: None,
+            snippet: None,
+            unit: None,
         }
     }
 
@@ -56,6 +120,96 @@ impl Finding {
         self.dedup_key = Some(key);
         self
     }
+
+    /// Record the table the finding is about, consuming and returning self.
+    ///
+    /// Sets [`table`](Self::table) to the catalog key and the `table`
+    /// template parameter to the name as written. Existing values are kept.
+    pub fn with_table(mut self, name: &QualifiedName) -> Self {
+        self.table
+            .get_or_insert_with(|| name.catalog_key().to_string());
+        self.with_param("table", name.display_name())
+    }
+
+    /// The finding's unit when it is a changeset within the file, or `None`
+    /// when there is no unit or the unit is the file itself (a SQL
+    /// migration, whose id is its file name).
+    pub fn changeset(&self) -> Option<&UnitRef> {
+        let unit = self.unit.as_ref()?;
+        let whole_file = self
+            .file
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy() == unit.id);
+        (!whole_file).then_some(unit)
+    }
+
+    /// [`changeset`](Self::changeset) as shown next to the file and line,
+    /// e.g. `changeset 42 by alice`.
+    pub fn unit_label(&self) -> Option<String> {
+        let unit = self.changeset()?;
+        Some(match &unit.author {
+            Some(author) => format!("changeset {} by {author}", unit.id),
+            None => format!("changeset {}", unit.id),
+        })
+    }
+
+    /// Attach a template parameter, consuming and returning self.
+    ///
+    /// An existing value for `name` is kept, so a rule can set a more
+    /// specific value before a shared helper fills in the generic one.
+    pub fn with_param(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.params.entry(name).or_insert_with(|| value.into());
+        self
+    }
+
+    /// Record SQL from the migration, such as a CHECK expression, that the
+    /// message or a parameter quotes, so [`redact`](Self::redact) can mask
+    /// its literals. Consumes and returns self.
+    pub fn with_quoted_sql(mut self, sql: &str) -> Self {
+        let masked = redact_literals(sql);
+        if masked != sql {
+            self.quoted.push((sql.to_string(), masked));
+        }
+        self
+    }
+
+    /// Attach template parameter `name` listing literal `values` from the
+    /// migration, joined with `, `, and record them for
+    /// [`redact`](Self::redact). Consumes and returns self.
+    pub fn with_literal_param(mut self, name: &'static str, values: &[impl AsRef<str>]) -> Self {
+        let join = |f: fn(&str) -> String| {
+            values
+                .iter()
+                .map(|v| f(v.as_ref()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let (written, masked) = (join(str::to_string), join(redact_literal_value));
+        if !written.is_empty() {
+            self.quoted.push((written.clone(), masked));
+        }
+        self.with_param(name, written)
+    }
+
+    /// Mask the literals in the quoted migration text wherever it appears in
+    /// the message and parameters (`output.redact_literals`). Run before
+    /// `[messages]` templates, so no template can bring the text back.
+    pub fn redact(&mut self) {
+        // Longest first, so text quoted inside a longer quote is masked as
+        // part of it.
+        self.quoted
+            .sort_by_key(|(written, _)| std::cmp::Reverse(written.len()));
+        for (written, masked) in &self.quoted {
+            if self.message.contains(written.as_str()) {
+                self.message = self.message.replace(written.as_str(), masked);
+            }
+            for value in self.params.values_mut() {
+                if value.contains(written.as_str()) {
+                    *value = value.replace(written.as_str(), masked);
+                }
+            }
+        }
+    }
 }
 
 /// Remove duplicate findings that share the same `(rule_id, dedup_key)`.
@@ -71,6 +225,25 @@ pub fn dedup_findings(findings: &mut Vec<Finding>) {
     });
 }
 
+/// Sort findings into the order every reporter emits them in.
+///
+/// The order is: file path (compared with `/` separators, so Windows and
+/// Linux agents agree), start line, end line, rule id, then message. The
+/// result depends only on the findings themselves, never on the order rules
+/// ran in or the platform, so report artifacts from different CI agents can
+/// be diffed directly.
+pub fn sort_findings(findings: &mut [Finding]) {
+    findings.sort_by_cached_key(|f| {
+        (
+            f.file.to_string_lossy().replace('\\', "/"),
+            f.start_line,
+            f.end_line,
+            f.rule_id,
+            f.message.clone(),
+        )
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,6 +304,46 @@ mod tests {
         assert_eq!(findings.len(), 2);
     }
 
+    #[test]
+    fn sort_orders_by_file_line_then_rule() {
+        let at = |file: &str, line: usize, rule_id: RuleId| {
+            let mut f = make_finding(rule_id, None, line);
+            f.file = PathBuf::from(file);
+            f
+        };
+        let mut findings = vec![
+            at("db/V002.sql", 1, RuleId::Pgm001),
+            at("db/V001.sql", 7, RuleId::Pgm001),
+            at("db\\V001.sql", 3, RuleId::Pgm501),
+            at("db/V001.sql", 3, RuleId::Pgm003),
+        ];
+        sort_findings(&mut findings);
+        let order: Vec<_> = findings.iter().map(|f| (f.start_line, f.rule_id)).collect();
+        assert_eq!(
+            order,
+            vec![
+                (3, RuleId::Pgm003),
+                (3, RuleId::Pgm501),
+                (7, RuleId::Pgm001),
+                (1, RuleId::Pgm001),
+            ]
+        );
+    }
+
+    #[test]
+    fn redact_masks_quoted_text_in_message_and_params() {
+        let mut f = make_finding(RuleId::Pgm528, None, 1);
+        f.message = "CHECK (owner <> 'ops@example.com') lists pending, 42.".to_string();
+        let mut f = f
+            .with_quoted_sql("owner <> 'ops@example.com'")
+            .with_literal_param("values", &["pending", "42"])
+            .with_param("column", "owner");
+        f.redact();
+        assert_eq!(f.message, "CHECK (owner <> '?') lists '?', ?.");
+        assert_eq!(f.params["values"], "'?', ?");
+        assert_eq!(f.params["column"], "owner");
+    }
+
     #[test]
     fn dedup_after_suppression_promotes_second() {
         // Simulate: first finding was removed by suppression, second survives dedup
@@ -1,6 +1,14 @@
-use std::{collections::HashSet, path::Path};
+use std::cell::RefCell;
+use std::{
+    collections::{BTreeSet, HashSet},
+    path::Path,
+};
 
 use crate::catalog::types::IndexState;
+use crate::config::Config;
+use crate::parser::ir::QualifiedName;
+use crate::rules::{PatternHistory, PendingReferences, RuleId};
+use crate::suppress::Suppressions;
 use crate::{Catalog, rules::TableScope};
 
 /// Context available to rules during linting.
@@ -28,14 +36,74 @@ pub struct LintContext<'a> {
 
     /// The source file being linted.
     pub file: &'a Path,
+
+    /// The loaded configuration (per-rule options, schema ownership).
+    pub config: &'a Config,
+
+    /// References made by earlier changed units to tables or columns that
+    /// did not exist yet (PGM027).
+    pub pending_references: &'a PendingReferences,
+
+    /// Pattern-relevant operations from earlier units (PGM517).
+    pub pattern_history: &'a PatternHistory,
+
+    /// Tables missing from `catalog_before` that
+    /// [`is_existing_table`](Self::is_existing_table) reported as existing
+    /// because `catalog.assume_unknown_tables_exist` is set. The pipeline
+    /// collects them for the PGM902 summary.
+    pub assumed_tables: RefCell<BTreeSet<String>>,
+
+    /// The rules run on this unit.
+    pub active_rules: &'a [RuleId],
+
+    /// Inline suppressions of the unit, for rules that defer to another
+    /// rule only when that rule's finding will be reported.
+    pub suppressions: &'a Suppressions,
 }
 
 impl<'a> LintContext<'a> {
+    /// Whether `rule` runs on this unit and is not suppressed at `line`, so a
+    /// finding it makes there is reported.
+    pub fn reports(&self, rule: RuleId, line: usize) -> bool {
+        self.active_rules.contains(&rule) && !self.suppressions.is_suppressed(rule, line)
+    }
+
+    /// Check whether `name` appears in a config table list.
+    ///
+    /// Entries are matched against the qualified catalog key (`schema.table`);
+    /// unqualified entries match the table in any schema.
+    pub fn table_in_list(&self, name: &QualifiedName, list: &[String]) -> bool {
+        list.iter().any(|entry| {
+            if entry.contains('.') {
+                entry == name.catalog_key()
+            } else {
+                *entry == name.name
+            }
+        })
+    }
+
     /// Check if a table existed before this change and was not created in the
     /// current set of changed files.
+    ///
+    /// With `catalog.assume_unknown_tables_exist`, a table the catalog does
+    /// not have counts as existing too (its history was squashed away), and
+    /// is recorded in [`assumed_tables`](Self::assumed_tables).
     pub fn is_existing_table(&self, table_key: &str) -> bool {
-        self.catalog_before.has_table(table_key)
-            && !self.tables_created_in_change.contains(table_key)
+        if self.tables_created_in_change.contains(table_key) {
+            return false;
+        }
+        self.catalog_before.has_table(table_key) || self.assume_exists(table_key)
+    }
+
+    /// Whether an unknown table is assumed to exist, recording the decision.
+    fn assume_exists(&self, table_key: &str) -> bool {
+        if !self.config.catalog.assume_unknown_tables_exist {
+            return false;
+        }
+        self.assumed_tables
+            .borrow_mut()
+            .insert(table_key.to_string());
+        true
     }
 
     /// Check whether a partition child should be exempt from PK-related rules
@@ -96,7 +164,11 @@ impl<'a> LintContext<'a> {
     pub fn table_matches_scope(&self, table_key: &str, scope: TableScope) -> bool {
         match scope {
             TableScope::ExcludeCreatedInChange => self.is_existing_table(table_key),
-            TableScope::AnyPreExisting => self.catalog_before.has_table(table_key),
+            TableScope::AnyPreExisting => {
+                self.catalog_before.has_table(table_key)
+                    || (!self.tables_created_in_change.contains(table_key)
+                        && self.assume_exists(table_key))
+            }
         }
     }
 }
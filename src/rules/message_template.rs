@@ -0,0 +1,168 @@
+//! Configurable finding message templates (`[messages]`).
+//!
+//! Rules build their messages in code. A template rewrites a finding's
+//! message from named parameters: the built-in ones (`message`, `rule_id`,
+//! `severity`, `description`, `file`, `line`) plus whatever the rule attached
+//! with [`Finding::with_param`]. This lets teams append remediation links or
+//! translate messages without touching rule code.
+
+use crate::config::MessagesConfig;
+use crate::rules::{Finding, Rule};
+
+/// Check that `template` has balanced braces.
+///
+/// `{{` and `}}` are literal braces; every other `{` must be closed by a `}`
+/// before the next `{`.
+pub fn check_message_template(template: &str) -> Result<(), String> {
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+            }
+            '{' => loop {
+                match chars.next() {
+                    Some('}') => break,
+                    Some('{') => return Err("nested '{' inside a placeholder".to_string()),
+                    Some(_) => {}
+                    None => return Err("unclosed '{'".to_string()),
+                }
+            },
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+            }
+            '}' => return Err("unmatched '}' (write '}}' for a literal brace)".to_string()),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Render `template`, replacing each `{name}` with `lookup(name)`.
+///
+/// Placeholders `lookup` does not know are kept verbatim, so a template
+/// written for one rule's parameters degrades gracefully on another.
+fn render(template: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(pos) = rest.find(['{', '}']) {
+        out.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+        } else if let Some(end) = tail.strip_prefix('{').and_then(|t| t.find('}')) {
+            let name = &tail[1..=end];
+            match lookup(name) {
+                Some(value) => out.push_str(&value),
+                None => out.push_str(&tail[..end + 2]),
+            }
+            rest = &tail[end + 2..];
+        } else {
+            out.push_str(&tail[..1]);
+            rest = &tail[1..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Rewrite each finding's message from its configured template, if any.
+pub fn apply_message_templates(findings: &mut [Finding], config: &MessagesConfig) {
+    if config.is_empty() {
+        return;
+    }
+    for f in findings {
+        let Some(template) = config.template_for(f.rule_id) else {
+            continue;
+        };
+        let message = render(template, |name| match name {
+            "message" => Some(f.message.clone()),
+            "rule_id" => Some(f.rule_id.to_string()),
+            "severity" => Some(f.severity.to_string()),
+            "description" => Some(f.rule_id.description().to_string()),
+            "file" => Some(f.file.to_string_lossy().replace('\\', "/")),
+            "line" => Some(f.start_line.to_string()),
+            other => f.params.get(other).cloned(),
+        });
+        f.message = message;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::SourceSpan;
+    use crate::rules::RuleId;
+    use std::path::Path;
+
+    fn finding(rule_id: RuleId) -> Finding {
+        rule_id
+            .make_finding(
+                "Built-in message.".to_string(),
+                Path::new("migrations/002.sql"),
+                &SourceSpan::at(7, 7),
+            )
+            .with_param("table", "orders")
+    }
+
+    fn config(default: Option<&str>, templates: &[(RuleId, &str)]) -> MessagesConfig {
+        MessagesConfig {
+            default: default.map(str::to_string),
+            templates: templates
+                .iter()
+                .map(|(id, t)| ((*id).into(), t.to_string()))
+                .collect(),
+            locale_file: None,
+        }
+    }
+
+    #[test]
+    fn test_default_template_appends_link() {
+        let mut findings = vec![finding(RuleId::Pgm001)];
+        let config = config(Some("{message} See https://wiki/{rule_id}."), &[]);
+        apply_message_templates(&mut findings, &config);
+        assert_eq!(
+            findings[0].message,
+            "Built-in message. See https://wiki/PGM001."
+        );
+    }
+
+    #[test]
+    fn test_rule_template_overrides_default() {
+        let mut findings = vec![finding(RuleId::Pgm001), finding(RuleId::Pgm002)];
+        let config = config(
+            Some("{message} [{severity}]"),
+            &[(RuleId::Pgm001, "Index auf '{table}' ({file}:{line})")],
+        );
+        apply_message_templates(&mut findings, &config);
+        assert_eq!(
+            findings[0].message,
+            "Index auf 'orders' (migrations/002.sql:7)"
+        );
+        assert_eq!(findings[1].message, "Built-in message. [CRITICAL]");
+    }
+
+    #[test]
+    fn test_unknown_placeholder_and_escaped_braces_kept() {
+        let mut findings = vec![finding(RuleId::Pgm001)];
+        let config = config(Some("{{{rule_id}}} {column}"), &[]);
+        apply_message_templates(&mut findings, &config);
+        assert_eq!(findings[0].message, "{PGM001} {column}");
+    }
+
+    #[test]
+    fn test_no_templates_leaves_message() {
+        let mut findings = vec![finding(RuleId::Pgm001)];
+        apply_message_templates(&mut findings, &MessagesConfig::default());
+        assert_eq!(findings[0].message, "Built-in message.");
+    }
+
+    #[test]
+    fn test_check_message_template() {
+        assert!(check_message_template("{message} {{literal}}").is_ok());
+        assert!(check_message_template("{message").is_err());
+        assert!(check_message_template("message}").is_err());
+        assert!(check_message_template("{a{b}}").is_err());
+    }
+}
@@ -3,13 +3,25 @@
 //! Each rule implements the `Rule` trait and checks for specific migration safety issues.
 //! Rules receive IR nodes and catalog state, returning findings with severity levels.
 
+use crate::config::Config;
 use crate::parser::ir::{IrNode, Located, SourceSpan};
-pub use crate::rules::finding::{Finding, dedup_findings};
+pub use crate::rules::change_context::{ChangeContext, ChangedUnit};
+pub use crate::rules::finding::{Acknowledgment, Finding, UnitRef, dedup_findings, sort_findings};
 pub use crate::rules::lint_context::LintContext;
-pub use crate::rules::rule_id::RuleId;
+pub use crate::rules::message_template::{apply_message_templates, check_message_template};
+pub use crate::rules::new_grants::NewGrants;
+pub use crate::rules::new_tables::NewTables;
+pub use crate::rules::overrides::{apply_overrides, attach_tables, exempt_tracking_tables};
+pub use crate::rules::patterns::PatternHistory;
+pub use crate::rules::pending_refs::{PendingReference, PendingReferences};
+pub use crate::rules::registry::{RegisterError, RuleRef, RuleRegistry};
+pub use crate::rules::rule_id::{CustomRuleMeta, RuleId};
 pub use crate::rules::severity::Severity;
 
 mod alter_table_check;
+mod backfill_check;
+mod change_context;
+mod column_dependents;
 mod column_type_check;
 mod drop_column_check;
 mod existing_table_check;
@@ -18,13 +30,21 @@ mod fn_volatility;
 #[cfg(test)]
 mod fn_volatility_tests;
 mod lint_context;
-mod reserved_keywords;
+mod message_template;
+mod new_grants;
+mod new_tables;
+mod overrides;
+pub mod patterns;
+mod pending_refs;
+mod registry;
+mod rename_phase;
+pub(crate) mod reserved_keywords;
 #[cfg(test)]
 mod reserved_keywords_tests;
 mod rule_id;
 mod severity;
 #[cfg(test)]
-mod test_helpers;
+pub(crate) mod test_helpers;
 
 // 0xx — Unsafe DDL
 mod pgm001;
@@ -50,6 +70,19 @@ mod pgm020;
 mod pgm021;
 mod pgm022;
 mod pgm023;
+mod pgm024;
+mod pgm025;
+mod pgm026;
+mod pgm027;
+mod pgm028;
+mod pgm029;
+mod pgm030;
+mod pgm031;
+mod pgm032;
+mod pgm033;
+mod pgm034;
+mod pgm035;
+mod pgm036;
 
 // 1xx — Type anti-patterns
 mod pgm101;
@@ -61,6 +94,8 @@ mod pgm106;
 mod pgm107;
 mod pgm108;
 mod pgm109;
+mod pgm110;
+mod pgm111;
 
 // 2xx — Destructive operations
 mod pgm201;
@@ -68,6 +103,8 @@ mod pgm202;
 mod pgm203;
 mod pgm204;
 mod pgm205;
+mod pgm206;
+mod pgm207;
 
 // 3xx — DML in migrations
 mod pgm301;
@@ -89,6 +126,25 @@ mod pgm506;
 mod pgm507;
 mod pgm508;
 mod pgm509;
+mod pgm510;
+mod pgm511;
+mod pgm512;
+mod pgm513;
+mod pgm514;
+mod pgm515;
+mod pgm516;
+mod pgm517;
+mod pgm518;
+mod pgm519;
+mod pgm520;
+mod pgm521;
+mod pgm522;
+mod pgm523;
+mod pgm524;
+mod pgm525;
+mod pgm526;
+mod pgm527;
+mod pgm528;
 
 /// Trait that every rule implements.
 pub trait Rule: Send + Sync {
@@ -114,12 +170,92 @@ pub trait Rule: Send + Sync {
     /// The caller handles down-migration severity capping and suppression filtering.
     fn check(&self, statements: &[Located<IrNode>], ctx: &LintContext<'_>) -> Vec<Finding>;
 
+    /// Run the rule once per run, against every unit linted in it.
+    ///
+    /// Called by [`LintPipeline::finish`](crate::pipeline::LintPipeline::finish)
+    /// after the last unit, for analyses that need to see past the current
+    /// unit (an index added in a sibling migration, a VALIDATE in a later
+    /// one). Findings are attributed to the unit whose file and statement
+    /// lines they point at; the caller applies that unit's suppressions,
+    /// acknowledgments, and down-migration cap.
+    ///
+    /// Most rules only implement [`check`](Self::check); the default finds
+    /// nothing.
+    fn check_change(&self, _units: &[ChangedUnit], _ctx: &ChangeContext<'_>) -> Vec<Finding> {
+        Vec::new()
+    }
+
+    /// Whether the rule implements [`check_change`](Self::check_change).
+    ///
+    /// The pipeline only keeps the linted units around for `check_change`
+    /// when an active rule returns `true` here.
+    fn checks_change(&self) -> bool {
+        false
+    }
+
     /// Convenience method to construct a Finding with this rule's ID and default severity.
     fn make_finding(&self, message: String, file: &std::path::Path, span: &SourceSpan) -> Finding {
         Finding::new(self.id(), self.default_severity(), message, file, span)
     }
 }
 
+/// A rule with options, resolved from [`Config`] once when the rule is built
+/// (see [`RuleId::instantiate`]) rather than read from
+/// [`LintContext::config`] on every check. Precomputed state such as
+/// lowercased lists or parsed patterns lives in the implementing struct.
+///
+/// The rule's metadata (severity, description, explanation) still comes from
+/// its [`RuleId`].
+trait ConfiguredRule: Send + Sync + Sized + 'static {
+    /// Resolve the rule's options from `config`.
+    fn from_config(config: &Config) -> Self;
+
+    /// Run the rule; findings are attributed to `rule`.
+    fn check(
+        &self,
+        rule: RuleId,
+        statements: &[Located<IrNode>],
+        ctx: &LintContext<'_>,
+    ) -> Vec<Finding>;
+}
+
+/// A [`ConfiguredRule`] paired with its ID, implementing [`Rule`].
+struct Configured<R> {
+    id: RuleId,
+    rule: R,
+}
+
+impl<R: ConfiguredRule> Configured<R> {
+    fn boxed(id: RuleId, config: &Config) -> Box<dyn Rule> {
+        Box::new(Self {
+            id,
+            rule: R::from_config(config),
+        })
+    }
+}
+
+impl<R: ConfiguredRule> Rule for Configured<R> {
+    fn id(&self) -> RuleId {
+        self.id
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.id.default_severity()
+    }
+
+    fn description(&self) -> &'static str {
+        self.id.description()
+    }
+
+    fn explain(&self) -> &'static str {
+        self.id.explain()
+    }
+
+    fn check(&self, statements: &[Located<IrNode>], ctx: &LintContext<'_>) -> Vec<Finding> {
+        self.rule.check(self.id, statements, ctx)
+    }
+}
+
 /// Controls which tables a rule considers "existing".
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TableScope {
@@ -135,10 +271,14 @@ pub enum TableScope {
 /// Cap all finding severities to INFO for down/rollback migrations (PGM901).
 ///
 /// Down migrations are informational only. This function mutates the
-/// findings in place, setting every severity to `Severity::Info`.
-pub fn cap_for_down_migration(findings: &mut [Finding]) {
+/// findings in place, setting every severity to `Severity::Info`, except
+/// for findings whose rule is listed in `exempt` (configured via
+/// `[meta.pgm901] exempt_rules`), which keep their original severity.
+pub fn cap_for_down_migration(findings: &mut [Finding], exempt: &[RuleId]) {
     for f in findings {
-        f.severity = Severity::Info;
+        if !exempt.contains(&f.rule_id) {
+            f.severity = Severity::Info;
+        }
     }
 }
 
@@ -172,12 +312,37 @@ mod tests {
             ),
         ];
 
-        cap_for_down_migration(&mut findings);
+        cap_for_down_migration(&mut findings, &[]);
 
         assert_eq!(findings[0].severity, Severity::Info);
         assert_eq!(findings[1].severity, Severity::Info);
     }
 
+    #[test]
+    fn test_cap_for_down_migration_respects_exempt_rules() {
+        let mut findings = vec![
+            Finding::new(
+                RuleId::Pgm201,
+                Severity::Minor,
+                "test".to_string(),
+                Path::new("test.down.sql"),
+                &SourceSpan::at(1, 1),
+            ),
+            Finding::new(
+                RuleId::Pgm502,
+                Severity::Major,
+                "test".to_string(),
+                Path::new("test.down.sql"),
+                &SourceSpan::at(2, 2),
+            ),
+        ];
+
+        cap_for_down_migration(&mut findings, &[RuleId::Pgm201]);
+
+        assert_eq!(findings[0].severity, Severity::Minor);
+        assert_eq!(findings[1].severity, Severity::Info);
+    }
+
     #[test]
     fn test_severity_ordering() {
         assert!(Severity::Info < Severity::Minor);
@@ -243,7 +408,7 @@ mod tests {
             assert_eq!(id, parsed, "round-trip failed for {s}");
             assert_eq!(id.as_str(), s.as_str());
         }
-        assert_eq!(RuleId::iter().count(), 53);
+        assert_eq!(RuleId::iter().count(), 90);
     }
 
     #[test]
@@ -262,11 +427,23 @@ mod tests {
         assert!(RuleId::Pgm201 < RuleId::Pgm301);
         assert!(RuleId::Pgm303 < RuleId::Pgm401);
         assert!(RuleId::Pgm402 < RuleId::Pgm501);
-        assert!(RuleId::Pgm509 < RuleId::Pgm901);
+        assert!(RuleId::Pgm512 < RuleId::Pgm901);
         // Within a family
         assert!(RuleId::Pgm001 < RuleId::Pgm017);
     }
 
+    #[test]
+    fn test_rule_id_family_prefix() {
+        assert_eq!(RuleId::Pgm001.family_prefix(), "0xx");
+        assert_eq!(RuleId::Pgm106.family_prefix(), "1xx");
+        assert_eq!(RuleId::Pgm511.family_prefix(), "5xx");
+        assert_eq!(RuleId::Pgm901.family_prefix(), "9xx");
+        assert_eq!(
+            RuleId::lint_families(),
+            vec!["0xx", "1xx", "2xx", "3xx", "4xx", "5xx"]
+        );
+    }
+
     #[test]
     fn test_rule_id_serialize_json() {
         let id = RuleId::Pgm003;
@@ -0,0 +1,437 @@
+//! Tables and schemas created by the changed units, and the grants the
+//! changed units issue, kept until the end of the run.
+//!
+//! A table created in one migration is often granted in the next one, so
+//! PGM526 asks its question for the change as a whole: the pipeline records
+//! every table and schema the changed units create and every `GRANT` they
+//! issue, and after the last unit reports the objects no grant reaches.
+//!
+//! Grant order matters where PostgreSQL's does: `GRANT ... ON ALL TABLES IN
+//! SCHEMA` only covers tables that exist when it runs, and `ALTER DEFAULT
+//! PRIVILEGES` only tables created after it.
+
+use std::path::{Path, PathBuf};
+
+use crate::catalog::Catalog;
+use crate::parser::ir::{Grant, IrNode, Located, SourceSpan, TablePersistence};
+use crate::rules::{Finding, Rule, RuleId};
+use crate::suppress::Suppressions;
+
+/// What a `GRANT` in a changed unit applies to.
+#[derive(Debug, Clone, PartialEq)]
+enum GrantTarget {
+    /// `ON [TABLE] t`, by catalog key.
+    Table(String),
+    /// `ON ALL TABLES IN SCHEMA s`.
+    TablesInSchema(String),
+    /// `ON SCHEMA s`.
+    Schema(String),
+    /// `ALTER DEFAULT PRIVILEGES ... GRANT`, for objects created later.
+    DefaultPrivileges,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct RecordedGrant {
+    target: GrantTarget,
+    grantees: Vec<String>,
+    /// Position in the change, to order grants against creations.
+    seq: usize,
+}
+
+/// One `CREATE TABLE` or `CREATE SCHEMA` from a changed unit.
+#[derive(Debug, Clone, PartialEq)]
+struct NewObject {
+    /// Table catalog key, or schema name when `is_schema`.
+    key: String,
+    display_name: String,
+    is_schema: bool,
+    file: PathBuf,
+    span: SourceSpan,
+    seq: usize,
+}
+
+/// Objects created and grants issued by the changed units so far, in
+/// replay order.
+#[derive(Debug, Clone, Default)]
+pub struct NewGrants {
+    objects: Vec<NewObject>,
+    grants: Vec<RecordedGrant>,
+    next_seq: usize,
+}
+
+impl NewGrants {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the tables and schemas `statements` create and the grants they
+    /// issue. Temporary tables and partitions (reached through their parent)
+    /// need no grants; an `IF NOT EXISTS` for an object in `before` creates
+    /// nothing; statements that suppress PGM526 are skipped. Unqualified
+    /// grant targets resolve against `default_schema`.
+    pub fn record(
+        &mut self,
+        statements: &[Located<IrNode>],
+        file: &Path,
+        before: &Catalog,
+        suppressions: &Suppressions,
+        default_schema: &str,
+    ) {
+        for stmt in statements {
+            let seq = self.next_seq;
+            self.next_seq += 1;
+            let suppressed = suppressions.is_suppressed(RuleId::Pgm526, stmt.span.start_line);
+            match &stmt.node {
+                IrNode::CreateTable(ct) => {
+                    let key = ct.name.catalog_key();
+                    if suppressed
+                        || ct.persistence == TablePersistence::Temporary
+                        || ct.partition_of.is_some()
+                        || (ct.if_not_exists && before.has_table(key))
+                    {
+                        continue;
+                    }
+                    self.objects.push(NewObject {
+                        key: key.to_string(),
+                        display_name: ct.name.display_name(),
+                        is_schema: false,
+                        file: file.to_path_buf(),
+                        span: stmt.span.clone(),
+                        seq,
+                    });
+                }
+                IrNode::CreateSchema {
+                    name,
+                    if_not_exists,
+                } => {
+                    let prefix = format!("{name}.");
+                    if suppressed
+                        || (*if_not_exists && before.tables().any(|t| t.name.starts_with(&prefix)))
+                    {
+                        continue;
+                    }
+                    self.objects.push(NewObject {
+                        key: name.clone(),
+                        display_name: name.clone(),
+                        is_schema: true,
+                        file: file.to_path_buf(),
+                        span: stmt.span.clone(),
+                        seq,
+                    });
+                }
+                IrNode::DropSchema(ds) => {
+                    self.objects
+                        .retain(|o| !(o.is_schema && o.key == ds.schema_name));
+                }
+                IrNode::Grant(grant) if grant.is_grant => {
+                    self.grants
+                        .extend(
+                            grant_targets(grant, default_schema)
+                                .into_iter()
+                                .map(|target| RecordedGrant {
+                                    target,
+                                    grantees: grant.grantees.clone(),
+                                    seq,
+                                }),
+                        );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// PGM526: report each recorded table that still exists in `catalog`
+    /// (the state after the last unit), and each recorded schema, that no
+    /// grant in the change reaches for one of `roles`. With no roles
+    /// configured, a grant to any role is enough.
+    pub fn check_grants(
+        &self,
+        rule: &dyn Rule,
+        roles: &[String],
+        catalog: &Catalog,
+    ) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for object in &self.objects {
+            if !object.is_schema && !catalog.has_table(&object.key) {
+                continue;
+            }
+            let covering: Vec<&RecordedGrant> =
+                self.grants.iter().filter(|g| covers(g, object)).collect();
+            let missing: Vec<&str> = if roles.is_empty() {
+                if covering.is_empty() {
+                    vec!["any role"]
+                } else {
+                    vec![]
+                }
+            } else {
+                roles
+                    .iter()
+                    .filter(|role| !covering.iter().any(|g| grants_to(g, role)))
+                    .map(String::as_str)
+                    .collect()
+            };
+            if missing.is_empty() {
+                continue;
+            }
+
+            let roles = missing.join(", ");
+            let (kind, fix) = if object.is_schema {
+                (
+                    "Schema",
+                    format!("GRANT USAGE ON SCHEMA {}", object.display_name),
+                )
+            } else {
+                ("Table", format!("GRANT ... ON {}", object.display_name))
+            };
+            let finding = rule
+                .make_finding(
+                    format!(
+                        "{kind} '{name}' is created in this change, but no migration in the \
+                         change grants privileges on it to {roles}. Roles without a grant get \
+                         'permission denied' as soon as the migration is deployed; add \
+                         {fix} TO <role>.",
+                        name = object.display_name,
+                    ),
+                    &object.file,
+                    &object.span,
+                )
+                .with_param("roles", roles);
+            if object.is_schema {
+                findings.push(finding.with_param("schema", &object.display_name));
+            } else {
+                let mut finding = finding.with_param("table", &object.display_name);
+                finding.table = Some(object.key.clone());
+                findings.push(finding);
+            }
+        }
+        findings
+    }
+}
+
+/// The targets of a `GRANT`, with table names resolved to catalog keys.
+fn grant_targets(grant: &Grant, default_schema: &str) -> Vec<GrantTarget> {
+    match grant.object_kind.as_str() {
+        "DEFAULT PRIVILEGES" => vec![GrantTarget::DefaultPrivileges],
+        "SCHEMA" => grant
+            .objects
+            .iter()
+            .map(|s| GrantTarget::Schema(s.clone()))
+            .collect(),
+        "TABLE" if grant.all_in_schema => grant
+            .objects
+            .iter()
+            .map(|s| GrantTarget::TablesInSchema(s.clone()))
+            .collect(),
+        "TABLE" => grant
+            .objects
+            .iter()
+            .map(|name| {
+                GrantTarget::Table(if name.contains('.') {
+                    name.clone()
+                } else {
+                    format!("{default_schema}.{name}")
+                })
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Whether `grant` applies to `object`.
+fn covers(grant: &RecordedGrant, object: &NewObject) -> bool {
+    match &grant.target {
+        GrantTarget::Schema(schema) => object.is_schema && *schema == object.key,
+        _ if object.is_schema => false,
+        GrantTarget::Table(key) => *key == object.key,
+        GrantTarget::TablesInSchema(schema) => {
+            grant.seq > object.seq && object.key.split_once('.').is_some_and(|(s, _)| s == schema)
+        }
+        GrantTarget::DefaultPrivileges => grant.seq < object.seq,
+    }
+}
+
+/// Whether `grant` reaches `role`, directly or through `PUBLIC`.
+fn grants_to(grant: &RecordedGrant, role: &str) -> bool {
+    grant
+        .grantees
+        .iter()
+        .any(|g| g == "PUBLIC" || g.eq_ignore_ascii_case(role))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rules::test_helpers::located;
+
+    fn create_table(name: &str) -> Located<IrNode> {
+        located(IrNode::CreateTable(CreateTable::test(
+            QualifiedName::qualified("public", name),
+        )))
+    }
+
+    fn create_schema(name: &str) -> Located<IrNode> {
+        located(IrNode::CreateSchema {
+            name: name.to_string(),
+            if_not_exists: false,
+        })
+    }
+
+    fn grant(objects: &[&str], grantees: &[&str]) -> Located<IrNode> {
+        located(Grant::test(objects, grantees).into())
+    }
+
+    fn record(grants: &mut NewGrants, stmts: &[Located<IrNode>]) {
+        grants.record(
+            stmts,
+            Path::new("migrations/V002__invoices.sql"),
+            &Catalog::new(),
+            &Suppressions::default(),
+            "public",
+        );
+    }
+
+    fn catalog(tables: &[&str]) -> Catalog {
+        let mut builder = CatalogBuilder::new();
+        for name in tables {
+            builder = builder.table(&format!("public.{name}"), |t| {
+                t.column("id", "bigint", false).pk(&["id"]);
+            });
+        }
+        builder.build()
+    }
+
+    fn roles(names: &[&str]) -> Vec<String> {
+        names.iter().map(|r| r.to_string()).collect()
+    }
+
+    #[test]
+    fn test_objects_without_grant_to_role_fire() {
+        let mut grants = NewGrants::new();
+        record(
+            &mut grants,
+            &[
+                create_schema("billing"),
+                create_table("invoices"),
+                create_table("payments"),
+                grant(&["invoices"], &["app_ro"]),
+                grant(&["payments"], &["PUBLIC"]),
+            ],
+        );
+
+        let findings = grants.check_grants(
+            &RuleId::Pgm526,
+            &roles(&["app_rw", "app_ro"]),
+            &catalog(&["invoices", "payments"]),
+        );
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_grant_in_later_unit_covers_table() {
+        let mut grants = NewGrants::new();
+        record(&mut grants, &[create_table("invoices")]);
+        record(
+            &mut grants,
+            &[located(
+                Grant::test(&["public"], &["app_rw"])
+                    .with_all_in_schema(true)
+                    .into(),
+            )],
+        );
+
+        assert!(
+            grants
+                .check_grants(
+                    &RuleId::Pgm526,
+                    &roles(&["app_rw"]),
+                    &catalog(&["invoices"])
+                )
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_grant_order_follows_postgres() {
+        let all_tables = located(
+            Grant::test(&["public"], &["app_rw"])
+                .with_all_in_schema(true)
+                .into(),
+        );
+        let default_privileges = located(
+            Grant::test(&[], &["app_rw"])
+                .with_object_kind("DEFAULT PRIVILEGES")
+                .into(),
+        );
+        let app_rw = roles(&["app_rw"]);
+        let invoices = catalog(&["invoices"]);
+
+        // ALL TABLES IN SCHEMA before the table does not cover it.
+        let mut grants = NewGrants::new();
+        record(&mut grants, &[all_tables, create_table("invoices")]);
+        assert_eq!(
+            grants
+                .check_grants(&RuleId::Pgm526, &app_rw, &invoices)
+                .len(),
+            1
+        );
+
+        // Default privileges set before the table do.
+        let mut grants = NewGrants::new();
+        record(&mut grants, &[default_privileges, create_table("invoices")]);
+        assert!(
+            grants
+                .check_grants(&RuleId::Pgm526, &app_rw, &invoices)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_no_roles_configured_needs_any_grant() {
+        let mut grants = NewGrants::new();
+        record(
+            &mut grants,
+            &[
+                create_table("invoices"),
+                create_table("payments"),
+                grant(&["public.invoices"], &["reporting"]),
+            ],
+        );
+
+        let findings =
+            grants.check_grants(&RuleId::Pgm526, &[], &catalog(&["invoices", "payments"]));
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].table.as_deref(), Some("public.payments"));
+    }
+
+    #[test]
+    fn test_dropped_temp_and_partition_tables_not_checked() {
+        let mut temp = CreateTable::test(QualifiedName::qualified("public", "scratch"));
+        temp.persistence = TablePersistence::Temporary;
+        let mut partition = CreateTable::test(QualifiedName::qualified("public", "events_2024"));
+        partition.partition_of = Some(QualifiedName::qualified("public", "events"));
+
+        let mut grants = NewGrants::new();
+        record(
+            &mut grants,
+            &[
+                create_table("dropped"),
+                located(IrNode::CreateTable(temp)),
+                located(IrNode::CreateTable(partition)),
+                create_schema("staging"),
+                located(DropSchema::test("staging").into()),
+            ],
+        );
+
+        assert!(
+            grants
+                .check_grants(
+                    &RuleId::Pgm526,
+                    &roles(&["app_rw"]),
+                    &catalog(&["scratch", "events_2024"]),
+                )
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,221 @@
+//! Tables created by the changed units, kept until the end of the run.
+//!
+//! PGM501 looks for a covering index at the end of each unit, so a table
+//! whose foreign-key indexes are added by a sibling changed migration is
+//! reported there but fine once the whole change is deployed. PGM522 asks the
+//! question for the change as a whole: the pipeline records every table the
+//! changed units create, and after the last unit checks each one's foreign
+//! keys against the final catalog.
+
+use std::path::{Path, PathBuf};
+
+use crate::catalog::Catalog;
+use crate::catalog::types::ConstraintState;
+use crate::parser::ir::{IrNode, Located, SourceSpan};
+use crate::rules::{Finding, Rule, RuleId};
+use crate::suppress::Suppressions;
+
+/// One `CREATE TABLE` from a changed unit.
+#[derive(Debug, Clone, PartialEq)]
+struct NewTable {
+    table_key: String,
+    display_name: String,
+    file: PathBuf,
+    span: SourceSpan,
+}
+
+/// Tables created by the changed units so far, in replay order.
+#[derive(Debug, Clone, Default)]
+pub struct NewTables {
+    tables: Vec<NewTable>,
+}
+
+impl NewTables {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the tables `statements` create. A `CREATE TABLE IF NOT EXISTS`
+    /// for a table in `before` creates nothing, and statements that suppress
+    /// PGM522 are skipped.
+    pub fn record(
+        &mut self,
+        statements: &[Located<IrNode>],
+        file: &Path,
+        before: &Catalog,
+        suppressions: &Suppressions,
+    ) {
+        for stmt in statements {
+            let IrNode::CreateTable(ct) = &stmt.node else {
+                continue;
+            };
+            let key = ct.name.catalog_key();
+            if (ct.if_not_exists && before.has_table(key))
+                || suppressions.is_suppressed(RuleId::Pgm522, stmt.span.start_line)
+            {
+                continue;
+            }
+            self.tables.push(NewTable {
+                table_key: key.to_string(),
+                display_name: ct.name.display_name(),
+                file: file.to_path_buf(),
+                span: stmt.span.clone(),
+            });
+        }
+    }
+
+    /// PGM522: report each recorded table that still exists in `catalog`
+    /// (the state after the last unit) and has foreign keys no index covers.
+    ///
+    /// Foreign keys added to the table by later changed units count too.
+    /// Partition children are covered by their parent's indexes, as in
+    /// PGM501.
+    pub fn check_foreign_key_coverage(&self, rule: &dyn Rule, catalog: &Catalog) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for new_table in &self.tables {
+            let Some(table) = catalog.get_table(&new_table.table_key) else {
+                continue;
+            };
+            if table.incomplete {
+                continue;
+            }
+            let parent = table
+                .parent_table
+                .as_ref()
+                .and_then(|key| catalog.get_table(key));
+            let uncovered: Vec<String> = table
+                .constraints
+                .iter()
+                .filter_map(|c| match c {
+                    ConstraintState::ForeignKey { columns, .. } => Some(columns),
+                    _ => None,
+                })
+                .filter(|columns| {
+                    !table.has_covering_index(columns)
+                        && !parent.is_some_and(|p| p.has_covering_index(columns))
+                })
+                .map(|columns| format!("({})", columns.join(", ")))
+                .collect();
+            if uncovered.is_empty() {
+                continue;
+            }
+
+            let columns = uncovered.join(", ");
+            let mut finding = rule
+                .make_finding(
+                    format!(
+                        "Table '{table}' is created in this change with foreign key \
+                         columns {columns}, but no migration in the change creates an \
+                         index covering them. Deletes and updates on the referenced \
+                         tables will scan '{table}'; add the indexes in this change.",
+                        table = new_table.display_name,
+                    ),
+                    &new_table.file,
+                    &new_table.span,
+                )
+                .with_param("table", &new_table.display_name)
+                .with_param("columns", columns);
+            finding.table = Some(new_table.table_key.clone());
+            findings.push(finding);
+        }
+        findings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rules::test_helpers::located;
+
+    fn create(name: &str) -> Located<IrNode> {
+        located(IrNode::CreateTable(CreateTable::test(
+            QualifiedName::unqualified(name),
+        )))
+    }
+
+    fn record(tables: &mut NewTables, names: &[&str]) {
+        let stmts: Vec<_> = names.iter().map(|n| create(n)).collect();
+        tables.record(
+            &stmts,
+            Path::new("migrations/V002__order_items.sql"),
+            &Catalog::new(),
+            &Suppressions::default(),
+        );
+    }
+
+    #[test]
+    fn test_fk_without_index_in_change_fires() {
+        let mut tables = NewTables::new();
+        record(&mut tables, &["order_items"]);
+
+        let catalog = CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "bigint", false).pk(&["id"]);
+            })
+            .table("order_items", |t| {
+                t.column("id", "bigint", false)
+                    .column("order_id", "bigint", false)
+                    .column("product_id", "bigint", false)
+                    .pk(&["id"])
+                    .fk("fk_order", &["order_id"], "orders", &["id"])
+                    .fk("fk_product", &["product_id"], "products", &["id"])
+                    .index("idx_items_product", &["product_id"], false);
+            })
+            .build();
+
+        let findings = tables.check_foreign_key_coverage(&RuleId::Pgm522, &catalog);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_index_from_sibling_unit_no_finding() {
+        let mut tables = NewTables::new();
+        record(&mut tables, &["order_items"]);
+
+        // The index was created by a later changed unit; only the final
+        // catalog is consulted.
+        let catalog = CatalogBuilder::new()
+            .table("order_items", |t| {
+                t.column("id", "bigint", false)
+                    .column("order_id", "bigint", false)
+                    .pk(&["id"])
+                    .fk("fk_order", &["order_id"], "orders", &["id"])
+                    .index("idx_items_order", &["order_id", "id"], false);
+            })
+            .build();
+
+        assert!(
+            tables
+                .check_foreign_key_coverage(&RuleId::Pgm522, &catalog)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_dropped_or_guarded_table_not_checked() {
+        let before = CatalogBuilder::new()
+            .table("audit_log", |t| {
+                t.column("user_id", "bigint", false)
+                    .fk("fk_user", &["user_id"], "users", &["id"]);
+            })
+            .build();
+        let mut tables = NewTables::new();
+        let guarded =
+            CreateTable::test(QualifiedName::unqualified("audit_log")).with_if_not_exists(true);
+        tables.record(
+            &[located(IrNode::CreateTable(guarded)), create("scratch")],
+            Path::new("migrations/V002.sql"),
+            &before,
+            &Suppressions::default(),
+        );
+
+        // `scratch` was dropped again later in the change.
+        assert!(
+            tables
+                .check_foreign_key_coverage(&RuleId::Pgm522, &before)
+                .is_empty()
+        );
+    }
+}
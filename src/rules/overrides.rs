@@ -0,0 +1,182 @@
+//! Per-rule table filters (`[[rules.overrides]]`).
+//!
+//! An override restricts one rule to matching tables (`tables`) or exempts
+//! matching tables from it (`exclude_tables`). Overrides are applied to the
+//! findings after the rules have run, using the table recorded on each
+//! finding. Rules that do not record one fall back to the table targeted by
+//! the statement the finding points at. Findings with no table are kept.
+//!
+//! Findings on the bookkeeping tables of migration tools are dropped the
+//! same way unless `[rules] lint_tracking_tables` is set: those tables are
+//! written by the tools themselves, not designed by the team.
+
+use crate::config::RuleOverride;
+use crate::parser::ir::{IrNode, Located};
+use crate::rules::Finding;
+use crate::rules::pgm110::glob_match;
+
+/// Bookkeeping tables of Liquibase, Rails/golang-migrate, and Flyway.
+pub const TRACKING_TABLES: &[&str] = &[
+    "databasechangelog",
+    "databasechangeloglock",
+    "schema_migrations",
+    "flyway_schema_history",
+];
+
+/// Record the statement's target table on findings that have none.
+pub fn attach_tables(findings: &mut [Finding], statements: &[Located<IrNode>]) {
+    for f in findings.iter_mut().filter(|f| f.table.is_none()) {
+        f.table = statements
+            .iter()
+            .find(|stmt| stmt.span.start_line == f.start_line)
+            .and_then(|stmt| stmt.node.target_table())
+            .map(|name| name.catalog_key().to_string());
+    }
+}
+
+/// Drop findings that an override keeps off their table.
+pub fn apply_overrides(findings: &mut Vec<Finding>, overrides: &[RuleOverride]) {
+    if overrides.is_empty() {
+        return;
+    }
+    findings.retain(|f| {
+        let Some(table) = f.table.as_deref() else {
+            return true;
+        };
+        overrides.iter().filter(|o| o.rule == f.rule_id).all(|o| {
+            (o.tables.is_empty() || matches_any(&o.tables, table))
+                && !matches_any(&o.exclude_tables, table)
+        })
+    });
+}
+
+/// Drop findings on migration-tracking tables ([`TRACKING_TABLES`]), in any
+/// schema.
+pub fn exempt_tracking_tables(findings: &mut Vec<Finding>) {
+    findings.retain(|f| {
+        f.table.as_deref().is_none_or(|key| {
+            let name = key.split_once('.').map_or(key, |(_, name)| name);
+            !TRACKING_TABLES.iter().any(|t| t.eq_ignore_ascii_case(name))
+        })
+    });
+}
+
+/// Whether `table_key` (`schema.table`) matches one of `patterns`.
+///
+/// A pattern with a dot is matched against the whole key; one without is
+/// matched against the table name, in any schema.
+fn matches_any(patterns: &[String], table_key: &str) -> bool {
+    let name = table_key
+        .split_once('.')
+        .map_or(table_key, |(_, name)| name);
+    patterns.iter().any(|pattern| {
+        if pattern.contains('.') {
+            glob_match(pattern, table_key)
+        } else {
+            glob_match(pattern, name)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::SourceSpan;
+    use crate::parser::ir::{DropTable, QualifiedName};
+    use crate::rules::test_helpers::located_at;
+    use crate::rules::{Rule, RuleId};
+    use std::path::Path;
+
+    fn finding(rule: RuleId, line: usize, table: Option<&str>) -> Finding {
+        let mut f = Finding::new(
+            rule,
+            rule.default_severity(),
+            "message".to_string(),
+            Path::new("V002.sql"),
+            &SourceSpan::at(line, line),
+        );
+        f.table = table.map(str::to_string);
+        f
+    }
+
+    fn overrides(toml: &str) -> Vec<RuleOverride> {
+        let config: crate::config::Config = toml::from_str(toml).unwrap();
+        config.rules.overrides
+    }
+
+    #[test]
+    fn test_exclude_tables_drops_matching_findings() {
+        let overrides = overrides(
+            r#"
+[[rules.overrides]]
+rule = "PGM302"
+exclude_tables = ["public.schema_migrations", "flyway_*"]
+"#,
+        );
+        let mut findings = vec![
+            finding(RuleId::Pgm302, 1, Some("public.schema_migrations")),
+            finding(RuleId::Pgm302, 2, Some("audit.Flyway_Schema_History")),
+            finding(RuleId::Pgm302, 3, Some("public.orders")),
+            finding(RuleId::Pgm302, 4, None),
+            finding(RuleId::Pgm201, 5, Some("public.schema_migrations")),
+        ];
+        apply_overrides(&mut findings, &overrides);
+
+        let kept: Vec<usize> = findings.iter().map(|f| f.start_line).collect();
+        assert_eq!(kept, vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn test_tables_restricts_rule_to_matching_tables() {
+        let overrides = overrides(
+            r#"
+[[rules.overrides]]
+rule = "PGM201"
+tables = ["billing.*"]
+exclude_tables = ["billing.tmp_*"]
+"#,
+        );
+        let mut findings = vec![
+            finding(RuleId::Pgm201, 1, Some("billing.invoices")),
+            finding(RuleId::Pgm201, 2, Some("billing.tmp_import")),
+            finding(RuleId::Pgm201, 3, Some("public.invoices")),
+        ];
+        apply_overrides(&mut findings, &overrides);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].table.as_deref(), Some("billing.invoices"));
+    }
+
+    #[test]
+    fn test_tracking_tables_exempt_in_any_schema() {
+        let mut findings = vec![
+            finding(RuleId::Pgm201, 1, Some("public.schema_migrations")),
+            finding(RuleId::Pgm301, 2, Some("liquibase.DATABASECHANGELOG")),
+            finding(RuleId::Pgm001, 3, Some("public.flyway_schema_history")),
+            finding(RuleId::Pgm201, 4, Some("public.orders")),
+            finding(RuleId::Pgm201, 5, None),
+        ];
+        exempt_tracking_tables(&mut findings);
+
+        let kept: Vec<usize> = findings.iter().map(|f| f.start_line).collect();
+        assert_eq!(kept, vec![4, 5]);
+    }
+
+    #[test]
+    fn test_attach_tables_uses_statement_target() {
+        let statements = vec![located_at(
+            DropTable::test(QualifiedName::qualified("public", "orders")).into(),
+            7,
+        )];
+        let mut findings = vec![
+            finding(RuleId::Pgm201, 7, None),
+            finding(RuleId::Pgm201, 7, Some("public.kept")),
+            finding(RuleId::Pgm201, 9, None),
+        ];
+        attach_tables(&mut findings, &statements);
+
+        assert_eq!(findings[0].table.as_deref(), Some("public.orders"));
+        assert_eq!(findings[1].table.as_deref(), Some("public.kept"));
+        assert_eq!(findings[2].table, None);
+    }
+}
@@ -0,0 +1,565 @@
+//! Required migration patterns from `[[patterns]]` (PGM517).
+//!
+//! Platform teams describe how a risky operation must be rolled out, for
+//! example "SET NOT NULL only after a validated CHECK in an earlier
+//! migration" or "a new foreign key is added NOT VALID and validated later in
+//! the same change". Each `[[patterns]]` entry names the operation it applies
+//! to (`on`) and what it requires:
+//!
+//! - `require`: options the operation itself must use (`not_valid`,
+//!   `concurrently`).
+//! - `preceded_by`: a related operation must appear earlier in the migration
+//!   history, in a changed or an already-merged unit.
+//! - `followed_by`: a related operation must appear later in the changed
+//!   units. This can only be decided once every unit has been linted, so it
+//!   is checked by [`LintPipeline::finish`](crate::LintPipeline::finish).
+//!
+//! Two operations are related when they are on the same table and either
+//! name the same constraint or share a column.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::catalog::types::expression_mentions_column;
+use crate::catalog::{Catalog, ConstraintState};
+use crate::config::PatternConfig;
+use crate::parser::SourceSpan;
+use crate::parser::ir::{
+    AlterTableAction, IndexColumn, IrNode, Located, QualifiedName, TableConstraint,
+};
+use crate::rules::{Finding, Rule, RuleId, Severity};
+use crate::suppress::Suppressions;
+
+/// An operation a pattern can apply to or require.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PatternOp {
+    AddColumn,
+    DropColumn,
+    SetNotNull,
+    AlterColumnType,
+    RenameColumn,
+    AddForeignKey,
+    AddCheck,
+    AddUnique,
+    AddPrimaryKey,
+    ValidateConstraint,
+    /// A CHECK that holds for existing rows: added without NOT VALID, or
+    /// validated with VALIDATE CONSTRAINT.
+    ValidatedCheck,
+    CreateIndex,
+}
+
+impl PatternOp {
+    /// How the operation is named in finding messages.
+    pub fn label(self) -> &'static str {
+        match self {
+            PatternOp::AddColumn => "ADD COLUMN",
+            PatternOp::DropColumn => "DROP COLUMN",
+            PatternOp::SetNotNull => "SET NOT NULL",
+            PatternOp::AlterColumnType => "ALTER COLUMN TYPE",
+            PatternOp::RenameColumn => "RENAME COLUMN",
+            PatternOp::AddForeignKey => "ADD FOREIGN KEY",
+            PatternOp::AddCheck => "ADD CHECK",
+            PatternOp::AddUnique => "ADD UNIQUE",
+            PatternOp::AddPrimaryKey => "ADD PRIMARY KEY",
+            PatternOp::ValidateConstraint => "VALIDATE CONSTRAINT",
+            PatternOp::ValidatedCheck => "a validated CHECK",
+            PatternOp::CreateIndex => "CREATE INDEX",
+        }
+    }
+
+    /// True if `operation` is an instance of this operation.
+    fn matches(self, operation: &PatternOperation) -> bool {
+        match self {
+            PatternOp::ValidatedCheck => match operation.op {
+                PatternOp::AddCheck => !operation.not_valid,
+                PatternOp::ValidateConstraint => operation.validates_check,
+                _ => false,
+            },
+            op => operation.op == op,
+        }
+    }
+}
+
+/// An option a pattern's operation must use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PatternFlag {
+    /// `ADD CONSTRAINT ... NOT VALID` (foreign keys and CHECKs).
+    NotValid,
+    /// `CREATE INDEX CONCURRENTLY`.
+    Concurrently,
+}
+
+impl PatternFlag {
+    pub fn label(self) -> &'static str {
+        match self {
+            PatternFlag::NotValid => "NOT VALID",
+            PatternFlag::Concurrently => "CONCURRENTLY",
+        }
+    }
+
+    /// Operations that can carry this option.
+    pub fn applies_to(self, op: PatternOp) -> bool {
+        match self {
+            PatternFlag::NotValid => matches!(op, PatternOp::AddForeignKey | PatternOp::AddCheck),
+            PatternFlag::Concurrently => op == PatternOp::CreateIndex,
+        }
+    }
+}
+
+/// One pattern-relevant operation found in a migration unit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternOperation {
+    /// Never [`PatternOp::ValidatedCheck`]; see [`validates_check`](Self::validates_check).
+    pub op: PatternOp,
+    /// Catalog key of the table.
+    pub table_key: String,
+    /// Display name of the table, for messages.
+    pub table: String,
+    /// Columns the operation touches. Empty when unknown.
+    pub columns: Vec<String>,
+    /// Constraint name, explicit or the one PostgreSQL would generate.
+    pub constraint: Option<String>,
+    pub not_valid: bool,
+    pub concurrently: bool,
+    /// VALIDATE CONSTRAINT of a CHECK constraint.
+    pub validates_check: bool,
+    /// The table was created earlier in the same change.
+    pub new_table: bool,
+    pub file: PathBuf,
+    pub span: SourceSpan,
+    /// Position of the unit in the run, counting from 0.
+    pub unit: usize,
+}
+
+impl PatternOperation {
+    fn has_flag(&self, flag: PatternFlag) -> bool {
+        match flag {
+            PatternFlag::NotValid => self.not_valid,
+            PatternFlag::Concurrently => self.concurrently,
+        }
+    }
+
+    /// Same table, and the same constraint or an overlapping column.
+    fn is_related(&self, other: &PatternOperation) -> bool {
+        if self.table_key != other.table_key {
+            return false;
+        }
+        if let (Some(a), Some(b)) = (&self.constraint, &other.constraint)
+            && (self.op == PatternOp::ValidateConstraint
+                || other.op == PatternOp::ValidateConstraint)
+        {
+            return a == b;
+        }
+        self.columns.is_empty()
+            || other.columns.is_empty()
+            || self.columns.iter().any(|c| other.columns.contains(c))
+    }
+
+    /// `'orders.status'` or `'orders'`, for messages.
+    fn target(&self) -> String {
+        match self.columns.as_slice() {
+            [] => format!("'{}'", self.table),
+            columns => format!("'{}.{}'", self.table, columns.join(", ")),
+        }
+    }
+}
+
+/// Pattern-relevant operations seen so far, in replay order.
+#[derive(Debug, Clone, Default)]
+pub struct PatternHistory {
+    operations: Vec<PatternOperation>,
+    /// Per operation: whether it is checked for a missing follow-up. Only
+    /// operations from linted units whose statement does not suppress PGM517
+    /// are; the rest can still satisfy other operations' patterns.
+    awaits_follow_up: Vec<bool>,
+    units: usize,
+}
+
+impl PatternHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shared empty instance, for contexts built outside the pipeline.
+    pub fn empty_ref() -> &'static PatternHistory {
+        static EMPTY: LazyLock<PatternHistory> = LazyLock::new(PatternHistory::new);
+        &EMPTY
+    }
+
+    /// Number of units recorded so far; the position the next unit gets.
+    pub fn units(&self) -> usize {
+        self.units
+    }
+
+    /// Operations recorded so far.
+    pub fn operations(&self) -> &[PatternOperation] {
+        &self.operations
+    }
+
+    /// Record the operations of one unit that any of `patterns` mentions.
+    /// `suppressions` is `None` for units whose operations are not checked
+    /// for follow-ups: replayed units and down migrations.
+    pub fn record(
+        &mut self,
+        patterns: &[PatternConfig],
+        operations: Vec<PatternOperation>,
+        suppressions: Option<&Suppressions>,
+    ) {
+        let mentioned = |op: &PatternOperation| {
+            patterns.iter().any(|p| {
+                [Some(p.on), p.preceded_by, p.followed_by]
+                    .into_iter()
+                    .flatten()
+                    .any(|mentioned| mentioned.matches(op))
+            })
+        };
+        for operation in operations.into_iter().filter(mentioned) {
+            self.awaits_follow_up.push(
+                suppressions
+                    .is_some_and(|s| !s.is_suppressed(RuleId::Pgm517, operation.span.start_line)),
+            );
+            self.operations.push(operation);
+        }
+        self.units += 1;
+    }
+}
+
+/// Extract the pattern-relevant operations from one unit's statements.
+///
+/// `before` and `after` are the catalog around the unit; they resolve the
+/// constraint a VALIDATE CONSTRAINT refers to and the columns a CHECK
+/// mentions.
+pub fn operations(
+    statements: &[Located<IrNode>],
+    file: &Path,
+    unit: usize,
+    before: &Catalog,
+    after: &Catalog,
+    tables_created_in_change: &HashSet<String>,
+) -> Vec<PatternOperation> {
+    let mut ops = Vec::new();
+
+    for stmt in statements {
+        let base = |table: &QualifiedName, op: PatternOp, columns: Vec<String>| {
+            let key = table.catalog_key();
+            PatternOperation {
+                op,
+                table_key: key.to_string(),
+                table: table.display_name(),
+                columns,
+                constraint: None,
+                not_valid: false,
+                concurrently: false,
+                validates_check: false,
+                new_table: tables_created_in_change.contains(key),
+                file: file.to_path_buf(),
+                span: stmt.span.clone(),
+                unit,
+            }
+        };
+
+        match &stmt.node {
+            IrNode::AlterTable(at) => {
+                for action in &at.actions {
+                    match action {
+                        AlterTableAction::AddColumn(col) => {
+                            ops.push(base(&at.name, PatternOp::AddColumn, vec![col.name.clone()]));
+                        }
+                        AlterTableAction::DropColumn { name } => {
+                            ops.push(base(&at.name, PatternOp::DropColumn, vec![name.clone()]));
+                        }
+                        AlterTableAction::SetNotNull { column_name } => {
+                            ops.push(base(
+                                &at.name,
+                                PatternOp::SetNotNull,
+                                vec![column_name.clone()],
+                            ));
+                        }
+                        AlterTableAction::AlterColumnType { column_name, .. } => {
+                            ops.push(base(
+                                &at.name,
+                                PatternOp::AlterColumnType,
+                                vec![column_name.clone()],
+                            ));
+                        }
+                        AlterTableAction::AddConstraint(constraint) => {
+                            let relname = &at.name.name;
+                            let (op, name, columns, not_valid) = match constraint {
+                                TableConstraint::ForeignKey {
+                                    name,
+                                    columns,
+                                    not_valid,
+                                    ..
+                                } => (
+                                    PatternOp::AddForeignKey,
+                                    name.clone().or_else(|| {
+                                        Some(format!("{relname}_{}_fkey", columns.join("_")))
+                                    }),
+                                    columns.clone(),
+                                    *not_valid,
+                                ),
+                                TableConstraint::Check {
+                                    name,
+                                    expression,
+                                    not_valid,
+                                    ..
+                                } => (
+                                    PatternOp::AddCheck,
+                                    name.clone(),
+                                    check_columns(after, at.name.catalog_key(), expression),
+                                    *not_valid,
+                                ),
+                                TableConstraint::Unique { name, columns, .. } => (
+                                    PatternOp::AddUnique,
+                                    name.clone().or_else(|| {
+                                        Some(format!("{relname}_{}_key", columns.join("_")))
+                                    }),
+                                    columns.clone(),
+                                    false,
+                                ),
+                                TableConstraint::PrimaryKey { name, columns, .. } => (
+                                    PatternOp::AddPrimaryKey,
+                                    name.clone().or_else(|| Some(format!("{relname}_pkey"))),
+                                    columns.clone(),
+                                    false,
+                                ),
+                                TableConstraint::Exclude { .. } => continue,
+                            };
+                            ops.push(PatternOperation {
+                                constraint: name,
+                                not_valid,
+                                ..base(&at.name, op, columns)
+                            });
+                        }
+                        AlterTableAction::ValidateConstraint { constraint_name } => {
+                            let constraint = before
+                                .get_table(at.name.catalog_key())
+                                .or_else(|| after.get_table(at.name.catalog_key()))
+                                .and_then(|t| t.constraint_named(constraint_name));
+                            let (columns, validates_check) = match constraint {
+                                Some(ConstraintState::Check { expression, .. }) => (
+                                    check_columns(after, at.name.catalog_key(), expression),
+                                    true,
+                                ),
+                                Some(ConstraintState::ForeignKey { columns, .. }) => {
+                                    (columns.clone(), false)
+                                }
+                                _ => (Vec::new(), false),
+                            };
+                            ops.push(PatternOperation {
+                                constraint: Some(constraint_name.clone()),
+                                validates_check,
+                                ..base(&at.name, PatternOp::ValidateConstraint, columns)
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            IrNode::CreateIndex(ci) => {
+                let columns = ci
+                    .columns
+                    .iter()
+                    .filter_map(|c| match c {
+                        IndexColumn::Column(name) => Some(name.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                ops.push(PatternOperation {
+                    constraint: ci.index_name.clone(),
+                    concurrently: ci.concurrent,
+                    ..base(&ci.table_name, PatternOp::CreateIndex, columns)
+                });
+            }
+            IrNode::RenameColumn {
+                table, old_name, ..
+            } => {
+                ops.push(base(table, PatternOp::RenameColumn, vec![old_name.clone()]));
+            }
+            _ => {}
+        }
+    }
+
+    ops
+}
+
+/// Columns of `table_key` that a CHECK expression mentions.
+fn check_columns(catalog: &Catalog, table_key: &str, expression: &str) -> Vec<String> {
+    catalog
+        .get_table(table_key)
+        .map(|t| {
+            t.columns
+                .iter()
+                .filter(|c| expression_mentions_column(expression, &c.name))
+                .map(|c| c.name.clone())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Check the `require` and `preceded_by` parts of every pattern against the
+/// operations of the unit being linted.
+///
+/// `current` are that unit's operations; `history` holds every earlier unit.
+/// Operations on tables created earlier in the same change are not checked:
+/// nobody else can be using the table yet.
+pub fn check_unit(
+    rule: &dyn Rule,
+    patterns: &[PatternConfig],
+    current: &[PatternOperation],
+    history: &PatternHistory,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for (i, operation) in current.iter().enumerate() {
+        if operation.new_table {
+            continue;
+        }
+        for pattern in patterns.iter().filter(|p| p.on.matches(operation)) {
+            let missing: Vec<&str> = pattern
+                .require
+                .iter()
+                .filter(|flag| !operation.has_flag(**flag))
+                .map(|flag| flag.label())
+                .collect();
+            if !missing.is_empty() {
+                findings.push(finding(
+                    rule,
+                    pattern,
+                    operation,
+                    format!("must use {}", missing.join(" and ")),
+                ));
+            }
+
+            let Some(prerequisite) = pattern.preceded_by else {
+                continue;
+            };
+            let same_unit = if pattern.separate_unit {
+                &[][..]
+            } else {
+                &current[..i]
+            };
+            let satisfied = history
+                .operations()
+                .iter()
+                .chain(same_unit)
+                .any(|earlier| prerequisite.matches(earlier) && earlier.is_related(operation));
+            if !satisfied {
+                findings.push(finding(
+                    rule,
+                    pattern,
+                    operation,
+                    format!(
+                        "must be preceded by {} on the same {}{}",
+                        prerequisite.label(),
+                        relation(operation),
+                        if pattern.separate_unit {
+                            " in an earlier migration"
+                        } else {
+                            ""
+                        },
+                    ),
+                ));
+            }
+        }
+    }
+
+    findings
+}
+
+/// Check the `followed_by` part of every pattern once all units are linted.
+///
+/// Only operations from linted (changed) units are checked, and only those
+/// whose statement does not suppress PGM517.
+pub fn check_follow_ups(
+    rule: &dyn Rule,
+    patterns: &[PatternConfig],
+    history: &PatternHistory,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let ops = history.operations();
+
+    for (i, operation) in ops.iter().enumerate() {
+        if !history.awaits_follow_up[i] || operation.new_table {
+            continue;
+        }
+        for pattern in patterns.iter().filter(|p| p.on.matches(operation)) {
+            let Some(follow_up) = pattern.followed_by else {
+                continue;
+            };
+            let satisfied = ops[i + 1..].iter().any(|later| {
+                (!pattern.separate_unit || later.unit > operation.unit)
+                    && follow_up.matches(later)
+                    && later.is_related(operation)
+            });
+            if !satisfied {
+                findings.push(finding(
+                    rule,
+                    pattern,
+                    operation,
+                    format!(
+                        "must be followed by {} on the same {}{}",
+                        follow_up.label(),
+                        relation(operation),
+                        if pattern.separate_unit {
+                            " in a later migration of this change"
+                        } else {
+                            " in this change"
+                        },
+                    ),
+                ));
+            }
+        }
+    }
+
+    findings
+}
+
+/// What a related operation must share with `operation`, for messages.
+fn relation(operation: &PatternOperation) -> &'static str {
+    match (&operation.constraint, operation.columns.is_empty()) {
+        (Some(_), _) if operation.op != PatternOp::CreateIndex => "constraint",
+        (_, false) => "column",
+        _ => "table",
+    }
+}
+
+fn finding(
+    rule: &dyn Rule,
+    pattern: &PatternConfig,
+    operation: &PatternOperation,
+    problem: String,
+) -> Finding {
+    let severity = pattern
+        .severity
+        .as_deref()
+        .and_then(Severity::parse)
+        .unwrap_or_else(|| rule.default_severity());
+    let mut message = format!(
+        "Pattern '{}': {} on {} {problem}.",
+        pattern.name,
+        operation.op.label(),
+        operation.target(),
+    );
+    if let Some(extra) = &pattern.message {
+        message.push(' ');
+        message.push_str(extra);
+    }
+    Finding::new(
+        rule.id(),
+        severity,
+        message,
+        &operation.file,
+        &operation.span,
+    )
+    .with_param("pattern", pattern.name.clone())
+    .with_param("table", operation.table.clone())
+    .with_param("operation", operation.op.label())
+}
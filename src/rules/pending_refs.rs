@@ -0,0 +1,287 @@
+//! References from changed units to tables or columns the catalog does not have yet.
+//!
+//! A changed unit that alters, indexes, or writes to a table no earlier
+//! migration created is normal in incremental CI (the table may predate the
+//! tracked history). It is an ordering bug when a *later* changed unit then
+//! creates that table or column: the migrations replay fine in review but the
+//! earlier one fails at deploy. The pipeline records these references as it
+//! lints and drops them once the catalog catches up; PGM027 reports a
+//! creation that satisfies one.
+
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use crate::catalog::Catalog;
+use crate::parser::ir::{
+    AlterTableAction, IndexColumn, IrNode, Located, QualifiedName, TableConstraint,
+};
+
+/// One reference to a table (or a column of it) that was missing from the
+/// catalog when the referencing unit was linted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingReference {
+    /// Catalog key of the referenced table.
+    pub table_key: String,
+    /// `None` for a reference to the table itself.
+    pub column: Option<String>,
+    /// Statement kind, for messages (e.g. `"CREATE INDEX"`).
+    pub statement: &'static str,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+/// Unresolved references recorded so far, in replay order.
+#[derive(Debug, Clone, Default)]
+pub struct PendingReferences {
+    refs: Vec<PendingReference>,
+}
+
+impl PendingReferences {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shared empty instance, for contexts built outside the pipeline.
+    pub fn empty_ref() -> &'static PendingReferences {
+        static EMPTY: LazyLock<PendingReferences> = LazyLock::new(PendingReferences::new);
+        &EMPTY
+    }
+
+    /// Add a reference directly (used by rule tests).
+    pub fn push(&mut self, reference: PendingReference) {
+        self.refs.push(reference);
+    }
+
+    /// References to the table itself.
+    pub fn for_table<'s>(
+        &'s self,
+        table_key: &'s str,
+    ) -> impl Iterator<Item = &'s PendingReference> {
+        self.refs
+            .iter()
+            .filter(move |r| r.table_key == table_key && r.column.is_none())
+    }
+
+    /// References to `column` of the table.
+    pub fn for_column<'s>(
+        &'s self,
+        table_key: &'s str,
+        column: &'s str,
+    ) -> impl Iterator<Item = &'s PendingReference> {
+        self.refs
+            .iter()
+            .filter(move |r| r.table_key == table_key && r.column.as_deref() == Some(column))
+    }
+
+    /// Drop references that `catalog` now satisfies.
+    pub fn resolve(&mut self, catalog: &Catalog) {
+        self.refs.retain(|r| match catalog.get_table(&r.table_key) {
+            None => true,
+            Some(table) => r
+                .column
+                .as_deref()
+                .is_some_and(|col| table.get_column(col).is_none()),
+        });
+    }
+
+    /// Record the references in `statements` that neither `before` nor
+    /// `after` (the catalog around the unit) can satisfy.
+    ///
+    /// Column references are only recorded for fully tracked tables, so an
+    /// `incomplete` table (touched by unparseable SQL) never contributes.
+    pub fn record(
+        &mut self,
+        statements: &[Located<IrNode>],
+        file: &Path,
+        before: &Catalog,
+        after: &Catalog,
+    ) {
+        let mut add = |table: &QualifiedName, column: Option<&str>, statement, line| {
+            let key = table.catalog_key();
+            let missing = match column {
+                None => !before.has_table(key) && !after.has_table(key),
+                Some(col) => after.get_table(key).is_some_and(|t| {
+                    !t.incomplete
+                        && t.get_column(col).is_none()
+                        && before
+                            .get_table(key)
+                            .is_none_or(|b| b.get_column(col).is_none())
+                }),
+            };
+            if missing {
+                self.refs.push(PendingReference {
+                    table_key: key.to_string(),
+                    column: column.map(str::to_string),
+                    statement,
+                    file: file.to_path_buf(),
+                    line,
+                });
+            }
+        };
+
+        for stmt in statements {
+            let line = stmt.span.start_line;
+            match &stmt.node {
+                IrNode::AlterTable(at) => {
+                    add(&at.name, None, "ALTER TABLE", line);
+                    for action in &at.actions {
+                        match action {
+                            AlterTableAction::AlterColumnType { column_name, .. }
+                            | AlterTableAction::SetNotNull { column_name }
+                            | AlterTableAction::DropNotNull { column_name }
+                            | AlterTableAction::SetDefault { column_name, .. }
+                            | AlterTableAction::DropDefault { column_name } => {
+                                add(&at.name, Some(column_name), "ALTER COLUMN", line);
+                            }
+                            AlterTableAction::AddConstraint(TableConstraint::ForeignKey {
+                                ref_table,
+                                ref_columns,
+                                ..
+                            }) => {
+                                add(ref_table, None, "FOREIGN KEY", line);
+                                for col in ref_columns {
+                                    add(ref_table, Some(col), "FOREIGN KEY", line);
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                IrNode::CreateTable(ct) => {
+                    for constraint in &ct.constraints {
+                        if let TableConstraint::ForeignKey {
+                            ref_table,
+                            ref_columns,
+                            ..
+                        } = constraint
+                        {
+                            add(ref_table, None, "FOREIGN KEY", line);
+                            for col in ref_columns {
+                                add(ref_table, Some(col), "FOREIGN KEY", line);
+                            }
+                        }
+                    }
+                }
+                IrNode::CreateIndex(ci) => {
+                    add(&ci.table_name, None, "CREATE INDEX", line);
+                    for col in &ci.columns {
+                        if let IndexColumn::Column(name) = col {
+                            add(&ci.table_name, Some(name), "CREATE INDEX", line);
+                        }
+                    }
+                }
+                IrNode::InsertInto(ins) => add(&ins.table_name, None, "INSERT", line),
+                IrNode::UpdateTable(upd) => {
+                    add(&upd.table_name, None, "UPDATE", line);
+                    for col in &upd.set_columns {
+                        add(&upd.table_name, Some(col), "UPDATE", line);
+                    }
+                }
+                IrNode::DeleteFrom(del) => add(&del.table_name, None, "DELETE", line),
+                IrNode::RenameColumn {
+                    table, old_name, ..
+                } => {
+                    add(table, None, "RENAME COLUMN", line);
+                    add(table, Some(old_name), "RENAME COLUMN", line);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rules::test_helpers::located;
+
+    fn orders_catalog() -> Catalog {
+        CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "bigint", false).pk(&["id"]);
+            })
+            .build()
+    }
+
+    fn create_index(table: &str, column: &str) -> Located<IrNode> {
+        located(IrNode::CreateIndex(
+            CreateIndex::test(Some("idx".to_string()), QualifiedName::unqualified(table))
+                .with_columns(vec![IndexColumn::Column(column.to_string())]),
+        ))
+    }
+
+    #[test]
+    fn test_records_missing_table_and_resolves_on_create() {
+        let catalog = Catalog::new();
+        let mut pending = PendingReferences::new();
+        pending.record(
+            &[create_index("shipments", "order_id")],
+            Path::new("V002.sql"),
+            &catalog,
+            &catalog,
+        );
+        assert_eq!(pending.for_table("shipments").count(), 1);
+        assert_eq!(pending.for_column("shipments", "order_id").count(), 0);
+
+        let after = CatalogBuilder::new()
+            .table("shipments", |t| {
+                t.column("order_id", "bigint", false);
+            })
+            .build();
+        pending.resolve(&after);
+        assert_eq!(pending.for_table("shipments").count(), 0);
+    }
+
+    #[test]
+    fn test_records_missing_column_on_known_table() {
+        let catalog = orders_catalog();
+        let mut pending = PendingReferences::new();
+        pending.record(
+            &[
+                create_index("orders", "status"),
+                create_index("orders", "id"),
+            ],
+            Path::new("V002.sql"),
+            &catalog,
+            &catalog,
+        );
+        assert_eq!(pending.for_table("orders").count(), 0);
+        let refs: Vec<_> = pending.for_column("orders", "status").collect();
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].statement, "CREATE INDEX");
+        assert_eq!(pending.for_column("orders", "id").count(), 0);
+    }
+
+    #[test]
+    fn test_table_created_in_same_unit_not_recorded() {
+        let before = Catalog::new();
+        let after = orders_catalog();
+        let mut pending = PendingReferences::new();
+        pending.record(
+            &[create_index("orders", "id")],
+            Path::new("V001.sql"),
+            &before,
+            &after,
+        );
+        assert_eq!(pending.for_table("orders").count(), 0);
+    }
+
+    #[test]
+    fn test_incomplete_table_columns_not_recorded() {
+        let catalog = CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "bigint", false).incomplete();
+            })
+            .build();
+        let mut pending = PendingReferences::new();
+        pending.record(
+            &[create_index("orders", "status")],
+            Path::new("V002.sql"),
+            &catalog,
+            &catalog,
+        );
+        assert_eq!(pending.for_column("orders", "status").count(), 0);
+    }
+}
@@ -59,11 +59,7 @@ pub(super) fn check(
             if let AlterTableAction::AttachPartition { child } = action {
                 let child_key = child.catalog_key();
 
-                if !ctx.catalog_before.has_table(child_key) {
-                    return vec![];
-                }
-
-                if ctx.tables_created_in_change.contains(child_key) {
+                if !ctx.is_existing_table(child_key) {
                     return vec![];
                 }
 
@@ -133,7 +133,10 @@ fn check_column(
 ) -> Option<Finding> {
     let func_name = match &col.default_expr {
         Some(DefaultExpr::FunctionCall { name, .. }) => name,
-        Some(DefaultExpr::Literal(_)) | Some(DefaultExpr::Other(_)) | None => return None,
+        Some(DefaultExpr::Literal(_) | DefaultExpr::ColumnRef(_) | DefaultExpr::Other(_))
+        | None => {
+            return None;
+        }
     };
 
     match fn_volatility::lookup(func_name) {
@@ -202,7 +205,7 @@ fn check_set_default(
 ) -> Option<Finding> {
     let func_name = match default_expr {
         DefaultExpr::FunctionCall { name, .. } => name,
-        DefaultExpr::Literal(_) | DefaultExpr::Other(_) => return None,
+        DefaultExpr::Literal(_) | DefaultExpr::ColumnRef(_) | DefaultExpr::Other(_) => return None,
     };
 
     match fn_volatility::lookup(func_name) {
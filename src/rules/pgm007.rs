@@ -4,7 +4,13 @@
 //! exist in the catalog. Most type changes require a full table rewrite under
 //! an `ACCESS EXCLUSIVE` lock. A hardcoded allowlist of safe (binary-coercible)
 //! casts suppresses the finding for known safe conversions.
+//!
+//! The finding escalates when the column is indexed or part of a primary key
+//! or unique constraint (the rewrite rebuilds those indexes too), and always
+//! fires as BLOCKER for partition key columns, which PostgreSQL refuses to
+//! alter at all.
 
+use crate::catalog::types::{ConstraintState, TableState};
 use crate::parser::ir::{AlterTableAction, IrNode, Located, TypeName};
 use crate::rules::{Finding, LintContext, Rule, Severity, TableScope, alter_table_check};
 
@@ -35,6 +41,17 @@ pub(super) const EXPLAIN: &str = "PGM007 — ALTER COLUMN TYPE on existing table
          \n\
          All other type changes fire as CRITICAL.\n\
          \n\
+         Indexed columns:\n\
+         When the column is part of an index, primary key, or unique\n\
+         constraint, every one of those indexes is rebuilt after the rewrite,\n\
+         still under the ACCESS EXCLUSIVE lock. The finding lists them and\n\
+         escalates: CRITICAL becomes BLOCKER, INFO becomes MAJOR.\n\
+         \n\
+         Partition key columns:\n\
+         PostgreSQL rejects ALTER COLUMN TYPE on a column in the table's\n\
+         partition key, so the migration fails. This fires as BLOCKER even for\n\
+         otherwise safe casts.\n\
+         \n\
          Example (bad):\n\
            ALTER TABLE orders ALTER COLUMN amount TYPE bigint;\n\
          \n\
@@ -67,11 +84,32 @@ pub(super) fn check(
             };
 
             let table_key = at.name.catalog_key();
+            let table = ctx.catalog_before.get_table(table_key);
+
+            if table.is_some_and(|t| {
+                t.partition_by
+                    .as_ref()
+                    .is_some_and(|p| p.columns.iter().any(|c| c == column_name))
+            }) {
+                return vec![Finding::new(
+                    rule.id(),
+                    Severity::Blocker,
+                    format!(
+                        "Column '{col}' is part of the partition key of '{table}'. \
+                         PostgreSQL rejects ALTER COLUMN TYPE on partition key \
+                         columns, so this migration will fail. Changing the type \
+                         requires a new partitioned table and moving the data into it.",
+                        table = at.name.display_name(),
+                        col = column_name,
+                    ),
+                    ctx.file,
+                    &stmt.span,
+                )];
+            }
 
             // Resolve old_type: prefer the one from the IR, fall back to catalog.
             let resolved_old_type = old_type.as_ref().or_else(|| {
-                ctx.catalog_before
-                    .get_table(table_key)
+                table
                     .and_then(|t| t.get_column(column_name))
                     .map(|c| &c.type_name)
             });
@@ -84,15 +122,30 @@ pub(super) fn check(
                 }
             };
 
-            let severity = match safety {
-                CastSafety::Safe => return vec![],
-                CastSafety::Info => Severity::Info,
-                CastSafety::Unsafe => rule.default_severity(),
+            let dependents = table
+                .map(|t| dependent_indexes(t, column_name))
+                .unwrap_or_default();
+            let severity = match (safety, dependents.is_empty()) {
+                (CastSafety::Safe, _) => return vec![],
+                (CastSafety::Info, true) => Severity::Info,
+                (CastSafety::Info, false) => Severity::Major,
+                (CastSafety::Unsafe, true) => rule.default_severity(),
+                (CastSafety::Unsafe, false) => Severity::Blocker,
             };
 
             let old_display = resolved_old_type
                 .map(|t| t.to_string())
                 .unwrap_or_else(|| "unknown".to_string());
+            let rebuilds = if dependents.is_empty() {
+                String::new()
+            } else {
+                format!(
+                    " The rewrite also rebuilds every index on '{col}' under the \
+                     same lock: {objects}.",
+                    col = column_name,
+                    objects = dependents.join(", "),
+                )
+            };
 
             vec![Finding::new(
                 rule.id(),
@@ -100,9 +153,9 @@ pub(super) fn check(
                 format!(
                     "Changing column type on existing table '{table}' \
                      ('{col}': {old} \u{2192} {new}) rewrites the entire table \
-                     under an ACCESS EXCLUSIVE lock. For large tables, this causes \
-                     extended downtime. Consider creating a new column, backfilling, \
-                     and swapping instead.",
+                     under an ACCESS EXCLUSIVE lock.{rebuilds} For large tables, \
+                     this causes extended downtime. Consider creating a new column, \
+                     backfilling, and swapping instead.",
                     table = at.name.display_name(),
                     col = column_name,
                     old = old_display,
@@ -115,6 +168,44 @@ pub(super) fn check(
     )
 }
 
+/// Indexes rebuilt when `column` changes type: every index that references
+/// it, plus primary key and unique constraints on it with no matching unique
+/// index in the catalog. Each entry names the kind of object.
+fn dependent_indexes(table: &TableState, column: &str) -> Vec<String> {
+    let mut objects: Vec<String> = table
+        .indexes
+        .iter()
+        .filter(|idx| idx.references_column(column))
+        .map(|idx| format!("index {}", idx.name))
+        .collect();
+    let relname = table.name.rsplit('.').next().unwrap_or(&table.name);
+    for constraint in table.constraints_involving_column(column) {
+        let (kind, columns) = match constraint {
+            ConstraintState::PrimaryKey { columns, .. } => ("primary key", columns),
+            ConstraintState::Unique {
+                columns,
+                using_index: None,
+                ..
+            } => ("unique constraint", columns),
+            // USING INDEX constraints are backed by an index listed above.
+            _ => continue,
+        };
+        let backed_by_index = table
+            .indexes
+            .iter()
+            .any(|idx| idx.unique && idx.column_names().eq(columns.iter().map(String::as_str)));
+        if !backed_by_index {
+            let name = constraint
+                .name()
+                .map(str::to_string)
+                .or_else(|| constraint.default_name(relname))
+                .unwrap_or_default();
+            objects.push(format!("{kind} {name}"));
+        }
+    }
+    objects
+}
+
 /// Result of checking whether a type cast is safe.
 #[derive(Debug, PartialEq, Eq)]
 pub enum CastSafety {
@@ -138,8 +229,13 @@ pub enum CastSafety {
 /// INFO casts:
 /// - `timestamp` -> `timestamptz`
 ///
-/// Everything else: Unsafe.
+/// Everything else: Unsafe. Array types are never treated as safe: the
+/// element-wise coercion is not a simple relabel.
 pub fn is_safe_cast(old: &TypeName, new: &TypeName) -> CastSafety {
+    if old.is_array() || new.is_array() {
+        return CastSafety::Unsafe;
+    }
+
     let old_name = old.name.to_lowercase();
     let new_name = new.name.to_lowercase();
 
@@ -311,6 +407,18 @@ mod tests {
         assert_eq!(is_safe_cast(&old, &new), expected);
     }
 
+    #[test]
+    fn test_array_casts_are_unsafe() {
+        let varchar_array = TypeName::with_modifiers("varchar", vec![10]).with_array_dims(1);
+        let text_array = TypeName::simple("text").with_array_dims(1);
+        let text = TypeName::simple("text");
+        assert_eq!(
+            is_safe_cast(&varchar_array, &text_array),
+            CastSafety::Unsafe
+        );
+        assert_eq!(is_safe_cast(&text_array, &text), CastSafety::Unsafe);
+    }
+
     #[rstest]
     #[case::normalize_numeric_modifiers_empty(&[], (-1, -1))]
     #[case::normalize_numeric_modifiers_single(&[10], (10, 0))]
@@ -440,7 +548,9 @@ mod tests {
     fn test_int_to_bigint_fires_critical() {
         let before = CatalogBuilder::new()
             .table("orders", |t| {
-                t.column("amount", "integer", false).pk(&["amount"]);
+                t.column("id", "bigint", false)
+                    .column("amount", "integer", false)
+                    .pk(&["id"]);
             })
             .build();
         let after = before.clone();
@@ -504,4 +614,97 @@ mod tests {
         let findings = RuleId::Pgm007.check(&stmts, &ctx);
         assert!(findings.is_empty());
     }
+
+    fn alter_type(table: &str, column: &str, new_type: &str) -> Vec<Located<IrNode>> {
+        vec![located(IrNode::AlterTable(AlterTable {
+            name: QualifiedName::unqualified(table),
+            actions: vec![AlterTableAction::AlterColumnType {
+                column_name: column.to_string(),
+                new_type: TypeName::simple(new_type),
+                old_type: None,
+            }],
+        }))]
+    }
+
+    #[test]
+    fn test_indexed_column_escalates_to_blocker() {
+        let before = CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "integer", false)
+                    .column("customer_id", "integer", false)
+                    .pk(&["id"])
+                    .index("idx_orders_customer", &["customer_id", "id"], false)
+                    .unique("uq_orders_customer_id", &["customer_id", "id"]);
+            })
+            .build();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let findings = RuleId::Pgm007.check(&alter_type("orders", "id", "bigint"), &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_indexed_column_info_cast_escalates_to_major() {
+        let before = CatalogBuilder::new()
+            .table("events", |t| {
+                t.column("created_at", "timestamp", true).index(
+                    "idx_events_created_at",
+                    &["created_at"],
+                    false,
+                );
+            })
+            .build();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let findings =
+            RuleId::Pgm007.check(&alter_type("events", "created_at", "timestamptz"), &ctx);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Major);
+        assert!(
+            findings[0].message.contains("index idx_events_created_at"),
+            "{}",
+            findings[0].message
+        );
+    }
+
+    #[test]
+    fn test_safe_cast_on_indexed_column_no_finding() {
+        let before = CatalogBuilder::new()
+            .table("users", |t| {
+                t.column("email", "varchar", false)
+                    .index("idx_users_email", &["email"], true);
+            })
+            .build();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let findings = RuleId::Pgm007.check(&alter_type("users", "email", "text"), &ctx);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_partition_key_column_fires_blocker() {
+        let before = CatalogBuilder::new()
+            .table("measurements", |t| {
+                t.column("logged_at", "timestamp", false)
+                    .column("value", "integer", true)
+                    .partitioned_by(PartitionStrategy::Range, &["logged_at"]);
+            })
+            .build();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        // Even a cast that never rewrites is rejected on a partition key.
+        let findings = RuleId::Pgm007.check(
+            &alter_type("measurements", "logged_at", "timestamptz"),
+            &ctx,
+        );
+        insta::assert_yaml_snapshot!(findings);
+
+        let findings = RuleId::Pgm007.check(&alter_type("measurements", "value", "bigint"), &ctx);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Critical);
+    }
 }
@@ -3,8 +3,13 @@
 //! Detects `ALTER TABLE ... ADD COLUMN ... NOT NULL` without a `DEFAULT` clause
 //! on tables that already exist. This command will fail outright if the table
 //! has any rows.
+//!
+//! When `[postgres] version` is set, also flags `NOT NULL` columns whose
+//! `DEFAULT` rewrites the table: any default before PostgreSQL 11, a volatile
+//! one from 11 on. Without a version only the missing default is flagged.
 
-use crate::parser::ir::{AlterTableAction, IrNode, Located};
+use crate::parser::ir::{AlterTableAction, ColumnDef, DefaultExpr, IrNode, Located};
+use crate::rules::fn_volatility::{self, FnVolatility};
 use crate::rules::{Finding, LintContext, Rule, Severity, TableScope, alter_table_check};
 
 pub(super) const DESCRIPTION: &str = "ADD COLUMN NOT NULL without DEFAULT on existing table";
@@ -26,6 +31,12 @@ pub(super) const EXPLAIN: &str = "PGM008 — ADD COLUMN NOT NULL without DEFAULT
          default is applied lazily without rewriting the table (for non-volatile\n\
          defaults).\n\
          \n\
+         Version-aware checks ([postgres] version):\n\
+         Before PG 11 every ADD COLUMN ... DEFAULT rewrites the table under an\n\
+         ACCESS EXCLUSIVE lock, and from 11 on a volatile default (random(),\n\
+         clock_timestamp(), ...) still does. With the version configured, these\n\
+         NOT NULL DEFAULT cases are flagged too; use option B below.\n\
+         \n\
          Example (bad):\n\
            ALTER TABLE orders ADD COLUMN status text NOT NULL;\n\
          \n\
@@ -39,6 +50,52 @@ pub(super) const EXPLAIN: &str = "PGM008 — ADD COLUMN NOT NULL without DEFAULT
 
 pub(super) const DEFAULT_SEVERITY: Severity = Severity::Critical;
 
+/// First major version where a non-volatile `ADD COLUMN ... DEFAULT` is
+/// stored in the catalog instead of rewriting the table.
+const LAZY_DEFAULT_VERSION: u32 = 11;
+
+/// Why a `NOT NULL DEFAULT` column rewrites the table on `version`, if it does.
+fn rewrite_reason(default: &DefaultExpr, version: u32) -> Option<String> {
+    if version < LAZY_DEFAULT_VERSION {
+        return Some(format!(
+            "before PostgreSQL {LAZY_DEFAULT_VERSION} (configured: {version}) every \
+             ADD COLUMN DEFAULT is written into each existing row"
+        ));
+    }
+    match default {
+        DefaultExpr::FunctionCall { name, .. }
+            if fn_volatility::lookup(name) == Some(FnVolatility::Volatile) =>
+        {
+            Some(format!(
+                "'{name}()' is volatile, so PostgreSQL {version} evaluates it for \
+                 each existing row"
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn check_column(col: &ColumnDef, table: &str, ctx: &LintContext<'_>) -> Option<String> {
+    if col.nullable {
+        return None;
+    }
+    let Some(default) = &col.default_expr else {
+        return Some(format!(
+            "Adding NOT NULL column '{col}' to existing table '{table}' \
+             without a DEFAULT will fail if the table has any rows. \
+             Add a DEFAULT value, or add the column as nullable and backfill.",
+            col = col.name,
+        ));
+    };
+    let reason = rewrite_reason(default, ctx.config.postgres.version?)?;
+    Some(format!(
+        "Adding NOT NULL column '{col}' with a DEFAULT to existing table '{table}' \
+         rewrites the table under an ACCESS EXCLUSIVE lock: {reason}. Add the column \
+         as nullable, backfill in batches, then SET NOT NULL.",
+        col = col.name,
+    ))
+}
+
 pub(super) fn check(
     rule: impl Rule,
     statements: &[Located<IrNode>],
@@ -49,24 +106,13 @@ pub(super) fn check(
         ctx,
         TableScope::ExcludeCreatedInChange,
         |at, action, stmt, ctx| {
-            if let AlterTableAction::AddColumn(col) = action
-                && !col.nullable
-                && col.default_expr.is_none()
-            {
-                vec![rule.make_finding(
-                    format!(
-                        "Adding NOT NULL column '{col}' to existing table '{table}' \
-                         without a DEFAULT will fail if the table has any rows. \
-                         Add a DEFAULT value, or add the column as nullable and backfill.",
-                        col = col.name,
-                        table = at.name.display_name(),
-                    ),
-                    ctx.file,
-                    &stmt.span,
-                )]
-            } else {
-                vec![]
-            }
+            let AlterTableAction::AddColumn(col) = action else {
+                return vec![];
+            };
+            check_column(col, &at.name.display_name(), ctx)
+                .map(|message| rule.make_finding(message, ctx.file, &stmt.span))
+                .into_iter()
+                .collect()
         },
     )
 }
@@ -76,6 +122,7 @@ mod tests {
     use super::*;
     use crate::catalog::Catalog;
     use crate::catalog::builder::CatalogBuilder;
+    use crate::config::Config;
     use crate::parser::ir::*;
     use crate::rules::RuleId;
     use crate::rules::test_helpers::{lint_ctx, located};
@@ -165,4 +212,91 @@ mod tests {
         let findings = RuleId::Pgm008.check(&stmts, &ctx);
         assert!(findings.is_empty());
     }
+
+    fn add_not_null_with_default(default: DefaultExpr) -> Vec<Located<IrNode>> {
+        vec![located(IrNode::AlterTable(AlterTable {
+            name: QualifiedName::unqualified("orders"),
+            actions: vec![AlterTableAction::AddColumn(
+                ColumnDef::test("status", "text")
+                    .with_nullable(false)
+                    .with_default(default),
+            )],
+        }))]
+    }
+
+    fn orders_catalog() -> Catalog {
+        CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "integer", false).pk(&["id"]);
+            })
+            .build()
+    }
+
+    fn config_for_version(version: u32) -> Config {
+        let mut config = Config::default();
+        config.postgres.version = Some(version);
+        config
+    }
+
+    #[test]
+    fn test_constant_default_before_pg11_fires() {
+        let before = orders_catalog();
+        let after = before.clone();
+        let config = config_for_version(10);
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql", config: &config);
+
+        let stmts = add_not_null_with_default(DefaultExpr::Literal("pending".to_string()));
+
+        let findings = RuleId::Pgm008.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_constant_default_on_pg11_no_finding() {
+        let before = orders_catalog();
+        let after = before.clone();
+        let config = config_for_version(11);
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql", config: &config);
+
+        let stmts = add_not_null_with_default(DefaultExpr::Literal("pending".to_string()));
+
+        let findings = RuleId::Pgm008.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_volatile_default_on_pg16_fires() {
+        let before = orders_catalog();
+        let after = before.clone();
+        let config = config_for_version(16);
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql", config: &config);
+
+        let stmts = add_not_null_with_default(DefaultExpr::FunctionCall {
+            name: "clock_timestamp".to_string(),
+            args: vec![],
+        });
+
+        let findings = RuleId::Pgm008.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert!(
+            findings[0]
+                .message
+                .contains("'clock_timestamp()' is volatile")
+        );
+    }
+
+    #[test]
+    fn test_volatile_default_without_version_no_finding() {
+        let before = orders_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = add_not_null_with_default(DefaultExpr::FunctionCall {
+            name: "clock_timestamp".to_string(),
+            args: vec![],
+        });
+
+        let findings = RuleId::Pgm008.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
 }
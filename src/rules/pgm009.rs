@@ -4,9 +4,15 @@
 //! While the DDL itself is cheap (PostgreSQL marks the column as dropped
 //! without rewriting the table), the risk is application-level: queries
 //! referencing the column will break.
+//!
+//! When the catalog shows indexes, constraints, or foreign keys from other
+//! tables that use the column, they are listed in the message and the
+//! finding is raised to MINOR.
 
 use crate::parser::ir::{AlterTableAction, IrNode, Located};
-use crate::rules::{Finding, LintContext, Rule, Severity, TableScope, alter_table_check};
+use crate::rules::{
+    Finding, LintContext, Rule, Severity, TableScope, alter_table_check, column_dependents,
+};
 
 pub(super) const DESCRIPTION: &str = "DROP COLUMN on existing table";
 
@@ -31,7 +37,11 @@ pub(super) const EXPLAIN: &str = "PGM009 — DROP COLUMN on existing table\n\
          3. Then drop the column in a subsequent migration.\n\
          \n\
          This rule is informational (INFO severity) to increase visibility\n\
-         of column drops in code review.";
+         of column drops in code review. When the column is used by indexes,\n\
+         constraints, or foreign keys from other tables, the finding lists\n\
+         them and is raised to MINOR: PostgreSQL drops the indexes and\n\
+         constraints along with the column, and refuses the drop without\n\
+         CASCADE while another table's foreign key references it.";
 
 pub(super) const DEFAULT_SEVERITY: Severity = Severity::Info;
 
@@ -45,21 +55,33 @@ pub(super) fn check(
         ctx,
         TableScope::ExcludeCreatedInChange,
         |at, action, stmt, ctx| {
-            if let AlterTableAction::DropColumn { name } = action {
-                vec![rule.make_finding(
-                    format!(
-                        "Dropping column '{col}' from existing table '{table}'. \
-                         The DDL is cheap but ensure no application code references \
-                         this column.",
-                        col = name,
-                        table = at.name.display_name(),
-                    ),
-                    ctx.file,
-                    &stmt.span,
-                )]
+            let AlterTableAction::DropColumn { name } = action else {
+                return vec![];
+            };
+            let mut message = format!(
+                "Dropping column '{col}' from existing table '{table}'. \
+                 The DDL is cheap but ensure no application code references \
+                 this column.",
+                col = name,
+                table = at.name.display_name(),
+            );
+            let dependents = column_dependents::dependent_labels(ctx, at.name.catalog_key(), name);
+            let severity = if dependents.labels.is_empty() {
+                rule.default_severity()
             } else {
-                vec![]
-            }
+                message.push_str(&format!(
+                    " It is used by {}; these are dropped with the column, or block \
+                     the drop if they are on another table.",
+                    dependents.labels.join(", ")
+                ));
+                Severity::Minor
+            };
+            vec![
+                dependents.quoted_on(
+                    Finding::new(rule.id(), severity, message, ctx.file, &stmt.span)
+                        .with_param("column", name.as_str()),
+                ),
+            ]
         },
     )
 }
@@ -96,6 +118,39 @@ mod tests {
         insta::assert_yaml_snapshot!(findings);
     }
 
+    #[test]
+    fn test_drop_column_with_dependents_lists_them() {
+        let before = CatalogBuilder::new()
+            .table("customers", |t| {
+                t.column("id", "integer", false)
+                    .column("email", "text", false)
+                    .pk(&["id"])
+                    .index("idx_customers_email", &["email"], false)
+                    .check_constraint(None, "(email <> '')", false);
+            })
+            .table("invoices", |t| {
+                t.column("customer_email", "text", true).fk(
+                    "invoices_customer_email_fk",
+                    &["customer_email"],
+                    "customers",
+                    &["email"],
+                );
+            })
+            .build();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/003.sql");
+
+        let stmts = vec![located(IrNode::AlterTable(AlterTable {
+            name: QualifiedName::unqualified("customers"),
+            actions: vec![AlterTableAction::DropColumn {
+                name: "email".to_string(),
+            }],
+        }))];
+
+        let findings = RuleId::Pgm009.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
     #[test]
     fn test_drop_column_new_table_no_finding() {
         let before = Catalog::new();
@@ -277,11 +277,14 @@ mod tests {
                     start_offset: 0,
                     end_offset: 0,
                 },
+                raw_sql: Default::default(),
             }],
             source_file: PathBuf::from("migrations/001.sql"),
             source_line_offset: 1,
             run_in_transaction: true,
             is_down: false,
+            suppressions: Default::default(),
+            changeset: Default::default(),
         };
         apply(&mut catalog, &unit);
 
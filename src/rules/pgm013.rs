@@ -6,7 +6,9 @@
 //! with NOT VALID, validate it, then set NOT NULL.
 
 use crate::parser::ir::{AlterTableAction, IrNode, Located};
-use crate::rules::{Finding, LintContext, Rule, Severity, TableScope, alter_table_check};
+use crate::rules::{
+    Finding, LintContext, Rule, Severity, TableScope, alter_table_check, backfill_check,
+};
 
 pub(super) const DESCRIPTION: &str =
     "SET NOT NULL on existing table requires ACCESS EXCLUSIVE lock";
@@ -43,7 +45,10 @@ pub(super) const EXPLAIN: &str = "PGM013 — SET NOT NULL on existing table requ
            ALTER TABLE orders ADD CONSTRAINT orders_status_nn\n\
              CHECK (status IS NOT NULL) NOT VALID;\n\
            ALTER TABLE orders VALIDATE CONSTRAINT orders_status_nn;\n\
-           ALTER TABLE orders ALTER COLUMN status SET NOT NULL;";
+           ALTER TABLE orders ALTER COLUMN status SET NOT NULL;\n\
+         \n\
+         When the column was added and backfilled earlier in the same migration,\n\
+         PGM024 reports the whole sequence instead.";
 
 pub(super) const DEFAULT_SEVERITY: Severity = Severity::Critical;
 
@@ -52,12 +57,19 @@ pub(super) fn check(
     statements: &[Located<IrNode>],
     ctx: &LintContext<'_>,
 ) -> Vec<Finding> {
+    // Backfill sequences are reported as a whole by PGM024.
+    let backfills = backfill_check::reported_backfills(statements, ctx);
+
     alter_table_check::check_alter_actions(
         statements,
         ctx,
         TableScope::ExcludeCreatedInChange,
         |at, action, stmt, ctx| {
-            if let AlterTableAction::SetNotNull { column_name } = action {
+            if let AlterTableAction::SetNotNull { column_name } = action
+                && !backfills
+                    .iter()
+                    .any(|b| b.is_set_not_null(stmt, column_name))
+            {
                 vec![rule.make_finding(
                     format!(
                         "SET NOT NULL on column '{col}' of existing table '{table}' \
@@ -98,6 +98,7 @@ mod tests {
                 name: Some("orders_status_check".to_string()),
                 expression: "status IN ('pending', 'shipped')".to_string(),
                 not_valid: false,
+                deps: CheckExprDeps::default(),
             })],
         }))];
 
@@ -123,6 +124,7 @@ mod tests {
                 name: Some("orders_status_check".to_string()),
                 expression: "status IN ('pending', 'shipped')".to_string(),
                 not_valid: true,
+                deps: CheckExprDeps::default(),
             })],
         }))];
 
@@ -148,6 +150,7 @@ mod tests {
                 name: Some("orders_status_check".to_string()),
                 expression: "status IN ('pending', 'shipped')".to_string(),
                 not_valid: false,
+                deps: CheckExprDeps::default(),
             })],
         }))];
 
@@ -167,6 +170,7 @@ mod tests {
                 name: Some("orders_status_check".to_string()),
                 expression: "status IN ('pending', 'shipped')".to_string(),
                 not_valid: false,
+                deps: CheckExprDeps::default(),
             })],
         }))];
 
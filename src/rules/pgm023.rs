@@ -151,11 +151,27 @@ fn table_key_of(node: &IrNode) -> Option<String> {
         IrNode::Reindex(_) => None,
         IrNode::RenameTable { name, .. } => Some(name.catalog_key().to_string()),
         IrNode::RenameColumn { table, .. } => Some(table.catalog_key().to_string()),
+        // Comments and triggers sit between ALTERs without splitting them.
+        IrNode::TableAttachment { .. } => None,
         // These don't have a table name to extract
         IrNode::AlterTable(_)
         | IrNode::DropIndex(_)
+        | IrNode::CreateSchema { .. }
         | IrNode::DropSchema(_)
+        | IrNode::CreateExtension(_)
+        | IrNode::DropExtension(_)
+        | IrNode::CreateSequence { .. }
+        | IrNode::SetSequenceOwner { .. }
+        | IrNode::DropSequence { .. }
+        | IrNode::RenameSequence { .. }
+        | IrNode::Grant(_)
+        | IrNode::AlterOwner(_)
+        | IrNode::Replication(_)
         | IrNode::AlterIndexAttachPartition { .. }
+        | IrNode::AlterIndexSetTablespace { .. }
+        | IrNode::MoveAllInTablespace { .. }
+        | IrNode::VacuumAllTables
+        | IrNode::RenameIndex { .. }
         | IrNode::Ignored { .. }
         | IrNode::Unparseable { .. } => None,
     }
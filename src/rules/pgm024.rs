@@ -0,0 +1,258 @@
+//! PGM024 — In-migration backfill of a new column on an existing table
+//!
+//! Detects the sequence `ADD COLUMN` (nullable) → `UPDATE ... SET col = ...` →
+//! `ALTER COLUMN col SET NOT NULL` on a pre-existing table within one migration.
+//! The whole sequence is reported as a single finding; PGM302 (UPDATE) and
+//! PGM013 (SET NOT NULL) stay silent for the statements it covers, unless
+//! PGM024 is disabled or suppressed there.
+
+use crate::parser::ir::{IrNode, Located};
+use crate::rules::{Finding, LintContext, Rule, Severity, backfill_check};
+
+pub(super) const DESCRIPTION: &str =
+    "New column backfilled and set NOT NULL in the same migration on existing table";
+
+pub(super) const EXPLAIN: &str = "PGM024 — In-migration backfill of a new column on an existing table\n\
+         \n\
+         What it detects:\n\
+         Within one migration, on a table that already exists:\n\
+           1. ALTER TABLE ... ADD COLUMN col (nullable)\n\
+           2. UPDATE ... SET col = ...\n\
+           3. ALTER TABLE ... ALTER COLUMN col SET NOT NULL\n\
+         This replaces the separate PGM302 and PGM013 findings for those\n\
+         statements with one consolidated finding.\n\
+         \n\
+         Why it's dangerous:\n\
+         The UPDATE rewrites every row of the table in a single statement,\n\
+         holding row locks until the migration commits and generating WAL\n\
+         for the whole table at once. SET NOT NULL then takes an ACCESS\n\
+         EXCLUSIVE lock and scans the full table. If the migration runs in a\n\
+         transaction, the ACCESS EXCLUSIVE lock from ADD COLUMN is held for\n\
+         the entire backfill, blocking all reads and writes.\n\
+         \n\
+         Example (bad):\n\
+           ALTER TABLE orders ADD COLUMN region text;\n\
+           UPDATE orders SET region = 'eu';\n\
+           ALTER TABLE orders ALTER COLUMN region SET NOT NULL;\n\
+         \n\
+         Fix (split across deploys):\n\
+           -- Migration 1: add the column (instant)\n\
+           ALTER TABLE orders ADD COLUMN region text;\n\
+           -- Out of band: backfill in batches\n\
+           UPDATE orders SET region = 'eu'\n\
+             WHERE id BETWEEN 1 AND 10000 AND region IS NULL;\n\
+           -- Migration 2: enforce NOT NULL without a long lock\n\
+           ALTER TABLE orders ADD CONSTRAINT orders_region_nn\n\
+             CHECK (region IS NOT NULL) NOT VALID;\n\
+           ALTER TABLE orders VALIDATE CONSTRAINT orders_region_nn;\n\
+           ALTER TABLE orders ALTER COLUMN region SET NOT NULL;";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Critical;
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    backfill_check::find_backfills(statements, ctx)
+        .iter()
+        .map(|b| {
+            rule.make_finding(
+                format!(
+                    "Column '{col}' is added to existing table '{table}', backfilled \
+                     with UPDATE (line {update_line}), and set NOT NULL (line {snn_line}) \
+                     in the same migration. The UPDATE rewrites every row in one statement \
+                     and SET NOT NULL takes an ACCESS EXCLUSIVE lock for a full table scan. \
+                     Add the column in one migration, backfill in batches out of band, \
+                     then enforce NOT NULL via a validated CHECK constraint in a later \
+                     migration.",
+                    col = b.column,
+                    table = b.table.display_name(),
+                    update_line = b.update.span.start_line,
+                    snn_line = b.set_not_null.span.start_line,
+                ),
+                ctx.file,
+                &b.span(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located_at};
+    use crate::suppress::parse_suppressions;
+
+    fn orders_catalog() -> crate::catalog::Catalog {
+        CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "bigint", false).pk(&["id"]);
+            })
+            .build()
+    }
+
+    fn add_region() -> IrNode {
+        IrNode::AlterTable(AlterTable {
+            name: QualifiedName::unqualified("orders"),
+            actions: vec![AlterTableAction::AddColumn(ColumnDef::test(
+                "region", "text",
+            ))],
+        })
+    }
+
+    fn set_region_not_null() -> IrNode {
+        IrNode::AlterTable(AlterTable {
+            name: QualifiedName::unqualified("orders"),
+            actions: vec![AlterTableAction::SetNotNull {
+                column_name: "region".to_string(),
+            }],
+        })
+    }
+
+    fn update_region() -> IrNode {
+        UpdateTable::test(QualifiedName::unqualified("orders"))
+            .with_set_columns(&["region"])
+            .into()
+    }
+
+    #[test]
+    fn test_backfill_sequence_fires_once() {
+        let before = orders_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![
+            located_at(add_region(), 1),
+            located_at(update_region(), 2),
+            located_at(set_region_not_null(), 3),
+        ];
+
+        let findings = RuleId::Pgm024.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_supersedes_pgm013_and_pgm302() {
+        let before = orders_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![
+            located_at(add_region(), 1),
+            located_at(update_region(), 2),
+            located_at(set_region_not_null(), 3),
+        ];
+
+        assert!(RuleId::Pgm013.check(&stmts, &ctx).is_empty());
+        assert!(RuleId::Pgm302.check(&stmts, &ctx).is_empty());
+    }
+
+    #[test]
+    fn test_pgm024_disabled_pgm013_and_pgm302_still_fire() {
+        let before = orders_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+        let ctx = LintContext {
+            active_rules: &[RuleId::Pgm013, RuleId::Pgm302],
+            ..ctx
+        };
+
+        let stmts = vec![
+            located_at(add_region(), 1),
+            located_at(update_region(), 2),
+            located_at(set_region_not_null(), 3),
+        ];
+
+        assert_eq!(RuleId::Pgm013.check(&stmts, &ctx).len(), 1);
+        assert_eq!(RuleId::Pgm302.check(&stmts, &ctx).len(), 1);
+    }
+
+    #[test]
+    fn test_pgm024_suppressed_pgm013_and_pgm302_still_fire() {
+        let before = orders_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+        let suppressions = parse_suppressions("-- pgm-lint:suppress-file PGM024\n");
+        let ctx = LintContext {
+            suppressions: &suppressions,
+            ..ctx
+        };
+
+        let stmts = vec![
+            located_at(add_region(), 1),
+            located_at(update_region(), 2),
+            located_at(set_region_not_null(), 3),
+        ];
+
+        assert_eq!(RuleId::Pgm013.check(&stmts, &ctx).len(), 1);
+        assert_eq!(RuleId::Pgm302.check(&stmts, &ctx).len(), 1);
+    }
+
+    #[test]
+    fn test_update_of_other_column_no_finding() {
+        let before = orders_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![
+            located_at(add_region(), 1),
+            located_at(
+                UpdateTable::test(QualifiedName::unqualified("orders"))
+                    .with_set_columns(&["id"])
+                    .into(),
+                2,
+            ),
+            located_at(set_region_not_null(), 3),
+        ];
+
+        assert!(RuleId::Pgm024.check(&stmts, &ctx).is_empty());
+        // The individual rules still report their statements.
+        assert_eq!(RuleId::Pgm013.check(&stmts, &ctx).len(), 1);
+        assert_eq!(RuleId::Pgm302.check(&stmts, &ctx).len(), 1);
+    }
+
+    #[test]
+    fn test_missing_set_not_null_no_finding() {
+        let before = orders_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located_at(add_region(), 1), located_at(update_region(), 2)];
+
+        assert!(RuleId::Pgm024.check(&stmts, &ctx).is_empty());
+    }
+
+    #[test]
+    fn test_out_of_order_no_finding() {
+        let before = orders_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![
+            located_at(update_region(), 1),
+            located_at(add_region(), 2),
+            located_at(set_region_not_null(), 3),
+        ];
+
+        assert!(RuleId::Pgm024.check(&stmts, &ctx).is_empty());
+    }
+
+    #[test]
+    fn test_new_table_no_finding() {
+        let before = crate::catalog::Catalog::new();
+        let after = orders_catalog();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql", created: ["orders"]);
+
+        let stmts = vec![
+            located_at(add_region(), 1),
+            located_at(update_region(), 2),
+            located_at(set_region_not_null(), 3),
+        ];
+
+        assert!(RuleId::Pgm024.check(&stmts, &ctx).is_empty());
+    }
+}
@@ -0,0 +1,207 @@
+//! PGM025 — `DROP CONSTRAINT` removes primary key or unique constraint
+//!
+//! Detects `ALTER TABLE ... DROP CONSTRAINT name` where `name` is the primary
+//! key or a `UNIQUE` constraint of the table in `catalog_before`. The backing
+//! index goes with it, and any foreign key referencing those columns either
+//! blocks the drop or, with `CASCADE`, is dropped along with it.
+
+use crate::catalog::types::{ConstraintState, TableState};
+use crate::parser::ir::{AlterTableAction, IrNode, Located};
+use crate::rules::{Finding, LintContext, Rule, Severity, TableScope, alter_table_check};
+
+pub(super) const DESCRIPTION: &str =
+    "DROP CONSTRAINT removes primary key or unique constraint on existing table";
+
+pub(super) const EXPLAIN: &str = "PGM025 — DROP CONSTRAINT removes primary key or unique constraint on existing table\n\
+         \n\
+         What it detects:\n\
+         ALTER TABLE ... DROP CONSTRAINT where the constraint is the table's\n\
+         PRIMARY KEY or a UNIQUE constraint.\n\
+         \n\
+         Why it matters:\n\
+         The constraint's backing index is dropped with it, so the table\n\
+         loses its row identity or uniqueness guarantee and every query that\n\
+         used the index falls back to another plan. Foreign keys that\n\
+         reference the constrained columns depend on it: without CASCADE the\n\
+         DROP fails, and with CASCADE those foreign keys are silently\n\
+         dropped too.\n\
+         \n\
+         Example (bad):\n\
+           -- customers has PRIMARY KEY (id), orders references customers(id)\n\
+           ALTER TABLE customers DROP CONSTRAINT customers_pkey CASCADE;\n\
+           -- orders' foreign key is silently removed.\n\
+         \n\
+         Fix:\n\
+         Add the replacement key first (for example, a new UNIQUE constraint\n\
+         built with CREATE UNIQUE INDEX CONCURRENTLY ... USING INDEX), move\n\
+         referencing foreign keys to it, then drop the old constraint.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Major;
+
+/// Display names of tables whose foreign keys reference `columns` of `table`.
+///
+/// A foreign key written without a column list references the primary key,
+/// so it matches when `is_pk` is set.
+fn referencing_tables(
+    ctx: &LintContext<'_>,
+    table: &TableState,
+    columns: &[String],
+    is_pk: bool,
+) -> Vec<String> {
+    let mut dependents: Vec<String> = ctx
+        .catalog_before
+        .tables()
+        .filter(|t| {
+            t.constraints.iter().any(|c| {
+                matches!(
+                    c,
+                    ConstraintState::ForeignKey { ref_table, ref_columns, .. }
+                        if *ref_table == table.name
+                            && (ref_columns == columns || (is_pk && ref_columns.is_empty()))
+                )
+            })
+        })
+        .map(|t| t.display_name.clone())
+        .collect();
+    dependents.sort();
+    dependents
+}
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    alter_table_check::check_alter_actions(
+        statements,
+        ctx,
+        TableScope::AnyPreExisting,
+        |at, action, stmt, ctx| {
+            let AlterTableAction::DropConstraint {
+                constraint_name, ..
+            } = action
+            else {
+                return vec![];
+            };
+            let Some(table) = ctx.catalog_before.get_table(at.name.catalog_key()) else {
+                return vec![];
+            };
+            let (kind, columns, is_pk) = match table.constraint_named(constraint_name) {
+                Some(ConstraintState::PrimaryKey { columns, .. }) => ("primary key", columns, true),
+                Some(ConstraintState::Unique { columns, .. }) => {
+                    ("unique constraint", columns, false)
+                }
+                _ => return vec![],
+            };
+
+            let dependents = referencing_tables(ctx, table, columns, is_pk);
+            let fk_note = if dependents.is_empty() {
+                String::new()
+            } else {
+                format!(
+                    " Foreign keys from {} reference these columns: the DROP fails \
+                     without CASCADE and drops them with it.",
+                    dependents.join(", ")
+                )
+            };
+
+            vec![rule.make_finding(
+                format!(
+                    "Dropping {kind} '{constraint_name}' ({cols}) on table '{table}' \
+                     removes its backing index and the guarantee it enforced.{fk_note}",
+                    cols = columns.join(", "),
+                    table = at.name.display_name(),
+                ),
+                ctx.file,
+                &stmt.span,
+            )]
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    fn drop_constraint(table: &str, name: &str) -> IrNode {
+        IrNode::AlterTable(AlterTable {
+            name: QualifiedName::unqualified(table),
+            actions: vec![AlterTableAction::DropConstraint {
+                constraint_name: name.to_string(),
+                if_exists: false,
+            }],
+        })
+    }
+
+    fn customers_with_orders() -> Catalog {
+        CatalogBuilder::new()
+            .table("customers", |t| {
+                t.column("id", "bigint", false)
+                    .column("email", "text", false)
+                    .pk(&["id"])
+                    .unique("customers_email_key", &["email"]);
+            })
+            .table("orders", |t| {
+                t.column("id", "bigint", false)
+                    .column("customer_id", "bigint", false)
+                    .pk(&["id"])
+                    .fk("orders_customer_fk", &["customer_id"], "customers", &["id"]);
+            })
+            .build()
+    }
+
+    #[test]
+    fn test_drop_referenced_pk_fires() {
+        let before = customers_with_orders();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(drop_constraint("customers", "customers_pkey"))];
+
+        let findings = RuleId::Pgm025.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_drop_unique_fires() {
+        let before = customers_with_orders();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(drop_constraint("customers", "customers_email_key"))];
+
+        let findings = RuleId::Pgm025.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("unique constraint"));
+        assert!(!findings[0].message.contains("Foreign keys"));
+    }
+
+    #[test]
+    fn test_drop_fk_no_finding() {
+        let before = customers_with_orders();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(drop_constraint("orders", "orders_customer_fk"))];
+
+        let findings = RuleId::Pgm025.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_table_not_in_catalog_before_no_finding() {
+        let before = Catalog::new();
+        let after = customers_with_orders();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql");
+
+        let stmts = vec![located(drop_constraint("customers", "customers_pkey"))];
+
+        let findings = RuleId::Pgm025.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+}
@@ -0,0 +1,149 @@
+//! PGM026 — `DROP CONSTRAINT` removes foreign key
+//!
+//! Detects `ALTER TABLE ... DROP CONSTRAINT name` where `name` is a
+//! `FOREIGN KEY` constraint of the table in `catalog_before`. The drop is
+//! cheap, but referential integrity is no longer enforced from then on.
+
+use crate::catalog::types::ConstraintState;
+use crate::parser::ir::{AlterTableAction, IrNode, Located};
+use crate::rules::{Finding, LintContext, Rule, Severity, TableScope, alter_table_check};
+
+pub(super) const DESCRIPTION: &str = "DROP CONSTRAINT removes foreign key on existing table";
+
+pub(super) const EXPLAIN: &str = "PGM026 — DROP CONSTRAINT removes foreign key on existing table\n\
+         \n\
+         What it detects:\n\
+         ALTER TABLE ... DROP CONSTRAINT where the constraint is a FOREIGN KEY\n\
+         on a table that already exists.\n\
+         \n\
+         Why it matters:\n\
+         Once the foreign key is gone, PostgreSQL no longer prevents rows that\n\
+         point at missing parents, and deletes on the referenced table no\n\
+         longer cascade or fail. Orphaned rows accumulate silently. This is\n\
+         sometimes intended (for example, before moving a table to another\n\
+         database), so the finding is informational.\n\
+         \n\
+         Example:\n\
+           ALTER TABLE orders DROP CONSTRAINT orders_customer_fk;\n\
+         \n\
+         Fix:\n\
+         Confirm the integrity guarantee is no longer needed. To replace the\n\
+         foreign key, add the new one with NOT VALID and VALIDATE it before\n\
+         dropping the old one.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Info;
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    alter_table_check::check_alter_actions(
+        statements,
+        ctx,
+        TableScope::AnyPreExisting,
+        |at, action, stmt, ctx| {
+            let AlterTableAction::DropConstraint {
+                constraint_name, ..
+            } = action
+            else {
+                return vec![];
+            };
+            let Some(table) = ctx.catalog_before.get_table(at.name.catalog_key()) else {
+                return vec![];
+            };
+            let Some(ConstraintState::ForeignKey {
+                columns,
+                ref_table_display,
+                ..
+            }) = table.constraint_named(constraint_name)
+            else {
+                return vec![];
+            };
+
+            vec![rule.make_finding(
+                format!(
+                    "Dropping foreign key '{constraint_name}' ({cols}) \u{2192} \
+                     {ref_table_display} on table '{table}'. Referential integrity \
+                     is no longer enforced; verify the guarantee is no longer needed.",
+                    cols = columns.join(", "),
+                    table = at.name.display_name(),
+                ),
+                ctx.file,
+                &stmt.span,
+            )]
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    fn drop_constraint(name: &str) -> IrNode {
+        IrNode::AlterTable(AlterTable {
+            name: QualifiedName::unqualified("orders"),
+            actions: vec![AlterTableAction::DropConstraint {
+                constraint_name: name.to_string(),
+                if_exists: false,
+            }],
+        })
+    }
+
+    fn orders_catalog() -> Catalog {
+        CatalogBuilder::new()
+            .table("customers", |t| {
+                t.column("id", "bigint", false).pk(&["id"]);
+            })
+            .table("orders", |t| {
+                t.column("id", "bigint", false)
+                    .column("customer_id", "bigint", false)
+                    .column("total", "numeric", false)
+                    .pk(&["id"])
+                    .fk("orders_customer_fk", &["customer_id"], "customers", &["id"])
+                    .check_constraint(Some("orders_total_check"), "total >= 0", false);
+            })
+            .build()
+    }
+
+    #[test]
+    fn test_drop_fk_fires() {
+        let before = orders_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(drop_constraint("orders_customer_fk"))];
+
+        let findings = RuleId::Pgm026.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_drop_check_no_finding() {
+        let before = orders_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(drop_constraint("orders_total_check"))];
+
+        let findings = RuleId::Pgm026.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_constraint_no_finding() {
+        let before = orders_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(drop_constraint("orders_added_later_fk"))];
+
+        let findings = RuleId::Pgm026.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+}
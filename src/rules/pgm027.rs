@@ -0,0 +1,243 @@
+//! PGM027 — Table or column created after a migration that uses it
+//!
+//! Detects a `CREATE TABLE` or `ADD COLUMN` in a changed unit that creates
+//! an object an *earlier* changed unit already referenced (by `ALTER TABLE`,
+//! `CREATE INDEX`, a foreign key, or DML). The references are recorded by
+//! the pipeline in replay order (see [`crate::rules::PendingReferences`]).
+//! Rollout applies the earlier unit first, so it fails at deploy.
+
+use crate::parser::ir::{AlterTableAction, IrNode, Located};
+use crate::rules::{Finding, LintContext, PendingReference, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str = "Table or column created after a migration that uses it";
+
+pub(super) const EXPLAIN: &str = "PGM027 — Table or column created after a migration that uses it\n\
+         \n\
+         What it detects:\n\
+         A CREATE TABLE or ALTER TABLE ... ADD COLUMN that creates a table or\n\
+         column which an earlier migration in the same change already\n\
+         references — through ALTER TABLE, CREATE INDEX, a FOREIGN KEY, or\n\
+         INSERT/UPDATE/DELETE. The finding is reported on the creating\n\
+         statement and names the earlier reference.\n\
+         \n\
+         Why it matters:\n\
+         Migrations run in order. The earlier migration fails with\n\
+         'relation does not exist' or 'column does not exist' at deploy time,\n\
+         typically after a rename or renumbering put the files out of order.\n\
+         Reviewing the files one at a time does not reveal it.\n\
+         \n\
+         Example (bad):\n\
+           -- V010__index_shipments.sql\n\
+           CREATE INDEX CONCURRENTLY idx_shipments_order ON shipments (order_id);\n\
+           -- V011__create_shipments.sql\n\
+           CREATE TABLE shipments (id bigint PRIMARY KEY, order_id bigint);\n\
+         \n\
+         Fix:\n\
+         Reorder the migrations so the object is created first, or move the\n\
+         reference into a migration after the one that creates it.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Critical;
+
+/// `file:line (STATEMENT)` for each reference, at most three, then a count.
+fn describe(refs: &[&PendingReference]) -> String {
+    const SHOWN: usize = 3;
+    let mut parts: Vec<String> = refs
+        .iter()
+        .take(SHOWN)
+        .map(|r| {
+            format!(
+                "{}:{} ({})",
+                r.file.to_string_lossy().replace('\\', "/"),
+                r.line,
+                r.statement
+            )
+        })
+        .collect();
+    if refs.len() > SHOWN {
+        parts.push(format!("and {} more", refs.len() - SHOWN));
+    }
+    parts.join(", ")
+}
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for stmt in statements {
+        match &stmt.node {
+            IrNode::CreateTable(ct) => {
+                let refs: Vec<_> = ctx
+                    .pending_references
+                    .for_table(ct.name.catalog_key())
+                    .collect();
+                if refs.is_empty() {
+                    continue;
+                }
+                findings.push(
+                    rule.make_finding(
+                        format!(
+                            "Table '{}' is created here, but an earlier migration already \
+                             uses it: {}. That migration runs first and fails at deploy; \
+                             reorder the migrations.",
+                            ct.name.display_name(),
+                            describe(&refs),
+                        ),
+                        ctx.file,
+                        &stmt.span,
+                    )
+                    .with_table(&ct.name),
+                );
+            }
+            IrNode::AlterTable(at) => {
+                for action in &at.actions {
+                    let AlterTableAction::AddColumn(col) = action else {
+                        continue;
+                    };
+                    let refs: Vec<_> = ctx
+                        .pending_references
+                        .for_column(at.name.catalog_key(), &col.name)
+                        .collect();
+                    if refs.is_empty() {
+                        continue;
+                    }
+                    findings.push(
+                        rule.make_finding(
+                            format!(
+                                "Column '{}.{}' is created here, but an earlier migration \
+                                 already uses it: {}. That migration runs first and fails \
+                                 at deploy; reorder the migrations.",
+                                at.name.display_name(),
+                                col.name,
+                                describe(&refs),
+                            ),
+                            ctx.file,
+                            &stmt.span,
+                        )
+                        .with_table(&at.name)
+                        .with_param("column", col.name.as_str()),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rules::test_helpers::{lint_ctx, located};
+    use crate::rules::{PendingReferences, RuleId};
+    use std::path::PathBuf;
+
+    fn pending(table: &str, column: Option<&str>, statement: &'static str) -> PendingReference {
+        PendingReference {
+            table_key: table.to_string(),
+            column: column.map(str::to_string),
+            statement,
+            file: PathBuf::from("migrations/V010__index_shipments.sql"),
+            line: 3,
+        }
+    }
+
+    #[test]
+    fn test_create_table_after_reference_fires() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(
+            ctx,
+            &before,
+            &after,
+            "migrations/V011__create_shipments.sql"
+        );
+        let mut refs = PendingReferences::new();
+        refs.push(pending("shipments", None, "CREATE INDEX"));
+        let ctx = LintContext {
+            pending_references: &refs,
+            ..ctx
+        };
+
+        let stmts = vec![located(IrNode::CreateTable(CreateTable::test(
+            QualifiedName::unqualified("shipments"),
+        )))];
+
+        let findings = RuleId::Pgm027.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_add_column_after_reference_fires() {
+        let before = CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "bigint", false).pk(&["id"]);
+            })
+            .build();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/V011.sql");
+        let mut refs = PendingReferences::new();
+        for _ in 0..4 {
+            refs.push(pending("orders", Some("status"), "UPDATE"));
+        }
+        let ctx = LintContext {
+            pending_references: &refs,
+            ..ctx
+        };
+
+        let stmts = vec![located(IrNode::AlterTable(AlterTable {
+            name: QualifiedName::unqualified("orders"),
+            actions: vec![AlterTableAction::AddColumn(ColumnDef::test(
+                "status", "text",
+            ))],
+        }))];
+
+        let findings = RuleId::Pgm027.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("'orders.status'"));
+        assert!(findings[0].message.contains("and 1 more"));
+    }
+
+    #[test]
+    fn test_table_reference_does_not_match_column_creation() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/V011.sql");
+        let mut refs = PendingReferences::new();
+        refs.push(pending("orders", None, "INSERT"));
+        let ctx = LintContext {
+            pending_references: &refs,
+            ..ctx
+        };
+
+        let stmts = vec![located(IrNode::AlterTable(AlterTable {
+            name: QualifiedName::unqualified("orders"),
+            actions: vec![AlterTableAction::AddColumn(ColumnDef::test(
+                "status", "text",
+            ))],
+        }))];
+
+        let findings = RuleId::Pgm027.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_no_pending_references_no_finding() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/V011.sql");
+
+        let stmts = vec![located(IrNode::CreateTable(CreateTable::test(
+            QualifiedName::unqualified("shipments"),
+        )))];
+
+        let findings = RuleId::Pgm027.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+}
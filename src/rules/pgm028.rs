@@ -0,0 +1,270 @@
+//! PGM028 — CHECK constraint with a subquery or non-immutable expression
+//!
+//! Detects CHECK constraints (in `CREATE TABLE` or `ALTER TABLE ... ADD
+//! CONSTRAINT`) whose expression contains a subquery, which PostgreSQL
+//! rejects, or calls time-, session-, or sequence-dependent functions, which
+//! PostgreSQL accepts but only evaluates when a row is written.
+//!
+//! Only built-in functions are classified. A user-defined function that reads
+//! other tables is invisible here; its volatility is not known to the linter.
+
+use crate::parser::ir::{AlterTableAction, CheckExprDeps, IrNode, Located, TableConstraint};
+use crate::rules::{Finding, LintContext, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str = "CHECK constraint with a subquery or non-immutable expression";
+
+pub(super) const EXPLAIN: &str = "PGM028 — CHECK constraint with a subquery or non-immutable expression\n\
+         \n\
+         What it detects:\n\
+         A CHECK constraint whose expression contains a subquery\n\
+         (EXISTS (...), x IN (SELECT ...)), or calls a function whose result\n\
+         depends on time, the session, or a sequence: now(), random(),\n\
+         nextval(), current_setting(), CURRENT_USER, CURRENT_DATE, ...\n\
+         \n\
+         Why it matters:\n\
+         PostgreSQL rejects subqueries in CHECK constraints ('cannot use\n\
+         subquery in check constraint'), so the migration fails at deploy.\n\
+         Non-immutable functions are accepted, but a CHECK is evaluated only\n\
+         when a row is inserted or updated. Rows that passed yesterday may\n\
+         fail today, so unrelated UPDATEs start failing and a pg_dump restore\n\
+         can reject data that is already in the table.\n\
+         \n\
+         Example (bad):\n\
+           ALTER TABLE orders ADD CONSTRAINT orders_customer_active\n\
+             CHECK (customer_id IN (SELECT id FROM customers WHERE active));\n\
+           ALTER TABLE orders ADD CONSTRAINT orders_not_future\n\
+             CHECK (created_at <= now());\n\
+         \n\
+         Fix:\n\
+         Use a FOREIGN KEY for cross-table rules, or enforce them in a\n\
+         trigger. Keep CHECK expressions to the row's own columns and\n\
+         immutable functions.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Critical;
+
+/// Built-in functions whose result can change between evaluations of the
+/// same row: time, randomness, sequences, and session state.
+const NON_IMMUTABLE_FUNCTIONS: &[&str] = &[
+    "clock_timestamp",
+    "current_database",
+    "current_setting",
+    "currval",
+    "gen_random_uuid",
+    "inet_client_addr",
+    "lastval",
+    "nextval",
+    "now",
+    "pg_backend_pid",
+    "pg_current_xact_id",
+    "random",
+    "random_normal",
+    "setval",
+    "statement_timestamp",
+    "timeofday",
+    "transaction_timestamp",
+    "txid_current",
+    "uuid_generate_v1",
+    "uuid_generate_v1mc",
+    "uuid_generate_v4",
+    "uuidv4",
+    "uuidv7",
+];
+
+/// The non-immutable calls in `deps`, for the finding message.
+fn non_immutable_calls(deps: &CheckExprDeps) -> Vec<String> {
+    deps.functions
+        .iter()
+        .filter(|f| NON_IMMUTABLE_FUNCTIONS.contains(&f.as_str()))
+        .map(|f| format!("{f}()"))
+        .chain(deps.session_values.iter().cloned())
+        .collect()
+}
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for stmt in statements {
+        let (table, checks): (_, Vec<&TableConstraint>) = match &stmt.node {
+            IrNode::CreateTable(ct) => (&ct.name, ct.constraints.iter().collect()),
+            IrNode::AlterTable(at) => (
+                &at.name,
+                at.actions
+                    .iter()
+                    .filter_map(|a| match a {
+                        AlterTableAction::AddConstraint(c) => Some(c),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            _ => continue,
+        };
+
+        for constraint in checks {
+            let TableConstraint::Check {
+                name,
+                expression,
+                deps,
+                ..
+            } = constraint
+            else {
+                continue;
+            };
+            let label = match name {
+                Some(name) => format!("CHECK constraint '{name}'"),
+                None => format!("CHECK ({expression})"),
+            };
+
+            let message = if deps.has_subquery {
+                format!(
+                    "{label} on '{}' contains a subquery. PostgreSQL rejects subqueries \
+                     in CHECK constraints, so this migration fails at deploy. Use a \
+                     FOREIGN KEY or a trigger instead.",
+                    table.display_name(),
+                )
+            } else {
+                let calls = non_immutable_calls(deps);
+                if calls.is_empty() {
+                    continue;
+                }
+                format!(
+                    "{label} on '{}' uses {}, which can change after a row is written. \
+                     Rows that passed may later violate the constraint, breaking \
+                     unrelated UPDATEs and dump/restore. Keep CHECK expressions \
+                     immutable.",
+                    table.display_name(),
+                    calls.join(", "),
+                )
+            };
+            findings.push(
+                rule.make_finding(message, ctx.file, &stmt.span)
+                    .with_table(table)
+                    .with_quoted_sql(expression),
+            );
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    fn add_check(name: &str, expression: &str, deps: CheckExprDeps) -> IrNode {
+        IrNode::AlterTable(AlterTable {
+            name: QualifiedName::unqualified("orders"),
+            actions: vec![AlterTableAction::AddConstraint(TableConstraint::Check {
+                name: Some(name.to_string()),
+                expression: expression.to_string(),
+                not_valid: true,
+                deps,
+            })],
+        })
+    }
+
+    #[test]
+    fn test_subquery_fires() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(add_check(
+            "orders_customer_active",
+            "customer_id IN (SELECT id FROM customers WHERE active)",
+            CheckExprDeps {
+                has_subquery: true,
+                ..Default::default()
+            },
+        ))];
+
+        let findings = RuleId::Pgm028.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_non_immutable_calls_fire_on_create_table() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql");
+
+        let stmts = vec![located(IrNode::CreateTable(
+            CreateTable::test(QualifiedName::unqualified("orders")).with_constraints(vec![
+                TableConstraint::Check {
+                    name: None,
+                    expression: "(created_at <= now())".to_string(),
+                    not_valid: false,
+                    deps: CheckExprDeps {
+                        functions: vec!["now".to_string(), "length".to_string()],
+                        session_values: vec!["CURRENT_USER".to_string()],
+                        ..Default::default()
+                    },
+                },
+            ]),
+        ))];
+
+        let findings = RuleId::Pgm028.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert!(
+            findings[0]
+                .message
+                .starts_with("CHECK ((created_at <= now())) on 'orders' uses now(), CURRENT_USER,")
+        );
+    }
+
+    #[test]
+    fn test_immutable_check_no_finding() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(add_check(
+            "orders_code_len",
+            "length(code) = 3",
+            CheckExprDeps {
+                functions: vec!["length".to_string()],
+                ..Default::default()
+            },
+        ))];
+
+        let findings = RuleId::Pgm028.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_redact_masks_quoted_expression() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql");
+
+        let stmts = vec![located(IrNode::CreateTable(
+            CreateTable::test(QualifiedName::unqualified("orders")).with_constraints(vec![
+                TableConstraint::Check {
+                    name: None,
+                    expression: "(owner <> 'ops@example.com' OR now() > '2024-01-01')".to_string(),
+                    not_valid: false,
+                    deps: CheckExprDeps {
+                        functions: vec!["now".to_string()],
+                        ..Default::default()
+                    },
+                },
+            ]),
+        ))];
+
+        let mut findings = RuleId::Pgm028.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        findings[0].redact();
+        assert!(
+            findings[0]
+                .message
+                .starts_with("CHECK ((owner <> '?' OR now() > '?')) on 'orders'")
+        );
+    }
+}
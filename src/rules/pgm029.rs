@@ -0,0 +1,170 @@
+//! PGM029 — `serial` / identity column added to existing table
+//!
+//! Detects `ALTER TABLE ... ADD COLUMN` of a `serial`/`bigserial`/`smallserial`
+//! column or a `GENERATED ... AS IDENTITY` column on a table that already
+//! exists. PostgreSQL must draw a sequence value for every existing row, so
+//! the table is rewritten under an ACCESS EXCLUSIVE lock.
+//!
+//! This is the locking hazard; PGM105 separately flags `serial` as a type
+//! choice wherever it appears.
+
+use crate::parser::ir::{AlterTableAction, ColumnDef, IrNode, Located};
+use crate::rules::{Finding, LintContext, Rule, Severity, TableScope, alter_table_check};
+
+pub(super) const DESCRIPTION: &str = "serial or identity column added to existing table";
+
+pub(super) const EXPLAIN: &str = "PGM029 — serial or identity column added to existing table\n\
+         \n\
+         What it detects:\n\
+         ALTER TABLE ... ADD COLUMN with a serial, bigserial, or smallserial\n\
+         type, or with GENERATED { ALWAYS | BY DEFAULT } AS IDENTITY, where the\n\
+         table already exists (not created in the same set of changed files).\n\
+         \n\
+         Why it's dangerous:\n\
+         Both forms give the new column a sequence-backed value, and\n\
+         PostgreSQL fills it for every existing row at once. That rewrites\n\
+         the whole table while holding an ACCESS EXCLUSIVE lock, blocking all\n\
+         reads and writes for as long as the rewrite takes. The lazy-default\n\
+         optimization of PostgreSQL 11+ does not apply: nextval() is volatile.\n\
+         \n\
+         Example (bad):\n\
+           ALTER TABLE orders ADD COLUMN seq_no bigserial;\n\
+           ALTER TABLE orders ADD COLUMN seq_no bigint GENERATED ALWAYS AS IDENTITY;\n\
+         \n\
+         Fix (add plain, backfill, then attach the sequence):\n\
+           ALTER TABLE orders ADD COLUMN seq_no bigint;\n\
+           CREATE SEQUENCE orders_seq_no_seq OWNED BY orders.seq_no;\n\
+           ALTER TABLE orders ALTER COLUMN seq_no\n\
+             SET DEFAULT nextval('orders_seq_no_seq');\n\
+           -- backfill existing rows in batches, in a separate migration:\n\
+           UPDATE orders SET seq_no = nextval('orders_seq_no_seq')\n\
+             WHERE seq_no IS NULL AND id BETWEEN ...;\n\
+         SET DEFAULT only affects new rows and does not rewrite the table.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Critical;
+
+/// How the column gets its sequence, for the finding message.
+fn sequence_kind(col: &ColumnDef) -> Option<&'static str> {
+    if col.is_identity {
+        Some("identity")
+    } else if col.is_serial {
+        Some("serial")
+    } else {
+        None
+    }
+}
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    alter_table_check::check_alter_actions(
+        statements,
+        ctx,
+        TableScope::ExcludeCreatedInChange,
+        |at, action, stmt, ctx| {
+            let AlterTableAction::AddColumn(col) = action else {
+                return vec![];
+            };
+            let Some(kind) = sequence_kind(col) else {
+                return vec![];
+            };
+            vec![
+                rule.make_finding(
+                    format!(
+                        "Adding {kind} column '{col}' to existing table '{table}' fills \
+                         every existing row from a sequence, rewriting the table under an \
+                         ACCESS EXCLUSIVE lock. Add a plain column, attach the sequence \
+                         with SET DEFAULT, and backfill in batches.",
+                        col = col.name,
+                        table = at.name.display_name(),
+                    ),
+                    ctx.file,
+                    &stmt.span,
+                )
+                .with_param("column", col.name.clone()),
+            ]
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    fn orders_catalog() -> Catalog {
+        CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "bigint", false).pk(&["id"]);
+            })
+            .build()
+    }
+
+    fn add_column(col: ColumnDef) -> Located<IrNode> {
+        located(IrNode::AlterTable(AlterTable {
+            name: QualifiedName::unqualified("orders"),
+            actions: vec![AlterTableAction::AddColumn(col)],
+        }))
+    }
+
+    #[test]
+    fn test_serial_on_existing_table_fires() {
+        let before = orders_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![add_column(ColumnDef::test("seq_no", "int8").with_serial())];
+
+        let findings = RuleId::Pgm029.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_identity_on_existing_table_fires() {
+        let before = orders_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![add_column(
+            ColumnDef::test("seq_no", "int8").with_identity(),
+        )];
+
+        let findings = RuleId::Pgm029.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert!(
+            findings[0]
+                .message
+                .starts_with("Adding identity column 'seq_no' to existing table 'orders'")
+        );
+    }
+
+    #[test]
+    fn test_plain_column_no_finding() {
+        let before = orders_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![add_column(ColumnDef::test("seq_no", "int8"))];
+
+        let findings = RuleId::Pgm029.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_new_table_no_finding() {
+        let before = Catalog::new();
+        let after = orders_catalog();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql", created: ["orders"]);
+
+        let stmts = vec![add_column(ColumnDef::test("seq_no", "int8").with_serial())];
+
+        let findings = RuleId::Pgm029.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+}
@@ -0,0 +1,224 @@
+//! PGM030 — Multiple constraints added in one `ALTER TABLE` on existing table
+//!
+//! Detects a single `ALTER TABLE` statement that adds more than one
+//! constraint to a table that already exists. Each addition is validated (or
+//! at least locked) inside the same statement, so the lock is held for the
+//! sum of all of them and a failure in any one rolls back the rest.
+//!
+//! This pulls the opposite way from PGM023: combining ALTERs saves lock
+//! acquisitions for cheap catalog-only actions, but constraint additions are
+//! the expensive ones and are better issued one per migration.
+
+use crate::parser::ir::{AlterTableAction, IrNode, Located, TableConstraint};
+use crate::rules::{Finding, LintContext, Rule, Severity, TableScope};
+
+pub(super) const DESCRIPTION: &str =
+    "Multiple constraints added in one ALTER TABLE on existing table";
+
+pub(super) const EXPLAIN: &str = "PGM030 — Multiple constraints added in one ALTER TABLE on existing table\n\
+         \n\
+         What it detects:\n\
+         A single ALTER TABLE statement with more than one ADD CONSTRAINT\n\
+         (FOREIGN KEY, CHECK, UNIQUE, PRIMARY KEY, EXCLUDE), on a table that\n\
+         already exists (not created in the same set of changed files).\n\
+         \n\
+         Why it matters:\n\
+         All constraints in the statement are added under one lock. Without\n\
+         NOT VALID, each one scans the table, so the lock is held for every\n\
+         scan back to back. Even with NOT VALID, each foreign key also locks\n\
+         its referenced table, and the follow-up VALIDATE CONSTRAINT advice\n\
+         has to be applied per constraint anyway. If any one constraint\n\
+         fails (a violating row, a lock timeout), the whole statement rolls\n\
+         back and has to be retried as a unit.\n\
+         \n\
+         Example (bad):\n\
+           ALTER TABLE orders\n\
+             ADD CONSTRAINT fk_customer FOREIGN KEY (customer_id)\n\
+               REFERENCES customers (id) NOT VALID,\n\
+             ADD CONSTRAINT fk_product FOREIGN KEY (product_id)\n\
+               REFERENCES products (id) NOT VALID;\n\
+         \n\
+         Fix (one constraint per migration):\n\
+           -- V042:\n\
+           ALTER TABLE orders ADD CONSTRAINT fk_customer FOREIGN KEY (customer_id)\n\
+             REFERENCES customers (id) NOT VALID;\n\
+           -- V043:\n\
+           ALTER TABLE orders ADD CONSTRAINT fk_product FOREIGN KEY (product_id)\n\
+             REFERENCES products (id) NOT VALID;\n\
+         Then VALIDATE each constraint in its own migration.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Minor;
+
+/// Short label for a constraint in the finding message.
+fn describe(constraint: &TableConstraint) -> String {
+    let (kind, name, columns) = match constraint {
+        TableConstraint::PrimaryKey { name, columns, .. } => ("PRIMARY KEY", name, Some(columns)),
+        TableConstraint::ForeignKey { name, columns, .. } => ("FOREIGN KEY", name, Some(columns)),
+        TableConstraint::Unique { name, columns, .. } => ("UNIQUE", name, Some(columns)),
+        TableConstraint::Check { name, .. } => ("CHECK", name, None),
+        TableConstraint::Exclude { name } => ("EXCLUDE", name, None),
+    };
+    match (name, columns) {
+        (Some(name), _) => format!("{kind} '{name}'"),
+        (None, Some(columns)) if !columns.is_empty() => {
+            format!("{kind} ({})", columns.join(", "))
+        }
+        (None, _) => kind.to_string(),
+    }
+}
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for stmt in statements {
+        let IrNode::AlterTable(at) = &stmt.node else {
+            continue;
+        };
+        if !ctx.table_matches_scope(at.name.catalog_key(), TableScope::ExcludeCreatedInChange) {
+            continue;
+        }
+        let added: Vec<String> = at
+            .actions
+            .iter()
+            .filter_map(|a| match a {
+                AlterTableAction::AddConstraint(c) => Some(describe(c)),
+                _ => None,
+            })
+            .collect();
+        if added.len() < 2 {
+            continue;
+        }
+
+        findings.push(
+            rule.make_finding(
+                format!(
+                    "ALTER TABLE on existing table '{}' adds {} constraints in one \
+                     statement ({}). They are added under a single lock and fail or \
+                     succeed together; add one constraint per migration.",
+                    at.name.display_name(),
+                    added.len(),
+                    added.join(", "),
+                ),
+                ctx.file,
+                &stmt.span,
+            )
+            .with_table(&at.name),
+        );
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    fn orders_catalog() -> Catalog {
+        CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "bigint", false)
+                    .column("customer_id", "bigint", true)
+                    .column("product_id", "bigint", true)
+                    .pk(&["id"]);
+            })
+            .build()
+    }
+
+    fn fk(name: &str, column: &str, ref_table: &str) -> AlterTableAction {
+        AlterTableAction::AddConstraint(TableConstraint::ForeignKey {
+            name: Some(name.to_string()),
+            columns: vec![column.to_string()],
+            ref_table: QualifiedName::unqualified(ref_table),
+            ref_columns: vec!["id".to_string()],
+            not_valid: true,
+        })
+    }
+
+    fn alter_orders(actions: Vec<AlterTableAction>) -> Vec<Located<IrNode>> {
+        vec![located(IrNode::AlterTable(AlterTable {
+            name: QualifiedName::unqualified("orders"),
+            actions,
+        }))]
+    }
+
+    #[test]
+    fn test_two_foreign_keys_fire() {
+        let before = orders_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = alter_orders(vec![
+            fk("fk_customer", "customer_id", "customers"),
+            fk("fk_product", "product_id", "products"),
+        ]);
+
+        let findings = RuleId::Pgm030.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_mixed_unnamed_constraints_fire() {
+        let before = orders_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = alter_orders(vec![
+            AlterTableAction::AddConstraint(TableConstraint::Unique {
+                name: None,
+                columns: vec!["customer_id".to_string(), "product_id".to_string()],
+                using_index: None,
+            }),
+            AlterTableAction::AddConstraint(TableConstraint::Check {
+                name: None,
+                expression: "product_id > 0".to_string(),
+                not_valid: true,
+                deps: CheckExprDeps::default(),
+            }),
+        ]);
+
+        let findings = RuleId::Pgm030.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains(
+            "adds 2 constraints in one statement (UNIQUE (customer_id, product_id), CHECK)"
+        ));
+    }
+
+    #[test]
+    fn test_single_constraint_no_finding() {
+        let before = orders_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = alter_orders(vec![
+            fk("fk_customer", "customer_id", "customers"),
+            AlterTableAction::AddColumn(ColumnDef::test("note", "text")),
+        ]);
+
+        let findings = RuleId::Pgm030.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_new_table_no_finding() {
+        let before = Catalog::new();
+        let after = orders_catalog();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql", created: ["orders"]);
+
+        let stmts = alter_orders(vec![
+            fk("fk_customer", "customer_id", "customers"),
+            fk("fk_product", "product_id", "products"),
+        ]);
+
+        let findings = RuleId::Pgm030.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+}
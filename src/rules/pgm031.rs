@@ -0,0 +1,173 @@
+//! PGM031 — Bulk maintenance statement in a migration
+//!
+//! Detects statements that act on every object in a tablespace, schema, or
+//! database instead of on named tables: `ALTER TABLE|INDEX|MATERIALIZED VIEW
+//! ALL IN TABLESPACE`, `REINDEX SCHEMA|DATABASE|SYSTEM`, and `VACUUM` without
+//! a table list. Their lock footprint depends on what the database holds at
+//! deploy time, not on anything the migration names.
+
+use crate::parser::ir::{IrNode, Located, ReindexObjectKind, ReindexTarget};
+use crate::rules::{Finding, LintContext, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str = "Bulk maintenance statement in a migration";
+
+pub(super) const EXPLAIN: &str = "PGM031 — Bulk maintenance statement in a migration\n\
+         \n\
+         What it detects:\n\
+         Maintenance statements that target every object in a tablespace,\n\
+         schema, or database rather than named tables:\n\
+         - ALTER TABLE | INDEX | MATERIALIZED VIEW ALL IN TABLESPACE ... SET TABLESPACE\n\
+         - REINDEX SCHEMA | DATABASE | SYSTEM (with or without CONCURRENTLY)\n\
+         - VACUUM without a table list\n\
+         \n\
+         Why it matters:\n\
+         ALL IN TABLESPACE takes an ACCESS EXCLUSIVE lock on every relation it\n\
+         moves and rewrites each one, so every table in the tablespace becomes\n\
+         unavailable, one after another, for as long as the copy takes.\n\
+         REINDEX SCHEMA/DATABASE rebuilds every index in scope; without\n\
+         CONCURRENTLY each table is locked in turn (see also PGM022), and even\n\
+         with it the I/O load and duration scale with the whole database.\n\
+         VACUUM without a table list processes every table in the database and\n\
+         cannot run inside a transaction block at all. What these statements\n\
+         touch depends on the database at deploy time, not on the migration,\n\
+         so the same file that is instant in staging can run for hours in\n\
+         production.\n\
+         \n\
+         Example (flagged):\n\
+           ALTER TABLE ALL IN TABLESPACE pg_default SET TABLESPACE fast_ssd;\n\
+           REINDEX SCHEMA CONCURRENTLY billing;\n\
+           VACUUM ANALYZE;\n\
+         \n\
+         Fix:\n\
+         Run cluster-wide maintenance as an operational task, outside the\n\
+         migration chain. If a migration must do it, name the tables:\n\
+           ALTER TABLE orders SET TABLESPACE fast_ssd;\n\
+           REINDEX TABLE CONCURRENTLY billing.invoices;\n\
+           VACUUM ANALYZE orders;";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Critical;
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for stmt in statements {
+        let (statement, message) = match &stmt.node {
+            IrNode::MoveAllInTablespace {
+                object_kind,
+                tablespace,
+                new_tablespace,
+                owned_by,
+            } => {
+                let owners = if owned_by.is_empty() {
+                    String::new()
+                } else {
+                    format!(" owned by {}", owned_by.join(", "))
+                };
+                (
+                    format!("ALTER {object_kind} ALL IN TABLESPACE"),
+                    format!(
+                        "ALTER {object_kind} ALL IN TABLESPACE moves every {kind}{owners} \
+                         from '{tablespace}' to '{new_tablespace}', rewriting each one \
+                         under ACCESS EXCLUSIVE lock. Move named relations with \
+                         ALTER {object_kind} ... SET TABLESPACE, or run the move outside \
+                         the migration.",
+                        kind = object_kind.to_lowercase(),
+                    ),
+                )
+            }
+            IrNode::Reindex(r)
+                if matches!(
+                    r.kind,
+                    ReindexObjectKind::Schema
+                        | ReindexObjectKind::Database
+                        | ReindexObjectKind::System
+                ) =>
+            {
+                let scope = match &r.target {
+                    ReindexTarget::Named(name) if !name.is_empty() => {
+                        format!("{} '{name}'", r.kind.to_string().to_lowercase())
+                    }
+                    _ => "the current database".to_string(),
+                };
+                (
+                    format!("REINDEX {}", r.kind),
+                    format!(
+                        "REINDEX {} rebuilds every index in {scope}; its duration and \
+                         lock footprint depend on the whole database, not on this \
+                         migration. Reindex named tables or indexes, or run it outside \
+                         the migration.",
+                        r.kind,
+                    ),
+                )
+            }
+            IrNode::VacuumAllTables => (
+                "VACUUM".to_string(),
+                "VACUUM without a table list processes every table in the database \
+                 and cannot run inside a transaction block. Vacuum named tables, or \
+                 leave database-wide vacuuming to autovacuum or a maintenance job."
+                    .to_string(),
+            ),
+            _ => continue,
+        };
+
+        findings.push(
+            rule.make_finding(message, ctx.file, &stmt.span)
+                .with_param("statement", statement),
+        );
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    #[test]
+    fn test_bulk_statements_fire() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/010.sql");
+
+        let stmts = vec![
+            located(IrNode::MoveAllInTablespace {
+                object_kind: "TABLE".to_string(),
+                tablespace: "pg_default".to_string(),
+                new_tablespace: "fast_ssd".to_string(),
+                owned_by: vec!["app".to_string()],
+            }),
+            located(Reindex::test_schema("billing").with_concurrent().into()),
+            located(Reindex::test_database("").into()),
+            located(IrNode::VacuumAllTables),
+        ];
+
+        let findings = RuleId::Pgm031.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_targeted_statements_no_finding() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/010.sql");
+
+        let stmts = vec![
+            located(Reindex::test_table(QualifiedName::unqualified("orders")).into()),
+            located(VacuumFull::test_all().into()),
+            located(IrNode::AlterIndexSetTablespace {
+                index_name: "idx_orders_status".to_string(),
+                tablespace: "fast_ssd".to_string(),
+            }),
+        ];
+
+        assert!(RuleId::Pgm031.check(&stmts, &ctx).is_empty());
+    }
+}
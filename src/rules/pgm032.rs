@@ -0,0 +1,489 @@
+//! PGM032 — Estimated lock duration exceeds the configured budget
+//!
+//! Estimates how long each migration unit holds locks that block writes,
+//! from the work each statement does under its lock (validation scan, table
+//! rewrite, index build) and the row counts in `[lock_budget.table_rows]`,
+//! and flags units over `[lock_budget] max_seconds`. The model is a rough
+//! throughput estimate, not a prediction: it exists to catch the migration
+//! that rewrites a 40-million-row table, not to tell 3 seconds from 4.
+//!
+//! Inactive until `max_seconds` is set.
+
+use crate::parser::ir::{
+    AlterTableAction, DefaultExpr, IrNode, Located, ReindexObjectKind, ReindexTarget,
+    TableConstraint,
+};
+use crate::rules::fn_volatility::{self, FnVolatility};
+use crate::rules::pgm007::{CastSafety, is_safe_cast};
+use crate::rules::{Finding, LintContext, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str = "Estimated lock duration exceeds the configured budget";
+
+pub(super) const EXPLAIN: &str = "PGM032 — Estimated lock duration exceeds the configured budget\n\
+         \n\
+         What it detects:\n\
+         A migration unit whose estimated time holding locks that block\n\
+         writes exceeds [lock_budget] max_seconds. Each statement on an\n\
+         existing table is costed from the rows in [lock_budget.table_rows]:\n\
+         - Validation scan (SET NOT NULL, ADD CHECK, ADD FOREIGN KEY without\n\
+           NOT VALID, ATTACH PARTITION): rows / scan_rows_per_second.\n\
+         - Index build (CREATE INDEX, ADD PRIMARY KEY / UNIQUE / EXCLUDE,\n\
+           REINDEX without CONCURRENTLY): rows / rewrite_rows_per_second per\n\
+           index.\n\
+         - Table rewrite (ALTER COLUMN TYPE, ADD COLUMN with a volatile\n\
+           default, SET LOGGED / UNLOGGED, VACUUM FULL, CLUSTER): the same,\n\
+           once for the table and once per existing index.\n\
+         Catalog-only changes and CONCURRENTLY / NOT VALID variants cost\n\
+         nothing. In a transaction the unit's statements add up, because every\n\
+         lock is held until commit; otherwise the slowest statement counts.\n\
+         \n\
+         Why it matters:\n\
+         While a statement holds its lock, every write to the table waits,\n\
+         and so does every later query queued behind it. Whether that is a\n\
+         blip or an outage depends on table size, which the SQL does not\n\
+         show. The estimate is approximate; the point is to catch the unit\n\
+         that will lock a large table for minutes before it reaches\n\
+         production.\n\
+         \n\
+         This rule is inactive until a budget is configured:\n\
+           [lock_budget]\n\
+           max_seconds = 5\n\
+           [lock_budget.table_rows]\n\
+           orders = 40000000\n\
+         \n\
+         Example (flagged, ~160s with the config above):\n\
+           ALTER TABLE orders ALTER COLUMN amount TYPE bigint;\n\
+         \n\
+         Fix:\n\
+         Use the variants that avoid long locks, and split the rest across\n\
+         migrations so each stays within budget:\n\
+           ALTER TABLE orders ADD COLUMN amount_new bigint;\n\
+           -- backfill in batches, then swap the columns in a later migration\n\
+         \n\
+         Tables created in the same change are not checked.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Major;
+
+/// Work a statement does on one table while holding a lock that blocks writes.
+#[derive(Debug, Clone, Copy)]
+enum Work {
+    /// Reads every row to validate it.
+    Scan,
+    /// Builds this many indexes.
+    IndexBuilds(usize),
+    /// Writes a new copy of the table and rebuilds its indexes.
+    Rewrite,
+}
+
+/// Estimated cost of one statement, described by its most expensive part.
+struct Estimate<'a> {
+    stmt: &'a Located<IrNode>,
+    seconds: f64,
+    table: String,
+    operation: &'static str,
+    rows: u64,
+    part_seconds: f64,
+}
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    let Some(budget) = ctx.config.lock_budget.max_seconds else {
+        return vec![];
+    };
+
+    let estimates: Vec<Estimate> = statements
+        .iter()
+        .filter_map(|stmt| estimate(stmt, ctx))
+        .collect();
+    let total = if ctx.run_in_transaction {
+        estimates.iter().map(|e| e.seconds).sum()
+    } else {
+        estimates.iter().map(|e| e.seconds).fold(0.0, f64::max)
+    };
+    if total <= budget {
+        return vec![];
+    }
+    let Some(dominant) = estimates
+        .iter()
+        .max_by(|a, b| a.seconds.total_cmp(&b.seconds))
+    else {
+        return vec![];
+    };
+
+    let held = if ctx.run_in_transaction {
+        " The unit runs in one transaction, so every lock is held until commit \
+         and the estimates add up."
+    } else {
+        ""
+    };
+    vec![
+        rule.make_finding(
+            format!(
+                "Estimated time this migration holds locks that block writes is \
+                 {total:.1}s, over the {budget}s budget. The largest share is \
+                 {operation} on '{table}' (~{rows} rows, {part:.1}s).{held} Use \
+                 CONCURRENTLY or NOT VALID where possible and split the rest across \
+                 migrations, or correct [lock_budget.table_rows] if the row count is off.",
+                operation = dominant.operation,
+                table = dominant.table,
+                rows = dominant.rows,
+                part = dominant.part_seconds,
+            ),
+            ctx.file,
+            &dominant.stmt.span,
+        )
+        .with_param("table", dominant.table.as_str())
+        .with_param("statement", dominant.operation)
+        .with_param("estimate", format!("{total:.1}"))
+        .with_param("budget", budget.to_string()),
+    ]
+}
+
+/// Cost `stmt`, or `None` if it takes no lock that blocks writes on an
+/// existing table.
+fn estimate<'a>(stmt: &'a Located<IrNode>, ctx: &LintContext<'_>) -> Option<Estimate<'a>> {
+    let parts = work(&stmt.node, ctx);
+    let mut costed = parts.into_iter().map(|(table_key, operation, work)| {
+        let (rows, seconds) = cost(&table_key, work, ctx);
+        (table_key, operation, rows, seconds)
+    });
+    let first = costed.next()?;
+    let (mut seconds, mut dominant) = (first.3, first);
+    for part in costed {
+        seconds += part.3;
+        if part.3 > dominant.3 {
+            dominant = part;
+        }
+    }
+    let (table_key, operation, rows, part_seconds) = dominant;
+    let table = ctx
+        .catalog_before
+        .get_table(&table_key)
+        .map_or(table_key.clone(), |t| t.display_name.clone());
+    Some(Estimate {
+        stmt,
+        seconds,
+        table,
+        operation,
+        rows,
+        part_seconds,
+    })
+}
+
+/// The locked work `node` does, as `(table key, operation, work)` parts.
+fn work(node: &IrNode, ctx: &LintContext<'_>) -> Vec<(String, &'static str, Work)> {
+    let existing = |key: &str| ctx.is_existing_table(key);
+    match node {
+        IrNode::AlterTable(at) if existing(at.name.catalog_key()) => {
+            let key = at.name.catalog_key();
+            let mut parts = Vec::new();
+            for action in &at.actions {
+                if let Some((operation, work)) = alter_action_work(action, key, ctx) {
+                    parts.push((key.to_string(), operation, work));
+                } else if let AlterTableAction::AttachPartition { child } = action
+                    && existing(child.catalog_key())
+                {
+                    parts.push((
+                        child.catalog_key().to_string(),
+                        "ATTACH PARTITION",
+                        Work::Scan,
+                    ));
+                }
+            }
+            // One ALTER TABLE validates while it rewrites: a rewrite makes the
+            // scans of the same table free.
+            if parts
+                .iter()
+                .any(|(k, _, w)| k == key && matches!(w, Work::Rewrite))
+            {
+                parts.retain(|(k, _, w)| !(k == key && matches!(w, Work::Scan)));
+            }
+            parts
+        }
+        IrNode::CreateIndex(ci)
+            if !ci.concurrent && !ci.only && existing(ci.table_name.catalog_key()) =>
+        {
+            vec![(
+                ci.table_name.catalog_key().to_string(),
+                "CREATE INDEX",
+                Work::IndexBuilds(1),
+            )]
+        }
+        IrNode::Reindex(r) if !r.concurrent => {
+            let (key, indexes) = match (&r.kind, &r.target) {
+                (ReindexObjectKind::Table, ReindexTarget::Relation(table)) => {
+                    let key = table.catalog_key();
+                    let indexes = ctx
+                        .catalog_before
+                        .get_table(key)
+                        .map_or(0, |t| t.indexes.len());
+                    (key, indexes)
+                }
+                (ReindexObjectKind::Index, ReindexTarget::Relation(index)) => {
+                    let Some(key) = ctx.catalog_before.table_for_index(&index.name) else {
+                        return vec![];
+                    };
+                    (key, 1)
+                }
+                _ => return vec![],
+            };
+            if !existing(key) {
+                return vec![];
+            }
+            vec![(key.to_string(), "REINDEX", Work::IndexBuilds(indexes))]
+        }
+        IrNode::VacuumFull(vf) => match &vf.table {
+            Some(table) if existing(table.catalog_key()) => {
+                vec![(
+                    table.catalog_key().to_string(),
+                    "VACUUM FULL",
+                    Work::Rewrite,
+                )]
+            }
+            _ => vec![],
+        },
+        IrNode::Cluster(c) if existing(c.table.catalog_key()) => {
+            vec![(c.table.catalog_key().to_string(), "CLUSTER", Work::Rewrite)]
+        }
+        _ => vec![],
+    }
+}
+
+/// The locked work of one `ALTER TABLE` action on the table itself.
+fn alter_action_work(
+    action: &AlterTableAction,
+    table_key: &str,
+    ctx: &LintContext<'_>,
+) -> Option<(&'static str, Work)> {
+    match action {
+        AlterTableAction::AddColumn(col) => {
+            let default = col.default_expr.as_ref()?;
+            let rewrites = ctx.config.postgres.version.is_some_and(|v| v < 11)
+                || matches!(
+                    default,
+                    DefaultExpr::FunctionCall { name, .. }
+                        if fn_volatility::lookup(name) == Some(FnVolatility::Volatile)
+                );
+            rewrites.then_some(("ADD COLUMN with a volatile default", Work::Rewrite))
+        }
+        AlterTableAction::AlterColumnType {
+            column_name,
+            new_type,
+            old_type,
+        } => {
+            let old = old_type.as_ref().or_else(|| {
+                ctx.catalog_before
+                    .get_table(table_key)
+                    .and_then(|t| t.get_column(column_name))
+                    .map(|c| &c.type_name)
+            });
+            let safe = old.is_some_and(|old| is_safe_cast(old, new_type) == CastSafety::Safe);
+            (!safe).then_some(("ALTER COLUMN TYPE", Work::Rewrite))
+        }
+        // A no-op when the table already has that persistence.
+        AlterTableAction::SetLogged | AlterTableAction::SetUnlogged => {
+            let unlogged = matches!(action, AlterTableAction::SetUnlogged);
+            let changes = ctx
+                .catalog_before
+                .get_table(table_key)
+                .is_some_and(|t| t.unlogged != unlogged);
+            changes.then_some((
+                if unlogged {
+                    "SET UNLOGGED"
+                } else {
+                    "SET LOGGED"
+                },
+                Work::Rewrite,
+            ))
+        }
+        AlterTableAction::SetNotNull { .. } => Some(("SET NOT NULL", Work::Scan)),
+        AlterTableAction::AddConstraint(constraint) => match constraint {
+            TableConstraint::Check {
+                not_valid: false, ..
+            } => Some(("ADD CHECK", Work::Scan)),
+            TableConstraint::ForeignKey {
+                not_valid: false, ..
+            } => Some(("ADD FOREIGN KEY", Work::Scan)),
+            TableConstraint::PrimaryKey {
+                using_index: None, ..
+            } => Some(("ADD PRIMARY KEY", Work::IndexBuilds(1))),
+            TableConstraint::Unique {
+                using_index: None, ..
+            } => Some(("ADD UNIQUE", Work::IndexBuilds(1))),
+            TableConstraint::Exclude { .. } => Some(("ADD EXCLUDE", Work::IndexBuilds(1))),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Estimated rows of the table and seconds for `work` on it.
+fn cost(table_key: &str, work: Work, ctx: &LintContext<'_>) -> (u64, f64) {
+    let budget = &ctx.config.lock_budget;
+    let rows = budget.rows_for(table_key);
+    let passes = match work {
+        Work::Scan => return (rows, rows as f64 / budget.scan_rows_per_second as f64),
+        Work::IndexBuilds(n) => n,
+        Work::Rewrite => {
+            1 + ctx
+                .catalog_before
+                .get_table(table_key)
+                .map_or(0, |t| t.indexes.len())
+        }
+    };
+    (
+        rows,
+        (rows as f64 * passes as f64) / budget.rewrite_rows_per_second as f64,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Config;
+    use crate::catalog::Catalog;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located, located_at};
+
+    fn orders() -> Catalog {
+        CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "bigint", false)
+                    .column("amount", "integer", false)
+                    .column("status", "text", true)
+                    .pk(&["id"])
+                    .index("idx_orders_amount", &["amount"], false);
+            })
+            .build()
+    }
+
+    fn budget(max_seconds: f64) -> Config {
+        let mut config = Config::default();
+        config.lock_budget.max_seconds = Some(max_seconds);
+        config
+            .lock_budget
+            .table_rows
+            .insert("orders".to_string(), 10_000_000);
+        config
+    }
+
+    fn set_not_null(line: usize) -> Located<IrNode> {
+        located_at(
+            IrNode::AlterTable(AlterTable {
+                name: QualifiedName::unqualified("orders"),
+                actions: vec![AlterTableAction::SetNotNull {
+                    column_name: "status".to_string(),
+                }],
+            }),
+            line,
+        )
+    }
+
+    #[test]
+    fn test_rewrite_and_index_build_over_budget_fires() {
+        let before = orders();
+        let after = before.clone();
+        let config = budget(5.0);
+        lint_ctx!(ctx, &before, &after, "migrations/V010.sql", config: &config);
+
+        let stmts = vec![
+            located_at(
+                IrNode::AlterTable(AlterTable {
+                    name: QualifiedName::unqualified("orders"),
+                    actions: vec![AlterTableAction::AlterColumnType {
+                        column_name: "amount".to_string(),
+                        new_type: TypeName::simple("bigint"),
+                        old_type: None,
+                    }],
+                }),
+                1,
+            ),
+            located_at(
+                CreateIndex::test(
+                    Some("idx_orders_status".to_string()),
+                    QualifiedName::unqualified("orders"),
+                )
+                .into(),
+                2,
+            ),
+        ];
+
+        let findings = RuleId::Pgm032.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_outside_transaction_slowest_statement_counts() {
+        let before = orders();
+        let after = before.clone();
+        let config = budget(8.0);
+        let stmts = vec![set_not_null(1), set_not_null(2)];
+
+        lint_ctx!(ctx, &before, &after, "migrations/V010.sql", config: &config);
+        let findings = RuleId::Pgm032.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("10.0s"), "{findings:?}");
+
+        let ctx = LintContext {
+            run_in_transaction: false,
+            ..ctx
+        };
+        assert!(RuleId::Pgm032.check(&stmts, &ctx).is_empty());
+    }
+
+    #[test]
+    fn test_unconfigured_concurrent_or_new_table_no_finding() {
+        let before = orders();
+        let after = before.clone();
+        let index = |concurrent| {
+            vec![located(
+                CreateIndex::test(None, QualifiedName::unqualified("orders"))
+                    .with_concurrent(concurrent)
+                    .into(),
+            )]
+        };
+
+        lint_ctx!(ctx, &before, &after, "migrations/V010.sql");
+        assert!(RuleId::Pgm032.check(&index(false), &ctx).is_empty());
+
+        let config = budget(1.0);
+        lint_ctx!(ctx, &before, &after, "migrations/V010.sql", config: &config);
+        assert!(RuleId::Pgm032.check(&index(true), &ctx).is_empty());
+        assert_eq!(RuleId::Pgm032.check(&index(false), &ctx).len(), 1);
+
+        let created = ["orders".to_string()].into_iter().collect();
+        let ctx = LintContext {
+            tables_created_in_change: &created,
+            ..ctx
+        };
+        assert!(RuleId::Pgm032.check(&index(false), &ctx).is_empty());
+    }
+
+    #[test]
+    fn test_persistence_change_costs_a_rewrite_only_when_it_changes() {
+        let before = orders();
+        let after = before.clone();
+        let config = budget(1.0);
+        lint_ctx!(ctx, &before, &after, "migrations/V010.sql", config: &config);
+        let set = |action| {
+            vec![located(IrNode::AlterTable(AlterTable {
+                name: QualifiedName::unqualified("orders"),
+                actions: vec![action],
+            }))]
+        };
+
+        assert!(
+            RuleId::Pgm032
+                .check(&set(AlterTableAction::SetLogged), &ctx)
+                .is_empty()
+        );
+        let findings = RuleId::Pgm032.check(&set(AlterTableAction::SetUnlogged), &ctx);
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("SET UNLOGGED"), "{findings:?}");
+    }
+}
@@ -0,0 +1,285 @@
+//! PGM033 — CHECK constraint or DEFAULT references a column that does not exist
+//!
+//! Detects CHECK constraints (in `CREATE TABLE` or `ALTER TABLE ... ADD
+//! CONSTRAINT`) whose expression names a column the table does not have
+//! after the unit, and column defaults that are a column reference, which
+//! PostgreSQL never allows. Both are typos that only fail at deploy.
+//!
+//! Column references come from the parser: every column named in a CHECK
+//! expression, and a DEFAULT that is a bare column reference. A column used
+//! inside a larger DEFAULT expression (`DEFAULT lower(nme)`) is not seen.
+
+use crate::catalog::Catalog;
+use crate::catalog::types::TableState;
+use crate::parser::ir::{
+    AlterTableAction, DefaultExpr, IrNode, Located, QualifiedName, TableConstraint,
+};
+use crate::rules::{Finding, LintContext, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str =
+    "CHECK constraint or DEFAULT references a column that does not exist";
+
+pub(super) const EXPLAIN: &str = "PGM033 — CHECK constraint or DEFAULT references a column that does not exist\n\
+         \n\
+         What it detects:\n\
+         A CHECK constraint whose expression names a column the table does\n\
+         not have, and a column DEFAULT that is a column reference rather\n\
+         than a value — usually a misspelled keyword or a string written in\n\
+         double quotes (DEFAULT \"active\").\n\
+         \n\
+         Why it matters:\n\
+         PostgreSQL rejects both when the statement runs ('column does not\n\
+         exist', 'cannot use column reference in DEFAULT expression'), so the\n\
+         migration fails at deploy. Nothing catches it earlier unless the\n\
+         migration is run against a database in CI.\n\
+         \n\
+         Example (bad):\n\
+           ALTER TABLE orders ADD CONSTRAINT orders_amount_positive\n\
+             CHECK (ammount > 0);\n\
+           ALTER TABLE orders ADD COLUMN status text DEFAULT \"pending\";\n\
+         \n\
+         Fix:\n\
+         Use the column's actual name, and write string literals in single\n\
+         quotes:\n\
+           ALTER TABLE orders ADD CONSTRAINT orders_amount_positive\n\
+             CHECK (amount > 0);\n\
+           ALTER TABLE orders ADD COLUMN status text DEFAULT 'pending';";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Critical;
+
+/// System columns. A CHECK may only use `tableoid`; the others are
+/// rejected with a different error and are not reported here.
+const SYSTEM_COLUMNS: &[&str] = &["tableoid", "ctid", "xmin", "xmax", "cmin", "cmax", "oid"];
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for stmt in statements {
+        let (table, checks, defaults) = match &stmt.node {
+            IrNode::CreateTable(ct) => (
+                &ct.name,
+                ct.constraints.iter().collect(),
+                ct.columns
+                    .iter()
+                    .filter_map(|c| Some((c.name.as_str(), c.default_expr.as_ref()?)))
+                    .collect(),
+            ),
+            IrNode::AlterTable(at) => {
+                let mut checks: Vec<&TableConstraint> = Vec::new();
+                let mut defaults: Vec<(&str, &DefaultExpr)> = Vec::new();
+                for action in &at.actions {
+                    match action {
+                        AlterTableAction::AddConstraint(c) => checks.push(c),
+                        AlterTableAction::AddColumn(col) => {
+                            if let Some(default_expr) = &col.default_expr {
+                                defaults.push((&col.name, default_expr));
+                            }
+                        }
+                        AlterTableAction::SetDefault {
+                            column_name,
+                            default_expr,
+                        } => defaults.push((column_name, default_expr)),
+                        _ => {}
+                    }
+                }
+                (&at.name, checks, defaults)
+            }
+            _ => continue,
+        };
+
+        for (column, default_expr) in defaults {
+            let DefaultExpr::ColumnRef(reference) = default_expr else {
+                continue;
+            };
+            findings.push(
+                rule.make_finding(
+                    format!(
+                        "DEFAULT for column '{column}' on '{}' is the column reference \
+                         \"{reference}\", not a value. PostgreSQL rejects column references \
+                         in DEFAULT expressions, so this migration fails at deploy. Write \
+                         string literals in single quotes.",
+                        table.display_name(),
+                    ),
+                    ctx.file,
+                    &stmt.span,
+                )
+                .with_table(table)
+                .with_param("column", column),
+            );
+        }
+
+        let Some(state) = columns_known(ctx.catalog_after, table) else {
+            continue;
+        };
+        for constraint in checks {
+            let TableConstraint::Check {
+                name,
+                expression,
+                deps,
+                ..
+            } = constraint
+            else {
+                continue;
+            };
+            let missing: Vec<&str> = deps
+                .columns
+                .iter()
+                .map(String::as_str)
+                .filter(|c| !SYSTEM_COLUMNS.contains(c) && state.get_column(c).is_none())
+                .collect();
+            if missing.is_empty() {
+                continue;
+            }
+
+            let label = match name {
+                Some(name) => format!("CHECK constraint '{name}'"),
+                None => format!("CHECK ({expression})"),
+            };
+            let columns = missing
+                .iter()
+                .map(|c| format!("'{c}'"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            findings.push(
+                rule.make_finding(
+                    format!(
+                        "{label} on '{table}' references {columns}, which '{table}' does \
+                         not have. PostgreSQL rejects the constraint, so this migration \
+                         fails at deploy. Check the column names for typos.",
+                        table = table.display_name(),
+                    ),
+                    ctx.file,
+                    &stmt.span,
+                )
+                .with_table(table)
+                .with_param("columns", missing.join(", "))
+                .with_quoted_sql(expression),
+            );
+        }
+    }
+
+    findings
+}
+
+/// The table's state after the unit, if its full column list is known:
+/// the table exists, was not touched by unparseable SQL, and every parent
+/// it inherits columns from is in the catalog.
+fn columns_known<'c>(catalog: &'c Catalog, table: &QualifiedName) -> Option<&'c TableState> {
+    let state = catalog.get_table(table.catalog_key())?;
+    let parents_known = state
+        .parent_table
+        .iter()
+        .chain(&state.inherits)
+        .all(|parent| catalog.has_table(parent));
+    (!state.incomplete && parents_known).then_some(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    fn orders() -> Catalog {
+        CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "bigint", false)
+                    .column("amount", "numeric", false)
+                    .pk(&["id"]);
+            })
+            .build()
+    }
+
+    fn add_check(columns: &[&str]) -> Located<IrNode> {
+        located(IrNode::AlterTable(AlterTable {
+            name: QualifiedName::unqualified("orders"),
+            actions: vec![AlterTableAction::AddConstraint(TableConstraint::Check {
+                name: Some("orders_amount_positive".to_string()),
+                expression: "ammount > 0".to_string(),
+                not_valid: false,
+                deps: CheckExprDeps {
+                    columns: columns.iter().map(|c| c.to_string()).collect(),
+                    ..CheckExprDeps::default()
+                },
+            })],
+        }))
+    }
+
+    #[test]
+    fn test_check_with_missing_column_fires() {
+        let before = orders();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/V002.sql");
+
+        let findings = RuleId::Pgm033.check(&[add_check(&["ammount", "tableoid"])], &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_check_with_existing_columns_no_finding() {
+        let before = orders();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/V002.sql");
+
+        assert!(
+            RuleId::Pgm033
+                .check(&[add_check(&["amount", "id"])], &ctx)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_check_on_incomplete_or_unknown_parent_no_finding() {
+        let before = Catalog::new();
+        let after = CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "bigint", false).incomplete();
+            })
+            .table("orders_2024", |t| {
+                t.column("id", "bigint", false)
+                    .partition_of("orders_unknown");
+            })
+            .build();
+        lint_ctx!(ctx, &before, &after, "migrations/V002.sql", created: ["orders_2024"]);
+
+        let mut child = add_check(&["ammount"]);
+        if let IrNode::AlterTable(at) = &mut child.node {
+            at.name = QualifiedName::unqualified("orders_2024");
+        }
+        assert!(
+            RuleId::Pgm033
+                .check(&[add_check(&["ammount"]), child], &ctx)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_column_ref_default_fires() {
+        let before = orders();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/V002.sql");
+
+        let stmts = vec![located(IrNode::AlterTable(AlterTable {
+            name: QualifiedName::unqualified("orders"),
+            actions: vec![
+                AlterTableAction::AddColumn(
+                    ColumnDef::test("status", "text")
+                        .with_default(DefaultExpr::ColumnRef("pending".to_string())),
+                ),
+                AlterTableAction::SetDefault {
+                    column_name: "amount".to_string(),
+                    default_expr: DefaultExpr::Literal("0".to_string()),
+                },
+            ],
+        }))];
+
+        let findings = RuleId::Pgm033.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+}
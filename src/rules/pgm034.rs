@@ -0,0 +1,50 @@
+//! PGM034 — Statement PostgreSQL would reject (strict replay)
+//!
+//! Only active with `catalog.strict_replay`. Replay then refuses statements
+//! PostgreSQL would reject against the replayed schema (see
+//! `catalog::replay::apply_with`) and the pipeline reports each refused
+//! statement of a linted unit at that statement. The per-unit check below
+//! never fires.
+
+use crate::parser::ir::{IrNode, Located};
+use crate::rules::{Finding, LintContext, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str = "Statement PostgreSQL would reject (strict replay)";
+
+pub(super) const EXPLAIN: &str = "PGM034 — Statement PostgreSQL would reject (strict replay)\n\
+         \n\
+         What it detects:\n\
+         With `[catalog] strict_replay = true`, a statement that PostgreSQL\n\
+         would reject against the schema the earlier migrations built:\n\
+         CREATE TABLE or CREATE INDEX for a relation that already exists,\n\
+         ADD COLUMN for a column the table already has, DROP CONSTRAINT for\n\
+         a constraint it does not have, and a foreign key to a table that\n\
+         does not exist. IF [NOT] EXISTS guards are honored. Tables touched\n\
+         by unparseable SQL are not checked.\n\
+         \n\
+         Why it matters:\n\
+         The migration fails at deploy. By default replay is lenient: it\n\
+         applies what it can and keeps going, so the broken statement only\n\
+         shows up as a replay warning, and later migrations are linted\n\
+         against a schema that never exists. Strict replay skips the\n\
+         statement and reports it here instead.\n\
+         \n\
+         Example (flagged):\n\
+           -- V001: CREATE TABLE customers (id bigint PRIMARY KEY, email text);\n\
+           ALTER TABLE customers ADD COLUMN email text;\n\
+         \n\
+         Fix:\n\
+         Drop the duplicate statement, or guard it when the migration must\n\
+         run against databases in different states:\n\
+           ALTER TABLE customers ADD COLUMN IF NOT EXISTS email text;";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Blocker;
+
+pub(super) fn check(
+    _rule: impl Rule,
+    _statements: &[Located<IrNode>],
+    _ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    // Reported by the pipeline from the statements strict replay refused.
+    Vec::new()
+}
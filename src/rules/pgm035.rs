@@ -0,0 +1,257 @@
+//! PGM035 — Replica identity change that burdens or breaks logical replication
+//!
+//! Detects `ALTER TABLE ... REPLICA IDENTITY FULL` on a table that existed
+//! before the change, and dropping the index a table uses for `REPLICA
+//! IDENTITY USING INDEX` (`DROP INDEX`, or `DROP CONSTRAINT` of the
+//! constraint that owns it). The replica identity is followed through the
+//! unit, so switching to another identity before the drop is not reported.
+
+use std::collections::HashMap;
+
+use crate::parser::ir::{AlterTableAction, IrNode, Located, ReplicaIdentity};
+use crate::rules::{Finding, LintContext, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str =
+    "Replica identity change that burdens or breaks logical replication";
+
+pub(super) const EXPLAIN: &str = "PGM035 — Replica identity change that burdens or breaks logical replication\n\
+         \n\
+         What it detects:\n\
+         - ALTER TABLE ... REPLICA IDENTITY FULL on an existing table.\n\
+         - Dropping the index a table uses for REPLICA IDENTITY USING INDEX,\n\
+           with DROP INDEX or by dropping the constraint that owns it.\n\
+         \n\
+         Why it matters:\n\
+         The replica identity decides which old-row values logical decoding\n\
+         writes to the WAL for every UPDATE and DELETE. With FULL, every\n\
+         column of the old row is logged, which multiplies WAL volume on a\n\
+         large, busy table, and subscribers without a usable index look up\n\
+         each change with a sequential scan.\n\
+         When the USING INDEX index is dropped, the table behaves as\n\
+         REPLICA IDENTITY NOTHING: UPDATE and DELETE fail on a table that is\n\
+         part of a publication, and CDC consumers no longer receive keys for\n\
+         changed rows. Dropping the identity index is reported as CRITICAL.\n\
+         \n\
+         Example (flagged):\n\
+           ALTER TABLE orders REPLICA IDENTITY USING INDEX orders_ref_key;\n\
+           -- later\n\
+           DROP INDEX orders_ref_key;\n\
+         \n\
+         Fix:\n\
+         Point the replica identity at another unique index (or back to the\n\
+         primary key) before dropping the index:\n\
+           ALTER TABLE orders REPLICA IDENTITY DEFAULT;\n\
+           DROP INDEX orders_ref_key;\n\
+         Prefer a primary key or a unique index on NOT NULL columns over\n\
+         REPLICA IDENTITY FULL.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Major;
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    // Replica identities set earlier in the unit, by table key.
+    let mut identities: HashMap<String, ReplicaIdentity> = HashMap::new();
+
+    for stmt in statements {
+        match &stmt.node {
+            IrNode::AlterTable(at) => {
+                let table_key = at.name.catalog_key();
+                for action in &at.actions {
+                    match action {
+                        AlterTableAction::SetReplicaIdentity(ReplicaIdentity::Full)
+                            if ctx.is_existing_table(table_key)
+                                && current_identity(&identities, ctx, table_key)
+                                    != Some(&ReplicaIdentity::Full) =>
+                        {
+                            findings.push(
+                                rule.make_finding(
+                                    format!(
+                                        "REPLICA IDENTITY FULL on existing table '{table}' \
+                                         makes logical decoding write every column of the old \
+                                         row for each UPDATE and DELETE, and subscribers \
+                                         without a matching index apply each change with a \
+                                         sequential scan. Use the primary key or REPLICA \
+                                         IDENTITY USING INDEX on a unique index instead.",
+                                        table = at.name.display_name(),
+                                    ),
+                                    ctx.file,
+                                    &stmt.span,
+                                )
+                                .with_table(&at.name),
+                            );
+                        }
+                        AlterTableAction::DropConstraint {
+                            constraint_name, ..
+                        } => {
+                            if let Some(finding) = identity_index_dropped(
+                                &rule,
+                                &identities,
+                                ctx,
+                                stmt,
+                                table_key,
+                                constraint_name,
+                            ) {
+                                findings.push(finding);
+                            }
+                        }
+                        _ => {}
+                    }
+                    if let AlterTableAction::SetReplicaIdentity(identity) = action {
+                        identities.insert(table_key.to_string(), identity.clone());
+                    }
+                }
+            }
+            IrNode::DropIndex(di) => {
+                let Some(table_key) = ctx.catalog_before.table_for_index(&di.index_name) else {
+                    continue;
+                };
+                if let Some(finding) =
+                    identity_index_dropped(&rule, &identities, ctx, stmt, table_key, &di.index_name)
+                {
+                    findings.push(finding);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    findings
+}
+
+/// The table's replica identity at this point of the unit.
+fn current_identity<'a>(
+    identities: &'a HashMap<String, ReplicaIdentity>,
+    ctx: &'a LintContext<'_>,
+    table_key: &str,
+) -> Option<&'a ReplicaIdentity> {
+    identities.get(table_key).or_else(|| {
+        ctx.catalog_before
+            .get_table(table_key)
+            .map(|t| &t.replica_identity)
+    })
+}
+
+/// A finding if `index` is the table's `REPLICA IDENTITY USING INDEX` index.
+fn identity_index_dropped(
+    rule: &impl Rule,
+    identities: &HashMap<String, ReplicaIdentity>,
+    ctx: &LintContext<'_>,
+    stmt: &Located<IrNode>,
+    table_key: &str,
+    index: &str,
+) -> Option<Finding> {
+    match current_identity(identities, ctx, table_key)? {
+        ReplicaIdentity::UsingIndex(name) if name == index => {}
+        _ => return None,
+    }
+    let table = ctx
+        .catalog_before
+        .get_table(table_key)
+        .map_or(table_key, |t| t.display_name.as_str());
+    Some(
+        Finding::new(
+            rule.id(),
+            Severity::Critical,
+            format!(
+                "Dropping index '{index}' removes the replica identity of '{table}' \
+                 (REPLICA IDENTITY USING INDEX). The table then behaves as REPLICA IDENTITY \
+                 NOTHING: UPDATE and DELETE fail while it is in a publication, and CDC \
+                 consumers lose the row keys. Set another replica identity first.",
+            ),
+            ctx.file,
+            &stmt.span,
+        )
+        .with_param("table", table)
+        .with_param("index", index),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    fn orders(identity: ReplicaIdentity) -> Catalog {
+        CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "bigint", false)
+                    .column("ref", "text", false)
+                    .pk(&["id"])
+                    .index("orders_ref_key", &["ref"], true)
+                    .unique("orders_ref_key", &["ref"])
+                    .replica_identity(identity);
+            })
+            .build()
+    }
+
+    fn set_identity(identity: ReplicaIdentity) -> Located<IrNode> {
+        located(IrNode::AlterTable(AlterTable {
+            name: QualifiedName::unqualified("orders"),
+            actions: vec![AlterTableAction::SetReplicaIdentity(identity)],
+        }))
+    }
+
+    #[test]
+    fn test_full_on_existing_table_fires() {
+        let before = orders(ReplicaIdentity::Default);
+        let after = orders(ReplicaIdentity::Full);
+        lint_ctx!(ctx, &before, &after, "migrations/V002.sql");
+
+        let findings = RuleId::Pgm035.check(&[set_identity(ReplicaIdentity::Full)], &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_full_on_new_table_no_finding() {
+        let before = Catalog::new();
+        let after = orders(ReplicaIdentity::Full);
+        lint_ctx!(ctx, &before, &after, "migrations/V002.sql", created: ["orders"]);
+
+        assert!(
+            RuleId::Pgm035
+                .check(&[set_identity(ReplicaIdentity::Full)], &ctx)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_dropping_identity_index_fires() {
+        let before = orders(ReplicaIdentity::UsingIndex("orders_ref_key".to_string()));
+        let after = orders(ReplicaIdentity::Nothing);
+        lint_ctx!(ctx, &before, &after, "migrations/V003.sql");
+
+        let stmts = vec![
+            located(DropIndex::test("orders_ref_key").into()),
+            located(IrNode::AlterTable(AlterTable {
+                name: QualifiedName::unqualified("orders"),
+                actions: vec![AlterTableAction::DropConstraint {
+                    constraint_name: "orders_ref_key".to_string(),
+                    if_exists: false,
+                }],
+            })),
+        ];
+        let findings = RuleId::Pgm035.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_identity_moved_before_drop_no_finding() {
+        let before = orders(ReplicaIdentity::UsingIndex("orders_ref_key".to_string()));
+        let after = orders(ReplicaIdentity::Default);
+        lint_ctx!(ctx, &before, &after, "migrations/V003.sql");
+
+        let stmts = vec![
+            set_identity(ReplicaIdentity::Default),
+            located(DropIndex::test("orders_ref_key").into()),
+        ];
+        assert!(RuleId::Pgm035.check(&stmts, &ctx).is_empty());
+    }
+}
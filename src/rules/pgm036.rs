@@ -0,0 +1,55 @@
+//! PGM036 — Statement fails when applied to PostgreSQL
+//!
+//! Only active with `--verify-against-db` (feature `verify-db`). The
+//! migrations are then applied to a disposable database inside a rolled-back
+//! transaction, and each statement of a changed unit that PostgreSQL rejects
+//! is reported with its error (see `crate::verify`). The per-unit check
+//! below never fires.
+
+use crate::parser::ir::{IrNode, Located};
+use crate::rules::{Finding, LintContext, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str = "Statement fails when applied to PostgreSQL";
+
+pub(super) const EXPLAIN: &str = "PGM036 — Statement fails when applied to PostgreSQL\n\
+         \n\
+         What it detects:\n\
+         With --verify-against-db <conn> (built with the verify-db feature),\n\
+         a statement of a changed migration that fails when the migrations\n\
+         are applied with psql to the given database. Everything runs in one\n\
+         transaction that is rolled back, and each statement in a savepoint,\n\
+         so one failure does not stop the rest. The finding carries\n\
+         PostgreSQL's error message.\n\
+         \n\
+         Why it matters:\n\
+         Static rules only see what the replayed catalog models. Duplicate\n\
+         objects the catalog does not track, casts that fail on the column\n\
+         type, and function bodies that do not compile only fail when\n\
+         PostgreSQL runs the statement, which otherwise happens at deploy.\n\
+         \n\
+         Point the flag at a disposable database with the same extensions\n\
+         and roles as production. Failures in unchanged migrations are\n\
+         printed as warnings: the database does not match what the history\n\
+         expects. CONCURRENTLY is dropped from index statements, and\n\
+         transaction control and VACUUM are skipped. Statements that did not\n\
+         parse are not sent, and a migration with a psql backslash command\n\
+         inside a statement is refused.\n\
+         \n\
+         Example (flagged):\n\
+           -- V001: CREATE TABLE orders (id bigint PRIMARY KEY, note text);\n\
+           ALTER TABLE orders ALTER COLUMN note TYPE integer;\n\
+           -- ERROR: column \"note\" cannot be cast automatically to type integer\n\
+         \n\
+         Fix:\n\
+           ALTER TABLE orders ALTER COLUMN note TYPE integer USING note::integer;";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Blocker;
+
+pub(super) fn check(
+    _rule: impl Rule,
+    _statements: &[Located<IrNode>],
+    _ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    // Reported by the CLI after applying the migrations to the database.
+    Vec::new()
+}
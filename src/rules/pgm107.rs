@@ -5,9 +5,13 @@
 //! exhaust the 2.1 billion (`integer`) or 32 000 (`smallint`) limit.
 //! Migrating to `bigint` later requires an ACCESS EXCLUSIVE lock and full
 //! table rewrite.
+//!
+//! Tables listed in `[rules.pgm107] allow_tables` (small lookup tables) are
+//! exempt.
 
-use crate::parser::ir::{AlterTableAction, IrNode, Located, TableConstraint};
-use crate::rules::{Finding, LintContext, Rule, Severity};
+use crate::config::Config;
+use crate::parser::ir::{AlterTableAction, IrNode, Located, TableConstraint, TypeName};
+use crate::rules::{ConfiguredRule, Finding, LintContext, Rule, RuleId, Severity};
 
 pub(super) const DESCRIPTION: &str =
     "Primary key column uses integer or smallint instead of bigint";
@@ -34,13 +38,18 @@ pub(super) const EXPLAIN: &str = "PGM107 — Integer Primary Key\n\
          Fix:\n\
            CREATE TABLE orders (\n\
              id bigint GENERATED ALWAYS AS IDENTITY PRIMARY KEY\n\
-           );";
+           );\n\
+         \n\
+         Small lookup tables that will never outgrow integer can be exempted\n\
+         in pg-migration-lint.toml:\n\
+           [rules.pgm107]\n\
+           allow_tables = [\"countries\", \"ref.currencies\"]";
 
 pub(super) const DEFAULT_SEVERITY: Severity = Severity::Major;
 
 /// Returns `true` for integer types that are too small for a primary key.
-fn is_small_int_type(type_name: &str) -> bool {
-    matches!(type_name, "int2" | "int4" | "smallint" | "integer")
+fn is_small_int_type(type_name: &TypeName) -> bool {
+    !type_name.is_array() && matches!(&*type_name.name, "int2" | "int4" | "smallint" | "integer")
 }
 
 /// Human-readable label for the flagged type.
@@ -52,102 +61,60 @@ fn display_type(type_name: &str) -> &str {
     }
 }
 
-pub(super) fn check(
-    rule: impl Rule,
-    statements: &[Located<IrNode>],
-    ctx: &LintContext<'_>,
-) -> Vec<Finding> {
-    let mut findings = Vec::new();
-
-    for stmt in statements {
-        match &stmt.node {
-            IrNode::CreateTable(ct) => {
-                // Collect PK column names from table-level constraints
-                let pk_columns: Vec<&str> = ct
-                    .constraints
-                    .iter()
-                    .filter_map(|c| match c {
-                        TableConstraint::PrimaryKey { columns, .. } => Some(columns.as_slice()),
-                        _ => None,
-                    })
-                    .flatten()
-                    .map(String::as_str)
-                    .collect();
-
-                for col in &ct.columns {
-                    if !is_small_int_type(&col.type_name.name) {
-                        continue;
-                    }
-
-                    let is_pk = col.is_inline_pk || pk_columns.iter().any(|&pk| pk == col.name);
-
-                    if is_pk {
-                        findings.push(rule.make_finding(
-                            format!(
-                                "Primary key column '{}' on '{}' uses {}. \
-                                 Consider using bigint to avoid exhausting the \
-                                 integer range on high-write tables.",
-                                col.name,
-                                ct.name.display_name(),
-                                display_type(&col.type_name.name),
-                            ),
-                            ctx.file,
-                            &stmt.span,
-                        ));
-                    }
-                }
-            }
-            IrNode::AlterTable(at) => {
-                for action in &at.actions {
-                    let AlterTableAction::AddConstraint(TableConstraint::PrimaryKey {
-                        columns,
-                        using_index,
-                        ..
-                    }) = action
-                    else {
-                        continue;
-                    };
+/// PGM107 with `[rules.pgm107]` resolved.
+pub(super) struct Pgm107 {
+    allow_tables: Vec<String>,
+}
 
-                    // Look up column types from catalog (before or after)
-                    let table_key = at.name.catalog_key();
-                    let table = ctx
-                        .catalog_before
-                        .get_table(table_key)
-                        .or_else(|| ctx.catalog_after.get_table(table_key));
+impl ConfiguredRule for Pgm107 {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            allow_tables: config.rules.pgm107.allow_tables.clone(),
+        }
+    }
 
-                    let Some(table) = table else {
+    fn check(
+        &self,
+        rule: RuleId,
+        statements: &[Located<IrNode>],
+        ctx: &LintContext<'_>,
+    ) -> Vec<Finding> {
+        let mut findings = Vec::new();
+
+        for stmt in statements {
+            match &stmt.node {
+                IrNode::CreateTable(ct) => {
+                    if ctx.table_in_list(&ct.name, &self.allow_tables) {
                         continue;
-                    };
-
-                    // When USING INDEX is specified, columns is empty — resolve
-                    // the PK columns from the referenced index instead.
-                    let resolved_columns: Vec<String>;
-                    let col_names: &[String] = if columns.is_empty() {
-                        if let Some(idx_name) = using_index {
-                            if let Some(idx) = ctx.get_index(idx_name) {
-                                resolved_columns = idx.column_names().map(String::from).collect();
-                                &resolved_columns
-                            } else {
-                                continue;
-                            }
-                        } else {
+                    }
+
+                    // Collect PK column names from table-level constraints
+                    let pk_columns: Vec<&str> = ct
+                        .constraints
+                        .iter()
+                        .filter_map(|c| match c {
+                            TableConstraint::PrimaryKey { columns, .. } => Some(columns.as_slice()),
+                            _ => None,
+                        })
+                        .flatten()
+                        .map(String::as_str)
+                        .collect();
+
+                    for col in &ct.columns {
+                        if !is_small_int_type(&col.type_name) {
                             continue;
                         }
-                    } else {
-                        columns
-                    };
-
-                    for col_name in col_names {
-                        if let Some(col) = table.get_column(col_name)
-                            && is_small_int_type(&col.type_name.name)
-                        {
+
+                        let is_pk = col.is_inline_pk || pk_columns.iter().any(|&pk| pk == col.name);
+
+                        if is_pk {
                             findings.push(rule.make_finding(
                                 format!(
                                     "Primary key column '{}' on '{}' uses {}. \
                                      Consider using bigint to avoid exhausting the \
                                      integer range on high-write tables.",
-                                    col_name,
-                                    at.name.display_name(),
+                                    col.name,
+                                    ct.name.display_name(),
                                     display_type(&col.type_name.name),
                                 ),
                                 ctx.file,
@@ -156,12 +123,85 @@ pub(super) fn check(
                         }
                     }
                 }
+                IrNode::AlterTable(at) => {
+                    if ctx.table_in_list(&at.name, &self.allow_tables) {
+                        continue;
+                    }
+
+                    for action in &at.actions {
+                        let AlterTableAction::AddConstraint(TableConstraint::PrimaryKey {
+                            columns,
+                            using_index,
+                            ..
+                        }) = action
+                        else {
+                            continue;
+                        };
+
+                        // Look up column types from catalog (before or after)
+                        let table_key = at.name.catalog_key();
+                        let table = ctx
+                            .catalog_before
+                            .get_table(table_key)
+                            .or_else(|| ctx.catalog_after.get_table(table_key));
+
+                        let Some(table) = table else {
+                            continue;
+                        };
+
+                        // When USING INDEX is specified, columns is empty — resolve
+                        // the PK columns from the referenced index instead.
+                        let resolved_columns: Vec<String>;
+                        let col_names: &[String] = if columns.is_empty() {
+                            if let Some(idx_name) = using_index {
+                                if let Some(idx) = ctx.get_index(idx_name) {
+                                    resolved_columns =
+                                        idx.column_names().map(String::from).collect();
+                                    &resolved_columns
+                                } else {
+                                    continue;
+                                }
+                            } else {
+                                continue;
+                            }
+                        } else {
+                            columns
+                        };
+
+                        for col_name in col_names {
+                            if let Some(col) = table.get_column(col_name)
+                                && is_small_int_type(&col.type_name)
+                            {
+                                findings.push(rule.make_finding(
+                                    format!(
+                                        "Primary key column '{}' on '{}' uses {}. \
+                                         Consider using bigint to avoid exhausting the \
+                                         integer range on high-write tables.",
+                                        col_name,
+                                        at.name.display_name(),
+                                        display_type(&col.type_name.name),
+                                    ),
+                                    ctx.file,
+                                    &stmt.span,
+                                ));
+                            }
+                        }
+                    }
+                }
+                _ => {}
             }
-            _ => {}
         }
+
+        findings
     }
+}
 
-    findings
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    Pgm107::from_config(ctx.config).check(rule.id(), statements, ctx)
 }
 
 #[cfg(test)]
@@ -415,4 +455,58 @@ mod tests {
         let findings = RuleId::Pgm107.check(&stmts, &ctx);
         assert!(findings.is_empty());
     }
+
+    #[test]
+    fn test_allow_tables_unqualified_entry_no_finding() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        let mut config = crate::config::Config::default();
+        config.rules.pgm107.allow_tables = vec!["countries".to_string()];
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql", config: &config);
+
+        let mut name = QualifiedName::unqualified("countries");
+        name.set_default_schema("public");
+        let stmts = vec![located(IrNode::CreateTable(
+            CreateTable::test(name).with_columns(vec![
+                ColumnDef::test("id", "int4")
+                    .with_nullable(false)
+                    .with_inline_pk(),
+            ]),
+        ))];
+
+        let findings = RuleId::Pgm107.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_allow_tables_qualified_entry_only_matches_that_schema() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        let mut config = crate::config::Config::default();
+        config.rules.pgm107.allow_tables = vec!["ref.countries".to_string()];
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql", config: &config);
+
+        let stmts = vec![
+            located(IrNode::CreateTable(
+                CreateTable::test(QualifiedName::qualified("ref", "countries")).with_columns(vec![
+                    ColumnDef::test("id", "int4")
+                        .with_nullable(false)
+                        .with_inline_pk(),
+                ]),
+            )),
+            located(IrNode::CreateTable(
+                CreateTable::test(QualifiedName::qualified("sales", "countries")).with_columns(
+                    vec![
+                        ColumnDef::test("id", "int4")
+                            .with_nullable(false)
+                            .with_inline_pk(),
+                    ],
+                ),
+            )),
+        ];
+
+        let findings = RuleId::Pgm107.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("sales.countries"));
+    }
 }
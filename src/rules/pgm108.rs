@@ -148,10 +148,7 @@ mod tests {
             name: QualifiedName::unqualified("users"),
             actions: vec![AlterTableAction::AlterColumnType {
                 column_name: "name".to_string(),
-                new_type: TypeName {
-                    name: "varchar".to_string(),
-                    modifiers: vec![200],
-                },
+                new_type: TypeName::with_modifiers("varchar", vec![200]),
                 old_type: None,
             }],
         }))];
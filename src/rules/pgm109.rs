@@ -106,7 +106,7 @@ mod tests {
             name: QualifiedName::unqualified("sensors"),
             actions: vec![AlterTableAction::AlterColumnType {
                 column_name: "reading".to_string(),
-                new_type: TypeName { name: "float4".to_string(), modifiers: vec![] },
+                new_type: TypeName::simple("float4"),
                 old_type: None,
             }],
         }))
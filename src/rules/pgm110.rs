@@ -0,0 +1,448 @@
+//! PGM110 — UUID stored as text
+//!
+//! Detects `text`, `varchar`, or `char(n)` columns that evidently hold UUIDs:
+//! the column name matches a UUID-ish pattern (`*_id`, `*uuid*`) **and** either
+//! its default calls a UUID generator (`gen_random_uuid()`, ...) or it is a
+//! foreign key to a column that is itself a `uuid` or UUID-generated column.
+//!
+//! The native `uuid` type is 16 bytes instead of 36+, validates its input, and
+//! compares faster. Converting a populated column later requires an ACCESS
+//! EXCLUSIVE lock and full table rewrite.
+//!
+//! Both heuristics are configurable under `[rules.pgm110]`.
+
+use crate::catalog::ColumnState;
+use crate::config::Config;
+use crate::parser::ir::{
+    AlterTableAction, ColumnDef, DefaultExpr, IrNode, Located, QualifiedName, TableConstraint,
+    TypeName,
+};
+use crate::rules::{ConfiguredRule, Finding, LintContext, Rule, RuleId, Severity};
+
+pub(super) const DESCRIPTION: &str = "UUID value stored in a text or varchar column";
+
+pub(super) const EXPLAIN: &str = "PGM110 — UUID stored as text\n\
+         \n\
+         What it detects:\n\
+         A text, varchar, or char(n) column in CREATE TABLE or ADD COLUMN whose\n\
+         name looks like an identifier (*_id, *uuid*) and which evidently holds\n\
+         UUIDs: its DEFAULT calls a UUID generator (gen_random_uuid(),\n\
+         uuid_generate_v4(), ...), or it is a foreign key referencing a uuid\n\
+         column or a UUID-generated column.\n\
+         \n\
+         Why it matters:\n\
+         A UUID in text form takes 36+ bytes per value (plus per-index copies)\n\
+         instead of 16, accepts malformed values, and compares byte-by-byte\n\
+         under collation rules. Converting a populated column to uuid later\n\
+         requires an ACCESS EXCLUSIVE lock and full table rewrite.\n\
+         \n\
+         Example (flagged):\n\
+           CREATE TABLE orders (\n\
+             order_id text DEFAULT gen_random_uuid()::text PRIMARY KEY\n\
+           );\n\
+         \n\
+         Fix:\n\
+           CREATE TABLE orders (\n\
+             order_id uuid DEFAULT gen_random_uuid() PRIMARY KEY\n\
+           );\n\
+         \n\
+         The heuristics are configurable in pg-migration-lint.toml:\n\
+           [rules.pgm110]\n\
+           column_patterns = [\"*_id\", \"*uuid*\"]\n\
+           uuid_functions = [\"gen_random_uuid\", \"uuid_generate_v4\"]";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Minor;
+
+/// Returns `true` for the character types a UUID might be stored in.
+fn is_text_like(type_name: &TypeName) -> bool {
+    !type_name.is_array() && matches!(&*type_name.name, "text" | "varchar" | "bpchar")
+}
+
+/// Human-readable label for the flagged type.
+fn display_type(type_name: &TypeName) -> &str {
+    match &*type_name.name {
+        "bpchar" => "char",
+        name => name,
+    }
+}
+
+/// Case-insensitive glob match where `*` matches any (possibly empty) run of characters.
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.to_ascii_lowercase();
+    let text = text.to_ascii_lowercase();
+    let mut parts = pattern.split('*');
+    // `split` always yields at least one element.
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No `*` in the pattern: require an exact match.
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// Returns the name of the UUID generator called by `expr`, if any.
+///
+/// `Other` expressions are searched textually so that casts such as
+/// `gen_random_uuid()::text` are recognized.
+fn uuid_generator<'f>(expr: &DefaultExpr, functions: &'f [String]) -> Option<&'f str> {
+    match expr {
+        DefaultExpr::FunctionCall { name, .. } => functions
+            .iter()
+            .find(|f| f.eq_ignore_ascii_case(name))
+            .map(String::as_str),
+        DefaultExpr::Other(text) => {
+            let text = text.to_ascii_lowercase();
+            functions
+                .iter()
+                .find(|f| text.contains(&format!("{}(", f.to_ascii_lowercase())))
+                .map(String::as_str)
+        }
+        DefaultExpr::Literal(_) | DefaultExpr::ColumnRef(_) => None,
+    }
+}
+
+/// Returns `true` if a referenced column is a `uuid` or is populated by a UUID generator.
+fn column_holds_uuid(col: &ColumnState, functions: &[String]) -> bool {
+    &*col.type_name.name == "uuid"
+        || col
+            .default_expr
+            .as_ref()
+            .is_some_and(|d| uuid_generator(d, functions).is_some())
+}
+
+/// PGM110 with `[rules.pgm110]` resolved.
+pub(super) struct Pgm110 {
+    column_patterns: Vec<String>,
+    uuid_functions: Vec<String>,
+}
+
+impl Pgm110 {
+    /// Describe why `col` evidently holds UUIDs, or `None` if it does not.
+    ///
+    /// The column must be text-like and match a configured name pattern; the
+    /// evidence is either a UUID-generating default or a foreign key (among
+    /// `constraints` of the same statement) referencing a UUID column.
+    fn uuid_evidence(
+        &self,
+        col: &ColumnDef,
+        constraints: &[&TableConstraint],
+        ctx: &LintContext<'_>,
+    ) -> Option<String> {
+        if !is_text_like(&col.type_name)
+            || !self
+                .column_patterns
+                .iter()
+                .any(|p| glob_match(p, &col.name))
+        {
+            return None;
+        }
+
+        if let Some(func) = col
+            .default_expr
+            .as_ref()
+            .and_then(|d| uuid_generator(d, &self.uuid_functions))
+        {
+            return Some(format!("default calls {func}()"));
+        }
+
+        for constraint in constraints {
+            let TableConstraint::ForeignKey {
+                columns,
+                ref_table,
+                ref_columns,
+                ..
+            } = constraint
+            else {
+                continue;
+            };
+            let Some(pos) = columns.iter().position(|c| *c == col.name) else {
+                continue;
+            };
+            let Some(ref_col_name) = ref_columns.get(pos) else {
+                continue;
+            };
+            let ref_key = ref_table.catalog_key();
+            let ref_col = ctx
+                .catalog_after
+                .get_table(ref_key)
+                .or_else(|| ctx.catalog_before.get_table(ref_key))
+                .and_then(|t| t.get_column(ref_col_name));
+            if let Some(ref_col) = ref_col
+                && column_holds_uuid(ref_col, &self.uuid_functions)
+            {
+                return Some(format!(
+                    "references UUID column {}.{}",
+                    ref_table.display_name(),
+                    ref_col_name
+                ));
+            }
+        }
+
+        None
+    }
+}
+
+impl ConfiguredRule for Pgm110 {
+    fn from_config(config: &Config) -> Self {
+        let config = &config.rules.pgm110;
+        Self {
+            column_patterns: config.column_patterns.clone(),
+            uuid_functions: config.uuid_functions.clone(),
+        }
+    }
+
+    fn check(
+        &self,
+        rule: RuleId,
+        statements: &[Located<IrNode>],
+        ctx: &LintContext<'_>,
+    ) -> Vec<Finding> {
+        let mut findings = Vec::new();
+
+        for stmt in statements {
+            let (table, columns, constraints): (
+                &QualifiedName,
+                Vec<&ColumnDef>,
+                Vec<&TableConstraint>,
+            ) = match &stmt.node {
+                IrNode::CreateTable(ct) => (
+                    &ct.name,
+                    ct.columns.iter().collect(),
+                    ct.constraints.iter().collect(),
+                ),
+                IrNode::AlterTable(at) => (
+                    &at.name,
+                    at.actions
+                        .iter()
+                        .filter_map(|a| match a {
+                            AlterTableAction::AddColumn(col) => Some(col),
+                            _ => None,
+                        })
+                        .collect(),
+                    at.actions
+                        .iter()
+                        .filter_map(|a| match a {
+                            AlterTableAction::AddConstraint(c) => Some(c),
+                            _ => None,
+                        })
+                        .collect(),
+                ),
+                _ => continue,
+            };
+
+            for col in columns {
+                if let Some(evidence) = self.uuid_evidence(col, &constraints, ctx) {
+                    findings.push(rule.make_finding(
+                        format!(
+                            "Column '{}' on '{}' stores UUIDs as {} ({}). \
+                             Use the native uuid type: 16 bytes instead of 36+, \
+                             with input validation and faster comparisons.",
+                            col.name,
+                            table.display_name(),
+                            display_type(&col.type_name),
+                            evidence,
+                        ),
+                        ctx.file,
+                        &stmt.span,
+                    ));
+                }
+            }
+        }
+
+        findings
+    }
+}
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    Pgm110::from_config(ctx.config).check(rule.id(), statements, ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::config::Config;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    fn uuid_default() -> DefaultExpr {
+        DefaultExpr::FunctionCall {
+            name: "gen_random_uuid".to_string(),
+            args: vec![],
+        }
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*_id", "customer_id"));
+        assert!(glob_match("*_id", "CUSTOMER_ID"));
+        assert!(!glob_match("*_id", "identity"));
+        assert!(glob_match("*uuid*", "external_uuid_v2"));
+        assert!(glob_match("id", "id"));
+        assert!(!glob_match("id", "ids"));
+        assert!(glob_match("ref_*_key", "ref_order_key"));
+        assert!(!glob_match("ref_*_key", "ref_key"));
+    }
+
+    #[test]
+    fn test_text_with_uuid_default_fires() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql");
+
+        let stmts = vec![located(IrNode::CreateTable(
+            CreateTable::test(QualifiedName::unqualified("orders")).with_columns(vec![
+                ColumnDef::test("order_id", "text")
+                    .with_default(uuid_default())
+                    .with_inline_pk(),
+            ]),
+        ))];
+
+        let findings = RuleId::Pgm110.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_cast_uuid_default_fires() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql");
+
+        let stmts = vec![located(IrNode::CreateTable(
+            CreateTable::test(QualifiedName::unqualified("orders"))
+                .with_columns(vec![ColumnDef::test("order_uuid", "varchar").with_default(
+                    DefaultExpr::Other("(gen_random_uuid())::text".to_string()),
+                )]),
+        ))];
+
+        let findings = RuleId::Pgm110.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+    }
+
+    #[test]
+    fn test_fk_to_uuid_column_fires() {
+        let before = CatalogBuilder::new()
+            .table("customers", |t| {
+                t.column("id", "uuid", false).pk(&["id"]);
+            })
+            .build();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(IrNode::AlterTable(AlterTable {
+            name: QualifiedName::unqualified("orders"),
+            actions: vec![
+                AlterTableAction::AddColumn(ColumnDef::test("customer_id", "text")),
+                AlterTableAction::AddConstraint(TableConstraint::ForeignKey {
+                    name: None,
+                    columns: vec!["customer_id".to_string()],
+                    ref_table: QualifiedName::unqualified("customers"),
+                    ref_columns: vec!["id".to_string()],
+                    not_valid: false,
+                }),
+            ],
+        }))];
+
+        let findings = RuleId::Pgm110.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_fk_to_text_column_no_finding() {
+        let before = CatalogBuilder::new()
+            .table("countries", |t| {
+                t.column("code", "text", false).pk(&["code"]);
+            })
+            .build();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(IrNode::CreateTable(
+            CreateTable::test(QualifiedName::unqualified("addresses"))
+                .with_columns(vec![ColumnDef::test("country_id", "text")])
+                .with_constraints(vec![TableConstraint::ForeignKey {
+                    name: None,
+                    columns: vec!["country_id".to_string()],
+                    ref_table: QualifiedName::unqualified("countries"),
+                    ref_columns: vec!["code".to_string()],
+                    not_valid: false,
+                }]),
+        ))];
+
+        let findings = RuleId::Pgm110.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_name_without_pattern_no_finding() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql");
+
+        let stmts = vec![located(IrNode::CreateTable(
+            CreateTable::test(QualifiedName::unqualified("orders")).with_columns(vec![
+                ColumnDef::test("token", "text").with_default(uuid_default()),
+            ]),
+        ))];
+
+        let findings = RuleId::Pgm110.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_native_uuid_no_finding() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql");
+
+        let stmts = vec![located(IrNode::CreateTable(
+            CreateTable::test(QualifiedName::unqualified("orders")).with_columns(vec![
+                ColumnDef::test("order_id", "uuid").with_default(uuid_default()),
+            ]),
+        ))];
+
+        let findings = RuleId::Pgm110.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_custom_patterns_and_functions() {
+        let mut config = Config::default();
+        config.rules.pgm110.column_patterns = vec!["*_ref".to_string()];
+        config.rules.pgm110.uuid_functions = vec!["app.new_id".to_string(), "new_id".to_string()];
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql", config: &config);
+
+        let stmts = vec![located(IrNode::CreateTable(
+            CreateTable::test(QualifiedName::unqualified("orders")).with_columns(vec![
+                ColumnDef::test("order_ref", "text").with_default(DefaultExpr::FunctionCall {
+                    name: "new_id".to_string(),
+                    args: vec![],
+                }),
+                // Default patterns no longer apply.
+                ColumnDef::test("order_id", "text").with_default(uuid_default()),
+            ]),
+        ))];
+
+        let findings = RuleId::Pgm110.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("order_ref"));
+    }
+}
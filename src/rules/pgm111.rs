@@ -0,0 +1,177 @@
+//! PGM111 — Column uses a discouraged type
+//!
+//! Detects columns whose type is on the configurable `[rules.pgm111] types`
+//! list. The default list covers types that are deprecated or removed
+//! (`abstime`, `reltime`), discouraged by the PostgreSQL docs (`timetz`), or
+//! better served by a built-in alternative (`hstore`). Organizations can add
+//! their own banned types without a new built-in rule.
+
+use crate::config::Config;
+use crate::parser::ir::{IrNode, Located, TypeName};
+use crate::rules::column_type_check;
+use crate::rules::{ConfiguredRule, Finding, LintContext, Rule, RuleId, Severity};
+
+pub(super) const DESCRIPTION: &str = "Column uses a discouraged type";
+
+pub(super) const EXPLAIN: &str = "PGM111 — Column uses a discouraged type\n\
+         \n\
+         What it detects:\n\
+         A column in CREATE TABLE, ADD COLUMN, or ALTER COLUMN TYPE whose\n\
+         type (or array element type) is listed in [rules.pgm111] types.\n\
+         \n\
+         Default list:\n\
+           hstore   — extension key/value type; jsonb is built in, indexable,\n\
+                      and supports nesting.\n\
+           abstime  — removed in PostgreSQL 12; use timestamptz.\n\
+           reltime  — removed in PostgreSQL 12; use interval.\n\
+           timetz   — a time of day with an offset is ambiguous across DST;\n\
+                      the PostgreSQL docs discourage it. Use timestamptz.\n\
+         \n\
+         money is covered by PGM104 and is not in the default list.\n\
+         \n\
+         Example (flagged):\n\
+           CREATE TABLE products (attrs hstore);\n\
+         \n\
+         Fix:\n\
+           CREATE TABLE products (attrs jsonb);\n\
+         \n\
+         Configure the list in pg-migration-lint.toml:\n\
+           [rules.pgm111]\n\
+           types = [\"hstore\", \"abstime\", \"reltime\", \"timetz\", \"xml\"]";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Minor;
+
+/// Suggested replacement for the types on the default list.
+fn alternative(type_name: &str) -> Option<&'static str> {
+    match type_name {
+        "hstore" => Some("jsonb"),
+        "abstime" | "timetz" => Some("timestamptz"),
+        "reltime" => Some("interval"),
+        _ => None,
+    }
+}
+
+/// PGM111 with `[rules.pgm111] types` resolved.
+pub(super) struct Pgm111 {
+    types: Vec<String>,
+}
+
+impl ConfiguredRule for Pgm111 {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            types: config.rules.pgm111.types.clone(),
+        }
+    }
+
+    fn check(
+        &self,
+        rule: RuleId,
+        statements: &[Located<IrNode>],
+        ctx: &LintContext<'_>,
+    ) -> Vec<Finding> {
+        if self.types.is_empty() {
+            return vec![];
+        }
+        let is_discouraged =
+            |tn: &TypeName| self.types.iter().any(|t| tn.name.eq_ignore_ascii_case(t));
+
+        column_type_check::check_column_types(
+            statements,
+            ctx,
+            rule,
+            is_discouraged,
+            |col, table, tn| {
+                let advice = match alternative(&tn.name) {
+                    Some(alt) => format!("Use {alt} instead."),
+                    None => "Choose a different type.".to_string(),
+                };
+                format!(
+                    "Column '{col}' on '{table}' uses '{tn}', which is on the discouraged \
+                     types list ([rules.pgm111] types). {advice}",
+                    table = table.display_name(),
+                )
+            },
+        )
+    }
+}
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    Pgm111::from_config(ctx.config).check(rule.id(), statements, ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::config::Config;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    fn create_with(type_name: TypeName) -> Located<IrNode> {
+        let mut col = ColumnDef::test("attrs", "text");
+        col.type_name = type_name;
+        located(IrNode::CreateTable(
+            CreateTable::test(QualifiedName::unqualified("products")).with_columns(vec![col]),
+        ))
+    }
+
+    #[test]
+    fn test_hstore_fires() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql");
+
+        let stmts = vec![create_with(TypeName::simple("hstore"))];
+
+        let findings = RuleId::Pgm111.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_array_of_discouraged_type_fires() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql");
+
+        let stmts = vec![create_with(TypeName::simple("timetz").with_array_dims(1))];
+
+        let findings = RuleId::Pgm111.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("'timetz[]'"));
+    }
+
+    #[test]
+    fn test_custom_type_list() {
+        let mut config = Config::default();
+        config.rules.pgm111.types = vec!["XML".to_string()];
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql", config: &config);
+
+        let stmts = vec![
+            create_with(TypeName::simple("xml")),
+            create_with(TypeName::simple("hstore")),
+        ];
+
+        let findings = RuleId::Pgm111.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.ends_with("Choose a different type."));
+    }
+
+    #[test]
+    fn test_allowed_type_no_finding() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql");
+
+        let stmts = vec![create_with(TypeName::simple("jsonb"))];
+
+        let findings = RuleId::Pgm111.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+}
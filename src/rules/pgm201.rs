@@ -4,9 +4,13 @@
 //! Dropping a table is intentional but destructive and irreversible in
 //! production. The DDL itself is instant (no table scan, no extended lock),
 //! so this is not a downtime risk — it is a data loss risk.
+//!
+//! `[rules.pgm201] if_exists_severity` sets a different severity for
+//! `DROP TABLE IF EXISTS`, for teams whose guarded drops are reviewed cleanup.
 
+use crate::config::Config;
 use crate::parser::ir::{IrNode, Located};
-use crate::rules::{Finding, LintContext, Rule, Severity, existing_table_check};
+use crate::rules::{ConfiguredRule, Finding, LintContext, Rule, RuleId, Severity};
 
 pub(super) const DESCRIPTION: &str = "DROP TABLE on existing table";
 
@@ -33,29 +37,69 @@ pub(super) const EXPLAIN: &str = "PGM201 — DROP TABLE on existing table\n\
          2. Consider renaming the table first and waiting before dropping.\n\
          3. Take a backup of the table data if it may be needed later.\n\
          \n\
-         This rule is MINOR severity to flag the operation for human review.";
+         This rule is MINOR severity to flag the operation for human review.\n\
+         Guarded drops can be reported at another severity:\n\
+           [rules.pgm201]\n\
+           if_exists_severity = \"info\"";
 
 pub(super) const DEFAULT_SEVERITY: Severity = Severity::Minor;
 
+/// PGM201 with `[rules.pgm201]` resolved.
+pub(super) struct Pgm201 {
+    if_exists_severity: Option<Severity>,
+}
+
+impl ConfiguredRule for Pgm201 {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            if_exists_severity: config.rules.pgm201.severity(),
+        }
+    }
+
+    fn check(
+        &self,
+        rule: RuleId,
+        statements: &[Located<IrNode>],
+        ctx: &LintContext<'_>,
+    ) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for stmt in statements {
+            let IrNode::DropTable(dt) = &stmt.node else {
+                continue;
+            };
+            if !ctx.is_existing_table(dt.name.catalog_key()) {
+                continue;
+            }
+
+            let mut finding = rule
+                .make_finding(
+                    format!(
+                        "DROP TABLE '{}' removes an existing table. \
+                         This is irreversible and all data will be lost.",
+                        dt.name.display_name()
+                    ),
+                    ctx.file,
+                    &stmt.span,
+                )
+                .with_dedup_key(dt.name.catalog_key().to_string())
+                .with_table(&dt.name);
+            if dt.if_exists
+                && let Some(severity) = self.if_exists_severity
+            {
+                finding.severity = severity;
+            }
+            findings.push(finding);
+        }
+        findings
+    }
+}
+
 pub(super) fn check(
     rule: impl Rule,
     statements: &[Located<IrNode>],
     ctx: &LintContext<'_>,
 ) -> Vec<Finding> {
-    existing_table_check::check_existing_table(statements, ctx, rule, |node| {
-        if let IrNode::DropTable(dt) = node {
-            Some((
-                &dt.name,
-                format!(
-                    "DROP TABLE '{}' removes an existing table. \
-                     This is irreversible and all data will be lost.",
-                    dt.name.display_name()
-                ),
-            ))
-        } else {
-            None
-        }
-    })
+    Pgm201::from_config(ctx.config).check(rule.id(), statements, ctx)
 }
 
 #[cfg(test)]
@@ -112,4 +156,36 @@ mod tests {
         let findings = RuleId::Pgm201.check(&stmts, &ctx);
         assert!(findings.is_empty());
     }
+
+    #[test]
+    fn test_if_exists_severity_applies_only_to_guarded_drops() {
+        let before = CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "integer", false).pk(&["id"]);
+            })
+            .table("orders_old", |t| {
+                t.column("id", "integer", false).pk(&["id"]);
+            })
+            .build();
+        let after = Catalog::new();
+        let mut config = Config::default();
+        config.rules.pgm201.if_exists_severity = Some("info".to_string());
+        lint_ctx!(ctx, &before, &after, "migrations/003.sql", config: &config);
+
+        let stmts = vec![
+            located(IrNode::DropTable(
+                DropTable::test(QualifiedName::unqualified("orders_old")).with_if_exists(true),
+            )),
+            located(IrNode::DropTable(
+                DropTable::test(QualifiedName::unqualified("orders")).with_if_exists(false),
+            )),
+        ];
+
+        let findings = RuleId::Pgm201.instantiate(&config).check(&stmts, &ctx);
+        let severities: Vec<_> = findings.iter().map(|f| f.severity).collect();
+        assert_eq!(severities, [Severity::Info, Severity::Minor]);
+
+        let findings = RuleId::Pgm201.check(&stmts, &ctx);
+        assert_eq!(findings[0].severity, Severity::Info);
+    }
 }
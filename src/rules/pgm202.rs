@@ -3,10 +3,13 @@
 //! Detects `DROP TABLE ... CASCADE` targeting a table that exists in `catalog_before`.
 //! CASCADE silently drops all dependent objects (views, foreign keys, triggers, rules)
 //! that reference the dropped table, amplifying the blast radius beyond a simple DROP.
+//! `[rules.pgm202] if_exists_severity` sets a different severity for
+//! `DROP TABLE IF EXISTS ... CASCADE`.
 
 use crate::catalog::types::ConstraintState;
+use crate::config::Config;
 use crate::parser::ir::{IrNode, Located};
-use crate::rules::{Finding, LintContext, Rule, Severity};
+use crate::rules::{ConfiguredRule, Finding, LintContext, Rule, RuleId, Severity};
 
 pub(super) const DESCRIPTION: &str = "DROP TABLE CASCADE on existing table";
 
@@ -38,68 +41,99 @@ pub(super) const EXPLAIN: &str = "PGM202 — DROP TABLE CASCADE on existing tabl
             if unexpected dependencies remain.\n\
          \n\
          This rule is MAJOR severity because CASCADE silently destroys\n\
-         dependent objects the developer may not be aware of.";
+         dependent objects the developer may not be aware of. Guarded drops\n\
+         can be reported at another severity:\n\
+           [rules.pgm202]\n\
+           if_exists_severity = \"minor\"";
 
 pub(super) const DEFAULT_SEVERITY: Severity = Severity::Major;
 
-pub(super) fn check(
-    rule: impl Rule,
-    statements: &[Located<IrNode>],
-    ctx: &LintContext<'_>,
-) -> Vec<Finding> {
-    let mut findings = Vec::new();
+/// PGM202 with `[rules.pgm202]` resolved.
+pub(super) struct Pgm202 {
+    if_exists_severity: Option<Severity>,
+}
 
-    for stmt in statements {
-        if let IrNode::DropTable(ref dt) = stmt.node {
-            if !dt.cascade {
-                continue;
-            }
+impl ConfiguredRule for Pgm202 {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            if_exists_severity: config.rules.pgm202.severity(),
+        }
+    }
 
-            let table_key = dt.name.catalog_key();
+    fn check(
+        &self,
+        rule: RuleId,
+        statements: &[Located<IrNode>],
+        ctx: &LintContext<'_>,
+    ) -> Vec<Finding> {
+        let mut findings = Vec::new();
 
-            if !ctx.is_existing_table(table_key) {
-                continue;
-            }
+        for stmt in statements {
+            if let IrNode::DropTable(ref dt) = stmt.node {
+                if !dt.cascade {
+                    continue;
+                }
+
+                let table_key = dt.name.catalog_key();
 
-            // Find FK dependencies: tables whose FKs reference the dropped table
-            let mut dependents: Vec<String> = Vec::new();
-            for table in ctx.catalog_before.tables() {
-                // Skip the table being dropped itself
-                if table.name == table_key {
+                if !ctx.is_existing_table(table_key) {
                     continue;
                 }
-                for constraint in &table.constraints {
-                    if let ConstraintState::ForeignKey { ref_table, .. } = constraint
-                        && ref_table == table_key
-                    {
-                        dependents.push(table.display_name.clone());
-                        break; // One mention per table is enough
+
+                // Find FK dependencies: tables whose FKs reference the dropped table
+                let mut dependents: Vec<String> = Vec::new();
+                for table in ctx.catalog_before.tables() {
+                    // Skip the table being dropped itself
+                    if table.name == table_key {
+                        continue;
+                    }
+                    for constraint in &table.constraints {
+                        if let ConstraintState::ForeignKey { ref_table, .. } = constraint
+                            && ref_table == table_key
+                        {
+                            dependents.push(table.display_name.clone());
+                            break; // One mention per table is enough
+                        }
                     }
                 }
-            }
 
-            let message = if dependents.is_empty() {
-                format!(
-                    "DROP TABLE CASCADE on '{}' will silently drop dependent objects. \
-                     Views, triggers, and rules referencing this table are not tracked \
-                     by the catalog and may also be affected.",
-                    dt.name.display_name()
-                )
-            } else {
-                dependents.sort();
-                format!(
-                    "DROP TABLE CASCADE on '{}' will silently drop dependent objects. \
-                     Known FK dependencies from: {}.",
-                    dt.name.display_name(),
-                    dependents.join(", ")
-                )
-            };
-
-            findings.push(rule.make_finding(message, ctx.file, &stmt.span));
+                let message = if dependents.is_empty() {
+                    format!(
+                        "DROP TABLE CASCADE on '{}' will silently drop dependent objects. \
+                         Views, triggers, and rules referencing this table are not tracked \
+                         by the catalog and may also be affected.",
+                        dt.name.display_name()
+                    )
+                } else {
+                    dependents.sort();
+                    format!(
+                        "DROP TABLE CASCADE on '{}' will silently drop dependent objects. \
+                         Known FK dependencies from: {}.",
+                        dt.name.display_name(),
+                        dependents.join(", ")
+                    )
+                };
+
+                let mut finding = rule.make_finding(message, ctx.file, &stmt.span);
+                if dt.if_exists
+                    && let Some(severity) = self.if_exists_severity
+                {
+                    finding.severity = severity;
+                }
+                findings.push(finding);
+            }
         }
+
+        findings
     }
+}
 
-    findings
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    Pgm202::from_config(ctx.config).check(rule.id(), statements, ctx)
 }
 
 #[cfg(test)]
@@ -221,4 +255,30 @@ mod tests {
         let findings = rule_id().check(&stmts, &ctx);
         assert!(findings.is_empty());
     }
+
+    #[test]
+    fn test_drop_cascade_if_exists_uses_configured_severity() {
+        let before = CatalogBuilder::new()
+            .table("customers", |t| {
+                t.column("id", "integer", false).pk(&["id"]);
+            })
+            .build();
+        let after = Catalog::new();
+        let mut config = Config::default();
+        config.rules.pgm202.if_exists_severity = Some("minor".to_string());
+        lint_ctx!(ctx, &before, &after, "migrations/010.sql", config: &config);
+
+        let drop = |if_exists| {
+            vec![located(IrNode::DropTable(
+                DropTable::test(QualifiedName::unqualified("customers"))
+                    .with_if_exists(if_exists)
+                    .with_cascade(true),
+            ))]
+        };
+
+        let findings = rule_id().check(&drop(true), &ctx);
+        assert_eq!(findings[0].severity, Severity::Minor);
+        let findings = rule_id().check(&drop(false), &ctx);
+        assert_eq!(findings[0].severity, Severity::Major);
+    }
 }
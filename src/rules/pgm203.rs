@@ -3,9 +3,12 @@
 //! Detects `TRUNCATE TABLE` targeting a table that exists in `catalog_before`.
 //! TRUNCATE removes all rows instantly but is irreversible and does not fire
 //! ON DELETE triggers. Unlike DELETE, there is no WHERE clause — every row is gone.
+//! `RESTART IDENTITY` also resets the table's sequences and raises the severity;
+//! `TRUNCATE ONLY` on a partitioned parent gets its own message, since PostgreSQL
+//! rejects it.
 
-use crate::parser::ir::{IrNode, Located};
-use crate::rules::{Finding, LintContext, Rule, Severity, existing_table_check};
+use crate::parser::ir::{IrNode, Located, TruncateTable};
+use crate::rules::{Finding, LintContext, Rule, Severity};
 
 pub(super) const DESCRIPTION: &str = "TRUNCATE TABLE on existing table";
 
@@ -30,6 +33,18 @@ pub(super) const EXPLAIN: &str = "PGM203 — TRUNCATE TABLE on existing table\n\
          2. Consider whether ON DELETE triggers need to fire — if so, use DELETE.\n\
          3. If truncating for a schema migration, document the intent clearly.\n\
          \n\
+         RESTART IDENTITY:\n\
+         TRUNCATE ... RESTART IDENTITY also resets the sequences owned by the\n\
+         table's columns. New rows reuse ids that were already handed out, so\n\
+         consumers that rely on monotonic ids (CDC pipelines, caches, external\n\
+         systems holding the old ids) see duplicates or go backwards. The\n\
+         finding is raised to MAJOR.\n\
+         \n\
+         TRUNCATE ONLY on a partitioned table:\n\
+         PostgreSQL refuses to truncate only a partitioned parent, which holds\n\
+         no rows itself. Truncate the partitions directly, or drop ONLY to\n\
+         truncate every partition.\n\
+         \n\
          This rule is MINOR severity to flag the operation for human review.";
 
 pub(super) const DEFAULT_SEVERITY: Severity = Severity::Minor;
@@ -39,20 +54,63 @@ pub(super) fn check(
     statements: &[Located<IrNode>],
     ctx: &LintContext<'_>,
 ) -> Vec<Finding> {
-    existing_table_check::check_existing_table(statements, ctx, rule, |node| {
-        if let IrNode::TruncateTable(tt) = node {
-            Some((
-                &tt.name,
+    let mut findings = Vec::new();
+    for stmt in statements {
+        let IrNode::TruncateTable(ref tt) = stmt.node else {
+            continue;
+        };
+        let table_key = tt.name.catalog_key();
+        if !ctx.is_existing_table(table_key) {
+            continue;
+        }
+
+        let (message, severity) = if only_on_partitioned(tt, ctx) {
+            (
+                format!(
+                    "TRUNCATE ONLY '{}' targets a partitioned table. PostgreSQL \
+                     rejects it because the parent holds no rows; truncate the \
+                     partitions directly, or drop ONLY to truncate all of them.",
+                    tt.name.display_name()
+                ),
+                rule.default_severity(),
+            )
+        } else if tt.restart_identity {
+            (
+                format!(
+                    "TRUNCATE TABLE '{}' RESTART IDENTITY removes all rows from an \
+                     existing table and resets its sequences. New rows reuse ids \
+                     already handed out, breaking consumers that rely on monotonic ids.",
+                    tt.name.display_name()
+                ),
+                Severity::Major,
+            )
+        } else {
+            (
                 format!(
                     "TRUNCATE TABLE '{}' removes all rows from an existing table. \
                      This is irreversible and does not fire ON DELETE triggers.",
                     tt.name.display_name()
                 ),
-            ))
-        } else {
-            None
-        }
-    })
+                rule.default_severity(),
+            )
+        };
+
+        findings.push(
+            Finding::new(rule.id(), severity, message, ctx.file, &stmt.span)
+                .with_dedup_key(table_key.to_string())
+                .with_table(&tt.name),
+        );
+    }
+    findings
+}
+
+/// `TRUNCATE ONLY` on a partitioned parent, which PostgreSQL rejects.
+pub(super) fn only_on_partitioned(tt: &TruncateTable, ctx: &LintContext<'_>) -> bool {
+    tt.only
+        && ctx
+            .catalog_before
+            .get_table(tt.name.catalog_key())
+            .is_some_and(|t| t.is_partitioned)
 }
 
 #[cfg(test)]
@@ -137,4 +195,61 @@ mod tests {
         let findings = rule_id().check(&stmts, &ctx);
         assert!(findings.is_empty());
     }
+
+    #[test]
+    fn test_truncate_restart_identity_raises_severity() {
+        let before = CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "bigint", false).pk(&["id"]);
+            })
+            .build();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/007.sql");
+
+        let stmts = vec![located(
+            TruncateTable::test(QualifiedName::unqualified("orders"))
+                .with_restart_identity(true)
+                .into(),
+        )];
+
+        let findings = rule_id().check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Major);
+        assert!(findings[0].message.contains("resets its sequences"));
+    }
+
+    #[test]
+    fn test_truncate_only_partitioned_parent_message() {
+        let before = CatalogBuilder::new()
+            .table("events", |t| {
+                t.column("id", "bigint", false)
+                    .column("created_at", "timestamptz", false)
+                    .partitioned_by(PartitionStrategy::Range, &["created_at"]);
+            })
+            .table("plain", |t| {
+                t.column("id", "bigint", false);
+            })
+            .build();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/008.sql");
+
+        let stmts = vec![
+            located(
+                TruncateTable::test(QualifiedName::unqualified("events"))
+                    .with_only(true)
+                    .into(),
+            ),
+            located(
+                TruncateTable::test(QualifiedName::unqualified("plain"))
+                    .with_only(true)
+                    .into(),
+            ),
+        ];
+
+        let findings = rule_id().check(&stmts, &ctx);
+        assert_eq!(findings.len(), 2);
+        assert!(findings[0].message.contains("targets a partitioned table"));
+        assert_eq!(findings[0].severity, Severity::Minor);
+        assert!(findings[1].message.contains("removes all rows"));
+    }
 }
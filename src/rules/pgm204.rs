@@ -2,11 +2,12 @@
 //!
 //! Detects `TRUNCATE TABLE ... CASCADE` targeting a table that exists in `catalog_before`.
 //! CASCADE silently extends the truncation to all tables with foreign key references
-//! to the truncated table, and recursively to their dependents.
+//! to the truncated table, and recursively to their dependents. `RESTART IDENTITY`
+//! raises the severity to CRITICAL, as in PGM203.
 
 use crate::catalog::types::ConstraintState;
 use crate::parser::ir::{IrNode, Located};
-use crate::rules::{Finding, LintContext, Rule, Severity};
+use crate::rules::{Finding, LintContext, Rule, Severity, pgm203};
 
 pub(super) const DESCRIPTION: &str = "TRUNCATE TABLE CASCADE on existing table";
 
@@ -37,6 +38,12 @@ pub(super) const EXPLAIN: &str = "PGM204 — TRUNCATE TABLE CASCADE on existing
          3. Use plain TRUNCATE (without CASCADE) so PostgreSQL will error\n\
             if unexpected dependencies remain.\n\
          \n\
+         With RESTART IDENTITY, the sequences of every truncated table are\n\
+         reset as well, and the finding is raised to CRITICAL: ids restart in\n\
+         tables the developer may not know were truncated. TRUNCATE ONLY on a\n\
+         partitioned table is rejected by PostgreSQL and reported with its\n\
+         own message.\n\
+         \n\
          This rule is MAJOR severity because CASCADE silently destroys\n\
          data in dependent tables the developer may not be aware of.";
 
@@ -61,6 +68,17 @@ pub(super) fn check(
                 continue;
             }
 
+            if pgm203::only_on_partitioned(tt, ctx) {
+                let message = format!(
+                    "TRUNCATE ONLY '{}' CASCADE targets a partitioned table. PostgreSQL \
+                     rejects it because the parent holds no rows; without ONLY, the \
+                     truncation covers every partition and cascades from each of them.",
+                    tt.name.display_name()
+                );
+                findings.push(rule.make_finding(message, ctx.file, &stmt.span));
+                continue;
+            }
+
             // Find FK dependencies: tables whose FKs reference the truncated table
             let mut dependents: Vec<String> = Vec::new();
             for table in ctx.catalog_before.tables() {
@@ -78,7 +96,7 @@ pub(super) fn check(
                 }
             }
 
-            let message = if dependents.is_empty() {
+            let mut message = if dependents.is_empty() {
                 format!(
                     "TRUNCATE TABLE '{}' CASCADE silently extends to all tables with \
                      foreign key references to '{}', and recursively to their dependents. \
@@ -98,7 +116,23 @@ pub(super) fn check(
                 )
             };
 
-            findings.push(rule.make_finding(message, ctx.file, &stmt.span));
+            let severity = if tt.restart_identity {
+                message.push_str(
+                    " RESTART IDENTITY also resets the sequences of every truncated \
+                     table, so new rows reuse ids already handed out.",
+                );
+                Severity::Critical
+            } else {
+                rule.default_severity()
+            };
+
+            findings.push(Finding::new(
+                rule.id(),
+                severity,
+                message,
+                ctx.file,
+                &stmt.span,
+            ));
         }
     }
 
@@ -224,4 +258,34 @@ mod tests {
         let findings = rule_id().check(&stmts, &ctx);
         assert!(findings.is_empty());
     }
+
+    #[test]
+    fn test_truncate_cascade_restart_identity_is_critical() {
+        let before = CatalogBuilder::new()
+            .table("customers", |t| {
+                t.column("id", "integer", false).pk(&["id"]);
+            })
+            .build();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/011.sql");
+
+        let stmts = vec![
+            located(
+                TruncateTable::test(QualifiedName::unqualified("customers"))
+                    .with_cascade(true)
+                    .with_restart_identity(true)
+                    .into(),
+            ),
+            located(
+                TruncateTable::test(QualifiedName::unqualified("customers"))
+                    .with_restart_identity(true)
+                    .into(),
+            ),
+        ];
+
+        let findings = rule_id().check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Critical);
+        assert!(findings[0].message.contains("RESTART IDENTITY"));
+    }
 }
@@ -0,0 +1,189 @@
+//! PGM206 — `DROP EXTENSION` with dependent columns
+//!
+//! Detects `DROP EXTENSION` when columns in `catalog_before` use a type the
+//! extension provides (e.g. PostGIS `geometry`, `hstore`) or have a default
+//! calling one of its functions (e.g. `uuid_generate_v4()` from `uuid-ossp`).
+//! Without `CASCADE` the drop fails; with `CASCADE` PostgreSQL silently drops
+//! those columns and defaults.
+//!
+//! Only well-known extensions are recognized (see
+//! [`crate::catalog::extensions`]); dropping an unknown extension never fires.
+
+use crate::catalog::extensions::{self, ExtensionDependency};
+use crate::parser::ir::{IrNode, Located};
+use crate::rules::{Finding, LintContext, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str = "DROP EXTENSION with dependent columns";
+
+pub(super) const EXPLAIN: &str = "PGM206 — DROP EXTENSION with dependent columns\n\
+         \n\
+         What it detects:\n\
+         A DROP EXTENSION statement for an extension that tracked columns\n\
+         depend on, either through their type (hstore, citext, ltree, PostGIS\n\
+         geometry/geography, ...) or through a default that calls one of the\n\
+         extension's functions (uuid_generate_v4() from uuid-ossp, ...).\n\
+         \n\
+         Why it matters:\n\
+         Without CASCADE, PostgreSQL refuses the drop and the migration fails.\n\
+         With CASCADE, it silently drops every column whose type belongs to\n\
+         the extension — with all its data — and removes defaults that call\n\
+         the extension's functions, so subsequent inserts get NULLs or fail\n\
+         NOT NULL checks.\n\
+         \n\
+         Example:\n\
+           -- products.id uuid DEFAULT uuid_generate_v4()\n\
+           -- products.attrs hstore\n\
+           DROP EXTENSION \"uuid-ossp\", hstore CASCADE;\n\
+         \n\
+         Fix:\n\
+         Migrate the dependent columns first (e.g. switch the default to the\n\
+         built-in gen_random_uuid(), convert hstore to jsonb), then drop the\n\
+         extension without CASCADE so PostgreSQL verifies nothing else\n\
+         depends on it.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Major;
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for stmt in statements {
+        let IrNode::DropExtension(ref de) = stmt.node else {
+            continue;
+        };
+        let Some(ext) = extensions::known_extension(&de.name) else {
+            continue;
+        };
+        let dependents = extensions::dependent_columns(ctx.catalog_before, ext);
+        if dependents.is_empty() {
+            continue;
+        }
+
+        let described: Vec<String> = dependents
+            .iter()
+            .map(|(table, col, dep)| match dep {
+                ExtensionDependency::Type => {
+                    format!(
+                        "{}.{} (type {})",
+                        table.display_name, col.name, col.type_name
+                    )
+                }
+                ExtensionDependency::Default => {
+                    format!("{}.{} (default)", table.display_name, col.name)
+                }
+            })
+            .collect();
+        let has_type = dependents
+            .iter()
+            .any(|(_, _, dep)| *dep == ExtensionDependency::Type);
+        let has_default = dependents
+            .iter()
+            .any(|(_, _, dep)| *dep == ExtensionDependency::Default);
+        let dropped = match (has_type, has_default) {
+            (true, true) => "the typed columns with their data, and the defaults",
+            (true, false) => "the typed columns with their data",
+            _ => "the defaults",
+        };
+        let consequence = if de.cascade {
+            format!("CASCADE silently drops {dropped}")
+        } else {
+            format!("The DROP fails without CASCADE; with CASCADE it would drop {dropped}")
+        };
+
+        findings.push(rule.make_finding(
+            format!(
+                "DROP EXTENSION '{}': columns depend on it: {}. {consequence}.",
+                de.name,
+                described.join(", "),
+            ),
+            ctx.file,
+            &stmt.span,
+        ));
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    fn products_catalog() -> Catalog {
+        CatalogBuilder::new()
+            .table("products", |t| {
+                t.column_with_default(
+                    "id",
+                    "uuid",
+                    false,
+                    DefaultExpr::FunctionCall {
+                        name: "uuid_generate_v4".to_string(),
+                        args: vec![],
+                    },
+                )
+                .column("attrs", "hstore", true)
+                .pk(&["id"]);
+            })
+            .build()
+    }
+
+    #[test]
+    fn test_drop_extension_with_typed_column_fires() {
+        let before = products_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(
+            DropExtension::test("hstore").with_cascade(true).into(),
+        )];
+
+        let findings = RuleId::Pgm206.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_drop_extension_with_default_fires() {
+        let before = products_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(DropExtension::test("uuid-ossp").into())];
+
+        let findings = RuleId::Pgm206.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("products.id (default)"));
+        assert!(findings[0].message.contains("fails without CASCADE"));
+        assert!(findings[0].message.ends_with("would drop the defaults."));
+    }
+
+    #[test]
+    fn test_drop_extension_without_dependents_no_finding() {
+        let before = products_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(DropExtension::test("citext").into())];
+
+        let findings = RuleId::Pgm206.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_drop_unknown_extension_no_finding() {
+        let before = products_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(DropExtension::test("pg_stat_statements").into())];
+
+        let findings = RuleId::Pgm206.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+}
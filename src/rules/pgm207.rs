@@ -0,0 +1,280 @@
+//! PGM207 — Column dropped and re-added with a different type
+//!
+//! Detects a column of a table in `catalog_before` that a changed unit drops
+//! and the same or a later changed unit adds back under the same name with
+//! another type. The pair reads like a type change, but every stored value
+//! is lost: the new column starts out NULL or filled with its default.
+//!
+//! The statements are usually in different units, so this rule runs in
+//! [`Rule::check_change`] and points at the `ADD COLUMN`. PGM009 still
+//! reports the drop on its own.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::parser::ir::{AlterTableAction, IrNode, Located, TypeName};
+use crate::rules::{ChangeContext, ChangedUnit, Finding, LintContext, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str = "Column dropped and re-added with a different type";
+
+pub(super) const EXPLAIN: &str = "PGM207 — Column dropped and re-added with a different type\n\
+         \n\
+         What it detects:\n\
+         ALTER TABLE ... DROP COLUMN on an existing table, followed in the\n\
+         same change (the same migration or a later one) by ALTER TABLE ...\n\
+         ADD COLUMN with the same name and a different type.\n\
+         \n\
+         Why it matters:\n\
+         The pair looks like a type change in review, and the individual\n\
+         findings (a DROP COLUMN here, a new column there) are easy not to\n\
+         connect. But DROP COLUMN discards every value in the column, and\n\
+         the re-added column starts out NULL, or with its default, in every\n\
+         row. Nothing converts the old values.\n\
+         \n\
+         Example:\n\
+           ALTER TABLE orders DROP COLUMN status;\n\
+           ALTER TABLE orders ADD COLUMN status integer;\n\
+         \n\
+         Fix:\n\
+         Change the type in place so PostgreSQL converts the values:\n\
+           ALTER TABLE orders ALTER COLUMN status TYPE integer USING status::integer;\n\
+         On a large table, avoid the rewrite: add a column under a new name,\n\
+         backfill it in batches, switch the application over, and drop the\n\
+         old column in a later release.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Critical;
+
+/// Nothing to report per unit; see [`check_change`].
+pub(super) fn check(
+    _rule: impl Rule,
+    _statements: &[Located<IrNode>],
+    _ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    vec![]
+}
+
+/// A pre-existing column dropped earlier in the change.
+struct Dropped<'a> {
+    type_name: &'a TypeName,
+    file: &'a Path,
+    line: usize,
+}
+
+pub(super) fn check_change(
+    rule: impl Rule,
+    units: &[ChangedUnit],
+    ctx: &ChangeContext<'_>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    // Keyed by (table catalog key, column name).
+    let mut dropped: HashMap<(&str, &str), Dropped<'_>> = HashMap::new();
+
+    for changed in units.iter().filter(|c| !c.unit.is_down) {
+        let file = changed.unit.source_file.as_path();
+        for stmt in &changed.unit.statements {
+            let IrNode::AlterTable(ref at) = stmt.node else {
+                continue;
+            };
+            let table_key = at.name.catalog_key();
+            if ctx.tables_created_in_change.contains(table_key) {
+                continue;
+            }
+            for action in &at.actions {
+                match action {
+                    AlterTableAction::DropColumn { name } => {
+                        let Some(column) = ctx
+                            .catalog_before
+                            .get_table(table_key)
+                            .and_then(|t| t.get_column(name))
+                        else {
+                            continue;
+                        };
+                        dropped.insert(
+                            (table_key, name.as_str()),
+                            Dropped {
+                                type_name: &column.type_name,
+                                file,
+                                line: stmt.span.start_line,
+                            },
+                        );
+                    }
+                    AlterTableAction::AddColumn(col) => {
+                        let Some(earlier) = dropped.remove(&(table_key, col.name.as_str())) else {
+                            continue;
+                        };
+                        if *earlier.type_name == col.type_name {
+                            continue;
+                        }
+                        let dropped_at = if earlier.file == file {
+                            format!("line {}", earlier.line)
+                        } else {
+                            format!("{}:{}", earlier.file.display(), earlier.line)
+                        };
+                        findings.push(
+                            rule.make_finding(
+                                format!(
+                                    "Column '{col}' on existing table '{table}' is dropped \
+                                     ({dropped_at}) and re-added as {new} instead of {old}. \
+                                     Every stored value is lost; use ALTER COLUMN ... TYPE \
+                                     ... USING to convert the data.",
+                                    col = col.name,
+                                    table = at.name.display_name(),
+                                    new = col.type_name,
+                                    old = earlier.type_name,
+                                ),
+                                file,
+                                &stmt.span,
+                            )
+                            .with_table(&at.name)
+                            .with_param("column", col.name.as_str())
+                            .with_param("type", col.type_name.to_string()),
+                        );
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::config::Config;
+    use crate::input::MigrationUnit;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::located_at;
+
+    fn orders_catalog() -> Catalog {
+        CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "bigint", false)
+                    .column("status", "text", true)
+                    .pk(&["id"]);
+            })
+            .build()
+    }
+
+    fn alter(action: AlterTableAction, line: usize) -> Located<IrNode> {
+        located_at(
+            IrNode::AlterTable(AlterTable {
+                name: QualifiedName::unqualified("orders"),
+                actions: vec![action],
+            }),
+            line,
+        )
+    }
+
+    fn drop_status(line: usize) -> Located<IrNode> {
+        alter(
+            AlterTableAction::DropColumn {
+                name: "status".to_string(),
+            },
+            line,
+        )
+    }
+
+    fn add_status(type_name: &str, line: usize) -> Located<IrNode> {
+        alter(
+            AlterTableAction::AddColumn(ColumnDef::test("status", type_name)),
+            line,
+        )
+    }
+
+    fn changed(file: &str, statements: Vec<Located<IrNode>>) -> ChangedUnit {
+        ChangedUnit {
+            unit: MigrationUnit {
+                id: file.to_string(),
+                statements,
+                source_file: PathBuf::from(file),
+                source_line_offset: 1,
+                run_in_transaction: true,
+                is_down: false,
+                suppressions: Default::default(),
+                changeset: Default::default(),
+            },
+            tables_created: vec![],
+        }
+    }
+
+    fn check_units(units: &[ChangedUnit], created: &[&str]) -> Vec<Finding> {
+        let before = orders_catalog();
+        let after = before.clone();
+        let created: HashSet<String> = created.iter().map(|t| t.to_string()).collect();
+        let config = Config::default();
+        let ctx = ChangeContext {
+            catalog_before: &before,
+            catalog_after: &after,
+            tables_created_in_change: &created,
+            config: &config,
+        };
+        RuleId::Pgm207.check_change(units, &ctx)
+    }
+
+    #[test]
+    fn test_drop_and_add_across_units_fires() {
+        let units = vec![
+            changed("migrations/003.sql", vec![drop_status(2)]),
+            changed("migrations/004.sql", vec![add_status("integer", 1)]),
+        ];
+        let findings = check_units(&units, &[]);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_drop_and_add_in_one_unit_fires() {
+        let units = vec![changed(
+            "migrations/003.sql",
+            vec![drop_status(1), add_status("jsonb", 2)],
+        )];
+        let findings = check_units(&units, &[]);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].start_line, 2);
+        assert!(findings[0].message.contains("(line 1)"));
+        assert_eq!(findings[0].severity, Severity::Critical);
+    }
+
+    #[test]
+    fn test_same_type_does_not_fire() {
+        let units = vec![changed(
+            "migrations/003.sql",
+            vec![drop_status(1), add_status("text", 2)],
+        )];
+        assert!(check_units(&units, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_add_without_drop_does_not_fire() {
+        let add = alter(
+            AlterTableAction::AddColumn(ColumnDef::test("priority", "integer")),
+            2,
+        );
+        let units = vec![changed("migrations/003.sql", vec![drop_status(1), add])];
+        assert!(check_units(&units, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_table_created_in_change_does_not_fire() {
+        let units = vec![changed(
+            "migrations/003.sql",
+            vec![drop_status(1), add_status("integer", 2)],
+        )];
+        assert!(check_units(&units, &["orders"]).is_empty());
+    }
+
+    #[test]
+    fn test_down_migration_is_ignored() {
+        let mut down = changed("migrations/004.down.sql", vec![add_status("integer", 1)]);
+        down.unit.is_down = true;
+        let units = vec![changed("migrations/003.sql", vec![drop_status(1)]), down];
+        assert!(check_units(&units, &[]).is_empty());
+    }
+}
@@ -5,7 +5,7 @@
 //! duration and can cause significant contention on busy tables.
 
 use crate::parser::ir::{IrNode, Located};
-use crate::rules::{Finding, LintContext, Rule, Severity, existing_table_check};
+use crate::rules::{Finding, LintContext, Rule, Severity, backfill_check, existing_table_check};
 
 pub(super) const DESCRIPTION: &str = "UPDATE on existing table in migration";
 
@@ -33,6 +33,8 @@ pub(super) const EXPLAIN: &str = "PGM302 — UPDATE on existing table in migrati
          \n\
          Not flagged:\n\
          - UPDATE on a table created in the same migration file.\n\
+         - UPDATE that backfills a column added and set NOT NULL in the same\n\
+           migration (reported by PGM024).\n\
          \n\
          This rule is MINOR severity.";
 
@@ -43,8 +45,13 @@ pub(super) fn check(
     statements: &[Located<IrNode>],
     ctx: &LintContext<'_>,
 ) -> Vec<Finding> {
+    // Backfill sequences are reported as a whole by PGM024.
+    let backfills = backfill_check::reported_backfills(statements, ctx);
+
     existing_table_check::check_existing_table(statements, ctx, rule, |node| {
-        if let IrNode::UpdateTable(ut) = node {
+        if let IrNode::UpdateTable(ut) = node
+            && !backfills.iter().any(|b| b.is_update(node))
+        {
             Some((
                 &ut.table_name,
                 format!(
@@ -1,19 +1,21 @@
-//! PGM402 — Missing `IF NOT EXISTS` on `CREATE TABLE` / `CREATE INDEX`
+//! PGM402 — Missing `IF NOT EXISTS` on `CREATE TABLE` / `CREATE INDEX` / `CREATE EXTENSION`
 //!
-//! Detects `CREATE TABLE` or `CREATE INDEX` without the `IF NOT EXISTS` clause.
+//! Detects `CREATE TABLE`, `CREATE INDEX`, or `CREATE EXTENSION` without the
+//! `IF NOT EXISTS` clause.
 //! Without `IF NOT EXISTS`, the statement fails if the object already exists.
 //! In migration pipelines that may be re-run, this causes hard failures.
 
 use crate::parser::ir::{IrNode, Located};
 use crate::rules::{Finding, LintContext, Rule, Severity};
 
-pub(super) const DESCRIPTION: &str = "Missing IF NOT EXISTS on CREATE TABLE / CREATE INDEX";
+pub(super) const DESCRIPTION: &str =
+    "Missing IF NOT EXISTS on CREATE TABLE / CREATE INDEX / CREATE EXTENSION";
 
-pub(super) const EXPLAIN: &str = "PGM402 — Missing IF NOT EXISTS on CREATE TABLE / CREATE INDEX\n\
+pub(super) const EXPLAIN: &str = "PGM402 — Missing IF NOT EXISTS on CREATE TABLE / CREATE INDEX / CREATE EXTENSION\n\
          \n\
          What it detects:\n\
-         A CREATE TABLE or CREATE INDEX statement that does not include the\n\
-         IF NOT EXISTS clause.\n\
+         A CREATE TABLE, CREATE INDEX, or CREATE EXTENSION statement that does\n\
+         not include the IF NOT EXISTS clause.\n\
          \n\
          Why it matters:\n\
          Without IF NOT EXISTS, the statement fails if the object already exists.\n\
@@ -25,10 +27,16 @@ pub(super) const EXPLAIN: &str = "PGM402 — Missing IF NOT EXISTS on CREATE TAB
            -- Fails if 'orders' already exists:\n\
            CREATE TABLE orders (id bigint PRIMARY KEY);\n\
            CREATE INDEX idx_orders_status ON orders (status);\n\
+           CREATE EXTENSION pgcrypto;\n\
          \n\
          Recommended fix:\n\
            CREATE TABLE IF NOT EXISTS orders (id bigint PRIMARY KEY);\n\
-           CREATE INDEX IF NOT EXISTS idx_orders_status ON orders (status);";
+           CREATE INDEX IF NOT EXISTS idx_orders_status ON orders (status);\n\
+           CREATE EXTENSION IF NOT EXISTS pgcrypto;\n\
+         \n\
+         Extensions are database-wide and often pre-installed by the platform\n\
+         or another service, so CREATE EXTENSION is especially likely to hit\n\
+         an existing object.";
 
 pub(super) const DEFAULT_SEVERITY: Severity = Severity::Minor;
 
@@ -62,6 +70,16 @@ pub(super) fn check(
                     &stmt.span,
                 ));
             }
+            IrNode::CreateExtension(ce) if !ce.if_not_exists => {
+                findings.push(rule.make_finding(
+                    format!(
+                        "CREATE EXTENSION '{}': add IF NOT EXISTS for idempotent migrations.",
+                        ce.name
+                    ),
+                    ctx.file,
+                    &stmt.span,
+                ));
+            }
             _ => {}
         }
     }
@@ -152,4 +170,23 @@ mod tests {
         let findings = RuleId::Pgm402.check(&stmts, &ctx);
         assert!(findings.is_empty());
     }
+
+    #[test]
+    fn test_create_extension_without_if_not_exists_fires() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/003.sql");
+
+        let stmts = vec![
+            located(CreateExtension::test("pgcrypto").into()),
+            located(
+                CreateExtension::test("hstore")
+                    .with_if_not_exists(true)
+                    .into(),
+            ),
+        ];
+
+        let findings = RuleId::Pgm402.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
 }
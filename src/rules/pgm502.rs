@@ -5,6 +5,7 @@
 //! processed, so `ALTER TABLE ... ADD PRIMARY KEY` later in the same file
 //! avoids a false positive.
 
+use crate::catalog::replay::sibling_key;
 use crate::parser::ir::{IrNode, Located, TablePersistence};
 use crate::rules::{Finding, LintContext, Rule, Severity};
 
@@ -41,10 +42,25 @@ pub(super) const EXPLAIN: &str = "PGM502 — Table without primary key\n\
          primary key from their parent table. This rule is suppressed for\n\
          partition children when the parent already has a PK or when the\n\
          parent is not in the catalog (common in incremental CI where only\n\
-         new migrations are analyzed).";
+         new migrations are analyzed).\n\
+         \n\
+         Inheritance children (CREATE TABLE ... INHERITS (parent)) are\n\
+         different: PostgreSQL does not copy the parent's primary key, so\n\
+         the child needs its own.";
 
 pub(super) const DEFAULT_SEVERITY: Severity = Severity::Major;
 
+/// The key `table_key` is renamed to by an `ALTER TABLE ... RENAME TO` in
+/// `statements`.
+fn renamed_key(statements: &[Located<IrNode>], table_key: &str) -> Option<String> {
+    statements.iter().find_map(|stmt| match &stmt.node {
+        IrNode::RenameTable { name, new_name } if name.catalog_key() == table_key => {
+            Some(sibling_key(name, new_name))
+        }
+        _ => None,
+    })
+}
+
 pub(super) fn check(
     rule: impl Rule,
     statements: &[Located<IrNode>],
@@ -67,7 +83,10 @@ pub(super) fn check(
             }
 
             // Post-file check: look at catalog_after to see if a PK was added.
-            let table_state = ctx.catalog_after.get_table(table_key);
+            // A table renamed later in the file is looked up under its new name.
+            let table_state = ctx.catalog_after.get_table(table_key).or_else(|| {
+                renamed_key(statements, table_key).and_then(|k| ctx.catalog_after.get_table(&k))
+            });
             let has_pk = table_state.map(|t| t.has_primary_key).unwrap_or(false);
 
             if !has_pk {
@@ -78,11 +97,19 @@ pub(super) fn check(
                     .unwrap_or(false);
 
                 if !has_unique_not_null {
-                    findings.push(rule.make_finding(
-                        format!("Table '{}' has no primary key.", ct.name.display_name()),
-                        ctx.file,
-                        &stmt.span,
-                    ));
+                    let message = if ct.inherits.is_empty() {
+                        format!("Table '{}' has no primary key.", ct.name.display_name())
+                    } else {
+                        let parents: Vec<String> =
+                            ct.inherits.iter().map(|p| p.display_name()).collect();
+                        format!(
+                            "Table '{}' has no primary key. It inherits from '{}', but \
+                             primary keys are not inherited; declare one on the child.",
+                            ct.name.display_name(),
+                            parents.join("', '"),
+                        )
+                    };
+                    findings.push(rule.make_finding(message, ctx.file, &stmt.span));
                 }
             }
         }
@@ -153,6 +180,34 @@ mod tests {
         assert!(findings.is_empty());
     }
 
+    #[test]
+    fn test_renamed_later_in_file_no_finding() {
+        let before = Catalog::new();
+        let after = CatalogBuilder::new()
+            .table("events_v2", |t| {
+                t.column("id", "bigint", false).pk(&["id"]);
+            })
+            .build();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql");
+
+        let stmts = vec![
+            located(IrNode::CreateTable(
+                CreateTable::test(QualifiedName::unqualified("events")).with_columns(vec![
+                    ColumnDef::test("id", "bigint")
+                        .with_nullable(false)
+                        .with_inline_pk(),
+                ]),
+            )),
+            located(IrNode::RenameTable {
+                name: QualifiedName::unqualified("events"),
+                new_name: "events_v2".to_string(),
+            }),
+        ];
+
+        let findings = RuleId::Pgm502.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+
     #[test]
     fn test_pk_added_later_in_file_no_finding() {
         let before = Catalog::new();
@@ -319,6 +374,33 @@ mod tests {
         assert_eq!(findings.len(), 1, "Should fire when parent lacks PK");
     }
 
+    #[test]
+    fn test_inheritance_child_does_not_inherit_pk() {
+        let before = Catalog::new();
+        let after = CatalogBuilder::new()
+            .table("cities", |t| {
+                t.column("id", "integer", false).pk(&["id"]);
+            })
+            .table("capitals", |t| {
+                t.column("id", "integer", false).inherits(&["cities"]);
+            })
+            .build();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql");
+
+        let stmts = vec![located(IrNode::CreateTable(
+            CreateTable::test(QualifiedName::unqualified("capitals"))
+                .with_inherits(vec![QualifiedName::unqualified("cities")]),
+        ))];
+
+        let findings = RuleId::Pgm502.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert!(
+            findings[0]
+                .message
+                .contains("It inherits from 'cities', but primary keys are not inherited")
+        );
+    }
+
     #[test]
     fn test_partition_child_parent_not_in_catalog_suppressed() {
         let before = Catalog::new();
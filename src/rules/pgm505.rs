@@ -3,9 +3,13 @@
 //! Detects `ALTER TABLE ... RENAME COLUMN ... TO ...` on tables that already
 //! exist. Renaming a column breaks any queries, views, or application code
 //! that references the old column name.
+//!
+//! When the catalog shows indexes, constraints, or foreign keys from other
+//! tables that use the column, they are listed in the message and the
+//! finding is raised to MINOR.
 
 use crate::parser::ir::{IrNode, Located};
-use crate::rules::{Finding, LintContext, Rule, Severity, existing_table_check};
+use crate::rules::{Finding, LintContext, Rule, Severity, column_dependents};
 
 pub(super) const DESCRIPTION: &str = "RENAME COLUMN on existing table";
 
@@ -36,7 +40,12 @@ pub(super) const EXPLAIN: &str = "PGM505 — RENAME COLUMN on existing table\n\
          \n\
          This rule does NOT fire when the table is created in the same set of\n\
          changed files, because renaming a column on a new table has no\n\
-         external consumers.";
+         external consumers.\n\
+         \n\
+         When the column is used by indexes, constraints, or foreign keys\n\
+         from other tables, the finding lists them and is raised to MINOR.\n\
+         PostgreSQL carries those objects over to the new name, but they mark\n\
+         the column as a key that joins and lookups elsewhere depend on.";
 
 pub(super) const DEFAULT_SEVERITY: Severity = Severity::Info;
 
@@ -45,27 +54,47 @@ pub(super) fn check(
     statements: &[Located<IrNode>],
     ctx: &LintContext<'_>,
 ) -> Vec<Finding> {
-    existing_table_check::check_existing_table(statements, ctx, rule, |node| {
-        if let IrNode::RenameColumn {
+    let mut findings = Vec::new();
+    for stmt in statements {
+        let IrNode::RenameColumn {
             table,
             old_name,
             new_name,
-        } = node
-        {
-            Some((
-                table,
-                format!(
-                    "Renaming column '{old_name}' to '{new_name}' on existing table \
-                     '{table}' will break queries referencing the old column name.",
-                    old_name = old_name,
-                    new_name = new_name,
-                    table = table.display_name(),
-                ),
-            ))
-        } else {
-            None
+        } = &stmt.node
+        else {
+            continue;
+        };
+        let table_key = table.catalog_key();
+        if !ctx.is_existing_table(table_key) {
+            continue;
         }
-    })
+
+        let mut message = format!(
+            "Renaming column '{old_name}' to '{new_name}' on existing table \
+             '{table}' will break queries referencing the old column name.",
+            table = table.display_name(),
+        );
+        let dependents = column_dependents::dependent_labels(ctx, table_key, old_name);
+        let severity = if dependents.labels.is_empty() {
+            rule.default_severity()
+        } else {
+            message.push_str(&format!(
+                " It is used by {}; queries joining or filtering through these \
+                 are the ones most likely to break.",
+                dependents.labels.join(", ")
+            ));
+            Severity::Minor
+        };
+        findings.push(
+            dependents.quoted_on(
+                Finding::new(rule.id(), severity, message, ctx.file, &stmt.span)
+                    .with_dedup_key(table_key.to_string())
+                    .with_table(table)
+                    .with_param("column", old_name.as_str()),
+            ),
+        );
+    }
+    findings
 }
 
 #[cfg(test)]
@@ -99,6 +128,38 @@ mod tests {
         insta::assert_yaml_snapshot!(findings);
     }
 
+    #[test]
+    fn test_rename_referenced_column_raises_severity() {
+        let before = CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "integer", false).pk(&["id"]);
+            })
+            .table("order_items", |t| {
+                t.column("order_id", "integer", false).fk(
+                    "order_items_order_fk",
+                    &["order_id"],
+                    "orders",
+                    &["id"],
+                );
+            })
+            .build();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(IrNode::RenameColumn {
+            table: QualifiedName::unqualified("orders"),
+            old_name: "id".to_string(),
+            new_name: "order_id".to_string(),
+        })];
+
+        let findings = RuleId::Pgm505.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Minor);
+        assert!(findings[0].message.contains(
+            "It is used by PRIMARY KEY (id), FOREIGN KEY 'order_items_order_fk' on 'order_items';"
+        ));
+    }
+
     #[test]
     fn test_rename_column_on_new_table_no_finding() {
         let before = Catalog::new();
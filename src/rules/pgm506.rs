@@ -1,19 +1,28 @@
-//! PGM506 — `CREATE UNLOGGED TABLE`
+//! PGM506 — Unlogged tables
 //!
-//! Detects `CREATE UNLOGGED TABLE` statements. Unlogged tables are not
-//! written to the write-ahead log, which makes them faster for write-heavy
-//! workloads but means they are truncated after a crash and are not
-//! replicated to standby servers.
+//! Detects `CREATE UNLOGGED TABLE` statements and changes to the persistence
+//! of existing tables. Unlogged tables are not written to the write-ahead
+//! log, which makes them faster for write-heavy workloads but means they are
+//! truncated after a crash and are not replicated to standby servers.
+//!
+//! Persistence is tracked in the catalog across the migration history, so
+//! `SET LOGGED` is only reported for tables that are unlogged before the
+//! change and `SET UNLOGGED` only for tables that are logged.
 
-use crate::parser::ir::{IrNode, Located, TablePersistence};
-use crate::rules::{Finding, LintContext, Rule, Severity};
+use crate::parser::ir::{AlterTableAction, IrNode, Located, TablePersistence};
+use crate::rules::{Finding, LintContext, Rule, Severity, TableScope, alter_table_check};
 
-pub(super) const DESCRIPTION: &str = "CREATE UNLOGGED TABLE";
+pub(super) const DESCRIPTION: &str = "Unlogged table created, or table persistence changed";
 
-pub(super) const EXPLAIN: &str = "PGM506 — CREATE UNLOGGED TABLE\n\
+pub(super) const EXPLAIN: &str = "PGM506 — Unlogged table created, or table persistence changed\n\
          \n\
          What it detects:\n\
-         A CREATE TABLE statement that uses the UNLOGGED keyword.\n\
+         - A CREATE TABLE statement that uses the UNLOGGED keyword (INFO).\n\
+         - ALTER TABLE ... SET UNLOGGED on an existing logged table (MAJOR).\n\
+         - ALTER TABLE ... SET LOGGED on an existing unlogged table (MINOR).\n\
+         Whether an existing table is logged is taken from the replayed\n\
+         migration history. Tables created in the same set of changed files\n\
+         are only reported for the CREATE.\n\
          \n\
          Why it matters:\n\
          Unlogged tables offer better write performance because they skip\n\
@@ -24,16 +33,24 @@ pub(super) const EXPLAIN: &str = "PGM506 — CREATE UNLOGGED TABLE\n\
          These characteristics make unlogged tables unsuitable for any\n\
          data that must survive a crash or be available on replicas.\n\
          \n\
+         Converting an existing table to UNLOGGED puts data that was durable\n\
+         at risk: the next crash empties the table, and standbys lose it.\n\
+         Converting an UNLOGGED table back with SET LOGGED rewrites the whole\n\
+         table under an ACCESS EXCLUSIVE lock and writes all of it to the\n\
+         WAL, which blocks reads and writes and can flood replication.\n\
+         \n\
          Example (flagged):\n\
            CREATE UNLOGGED TABLE scratch_data (id int, payload text);\n\
+           ALTER TABLE orders SET UNLOGGED;\n\
+           ALTER TABLE scratch_data SET LOGGED;\n\
          \n\
          When unlogged tables are appropriate:\n\
          - Ephemeral staging/import data that can be re-derived.\n\
          - Materialised caches where the source of truth lives elsewhere.\n\
          - ETL scratch space within a batch job.\n\
          \n\
-         This rule is INFO severity — it flags the table for review rather\n\
-         than treating it as a defect.";
+         CREATE UNLOGGED TABLE is INFO severity — it flags the table for\n\
+         review rather than treating it as a defect.";
 
 pub(super) const DEFAULT_SEVERITY: Severity = Severity::Info;
 
@@ -60,6 +77,44 @@ pub(super) fn check(
         }
     }
 
+    findings.extend(alter_table_check::check_alter_actions(
+        statements,
+        ctx,
+        TableScope::ExcludeCreatedInChange,
+        |at, action, stmt, ctx| {
+            let Some(table) = ctx.catalog_before.get_table(at.name.catalog_key()) else {
+                return vec![];
+            };
+            let (severity, message) = match action {
+                AlterTableAction::SetUnlogged if !table.unlogged => (
+                    Severity::Major,
+                    format!(
+                        "SET UNLOGGED on existing table '{}'. Its data will be truncated \
+                         on crash recovery and is no longer replicated to standbys.",
+                        at.name.display_name()
+                    ),
+                ),
+                AlterTableAction::SetLogged if table.unlogged => (
+                    Severity::Minor,
+                    format!(
+                        "SET LOGGED on unlogged table '{}' rewrites the whole table \
+                         under an ACCESS EXCLUSIVE lock and writes all of it to the WAL. \
+                         Schedule it for a maintenance window.",
+                        at.name.display_name()
+                    ),
+                ),
+                _ => return vec![],
+            };
+            vec![Finding::new(
+                rule.id(),
+                severity,
+                message,
+                ctx.file,
+                &stmt.span,
+            )]
+        },
+    ));
+
     findings
 }
 
@@ -67,6 +122,7 @@ pub(super) fn check(
 mod tests {
     use super::*;
     use crate::catalog::Catalog;
+    use crate::catalog::builder::CatalogBuilder;
     use crate::parser::ir::*;
     use crate::rules::RuleId;
     use crate::rules::test_helpers::{lint_ctx, located};
@@ -124,4 +180,84 @@ mod tests {
         let findings = rule_id().check(&stmts, &ctx);
         assert!(findings.is_empty());
     }
+
+    fn set_persistence(table: &str, action: AlterTableAction) -> Located<IrNode> {
+        located(IrNode::AlterTable(AlterTable {
+            name: QualifiedName::unqualified(table),
+            actions: vec![action],
+        }))
+    }
+
+    #[test]
+    fn test_set_unlogged_on_logged_table_fires_major() {
+        let before = CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "integer", false).pk(&["id"]);
+            })
+            .build();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![set_persistence("orders", AlterTableAction::SetUnlogged)];
+
+        let findings = rule_id().check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Major);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_set_logged_on_unlogged_table_fires_minor() {
+        let before = CatalogBuilder::new()
+            .table("scratch", |t| {
+                t.column("id", "integer", false).unlogged();
+            })
+            .build();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![set_persistence("scratch", AlterTableAction::SetLogged)];
+
+        let findings = rule_id().check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Minor);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_persistence_change_that_is_a_no_op_no_finding() {
+        let before = CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "integer", false);
+            })
+            .table("scratch", |t| {
+                t.column("id", "integer", false).unlogged();
+            })
+            .build();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![
+            set_persistence("orders", AlterTableAction::SetLogged),
+            set_persistence("scratch", AlterTableAction::SetUnlogged),
+        ];
+
+        assert!(rule_id().check(&stmts, &ctx).is_empty());
+    }
+
+    #[test]
+    fn test_set_unlogged_on_table_created_in_change_no_finding() {
+        // Created by an earlier changed unit, so it is in `before` but empty.
+        let before = CatalogBuilder::new()
+            .table("staging", |t| {
+                t.column("id", "integer", false);
+            })
+            .build();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql", created: ["staging"]);
+
+        let stmts = vec![set_persistence("staging", AlterTableAction::SetUnlogged)];
+
+        assert!(rule_id().check(&stmts, &ctx).is_empty());
+    }
 }
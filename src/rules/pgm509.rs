@@ -1,15 +1,20 @@
 //! PGM509 — Mixed-case identifiers or reserved words
 //!
-//! Detects table and column names that require perpetual double-quoting,
-//! either because they contain uppercase characters or because they are
-//! PostgreSQL reserved words.
+//! Detects identifiers created by CREATE/ALTER statements (tables, columns,
+//! indexes, constraints, schemas, sequences) that require perpetual
+//! double-quoting, either because they contain uppercase characters or
+//! because they are PostgreSQL reserved words.
 //!
-//! Key insight: `pg_query` (libpg_query) lowercases unquoted identifiers and
-//! preserves case for quoted ones. So if a name contains uppercase chars, it
-//! was necessarily quoted. If a name is a reserved word and the parse succeeded,
-//! it was necessarily quoted.
-
-use crate::parser::ir::{AlterTableAction, IrNode, Located};
+//! Table, sequence, and column names carry their source [`Spelling`], so only
+//! names the migration actually quoted are reported. Other names fall back on
+//! the key insight: `pg_query` (libpg_query) lowercases unquoted identifiers
+//! and preserves case for quoted ones. So if a name contains uppercase chars,
+//! it was necessarily quoted. If a name is a reserved word and the parse
+//! succeeded, it was necessarily quoted.
+
+use crate::parser::ir::{
+    AlterTableAction, IrNode, Located, Spelling, TableConstraint, requires_quoting,
+};
 use crate::rules::{Finding, LintContext, Rule, Severity, reserved_keywords};
 
 pub(super) const DESCRIPTION: &str =
@@ -18,8 +23,8 @@ pub(super) const DESCRIPTION: &str =
 pub(super) const EXPLAIN: &str = "PGM509 — Mixed-case identifiers or reserved words\n\
          \n\
          What it detects:\n\
-         Table and column names that will require double-quoting in every\n\
-         subsequent query. This happens when a name contains uppercase\n\
+         Table, column, index, constraint, schema, and sequence names that\n\
+         will require double-quoting in every subsequent reference. This happens when a name contains uppercase\n\
          characters (was necessarily quoted in the DDL) or is a PostgreSQL\n\
          reserved word (was necessarily quoted to be used as an identifier).\n\
          \n\
@@ -42,14 +47,17 @@ pub(super) const EXPLAIN: &str = "PGM509 — Mixed-case identifiers or reserved
          \n\
          Does NOT fire when:\n\
          - The identifier is all-lowercase and not a PostgreSQL reserved word.\n\
-         - The identifier is a schema name or index name (only table and\n\
-           column names are checked).\n\
+         - The identifier is only referenced, not created or renamed (e.g. the\n\
+           table of CREATE INDEX or the target of a foreign key).\n\
          \n\
          Statements checked:\n\
-         - CREATE TABLE — table name and all column names\n\
+         - CREATE TABLE — table name, all column names, constraint names\n\
          - ALTER TABLE ... ADD COLUMN — column name\n\
-         - RENAME TABLE — new name\n\
-         - RENAME COLUMN — new name";
+         - ALTER TABLE ... ADD CONSTRAINT — constraint name\n\
+         - CREATE INDEX — index name\n\
+         - CREATE SCHEMA — schema name\n\
+         - CREATE SEQUENCE — sequence name\n\
+         - RENAME TABLE / COLUMN / INDEX / SEQUENCE — new name";
 
 pub(super) const DEFAULT_SEVERITY: Severity = Severity::Info;
 
@@ -65,14 +73,42 @@ fn needs_quoting(name: &str) -> Option<&'static str> {
     if name.chars().any(|c| c.is_ascii_uppercase()) {
         return Some("contains uppercase characters");
     }
+    if requires_quoting(name) {
+        return Some("not a valid unquoted identifier");
+    }
     None
 }
 
+/// Like [`needs_quoting`], for a name whose source spelling is known: a name
+/// written without quotes never needs them.
+fn spelled_needs_quoting(spelling: &Spelling) -> Option<&'static str> {
+    if !spelling.quoted {
+        return None;
+    }
+    needs_quoting(&spelling.text)
+}
+
 /// Build a column-level finding message.
 fn column_message(col_name: &str, table_display: &str, reason: &'static str) -> String {
     format!("Column '{col_name}' on table '{table_display}' requires double-quoting ({reason}).")
 }
 
+/// Build a constraint-level finding message, or `None` for an unnamed
+/// constraint (PostgreSQL generates a lowercase name).
+fn constraint_message(constraint: &TableConstraint, table_display: &str) -> Option<String> {
+    let name = match constraint {
+        TableConstraint::PrimaryKey { name, .. }
+        | TableConstraint::ForeignKey { name, .. }
+        | TableConstraint::Unique { name, .. }
+        | TableConstraint::Check { name, .. }
+        | TableConstraint::Exclude { name } => name.as_deref()?,
+    };
+    let reason = needs_quoting(name)?;
+    Some(format!(
+        "Constraint '{name}' on table '{table_display}' requires double-quoting ({reason})."
+    ))
+}
+
 pub(super) fn check(
     rule: impl Rule,
     statements: &[Located<IrNode>],
@@ -84,7 +120,7 @@ pub(super) fn check(
         match &stmt.node {
             IrNode::CreateTable(ct) => {
                 // Check table name
-                if let Some(reason) = needs_quoting(&ct.name.name) {
+                if let Some(reason) = spelled_needs_quoting(&ct.name.name_spelling()) {
                     findings.push(rule.make_finding(
                         format!(
                             "Table '{}' requires double-quoting ({reason}).",
@@ -96,7 +132,7 @@ pub(super) fn check(
                 }
                 // Check all column names
                 for col in &ct.columns {
-                    if let Some(reason) = needs_quoting(&col.name) {
+                    if let Some(reason) = spelled_needs_quoting(&col.name_spelling()) {
                         findings.push(rule.make_finding(
                             column_message(&col.name, &ct.name.display_name(), reason),
                             ctx.file,
@@ -104,20 +140,91 @@ pub(super) fn check(
                         ));
                     }
                 }
+                for constraint in &ct.constraints {
+                    if let Some(message) = constraint_message(constraint, &ct.name.display_name()) {
+                        findings.push(rule.make_finding(message, ctx.file, &stmt.span));
+                    }
+                }
             }
             IrNode::AlterTable(at) => {
                 for action in &at.actions {
-                    if let AlterTableAction::AddColumn(col) = action
-                        && let Some(reason) = needs_quoting(&col.name)
-                    {
-                        findings.push(rule.make_finding(
-                            column_message(&col.name, &at.name.display_name(), reason),
-                            ctx.file,
-                            &stmt.span,
-                        ));
+                    let message = match action {
+                        AlterTableAction::AddColumn(col) => {
+                            needs_quoting(&col.name).map(|reason| {
+                                column_message(&col.name, &at.name.display_name(), reason)
+                            })
+                        }
+                        AlterTableAction::AddConstraint(constraint) => {
+                            constraint_message(constraint, &at.name.display_name())
+                        }
+                        _ => None,
+                    };
+                    if let Some(message) = message {
+                        findings.push(rule.make_finding(message, ctx.file, &stmt.span));
                     }
                 }
             }
+            IrNode::CreateIndex(ci) => {
+                if let Some(index_name) = &ci.index_name
+                    && let Some(reason) = needs_quoting(index_name)
+                {
+                    findings.push(rule.make_finding(
+                        format!(
+                            "Index '{index_name}' on table '{}' requires double-quoting ({reason}).",
+                            ci.table_name.display_name(),
+                        ),
+                        ctx.file,
+                        &stmt.span,
+                    ));
+                }
+            }
+            IrNode::CreateSchema { name, .. } => {
+                if let Some(reason) = needs_quoting(name) {
+                    findings.push(rule.make_finding(
+                        format!("Schema '{name}' requires double-quoting ({reason})."),
+                        ctx.file,
+                        &stmt.span,
+                    ));
+                }
+            }
+            IrNode::CreateSequence { name, .. } => {
+                if let Some(reason) = spelled_needs_quoting(&name.name_spelling()) {
+                    findings.push(rule.make_finding(
+                        format!(
+                            "Sequence '{}' requires double-quoting ({reason}).",
+                            name.display_name(),
+                        ),
+                        ctx.file,
+                        &stmt.span,
+                    ));
+                }
+            }
+            IrNode::RenameIndex {
+                index_name,
+                new_name,
+            } => {
+                if let Some(reason) = needs_quoting(new_name) {
+                    findings.push(rule.make_finding(
+                        format!(
+                            "Index '{new_name}' (renamed from '{index_name}') requires double-quoting ({reason})."
+                        ),
+                        ctx.file,
+                        &stmt.span,
+                    ));
+                }
+            }
+            IrNode::RenameSequence { name, new_name } => {
+                if let Some(reason) = needs_quoting(new_name) {
+                    findings.push(rule.make_finding(
+                        format!(
+                            "Sequence '{new_name}' (renamed from '{}') requires double-quoting ({reason}).",
+                            name.display_name(),
+                        ),
+                        ctx.file,
+                        &stmt.span,
+                    ));
+                }
+            }
             IrNode::RenameTable { name, new_name } => {
                 if let Some(reason) = needs_quoting(new_name) {
                     findings.push(rule.make_finding(
@@ -312,6 +419,134 @@ mod tests {
         insta::assert_yaml_snapshot!(findings);
     }
 
+    // -- Index, constraint, schema, and sequence tests --
+
+    #[test]
+    fn test_create_index_mixed_case_fires() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(IrNode::CreateIndex(CreateIndex::test(
+            Some("IX_Users_Email".to_string()),
+            QualifiedName::unqualified("users"),
+        )))];
+
+        let findings = RuleId::Pgm509.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(
+            findings[0].message,
+            "Index 'IX_Users_Email' on table 'users' requires double-quoting \
+             (contains uppercase characters)."
+        );
+    }
+
+    #[test]
+    fn test_constraint_names_fire() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![
+            located(IrNode::CreateTable(
+                CreateTable::test(QualifiedName::unqualified("users"))
+                    .with_columns(vec![ColumnDef::test("id", "bigint").with_nullable(false)])
+                    .with_constraints(vec![
+                        TableConstraint::PrimaryKey {
+                            name: Some("PK_Users".to_string()),
+                            columns: vec!["id".to_string()],
+                            using_index: None,
+                        },
+                        TableConstraint::Unique {
+                            name: None,
+                            columns: vec!["id".to_string()],
+                            using_index: None,
+                        },
+                    ]),
+            )),
+            located(IrNode::AlterTable(AlterTable {
+                name: QualifiedName::unqualified("users"),
+                actions: vec![AlterTableAction::AddConstraint(TableConstraint::Exclude {
+                    name: Some("check".to_string()),
+                })],
+            })),
+        ];
+
+        let findings = RuleId::Pgm509.check(&stmts, &ctx);
+        let messages: Vec<&str> = findings.iter().map(|f| f.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Constraint 'PK_Users' on table 'users' requires double-quoting \
+                 (contains uppercase characters).",
+                "Constraint 'check' on table 'users' requires double-quoting \
+                 (PostgreSQL reserved word).",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_schema_and_sequence_names_fire() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![
+            located(IrNode::CreateSchema {
+                name: "Billing".to_string(),
+                if_not_exists: false,
+            }),
+            located(IrNode::CreateSequence {
+                name: QualifiedName::qualified("billing", "InvoiceNo"),
+                if_not_exists: false,
+                owned_by: None,
+            }),
+            located(IrNode::CreateSequence {
+                name: QualifiedName::unqualified("invoice_no"),
+                if_not_exists: false,
+                owned_by: None,
+            }),
+        ];
+
+        let findings = RuleId::Pgm509.check(&stmts, &ctx);
+        let messages: Vec<&str> = findings.iter().map(|f| f.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Schema 'Billing' requires double-quoting (contains uppercase characters).",
+                "Sequence 'billing.InvoiceNo' requires double-quoting \
+                 (contains uppercase characters).",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rename_index_and_sequence_fire() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![
+            located(IrNode::RenameIndex {
+                index_name: "idx_users_email".to_string(),
+                new_name: "IdxUsersEmail".to_string(),
+            }),
+            located(IrNode::RenameSequence {
+                name: QualifiedName::unqualified("invoice_no"),
+                new_name: "user".to_string(),
+            }),
+        ];
+
+        let findings = RuleId::Pgm509.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 2);
+        assert!(
+            findings[0]
+                .message
+                .starts_with("Index 'IdxUsersEmail' (renamed from")
+        );
+        assert!(findings[1].message.contains("PostgreSQL reserved word"));
+    }
+
     // -- needs_quoting unit tests --
 
     #[test]
@@ -341,10 +576,50 @@ mod tests {
         assert_eq!(needs_quoting("Table"), Some("PostgreSQL reserved word"));
     }
 
+    #[test]
+    fn test_unquoted_spelling_no_finding() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        // `CREATE TABLE "user-accounts" (Id bigint)`: the table was quoted,
+        // the column was written in mixed case but folded to `id`.
+        let mut name = QualifiedName::unqualified("user-accounts");
+        name.set_spelling(
+            None,
+            Spelling {
+                quoted: true,
+                text: "user-accounts".to_string(),
+            },
+        );
+        let mut col = ColumnDef::test("id", "bigint");
+        col.spelling = Some(Spelling {
+            quoted: false,
+            text: "Id".to_string(),
+        });
+        let stmts = vec![located(IrNode::CreateTable(
+            CreateTable::test(name).with_columns(vec![col]),
+        ))];
+
+        let findings = RuleId::Pgm509.check(&stmts, &ctx);
+        let messages: Vec<&str> = findings.iter().map(|f| f.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Table 'user-accounts' requires double-quoting (not a valid unquoted identifier)."
+            ]
+        );
+    }
+
     #[test]
     fn test_needs_quoting_safe() {
         assert_eq!(needs_quoting("users"), None);
         assert_eq!(needs_quoting("order_status"), None);
         assert_eq!(needs_quoting("id"), None);
+        assert_eq!(needs_quoting("amount$"), None);
+        assert_eq!(
+            needs_quoting("2fa_codes"),
+            Some("not a valid unquoted identifier")
+        );
     }
 }
@@ -0,0 +1,209 @@
+//! PGM510 — Boolean `NOT NULL` column without default on new table
+//!
+//! Detects `boolean NOT NULL` columns without a `DEFAULT` on tables created in
+//! the current set of changed files. During a rolling deploy, application
+//! versions that predate the column omit it from their INSERTs, and every such
+//! insert fails. The usual "fix" under pressure is dropping `NOT NULL`, which
+//! turns a two-state flag into a three-state one.
+//!
+//! Existing tables are covered by PGM008, which catches the harder failure
+//! (the `ADD COLUMN` itself fails on a non-empty table).
+
+use crate::parser::ir::{AlterTableAction, ColumnDef, IrNode, Located};
+use crate::rules::{Finding, LintContext, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str = "Boolean NOT NULL column without DEFAULT on new table";
+
+pub(super) const EXPLAIN: &str = "PGM510 — Boolean NOT NULL column without DEFAULT on new table\n\
+         \n\
+         What it detects:\n\
+         A boolean column declared NOT NULL without a DEFAULT, in CREATE TABLE\n\
+         or in ALTER TABLE ... ADD COLUMN on a table created in the same set of\n\
+         changed files.\n\
+         \n\
+         Why it matters:\n\
+         During a rolling deploy, application versions that predate the column\n\
+         do not include it in their INSERTs, and every such insert fails with\n\
+         a NOT NULL violation. The common emergency fix is to drop NOT NULL,\n\
+         which leaves the flag with three states (true, false, NULL) that all\n\
+         callers must handle. A boolean flag almost always has a natural\n\
+         default; declare it.\n\
+         \n\
+         Existing tables are covered by PGM008.\n\
+         \n\
+         Example (flagged):\n\
+           CREATE TABLE features (\n\
+             id bigint GENERATED ALWAYS AS IDENTITY PRIMARY KEY,\n\
+             enabled boolean NOT NULL\n\
+           );\n\
+         \n\
+         Fix:\n\
+           CREATE TABLE features (\n\
+             id bigint GENERATED ALWAYS AS IDENTITY PRIMARY KEY,\n\
+             enabled boolean NOT NULL DEFAULT false\n\
+           );";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Minor;
+
+/// Returns `true` for a `boolean NOT NULL` column without a default.
+fn is_bool_not_null_without_default(col: &ColumnDef) -> bool {
+    matches!(&*col.type_name.name, "bool" | "boolean")
+        && !col.type_name.is_array()
+        && !col.nullable
+        && col.default_expr.is_none()
+}
+
+fn message(col: &str, table: &str) -> String {
+    format!(
+        "Boolean column '{col}' on new table '{table}' is NOT NULL without a DEFAULT. \
+         Inserts from application versions that predate the column will fail. \
+         Add a DEFAULT (true or false)."
+    )
+}
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for stmt in statements {
+        match &stmt.node {
+            IrNode::CreateTable(ct) => {
+                for col in &ct.columns {
+                    if is_bool_not_null_without_default(col) {
+                        findings.push(rule.make_finding(
+                            message(&col.name, &ct.name.display_name()),
+                            ctx.file,
+                            &stmt.span,
+                        ));
+                    }
+                }
+            }
+            IrNode::AlterTable(at) => {
+                // Existing tables are PGM008's territory.
+                if !ctx.tables_created_in_change.contains(at.name.catalog_key()) {
+                    continue;
+                }
+                for action in &at.actions {
+                    if let AlterTableAction::AddColumn(col) = action
+                        && is_bool_not_null_without_default(col)
+                    {
+                        findings.push(rule.make_finding(
+                            message(&col.name, &at.name.display_name()),
+                            ctx.file,
+                            &stmt.span,
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    #[test]
+    fn test_create_table_bool_not_null_without_default_fires() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql");
+
+        let stmts = vec![located(IrNode::CreateTable(
+            CreateTable::test(QualifiedName::unqualified("features")).with_columns(vec![
+                ColumnDef::test("id", "int8").with_inline_pk(),
+                ColumnDef::test("enabled", "bool").with_nullable(false),
+            ]),
+        ))];
+
+        let findings = RuleId::Pgm510.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_bool_not_null_with_default_no_finding() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql");
+
+        let stmts = vec![located(IrNode::CreateTable(
+            CreateTable::test(QualifiedName::unqualified("features")).with_columns(vec![
+                ColumnDef::test("enabled", "bool")
+                    .with_nullable(false)
+                    .with_default(DefaultExpr::Literal("false".to_string())),
+            ]),
+        ))];
+
+        let findings = RuleId::Pgm510.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_nullable_bool_no_finding() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql");
+
+        let stmts = vec![located(IrNode::CreateTable(
+            CreateTable::test(QualifiedName::unqualified("features"))
+                .with_columns(vec![ColumnDef::test("enabled", "bool")]),
+        ))];
+
+        let findings = RuleId::Pgm510.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_add_column_on_table_created_in_change_fires() {
+        let before = Catalog::new();
+        let after = CatalogBuilder::new()
+            .table("features", |t| {
+                t.column("id", "int8", false);
+            })
+            .build();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql", created: ["features"]);
+
+        let stmts = vec![located(IrNode::AlterTable(AlterTable {
+            name: QualifiedName::unqualified("features"),
+            actions: vec![AlterTableAction::AddColumn(
+                ColumnDef::test("enabled", "bool").with_nullable(false),
+            )],
+        }))];
+
+        let findings = RuleId::Pgm510.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+    }
+
+    #[test]
+    fn test_add_column_on_existing_table_no_finding() {
+        // Covered by PGM008 instead.
+        let before = CatalogBuilder::new()
+            .table("features", |t| {
+                t.column("id", "int8", false);
+            })
+            .build();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(IrNode::AlterTable(AlterTable {
+            name: QualifiedName::unqualified("features"),
+            actions: vec![AlterTableAction::AddColumn(
+                ColumnDef::test("enabled", "bool").with_nullable(false),
+            )],
+        }))];
+
+        let findings = RuleId::Pgm510.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+}
@@ -0,0 +1,388 @@
+//! PGM511 — DDL on a table in a schema owned by another team
+//!
+//! Enforces the schema ownership boundaries declared under `[schemas]` in the
+//! config. A schema listed in `[schemas.ownership]` may only be changed by
+//! migrations that live under a path mapped (via `[schemas.teams]`) to one of
+//! its owning teams. Schemas without an ownership entry are never flagged, so
+//! the rule is silent until ownership is configured.
+//!
+//! Only DDL is checked. DML (`INSERT`, `UPDATE`, `DELETE`) against another
+//! team's tables is a data concern, not a schema boundary violation.
+
+use crate::parser::ir::{IrNode, Located, QualifiedName, ReindexObjectKind, ReindexTarget};
+use crate::rules::{Finding, LintContext, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str = "DDL on a table in a schema owned by another team";
+
+pub(super) const EXPLAIN: &str = "PGM511 — DDL on a table in a schema owned by another team\n\
+         \n\
+         What it detects:\n\
+         CREATE/ALTER/DROP/TRUNCATE TABLE, CREATE/DROP INDEX, ALTER INDEX\n\
+         SET TABLESPACE, REINDEX, RENAME, CREATE/DROP TRIGGER, COMMENT ON a\n\
+         table or its columns, CLUSTER, VACUUM FULL, or DROP SCHEMA targeting\n\
+         a schema listed in [schemas.ownership], in a migration file that does\n\
+         not belong to one of the schema's owning teams. Statements that name\n\
+         only an index use the schema of the index's table. A file belongs to\n\
+         a team when it lives under one of the team's paths in [schemas.teams].\n\
+         \n\
+         Why it matters:\n\
+         In a shared database, a schema is usually the contract between a\n\
+         service and the rest of the organization. Indexes, constraints, and\n\
+         column changes added by another team bypass the owner's review, can\n\
+         take locks the owner has not planned for, and leave objects the\n\
+         owner does not know to maintain.\n\
+         \n\
+         DML (INSERT, UPDATE, DELETE) is not checked.\n\
+         \n\
+         Example (flagged, in db/migrations/orders/V12__index.sql):\n\
+           CREATE INDEX CONCURRENTLY idx_charges_order\n\
+             ON payments.charges (order_id);\n\
+         \n\
+         with:\n\
+           [schemas.ownership]\n\
+           payments = [\"payments_team\"]\n\
+         \n\
+           [schemas.teams]\n\
+           payments_team = [\"db/migrations/payments\"]\n\
+           orders_team = [\"db/migrations/orders\"]\n\
+         \n\
+         Fix:\n\
+         Ask the owning team to make the change in their migrations, or move\n\
+         the object into a schema your team owns.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Major;
+
+/// The operation name, target schema, and display name of a DDL statement,
+/// if it targets a schema.
+///
+/// Index names carry no schema, so statements naming only an index take the
+/// schema of the index's table in the catalog. Indexes the catalog does not
+/// know are skipped.
+fn ddl_target<'a>(
+    node: &'a IrNode,
+    ctx: &'a LintContext<'_>,
+) -> Option<(&'a str, &'a str, String)> {
+    fn table<'n>(op: &'n str, name: &'n QualifiedName) -> Option<(&'n str, &'n str, String)> {
+        name.schema
+            .as_deref()
+            .map(|schema| (op, schema, name.display_name()))
+    }
+    fn index<'c>(
+        op: &'static str,
+        index_name: &str,
+        ctx: &LintContext<'c>,
+    ) -> Option<(&'static str, &'c str, String)> {
+        let table_key = ctx
+            .catalog_before
+            .table_for_index(index_name)
+            .or_else(|| ctx.catalog_after.table_for_index(index_name))?;
+        let (schema, _) = table_key.split_once('.')?;
+        Some((op, schema, index_name.to_string()))
+    }
+    match node {
+        IrNode::CreateTable(ct) => table("CREATE TABLE", &ct.name),
+        IrNode::AlterTable(at) => table("ALTER TABLE", &at.name),
+        IrNode::CreateIndex(ci) => table("CREATE INDEX", &ci.table_name),
+        IrNode::DropIndex(di) => index("DROP INDEX", &di.index_name, ctx),
+        IrNode::RenameIndex { index_name, .. } => index("RENAME INDEX", index_name, ctx),
+        IrNode::AlterIndexSetTablespace { index_name, .. } => {
+            index("ALTER INDEX SET TABLESPACE", index_name, ctx)
+        }
+        IrNode::Reindex(r) => match (&r.kind, &r.target) {
+            (ReindexObjectKind::Table, ReindexTarget::Relation(name)) => table("REINDEX", name),
+            (ReindexObjectKind::Index, ReindexTarget::Relation(name)) => {
+                index("REINDEX", &name.name, ctx)
+            }
+            _ => None,
+        },
+        IrNode::DropTable(dt) => table("DROP TABLE", &dt.name),
+        IrNode::TruncateTable(tt) => table("TRUNCATE", &tt.name),
+        IrNode::RenameTable { name, .. } => table("RENAME TABLE", name),
+        IrNode::RenameColumn { table: name, .. } => table("RENAME COLUMN", name),
+        IrNode::TableAttachment {
+            statement,
+            table: name,
+        } => table(statement, name),
+        IrNode::Cluster(c) => table("CLUSTER", &c.table),
+        IrNode::VacuumFull(v) => v.table.as_ref().and_then(|t| table("VACUUM FULL", t)),
+        IrNode::DropSchema(ds) => Some(("DROP SCHEMA", &*ds.schema_name, ds.schema_name.clone())),
+        _ => None,
+    }
+}
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    let schemas = &ctx.config.schemas;
+    if schemas.ownership.is_empty() {
+        return vec![];
+    }
+    let file_teams = schemas.teams_for(ctx.file);
+
+    let mut findings = Vec::new();
+    for stmt in statements {
+        let Some((op, schema, target)) = ddl_target(&stmt.node, ctx) else {
+            continue;
+        };
+        let Some(owners) = schemas.owners(schema) else {
+            continue;
+        };
+        if owners.iter().any(|o| file_teams.contains(&o.as_str())) {
+            continue;
+        }
+
+        let author = if file_teams.is_empty() {
+            "a file not mapped to any team in [schemas.teams]".to_string()
+        } else {
+            format!("a file owned by {}", file_teams.join(", "))
+        };
+        findings.push(rule.make_finding(
+            format!(
+                "{op} on '{target}' changes schema '{schema}', owned by {owners}, \
+                 from {author}. Have an owning team make this change in their \
+                 migrations.",
+                owners = owners.join(", "),
+            ),
+            ctx.file,
+            &stmt.span,
+        ));
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::config::Config;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+    use rstest::rstest;
+    use std::path::PathBuf;
+
+    fn ownership_config() -> Config {
+        let mut config = Config::default();
+        config
+            .schemas
+            .ownership
+            .insert("payments".to_string(), vec!["payments_team".to_string()]);
+        config.schemas.teams.insert(
+            "payments_team".to_string(),
+            vec![PathBuf::from("db/migrations/payments")],
+        );
+        config.schemas.teams.insert(
+            "orders_team".to_string(),
+            vec![PathBuf::from("db/migrations/orders")],
+        );
+        config
+    }
+
+    fn index_on_charges() -> IrNode {
+        IrNode::CreateIndex(
+            CreateIndex::test(
+                Some("idx_charges_order".to_string()),
+                QualifiedName::qualified("payments", "charges"),
+            )
+            .with_columns(vec![IndexColumn::Column("order_id".to_string())])
+            .with_concurrent(true),
+        )
+    }
+
+    #[test]
+    fn test_other_teams_schema_fires() {
+        let config = ownership_config();
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "db/migrations/orders/V12__index.sql", config: &config);
+
+        let stmts = vec![located(index_on_charges())];
+
+        let findings = RuleId::Pgm511.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_owning_team_no_finding() {
+        let config = ownership_config();
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "db/migrations/payments/V3__index.sql", config: &config);
+
+        let stmts = vec![located(index_on_charges())];
+
+        let findings = RuleId::Pgm511.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_unmapped_file_fires() {
+        let config = ownership_config();
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "db/migrations/V1__init.sql", config: &config);
+
+        let stmts = vec![located(IrNode::AlterTable(AlterTable {
+            name: QualifiedName::qualified("payments", "charges"),
+            actions: vec![AlterTableAction::AddColumn(ColumnDef::test("note", "text"))],
+        }))];
+
+        let findings = RuleId::Pgm511.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("not mapped to any team"));
+    }
+
+    fn charges_catalog() -> Catalog {
+        CatalogBuilder::new()
+            .table("payments.charges", |t| {
+                t.column("order_id", "bigint", false).index(
+                    "idx_charges_order",
+                    &["order_id"],
+                    false,
+                );
+            })
+            .build()
+    }
+
+    #[rstest]
+    #[case::drop_index(IrNode::DropIndex(DropIndex::test("idx_charges_order")), "DROP INDEX")]
+    #[case::rename_index(
+        IrNode::RenameIndex {
+            index_name: "idx_charges_order".to_string(),
+            new_name: "idx_charges_by_order".to_string(),
+        },
+        "RENAME INDEX"
+    )]
+    #[case::set_tablespace(
+        IrNode::AlterIndexSetTablespace {
+            index_name: "idx_charges_order".to_string(),
+            tablespace: "fast_ssd".to_string(),
+        },
+        "ALTER INDEX SET TABLESPACE"
+    )]
+    #[case::reindex_index(
+        Reindex::test_index(QualifiedName::unqualified("idx_charges_order")).into(),
+        "REINDEX"
+    )]
+    fn test_index_resolved_through_catalog(#[case] node: IrNode, #[case] op: &str) {
+        let config = ownership_config();
+        let before = charges_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "db/migrations/orders/V13__index.sql", config: &config);
+
+        let findings = RuleId::Pgm511.check(&[located(node)], &ctx);
+        assert_eq!(findings.len(), 1, "{op}");
+        assert!(
+            findings[0].message.starts_with(&format!(
+                "{op} on 'idx_charges_order' changes schema 'payments'"
+            )),
+            "{}",
+            findings[0].message
+        );
+    }
+
+    #[test]
+    fn test_unknown_index_no_finding() {
+        let config = ownership_config();
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "db/migrations/orders/V13__index.sql", config: &config);
+
+        let stmts = vec![located(IrNode::DropIndex(DropIndex::test(
+            "idx_charges_order",
+        )))];
+
+        let findings = RuleId::Pgm511.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_index_created_in_same_migration_fires() {
+        let config = ownership_config();
+        let before = Catalog::new();
+        let after = charges_catalog();
+        lint_ctx!(ctx, &before, &after, "db/migrations/orders/V13__index.sql", config: &config);
+
+        let stmts = vec![located(IrNode::RenameIndex {
+            index_name: "idx_charges_order".to_string(),
+            new_name: "idx_charges_by_order".to_string(),
+        })];
+
+        let findings = RuleId::Pgm511.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+    }
+
+    #[rstest]
+    #[case::comment("COMMENT ON")]
+    #[case::create_trigger("CREATE TRIGGER")]
+    #[case::drop_trigger("DROP TRIGGER")]
+    fn test_table_attachment_fires(#[case] statement: &str) {
+        let config = ownership_config();
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "db/migrations/orders/V13__audit.sql", config: &config);
+
+        let stmts = vec![located(IrNode::TableAttachment {
+            statement: statement.to_string(),
+            table: QualifiedName::qualified("payments", "charges"),
+        })];
+
+        let findings = RuleId::Pgm511.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert!(
+            findings[0]
+                .message
+                .starts_with(&format!("{statement} on 'payments.charges'")),
+            "{}",
+            findings[0].message
+        );
+    }
+
+    #[test]
+    fn test_unowned_schema_no_finding() {
+        let config = ownership_config();
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "db/migrations/orders/V12__index.sql", config: &config);
+
+        let stmts = vec![located(IrNode::DropTable(DropTable {
+            name: QualifiedName::qualified("public", "legacy"),
+            if_exists: false,
+            cascade: false,
+        }))];
+
+        let findings = RuleId::Pgm511.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_dml_no_finding() {
+        let config = ownership_config();
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "db/migrations/orders/V12__fix.sql", config: &config);
+
+        let stmts = vec![located(
+            UpdateTable::test(QualifiedName::qualified("payments", "charges")).into(),
+        )];
+
+        let findings = RuleId::Pgm511.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_no_ownership_configured_no_finding() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "db/migrations/orders/V12__index.sql");
+
+        let stmts = vec![located(index_on_charges())];
+
+        let findings = RuleId::Pgm511.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+}
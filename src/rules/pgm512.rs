@@ -0,0 +1,177 @@
+//! PGM512 — RENAME INDEX on existing index
+//!
+//! Renaming an index breaks every script, hint, and monitoring query that
+//! refers to it by name. The rename itself is metadata-only.
+//!
+//! **Replacement detection**: if the same migration unit creates a new index
+//! with the old name (rename away + create replacement pattern), the finding
+//! is suppressed.
+
+use std::collections::HashSet;
+
+use crate::parser::ir::{IrNode, Located};
+use crate::rules::{Finding, LintContext, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str = "RENAME INDEX on existing index";
+
+pub(super) const EXPLAIN: &str = "PGM512 — RENAME INDEX on existing index\n\
+         \n\
+         What it detects:\n\
+         ALTER INDEX ... RENAME TO ... on an index of a table that already\n\
+         exists in the database (i.e., the table was not created in the same\n\
+         set of changed files).\n\
+         \n\
+         Why it matters:\n\
+         Queries do not name indexes, but operations do: DROP INDEX, REINDEX,\n\
+         CLUSTER ... USING, ADD CONSTRAINT ... USING INDEX, ALTER INDEX ...\n\
+         ATTACH PARTITION, planner hints, and monitoring dashboards all refer\n\
+         to the index by name and break silently after a rename.\n\
+         \n\
+         Example (flagged):\n\
+           ALTER INDEX idx_orders_customer RENAME TO idx_orders_customer_id;\n\
+         \n\
+         Example (safe — replacement pattern):\n\
+           ALTER INDEX idx_orders_customer RENAME TO idx_orders_customer_old;\n\
+           CREATE INDEX CONCURRENTLY idx_orders_customer\n\
+             ON orders (customer_id, created_at);\n\
+         \n\
+         Fix:\n\
+         Update every script and dashboard that names the index in the same\n\
+         change, or keep the old name.\n\
+         \n\
+         This rule does NOT fire when a replacement index with the old name\n\
+         is created in the same migration unit.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Info;
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    // Pass 1: collect all CREATE INDEX names in this unit.
+    let created_in_unit: HashSet<&str> = statements
+        .iter()
+        .filter_map(|stmt| match &stmt.node {
+            IrNode::CreateIndex(ci) => ci.index_name.as_deref(),
+            _ => None,
+        })
+        .collect();
+
+    // Pass 2: find RenameIndex on indexes of existing tables.
+    let mut findings = Vec::new();
+
+    for stmt in statements {
+        let IrNode::RenameIndex {
+            ref index_name,
+            ref new_name,
+        } = stmt.node
+        else {
+            continue;
+        };
+
+        let Some(table_key) = ctx.catalog_before.table_for_index(index_name) else {
+            continue;
+        };
+        if !ctx.is_existing_table(table_key) {
+            continue;
+        }
+
+        if created_in_unit.contains(index_name.as_str()) {
+            continue;
+        }
+
+        findings.push(rule.make_finding(
+            format!(
+                "Renaming existing index '{index_name}' to '{new_name}' breaks \
+                 scripts, hints, and monitoring that refer to the old name \
+                 (DROP INDEX, REINDEX, CLUSTER ... USING, ADD CONSTRAINT ... \
+                 USING INDEX)."
+            ),
+            ctx.file,
+            &stmt.span,
+        ));
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    fn orders_catalog() -> Catalog {
+        CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "integer", false)
+                    .column("customer_id", "integer", true)
+                    .pk(&["id"])
+                    .index("idx_orders_customer", &["customer_id"], false);
+            })
+            .build()
+    }
+
+    fn rename(old: &str, new: &str) -> IrNode {
+        IrNode::RenameIndex {
+            index_name: old.to_string(),
+            new_name: new.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_rename_existing_index_fires() {
+        let before = orders_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(rename(
+            "idx_orders_customer",
+            "idx_orders_customer_id",
+        ))];
+
+        let findings = RuleId::Pgm512.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_rename_index_on_new_table_no_finding() {
+        let before = Catalog::new();
+        let after = orders_catalog();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql", created: ["orders"]);
+
+        let stmts = vec![located(rename(
+            "idx_orders_customer",
+            "idx_orders_customer_id",
+        ))];
+
+        let findings = RuleId::Pgm512.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_rename_with_replacement_index_no_finding() {
+        let before = orders_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![
+            located(rename("idx_orders_customer", "idx_orders_customer_old")),
+            located(IrNode::CreateIndex(
+                CreateIndex::test(
+                    Some("idx_orders_customer".to_string()),
+                    QualifiedName::unqualified("orders"),
+                )
+                .with_columns(vec![IndexColumn::Column("customer_id".to_string())])
+                .with_concurrent(true),
+            )),
+        ];
+
+        let findings = RuleId::Pgm512.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+}
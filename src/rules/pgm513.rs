@@ -0,0 +1,171 @@
+//! PGM513 — Autovacuum disabled on existing table
+//!
+//! Detects `ALTER TABLE ... SET (autovacuum_enabled = false)` (or the
+//! `toast.` variant) on a table that already exists. Turning autovacuum off
+//! is a common bulk-load trick; left in place, the table bloats and its
+//! statistics go stale.
+
+use crate::parser::ir::{AlterTableAction, IrNode, Located, StorageParam};
+use crate::rules::{Finding, LintContext, Rule, Severity, TableScope, alter_table_check};
+
+pub(super) const DESCRIPTION: &str = "Autovacuum disabled on existing table";
+
+pub(super) const EXPLAIN: &str = "PGM513 — Autovacuum disabled on existing table\n\
+         \n\
+         What it detects:\n\
+         ALTER TABLE ... SET (autovacuum_enabled = false) or\n\
+         SET (toast.autovacuum_enabled = false) on a table that already\n\
+         exists.\n\
+         \n\
+         Why it matters:\n\
+         Disabling autovacuum is a bulk-load trick that is almost always\n\
+         meant to be temporary. Left behind, dead tuples are never reclaimed,\n\
+         the table and its indexes bloat, planner statistics go stale, and\n\
+         only emergency anti-wraparound vacuums still run — at the worst\n\
+         possible time.\n\
+         \n\
+         Example (bad):\n\
+           ALTER TABLE events SET (autovacuum_enabled = false);\n\
+           INSERT INTO events SELECT * FROM staging_events;\n\
+         \n\
+         Fix:\n\
+         Re-enable it in the same migration once the load is done:\n\
+           ALTER TABLE events RESET (autovacuum_enabled);\n\
+           ANALYZE events;";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Major;
+
+/// Values PostgreSQL's boolean reloption parser accepts as false.
+const FALSE_VALUES: &[&str] = &["false", "off", "no", "0", "f", "n"];
+
+/// Returns `true` if `param` turns autovacuum off for the table or its TOAST table.
+fn disables_autovacuum(param: &StorageParam) -> bool {
+    matches!(
+        param.name.as_str(),
+        "autovacuum_enabled" | "toast.autovacuum_enabled"
+    ) && param
+        .value
+        .as_deref()
+        .is_some_and(|v| FALSE_VALUES.iter().any(|f| v.eq_ignore_ascii_case(f)))
+}
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    alter_table_check::check_alter_actions(
+        statements,
+        ctx,
+        TableScope::ExcludeCreatedInChange,
+        |at, action, stmt, ctx| {
+            let AlterTableAction::SetStorageParams { params } = action else {
+                return vec![];
+            };
+            params
+                .iter()
+                .filter(|p| disables_autovacuum(p))
+                .map(|p| {
+                    rule.make_finding(
+                        format!(
+                            "Setting {} = {} on existing table '{}' disables autovacuum: \
+                             dead tuples accumulate and statistics go stale. Reset it \
+                             once any bulk load is done.",
+                            p.name,
+                            p.value.as_deref().unwrap_or_default(),
+                            at.name.display_name(),
+                        ),
+                        ctx.file,
+                        &stmt.span,
+                    )
+                })
+                .collect()
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    fn events_catalog() -> Catalog {
+        CatalogBuilder::new()
+            .table("events", |t| {
+                t.column("id", "bigint", false).pk(&["id"]);
+            })
+            .build()
+    }
+
+    fn set_params(params: Vec<StorageParam>) -> IrNode {
+        IrNode::AlterTable(AlterTable {
+            name: QualifiedName::unqualified("events"),
+            actions: vec![AlterTableAction::SetStorageParams { params }],
+        })
+    }
+
+    #[test]
+    fn test_disable_autovacuum_fires() {
+        let before = events_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(set_params(vec![StorageParam::test(
+            "autovacuum_enabled",
+            "false",
+        )]))];
+
+        let findings = RuleId::Pgm513.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_disable_toast_autovacuum_off_fires() {
+        let before = events_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(set_params(vec![
+            StorageParam::test("fillfactor", "70"),
+            StorageParam::test("toast.autovacuum_enabled", "OFF"),
+        ]))];
+
+        let findings = RuleId::Pgm513.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("toast.autovacuum_enabled"));
+    }
+
+    #[test]
+    fn test_enable_autovacuum_no_finding() {
+        let before = events_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(set_params(vec![StorageParam::test(
+            "autovacuum_enabled",
+            "true",
+        )]))];
+
+        let findings = RuleId::Pgm513.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_table_created_in_change_no_finding() {
+        let before = Catalog::new();
+        let after = events_catalog();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql", created: ["events"]);
+
+        let stmts = vec![located(set_params(vec![StorageParam::test(
+            "autovacuum_enabled",
+            "false",
+        )]))];
+
+        let findings = RuleId::Pgm513.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+}
@@ -0,0 +1,215 @@
+//! PGM514 — Permission or ownership change in a schema migration
+//!
+//! Detects `GRANT`, `REVOKE`, `ALTER DEFAULT PRIVILEGES`, and
+//! `ALTER ... OWNER TO` bundled into a migration. Teams that manage access
+//! control through a separate pipeline want those statements kept out of
+//! schema migrations, where they bypass the access review.
+//!
+//! This is a policy rule, not a migration hazard, so it is opt-in: it only
+//! runs when listed in `[rules] enabled`.
+
+use crate::parser::ir::{AlterTableAction, Grant, IrNode, Located};
+use crate::rules::{Finding, LintContext, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str = "Permission or ownership change in a schema migration";
+
+pub(super) const EXPLAIN: &str = "PGM514 — Permission or ownership change in a schema migration\n\
+         \n\
+         What it detects:\n\
+         GRANT and REVOKE (of privileges or role membership), ALTER DEFAULT\n\
+         PRIVILEGES, and ALTER TABLE/SCHEMA/FUNCTION/... OWNER TO in a\n\
+         migration file.\n\
+         \n\
+         Why it matters:\n\
+         Many organizations manage database access through a dedicated\n\
+         access-control pipeline with its own review and audit trail.\n\
+         Permission changes bundled into schema migrations bypass that\n\
+         review, drift from the declared access model, and are easy to miss\n\
+         among DDL changes.\n\
+         \n\
+         This rule is off by default. Enable it with:\n\
+           [rules]\n\
+           enabled = [\"PGM514\"]\n\
+         \n\
+         Example (flagged):\n\
+           CREATE TABLE invoices (id bigint PRIMARY KEY);\n\
+           GRANT SELECT ON invoices TO reporting;\n\
+         \n\
+         Fix:\n\
+         Move the GRANT/REVOKE/OWNER TO statements to the access-control\n\
+         pipeline and keep the migration to schema changes.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Info;
+
+/// Describe a GRANT/REVOKE for the finding message.
+fn describe_grant(g: &Grant) -> String {
+    let (verb, preposition) = if g.is_grant {
+        ("GRANT", "to")
+    } else {
+        ("REVOKE", "from")
+    };
+    let grantees = g.grantees.join(", ");
+    match g.object_kind.as_str() {
+        "ROLE" => format!(
+            "{verb} of role {} {preposition} {grantees}",
+            g.objects.join(", ")
+        ),
+        "DEFAULT PRIVILEGES" => {
+            format!("ALTER DEFAULT PRIVILEGES ({verb} {preposition} {grantees})")
+        }
+        kind => format!(
+            "{verb} on {kind} {} {preposition} {grantees}",
+            g.objects.join(", ")
+        ),
+    }
+}
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for stmt in statements {
+        let changes: Vec<String> = match &stmt.node {
+            IrNode::Grant(g) => vec![describe_grant(g)],
+            IrNode::AlterOwner(ao) => vec![format!(
+                "Ownership of {} {} changed to {}",
+                ao.object_kind, ao.name, ao.new_owner
+            )],
+            IrNode::AlterTable(at) => at
+                .actions
+                .iter()
+                .filter_map(|a| match a {
+                    AlterTableAction::ChangeOwner { new_owner } => Some(format!(
+                        "Ownership of TABLE {} changed to {new_owner}",
+                        at.name.display_name()
+                    )),
+                    _ => None,
+                })
+                .collect(),
+            _ => continue,
+        };
+
+        for change in changes {
+            findings.push(rule.make_finding(
+                format!(
+                    "{change} in a schema migration. Permission changes belong in the \
+                     access-control pipeline; move this statement out of the migration."
+                ),
+                ctx.file,
+                &stmt.span,
+            ));
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    #[test]
+    fn test_grant_fires() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(
+            Grant::test(&["invoices", "billing.charges"], &["reporting"]).into(),
+        )];
+
+        let findings = RuleId::Pgm514.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_revoke_and_role_membership_fire() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![
+            located(
+                Grant::test(&["invoices"], &["PUBLIC"])
+                    .with_is_grant(false)
+                    .into(),
+            ),
+            located(
+                Grant::test(&["app_rw"], &["alice"])
+                    .with_object_kind("ROLE")
+                    .into(),
+            ),
+        ];
+
+        let findings = RuleId::Pgm514.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 2);
+        assert!(
+            findings[0]
+                .message
+                .starts_with("REVOKE on TABLE invoices from PUBLIC")
+        );
+        assert!(
+            findings[1]
+                .message
+                .starts_with("GRANT of role app_rw to alice")
+        );
+    }
+
+    #[test]
+    fn test_owner_changes_fire() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![
+            located(IrNode::AlterTable(AlterTable {
+                name: QualifiedName::unqualified("invoices"),
+                actions: vec![AlterTableAction::ChangeOwner {
+                    new_owner: "billing_owner".to_string(),
+                }],
+            })),
+            located(
+                AlterOwner {
+                    object_kind: "SCHEMA".to_string(),
+                    name: "billing".to_string(),
+                    new_owner: "billing_owner".to_string(),
+                }
+                .into(),
+            ),
+        ];
+
+        let findings = RuleId::Pgm514.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 2);
+        assert!(
+            findings[0]
+                .message
+                .starts_with("Ownership of TABLE invoices changed to billing_owner")
+        );
+        assert!(
+            findings[1]
+                .message
+                .starts_with("Ownership of SCHEMA billing changed to billing_owner")
+        );
+    }
+
+    #[test]
+    fn test_plain_ddl_no_finding() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql");
+
+        let stmts = vec![located(IrNode::CreateTable(CreateTable::test(
+            QualifiedName::unqualified("invoices"),
+        )))];
+
+        let findings = RuleId::Pgm514.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+}
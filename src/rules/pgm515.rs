@@ -0,0 +1,198 @@
+//! PGM515 — Table rebuilt and renamed in one migration
+//!
+//! Detects a migration unit that creates a table (or adds a column to one),
+//! keeps changing it, and then renames it — the hand-rolled
+//! "copy into a new table, rename over the old one" rewrite. Reports once
+//! per unit, on the first such rename, and points at the view-swap pattern.
+
+use std::collections::HashSet;
+
+use crate::parser::ir::{AlterTableAction, IrNode, Located};
+use crate::rules::{Finding, LintContext, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str = "Table rebuilt and renamed in one migration";
+
+pub(super) const EXPLAIN: &str = "PGM515 — Table rebuilt and renamed in one migration\n\
+         \n\
+         What it detects:\n\
+         A migration that creates a table and then modifies it (INSERT,\n\
+         UPDATE, ALTER TABLE), or adds a column to a table, and then renames\n\
+         that table — typically to take over the name of the table it\n\
+         replaces.\n\
+         \n\
+         Why it matters:\n\
+         This is a table rewrite done by hand. The copy runs inside the\n\
+         deploy, the rename swaps the table under running queries in one\n\
+         step, and there is no way back short of another copy. Writes made to\n\
+         the old table during the copy are lost.\n\
+         \n\
+         Example (flagged):\n\
+           CREATE TABLE orders_new (LIKE orders INCLUDING ALL);\n\
+           ALTER TABLE orders_new ADD COLUMN region text;\n\
+           INSERT INTO orders_new SELECT *, 'eu' FROM orders;\n\
+           ALTER TABLE orders RENAME TO orders_old;\n\
+           ALTER TABLE orders_new RENAME TO orders;\n\
+         \n\
+         Fix (view swap):\n\
+         Create and backfill the new table in its own migrations, then\n\
+         switch readers over through a view:\n\
+           ALTER TABLE orders RENAME TO orders_v1;\n\
+           CREATE VIEW orders AS SELECT * FROM orders_v2;\n\
+         and drop the old table once nothing writes to it.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Info;
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    let mut created: HashSet<&str> = HashSet::new();
+    let mut churned: HashSet<&str> = HashSet::new();
+
+    for stmt in statements {
+        match &stmt.node {
+            IrNode::CreateTable(ct) => {
+                created.insert(ct.name.catalog_key());
+            }
+            IrNode::AlterTable(at) => {
+                let key = at.name.catalog_key();
+                let adds_column = at
+                    .actions
+                    .iter()
+                    .any(|a| matches!(a, AlterTableAction::AddColumn(_)));
+                if created.contains(key) || adds_column {
+                    churned.insert(key);
+                }
+            }
+            IrNode::InsertInto(ins) if created.contains(ins.table_name.catalog_key()) => {
+                churned.insert(ins.table_name.catalog_key());
+            }
+            IrNode::UpdateTable(upd) if created.contains(upd.table_name.catalog_key()) => {
+                churned.insert(upd.table_name.catalog_key());
+            }
+            IrNode::RenameTable { name, new_name } if churned.contains(name.catalog_key()) => {
+                return vec![
+                    rule.make_finding(
+                        format!(
+                            "Table '{}' is built up in this migration and then renamed to \
+                             '{}'. Rewriting a table by copy and renaming it in one \
+                             migration swaps it under live traffic with no way back; \
+                             backfill in separate migrations and switch over with a view.",
+                            name.display_name(),
+                            new_name,
+                        ),
+                        ctx.file,
+                        &stmt.span,
+                    )
+                    .with_table(name),
+                ];
+            }
+            _ => {}
+        }
+    }
+
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    fn rename(from: &str, to: &str) -> Located<IrNode> {
+        located(IrNode::RenameTable {
+            name: QualifiedName::unqualified(from),
+            new_name: to.to_string(),
+        })
+    }
+
+    fn add_column(table: &str, column: &str) -> Located<IrNode> {
+        located(IrNode::AlterTable(AlterTable {
+            name: QualifiedName::unqualified(table),
+            actions: vec![AlterTableAction::AddColumn(ColumnDef::test(column, "text"))],
+        }))
+    }
+
+    fn orders_catalog() -> Catalog {
+        CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "bigint", false).pk(&["id"]);
+            })
+            .build()
+    }
+
+    #[test]
+    fn test_copy_and_swap_fires_once() {
+        let before = orders_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![
+            located(IrNode::CreateTable(CreateTable::test(
+                QualifiedName::unqualified("orders_new"),
+            ))),
+            located(IrNode::InsertInto(InsertInto {
+                table_name: QualifiedName::unqualified("orders_new"),
+            })),
+            rename("orders", "orders_old"),
+            rename("orders_new", "orders"),
+        ];
+
+        let findings = RuleId::Pgm515.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_add_column_then_rename_fires() {
+        let before = orders_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![
+            add_column("orders", "region"),
+            rename("orders", "orders_v2"),
+        ];
+
+        let findings = RuleId::Pgm515.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert!(
+            findings[0]
+                .message
+                .starts_with("Table 'orders' is built up")
+        );
+    }
+
+    #[test]
+    fn test_create_then_rename_without_changes_no_finding() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/001.sql");
+
+        let stmts = vec![
+            located(IrNode::CreateTable(CreateTable::test(
+                QualifiedName::unqualified("staging"),
+            ))),
+            rename("staging", "events"),
+        ];
+
+        let findings = RuleId::Pgm515.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_plain_rename_no_finding() {
+        let before = orders_catalog();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![rename("orders", "orders_archive")];
+
+        let findings = RuleId::Pgm515.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+}
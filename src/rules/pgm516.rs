@@ -0,0 +1,115 @@
+//! PGM516 — Table inheritance (`INHERITS`)
+//!
+//! Detects `CREATE TABLE ... INHERITS (parent)`. Legacy table inheritance
+//! predates declarative partitioning and gets none of its guarantees: the
+//! parent's primary key, unique constraints, foreign keys, and indexes do
+//! not apply to the children. New uses should be declarative partitions.
+
+use crate::parser::ir::{IrNode, Located};
+use crate::rules::{Finding, LintContext, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str =
+    "Table inheritance (INHERITS) instead of declarative partitioning";
+
+pub(super) const EXPLAIN: &str = "PGM516 — Table inheritance (INHERITS) instead of declarative partitioning\n\
+         \n\
+         What it detects:\n\
+         CREATE TABLE ... INHERITS (parent, ...).\n\
+         \n\
+         Why it matters:\n\
+         Table inheritance is the pre-PostgreSQL 10 way to split a table.\n\
+         The parent's primary key, unique constraints, foreign keys, and\n\
+         indexes do not apply to the children, so uniqueness is not enforced\n\
+         across the hierarchy and foreign keys cannot point at it. Rows are\n\
+         routed to children only by hand-written triggers, and the planner's\n\
+         partition pruning does not apply. Declarative partitioning handles\n\
+         all of this.\n\
+         \n\
+         Example (flagged):\n\
+           CREATE TABLE measurements_2024 (\n\
+             CHECK (ts >= '2024-01-01' AND ts < '2025-01-01')\n\
+           ) INHERITS (measurements);\n\
+         \n\
+         Fix:\n\
+           CREATE TABLE measurements (id bigint, ts timestamptz NOT NULL)\n\
+             PARTITION BY RANGE (ts);\n\
+           CREATE TABLE measurements_2024 PARTITION OF measurements\n\
+             FOR VALUES FROM ('2024-01-01') TO ('2025-01-01');\n\
+         \n\
+         Existing inheritance hierarchies are replayed into the catalog and\n\
+         only new CREATE TABLE ... INHERITS statements are reported.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Info;
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for stmt in statements {
+        let IrNode::CreateTable(ct) = &stmt.node else {
+            continue;
+        };
+        if ct.inherits.is_empty() {
+            continue;
+        }
+        let parents: Vec<String> = ct.inherits.iter().map(|p| p.display_name()).collect();
+        findings.push(
+            rule.make_finding(
+                format!(
+                    "Table '{}' uses INHERITS ('{}'). Primary keys, unique constraints, \
+                     foreign keys, and indexes are not inherited; use declarative \
+                     partitioning (PARTITION BY / PARTITION OF) instead.",
+                    ct.name.display_name(),
+                    parents.join("', '"),
+                ),
+                ctx.file,
+                &stmt.span,
+            )
+            .with_table(&ct.name),
+        );
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    #[test]
+    fn test_inherits_fires() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(IrNode::CreateTable(
+            CreateTable::test(QualifiedName::unqualified("measurements_2024"))
+                .with_inherits(vec![QualifiedName::unqualified("measurements")]),
+        ))];
+
+        let findings = RuleId::Pgm516.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_partition_of_no_finding() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql");
+
+        let stmts = vec![located(IrNode::CreateTable(
+            CreateTable::test(QualifiedName::unqualified("measurements_2024"))
+                .with_partition_of(QualifiedName::unqualified("measurements")),
+        ))];
+
+        let findings = RuleId::Pgm516.check(&stmts, &ctx);
+        assert!(findings.is_empty());
+    }
+}
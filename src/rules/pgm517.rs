@@ -0,0 +1,254 @@
+//! PGM517 — Operation does not follow a required migration pattern
+//!
+//! Checks every operation against the `[[patterns]]` entries in the config:
+//! options the operation must use (`require`) and related operations that
+//! must come before it (`preceded_by`). Required follow-ups (`followed_by`)
+//! are checked by the pipeline after the last unit, see
+//! [`crate::rules::patterns`]. Without configured patterns the rule never
+//! fires.
+
+use crate::parser::ir::{IrNode, Located};
+use crate::rules::patterns;
+use crate::rules::{Finding, LintContext, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str = "Operation does not follow a required migration pattern";
+
+pub(super) const EXPLAIN: &str = "PGM517 — Operation does not follow a required migration pattern\n\
+         \n\
+         What it detects:\n\
+         An operation that a [[patterns]] entry in the configuration applies\n\
+         to, but that is not rolled out the way the entry requires: it lacks\n\
+         a required option (NOT VALID, CONCURRENTLY), no related operation\n\
+         precedes it (preceded_by), or no related operation follows it later\n\
+         in the change (followed_by). Operations are related when they are on\n\
+         the same table and name the same constraint or share a column.\n\
+         \n\
+         Why it matters:\n\
+         Many safe rollouts take several steps in separate migrations, such as\n\
+         adding a CHECK ... NOT VALID, validating it, and only then running\n\
+         SET NOT NULL. Individual rules see one statement at a time; a\n\
+         pattern lets a team require the whole sequence.\n\
+         \n\
+         Example configuration:\n\
+           [[patterns]]\n\
+           name = \"not-null-via-check\"\n\
+           on = \"set_not_null\"\n\
+           preceded_by = \"validated_check\"\n\
+         \n\
+         Example (flagged):\n\
+           -- V010__orders_status_not_null.sql\n\
+           ALTER TABLE orders ALTER COLUMN status SET NOT NULL;\n\
+         \n\
+         Fix:\n\
+           -- V009__orders_status_check.sql\n\
+           ALTER TABLE orders ADD CONSTRAINT orders_status_nn\n\
+             CHECK (status IS NOT NULL) NOT VALID;\n\
+           ALTER TABLE orders VALIDATE CONSTRAINT orders_status_nn;\n\
+           -- V010__orders_status_not_null.sql\n\
+           ALTER TABLE orders ALTER COLUMN status SET NOT NULL;\n\
+         \n\
+         Operations on tables created in the same change are not checked.\n\
+         Each pattern can set its own severity and append a message, such as\n\
+         a link to the team's runbook.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Major;
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    let configured = &ctx.config.patterns;
+    if configured.is_empty() {
+        return Vec::new();
+    }
+
+    let current = patterns::operations(
+        statements,
+        ctx.file,
+        ctx.pattern_history.units(),
+        ctx.catalog_before,
+        ctx.catalog_after,
+        ctx.tables_created_in_change,
+    );
+    patterns::check_unit(&rule, configured, &current, ctx.pattern_history)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Config;
+    use crate::catalog::Catalog;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::config::PatternConfig;
+    use crate::parser::ir::*;
+    use crate::rules::patterns::{PatternFlag, PatternOp};
+    use crate::rules::test_helpers::{lint_ctx, located};
+    use crate::rules::{PatternHistory, RuleId};
+
+    fn pattern(name: &str, on: PatternOp) -> PatternConfig {
+        PatternConfig {
+            name: name.to_string(),
+            on,
+            require: vec![],
+            preceded_by: None,
+            followed_by: None,
+            separate_unit: true,
+            severity: None,
+            message: None,
+        }
+    }
+
+    fn config(patterns: Vec<PatternConfig>) -> Config {
+        Config {
+            patterns,
+            ..Config::default()
+        }
+    }
+
+    fn orders() -> Catalog {
+        CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "bigint", false)
+                    .column("status", "text", true)
+                    .column("customer_id", "bigint", true)
+                    .pk(&["id"]);
+            })
+            .build()
+    }
+
+    fn alter(action: AlterTableAction) -> Located<IrNode> {
+        located(IrNode::AlterTable(AlterTable {
+            name: QualifiedName::unqualified("orders"),
+            actions: vec![action],
+        }))
+    }
+
+    fn set_not_null() -> Located<IrNode> {
+        alter(AlterTableAction::SetNotNull {
+            column_name: "status".to_string(),
+        })
+    }
+
+    fn not_null_via_check() -> Config {
+        config(vec![PatternConfig {
+            preceded_by: Some(PatternOp::ValidatedCheck),
+            ..pattern("not-null-via-check", PatternOp::SetNotNull)
+        }])
+    }
+
+    #[test]
+    fn test_set_not_null_without_check_fires() {
+        let before = orders();
+        let after = before.clone();
+        let config = not_null_via_check();
+        lint_ctx!(ctx, &before, &after, "migrations/V010.sql", config: &config);
+
+        let findings = RuleId::Pgm517.check(&[set_not_null()], &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_set_not_null_after_validated_check_no_finding() {
+        let before = orders();
+        let after = before.clone();
+        let config = not_null_via_check();
+        lint_ctx!(ctx, &before, &after, "migrations/V010.sql", config: &config);
+
+        let check = [alter(AlterTableAction::AddConstraint(
+            TableConstraint::Check {
+                name: Some("orders_status_nn".to_string()),
+                expression: "status IS NOT NULL".to_string(),
+                not_valid: false,
+                deps: CheckExprDeps::default(),
+            },
+        ))];
+        let mut history = PatternHistory::new();
+        history.record(
+            &config.patterns,
+            patterns::operations(
+                &check,
+                ctx.file,
+                0,
+                &before,
+                &after,
+                ctx.tables_created_in_change,
+            ),
+            None,
+        );
+        let ctx = LintContext {
+            pattern_history: &history,
+            ..ctx
+        };
+
+        let findings = RuleId::Pgm517.check(&[set_not_null()], &ctx);
+        assert!(findings.is_empty(), "{findings:?}");
+    }
+
+    #[test]
+    fn test_check_in_same_unit_needs_separate_unit_off() {
+        let before = orders();
+        let after = before.clone();
+        let stmts = vec![
+            alter(AlterTableAction::AddConstraint(TableConstraint::Check {
+                name: None,
+                expression: "status IS NOT NULL".to_string(),
+                not_valid: false,
+                deps: CheckExprDeps::default(),
+            })),
+            set_not_null(),
+        ];
+
+        let config = not_null_via_check();
+        lint_ctx!(ctx, &before, &after, "migrations/V010.sql", config: &config);
+        assert_eq!(RuleId::Pgm517.check(&stmts, &ctx).len(), 1);
+
+        let mut config = not_null_via_check();
+        config.patterns[0].separate_unit = false;
+        lint_ctx!(ctx, &before, &after, "migrations/V010.sql", config: &config);
+        assert!(RuleId::Pgm517.check(&stmts, &ctx).is_empty());
+    }
+
+    #[test]
+    fn test_required_not_valid_uses_pattern_severity_and_message() {
+        let before = orders();
+        let after = before.clone();
+        let config = config(vec![PatternConfig {
+            require: vec![PatternFlag::NotValid],
+            severity: Some("critical".to_string()),
+            message: Some("See https://wiki.example.com/db/fk.".to_string()),
+            ..pattern("fk-not-valid", PatternOp::AddForeignKey)
+        }]);
+        lint_ctx!(ctx, &before, &after, "migrations/V010.sql", config: &config);
+
+        let stmts = vec![alter(AlterTableAction::AddConstraint(
+            TableConstraint::ForeignKey {
+                name: None,
+                columns: vec!["customer_id".to_string()],
+                ref_table: QualifiedName::unqualified("customers"),
+                ref_columns: vec!["id".to_string()],
+                not_valid: false,
+            },
+        ))];
+
+        let findings = RuleId::Pgm517.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_new_table_and_no_patterns_no_finding() {
+        let before = Catalog::new();
+        let after = orders();
+        let config = not_null_via_check();
+        lint_ctx!(ctx, &before, &after, "migrations/V010.sql", created: ["orders"]);
+        let ctx = LintContext {
+            config: &config,
+            ..ctx
+        };
+        assert!(RuleId::Pgm517.check(&[set_not_null()], &ctx).is_empty());
+
+        let before = orders();
+        lint_ctx!(ctx, &before, &before, "migrations/V010.sql");
+        assert!(RuleId::Pgm517.check(&[set_not_null()], &ctx).is_empty());
+    }
+}
@@ -0,0 +1,161 @@
+//! PGM518 — Temporary table without `ON COMMIT DROP` in a transactional migration
+//!
+//! Detects `CREATE TEMP TABLE` in a unit that runs in a transaction when the
+//! table is neither created with `ON COMMIT DROP` nor dropped later in the
+//! same unit. Migration tools apply a whole run on one connection, so the
+//! table survives the commit and shadows same-named tables in later
+//! migrations of that run.
+
+use crate::parser::ir::{IrNode, Located, OnCommit, TablePersistence};
+use crate::rules::{Finding, LintContext, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str =
+    "Temporary table without ON COMMIT DROP in a transactional migration";
+
+pub(super) const EXPLAIN: &str = "PGM518 — Temporary table without ON COMMIT DROP in a transactional migration\n\
+         \n\
+         What it detects:\n\
+         CREATE TEMP TABLE in a migration that runs inside a transaction,\n\
+         without ON COMMIT DROP and without a DROP TABLE for it later in the\n\
+         same migration.\n\
+         \n\
+         Why it matters:\n\
+         A temporary table lives until the end of the session unless it is\n\
+         created with ON COMMIT DROP. Migration tools apply every pending\n\
+         migration on the same connection, so a scratch table from one\n\
+         migration is still there when the next one runs. The session's\n\
+         temporary schema comes first in the search path, so an unqualified\n\
+         name that matches it silently resolves to the scratch table instead\n\
+         of a real table with that name, and a later CREATE TEMP TABLE with\n\
+         the same name fails. ON COMMIT DELETE ROWS only empties the table;\n\
+         it is still there.\n\
+         \n\
+         Example (flagged):\n\
+           CREATE TEMP TABLE orders_backfill (id bigint PRIMARY KEY);\n\
+           INSERT INTO orders_backfill SELECT id FROM orders WHERE status IS NULL;\n\
+         \n\
+         Fix:\n\
+           CREATE TEMP TABLE orders_backfill (id bigint PRIMARY KEY) ON COMMIT DROP;\n\
+           INSERT INTO orders_backfill SELECT id FROM orders WHERE status IS NULL;\n\
+         \n\
+         Migrations that do not run in a transaction are not checked: there\n\
+         every statement commits on its own and ON COMMIT DROP would drop the\n\
+         table right after creating it.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Info;
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    if !ctx.run_in_transaction {
+        return Vec::new();
+    }
+
+    let mut findings = Vec::new();
+
+    for (i, stmt) in statements.iter().enumerate() {
+        let IrNode::CreateTable(ct) = &stmt.node else {
+            continue;
+        };
+        if ct.persistence != TablePersistence::Temporary || ct.on_commit == OnCommit::Drop {
+            continue;
+        }
+        let key = ct.name.catalog_key();
+        let dropped_later = statements[i + 1..].iter().any(
+            |later| matches!(&later.node, IrNode::DropTable(dt) if dt.name.catalog_key() == key),
+        );
+        if dropped_later {
+            continue;
+        }
+
+        findings.push(
+            rule.make_finding(
+                format!(
+                    "Temporary table '{}' is created without ON COMMIT DROP in a \
+                     transactional migration. It outlives the migration for the rest \
+                     of the session and shadows any table named '{}' in later \
+                     migrations of the same run; add ON COMMIT DROP or drop it \
+                     explicitly.",
+                    ct.name.display_name(),
+                    ct.name.name,
+                ),
+                ctx.file,
+                &stmt.span,
+            )
+            .with_table(&ct.name),
+        );
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    fn temp_table(on_commit: OnCommit) -> Located<IrNode> {
+        located(IrNode::CreateTable(
+            CreateTable::test(QualifiedName::unqualified("orders_backfill"))
+                .with_persistence(TablePersistence::Temporary)
+                .with_on_commit(on_commit),
+        ))
+    }
+
+    #[test]
+    fn test_temp_table_without_on_commit_drop_fires() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql", txn: true);
+
+        let findings = RuleId::Pgm518.check(&[temp_table(OnCommit::PreserveRows)], &ctx);
+        insta::assert_yaml_snapshot!(findings);
+
+        let findings = RuleId::Pgm518.check(&[temp_table(OnCommit::DeleteRows)], &ctx);
+        assert_eq!(findings.len(), 1);
+    }
+
+    #[test]
+    fn test_on_commit_drop_or_explicit_drop_no_finding() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql", txn: true);
+
+        assert!(
+            RuleId::Pgm518
+                .check(&[temp_table(OnCommit::Drop)], &ctx)
+                .is_empty()
+        );
+
+        let stmts = vec![
+            temp_table(OnCommit::PreserveRows),
+            located(IrNode::DropTable(DropTable::test(
+                QualifiedName::unqualified("orders_backfill"),
+            ))),
+        ];
+        assert!(RuleId::Pgm518.check(&stmts, &ctx).is_empty());
+    }
+
+    #[test]
+    fn test_non_transactional_or_permanent_no_finding() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql", txn: false);
+        assert!(
+            RuleId::Pgm518
+                .check(&[temp_table(OnCommit::PreserveRows)], &ctx)
+                .is_empty()
+        );
+
+        lint_ctx!(ctx, &before, &after, "migrations/002.sql", txn: true);
+        let stmts = vec![located(IrNode::CreateTable(CreateTable::test(
+            QualifiedName::unqualified("orders"),
+        )))];
+        assert!(RuleId::Pgm518.check(&stmts, &ctx).is_empty());
+    }
+}
@@ -0,0 +1,184 @@
+//! PGM519 — RENAME COLUMN outside the contract phase of the rename workflow
+//!
+//! Enforces an expand/contract rename process: renaming a column of an
+//! existing table is allowed only when the `[rename_workflow]` state file
+//! lists the column in the `contract` phase. Opt-in, like other team
+//! policy rules.
+
+use crate::parser::ir::{IrNode, Located};
+use crate::rules::{Finding, LintContext, Rule, Severity, rename_phase};
+
+pub(super) const DESCRIPTION: &str =
+    "RENAME COLUMN outside the contract phase of the rename workflow";
+
+pub(super) const EXPLAIN: &str = "PGM519 — RENAME COLUMN outside the contract phase of the rename workflow\n\
+         \n\
+         What it detects:\n\
+         ALTER TABLE ... RENAME COLUMN on an existing table when the column\n\
+         is not in the \"contract\" phase of the rename workflow state file\n\
+         ([rename_workflow] state_file).\n\
+         \n\
+         Why it matters:\n\
+         A rename takes effect the moment the migration commits, while\n\
+         application instances still running the old code keep using the old\n\
+         name. A zero-downtime rename goes through phases instead:\n\
+         1. Expand: add the new column and write to both.\n\
+         2. Migrate: backfill and move every reader to the new column.\n\
+         3. Contract: drop the old column once nothing uses it.\n\
+         The state file records which phase each object is in, so a rename\n\
+         that skips the earlier phases is caught in review.\n\
+         \n\
+         This rule is off by default. Enable it with:\n\
+           [rules]\n\
+           enabled = [\"PGM519\"]\n\
+           [rename_workflow]\n\
+           state_file = \"db/renames.toml\"\n\
+         \n\
+         Example (flagged, column not in the contract phase):\n\
+           ALTER TABLE orders RENAME COLUMN status TO order_status;\n\
+         \n\
+         Fix:\n\
+           ALTER TABLE orders ADD COLUMN order_status text;\n\
+           -- deploy dual writes, backfill, move readers, then later:\n\
+           ALTER TABLE orders DROP COLUMN status;\n\
+         Or, once the column is ready, record it in the state file:\n\
+           [[renames]]\n\
+           table = \"orders\"\n\
+           column = \"status\"\n\
+           phase = \"contract\"\n\
+         \n\
+         Tables created in the same change are not checked.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Major;
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for stmt in statements {
+        let IrNode::RenameColumn {
+            table,
+            old_name,
+            new_name,
+        } = &stmt.node
+        else {
+            continue;
+        };
+        if !ctx.is_existing_table(table.catalog_key()) {
+            continue;
+        }
+        let Some(reason) = rename_phase::outside_contract(ctx, table, Some(old_name)) else {
+            continue;
+        };
+
+        findings.push(
+            rule.make_finding(
+                format!(
+                    "Renaming column '{old_name}' to '{new_name}' on '{table}' is not \
+                     allowed: the column is not in the contract phase of the rename \
+                     workflow ({reason}). Add '{new_name}' as a new column, write to \
+                     both and backfill, move readers to it, then drop '{old_name}'.",
+                    table = table.display_name(),
+                ),
+                ctx.file,
+                &stmt.span,
+            )
+            .with_table(table)
+            .with_param("column", old_name.as_str()),
+        );
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Config;
+    use crate::catalog::Catalog;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rename_workflow::RenamePhase;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    fn orders() -> Catalog {
+        CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "bigint", false)
+                    .column("status", "text", true)
+                    .pk(&["id"]);
+            })
+            .build()
+    }
+
+    fn rename_status() -> Vec<Located<IrNode>> {
+        vec![located(IrNode::RenameColumn {
+            table: QualifiedName::unqualified("orders"),
+            old_name: "status".to_string(),
+            new_name: "order_status".to_string(),
+        })]
+    }
+
+    fn config_with(phase: Option<RenamePhase>) -> Config {
+        let mut config = Config::default();
+        config.rename_workflow.state_file = Some("db/renames.toml".into());
+        if let Some(phase) = phase {
+            config
+                .rename_workflow
+                .state
+                .set_phase("orders", Some("status"), phase);
+        }
+        config
+    }
+
+    #[test]
+    fn test_rename_in_expand_phase_fires() {
+        let before = orders();
+        let after = before.clone();
+        let config = config_with(Some(RenamePhase::Expand));
+        lint_ctx!(ctx, &before, &after, "migrations/V010.sql", config: &config);
+
+        let findings = RuleId::Pgm519.check(&rename_status(), &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_untracked_or_unconfigured_fires() {
+        let before = orders();
+        let after = before.clone();
+
+        let config = config_with(None);
+        lint_ctx!(ctx, &before, &after, "migrations/V010.sql", config: &config);
+        let findings = RuleId::Pgm519.check(&rename_status(), &ctx);
+        assert!(
+            findings[0]
+                .message
+                .contains("not listed in the rename workflow state file"),
+            "{findings:?}"
+        );
+
+        lint_ctx!(ctx, &before, &after, "migrations/V010.sql");
+        let findings = RuleId::Pgm519.check(&rename_status(), &ctx);
+        assert!(
+            findings[0]
+                .message
+                .contains("no rename_workflow.state_file"),
+            "{findings:?}"
+        );
+    }
+
+    #[test]
+    fn test_contract_phase_or_new_table_no_finding() {
+        let before = orders();
+        let after = before.clone();
+        let config = config_with(Some(RenamePhase::Contract));
+        lint_ctx!(ctx, &before, &after, "migrations/V010.sql", config: &config);
+        assert!(RuleId::Pgm519.check(&rename_status(), &ctx).is_empty());
+
+        let before = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/V010.sql", created: ["orders"]);
+        assert!(RuleId::Pgm519.check(&rename_status(), &ctx).is_empty());
+    }
+}
@@ -0,0 +1,129 @@
+//! PGM520 — RENAME TABLE outside the contract phase of the rename workflow
+//!
+//! Table counterpart of PGM519: renaming an existing table is allowed only
+//! when the `[rename_workflow]` state file lists the table (without a
+//! column) in the `contract` phase. Opt-in.
+
+use crate::parser::ir::{IrNode, Located};
+use crate::rules::{Finding, LintContext, Rule, Severity, rename_phase};
+
+pub(super) const DESCRIPTION: &str =
+    "RENAME TABLE outside the contract phase of the rename workflow";
+
+pub(super) const EXPLAIN: &str = "PGM520 — RENAME TABLE outside the contract phase of the rename workflow\n\
+         \n\
+         What it detects:\n\
+         ALTER TABLE ... RENAME TO on an existing table when the table is not\n\
+         in the \"contract\" phase of the rename workflow state file\n\
+         ([rename_workflow] state_file).\n\
+         \n\
+         Why it matters:\n\
+         Every query that names the old table fails as soon as the rename\n\
+         commits, including those from application instances still running\n\
+         the previous release. A zero-downtime rename first creates the new\n\
+         table (or a view under the new name) and writes to both, then moves\n\
+         readers over, and only retires the old name once nothing uses it.\n\
+         The state file records which phase each table is in.\n\
+         \n\
+         This rule is off by default. Enable it with:\n\
+           [rules]\n\
+           enabled = [\"PGM520\"]\n\
+           [rename_workflow]\n\
+           state_file = \"db/renames.toml\"\n\
+         \n\
+         Example (flagged, table not in the contract phase):\n\
+           ALTER TABLE orders RENAME TO purchases;\n\
+         \n\
+         Fix:\n\
+         Expand first, for example with an updatable view under the new name:\n\
+           CREATE VIEW purchases AS SELECT * FROM orders;\n\
+         Move readers and writers to 'purchases', then record the table in\n\
+         the state file and rename it in a later migration:\n\
+           [[renames]]\n\
+           table = \"orders\"\n\
+           phase = \"contract\"\n\
+         \n\
+         Tables created in the same change are not checked.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Major;
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for stmt in statements {
+        let IrNode::RenameTable { name, new_name } = &stmt.node else {
+            continue;
+        };
+        if !ctx.is_existing_table(name.catalog_key()) {
+            continue;
+        }
+        let Some(reason) = rename_phase::outside_contract(ctx, name, None) else {
+            continue;
+        };
+
+        findings.push(
+            rule.make_finding(
+                format!(
+                    "Renaming table '{table}' to '{new_name}' is not allowed: the table \
+                     is not in the contract phase of the rename workflow ({reason}). \
+                     Create '{new_name}' (or a view with that name), move readers and \
+                     writers to it, then retire '{table}'.",
+                    table = name.display_name(),
+                ),
+                ctx.file,
+                &stmt.span,
+            )
+            .with_table(name),
+        );
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Config;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rename_workflow::RenamePhase;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    fn rename_orders() -> Vec<Located<IrNode>> {
+        vec![located(IrNode::RenameTable {
+            name: QualifiedName::unqualified("orders"),
+            new_name: "purchases".to_string(),
+        })]
+    }
+
+    #[test]
+    fn test_rename_outside_contract_fires_until_contract() {
+        let before = CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "bigint", false).pk(&["id"]);
+            })
+            .build();
+        let after = before.clone();
+        let mut config = Config::default();
+        config.rename_workflow.state_file = Some("db/renames.toml".into());
+        config
+            .rename_workflow
+            .state
+            .set_phase("orders", None, RenamePhase::Migrate);
+        lint_ctx!(ctx, &before, &after, "migrations/V010.sql", config: &config);
+
+        let findings = RuleId::Pgm520.check(&rename_orders(), &ctx);
+        insta::assert_yaml_snapshot!(findings);
+
+        let mut config = config.clone();
+        config
+            .rename_workflow
+            .state
+            .set_phase("orders", None, RenamePhase::Contract);
+        lint_ctx!(ctx, &before, &after, "migrations/V010.sql", config: &config);
+        assert!(RuleId::Pgm520.check(&rename_orders(), &ctx).is_empty());
+    }
+}
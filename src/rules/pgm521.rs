@@ -0,0 +1,307 @@
+//! PGM521 — Column default uses a missing or cross-schema sequence
+//!
+//! Detects column defaults of the form `nextval('schema.seq')` whose sequence
+//! either does not exist in the replayed migration history or lives in a
+//! different schema than the table. A missing sequence is usually a typo or a
+//! migration that never made it into the repository, and the DDL fails when
+//! it runs. A cross-schema reference ties the table to another schema, so a
+//! per-schema `pg_dump --schema` restore fails on the default.
+
+use crate::parser::ir::{AlterTableAction, ColumnDef, DefaultExpr, IrNode, Located, QualifiedName};
+use crate::rules::{Finding, LintContext, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str = "Column default uses a missing or cross-schema sequence";
+
+pub(super) const EXPLAIN: &str = "PGM521 — Column default uses a missing or cross-schema sequence\n\
+         \n\
+         What it detects:\n\
+         A column DEFAULT (in CREATE TABLE, ADD COLUMN, or ALTER COLUMN\n\
+         SET DEFAULT) that calls nextval() on a sequence which either:\n\
+         - is not created anywhere in the migration history (CREATE SEQUENCE,\n\
+           or the implicit sequence of a serial or identity column), or\n\
+         - lives in a different schema than the table.\n\
+         \n\
+         Why it matters:\n\
+         nextval('name') resolves the sequence when the default is stored, so\n\
+         a missing sequence makes the migration fail with 'relation does not\n\
+         exist'. That usually means a typo or a sequence created by hand\n\
+         outside the migrations, which breaks every fresh environment.\n\
+         A sequence in another schema works at runtime, but a per-schema\n\
+         dump (pg_dump --schema) of the table's schema no longer restores on\n\
+         its own: the default references an object the dump does not contain.\n\
+         \n\
+         Example (flagged):\n\
+           CREATE SEQUENCE shared.order_number_seq;\n\
+           ALTER TABLE billing.orders\n\
+             ALTER COLUMN order_number SET DEFAULT nextval('shared.order_number_seq');\n\
+         \n\
+         Fix:\n\
+           CREATE SEQUENCE billing.order_number_seq;\n\
+           ALTER TABLE billing.orders\n\
+             ALTER COLUMN order_number SET DEFAULT nextval('billing.order_number_seq');\n\
+         \n\
+         A missing sequence is reported as CRITICAL; a cross-schema sequence\n\
+         uses the rule's default severity. Defaults whose argument is not a\n\
+         string literal (e.g. nextval(some_function())) are not checked.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Major;
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for stmt in statements {
+        let (table, defaults): (&QualifiedName, Vec<(&str, &DefaultExpr)>) = match &stmt.node {
+            IrNode::CreateTable(ct) => (&ct.name, column_defaults(&ct.columns)),
+            IrNode::AlterTable(at) => {
+                let defaults = at
+                    .actions
+                    .iter()
+                    .filter_map(|action| match action {
+                        AlterTableAction::AddColumn(col) => col
+                            .default_expr
+                            .as_ref()
+                            .map(|expr| (col.name.as_str(), expr)),
+                        AlterTableAction::SetDefault {
+                            column_name,
+                            default_expr,
+                        } => Some((column_name.as_str(), default_expr)),
+                        _ => None,
+                    })
+                    .collect();
+                (&at.name, defaults)
+            }
+            _ => continue,
+        };
+
+        for (column, expr) in defaults {
+            let Some((seq_schema, seq_name)) = nextval_sequence(expr) else {
+                continue;
+            };
+            let default_schema = ctx.config.migrations.default_schema_for(ctx.file);
+            let seq_schema = seq_schema.unwrap_or_else(|| default_schema.to_string());
+            let table_schema = table.schema.as_deref().unwrap_or(default_schema);
+            let seq_key = format!("{seq_schema}.{seq_name}");
+
+            let exists = ctx.catalog_before.has_sequence(&seq_key)
+                || ctx.catalog_after.has_sequence(&seq_key);
+
+            let finding = if !exists {
+                Finding::new(
+                    rule.id(),
+                    Severity::Critical,
+                    format!(
+                        "Default of column '{column}' on '{table}' calls nextval('{seq_key}'), \
+                         but no migration creates sequence '{seq_key}'. The statement fails \
+                         with 'relation does not exist'; fix the name or add the missing \
+                         CREATE SEQUENCE.",
+                        table = table.display_name(),
+                    ),
+                    ctx.file,
+                    &stmt.span,
+                )
+            } else if seq_schema != table_schema {
+                rule.make_finding(
+                    format!(
+                        "Default of column '{column}' on '{table}' uses sequence '{seq_key}' \
+                         from schema '{seq_schema}'. A per-schema dump of '{table_schema}' \
+                         no longer restores on its own; create the sequence in \
+                         '{table_schema}' instead.",
+                        table = table.display_name(),
+                    ),
+                    ctx.file,
+                    &stmt.span,
+                )
+            } else {
+                continue;
+            };
+
+            findings.push(
+                finding
+                    .with_table(table)
+                    .with_param("column", column)
+                    .with_param("sequence", seq_key),
+            );
+        }
+    }
+
+    findings
+}
+
+fn column_defaults(columns: &[ColumnDef]) -> Vec<(&str, &DefaultExpr)> {
+    columns
+        .iter()
+        .filter_map(|col| {
+            col.default_expr
+                .as_ref()
+                .map(|expr| (col.name.as_str(), expr))
+        })
+        .collect()
+}
+
+/// Extract the sequence named by a `nextval('...')` default as
+/// `(schema, name)`. Returns `None` for anything else, including the
+/// synthesized argument-less `nextval()` of serial columns and arguments
+/// that are not string literals.
+pub(crate) fn nextval_sequence(expr: &DefaultExpr) -> Option<(Option<String>, String)> {
+    let DefaultExpr::FunctionCall { name, args } = expr else {
+        return None;
+    };
+    if !name.eq_ignore_ascii_case("nextval") {
+        return None;
+    }
+    let arg = args.first()?.trim();
+    let arg = match arg.len().checked_sub("::regclass".len()) {
+        Some(cut) if arg.is_char_boundary(cut) && arg[cut..].eq_ignore_ascii_case("::regclass") => {
+            arg[..cut].trim_end()
+        }
+        _ => arg,
+    };
+    let literal = arg
+        .strip_prefix('\'')?
+        .strip_suffix('\'')?
+        .replace("''", "'");
+
+    let mut parts = split_identifier(&literal);
+    match parts.len() {
+        1 => Some((None, parts.pop()?)),
+        2 => {
+            let name = parts.pop()?;
+            Some((parts.pop(), name))
+        }
+        _ => None,
+    }
+}
+
+/// Split a possibly qualified identifier on dots outside double quotes,
+/// folding unquoted parts to lower case the way PostgreSQL does.
+fn split_identifier(text: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                current.push('"');
+            }
+            '"' => quoted = !quoted,
+            '.' if !quoted => parts.push(std::mem::take(&mut current)),
+            c if quoted => current.push(c),
+            c => current.extend(c.to_lowercase()),
+        }
+    }
+    parts.push(current);
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    fn nextval(arg: &str) -> DefaultExpr {
+        DefaultExpr::FunctionCall {
+            name: "nextval".to_string(),
+            args: vec![arg.to_string()],
+        }
+    }
+
+    fn set_default(table: QualifiedName, arg: &str) -> Located<IrNode> {
+        located(IrNode::AlterTable(AlterTable {
+            name: table,
+            actions: vec![AlterTableAction::SetDefault {
+                column_name: "order_number".to_string(),
+                default_expr: nextval(arg),
+            }],
+        }))
+    }
+
+    #[test]
+    fn test_missing_and_cross_schema_sequence_fire() {
+        let before = CatalogBuilder::new()
+            .table("billing.orders", |t| {
+                t.column("order_number", "bigint", false);
+            })
+            .sequence("shared.order_number_seq")
+            .build();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/V002.sql");
+
+        let stmts = vec![
+            set_default(
+                QualifiedName::qualified("billing", "orders"),
+                "'billing.order_numbr_seq'::regclass",
+            ),
+            set_default(
+                QualifiedName::qualified("billing", "orders"),
+                "'shared.order_number_seq'::regclass",
+            ),
+        ];
+
+        let findings = RuleId::Pgm521.check(&stmts, &ctx);
+        assert_eq!(findings[0].severity, Severity::Critical);
+        assert_eq!(findings[1].severity, Severity::Major);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_same_schema_and_serial_defaults_no_finding() {
+        let before = CatalogBuilder::new()
+            .sequence("billing.order_number_seq")
+            .sequence("billing.Invoice Seq")
+            .build();
+        let mut after = before.clone();
+        after.add_sequence("billing.invoices_id_seq", None);
+        lint_ctx!(ctx, &before, &after, "migrations/V002.sql", created: ["billing.invoices"]);
+
+        let stmts = vec![
+            located(IrNode::CreateTable(
+                CreateTable::test(QualifiedName::qualified("billing", "invoices")).with_columns(
+                    vec![
+                        ColumnDef::test("id", "integer").with_serial(),
+                        ColumnDef::test("number", "bigint")
+                            .with_default(nextval("'billing.\"Invoice Seq\"'::regclass")),
+                    ],
+                ),
+            )),
+            set_default(
+                QualifiedName::qualified("billing", "orders"),
+                "'Billing.Order_Number_Seq'::regclass",
+            ),
+            set_default(
+                QualifiedName::qualified("billing", "orders"),
+                "nextval_source()",
+            ),
+        ];
+
+        assert!(RuleId::Pgm521.check(&stmts, &ctx).is_empty());
+    }
+
+    #[test]
+    fn test_unqualified_sequence_resolves_to_default_schema() {
+        let before = CatalogBuilder::new().sequence("public.orders_seq").build();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/V002.sql");
+
+        let findings = RuleId::Pgm521.check(
+            &[
+                set_default(QualifiedName::qualified("public", "orders"), "'orders_seq'"),
+                set_default(
+                    QualifiedName::qualified("billing", "orders"),
+                    "'orders_seq'",
+                ),
+            ],
+            &ctx,
+        );
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Major);
+    }
+}
@@ -0,0 +1,57 @@
+//! PGM522 — Table created in the change has foreign keys no index covers
+//!
+//! The change-scoped counterpart of PGM501. The pipeline records every table
+//! the changed units create and, after the last unit, reports those whose
+//! foreign-key columns no index covers anywhere in the change (see
+//! [`NewTables`](crate::rules::NewTables)). An index added by a sibling
+//! changed migration therefore counts, which PGM501's per-unit check cannot
+//! see. The per-unit check below never fires.
+
+use crate::parser::ir::{IrNode, Located};
+use crate::rules::{Finding, LintContext, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str = "Table created in the change has foreign keys no index covers";
+
+pub(super) const EXPLAIN: &str = "PGM522 — Table created in the change has foreign keys no index covers\n\
+         \n\
+         What it detects:\n\
+         A table created by one of the changed migrations that has foreign\n\
+         keys (declared in the CREATE TABLE or added later in the change),\n\
+         where no migration in the change creates an index whose leading\n\
+         columns match the foreign-key columns. It is reported at the\n\
+         CREATE TABLE once every changed migration has been processed.\n\
+         \n\
+         Why it matters:\n\
+         Deletes and updates on the referenced table must check the new\n\
+         table for referencing rows; without an index that is a sequential\n\
+         scan per affected row. A new table is empty, so this goes unnoticed\n\
+         until it fills up, and adding the index then needs CREATE INDEX\n\
+         CONCURRENTLY on a busy table. PGM501 checks each migration on its\n\
+         own and cannot tell whether a sibling migration in the same change\n\
+         adds the index; this rule looks at the change as a whole.\n\
+         \n\
+         Example (flagged):\n\
+           -- V042__order_items.sql\n\
+           CREATE TABLE order_items (\n\
+             id bigint PRIMARY KEY,\n\
+             order_id bigint NOT NULL REFERENCES orders (id)\n\
+           );\n\
+           -- no index on order_items (order_id) in V042 or V043\n\
+         \n\
+         Fix:\n\
+           -- V043__order_items_indexes.sql\n\
+           CREATE INDEX idx_order_items_order_id ON order_items (order_id);\n\
+         \n\
+         Prefix matching and partition handling are the same as PGM501.\n\
+         Tables dropped again within the change are not reported.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Major;
+
+pub(super) fn check(
+    _rule: impl Rule,
+    _statements: &[Located<IrNode>],
+    _ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    // Reported by the pipeline after the last unit.
+    Vec::new()
+}
@@ -0,0 +1,253 @@
+//! PGM523 — Sequence not owned by a column
+//!
+//! Detects `CREATE SEQUENCE` whose sequence is still not owned by a column
+//! (`OWNED BY`) at the end of the unit, and `DROP TABLE` of a table whose
+//! column default calls `nextval()` on such a sequence. PostgreSQL drops a
+//! sequence with its owning column, so an unowned sequence outlives the
+//! table that used it. Implicit sequences of `serial` and identity columns
+//! are always owned and never reported.
+//!
+//! A sequence is not reported at `DROP TABLE` when the same unit drops it,
+//! or when a column default of a remaining table still calls it.
+
+use crate::catalog::Catalog;
+use crate::parser::ir::{DefaultExpr, IrNode, Located};
+use crate::rules::pgm521::nextval_sequence;
+use crate::rules::{Finding, LintContext, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str = "Sequence not owned by a column";
+
+pub(super) const EXPLAIN: &str = "PGM523 — Sequence not owned by a column\n\
+         \n\
+         What it detects:\n\
+         - CREATE SEQUENCE without OWNED BY, when no later statement in the\n\
+           migration sets an owner with ALTER SEQUENCE ... OWNED BY.\n\
+         - DROP TABLE of a table whose column default calls nextval() on a\n\
+           sequence that no column owns, leaving the sequence behind.\n\
+         \n\
+         Why it matters:\n\
+         PostgreSQL drops a sequence together with the column that owns it.\n\
+         A sequence created explicitly and wired up with DEFAULT nextval()\n\
+         has no owner unless OWNED BY says so, so dropping the table leaves\n\
+         an orphaned sequence behind. Orphans accumulate, confuse schema\n\
+         diffs, and block a later CREATE SEQUENCE of the same name.\n\
+         Sequences of serial and identity columns are owned automatically.\n\
+         \n\
+         Example (flagged):\n\
+           CREATE SEQUENCE invoice_no_seq;\n\
+           CREATE TABLE invoices (\n\
+             id bigint PRIMARY KEY,\n\
+             invoice_no bigint DEFAULT nextval('invoice_no_seq')\n\
+           );\n\
+         \n\
+         Fix:\n\
+           CREATE SEQUENCE invoice_no_seq;\n\
+           CREATE TABLE invoices (...);\n\
+           ALTER SEQUENCE invoice_no_seq OWNED BY invoices.invoice_no;\n\
+         \n\
+         A sequence shared by several tables cannot be owned by one of them;\n\
+         suppress the finding for it and drop it with the last table.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Minor;
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for stmt in statements {
+        match &stmt.node {
+            IrNode::CreateSequence { name, .. } => {
+                let key = name.catalog_key();
+                if !ctx.catalog_after.has_sequence(key)
+                    || ctx.catalog_after.sequence_owner(key).is_some()
+                {
+                    continue;
+                }
+                findings.push(
+                    rule.make_finding(
+                        format!(
+                            "Sequence '{seq}' is not owned by a column. PostgreSQL only drops \
+                             a sequence together with its owning column, so it is left behind \
+                             when the table using it is dropped. Add ALTER SEQUENCE {seq} \
+                             OWNED BY <table>.<column>.",
+                            seq = name.display_name(),
+                        ),
+                        ctx.file,
+                        &stmt.span,
+                    )
+                    .with_param("sequence", name.display_name()),
+                );
+            }
+            IrNode::DropTable(dt) => {
+                let table_key = dt.name.catalog_key();
+                let Some(table) = ctx.catalog_before.get_table(table_key) else {
+                    continue;
+                };
+                for col in &table.columns {
+                    let Some(seq_key) = col
+                        .default_expr
+                        .as_ref()
+                        .and_then(|expr| sequence_key(expr, ctx))
+                    else {
+                        continue;
+                    };
+                    if !ctx.catalog_before.has_sequence(&seq_key)
+                        || ctx.catalog_before.sequence_owner(&seq_key).is_some()
+                        || !ctx.catalog_after.has_sequence(&seq_key)
+                        || used_by_any_table(ctx.catalog_after, &seq_key, ctx)
+                    {
+                        continue;
+                    }
+                    findings.push(
+                        rule.make_finding(
+                            format!(
+                                "DROP TABLE '{table}' leaves sequence '{seq_key}' behind. It \
+                                 backs the default of column '{column}' but is not owned by \
+                                 it, so PostgreSQL does not drop it with the table. Drop the \
+                                 sequence in the same migration.",
+                                table = dt.name.display_name(),
+                                column = col.name,
+                            ),
+                            ctx.file,
+                            &stmt.span,
+                        )
+                        .with_table(&dt.name)
+                        .with_param("column", col.name.as_str())
+                        .with_param("sequence", seq_key),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    findings
+}
+
+/// Catalog key of the sequence a `nextval('...')` default calls.
+fn sequence_key(expr: &DefaultExpr, ctx: &LintContext<'_>) -> Option<String> {
+    let (schema, name) = nextval_sequence(expr)?;
+    let schema = schema.unwrap_or_else(|| {
+        ctx.config
+            .migrations
+            .default_schema_for(ctx.file)
+            .to_string()
+    });
+    Some(format!("{schema}.{name}"))
+}
+
+/// Whether a column default of any table in `catalog` still calls the
+/// sequence.
+fn used_by_any_table(catalog: &Catalog, seq_key: &str, ctx: &LintContext<'_>) -> bool {
+    catalog.tables().any(|table| {
+        table.columns.iter().any(|col| {
+            col.default_expr
+                .as_ref()
+                .and_then(|expr| sequence_key(expr, ctx))
+                .is_some_and(|key| key == seq_key)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    fn nextval(seq: &str) -> DefaultExpr {
+        DefaultExpr::FunctionCall {
+            name: "nextval".to_string(),
+            args: vec![format!("'{seq}'::regclass")],
+        }
+    }
+
+    fn invoices(seq: &str) -> CatalogBuilder {
+        CatalogBuilder::new().table("invoices", |t| {
+            t.column("id", "bigint", false)
+                .column_with_default("invoice_no", "bigint", false, nextval(seq))
+                .pk(&["id"]);
+        })
+    }
+
+    fn create_sequence(name: &str) -> Located<IrNode> {
+        located(IrNode::CreateSequence {
+            name: QualifiedName::qualified("public", name),
+            if_not_exists: false,
+            owned_by: None,
+        })
+    }
+
+    fn drop_invoices() -> Located<IrNode> {
+        located(DropTable::test(QualifiedName::unqualified("invoices")).into())
+    }
+
+    #[test]
+    fn test_create_sequence_without_owner_fires() {
+        let before = Catalog::new();
+        let after = CatalogBuilder::new()
+            .sequence("public.invoice_no_seq")
+            .owned_sequence("public.order_no_seq", "orders", "order_no")
+            .build();
+        lint_ctx!(ctx, &before, &after, "migrations/V002.sql");
+
+        let stmts = vec![
+            create_sequence("invoice_no_seq"),
+            create_sequence("order_no_seq"),
+            create_sequence("dropped_seq"),
+        ];
+        let findings = RuleId::Pgm523.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_drop_table_leaving_unowned_sequence_fires() {
+        let before = invoices("invoice_no_seq")
+            .sequence("public.invoice_no_seq")
+            .build();
+        let after = CatalogBuilder::new()
+            .sequence("public.invoice_no_seq")
+            .build();
+        lint_ctx!(ctx, &before, &after, "migrations/V003.sql");
+
+        let findings = RuleId::Pgm523.check(&[drop_invoices()], &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_drop_table_with_owned_dropped_or_shared_sequence_no_finding() {
+        let owned = invoices("invoice_no_seq")
+            .owned_sequence("public.invoice_no_seq", "invoices", "invoice_no")
+            .build();
+        let dropped_with_table = invoices("invoice_no_seq")
+            .sequence("public.invoice_no_seq")
+            .build();
+        let shared = CatalogBuilder::new()
+            .table("credit_notes", |t| {
+                t.column_with_default("note_no", "bigint", false, nextval("invoice_no_seq"));
+            })
+            .sequence("public.invoice_no_seq")
+            .build();
+        let shared_before = invoices("invoice_no_seq")
+            .table("credit_notes", |t| {
+                t.column_with_default("note_no", "bigint", false, nextval("invoice_no_seq"));
+            })
+            .sequence("public.invoice_no_seq")
+            .build();
+        let empty = Catalog::new();
+
+        for (before, after) in [
+            (&owned, &empty),
+            (&dropped_with_table, &empty),
+            (&shared_before, &shared),
+        ] {
+            lint_ctx!(ctx, before, after, "migrations/V003.sql");
+            assert!(RuleId::Pgm523.check(&[drop_invoices()], &ctx).is_empty());
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! PGM524 — Logical replication change in a schema migration
+//!
+//! Detects `CREATE`/`ALTER`/`DROP PUBLICATION` and `CREATE`/`ALTER`/`DROP
+//! SUBSCRIPTION` in a migration. Teams that run logical replication through
+//! an infrastructure pipeline want topology changes kept out of application
+//! migrations, where they bypass that pipeline's review and rollout.
+//!
+//! This is a policy rule, not a migration hazard, so it is opt-in: it only
+//! runs when listed in `[rules] enabled`.
+
+use crate::parser::ir::{IrNode, Located};
+use crate::rules::{Finding, LintContext, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str = "Logical replication change in a schema migration";
+
+pub(super) const EXPLAIN: &str = "PGM524 — Logical replication change in a schema migration\n\
+         \n\
+         What it detects:\n\
+         CREATE, ALTER, or DROP of a PUBLICATION or SUBSCRIPTION in a\n\
+         migration file.\n\
+         \n\
+         Why it matters:\n\
+         Publications and subscriptions define the logical replication\n\
+         topology: which tables stream to replicas, CDC pipelines, and other\n\
+         databases. Changing them from an application migration bypasses\n\
+         whoever operates that topology. A table added to a publication\n\
+         starts streaming before the consumers expect it, a dropped table\n\
+         silently stops, and a subscription change can stall or duplicate\n\
+         replication. The same migration also runs in every environment,\n\
+         including ones with a different topology.\n\
+         \n\
+         This rule is off by default. Enable it with:\n\
+           [rules]\n\
+           enabled = [\"PGM524\"]\n\
+         \n\
+         Example (flagged):\n\
+           CREATE TABLE refunds (id bigint PRIMARY KEY);\n\
+           ALTER PUBLICATION orders_pub ADD TABLE refunds;\n\
+         \n\
+         Fix:\n\
+         Move the publication or subscription change to the infrastructure\n\
+         pipeline and keep the migration to schema changes.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Info;
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for stmt in statements {
+        let IrNode::Replication(rc) = &stmt.node else {
+            continue;
+        };
+        let tables = if rc.tables.is_empty() {
+            String::new()
+        } else {
+            let names: Vec<String> = rc.tables.iter().map(|t| t.display_name()).collect();
+            format!(" (tables: {})", names.join(", "))
+        };
+        findings.push(
+            rule.make_finding(
+                format!(
+                    "{} {}{tables} in a schema migration changes the logical replication \
+                     topology. Replication changes belong in the infrastructure pipeline; \
+                     move this statement out of the migration.",
+                    rc.statement, rc.name,
+                ),
+                ctx.file,
+                &stmt.span,
+            )
+            .with_param("statement", rc.statement.as_str())
+            .with_param("name", rc.name.as_str()),
+        );
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    #[test]
+    fn test_publication_and_subscription_changes_fire() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/V002.sql");
+
+        let stmts = vec![
+            located(
+                ReplicationChange::test("ALTER PUBLICATION", "orders_pub")
+                    .with_tables(vec![
+                        QualifiedName::unqualified("refunds"),
+                        QualifiedName::qualified("billing", "invoices"),
+                    ])
+                    .into(),
+            ),
+            located(ReplicationChange::test("ALTER SUBSCRIPTION", "orders_sub").into()),
+        ];
+
+        let findings = RuleId::Pgm524.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_other_statements_no_finding() {
+        let before = Catalog::new();
+        let after = Catalog::new();
+        lint_ctx!(ctx, &before, &after, "migrations/V002.sql");
+
+        let stmts = vec![located(Grant::test(&["invoices"], &["reporting"]).into())];
+        assert!(RuleId::Pgm524.check(&stmts, &ctx).is_empty());
+    }
+}
@@ -0,0 +1,198 @@
+//! PGM525 — Column added to a table read with SELECT * or positional INSERT
+//!
+//! Detects `ALTER TABLE ... ADD COLUMN` on an existing table listed in
+//! `[rules.pgm525] tables`: heavily referenced tables whose consumers may
+//! read with `SELECT *` or write with `INSERT` without a column list. Both
+//! depend on the table's column count and order, which a new column changes.
+//!
+//! The notice is a reminder, not a hazard, so it is reported once per table:
+//! at the first statement in the unit that adds columns to it, and the
+//! pipeline drops it from later units of the same run. The rule is opt-in.
+
+use std::collections::HashSet;
+
+use crate::config::Config;
+use crate::parser::ir::{AlterTableAction, IrNode, Located};
+use crate::rules::{ConfiguredRule, Finding, LintContext, Rule, RuleId, Severity};
+
+pub(super) const DESCRIPTION: &str =
+    "Column added to a table read with SELECT * or positional INSERT";
+
+pub(super) const EXPLAIN: &str = "PGM525 — Column added to a table read with SELECT * or positional INSERT\n\
+         \n\
+         What it detects:\n\
+         ALTER TABLE ... ADD COLUMN on an existing table listed in\n\
+         [rules.pgm525] tables. Reported once per table per run.\n\
+         \n\
+         Why it matters:\n\
+         Adding a column is safe for PostgreSQL, but not for every consumer.\n\
+         Code that reads with SELECT * and maps columns by position, views\n\
+         and reports that expect a fixed shape, COPY without a column list,\n\
+         and INSERT INTO t VALUES (...) without a column list all depend on\n\
+         the number and order of columns. The new column shifts them: an\n\
+         INSERT without a column list fails or writes values into the wrong\n\
+         columns, and positional readers break or mis-map the row.\n\
+         \n\
+         This rule is off by default and needs a table list. Enable it with:\n\
+           [rules]\n\
+           enabled = [\"PGM525\"]\n\
+         \n\
+           [rules.pgm525]\n\
+           tables = [\"orders\", \"billing.invoices\"]\n\
+         \n\
+         Example (flagged):\n\
+           ALTER TABLE orders ADD COLUMN discount_cents bigint;\n\
+         \n\
+         Fix:\n\
+         Check the consumers of the table before deploying: name the columns\n\
+         in SELECT, INSERT, and COPY, and refresh views that expand SELECT *.";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Info;
+
+/// PGM525 with `[rules.pgm525]` resolved.
+pub(super) struct Pgm525 {
+    tables: Vec<String>,
+}
+
+impl ConfiguredRule for Pgm525 {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            tables: config.rules.pgm525.tables.clone(),
+        }
+    }
+
+    fn check(
+        &self,
+        rule: RuleId,
+        statements: &[Located<IrNode>],
+        ctx: &LintContext<'_>,
+    ) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        let mut reported: HashSet<&str> = HashSet::new();
+
+        for stmt in statements {
+            let IrNode::AlterTable(at) = &stmt.node else {
+                continue;
+            };
+            let table_key = at.name.catalog_key();
+            if !ctx.is_existing_table(table_key)
+                || !ctx.table_in_list(&at.name, &self.tables)
+                || reported.contains(table_key)
+            {
+                continue;
+            }
+            let columns: Vec<&str> = at
+                .actions
+                .iter()
+                .filter_map(|action| match action {
+                    AlterTableAction::AddColumn(col) => Some(col.name.as_str()),
+                    _ => None,
+                })
+                .collect();
+            if columns.is_empty() {
+                continue;
+            }
+            reported.insert(table_key);
+            let columns = columns.join(", ");
+
+            findings.push(
+                rule.make_finding(
+                    format!(
+                        "Adding column(s) {columns} to '{table}' changes the column count and \
+                         order that SELECT * readers and INSERT statements without a column \
+                         list rely on. Check the consumers of '{table}' before deploying.",
+                        table = at.name.display_name(),
+                    ),
+                    ctx.file,
+                    &stmt.span,
+                )
+                .with_dedup_key(table_key.to_string())
+                .with_table(&at.name)
+                .with_param("columns", columns),
+            );
+        }
+
+        findings
+    }
+}
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    Pgm525::from_config(ctx.config).check(rule.id(), statements, ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    fn orders() -> Catalog {
+        CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "bigint", false).pk(&["id"]);
+            })
+            .table("audit_log", |t| {
+                t.column("id", "bigint", false).pk(&["id"]);
+            })
+            .build()
+    }
+
+    fn add_column(table: &str, column: &str) -> Located<IrNode> {
+        located(IrNode::AlterTable(AlterTable {
+            name: QualifiedName::unqualified(table),
+            actions: vec![AlterTableAction::AddColumn(ColumnDef::test(
+                column, "bigint",
+            ))],
+        }))
+    }
+
+    fn config(tables: &[&str]) -> Config {
+        let mut config = Config::default();
+        config.rules.pgm525.tables = tables.iter().map(|t| t.to_string()).collect();
+        config
+    }
+
+    #[test]
+    fn test_listed_existing_table_fires_once() {
+        let before = orders();
+        let after = before.clone();
+        let config = config(&["orders"]);
+        lint_ctx!(ctx, &before, &after, "migrations/V002.sql", config: &config);
+
+        let stmts = vec![
+            add_column("orders", "discount_cents"),
+            add_column("orders", "coupon_code"),
+            add_column("audit_log", "actor"),
+        ];
+        let findings = RuleId::Pgm525.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_unlisted_or_new_table_no_finding() {
+        let before = orders();
+        let after = before.clone();
+        let unlisted = config(&[]);
+        lint_ctx!(ctx, &before, &after, "migrations/V002.sql", config: &unlisted);
+        assert!(
+            RuleId::Pgm525
+                .check(&[add_column("orders", "discount_cents")], &ctx)
+                .is_empty()
+        );
+
+        let empty = Catalog::new();
+        let listed = config(&["orders"]);
+        lint_ctx!(ctx, &empty, &after, "migrations/V002.sql", config: &listed);
+        assert!(
+            RuleId::Pgm525
+                .check(&[add_column("orders", "discount_cents")], &ctx)
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,64 @@
+//! PGM526 — Table or schema created without a GRANT to the application roles
+//!
+//! For databases where the application roles do not own the schema and
+//! every object needs an explicit grant. The pipeline records the tables and
+//! schemas the changed units create and the grants they issue, and after the
+//! last unit reports objects that no grant reaches for one of the roles in
+//! `[rules.pgm526] roles` (see [`NewGrants`](crate::rules::NewGrants)). A
+//! grant in a sibling changed migration counts. The per-unit check below
+//! never fires. The rule is opt-in.
+
+use crate::parser::ir::{IrNode, Located};
+use crate::rules::{Finding, LintContext, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str =
+    "Table or schema created without a GRANT to the application roles";
+
+pub(super) const EXPLAIN: &str = "PGM526 — Table or schema created without a GRANT to the application roles\n\
+         \n\
+         What it detects:\n\
+         A table or schema created by one of the changed migrations that no\n\
+         GRANT in the change reaches for one of the roles listed in\n\
+         [rules.pgm526] roles. With no roles listed, a grant to any role is\n\
+         enough. It is reported at the CREATE once every changed migration\n\
+         has been processed.\n\
+         \n\
+         Why it matters:\n\
+         When the application connects as a role that does not own the\n\
+         schema, every new object needs an explicit grant. Without one, the\n\
+         first query against the new table fails with 'permission denied'\n\
+         the moment the migration is deployed, and the outage lasts until\n\
+         someone runs the GRANT by hand.\n\
+         \n\
+         Grants count the way PostgreSQL applies them: GRANT ... ON ALL\n\
+         TABLES IN SCHEMA covers tables created before it, ALTER DEFAULT\n\
+         PRIVILEGES covers tables created after it, and grants to PUBLIC\n\
+         cover every role. Default privileges set by older migrations are\n\
+         not seen; suppress the finding or leave the rule off if you rely\n\
+         on them. Temporary tables and partitions are not checked.\n\
+         \n\
+         This rule is off by default. Enable it with:\n\
+           [rules]\n\
+           enabled = [\"PGM526\"]\n\
+         \n\
+           [rules.pgm526]\n\
+           roles = [\"app_rw\", \"app_ro\"]\n\
+         \n\
+         Example (flagged):\n\
+           CREATE TABLE invoices (id bigint PRIMARY KEY);\n\
+         \n\
+         Fix:\n\
+           CREATE TABLE invoices (id bigint PRIMARY KEY);\n\
+           GRANT SELECT, INSERT, UPDATE, DELETE ON invoices TO app_rw;\n\
+           GRANT SELECT ON invoices TO app_ro;";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Major;
+
+pub(super) fn check(
+    _rule: impl Rule,
+    _statements: &[Located<IrNode>],
+    _ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    // Reported by the pipeline after the last unit.
+    Vec::new()
+}
@@ -0,0 +1,178 @@
+//! PGM527 — Object created outside the configured service schema
+//!
+//! For services that keep all their objects in one schema, declared as
+//! `[schemas] required`. Detects `CREATE TABLE` and `CREATE SEQUENCE` whose
+//! name is unqualified and resolves to another schema, usually `public`, and
+//! objects created explicitly in `public`. The common cause is a Liquibase
+//! changeset without `schemaName`. Other explicitly named schemas are left
+//! alone: writing the schema out is a deliberate choice. The rule is opt-in
+//! and silent until a schema is configured.
+
+use crate::parser::ir::{IrNode, Located, QualifiedName, TablePersistence};
+use crate::rules::{Finding, LintContext, Rule, Severity};
+
+pub(super) const DESCRIPTION: &str = "Object created outside the configured service schema";
+
+pub(super) const EXPLAIN: &str = "PGM527 — Object created outside the configured service schema\n\
+         \n\
+         What it detects:\n\
+         CREATE TABLE or CREATE SEQUENCE with an unqualified name that\n\
+         resolves to a schema other than [schemas] required, or with a name\n\
+         explicitly in public. Unqualified names resolve to the default\n\
+         schema of the migration's path (migrations.default_schema or the\n\
+         path's own default_schema). Temporary tables are not checked.\n\
+         \n\
+         Why it matters:\n\
+         A service that owns one schema expects every object it creates to\n\
+         land there. An unqualified name goes wherever the search_path\n\
+         points, which is public unless the connection says otherwise. A\n\
+         Liquibase changeset that forgets schemaName does the same. The\n\
+         table is created, the migration succeeds, and the application\n\
+         queries the service schema and does not find it; or it finds an\n\
+         older table of the same name there.\n\
+         \n\
+         This rule is off by default and needs a schema. Enable it with:\n\
+           [rules]\n\
+           enabled = [\"PGM527\"]\n\
+         \n\
+           [schemas]\n\
+           required = \"billing\"\n\
+         \n\
+         Example (flagged):\n\
+           CREATE TABLE invoices (id bigint PRIMARY KEY);\n\
+         \n\
+         Fix:\n\
+           CREATE TABLE billing.invoices (id bigint PRIMARY KEY);";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Major;
+
+/// The schema `name` is created in, when that is not `required`.
+fn misplaced_schema<'n>(
+    name: &'n QualifiedName,
+    required: &str,
+    ctx: &'n LintContext<'_>,
+) -> Option<&'n str> {
+    let explicit = name.schema.is_some() && !name.schema_is_default();
+    let schema = name
+        .schema
+        .as_deref()
+        .unwrap_or_else(|| ctx.config.migrations.default_schema_for(ctx.file));
+    if schema == required || (explicit && schema != "public") {
+        return None;
+    }
+    Some(schema)
+}
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    let Some(required) = ctx.config.schemas.required.as_deref() else {
+        return Vec::new();
+    };
+    let mut findings = Vec::new();
+    for stmt in statements {
+        let (kind, name, is_table) = match &stmt.node {
+            IrNode::CreateTable(ct) if ct.persistence != TablePersistence::Temporary => {
+                ("Table", &ct.name, true)
+            }
+            IrNode::CreateSequence { name, .. } => ("Sequence", name, false),
+            _ => continue,
+        };
+        let Some(schema) = misplaced_schema(name, required, ctx) else {
+            continue;
+        };
+
+        let mut finding = rule
+            .make_finding(
+                format!(
+                    "{kind} '{display}' is created in schema '{schema}', not in the service \
+                     schema '{required}'. Qualify the name as '{required}.{name}'.",
+                    display = name.display_name(),
+                    name = name.name,
+                ),
+                ctx.file,
+                &stmt.span,
+            )
+            .with_param("schema", schema);
+        if is_table {
+            finding = finding.with_table(name);
+        }
+        findings.push(finding);
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::config::Config;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    fn config(required: &str) -> Config {
+        let mut config = Config::default();
+        config.schemas.required = Some(required.to_string());
+        config
+    }
+
+    fn create_table(name: QualifiedName) -> Located<IrNode> {
+        located(IrNode::CreateTable(CreateTable::test(name)))
+    }
+
+    fn defaulted(name: &str) -> QualifiedName {
+        let mut name = QualifiedName::unqualified(name);
+        name.set_default_schema("public");
+        name
+    }
+
+    #[test]
+    fn test_unqualified_and_public_objects_fire() {
+        let before = CatalogBuilder::new().build();
+        let after = before.clone();
+        let config = config("billing");
+        lint_ctx!(ctx, &before, &after, "migrations/V002.sql", config: &config);
+
+        let stmts = vec![
+            create_table(defaulted("invoices")),
+            create_table(QualifiedName::qualified("public", "payments")),
+            located(IrNode::CreateSequence {
+                name: defaulted("invoice_numbers"),
+                if_not_exists: false,
+                owned_by: None,
+            }),
+        ];
+        let findings = RuleId::Pgm527.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_service_or_other_explicit_schema_no_finding() {
+        let before = CatalogBuilder::new().build();
+        let after = before.clone();
+        let config = config("billing");
+        lint_ctx!(ctx, &before, &after, "migrations/V002.sql", config: &config);
+
+        let mut temp = CreateTable::test(defaulted("scratch"));
+        temp.persistence = TablePersistence::Temporary;
+        let stmts = vec![
+            create_table(QualifiedName::qualified("billing", "invoices")),
+            create_table(QualifiedName::qualified("reporting", "invoice_totals")),
+            located(IrNode::CreateTable(temp)),
+        ];
+        assert!(RuleId::Pgm527.check(&stmts, &ctx).is_empty());
+    }
+
+    #[test]
+    fn test_no_required_schema_no_finding() {
+        let before = CatalogBuilder::new().build();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/V002.sql");
+
+        let stmts = vec![create_table(defaulted("invoices"))];
+        assert!(RuleId::Pgm527.check(&stmts, &ctx).is_empty());
+    }
+}
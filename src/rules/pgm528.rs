@@ -0,0 +1,288 @@
+//! PGM528 — Enum-like CHECK constraint instead of an enum type or lookup table
+//!
+//! Detects CHECK constraints that pin a column to a fixed list of literals,
+//! `status IN ('draft', 'sent', 'paid')`, when the list is long (at least
+//! `[rules.pgm528] min_values` values) or repeats the value set a CHECK on
+//! another table already allows. The data modeling guidelines prefer an enum
+//! type or a lookup table for such domains. The parser extracts the lists
+//! into [`CheckExprDeps::literal_lists`](crate::parser::ir::CheckExprDeps),
+//! and the catalog keeps them on each CHECK so copies in earlier migrations
+//! are found. The rule is opt-in and informational.
+
+use crate::catalog::types::ConstraintState;
+use crate::config::Config;
+use crate::parser::ir::{
+    AlterTableAction, IrNode, LiteralList, Located, QualifiedName, TableConstraint,
+};
+use crate::rules::{ConfiguredRule, Finding, LintContext, Rule, RuleId, Severity};
+
+pub(super) const DESCRIPTION: &str =
+    "Enum-like CHECK constraint instead of an enum type or lookup table";
+
+pub(super) const EXPLAIN: &str = "PGM528 — Enum-like CHECK constraint instead of an enum type or lookup table\n\
+         \n\
+         What it detects:\n\
+         A CHECK constraint in CREATE TABLE or ALTER TABLE ... ADD CONSTRAINT\n\
+         that tests a column against a list of literals (col IN (...), or\n\
+         col = ANY (ARRAY[...])), when the list has at least\n\
+         [rules.pgm528] min_values values (default 5), or when a CHECK on\n\
+         another table already allows the same set of values.\n\
+         \n\
+         Why it matters:\n\
+         A long value list is a domain, and domains grow. Every new value\n\
+         means dropping and re-adding the constraint, which scans the table\n\
+         under lock unless it is re-added NOT VALID and validated separately.\n\
+         When the same list is copied onto several tables, each copy has to\n\
+         be changed in step, and the copies drift. An enum type (values are\n\
+         added with ALTER TYPE ... ADD VALUE) or a lookup table referenced by\n\
+         a foreign key keeps the list in one place.\n\
+         \n\
+         This rule is off by default. Enable it with:\n\
+           [rules]\n\
+           enabled = [\"PGM528\"]\n\
+         \n\
+           [rules.pgm528]\n\
+           min_values = 5\n\
+         \n\
+         Example (flagged):\n\
+           CREATE TABLE invoices (\n\
+             id bigint PRIMARY KEY,\n\
+             status text NOT NULL\n\
+               CHECK (status IN ('draft', 'sent', 'paid', 'void', 'overdue'))\n\
+           );\n\
+         \n\
+         Fix:\n\
+           CREATE TYPE invoice_status AS ENUM\n\
+             ('draft', 'sent', 'paid', 'void', 'overdue');\n\
+           CREATE TABLE invoices (\n\
+             id bigint PRIMARY KEY,\n\
+             status invoice_status NOT NULL\n\
+           );";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::Info;
+
+/// PGM528 with `[rules.pgm528]` resolved.
+pub(super) struct Pgm528 {
+    min_values: usize,
+}
+
+/// A literal list that an earlier CHECK already allows.
+struct Earlier<'a> {
+    /// Catalog key of the table.
+    table: String,
+    /// User-facing table name.
+    display: String,
+    column: &'a str,
+    values: Vec<&'a str>,
+}
+
+impl ConfiguredRule for Pgm528 {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            min_values: config.rules.pgm528.min_values,
+        }
+    }
+
+    fn check(
+        &self,
+        rule: RuleId,
+        statements: &[Located<IrNode>],
+        ctx: &LintContext<'_>,
+    ) -> Vec<Finding> {
+        // Lists in the catalog before this unit, then those of earlier
+        // statements in the unit, so only the later copy is reported.
+        let mut earlier: Vec<Earlier<'_>> = Vec::new();
+        let mut tables: Vec<_> = ctx.catalog_before.tables().collect();
+        tables.sort_by(|a, b| a.name.cmp(&b.name));
+        for table in tables {
+            for constraint in &table.constraints {
+                if let ConstraintState::Check { literal_lists, .. } = constraint {
+                    earlier.extend(literal_lists.iter().map(|list| Earlier {
+                        table: table.name.clone(),
+                        display: table.display_name.clone(),
+                        column: &list.column,
+                        values: list.value_set(),
+                    }));
+                }
+            }
+        }
+
+        let mut findings = Vec::new();
+        for stmt in statements {
+            let (table, constraints): (&QualifiedName, Vec<&TableConstraint>) = match &stmt.node {
+                IrNode::CreateTable(ct) => (&ct.name, ct.constraints.iter().collect()),
+                IrNode::AlterTable(at) => (
+                    &at.name,
+                    at.actions
+                        .iter()
+                        .filter_map(|action| match action {
+                            AlterTableAction::AddConstraint(c) => Some(c),
+                            _ => None,
+                        })
+                        .collect(),
+                ),
+                _ => continue,
+            };
+            let lists = constraints.into_iter().flat_map(|c| match c {
+                TableConstraint::Check { deps, .. } => deps.literal_lists.as_slice(),
+                _ => &[],
+            });
+
+            for list in lists {
+                if let Some(finding) = self.check_list(rule, table, list, &earlier, stmt, ctx) {
+                    findings.push(finding);
+                }
+                earlier.push(Earlier {
+                    table: table.catalog_key().to_string(),
+                    display: table.display_name(),
+                    column: &list.column,
+                    values: list.value_set(),
+                });
+            }
+        }
+        findings
+    }
+}
+
+impl Pgm528 {
+    fn check_list(
+        &self,
+        rule: RuleId,
+        table: &QualifiedName,
+        list: &LiteralList,
+        earlier: &[Earlier<'_>],
+        stmt: &Located<IrNode>,
+        ctx: &LintContext<'_>,
+    ) -> Option<Finding> {
+        let values = list.value_set();
+        let copy = earlier
+            .iter()
+            .find(|e| e.table != table.catalog_key() && e.values == values);
+        let column = format!("{}.{}", table.display_name(), list.column);
+
+        let message = match copy {
+            Some(copy) => format!(
+                "CHECK constraint on '{column}' repeats the {} values allowed on \
+                 '{}.{}'. Keep the list in one enum type or lookup table instead of \
+                 updating every copy.",
+                values.len(),
+                copy.display,
+                copy.column,
+            ),
+            None if values.len() >= self.min_values => format!(
+                "CHECK constraint on '{column}' lists {} allowed values. Lists that \
+                 long tend to grow; consider an enum type or a lookup table with a \
+                 foreign key.",
+                values.len(),
+            ),
+            None => return None,
+        };
+        Some(
+            rule.make_finding(message, ctx.file, &stmt.span)
+                .with_table(table)
+                .with_param("column", list.column.clone())
+                .with_literal_param("values", &values),
+        )
+    }
+}
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {
+    Pgm528::from_config(ctx.config).check(rule.id(), statements, ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rules::test_helpers::{lint_ctx, located};
+
+    fn in_list(column: &str, values: &[&str]) -> TableConstraint {
+        let quoted: Vec<String> = values.iter().map(|v| format!("'{v}'")).collect();
+        TableConstraint::Check {
+            name: None,
+            expression: format!("{column} IN ({})", quoted.join(", ")),
+            not_valid: false,
+            deps: CheckExprDeps {
+                columns: vec![column.to_string()],
+                literal_lists: vec![LiteralList {
+                    column: column.to_string(),
+                    values: values.iter().map(|v| v.to_string()).collect(),
+                }],
+                ..CheckExprDeps::default()
+            },
+        }
+    }
+
+    fn create_table(name: &str, constraints: Vec<TableConstraint>) -> Located<IrNode> {
+        let mut ct = CreateTable::test(QualifiedName::unqualified(name));
+        ct.constraints = constraints;
+        located(IrNode::CreateTable(ct))
+    }
+
+    const STATUSES: &[&str] = &["draft", "sent", "paid", "void", "overdue"];
+
+    #[test]
+    fn test_long_list_and_copy_fire() {
+        let before = CatalogBuilder::new()
+            .table("orders", |t| {
+                t.column("id", "bigint", false)
+                    .column("currency", "text", false)
+                    .check_in_list(None, "currency", &["EUR", "USD", "SEK"]);
+            })
+            .build();
+        let after = before.clone();
+        let config = Config::default();
+        lint_ctx!(ctx, &before, &after, "migrations/V002.sql", config: &config);
+
+        let stmts = vec![
+            create_table("invoices", vec![in_list("status", STATUSES)]),
+            located(IrNode::AlterTable(AlterTable {
+                name: QualifiedName::unqualified("refunds"),
+                actions: vec![AlterTableAction::AddConstraint(in_list(
+                    "currency",
+                    &["SEK", "EUR", "USD"],
+                ))],
+            })),
+        ];
+        let findings = RuleId::Pgm528.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_short_unique_list_no_finding() {
+        let before = Catalog::new();
+        let after = before.clone();
+        let config = Config::default();
+        lint_ctx!(ctx, &before, &after, "migrations/V002.sql", config: &config);
+
+        let stmts = vec![
+            create_table("invoices", vec![in_list("kind", &["credit", "debit"])]),
+            create_table("payments", vec![in_list("status", &["pending", "settled"])]),
+        ];
+        assert!(RuleId::Pgm528.check(&stmts, &ctx).is_empty());
+    }
+
+    #[test]
+    fn test_copy_within_unit_reports_later_table_only() {
+        let before = Catalog::new();
+        let after = before.clone();
+        let mut config = Config::default();
+        config.rules.pgm528.min_values = 10;
+        lint_ctx!(ctx, &before, &after, "migrations/V002.sql", config: &config);
+
+        let stmts = vec![
+            create_table("invoices", vec![in_list("status", STATUSES)]),
+            create_table("credit_notes", vec![in_list("status", STATUSES)]),
+        ];
+        let findings = RuleId::Pgm528.check(&stmts, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("'credit_notes.status'"));
+        assert!(findings[0].message.contains("'invoices.status'"));
+    }
+}
@@ -0,0 +1,383 @@
+//! Rule registry
+//!
+//! The [`RuleRegistry`] holds every rule implementation available to a run,
+//! with options resolved from the configuration. Rule IDs in the
+//! configuration (`rules.disabled`, `rules.enabled`,
+//! `meta.pgm901.exempt_rules`, `messages.templates`) are kept as
+//! [`RuleRef`]s when the file is parsed and only resolved once the registry
+//! is assembled, so IDs from a rule pack that is not loaded are reported as
+//! such instead of as unknown rules.
+//!
+//! A rule pack is a crate outside this one that provides rules under its own
+//! ID namespace (e.g. `ORG001`). Each rule implements [`Rule`] and returns a
+//! [`RuleId::Custom`] from [`Rule::id`]; the pack registers it with
+//! [`RuleRegistry::register`] and hands the registry to
+//! [`LintPipeline::with_registry`](crate::LintPipeline::with_registry). From
+//! there its findings go through the same configuration, suppressions, and
+//! reporters as the built-in rules.
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::config::{Config, ConfigError};
+use crate::rules::{Rule, RuleId};
+
+/// Namespace of the built-in rules (`PGMxxx`).
+const BUILTIN_NAMESPACE: &str = "PGM";
+
+/// A rule ID as written in the configuration, before it is resolved against
+/// a [`RuleRegistry`].
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize, JsonSchema,
+)]
+#[serde(transparent)]
+pub struct RuleRef(String);
+
+impl RuleRef {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The built-in rule this refers to, if any.
+    pub fn builtin(&self) -> Option<RuleId> {
+        self.0.parse().ok()
+    }
+
+    /// The leading letters of the ID (`"PGM"` for `PGM001`), or `None` if
+    /// the ID is not letters followed by digits.
+    pub(crate) fn namespace(&self) -> Option<&str> {
+        let split = self.0.find(|c: char| !c.is_ascii_alphabetic())?;
+        let (namespace, number) = self.0.split_at(split);
+        (!namespace.is_empty() && number.chars().all(|c| c.is_ascii_digit())).then_some(namespace)
+    }
+}
+
+impl From<RuleId> for RuleRef {
+    fn from(id: RuleId) -> Self {
+        Self(id.as_str().to_string())
+    }
+}
+
+impl Borrow<str> for RuleRef {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<RuleId> for RuleRef {
+    fn eq(&self, other: &RuleId) -> bool {
+        self.0 == other.as_str()
+    }
+}
+
+impl std::fmt::Display for RuleRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Why [`RuleRegistry::register`] refused a rule.
+#[derive(Debug, Error, PartialEq)]
+pub enum RegisterError {
+    #[error("rule '{0}' is built in; only RuleId::Custom rules can be registered")]
+    Builtin(RuleId),
+
+    #[error(
+        "invalid rule ID '{0}': expected letters followed by digits, outside the PGM namespace"
+    )]
+    InvalidId(&'static str),
+
+    #[error("rule '{0}' is already registered")]
+    Duplicate(&'static str),
+}
+
+/// Every lint rule available to a run, built with its options resolved from
+/// the configuration (see [`RuleId::instantiate`]), and the rules registered
+/// by rule packs.
+pub struct RuleRegistry {
+    rules: HashMap<RuleId, Box<dyn Rule>>,
+}
+
+impl RuleRegistry {
+    /// Build every lint rule with its options resolved from `config`.
+    pub fn assemble(config: &Config) -> Self {
+        Self {
+            rules: RuleId::lint_rules()
+                .map(|id| (id, id.instantiate(config)))
+                .collect(),
+        }
+    }
+
+    /// Rebuild the built-in rules for `config`, keeping registered rule pack
+    /// rules.
+    pub fn reassemble(&mut self, config: &Config) {
+        for id in RuleId::lint_rules() {
+            self.rules.insert(id, id.instantiate(config));
+        }
+    }
+
+    /// Add a rule from a rule pack. Its [`Rule::id`] must be a
+    /// [`RuleId::Custom`] whose ID is letters followed by digits, outside
+    /// the `PGM` namespace, and not registered yet.
+    pub fn register(&mut self, rule: Box<dyn Rule>) -> Result<(), RegisterError> {
+        let id = rule.id();
+        let RuleId::Custom(meta) = id else {
+            return Err(RegisterError::Builtin(id));
+        };
+        let namespace = RuleRef::new(meta.id).namespace().map(str::to_string);
+        if namespace.is_none_or(|ns| ns.eq_ignore_ascii_case(BUILTIN_NAMESPACE)) {
+            return Err(RegisterError::InvalidId(meta.id));
+        }
+        if self.rules.contains_key(&id) {
+            return Err(RegisterError::Duplicate(meta.id));
+        }
+        self.rules.insert(id, rule);
+        Ok(())
+    }
+
+    /// The implementation of `id`, if it is a registered lint rule.
+    pub fn get(&self, id: &RuleId) -> Option<&dyn Rule> {
+        self.rules.get(id).map(Box::as_ref)
+    }
+
+    /// Whether any of `rules` implements [`Rule::check_change`].
+    pub fn has_change_checks(&self, rules: &[RuleId]) -> bool {
+        rules
+            .iter()
+            .filter_map(|id| self.get(id))
+            .any(|rule| rule.checks_change())
+    }
+
+    /// Every registered lint rule, built-in rules first, in ID order.
+    pub fn lint_rules(&self) -> Vec<RuleId> {
+        let mut ids: Vec<RuleId> = self.rules.keys().copied().collect();
+        ids.sort();
+        ids
+    }
+
+    /// Rule ID namespaces provided by the registered rules.
+    pub fn namespaces(&self) -> Vec<&'static str> {
+        let mut namespaces = vec![BUILTIN_NAMESPACE];
+        for id in self.lint_rules() {
+            if let RuleId::Custom(meta) = id
+                && !namespaces.contains(&meta.namespace())
+            {
+                namespaces.push(meta.namespace());
+            }
+        }
+        namespaces
+    }
+
+    /// Resolve a configured rule ID. `field` names the setting it came from
+    /// and is only used in the error.
+    ///
+    /// An ID in a namespace no registered rule provides (e.g. `ORG001`)
+    /// means a rule pack is missing, and is reported differently from an ID
+    /// that no rule pack could provide.
+    pub fn resolve(&self, rule: &RuleRef, field: &str) -> Result<RuleId, ConfigError> {
+        if let Some(id) = rule.builtin() {
+            return Ok(id);
+        }
+        if let Some(id) = self
+            .rules
+            .keys()
+            .find(|id| matches!(id, RuleId::Custom(meta) if meta.id == rule.as_str()))
+        {
+            return Ok(*id);
+        }
+        match rule.namespace() {
+            Some(namespace) if !self.namespaces().contains(&namespace) => {
+                Err(ConfigError::Validation(format!(
+                    "rule '{rule}' in {field} is not loaded: no registered rule pack provides the '{namespace}' namespace"
+                )))
+            }
+            _ => Err(ConfigError::Validation(format!(
+                "unknown rule '{rule}' in {field}. Run --explain with a rule ID, or see docs/rules.md for the full list"
+            ))),
+        }
+    }
+
+    /// Check every rule ID in `config` against the registry.
+    pub fn validate_config(&self, config: &Config) -> Result<(), ConfigError> {
+        for rule in &config.rules.disabled {
+            self.resolve(rule, "rules.disabled")?;
+        }
+        for rule in &config.rules.enabled {
+            let id = self.resolve(rule, "rules.enabled")?;
+            if !id.is_opt_in() {
+                return Err(ConfigError::Validation(format!(
+                    "rule '{}' is on by default and cannot be listed in rules.enabled",
+                    id
+                )));
+            }
+        }
+        for rule in &config.meta.pgm901.exempt_rules {
+            let id = self.resolve(rule, "meta.pgm901.exempt_rules")?;
+            if id.is_meta() {
+                return Err(ConfigError::Validation(format!(
+                    "meta rule '{}' cannot be listed in meta.pgm901.exempt_rules",
+                    id
+                )));
+            }
+        }
+        for o in &config.rules.overrides {
+            let id = self.resolve(&o.rule, "rules.overrides")?;
+            if id.is_meta() {
+                return Err(ConfigError::Validation(format!(
+                    "meta rule '{}' has no findings and cannot have an override",
+                    id
+                )));
+            }
+            if o.tables.is_empty() && o.exclude_tables.is_empty() {
+                return Err(ConfigError::Validation(format!(
+                    "override for rule '{}' must set tables or exclude_tables",
+                    id
+                )));
+            }
+        }
+        for rule in config.messages.templates.keys() {
+            let id = self.resolve(rule, "messages.templates")?;
+            if id.is_meta() {
+                return Err(ConfigError::Validation(format!(
+                    "meta rule '{}' has no findings and cannot have a message template",
+                    id
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(toml: &str) -> Result<(), String> {
+        let config: Config = toml::from_str(toml).unwrap();
+        RuleRegistry::assemble(&config)
+            .validate_config(&config)
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_known_rules_resolve() {
+        assert!(
+            validate(
+                r#"
+[rules]
+disabled = ["PGM006", "PGM101"]
+enabled = ["PGM514"]
+
+[meta.pgm901]
+exempt_rules = ["PGM201"]
+
+[messages.templates]
+PGM001 = "{message}"
+"#
+            )
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_unknown_rule_distinguished_from_unloaded_pack() {
+        let err = validate("[rules]\ndisabled = [\"PGM999\"]").unwrap_err();
+        assert!(
+            err.contains("unknown rule 'PGM999' in rules.disabled"),
+            "{err}"
+        );
+
+        let err = validate("[rules]\ndisabled = [\"not-a-rule\"]").unwrap_err();
+        assert!(err.contains("unknown rule 'not-a-rule'"), "{err}");
+
+        let err = validate("[meta.pgm901]\nexempt_rules = [\"ORG001\"]").unwrap_err();
+        assert!(
+            err.contains("rule 'ORG001' in meta.pgm901.exempt_rules is not loaded"),
+            "{err}"
+        );
+        assert!(err.contains("'ORG' namespace"), "{err}");
+
+        let err = validate("[messages.templates]\nACME12 = \"{message}\"").unwrap_err();
+        assert!(err.contains("is not loaded"), "{err}");
+    }
+
+    #[test]
+    fn test_overrides_validated() {
+        assert!(
+            validate("[[rules.overrides]]\nrule = \"PGM302\"\nexclude_tables = [\"t\"]").is_ok()
+        );
+
+        let err = validate("[[rules.overrides]]\nrule = \"PGM302\"").unwrap_err();
+        assert!(err.contains("must set tables or exclude_tables"), "{err}");
+
+        let err = validate("[[rules.overrides]]\nrule = \"PGM901\"\ntables = [\"t\"]").unwrap_err();
+        assert!(err.contains("cannot have an override"), "{err}");
+
+        let err = validate("[[rules.overrides]]\nrule = \"PGM999\"\ntables = [\"t\"]").unwrap_err();
+        assert!(err.contains("in rules.overrides"), "{err}");
+    }
+
+    #[test]
+    fn test_registry_lookup() {
+        let registry = RuleRegistry::assemble(&Config::default());
+        assert!(registry.get(&RuleId::Pgm001).is_some());
+        assert!(registry.get(&RuleId::Pgm901).is_none());
+        assert_eq!(
+            registry
+                .resolve(&RuleRef::from(RuleId::Pgm501), "rules.disabled")
+                .unwrap(),
+            RuleId::Pgm501
+        );
+    }
+
+    #[test]
+    fn test_rule_pack_registration() {
+        use crate::rules::CustomRuleMeta;
+        use crate::rules::test_helpers::{TENANT_ID, TenantIdRule};
+
+        static BAD_ID: CustomRuleMeta = CustomRuleMeta {
+            id: "PGM999",
+            ..TENANT_ID
+        };
+        let config: Config = toml::from_str("[rules]\ndisabled = [\"ORG001\"]").unwrap();
+        let mut registry = RuleRegistry::assemble(&config);
+        assert!(registry.validate_config(&config).is_err());
+
+        registry
+            .register(Box::new(TenantIdRule(&TENANT_ID)))
+            .unwrap();
+        assert!(registry.validate_config(&config).is_ok());
+        assert_eq!(
+            registry
+                .resolve(&RuleRef::new("ORG001"), "rules.disabled")
+                .unwrap(),
+            RuleId::Custom(&TENANT_ID)
+        );
+        assert_eq!(registry.namespaces(), vec!["PGM", "ORG"]);
+        assert_eq!(
+            registry.lint_rules().last(),
+            Some(&RuleId::Custom(&TENANT_ID))
+        );
+
+        assert!(matches!(
+            registry.register(Box::new(TenantIdRule(&TENANT_ID))),
+            Err(RegisterError::Duplicate("ORG001"))
+        ));
+        assert!(matches!(
+            registry.register(Box::new(TenantIdRule(&BAD_ID))),
+            Err(RegisterError::InvalidId("PGM999"))
+        ));
+        assert!(matches!(
+            registry.register(RuleId::Pgm001.instantiate(&config)),
+            Err(RegisterError::Builtin(RuleId::Pgm001))
+        ));
+    }
+}
@@ -0,0 +1,28 @@
+//! Shared helper for the rename workflow rules (PGM519, PGM520).
+//!
+//! Looks the renamed object up in the `[rename_workflow]` state file and
+//! explains why the rename is not allowed yet.
+
+use crate::parser::ir::QualifiedName;
+use crate::rename_workflow::RenamePhase;
+use crate::rules::LintContext;
+
+/// `None` if the object is in the contract phase, otherwise why it is not,
+/// for the finding message: `"its phase is 'expand'"`, `"it is not listed
+/// in the rename workflow state file"`, or `"no rename_workflow.state_file
+/// is configured"`.
+pub(super) fn outside_contract(
+    ctx: &LintContext<'_>,
+    table: &QualifiedName,
+    column: Option<&str>,
+) -> Option<String> {
+    let workflow = &ctx.config.rename_workflow;
+    if workflow.state_file.is_none() {
+        return Some("no rename_workflow.state_file is configured".to_string());
+    }
+    match workflow.state.phase(table, column) {
+        Some(RenamePhase::Contract) => None,
+        Some(phase) => Some(format!("its phase is '{}'", phase.label())),
+        None => Some("it is not listed in the rename workflow state file".to_string()),
+    }
+}
@@ -4,14 +4,75 @@ use strum_macros::{EnumIter, EnumString, IntoStaticStr};
 
 use crate::{
     Finding, IrNode, Located, Rule,
-    rules::{LintContext, severity::Severity},
+    config::Config,
+    rules::{ChangeContext, ChangedUnit, Configured, LintContext, severity::Severity},
 };
 
+/// Metadata of a rule provided by a rule pack outside this crate.
+///
+/// A rule pack declares one `static` per rule and returns
+/// [`RuleId::Custom`] pointing at it from [`Rule::id`], then registers the
+/// implementation with [`RuleRegistry::register`](super::RuleRegistry::register).
+/// Two values are the same rule when their IDs are equal.
+#[derive(Debug)]
+pub struct CustomRuleMeta {
+    /// Rule ID in the pack's namespace: letters followed by digits, e.g.
+    /// `"ORG001"`.
+    pub id: &'static str,
+    /// Short description (see [`Rule::description`]).
+    pub description: &'static str,
+    /// Text for `--explain` (see [`Rule::explain`]).
+    pub explain: &'static str,
+    pub default_severity: Severity,
+}
+
+impl CustomRuleMeta {
+    /// The leading letters of the ID (`"ORG"` for `ORG001`).
+    pub fn namespace(&self) -> &'static str {
+        let end = self
+            .id
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(self.id.len());
+        &self.id[..end]
+    }
+}
+
+impl PartialEq for CustomRuleMeta {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for CustomRuleMeta {}
+
+impl PartialOrd for CustomRuleMeta {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CustomRuleMeta {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.id.cmp(other.id)
+    }
+}
+
+impl std::hash::Hash for CustomRuleMeta {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
 /// Strongly-typed rule identifier.
 ///
 /// A flat enum covering all rule families. Match statements are exhaustive:
 /// adding a new variant forces updates in `sonarqube_meta()`, `effort_minutes()`,
 /// and everywhere else a rule ID is dispatched on.
+///
+/// Rules from rule packs are [`RuleId::Custom`]. They are not listed by
+/// [`RuleId::lint_rules`] and not parsed by `FromStr`; the
+/// [`RuleRegistry`](super::RuleRegistry) they are registered with resolves
+/// their IDs.
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter, EnumString, IntoStaticStr,
 )]
@@ -86,6 +147,45 @@ pub enum RuleId {
     /// Multiple `ALTER TABLE` statements on the same table can be combined.
     #[strum(serialize = "PGM023")]
     Pgm023,
+    /// New column backfilled and set `NOT NULL` in the same migration.
+    #[strum(serialize = "PGM024")]
+    Pgm024,
+    /// `DROP CONSTRAINT` removes a primary key or unique constraint.
+    #[strum(serialize = "PGM025")]
+    Pgm025,
+    /// `DROP CONSTRAINT` removes a foreign key.
+    #[strum(serialize = "PGM026")]
+    Pgm026,
+    /// Table or column created after an earlier changed migration already uses it.
+    #[strum(serialize = "PGM027")]
+    Pgm027,
+    /// CHECK constraint contains a subquery or calls non-immutable functions.
+    #[strum(serialize = "PGM028")]
+    Pgm028,
+    /// `serial` or identity column added to an existing table.
+    #[strum(serialize = "PGM029")]
+    Pgm029,
+    /// More than one constraint added in a single `ALTER TABLE` on an existing table.
+    #[strum(serialize = "PGM030")]
+    Pgm030,
+    /// Bulk maintenance statement on a whole tablespace, schema, or database.
+    #[strum(serialize = "PGM031")]
+    Pgm031,
+    /// Estimated lock duration of a migration unit exceeds `[lock_budget]`.
+    #[strum(serialize = "PGM032")]
+    Pgm032,
+    /// CHECK constraint or DEFAULT references a column that does not exist
+    #[strum(serialize = "PGM033")]
+    Pgm033,
+    /// Statement PostgreSQL would reject (strict replay)
+    #[strum(serialize = "PGM034")]
+    Pgm034,
+    /// `REPLICA IDENTITY FULL` on an existing table, or its replica identity index dropped.
+    #[strum(serialize = "PGM035")]
+    Pgm035,
+    /// Statement fails when applied to PostgreSQL (`--verify-against-db`)
+    #[strum(serialize = "PGM036")]
+    Pgm036,
 
     // 1xx — Type anti-patterns
     /// `timestamp` without time zone.
@@ -115,6 +215,12 @@ pub enum RuleId {
     /// Floating-point column type (`real`/`double precision`).
     #[strum(serialize = "PGM109")]
     Pgm109,
+    /// UUID stored in a `text`/`varchar` column (use `uuid`).
+    #[strum(serialize = "PGM110")]
+    Pgm110,
+    /// Column type on the configurable discouraged types list.
+    #[strum(serialize = "PGM111")]
+    Pgm111,
 
     // 2xx — Destructive operations
     /// Dropping an existing table.
@@ -132,6 +238,12 @@ pub enum RuleId {
     /// `DROP SCHEMA CASCADE`.
     #[strum(serialize = "PGM205")]
     Pgm205,
+    /// `DROP EXTENSION` that tracked columns depend on.
+    #[strum(serialize = "PGM206")]
+    Pgm206,
+    /// Column dropped and re-added with a different type in the same change.
+    #[strum(serialize = "PGM207")]
+    Pgm207,
 
     // 3xx — DML in migrations
     /// `INSERT INTO` existing table in migration.
@@ -183,11 +295,79 @@ pub enum RuleId {
     /// Mixed-case identifier or reserved word requires double-quoting.
     #[strum(serialize = "PGM509")]
     Pgm509,
+    /// Boolean `NOT NULL` column without default on new table.
+    #[strum(serialize = "PGM510")]
+    Pgm510,
+    /// DDL on a table in a schema owned by another team.
+    #[strum(serialize = "PGM511")]
+    Pgm511,
+    /// RENAME INDEX on existing index.
+    #[strum(serialize = "PGM512")]
+    Pgm512,
+    /// Autovacuum disabled on existing table.
+    #[strum(serialize = "PGM513")]
+    Pgm513,
+    /// Permission or ownership change in a schema migration (opt-in).
+    #[strum(serialize = "PGM514")]
+    Pgm514,
+    /// Table created or given a column, modified, and renamed in one unit.
+    #[strum(serialize = "PGM515")]
+    Pgm515,
+    /// `CREATE TABLE ... INHERITS` instead of declarative partitioning.
+    #[strum(serialize = "PGM516")]
+    Pgm516,
+    /// Operation does not follow a required pattern from `[[patterns]]`.
+    #[strum(serialize = "PGM517")]
+    Pgm517,
+    /// Temporary table without `ON COMMIT DROP` in a transactional unit.
+    #[strum(serialize = "PGM518")]
+    Pgm518,
+    /// RENAME COLUMN outside the contract phase of the rename workflow (opt-in).
+    #[strum(serialize = "PGM519")]
+    Pgm519,
+    /// RENAME TABLE outside the contract phase of the rename workflow (opt-in).
+    #[strum(serialize = "PGM520")]
+    Pgm520,
+    /// Column default calls `nextval` on a missing or cross-schema sequence.
+    #[strum(serialize = "PGM521")]
+    Pgm521,
+    /// Table created in the change with foreign keys no changed migration indexes.
+    #[strum(serialize = "PGM522")]
+    Pgm522,
+    /// Sequence not owned by a column, or left behind by DROP TABLE.
+    #[strum(serialize = "PGM523")]
+    Pgm523,
+    /// Publication or subscription DDL in a schema migration (opt-in).
+    #[strum(serialize = "PGM524")]
+    Pgm524,
+    /// Column added to a table listed as read with `SELECT *` or positional
+    /// `INSERT` (opt-in).
+    #[strum(serialize = "PGM525")]
+    Pgm525,
+    /// Table or schema created in the change without a GRANT to the
+    /// configured application roles (opt-in).
+    #[strum(serialize = "PGM526")]
+    Pgm526,
+    /// Table or sequence created outside the `[schemas] required` service
+    /// schema (opt-in).
+    #[strum(serialize = "PGM527")]
+    Pgm527,
+    /// Enum-like CHECK constraint instead of an enum type or lookup table
+    #[strum(serialize = "PGM528")]
+    Pgm528,
 
     // 9xx — Meta-behavior
     /// Down-migration severity capping (not a standalone rule).
     #[strum(serialize = "PGM901")]
     Pgm901,
+    /// Summary of the decisions that assumed unknown tables exist
+    /// (`catalog.assume_unknown_tables_exist`; not a standalone rule).
+    #[strum(serialize = "PGM902")]
+    Pgm902,
+
+    /// A rule registered by a rule pack.
+    #[strum(disabled)]
+    Custom(&'static CustomRuleMeta),
 }
 
 impl RuleId {
@@ -196,18 +376,83 @@ impl RuleId {
     /// Delegates to the [`IntoStaticStr`] derive which maps each variant to
     /// its `#[strum(serialize = "…")]` string.
     pub fn as_str(&self) -> &'static str {
-        self.into()
+        match self {
+            Self::Custom(meta) => meta.id,
+            _ => self.into(),
+        }
     }
 
     /// Whether this is a meta-behavior rule (not a standalone lint rule).
     pub fn is_meta(&self) -> bool {
-        matches!(self, Self::Pgm901)
+        matches!(self, Self::Pgm901 | Self::Pgm902)
+    }
+
+    /// Whether this rule is off unless listed in `[rules] enabled`.
+    ///
+    /// Opt-in rules enforce a team policy rather than a migration hazard.
+    pub fn is_opt_in(&self) -> bool {
+        matches!(
+            self,
+            Self::Pgm514
+                | Self::Pgm519
+                | Self::Pgm520
+                | Self::Pgm524
+                | Self::Pgm525
+                | Self::Pgm526
+                | Self::Pgm527
+                | Self::Pgm528
+        )
+    }
+
+    /// Whether this rule only reports against a live database
+    /// (`--verify-against-db`). Static fixtures cannot trigger it.
+    pub fn needs_database(&self) -> bool {
+        matches!(self, Self::Pgm036)
     }
 
     /// Iterator over all non-meta rule IDs (rules that produce findings).
     pub fn lint_rules() -> impl Iterator<Item = Self> {
         Self::iter().filter(|r| !r.is_meta())
     }
+
+    /// The rule's family, named by its hundreds digit (e.g. `"5xx"` for
+    /// PGM501). Rule pack rules form one family per namespace (`"ORG"`).
+    pub fn family_prefix(&self) -> &'static str {
+        if let Self::Custom(meta) = self {
+            return meta.namespace();
+        }
+        const PREFIXES: [&str; 10] = [
+            "0xx", "1xx", "2xx", "3xx", "4xx", "5xx", "6xx", "7xx", "8xx", "9xx",
+        ];
+        PREFIXES[usize::from(self.as_str().as_bytes()[3] - b'0')]
+    }
+
+    /// Build this rule's implementation, with its options resolved from
+    /// `config` once.
+    ///
+    /// Rules without options are the `RuleId` itself. Rules with options
+    /// (PGM107, PGM110, PGM111, PGM201, PGM202, PGM525, PGM528) are [`ConfiguredRule`](super::ConfiguredRule)
+    /// structs, so a long run does not re-derive them for every unit.
+    pub fn instantiate(self, config: &Config) -> Box<dyn Rule> {
+        match self {
+            Self::Pgm107 => Configured::<super::pgm107::Pgm107>::boxed(self, config),
+            Self::Pgm110 => Configured::<super::pgm110::Pgm110>::boxed(self, config),
+            Self::Pgm111 => Configured::<super::pgm111::Pgm111>::boxed(self, config),
+            Self::Pgm201 => Configured::<super::pgm201::Pgm201>::boxed(self, config),
+            Self::Pgm202 => Configured::<super::pgm202::Pgm202>::boxed(self, config),
+            Self::Pgm525 => Configured::<super::pgm525::Pgm525>::boxed(self, config),
+            Self::Pgm528 => Configured::<super::pgm528::Pgm528>::boxed(self, config),
+            _ => Box::new(self),
+        }
+    }
+
+    /// Family prefixes that contain at least one lint rule, in order.
+    pub fn lint_families() -> Vec<&'static str> {
+        let mut families: Vec<&'static str> =
+            Self::lint_rules().map(|r| r.family_prefix()).collect();
+        families.dedup();
+        families
+    }
 }
 
 impl std::fmt::Display for RuleId {
@@ -232,10 +477,34 @@ impl<'de> serde::Deserialize<'de> for RuleId {
 // `FromStr` is derived via `EnumString` — strum generates a match from
 // `#[strum(serialize = "…")]` attributes. `Err` type is `strum::ParseError`.
 
+const PGM902_EXPLAIN: &str = "PGM902 — Findings rely on tables assumed to exist\n\
+         \n\
+         What it reports:\n\
+         With catalog.assume_unknown_tables_exist = true, rules that only\n\
+         fire on existing tables treat a table the replayed catalog does not\n\
+         have as existing, instead of as one created outside the history.\n\
+         PGM902 reports once per run how many of those decisions were made\n\
+         (one per unknown table per unit) and which tables they were about.\n\
+         \n\
+         Why it matters:\n\
+         When old migrations were squashed or deleted, ALTER TABLE and\n\
+         CREATE INDEX on production tables would otherwise go unchecked.\n\
+         The assumption can also be wrong, e.g. for a table that a deleted\n\
+         migration dropped, so findings on the listed tables deserve a\n\
+         second look. Rules that need the table's columns or indexes still\n\
+         skip tables the catalog does not have.\n\
+         \n\
+         This finding cannot be suppressed. Seed the catalog with\n\
+         --baseline-schema or --load-catalog to make the assumption\n\
+         unnecessary.";
+
 /// Generate the `impl Rule for RuleId` by dispatching each variant to
 /// its module's `DEFAULT_SEVERITY`, `DESCRIPTION`, `EXPLAIN`, and `check`.
+/// The few rules that look at the whole change (PGM207) also have a
+/// `check_change`, dispatched by hand.
 ///
-/// PGM901 is a meta-rule with no module — it's handled inline.
+/// PGM901 and PGM902 are meta-rules with no module — they're handled
+/// inline, as is the metadata of rule pack rules.
 macro_rules! dispatch_rules {
     ( $( $variant:ident => $module:ident ),+ $(,)? ) => {
         impl Rule for RuleId {
@@ -246,7 +515,8 @@ macro_rules! dispatch_rules {
             fn default_severity(&self) -> Severity {
                 match self {
                     $( Self::$variant => super::$module::DEFAULT_SEVERITY, )+
-                    Self::Pgm901 => Severity::Info,
+                    Self::Pgm901 | Self::Pgm902 => Severity::Info,
+                    Self::Custom(meta) => meta.default_severity,
                 }
             }
 
@@ -256,6 +526,8 @@ macro_rules! dispatch_rules {
                     Self::Pgm901 => {
                         "Meta rules alter the behavior of other rules, they are not rules themselves"
                     }
+                    Self::Pgm902 => "Findings rely on tables assumed to exist",
+                    Self::Custom(meta) => meta.description,
                 }
             }
 
@@ -263,6 +535,8 @@ macro_rules! dispatch_rules {
                 match self {
                     $( Self::$variant => super::$module::EXPLAIN, )+
                     Self::Pgm901 => "This rule caps severity of triggered rules to INFO (not in SonarQube)",
+                    Self::Pgm902 => PGM902_EXPLAIN,
+                    Self::Custom(meta) => meta.explain,
                 }
             }
 
@@ -273,9 +547,25 @@ macro_rules! dispatch_rules {
             ) -> Vec<Finding> {
                 match self {
                     $( Self::$variant => super::$module::check(*self, statements, ctx), )+
-                    Self::Pgm901 => vec![],
+                    // The implementation is the one registered with the registry.
+                    Self::Pgm901 | Self::Pgm902 | Self::Custom(_) => vec![],
                 }
             }
+
+            fn check_change(
+                &self,
+                units: &[ChangedUnit],
+                ctx: &ChangeContext<'_>,
+            ) -> Vec<Finding> {
+                match self {
+                    Self::Pgm207 => super::pgm207::check_change(*self, units, ctx),
+                    _ => vec![],
+                }
+            }
+
+            fn checks_change(&self) -> bool {
+                matches!(self, Self::Pgm207)
+            }
         }
     };
 }
@@ -305,6 +595,19 @@ dispatch_rules! {
     Pgm021 => pgm021,
     Pgm022 => pgm022,
     Pgm023 => pgm023,
+    Pgm024 => pgm024,
+    Pgm025 => pgm025,
+    Pgm026 => pgm026,
+    Pgm027 => pgm027,
+    Pgm028 => pgm028,
+    Pgm029 => pgm029,
+    Pgm030 => pgm030,
+    Pgm031 => pgm031,
+    Pgm032 => pgm032,
+    Pgm033 => pgm033,
+    Pgm034 => pgm034,
+    Pgm035 => pgm035,
+    Pgm036 => pgm036,
     // 1xx — Type anti-patterns
     Pgm101 => pgm101,
     Pgm102 => pgm102,
@@ -315,12 +618,16 @@ dispatch_rules! {
     Pgm107 => pgm107,
     Pgm108 => pgm108,
     Pgm109 => pgm109,
+    Pgm110 => pgm110,
+    Pgm111 => pgm111,
     // 2xx — Destructive operations
     Pgm201 => pgm201,
     Pgm202 => pgm202,
     Pgm203 => pgm203,
     Pgm204 => pgm204,
     Pgm205 => pgm205,
+    Pgm206 => pgm206,
+    Pgm207 => pgm207,
     // 3xx — DML in migrations
     Pgm301 => pgm301,
     Pgm302 => pgm302,
@@ -339,4 +646,23 @@ dispatch_rules! {
     Pgm507 => pgm507,
     Pgm508 => pgm508,
     Pgm509 => pgm509,
+    Pgm510 => pgm510,
+    Pgm511 => pgm511,
+    Pgm512 => pgm512,
+    Pgm513 => pgm513,
+    Pgm514 => pgm514,
+    Pgm515 => pgm515,
+    Pgm516 => pgm516,
+    Pgm517 => pgm517,
+    Pgm518 => pgm518,
+    Pgm519 => pgm519,
+    Pgm520 => pgm520,
+    Pgm521 => pgm521,
+    Pgm522 => pgm522,
+    Pgm523 => pgm523,
+    Pgm524 => pgm524,
+    Pgm525 => pgm525,
+    Pgm526 => pgm526,
+    Pgm527 => pgm527,
+    Pgm528 => pgm528,
 }
@@ -23,6 +23,17 @@ impl Severity {
         }
     }
 
+    /// Lowercase severity string, as written in the config.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Minor => "minor",
+            Severity::Major => "major",
+            Severity::Critical => "critical",
+            Severity::Blocker => "blocker",
+        }
+    }
+
     /// Title-case severity string for documentation output.
     pub fn title_case(&self) -> &'static str {
         match self {
@@ -1,10 +1,19 @@
 //! Shared test helpers for rule unit tests.
 
 use crate::catalog::Catalog;
+use crate::config::Config;
 use crate::parser::ir::*;
-use crate::rules::LintContext;
+use crate::rules::{LintContext, PatternHistory, PendingReferences, RuleId};
+use crate::suppress::Suppressions;
 use std::collections::HashSet;
 use std::path::Path;
+use std::sync::LazyLock;
+
+/// Every lint rule, the default rule set of a test context.
+pub fn all_rules() -> &'static [RuleId] {
+    static ALL: LazyLock<Vec<RuleId>> = LazyLock::new(|| RuleId::lint_rules().collect());
+    &ALL
+}
 
 /// Build a `LintContext` with default settings (in transaction, not a down migration).
 pub fn make_ctx<'a>(
@@ -20,6 +29,12 @@ pub fn make_ctx<'a>(
         run_in_transaction: true,
         is_down: false,
         file,
+        config: Config::default_ref(),
+        pending_references: PendingReferences::empty_ref(),
+        pattern_history: PatternHistory::empty_ref(),
+        assumed_tables: Default::default(),
+        active_rules: all_rules(),
+        suppressions: Suppressions::empty_ref(),
     }
 }
 
@@ -38,6 +53,26 @@ pub fn make_ctx_with_txn<'a>(
         run_in_transaction,
         is_down: false,
         file,
+        config: Config::default_ref(),
+        pending_references: PendingReferences::empty_ref(),
+        pattern_history: PatternHistory::empty_ref(),
+        assumed_tables: Default::default(),
+        active_rules: all_rules(),
+        suppressions: Suppressions::empty_ref(),
+    }
+}
+
+/// Build a `LintContext` with explicit rule options.
+pub fn make_ctx_with_config<'a>(
+    before: &'a Catalog,
+    after: &'a Catalog,
+    file: &'a Path,
+    created: &'a HashSet<String>,
+    config: &'a Config,
+) -> LintContext<'a> {
+    LintContext {
+        config,
+        ..make_ctx(before, after, file, created)
     }
 }
 
@@ -56,6 +91,9 @@ pub fn make_ctx_with_txn<'a>(
 ///
 /// // With explicit run_in_transaction flag:
 /// lint_ctx!(ctx, &before, &after, "migrations/001.sql", txn: false);
+///
+/// // With explicit rule options:
+/// lint_ctx!(ctx, &before, &after, "migrations/001.sql", config: &config);
 /// ```
 macro_rules! lint_ctx {
     ($ctx:ident, $before:expr, $after:expr, $file:expr) => {
@@ -80,6 +118,13 @@ macro_rules! lint_ctx {
             $before, $after, &__lint_file, &__lint_created, $txn,
         );
     };
+    ($ctx:ident, $before:expr, $after:expr, $file:expr, config: $config:expr) => {
+        let __lint_file = ::std::path::PathBuf::from($file);
+        let __lint_created = ::std::collections::HashSet::<String>::new();
+        let $ctx = $crate::rules::test_helpers::make_ctx_with_config(
+            $before, $after, &__lint_file, &__lint_created, $config,
+        );
+    };
 }
 
 pub(crate) use lint_ctx;
@@ -94,5 +139,63 @@ pub fn located_at(node: IrNode, line: usize) -> Located<IrNode> {
     Located {
         node,
         span: SourceSpan::at(line, line),
+        raw_sql: Default::default(),
+    }
+}
+
+/// Metadata of [`TenantIdRule`].
+pub static TENANT_ID: crate::rules::CustomRuleMeta = crate::rules::CustomRuleMeta {
+    id: "ORG001",
+    description: "Table created without a tenant_id column",
+    explain: "ORG001 — Table created without a tenant_id column\n\nEvery table must carry tenant_id.",
+    default_severity: crate::rules::Severity::Major,
+};
+
+/// A rule-pack rule as an external crate would write it: `CREATE TABLE`
+/// without a `tenant_id` column. Registered as [`TENANT_ID`]; other
+/// metadata tests ID validation.
+pub struct TenantIdRule(pub &'static crate::rules::CustomRuleMeta);
+
+impl crate::rules::Rule for TenantIdRule {
+    fn id(&self) -> crate::rules::RuleId {
+        crate::rules::RuleId::Custom(self.0)
+    }
+
+    fn default_severity(&self) -> crate::rules::Severity {
+        self.0.default_severity
+    }
+
+    fn description(&self) -> &'static str {
+        self.0.description
+    }
+
+    fn explain(&self) -> &'static str {
+        self.0.explain
+    }
+
+    fn check(
+        &self,
+        statements: &[Located<IrNode>],
+        ctx: &LintContext<'_>,
+    ) -> Vec<crate::rules::Finding> {
+        statements
+            .iter()
+            .filter_map(|stmt| match &stmt.node {
+                IrNode::CreateTable(ct) if !ct.columns.iter().any(|c| c.name == "tenant_id") => {
+                    Some(
+                        self.make_finding(
+                            format!(
+                                "Table '{}' has no tenant_id column.",
+                                ct.name.display_name()
+                            ),
+                            ctx.file,
+                            &stmt.span,
+                        )
+                        .with_table(&ct.name),
+                    )
+                }
+                _ => None,
+            })
+            .collect()
     }
 }
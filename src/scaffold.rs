@@ -0,0 +1,468 @@
+//! Scaffolding for new lint rules.
+//!
+//! Feature-gated behind `--features docgen`, like the docs generator.
+//! `pg-migration-lint dev new-rule PGM031` (or `--family 0xx` for the next
+//! free ID in a family) writes the rule module and its docs body, and wires
+//! the rule into `src/rules/mod.rs`, `src/rules/rule_id.rs`, and
+//! `src/output/sonarqube.rs`. The result compiles and its tests run; every
+//! decision left to the author is marked `TODO(PGMxxx)`.
+//!
+//! All edits are computed before anything is written, so a missing anchor
+//! leaves the tree untouched.
+
+use std::path::{Path, PathBuf};
+
+use crate::rules::Severity;
+
+/// Error type for rule scaffolding.
+#[derive(Debug, thiserror::Error)]
+pub enum ScaffoldError {
+    /// The rule ID is not of the form `PGMnnn`.
+    #[error("invalid rule ID '{0}': expected PGM followed by three digits, e.g. PGM031")]
+    InvalidId(String),
+    /// The rule ID does not belong to the requested family.
+    #[error("rule ID {id} is not in family {family}")]
+    FamilyMismatch { id: String, family: String },
+    /// Neither a rule ID nor a family was given.
+    #[error("give a rule ID (e.g. PGM031) or --family (e.g. 0xx)")]
+    MissingId,
+    /// A rule with this ID is already registered.
+    #[error("rule {0} already exists")]
+    AlreadyExists(String),
+    /// The family has no rule below the new ID to insert it after.
+    #[error(
+        "no existing rule in family {0} precedes the new ID; add the first rule of a family by hand"
+    )]
+    NoPredecessor(String),
+    /// A registration site no longer has the expected shape.
+    #[error("could not find `{anchor}` in {file}; update the scaffolder to match")]
+    MissingAnchor { file: PathBuf, anchor: String },
+    /// I/O error reading or writing project files.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Files written and updated by [`new_rule`].
+#[derive(Debug)]
+pub struct Scaffold {
+    /// Rule ID, e.g. `PGM031`.
+    pub id: String,
+    /// Newly created files.
+    pub created: Vec<PathBuf>,
+    /// Existing files the rule was registered in.
+    pub updated: Vec<PathBuf>,
+}
+
+const MOD_RS: &str = "src/rules/mod.rs";
+const RULE_ID_RS: &str = "src/rules/rule_id.rs";
+const SONARQUBE_RS: &str = "src/output/sonarqube.rs";
+
+/// Rule numbers registered in `src/rules/mod.rs`, from its `mod pgmNNN;` lines.
+fn registered_numbers(mod_rs: &str) -> Vec<u16> {
+    mod_rs
+        .lines()
+        .filter_map(|l| l.strip_prefix("mod pgm")?.strip_suffix(';')?.parse().ok())
+        .collect()
+}
+
+/// Parse `PGMnnn` (case-insensitive) into its number.
+fn parse_id(id: &str) -> Result<u16, ScaffoldError> {
+    let invalid = || ScaffoldError::InvalidId(id.to_string());
+    let digits = id
+        .get(..3)
+        .filter(|p| p.eq_ignore_ascii_case("PGM"))
+        .and(id.get(3..))
+        .ok_or_else(invalid)?;
+    if digits.len() != 3 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    digits.parse().map_err(|_| invalid())
+}
+
+fn family_of(number: u16) -> String {
+    format!("{}xx", number / 100)
+}
+
+/// The rule ID to create: `id` as given, checked against `family` when both
+/// are present, or the next unused ID in `family`.
+pub fn resolve_id(
+    root: &Path,
+    id: Option<&str>,
+    family: Option<&str>,
+) -> Result<String, ScaffoldError> {
+    match (id, family) {
+        (Some(id), Some(family)) => {
+            let number = parse_id(id)?;
+            if !family_of(number).eq_ignore_ascii_case(family) {
+                return Err(ScaffoldError::FamilyMismatch {
+                    id: id.to_string(),
+                    family: family.to_string(),
+                });
+            }
+            Ok(format!("PGM{number:03}"))
+        }
+        (Some(id), None) => Ok(format!("PGM{:03}", parse_id(id)?)),
+        (None, Some(family)) => next_free_id(root, family),
+        (None, None) => Err(ScaffoldError::MissingId),
+    }
+}
+
+/// The next unused rule ID in `family` (e.g. `0xx`), after the highest
+/// registered one.
+fn next_free_id(root: &Path, family: &str) -> Result<String, ScaffoldError> {
+    let mod_rs = std::fs::read_to_string(root.join(MOD_RS))?;
+    let first = family
+        .strip_suffix("xx")
+        .and_then(|d| d.parse::<u16>().ok())
+        .ok_or_else(|| ScaffoldError::InvalidId(family.to_string()))?;
+    let highest = registered_numbers(&mod_rs)
+        .into_iter()
+        .filter(|n| n / 100 == first)
+        .max()
+        .ok_or_else(|| ScaffoldError::NoPredecessor(family.to_string()))?;
+    Ok(format!("PGM{:03}", highest + 1))
+}
+
+/// Insert `text` after the first line equal to `anchor` (without newline).
+fn insert_after(
+    source: &str,
+    anchor: &str,
+    text: &str,
+    file: &str,
+) -> Result<String, ScaffoldError> {
+    let needle = format!("{anchor}\n");
+    let at = source
+        .find(&needle)
+        .ok_or_else(|| ScaffoldError::MissingAnchor {
+            file: PathBuf::from(file),
+            anchor: anchor.trim().to_string(),
+        })?
+        + needle.len();
+    Ok(format!("{}{}{}", &source[..at], text, &source[at..]))
+}
+
+/// Increment the rule count asserted in `src/rules/mod.rs` tests.
+fn bump_rule_count(mod_rs: &str) -> Result<String, ScaffoldError> {
+    const PREFIX: &str = "RuleId::iter().count(), ";
+    let missing = || ScaffoldError::MissingAnchor {
+        file: PathBuf::from(MOD_RS),
+        anchor: PREFIX.trim().to_string(),
+    };
+    let start = mod_rs.find(PREFIX).ok_or_else(missing)? + PREFIX.len();
+    let len = mod_rs[start..]
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(missing)?;
+    let count: usize = mod_rs[start..start + len].parse().map_err(|_| missing())?;
+    Ok(format!(
+        "{}{}{}",
+        &mod_rs[..start],
+        count + 1,
+        &mod_rs[start + len..]
+    ))
+}
+
+/// Create and register rule `id` under the project at `root`.
+pub fn new_rule(
+    root: &Path,
+    id: &str,
+    description: &str,
+    severity: Severity,
+) -> Result<Scaffold, ScaffoldError> {
+    let number = parse_id(id)?;
+    let id = format!("PGM{number:03}");
+    let module = id.to_lowercase();
+    let variant = format!("Pgm{number:03}");
+
+    let mod_rs = std::fs::read_to_string(root.join(MOD_RS))?;
+    let registered = registered_numbers(&mod_rs);
+    if registered.contains(&number) {
+        return Err(ScaffoldError::AlreadyExists(id));
+    }
+    let previous = registered
+        .iter()
+        .copied()
+        .filter(|n| n / 100 == number / 100 && *n < number)
+        .max()
+        .ok_or_else(|| ScaffoldError::NoPredecessor(family_of(number)))?;
+    let prev_module = format!("pgm{previous:03}");
+    let prev_variant = format!("Pgm{previous:03}");
+
+    let mod_rs = insert_after(
+        &mod_rs,
+        &format!("mod {prev_module};"),
+        &format!("mod {module};\n"),
+        MOD_RS,
+    )?;
+    let mod_rs = bump_rule_count(&mod_rs)?;
+
+    let rule_id_rs = std::fs::read_to_string(root.join(RULE_ID_RS))?;
+    let rule_id_rs = insert_after(
+        &rule_id_rs,
+        &format!("    {prev_variant},"),
+        &format!("    /// {description}\n    #[strum(serialize = \"{id}\")]\n    {variant},\n"),
+        RULE_ID_RS,
+    )?;
+    let rule_id_rs = insert_after(
+        &rule_id_rs,
+        &format!("    {prev_variant} => {prev_module},"),
+        &format!("    {variant} => {module},\n"),
+        RULE_ID_RS,
+    )?;
+
+    let sonarqube_rs = std::fs::read_to_string(root.join(SONARQUBE_RS))?;
+    let sonarqube_rs = insert_after(
+        &sonarqube_rs,
+        "fn sonarqube_meta(rule_id: RuleId) -> SonarQubeRuleMeta {\n    match rule_id {",
+        &format!(
+            "        // TODO({id}): pick metadata, or merge into an existing arm\n\
+             \x20       RuleId::{variant} => SonarQubeRuleMeta {{\n\
+             \x20           clean_code_attribute: \"CONVENTIONAL\",\n\
+             \x20           issue_type: \"CODE_SMELL\",\n\
+             \x20           software_quality: \"MAINTAINABILITY\",\n\
+             \x20           impact_severity: \"LOW\",\n\
+             \x20       }},\n"
+        ),
+        SONARQUBE_RS,
+    )?;
+    let sonarqube_rs = insert_after(
+        &sonarqube_rs,
+        "fn effort_minutes(rule_id: RuleId) -> u32 {\n    match rule_id {",
+        &format!(
+            "        // TODO({id}): estimate the fix effort\n        RuleId::{variant} => 10,\n"
+        ),
+        SONARQUBE_RS,
+    )?;
+
+    let rule_path = root.join(format!("src/rules/{module}.rs"));
+    let body_path = root.join(format!("docs/examples/{module}_body.md"));
+    for path in [&rule_path, &body_path] {
+        if path.exists() {
+            return Err(ScaffoldError::AlreadyExists(path.display().to_string()));
+        }
+    }
+
+    std::fs::write(
+        &rule_path,
+        rule_module(&id, &variant, description, severity),
+    )?;
+    std::fs::write(&body_path, docs_body(&id))?;
+    std::fs::write(root.join(MOD_RS), mod_rs)?;
+    std::fs::write(root.join(RULE_ID_RS), rule_id_rs)?;
+    std::fs::write(root.join(SONARQUBE_RS), sonarqube_rs)?;
+
+    Ok(Scaffold {
+        id,
+        created: vec![rule_path, body_path],
+        updated: [MOD_RS, RULE_ID_RS, SONARQUBE_RS]
+            .iter()
+            .map(|f| root.join(f))
+            .collect(),
+    })
+}
+
+/// Skeleton rule module: fires on any `ALTER TABLE` of an existing table
+/// until the author replaces the match.
+fn rule_module(id: &str, variant: &str, description: &str, severity: Severity) -> String {
+    format!(
+        r#"//! {id} — {description}
+//!
+//! TODO({id}): describe what the rule detects and why.
+
+use crate::parser::ir::{{IrNode, Located}};
+use crate::rules::{{Finding, LintContext, Rule, Severity}};
+
+pub(super) const DESCRIPTION: &str = "{description}";
+
+pub(super) const EXPLAIN: &str = "{id} — {description}\n\
+         \n\
+         What it detects:\n\
+         TODO({id})\n\
+         \n\
+         Why it matters:\n\
+         TODO({id})\n\
+         \n\
+         Example (flagged):\n\
+           TODO\n\
+         \n\
+         Fix:\n\
+           TODO";
+
+pub(super) const DEFAULT_SEVERITY: Severity = Severity::{severity:?};
+
+pub(super) fn check(
+    rule: impl Rule,
+    statements: &[Located<IrNode>],
+    ctx: &LintContext<'_>,
+) -> Vec<Finding> {{
+    let mut findings = Vec::new();
+    for stmt in statements {{
+        // TODO({id}): match the statements this rule detects.
+        let IrNode::AlterTable(at) = &stmt.node else {{
+            continue;
+        }};
+        if !ctx.is_existing_table(at.name.catalog_key()) {{
+            continue;
+        }}
+
+        findings.push(
+            rule.make_finding(
+                format!(
+                    "TODO({id}): explain the problem with '{{}}'.",
+                    at.name.display_name()
+                ),
+                ctx.file,
+                &stmt.span,
+            )
+            .with_table(&at.name),
+        );
+    }}
+    findings
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use crate::catalog::builder::CatalogBuilder;
+    use crate::parser::ir::*;
+    use crate::rules::RuleId;
+    use crate::rules::test_helpers::{{lint_ctx, located}};
+
+    #[test]
+    fn test_existing_table_fires() {{
+        let before = CatalogBuilder::new()
+            .table("orders", |t| {{
+                t.column("id", "bigint", false).pk(&["id"]);
+            }})
+            .build();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/V002.sql");
+
+        let stmts = vec![located(IrNode::AlterTable(AlterTable {{
+            name: QualifiedName::unqualified("orders"),
+            actions: vec![],
+        }}))];
+
+        let findings = RuleId::{variant}.check(&stmts, &ctx);
+        insta::assert_yaml_snapshot!(findings);
+    }}
+
+    #[test]
+    fn test_new_table_no_finding() {{
+        let before = CatalogBuilder::new().build();
+        let after = before.clone();
+        lint_ctx!(ctx, &before, &after, "migrations/V002.sql", created: ["orders"]);
+
+        let stmts = vec![located(IrNode::AlterTable(AlterTable {{
+            name: QualifiedName::unqualified("orders"),
+            actions: vec![],
+        }}))];
+
+        assert!(RuleId::{variant}.check(&stmts, &ctx).is_empty());
+    }}
+}}
+"#
+    )
+}
+
+fn docs_body(id: &str) -> String {
+    format!(
+        "TODO({id}): what the rule detects.\n\
+         \n\
+         TODO({id}): why it matters.\n\
+         \n\
+         **Example** (bad):\n\
+         ```sql\n\
+         -- TODO\n\
+         ```\n\
+         \n\
+         **Fix**:\n\
+         ```sql\n\
+         -- TODO\n\
+         ```\n"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A project root with minimal copies of the registration sites.
+    fn project() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for d in ["src/rules", "src/output", "docs/examples"] {
+            std::fs::create_dir_all(root.join(d)).unwrap();
+        }
+        std::fs::write(
+            root.join(MOD_RS),
+            "mod pgm001;\nmod pgm002;\nmod pgm101;\n\n\
+             fn t() {\n    assert_eq!(RuleId::iter().count(), 3);\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join(RULE_ID_RS),
+            "pub enum RuleId {\n    /// a\n    #[strum(serialize = \"PGM001\")]\n    Pgm001,\n\
+             \x20   /// b\n    #[strum(serialize = \"PGM002\")]\n    Pgm002,\n}\n\
+             dispatch_rules! {\n    Pgm001 => pgm001,\n    Pgm002 => pgm002,\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join(SONARQUBE_RS),
+            "fn sonarqube_meta(rule_id: RuleId) -> SonarQubeRuleMeta {\n    match rule_id {\n    }\n}\n\
+             fn effort_minutes(rule_id: RuleId) -> u32 {\n    match rule_id {\n    }\n}\n",
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_new_rule_registers_after_family_predecessor() {
+        let dir = project();
+        let root = dir.path();
+        assert_eq!(resolve_id(root, None, Some("0xx")).unwrap(), "PGM003");
+        assert!(matches!(
+            resolve_id(root, Some("PGM003"), Some("1xx")),
+            Err(ScaffoldError::FamilyMismatch { .. })
+        ));
+
+        let scaffold = new_rule(root, "pgm003", "Something risky", Severity::Major).unwrap();
+        assert_eq!(scaffold.id, "PGM003");
+
+        let read = |f: &str| std::fs::read_to_string(root.join(f)).unwrap();
+        assert!(
+            read(MOD_RS).starts_with("mod pgm001;\nmod pgm002;\nmod pgm003;\nmod pgm101;"),
+            "{}",
+            read(MOD_RS)
+        );
+        assert!(read(MOD_RS).contains("count(), 4)"));
+        assert!(read(RULE_ID_RS).contains(
+            "    Pgm002,\n    /// Something risky\n    #[strum(serialize = \"PGM003\")]\n    Pgm003,\n}"
+        ));
+        assert!(read(RULE_ID_RS).contains("    Pgm002 => pgm002,\n    Pgm003 => pgm003,\n"));
+        assert!(read(SONARQUBE_RS).contains("RuleId::Pgm003 => SonarQubeRuleMeta {"));
+        assert!(read(SONARQUBE_RS).contains("RuleId::Pgm003 => 10,"));
+
+        let module = read("src/rules/pgm003.rs");
+        assert!(module.contains("DEFAULT_SEVERITY: Severity = Severity::Major;"));
+        assert!(module.contains("RuleId::Pgm003.check(&stmts, &ctx)"));
+        assert!(root.join("docs/examples/pgm003_body.md").exists());
+    }
+
+    #[test]
+    fn test_new_rule_rejects_bad_or_taken_ids_without_writing() {
+        let dir = project();
+        let root = dir.path();
+        let before = std::fs::read_to_string(root.join(MOD_RS)).unwrap();
+
+        for (id, expected) in [
+            ("PGM02", "invalid rule ID"),
+            ("XYZ003", "invalid rule ID"),
+            ("PGM002", "already exists"),
+            ("PGM301", "no existing rule in family 3xx"),
+        ] {
+            let err = new_rule(root, id, "x", Severity::Minor).unwrap_err();
+            assert!(err.to_string().contains(expected), "{id}: {err}");
+        }
+        assert_eq!(std::fs::read_to_string(root.join(MOD_RS)).unwrap(), before);
+    }
+}
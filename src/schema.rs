@@ -0,0 +1,121 @@
+//! Machine-readable rule and configuration metadata
+//!
+//! `pg-migration-lint rules schema` prints one JSON document: a JSON Schema
+//! (draft 2020-12) for `pg-migration-lint.toml`, generated from the
+//! [`Config`] types, so editors can validate the file against it. The rule
+//! set travels in the same document under the `x-pg-migration-lint`
+//! extension keyword, which validators ignore: every lint rule with its
+//! family, default severity, whether it is opt-in, and the config keys that
+//! tune it. Policy tooling can diff the document across releases to see
+//! which rules and settings were added or changed.
+
+use serde_json::{Map, Value, json};
+
+use crate::config::Config;
+use crate::rules::{Rule, RuleId};
+
+/// Extension keyword holding the tool version and rule metadata.
+pub const EXTENSION_KEY: &str = "x-pg-migration-lint";
+
+/// The configuration keys (dotted TOML paths) that change what `id` reports.
+/// Settings that apply to every rule, such as `rules.disabled`, are not
+/// listed.
+pub fn rule_config_keys(id: RuleId) -> &'static [&'static str] {
+    match id {
+        RuleId::Pgm008 => &["postgres.version"],
+        RuleId::Pgm032 => &["lock_budget"],
+        RuleId::Pgm034 => &["catalog.strict_replay"],
+        RuleId::Pgm107 => &["rules.pgm107"],
+        RuleId::Pgm110 => &["rules.pgm110"],
+        RuleId::Pgm111 => &["rules.pgm111"],
+        RuleId::Pgm201 => &["rules.pgm201"],
+        RuleId::Pgm202 => &["rules.pgm202"],
+        RuleId::Pgm511 => &["schemas"],
+        RuleId::Pgm517 => &["patterns"],
+        RuleId::Pgm519 | RuleId::Pgm520 => &["rename_workflow"],
+        RuleId::Pgm525 => &["rules.pgm525"],
+        RuleId::Pgm526 => &["rules.pgm526"],
+        RuleId::Pgm527 => &["schemas"],
+        RuleId::Pgm528 => &["rules.pgm528"],
+        RuleId::Pgm901 => &["meta.pgm901"],
+        RuleId::Pgm902 => &["catalog.assume_unknown_tables_exist"],
+        _ => &[],
+    }
+}
+
+/// Metadata for every lint rule, in ID order.
+pub fn rules_metadata() -> Vec<Value> {
+    RuleId::lint_rules()
+        .map(|id| {
+            json!({
+                "id": id.as_str(),
+                "family": id.family_prefix(),
+                "description": id.description(),
+                "default_severity": id.default_severity().to_string(),
+                "opt_in": id.is_opt_in(),
+                "config": rule_config_keys(id),
+            })
+        })
+        .collect()
+}
+
+/// JSON Schema for `pg-migration-lint.toml`, with the rule metadata under
+/// [`EXTENSION_KEY`].
+pub fn rules_schema() -> Value {
+    let mut schema = schemars::schema_for!(Config).to_value();
+    if let Some(root) = schema.as_object_mut() {
+        root.insert(
+            "title".to_string(),
+            Value::String("pg-migration-lint.toml".to_string()),
+        );
+        let mut extension = Map::new();
+        extension.insert(
+            "version".to_string(),
+            Value::String(env!("CARGO_PKG_VERSION").to_string()),
+        );
+        extension.insert("rules".to_string(), Value::Array(rules_metadata()));
+        root.insert(EXTENSION_KEY.to_string(), Value::Object(extension));
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn test_every_lint_rule_listed_once() {
+        let rules = rules_metadata();
+        assert_eq!(rules.len(), RuleId::lint_rules().count());
+        let pgm201 = rules.iter().find(|r| r["id"] == "PGM201").unwrap();
+        assert_eq!(pgm201["family"], "2xx");
+        assert_eq!(pgm201["default_severity"], "MINOR");
+        assert_eq!(pgm201["config"], json!(["rules.pgm201"]));
+        let pgm519 = rules.iter().find(|r| r["id"] == "PGM519").unwrap();
+        assert_eq!(pgm519["opt_in"], true);
+    }
+
+    #[test]
+    fn test_rule_config_keys_name_config_sections() {
+        let schema = rules_schema();
+        let properties = schema["properties"].as_object().unwrap();
+        for id in RuleId::iter() {
+            for key in rule_config_keys(id) {
+                let section = key.split('.').next().unwrap();
+                assert!(properties.contains_key(section), "{id}: {key}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_schema_describes_config_sections() {
+        let schema = rules_schema();
+        assert_eq!(schema["title"], "pg-migration-lint.toml");
+        assert_eq!(schema[EXTENSION_KEY]["version"], env!("CARGO_PKG_VERSION"));
+        let properties = schema["properties"].as_object().unwrap();
+        for section in ["migrations", "rules", "lock_budget", "catalog", "warnings"] {
+            assert!(properties.contains_key(section), "missing {section}");
+        }
+    }
+}
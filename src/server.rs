@@ -0,0 +1,668 @@
+//! Long-running HTTP/JSON daemon (`pg-migration-lint serve`).
+//!
+//! Keeps the process (and the parser) warm so editors and migration services
+//! can lint SQL without paying process start-up per request. The protocol is
+//! deliberately tiny — plain HTTP/1.1 with JSON bodies, one request per
+//! connection — so it needs no dependencies beyond the standard library.
+//!
+//! Endpoints:
+//! - `GET /health` → `{"status": "ok"}`
+//! - `POST /lint` with `{"sql": "...", "file": "V001.sql", "config": {...},
+//!   "repo": "/path/to/repo"}` → `{"findings": [...]}`. All but `sql` are
+//!   optional; a missing `config` falls back to the configuration the daemon
+//!   was started with, or with `repo` to the repository's own config file.
+//!
+//! With `repo`, the SQL is linted as the next migration after the
+//! repository's history. The catalog replayed from that history is kept per
+//! repository and reused until a migration file or the configuration
+//! changes, so only the first request for a repository pays for the replay.
+//!
+//! The daemon reads and runs only what its operator allows: `repo` must lie
+//! under a directory allowed at start-up ([`ServeOptions::allowed_repos`]),
+//! and a request's `config` cannot name files or programs on the host
+//! (migration paths, Liquibase, output and cache directories). Those come
+//! from the repository's config file or the daemon's. Listening on anything
+//! but a loopback address requires a token, sent as
+//! `Authorization: Bearer <token>` on every request but `GET /health`.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::Catalog;
+use crate::Config;
+use crate::cache::{FileStamps, files_outside_tree};
+use crate::examples::CONFIG_FILE;
+use crate::normalize;
+use crate::pipeline::{LintPipeline, lint_sql_on, load_units};
+use crate::rules::{Finding, RuleRegistry};
+
+/// Largest request body accepted, in bytes.
+const MAX_BODY_BYTES: usize = 8 * 1024 * 1024;
+
+/// Connections answered at once. Beyond it, new connections get a `503`.
+const MAX_CONNECTIONS: usize = 64;
+
+/// How long a client may take to send each part of its request, and to
+/// read the response.
+const IO_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Body of a `POST /lint` request.
+#[derive(Debug, Deserialize)]
+struct LintRequest {
+    sql: String,
+    #[serde(default = "default_file")]
+    file: PathBuf,
+    #[serde(default)]
+    config: Option<Config>,
+    #[serde(default)]
+    repo: Option<PathBuf>,
+}
+
+fn default_file() -> PathBuf {
+    PathBuf::from("migration.sql")
+}
+
+/// Settings fixed when the daemon starts.
+#[derive(Debug, Clone, Default)]
+pub struct ServeOptions {
+    /// Directories a request's `repo` may name or lie under. Empty rejects
+    /// every request with `repo`.
+    pub allowed_repos: Vec<PathBuf>,
+    /// Token every request but `GET /health` must send as
+    /// `Authorization: Bearer <token>`. Required unless the daemon listens
+    /// on a loopback address.
+    pub token: Option<String>,
+}
+
+/// State shared by the connections of one daemon.
+struct Server {
+    config: Config,
+    /// `ServeOptions::allowed_repos`, canonicalized.
+    allowed_repos: Vec<PathBuf>,
+    token: Option<String>,
+    catalogs: WarmCatalogs,
+    active: AtomicUsize,
+}
+
+impl Server {
+    fn new(config: Config, options: ServeOptions) -> std::io::Result<Self> {
+        let allowed_repos = options
+            .allowed_repos
+            .iter()
+            .map(|dir| dir.canonicalize())
+            .collect::<std::io::Result<_>>()?;
+        Ok(Self {
+            config,
+            allowed_repos,
+            token: options.token,
+            catalogs: WarmCatalogs::default(),
+            active: AtomicUsize::new(0),
+        })
+    }
+
+    /// Whether `authorization`, the request's `Authorization` header, carries
+    /// the daemon's token. Always true without a token.
+    fn authorized(&self, authorization: Option<&str>) -> bool {
+        let Some(token) = &self.token else {
+            return true;
+        };
+        let sent = authorization
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .unwrap_or_default();
+        // Compare every byte, so the time taken does not reveal how much
+        // of the token matched.
+        sent.len() == token.len()
+            && sent
+                .bytes()
+                .zip(token.bytes())
+                .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    }
+
+    /// `repo` canonicalized, if it is an allowed directory or lies under one.
+    fn allowed_repo(&self, repo: &Path) -> Result<PathBuf, String> {
+        let canonical = repo
+            .canonicalize()
+            .map_err(|e| format!("repo {}: {e}", repo.display()))?;
+        if !canonical.is_dir() {
+            return Err(format!("repo {} is not a directory", repo.display()));
+        }
+        if self
+            .allowed_repos
+            .iter()
+            .any(|allowed| canonical.starts_with(allowed))
+        {
+            Ok(canonical)
+        } else {
+            Err(format!(
+                "repo {} is not under a directory the daemon allows (--allow-repo)",
+                repo.display()
+            ))
+        }
+    }
+}
+
+/// Catalogs replayed from each repository's migrations, keyed by the
+/// canonical repository directory.
+#[derive(Default)]
+struct WarmCatalogs(Mutex<HashMap<PathBuf, WarmCatalog>>);
+
+struct WarmCatalog {
+    /// The configuration the catalog was replayed under, as JSON.
+    config: String,
+    /// Files the replay read outside `migrations.paths` (includes).
+    outside: Vec<PathBuf>,
+    /// Size and modification time of every file the replay read, taken
+    /// before it; different stamps mean the catalog is stale.
+    stamps: FileStamps,
+    catalog: Arc<Catalog>,
+}
+
+impl WarmCatalogs {
+    /// The catalog replayed from `repo` under `config`, replaying it only
+    /// when none is kept, the configuration differs, or a migration file
+    /// was added, removed, or modified. Unchanged files are not read.
+    fn get(&self, repo: &Path, config: &Config) -> Result<Arc<Catalog>, String> {
+        let read_error =
+            |e: std::io::Error| format!("cannot read migrations in {}: {e}", repo.display());
+        let config_json = serde_json::to_string(config).map_err(|e| e.to_string())?;
+        let kept = self
+            .lock()
+            .get(repo)
+            .filter(|warm| warm.config == config_json)
+            .map(|warm| {
+                (
+                    warm.outside.clone(),
+                    warm.stamps.clone(),
+                    Arc::clone(&warm.catalog),
+                )
+            });
+        if let Some((outside, stamps, catalog)) = kept
+            && FileStamps::take(config, &outside).map_err(read_error)? == stamps
+        {
+            return Ok(catalog);
+        }
+
+        // Stamp before replaying, so a file changed during the replay
+        // makes the next request replay again.
+        let outside = files_outside_tree(config).map_err(read_error)?;
+        let stamps = FileStamps::take(config, &outside).map_err(read_error)?;
+        // Replay outside the lock so other repositories are not held up.
+        let catalog = Arc::new(replay_repository(config)?);
+        self.lock().insert(
+            repo.to_path_buf(),
+            WarmCatalog {
+                config: config_json,
+                outside,
+                stamps,
+                catalog: Arc::clone(&catalog),
+            },
+        );
+        Ok(catalog)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<PathBuf, WarmCatalog>> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Accept connections on `listener` forever, answering each on its own thread.
+///
+/// `config` is used for requests that do not carry their own `config` object,
+/// and for the host paths no request may set. Fails without serving if
+/// `listener` is not on a loopback address and `options` has no token, or
+/// an allowed repository directory cannot be resolved. A failed accept is
+/// logged and the daemon keeps listening.
+pub fn serve(listener: TcpListener, config: Config, options: ServeOptions) -> std::io::Result<()> {
+    if options.token.is_none() && !listener.local_addr()?.ip().is_loopback() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "a token is required to listen on a non-loopback address",
+        ));
+    }
+    let server = Arc::new(Server::new(config, options)?);
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("pg-migration-lint serve: accept error: {e}");
+                continue;
+            }
+        };
+        if server.active.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+            server.active.fetch_sub(1, Ordering::SeqCst);
+            let _ = stream.set_write_timeout(Some(IO_TIMEOUT));
+            let _ = write_response(&mut stream, 503, &json!({ "error": "server busy" }));
+            continue;
+        }
+        let server = Arc::clone(&server);
+        std::thread::spawn(move || {
+            if let Err(e) = handle_connection(stream, &server) {
+                eprintln!("pg-migration-lint serve: connection error: {e}");
+            }
+            server.active.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+    Ok(())
+}
+
+/// Read one HTTP request from `stream`, dispatch it, and write the response.
+fn handle_connection(mut stream: TcpStream, server: &Server) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut content_length = 0usize;
+    let mut authorization = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            continue;
+        };
+        let name = name.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.trim().parse().unwrap_or(0);
+        } else if name.eq_ignore_ascii_case("authorization") {
+            authorization = Some(value.trim().to_string());
+        }
+    }
+
+    let (status, body) = if content_length > MAX_BODY_BYTES {
+        (413, json!({ "error": "request body too large" }))
+    } else {
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(&mut body)?;
+        route(&method, &path, authorization.as_deref(), &body, server)
+    };
+    write_response(&mut stream, status, &body)
+}
+
+fn write_response(stream: &mut TcpStream, status: u16, body: &Value) -> std::io::Result<()> {
+    let payload = body.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason_phrase(status),
+        payload.len(),
+        payload
+    )?;
+    stream.flush()
+}
+
+/// Map a request to a status code and JSON response body. `authorization`
+/// is the request's `Authorization` header.
+fn route(
+    method: &str,
+    path: &str,
+    authorization: Option<&str>,
+    body: &[u8],
+    server: &Server,
+) -> (u16, Value) {
+    match (method, path) {
+        ("GET", "/health") => (200, json!({ "status": "ok" })),
+        _ if !server.authorized(authorization) => (401, json!({ "error": "unauthorized" })),
+        ("POST", "/lint") => match serde_json::from_slice::<LintRequest>(body) {
+            Ok(req) => match lint(req, server) {
+                Ok(findings) => (200, json!({ "findings": findings })),
+                Err(error) => (400, json!({ "error": error })),
+            },
+            Err(e) => (400, json!({ "error": format!("invalid request: {e}") })),
+        },
+        (_, "/health" | "/lint") => (405, json!({ "error": "method not allowed" })),
+        _ => (404, json!({ "error": format!("no route for {path}") })),
+    }
+}
+
+/// Answer a `POST /lint`: validate the request's configuration the way the
+/// CLI validates a config file, then lint against the repository's warm
+/// catalog or an empty one.
+fn lint(req: LintRequest, server: &Server) -> Result<Vec<Finding>, String> {
+    let Some(repo) = &req.repo else {
+        let config = match req.config {
+            Some(requested) => without_host_paths(requested, &server.config),
+            None => server.config.clone(),
+        };
+        validate(&config)?;
+        return Ok(lint_sql_on(Catalog::new(), &req.sql, &req.file, &config));
+    };
+    let repo = server.allowed_repo(repo)?;
+    let config = repo_config(&repo, req.config)?;
+    validate(&config)?;
+    let catalog = server.catalogs.get(&repo, &config)?;
+    let file = repo.join(&req.file);
+    Ok(lint_sql_on(
+        Catalog::clone(&catalog),
+        &req.sql,
+        &file,
+        &config,
+    ))
+}
+
+fn validate(config: &Config) -> Result<(), String> {
+    RuleRegistry::assemble(config)
+        .validate_config(config)
+        .map_err(|e| format!("invalid config: {e}"))
+}
+
+/// The configuration for `repo`: the repository's config file, or else the
+/// defaults, resolved against the repository. With a requested
+/// configuration, its settings apply except those that name files or
+/// programs on the host.
+fn repo_config(repo: &Path, requested: Option<Config>) -> Result<Config, String> {
+    let config_file = repo.join(CONFIG_FILE);
+    let base = if config_file.is_file() {
+        Config::from_file(&config_file)
+            .map_err(|e| format!("invalid config {}: {e}", config_file.display()))?
+    } else {
+        let mut config = Config::default();
+        config.resolve_paths(repo);
+        config
+    };
+    Ok(match requested {
+        Some(requested) => without_host_paths(requested, &base),
+        None => base,
+    })
+}
+
+/// `requested` with every setting that names a file or program on the
+/// daemon's host taken from `base`: where migrations are read from and how
+/// (`[migrations]` paths and includes), how Liquibase is run
+/// (`[liquibase]`), where output and the cache are written, and the files
+/// `rename_workflow.state_file` and `messages.locale_file` name.
+fn without_host_paths(requested: Config, base: &Config) -> Config {
+    let mut config = Config {
+        liquibase: base.liquibase.clone(),
+        output: base.output.clone(),
+        cache: base.cache.clone(),
+        rename_workflow: base.rename_workflow.clone(),
+        ..requested
+    };
+    let migrations = &mut config.migrations;
+    migrations.paths = base.migrations.paths.clone();
+    migrations.strategy = base.migrations.strategy.clone();
+    migrations.include = base.migrations.include.clone();
+    migrations.exclude = base.migrations.exclude.clone();
+    migrations.include_root = base.migrations.include_root.clone();
+    migrations.resolve_psql_includes = base.migrations.resolve_psql_includes;
+    migrations.follow_symlinks = base.migrations.follow_symlinks;
+    migrations.respect_gitignore = base.migrations.respect_gitignore;
+    config.messages.locale_file = base.messages.locale_file.clone();
+    config
+}
+
+/// Replay every migration of the repository `config` describes, as the CLI
+/// does for unchanged files, and return the resulting catalog.
+fn replay_repository(config: &Config) -> Result<Catalog, String> {
+    let units = load_units(config).map_err(|e| format!("cannot load migrations: {e}"))?;
+
+    let mut pipeline = LintPipeline::new().with_config(config.clone());
+    for mut unit in units {
+        let default_schema = config.migrations.default_schema_for(&unit.source_file);
+        normalize::normalize_unit(&mut unit, default_schema);
+        pipeline.replay(&unit);
+    }
+    Ok(pipeline.catalog().clone())
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        401 => "Unauthorized",
+        413 => "Payload Too Large",
+        503 => "Service Unavailable",
+        _ => "Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server() -> Server {
+        Server::new(Config::default(), ServeOptions::default()).unwrap()
+    }
+
+    #[test]
+    fn test_route_health() {
+        let (status, body) = route("GET", "/health", None, b"", &server());
+        assert_eq!(status, 200);
+        assert_eq!(body["status"], "ok");
+    }
+
+    #[test]
+    fn test_route_lint_returns_findings() {
+        let req =
+            r#"{"sql": "CREATE TABLE events (id bigint PRIMARY KEY, created_at timestamp);"}"#;
+        let (status, body) = route("POST", "/lint", None, req.as_bytes(), &server());
+        assert_eq!(status, 200);
+        let findings = body["findings"].as_array().expect("findings array");
+        assert!(findings.iter().any(|f| f["rule_id"] == "PGM101"));
+    }
+
+    #[test]
+    fn test_route_lint_rejects_malformed_body() {
+        let (status, body) = route("POST", "/lint", None, b"{not json", &server());
+        assert_eq!(status, 400);
+        assert!(
+            body["error"]
+                .as_str()
+                .unwrap()
+                .starts_with("invalid request")
+        );
+    }
+
+    #[test]
+    fn test_route_wrong_method_and_unknown_path() {
+        assert_eq!(route("GET", "/lint", None, b"", &server()).0, 405);
+        assert_eq!(route("GET", "/nope", None, b"", &server()).0, 404);
+    }
+
+    #[test]
+    fn test_route_lint_validates_config() {
+        let req = r#"{"sql": "SELECT 1;", "config": {"rules": {"disabled": ["PGM9999"]}}}"#;
+        let (status, body) = route("POST", "/lint", None, req.as_bytes(), &server());
+        assert_eq!(status, 400, "{body}");
+        assert!(
+            body["error"]
+                .as_str()
+                .unwrap()
+                .starts_with("invalid config")
+        );
+    }
+
+    #[test]
+    fn test_route_lint_against_warm_repo_catalog() {
+        let repo = tempfile::tempdir().unwrap();
+        let migrations = repo.path().join("migrations");
+        std::fs::create_dir_all(&migrations).unwrap();
+        std::fs::write(
+            migrations.join("V001__orders.sql"),
+            "CREATE TABLE orders (id bigint PRIMARY KEY, customer_id bigint);",
+        )
+        .unwrap();
+        std::fs::write(
+            repo.path().join(CONFIG_FILE),
+            "[migrations]\npaths = [\"migrations\"]\n",
+        )
+        .unwrap();
+
+        let server = Server::new(
+            Config::default(),
+            ServeOptions {
+                allowed_repos: vec![repo.path().to_path_buf()],
+                ..ServeOptions::default()
+            },
+        )
+        .unwrap();
+        let repo_dir = repo.path().canonicalize().unwrap();
+        let req = json!({
+            "sql": "CREATE INDEX idx_orders_customer ON orders (customer_id);",
+            "file": "migrations/V002__index.sql",
+            "repo": repo.path(),
+        })
+        .to_string();
+        let lint = || route("POST", "/lint", None, req.as_bytes(), &server);
+
+        let (status, body) = lint();
+        assert_eq!(status, 200, "{body}");
+        // The index is built on a table from the repository's history.
+        assert!(
+            body["findings"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|f| f["rule_id"] == "PGM001"),
+            "{body}"
+        );
+        let first = server
+            .catalogs
+            .get(&repo_dir, &repo_config(&repo_dir, None).unwrap());
+        assert_eq!(lint().0, 200);
+        let again = server
+            .catalogs
+            .get(&repo_dir, &repo_config(&repo_dir, None).unwrap());
+        assert!(Arc::ptr_eq(&first.unwrap(), &again.unwrap()));
+
+        std::fs::write(
+            migrations.join("V001__orders.sql"),
+            "CREATE TABLE orders (id bigint);",
+        )
+        .unwrap();
+        let (_, body) = lint();
+        assert!(
+            !body["findings"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|f| f["rule_id"] == "PGM001"),
+            "replayed again after the migration changed: {body}"
+        );
+    }
+
+    #[test]
+    fn test_serve_round_trip_over_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || serve(listener, Config::default(), ServeOptions::default()));
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream
+            .write_all(b"GET /health HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{response}");
+        assert!(response.ends_with("{\"status\":\"ok\"}"), "{response}");
+    }
+
+    #[test]
+    fn test_route_lint_rejects_repo_outside_allowlist() {
+        let allowed = tempfile::tempdir().unwrap();
+        let other = tempfile::tempdir().unwrap();
+        let server = Server::new(
+            Config::default(),
+            ServeOptions {
+                allowed_repos: vec![allowed.path().to_path_buf()],
+                ..ServeOptions::default()
+            },
+        )
+        .unwrap();
+
+        for repo in [other.path().to_path_buf(), allowed.path().join("..")] {
+            let req = json!({ "sql": "SELECT 1;", "repo": repo }).to_string();
+            let (status, body) = route("POST", "/lint", None, req.as_bytes(), &server);
+            assert_eq!(status, 400, "{body}");
+            assert!(
+                body["error"].as_str().unwrap().contains("--allow-repo"),
+                "{body}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_request_config_cannot_name_host_paths() {
+        let repo = tempfile::tempdir().unwrap();
+        let requested: Config = serde_json::from_value(json!({
+            "migrations": {
+                "paths": ["/etc"],
+                "strategy": "liquibase",
+                "include_root": "/",
+                "default_schema": "app",
+            },
+            "liquibase": { "binary_path": "/bin/sh", "bridge_jar_path": "/tmp/evil.jar" },
+            "output": { "dir": "/tmp/out" },
+            "cache": { "enabled": true, "dir": "/tmp/cache" },
+        }))
+        .unwrap();
+
+        let config = repo_config(repo.path(), Some(requested)).unwrap();
+        let base = repo_config(repo.path(), None).unwrap();
+        assert_eq!(
+            serde_json::to_value(&config.liquibase).unwrap(),
+            serde_json::to_value(&base.liquibase).unwrap()
+        );
+        assert_eq!(
+            config.migrations.source_paths(),
+            base.migrations.source_paths()
+        );
+        assert_eq!(config.migrations.strategy, base.migrations.strategy);
+        assert_eq!(config.migrations.include_root, base.migrations.include_root);
+        assert_eq!(config.output.dir, base.output.dir);
+        assert_eq!(config.cache.dir, base.cache.dir);
+        // Settings that only affect linting still apply.
+        assert_eq!(config.migrations.default_schema, "app");
+    }
+
+    #[test]
+    fn test_route_requires_token() {
+        let server = Server::new(
+            Config::default(),
+            ServeOptions {
+                token: Some("s3cret".to_string()),
+                ..ServeOptions::default()
+            },
+        )
+        .unwrap();
+        let req = br#"{"sql": "SELECT 1;"}"#;
+
+        assert_eq!(route("GET", "/health", None, b"", &server).0, 200);
+        assert_eq!(route("POST", "/lint", None, req, &server).0, 401);
+        assert_eq!(
+            route("POST", "/lint", Some("Bearer wrong!"), req, &server).0,
+            401
+        );
+        assert_eq!(
+            route("POST", "/lint", Some("Bearer s3cret"), req, &server).0,
+            200
+        );
+    }
+
+    #[test]
+    fn test_serve_refuses_non_loopback_without_token() {
+        let listener = TcpListener::bind("0.0.0.0:0").unwrap();
+        let err = serve(listener, Config::default(), ServeOptions::default()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+}
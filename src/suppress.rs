@@ -3,52 +3,162 @@
 //! Parses inline comments for suppression directives in both SQL and XML formats:
 //! - SQL: `-- pgm-lint:suppress PGM001` - suppress next statement
 //! - SQL: `-- pgm-lint:suppress-file PGM001,PGM501` - suppress entire file
-//! - XML: `<!-- pgm-lint:suppress PGM001 -->` - suppress next statement
+//! - SQL: `-- pgm-lint:disable-next-line PGM001` - suppress the line right
+//!   after the comment
+//! - SQL: `-- pgm-lint:disable PGM3xx` ... `-- pgm-lint:enable PGM3xx` -
+//!   suppress every line in between (`enable` without rules closes all open
+//!   blocks; an unclosed block runs to the end of the file)
+//! - XML: `<!-- pgm-lint:suppress PGM001 -->` - suppress the whole changeset
+//!   the comment sits in (or immediately precedes)
 //! - XML: `<!-- pgm-lint:suppress-file PGM001,PGM501 -->` - suppress entire file
+//!
+//! Acknowledgments keep a finding in the report but downgrade it to INFO,
+//! recording an optional ticket:
+//! - SQL: `-- pgm-lint:acknowledge PGM201 ticket=DB-123` - next statement
+//! - XML: `<!-- pgm-lint:acknowledge PGM201 ticket=DB-123 -->` - the whole
+//!   changeset the comment sits in (or immediately precedes)
+//!
+//! Rule lists are comma-separated and accept family wildcards such as
+//! `PGM3xx` for every rule in a family. IDs of rule pack rules (`ORG001`)
+//! are accepted as written.
 
 use std::{
     collections::{HashMap, HashSet},
     str::FromStr,
+    sync::LazyLock,
 };
 
-use crate::rules::RuleId;
+use crate::rules::{Acknowledgment, Finding, RuleId, RuleRef, Severity};
 
 /// Parsed suppression directives from a single file.
-#[derive(Debug, Default)]
+///
+/// [`parse_suppressions`] returns the view of a whole file; loaders narrow it
+/// with [`Suppressions::for_unit`] to the directives that apply to one
+/// migration unit, which is what [`MigrationUnit`](crate::input::MigrationUnit)
+/// carries.
+#[derive(Debug, Clone, Default)]
 pub struct Suppressions {
     /// Rules suppressed for the entire file.
-    file_level: HashSet<RuleId>,
+    file_level: HashSet<RuleRef>,
 
     /// Rules suppressed for a specific line (the statement after the comment).
     /// Key: line number of the statement (not the comment).
-    line_level: HashMap<usize, HashSet<RuleId>>,
+    line_level: HashMap<usize, HashSet<RuleRef>>,
+
+    /// Rules suppressed for a whole Liquibase changeset.
+    /// Key: line number of the `<changeSet` element.
+    changeset_level: HashMap<usize, HashSet<RuleRef>>,
+
+    /// Inclusive line ranges from `disable` ... `enable` blocks.
+    range_level: HashMap<RuleRef, Vec<(usize, usize)>>,
+
+    /// Rules acknowledged for a specific statement line, with optional ticket.
+    line_acks: HashMap<usize, HashMap<RuleRef, Option<String>>>,
+
+    /// Rules acknowledged for a whole Liquibase changeset, with optional
+    /// ticket. Key: line number of the `<changeSet` element.
+    changeset_acks: HashMap<usize, HashMap<RuleRef, Option<String>>>,
+
+    /// Rules acknowledged for every statement of the unit (filled by
+    /// [`Suppressions::for_unit`] from `changeset_acks`).
+    unit_acks: HashMap<RuleRef, Option<String>>,
 }
 
 impl Suppressions {
-    /// Return all distinct rule IDs referenced by any suppression directive.
+    /// A shared empty set of directives, for contexts built without a unit.
+    pub fn empty_ref() -> &'static Suppressions {
+        static EMPTY: LazyLock<Suppressions> = LazyLock::new(Suppressions::default);
+        &EMPTY
+    }
+
+    /// Return all distinct built-in rule IDs referenced by any suppression
+    /// directive.
     pub fn rule_ids(&self) -> HashSet<RuleId> {
-        let mut ids: HashSet<RuleId> = self.file_level.iter().cloned().collect();
-        for rules in self.line_level.values() {
-            ids.extend(rules.iter());
+        let mut refs: HashSet<&RuleRef> = self.file_level.iter().collect();
+        for rules in self
+            .line_level
+            .values()
+            .chain(self.changeset_level.values())
+        {
+            refs.extend(rules.iter());
+        }
+        refs.extend(self.range_level.keys());
+        for acks in self.line_acks.values().chain(self.changeset_acks.values()) {
+            refs.extend(acks.keys());
+        }
+        refs.extend(self.unit_acks.keys());
+        refs.into_iter().filter_map(RuleRef::builtin).collect()
+    }
+
+    /// Narrow a file's suppressions to one migration unit.
+    ///
+    /// `unit_line` is the line of the unit's `<changeSet` element. Directives
+    /// scoped to that changeset become unit-wide; directives scoped to other
+    /// changesets are dropped. For plain SQL files there are no changeset
+    /// directives and the result equals `self`.
+    pub fn for_unit(&self, unit_line: usize) -> Suppressions {
+        let mut file_level = self.file_level.clone();
+        if let Some(rules) = self.changeset_level.get(&unit_line) {
+            file_level.extend(rules.iter().cloned());
+        }
+        let mut unit_acks = self.unit_acks.clone();
+        if let Some(acks) = self.changeset_acks.get(&unit_line) {
+            unit_acks.extend(acks.clone());
+        }
+        Suppressions {
+            file_level,
+            line_level: self.line_level.clone(),
+            range_level: self.range_level.clone(),
+            line_acks: self.line_acks.clone(),
+            unit_acks,
+            ..Default::default()
+        }
+    }
+
+    /// Downgrade acknowledged findings to INFO, recording the ticket and the
+    /// original severity.
+    ///
+    /// Call on a unit's suppressions (see [`Suppressions::for_unit`]) so that
+    /// changeset-level acknowledgments are included.
+    pub fn apply_acknowledgments(&self, findings: &mut [Finding]) {
+        for finding in findings {
+            let ticket = self
+                .line_acks
+                .get(&finding.start_line)
+                .and_then(|acks| acks.get(finding.rule_id.as_str()))
+                .or_else(|| self.unit_acks.get(finding.rule_id.as_str()));
+            if let Some(ticket) = ticket {
+                finding.acknowledged = Some(Acknowledgment {
+                    ticket: ticket.clone(),
+                    original_severity: finding.severity,
+                });
+                finding.severity = Severity::Info;
+            }
         }
-        ids
     }
 
     /// Check if a rule is suppressed at a given line.
     pub fn is_suppressed(&self, rule_id: crate::rules::RuleId, statement_line: usize) -> bool {
         // Check file-level suppressions
-        if self.file_level.contains(&rule_id) {
+        if self.file_level.contains(rule_id.as_str()) {
             return true;
         }
 
         // Check line-level suppressions
         if let Some(rules) = self.line_level.get(&statement_line)
-            && rules.contains(&rule_id)
+            && rules.contains(rule_id.as_str())
         {
             return true;
         }
 
-        false
+        // Check disable/enable blocks
+        self.range_level
+            .get(rule_id.as_str())
+            .is_some_and(|ranges| {
+                ranges
+                    .iter()
+                    .any(|&(start, end)| (start..=end).contains(&statement_line))
+            })
     }
 }
 
@@ -58,6 +168,14 @@ enum Directive<'a> {
     File(&'a str),
     /// `pgm-lint:suppress RULES` — next-statement suppression.
     NextStatement(&'a str),
+    /// `pgm-lint:acknowledge RULES [ticket=ID]` — downgrade to INFO.
+    Acknowledge(&'a str),
+    /// `pgm-lint:disable-next-line RULES` — suppress the following line.
+    NextLine(&'a str),
+    /// `pgm-lint:disable RULES` — open a suppressed block.
+    Disable(&'a str),
+    /// `pgm-lint:enable [RULES]` — close the matching blocks (all when empty).
+    Enable(&'a str),
 }
 
 /// Try to extract a suppression directive from a single line of source text.
@@ -93,6 +211,18 @@ fn parse_directive_body(body: &str) -> Option<Directive<'_>> {
     if let Some(rules_str) = body.strip_prefix("pgm-lint:suppress-file") {
         return Some(Directive::File(rules_str.trim()));
     }
+    if let Some(rest) = body.strip_prefix("pgm-lint:acknowledge") {
+        return Some(Directive::Acknowledge(rest.trim()));
+    }
+    if let Some(rules_str) = body.strip_prefix("pgm-lint:disable-next-line") {
+        return Some(Directive::NextLine(rules_str.trim()));
+    }
+    if let Some(rules_str) = body.strip_prefix("pgm-lint:disable") {
+        return Some(Directive::Disable(rules_str.trim()));
+    }
+    if let Some(rules_str) = body.strip_prefix("pgm-lint:enable") {
+        return Some(Directive::Enable(rules_str.trim()));
+    }
     if let Some(rules_str) = body.strip_prefix("pgm-lint:suppress") {
         return Some(Directive::NextStatement(rules_str.trim()));
     }
@@ -122,56 +252,211 @@ fn is_comment_line(trimmed: &str) -> bool {
 pub fn parse_suppressions(source: &str) -> Suppressions {
     let mut suppressions = Suppressions::default();
     let lines: Vec<&str> = source.lines().collect();
+    // 1-based line of the `<changeSet` currently open, if any.
+    let mut open_changeset: Option<usize> = None;
+    // Open `disable` blocks: rule → 1-based line of the directive.
+    let mut open_blocks: HashMap<RuleRef, usize> = HashMap::new();
 
     for (idx, line) in lines.iter().enumerate() {
         let trimmed = line.trim();
 
+        if trimmed.starts_with("<changeSet") {
+            open_changeset = Some(idx + 1);
+        }
+        if trimmed.starts_with("</changeSet") {
+            open_changeset = None;
+        }
+
         if let Some(directive) = extract_directive(trimmed) {
             match directive {
                 Directive::File(rules_str) => {
-                    for rule_id in rules_str.split(',') {
-                        let rule_id = rule_id.trim();
-                        if let Ok(rule_id) = RuleId::from_str(rule_id) {
-                            suppressions.file_level.insert(rule_id);
-                        } else {
-                            eprintln!("WARNING: unknown rule '{rule_id}' in config file, ignoring");
-                        }
+                    suppressions
+                        .file_level
+                        .extend(parse_rule_list(rules_str, "suppression"));
+                }
+                Directive::NextLine(rules_str) => {
+                    suppressions
+                        .line_level
+                        .entry(idx + 2)
+                        .or_default()
+                        .extend(parse_rule_list(rules_str, "suppression"));
+                }
+                Directive::Disable(rules_str) => {
+                    for rule_id in parse_rule_list(rules_str, "suppression") {
+                        open_blocks.entry(rule_id).or_insert(idx + 1);
+                    }
+                }
+                Directive::Enable(rules_str) => {
+                    let closed: Vec<(RuleRef, usize)> = if rules_str.is_empty() {
+                        open_blocks.drain().collect()
+                    } else {
+                        parse_rule_list(rules_str, "suppression")
+                            .filter_map(|id| open_blocks.remove(&id).map(|start| (id, start)))
+                            .collect()
+                    };
+                    for (rule_id, start) in closed {
+                        suppressions
+                            .range_level
+                            .entry(rule_id)
+                            .or_default()
+                            .push((start, idx + 1));
                     }
                 }
                 Directive::NextStatement(rules_str) => {
-                    // Find the next non-comment, non-empty line
-                    for (next_idx, next_line_raw) in lines.iter().enumerate().skip(idx + 1) {
-                        let next_line = next_line_raw.trim();
-                        if !next_line.is_empty() && !is_comment_line(next_line) {
-                            // Statement line is 1-based
-                            let statement_line = next_idx + 1;
-                            let rule_set =
-                                suppressions.line_level.entry(statement_line).or_default();
-
-                            for rule_id in rules_str.split(',') {
-                                let rule_id = rule_id.trim();
-                                if let Ok(rule_id) = RuleId::from_str(rule_id) {
-                                    rule_set.insert(rule_id);
-                                } else {
-                                    eprintln!(
-                                        "WARNING: unknown rule '{rule_id}' in suppression comment, ignoring"
-                                    );
-                                }
-                            }
-                            break;
-                        }
+                    let rules: Vec<RuleRef> = parse_rule_list(rules_str, "suppression").collect();
+                    if let Some(statement_line) = next_statement_line(&lines, idx) {
+                        let rule_set = suppressions.line_level.entry(statement_line).or_default();
+                        rule_set.extend(rules.iter().cloned());
+                    }
+                    if let Some(changeset_line) =
+                        xml_changeset(&lines, idx, trimmed, open_changeset)
+                    {
+                        let rule_set = suppressions
+                            .changeset_level
+                            .entry(changeset_line)
+                            .or_default();
+                        rule_set.extend(rules);
+                    }
+                }
+                Directive::Acknowledge(body) => {
+                    let (rules, ticket) = parse_acknowledge_body(body);
+                    let acks = if let Some(changeset_line) =
+                        xml_changeset(&lines, idx, trimmed, open_changeset)
+                    {
+                        suppressions.changeset_acks.entry(changeset_line)
+                    } else if let Some(statement_line) = next_statement_line(&lines, idx) {
+                        suppressions.line_acks.entry(statement_line)
+                    } else {
+                        continue;
+                    }
+                    .or_default();
+                    for rule_id in rules {
+                        acks.insert(rule_id, ticket.clone());
                     }
                 }
             }
         }
     }
 
+    // Blocks never re-enabled run to the end of the file.
+    for (rule_id, start) in open_blocks {
+        suppressions
+            .range_level
+            .entry(rule_id)
+            .or_default()
+            .push((start, usize::MAX));
+    }
+
     suppressions
 }
 
+/// The `<changeSet` line an XML directive comment on line `idx` applies to:
+/// the changeset it sits in, or the one directly after it. `None` for SQL
+/// comments and XML comments outside any changeset.
+fn xml_changeset(
+    lines: &[&str],
+    idx: usize,
+    trimmed: &str,
+    open_changeset: Option<usize>,
+) -> Option<usize> {
+    if !trimmed.starts_with("<!--") {
+        return None;
+    }
+    open_changeset.or_else(|| {
+        next_statement_line(lines, idx).filter(|&l| lines[l - 1].trim().starts_with("<changeSet"))
+    })
+}
+
+/// Find the 1-based line of the `<changeSet` element with the given `id`
+/// attribute, for loaders that know a changeset's id but not its line.
+pub fn changeset_line(source: &str, changeset_id: &str) -> Option<usize> {
+    let double = format!("id=\"{changeset_id}\"");
+    let single = format!("id='{changeset_id}'");
+    source
+        .lines()
+        .position(|line| {
+            let line = line.trim();
+            line.starts_with("<changeSet")
+                && line
+                    .split_whitespace()
+                    .any(|attr| attr.starts_with(&double) || attr.starts_with(&single))
+        })
+        .map(|idx| idx + 1)
+}
+
+/// Find the 1-based line of the next non-comment, non-empty line after `idx`.
+fn next_statement_line(lines: &[&str], idx: usize) -> Option<usize> {
+    lines
+        .iter()
+        .enumerate()
+        .skip(idx + 1)
+        .find(|(_, l)| {
+            let l = l.trim();
+            !l.is_empty() && !is_comment_line(l)
+        })
+        .map(|(next_idx, _)| next_idx + 1)
+}
+
+/// Parse a comma-separated rule list, warning about unknown built-in IDs.
+///
+/// A family wildcard such as `PGM3xx` (case-insensitive) expands to every
+/// lint rule in that family.
+fn parse_rule_list<'a>(rules_str: &'a str, origin: &'a str) -> impl Iterator<Item = RuleRef> + 'a {
+    rules_str.split(',').flat_map(move |rule_id| {
+        let rule_id = rule_id.trim();
+        if rule_id.is_empty() {
+            return Vec::new();
+        }
+        if let Some(family) = family_wildcard(rule_id) {
+            return RuleId::lint_rules()
+                .filter(|r| r.family_prefix() == family)
+                .map(RuleRef::from)
+                .collect();
+        }
+        if RuleId::from_str(rule_id).is_ok() {
+            return vec![RuleRef::new(rule_id)];
+        }
+        // Rule pack IDs are kept unchecked: packs are registered after the
+        // sources are read.
+        let rule = RuleRef::new(rule_id);
+        if rule
+            .namespace()
+            .is_some_and(|ns| !ns.eq_ignore_ascii_case("PGM"))
+        {
+            return vec![rule];
+        }
+        eprintln!("WARNING: unknown rule '{rule_id}' in {origin} comment, ignoring");
+        Vec::new()
+    })
+}
+
+/// The family prefix (`"3xx"`) of a wildcard like `PGM3xx`, if `rule_id` is one.
+fn family_wildcard(rule_id: &str) -> Option<&'static str> {
+    let family = rule_id
+        .get(..3)
+        .filter(|p| p.eq_ignore_ascii_case("PGM"))
+        .and(rule_id.get(3..))?;
+    crate::config::parse_rule_family(family).ok()
+}
+
+/// Split an acknowledge body (`PGM201,PGM202 ticket=DB-123`) into rule IDs
+/// and the optional ticket.
+fn parse_acknowledge_body(body: &str) -> (Vec<RuleRef>, Option<String>) {
+    let mut rules = Vec::new();
+    let mut ticket = None;
+    for token in body.split_whitespace() {
+        if let Some(t) = token.strip_prefix("ticket=") {
+            ticket = Some(t.to_string());
+        } else {
+            rules.extend(parse_rule_list(token, "acknowledge"));
+        }
+    }
+    (rules, ticket)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::rules::RuleId;
+    use crate::rules::{Rule, RuleId};
 
     use super::*;
 
@@ -490,4 +775,242 @@ ALTER TABLE foo ADD COLUMN baz int;
         assert!(s2.is_suppressed(RuleId::Pgm001, 4));
         assert!(!s2.is_suppressed(RuleId::Pgm001, 3));
     }
+
+    fn finding(rule_id: RuleId, line: usize) -> Finding {
+        Finding::new(
+            rule_id,
+            rule_id.default_severity(),
+            "msg".to_string(),
+            std::path::Path::new("f.sql"),
+            &crate::parser::SourceSpan::at(line, line),
+        )
+    }
+
+    #[test]
+    fn test_acknowledge_next_statement_with_ticket() {
+        let source =
+            "-- pgm-lint:acknowledge PGM201 ticket=DB-123\nDROP TABLE foo;\nDROP TABLE bar;";
+        let suppressions = parse_suppressions(source);
+
+        let mut findings = vec![finding(RuleId::Pgm201, 2), finding(RuleId::Pgm201, 3)];
+        suppressions
+            .for_unit(1)
+            .apply_acknowledgments(&mut findings);
+
+        assert_eq!(findings[0].severity, Severity::Info);
+        assert_eq!(
+            findings[0].acknowledged,
+            Some(Acknowledgment {
+                ticket: Some("DB-123".to_string()),
+                original_severity: RuleId::Pgm201.default_severity(),
+            })
+        );
+        assert_eq!(findings[1].acknowledged, None);
+        assert!(!suppressions.is_suppressed(RuleId::Pgm201, 2));
+        assert!(suppressions.rule_ids().contains(&RuleId::Pgm201));
+    }
+
+    #[test]
+    fn test_acknowledge_xml_inside_changeset_covers_whole_changeset() {
+        let source = r#"<databaseChangeLog>
+    <changeSet id="1" author="dev">
+        <sql>DROP TABLE foo;</sql>
+    </changeSet>
+    <changeSet id="2" author="dev">
+        <!-- pgm-lint:acknowledge PGM201,PGM202 ticket=DB-123 -->
+        <dropTable tableName="bar"/>
+        <dropTable tableName="baz"/>
+    </changeSet>
+</databaseChangeLog>"#;
+        let suppressions = parse_suppressions(source);
+
+        // Findings in changeset 2 (opened on line 5), at any statement line.
+        let mut findings = vec![finding(RuleId::Pgm201, 1), finding(RuleId::Pgm201, 2)];
+        suppressions
+            .for_unit(5)
+            .apply_acknowledgments(&mut findings);
+        assert!(findings.iter().all(|f| f.severity == Severity::Info));
+        assert!(
+            findings
+                .iter()
+                .all(|f| f.acknowledged.as_ref().unwrap().ticket.as_deref() == Some("DB-123"))
+        );
+
+        // Changeset 1 (opened on line 2) is not acknowledged.
+        let mut findings = vec![finding(RuleId::Pgm201, 1)];
+        suppressions
+            .for_unit(2)
+            .apply_acknowledgments(&mut findings);
+        assert_eq!(findings[0].acknowledged, None);
+    }
+
+    #[test]
+    fn test_acknowledge_xml_preceding_changeset() {
+        let source = r#"<databaseChangeLog>
+    <!-- pgm-lint:acknowledge PGM201 -->
+    <changeSet id="1" author="dev">
+        <dropTable tableName="foo"/>
+    </changeSet>
+</databaseChangeLog>"#;
+        let suppressions = parse_suppressions(source);
+
+        let mut findings = vec![finding(RuleId::Pgm201, 1)];
+        suppressions
+            .for_unit(3)
+            .apply_acknowledgments(&mut findings);
+        assert_eq!(
+            findings[0].acknowledged,
+            Some(Acknowledgment {
+                ticket: None,
+                original_severity: RuleId::Pgm201.default_severity(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_acknowledge_other_rule_untouched() {
+        let source = "-- pgm-lint:acknowledge PGM201\nDROP TABLE foo;";
+        let suppressions = parse_suppressions(source);
+
+        let mut findings = vec![finding(RuleId::Pgm001, 2)];
+        suppressions
+            .for_unit(1)
+            .apply_acknowledgments(&mut findings);
+        assert_eq!(findings[0].severity, RuleId::Pgm001.default_severity());
+        assert_eq!(findings[0].acknowledged, None);
+    }
+
+    #[test]
+    fn test_xml_suppress_inside_changeset_covers_whole_changeset() {
+        let source = r#"<databaseChangeLog>
+    <changeSet id="1" author="dev">
+        <!-- pgm-lint:suppress PGM001 -->
+        <sql>CREATE INDEX idx_a ON a (x);</sql>
+        <sql>CREATE INDEX idx_b ON b (x);</sql>
+    </changeSet>
+    <changeSet id="2" author="dev">
+        <sql>CREATE INDEX idx_c ON c (x);</sql>
+    </changeSet>
+</databaseChangeLog>"#;
+        let suppressions = parse_suppressions(source);
+
+        let first = suppressions.for_unit(2);
+        assert!(first.is_suppressed(RuleId::Pgm001, 4));
+        assert!(first.is_suppressed(RuleId::Pgm001, 5));
+        assert!(!first.is_suppressed(RuleId::Pgm501, 5));
+
+        let second = suppressions.for_unit(7);
+        assert!(!second.is_suppressed(RuleId::Pgm001, 8));
+    }
+
+    #[test]
+    fn test_xml_suppress_preceding_changeset_covers_whole_changeset() {
+        let source = r#"<databaseChangeLog>
+    <!-- pgm-lint:suppress PGM001 -->
+    <changeSet id="1" author="dev">
+        <sql>CREATE INDEX idx_a ON a (x);</sql>
+        <sql>CREATE INDEX idx_b ON b (x);</sql>
+    </changeSet>
+</databaseChangeLog>"#;
+        let suppressions = parse_suppressions(source).for_unit(3);
+        assert!(suppressions.is_suppressed(RuleId::Pgm001, 3));
+        assert!(suppressions.is_suppressed(RuleId::Pgm001, 5));
+    }
+
+    #[test]
+    fn test_sql_suppress_is_not_changeset_scoped() {
+        let source =
+            "-- pgm-lint:suppress PGM001\nCREATE INDEX a ON t (x);\nCREATE INDEX b ON t (y);";
+        let suppressions = parse_suppressions(source).for_unit(1);
+        assert!(suppressions.is_suppressed(RuleId::Pgm001, 2));
+        assert!(!suppressions.is_suppressed(RuleId::Pgm001, 3));
+    }
+
+    #[test]
+    fn test_changeset_line_by_id() {
+        let source = r#"<databaseChangeLog>
+    <changeSet id="1" author="dev">
+    </changeSet>
+    <changeSet author="dev" id='10'>
+    </changeSet>
+</databaseChangeLog>"#;
+        assert_eq!(changeset_line(source, "1"), Some(2));
+        assert_eq!(changeset_line(source, "10"), Some(4));
+        assert_eq!(changeset_line(source, "2"), None);
+    }
+
+    #[test]
+    fn test_disable_next_line_targets_following_line_only() {
+        let source = "-- pgm-lint:disable-next-line PGM001\nCREATE INDEX a ON t (x);\nCREATE INDEX b ON t (y);";
+        let suppressions = parse_suppressions(source);
+        assert!(suppressions.is_suppressed(RuleId::Pgm001, 2));
+        assert!(!suppressions.is_suppressed(RuleId::Pgm001, 3));
+        assert!(!suppressions.is_suppressed(RuleId::Pgm002, 2));
+    }
+
+    #[test]
+    fn test_disable_enable_block() {
+        let source = r#"CREATE INDEX a ON t (x);
+-- pgm-lint:disable PGM001, PGM002
+CREATE INDEX b ON t (y);
+CREATE INDEX c ON t (z);
+-- pgm-lint:enable PGM001
+CREATE INDEX d ON t (w);
+"#;
+        let suppressions = parse_suppressions(source);
+        assert!(!suppressions.is_suppressed(RuleId::Pgm001, 1));
+        assert!(suppressions.is_suppressed(RuleId::Pgm001, 3));
+        assert!(suppressions.is_suppressed(RuleId::Pgm001, 4));
+        assert!(!suppressions.is_suppressed(RuleId::Pgm001, 6));
+        // PGM002 was never re-enabled: suppressed to the end of the file.
+        assert!(suppressions.is_suppressed(RuleId::Pgm002, 6));
+        assert!(suppressions.for_unit(1).is_suppressed(RuleId::Pgm002, 6));
+    }
+
+    #[test]
+    fn test_enable_without_rules_closes_all_blocks() {
+        let source = "-- pgm-lint:disable PGM001\n-- pgm-lint:disable PGM002\nSELECT 1;\n-- pgm-lint:enable\nSELECT 2;";
+        let suppressions = parse_suppressions(source);
+        assert!(suppressions.is_suppressed(RuleId::Pgm001, 3));
+        assert!(suppressions.is_suppressed(RuleId::Pgm002, 3));
+        assert!(!suppressions.is_suppressed(RuleId::Pgm001, 5));
+        assert!(!suppressions.is_suppressed(RuleId::Pgm002, 5));
+    }
+
+    #[test]
+    fn test_family_wildcard_expands_to_family() {
+        let source = "-- pgm-lint:disable pgm3xx\nALTER TABLE t ALTER COLUMN x TYPE bigint;\n-- pgm-lint:enable PGM3XX\nSELECT 1;";
+        let suppressions = parse_suppressions(source);
+        for rule in RuleId::lint_rules().filter(|r| r.family_prefix() == "3xx") {
+            assert!(suppressions.is_suppressed(rule, 2), "{rule}");
+            assert!(!suppressions.is_suppressed(rule, 4), "{rule}");
+        }
+        assert!(!suppressions.is_suppressed(RuleId::Pgm001, 2));
+        assert!(suppressions.rule_ids().contains(&RuleId::Pgm301));
+    }
+
+    #[test]
+    fn test_family_wildcard_in_suppress_file() {
+        let suppressions = parse_suppressions("-- pgm-lint:suppress-file PGM1xx\nSELECT 1;");
+        assert!(suppressions.is_suppressed(RuleId::Pgm101, 2));
+        assert!(!suppressions.is_suppressed(RuleId::Pgm001, 2));
+        assert!(family_wildcard("PGM9xx").is_none());
+        assert!(family_wildcard("PGM001").is_none());
+    }
+
+    #[test]
+    fn test_rule_pack_ids_accepted() {
+        use crate::rules::test_helpers::TENANT_ID;
+
+        let source = "-- pgm-lint:suppress ORG001, PGM501\nCREATE TABLE t (id int);\n\
+                      -- pgm-lint:suppress NOPE\nSELECT 1;";
+        let suppressions = parse_suppressions(source);
+        assert!(suppressions.is_suppressed(RuleId::Custom(&TENANT_ID), 2));
+        assert!(suppressions.is_suppressed(RuleId::Pgm501, 2));
+        assert!(!suppressions.is_suppressed(RuleId::Custom(&TENANT_ID), 4));
+        assert_eq!(
+            suppressions.rule_ids(),
+            std::collections::HashSet::from([RuleId::Pgm501])
+        );
+    }
 }
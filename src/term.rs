@@ -0,0 +1,163 @@
+//! Terminal capabilities
+//!
+//! Decides, in one place, whether output written to a terminal may use ANSI
+//! colors and whether it may draw indicators that redraw a line (progress).
+//! The environment is honored the usual way:
+//!
+//! - `NO_COLOR` (any non-empty value) turns colors off, whatever else is set.
+//! - `CLICOLOR_FORCE` (non-empty, not `0`) turns colors on even when the
+//!   stream is redirected, e.g. in CI logs that render ANSI codes.
+//! - `CLICOLOR=0` turns colors off.
+//! - Otherwise colors are on only when the stream is a terminal and `TERM`
+//!   is not `dumb`.
+//!
+//! Redrawing indicators are never forced: they need a real terminal.
+//! Reports written to files are always plain.
+
+use std::borrow::Cow;
+use std::io::IsTerminal;
+
+/// Output stream whose capabilities are detected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+/// What output to one stream may use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TermCaps {
+    /// ANSI colors and bold text.
+    pub color: bool,
+    /// The stream is an interactive terminal, so lines may be redrawn in
+    /// place (progress indicators).
+    pub interactive: bool,
+}
+
+/// Text styles used across the CLI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Bold,
+    /// BLOCKER and CRITICAL findings.
+    Error,
+    /// MAJOR findings.
+    Warning,
+    /// MINOR and INFO findings.
+    Note,
+    /// Secondary text such as acknowledgments.
+    Dim,
+}
+
+impl Style {
+    fn ansi_code(self) -> &'static str {
+        match self {
+            Style::Bold => "1",
+            Style::Error => "1;31",
+            Style::Warning => "33",
+            Style::Note => "36",
+            Style::Dim => "2",
+        }
+    }
+}
+
+impl TermCaps {
+    /// No colors, no redrawing. Used for files and embedders.
+    pub const PLAIN: Self = Self {
+        color: false,
+        interactive: false,
+    };
+
+    /// Detect the capabilities of `stream` from the process environment.
+    pub fn detect(stream: Stream) -> Self {
+        let is_terminal = match stream {
+            Stream::Stdout => std::io::stdout().is_terminal(),
+            Stream::Stderr => std::io::stderr().is_terminal(),
+        };
+        Self::from_env(
+            |name| std::env::var_os(name).map(|v| v.to_string_lossy().into_owned()),
+            is_terminal,
+        )
+    }
+
+    /// Capabilities for a stream that is (or is not) a terminal, reading
+    /// environment variables through `var`.
+    pub fn from_env(var: impl Fn(&str) -> Option<String>, is_terminal: bool) -> Self {
+        let non_empty = |name: &str| var(name).filter(|v| !v.is_empty());
+        let interactive = is_terminal && var("TERM").as_deref() != Some("dumb");
+        let color = if non_empty("NO_COLOR").is_some() {
+            false
+        } else if non_empty("CLICOLOR_FORCE").is_some_and(|v| v != "0") {
+            true
+        } else if var("CLICOLOR").as_deref() == Some("0") {
+            false
+        } else {
+            interactive
+        };
+        Self { color, interactive }
+    }
+
+    /// `text` in `style`, or unchanged when colors are off.
+    pub fn paint<'a>(&self, style: Style, text: &'a str) -> Cow<'a, str> {
+        if self.color {
+            Cow::Owned(format!("\x1b[{}m{text}\x1b[0m", style.ansi_code()))
+        } else {
+            Cow::Borrowed(text)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn caps(vars: &[(&str, &str)], is_terminal: bool) -> TermCaps {
+        TermCaps::from_env(
+            |name| {
+                vars.iter()
+                    .find(|(k, _)| *k == name)
+                    .map(|(_, v)| v.to_string())
+            },
+            is_terminal,
+        )
+    }
+
+    #[rstest]
+    #[case::terminal(&[], true, true)]
+    #[case::redirected(&[], false, false)]
+    #[case::no_color(&[("NO_COLOR", "1")], true, false)]
+    #[case::empty_no_color_ignored(&[("NO_COLOR", "")], true, true)]
+    #[case::no_color_beats_force(&[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")], true, false)]
+    #[case::force_when_redirected(&[("CLICOLOR_FORCE", "1")], false, true)]
+    #[case::force_zero_ignored(&[("CLICOLOR_FORCE", "0")], false, false)]
+    #[case::clicolor_zero(&[("CLICOLOR", "0")], true, false)]
+    #[case::force_beats_clicolor_zero(&[("CLICOLOR", "0"), ("CLICOLOR_FORCE", "1")], false, true)]
+    #[case::dumb_terminal(&[("TERM", "dumb")], true, false)]
+    fn test_color_detection(
+        #[case] vars: &[(&str, &str)],
+        #[case] is_terminal: bool,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(caps(vars, is_terminal).color, expected);
+    }
+
+    #[test]
+    fn test_interactive_is_never_forced() {
+        assert!(!caps(&[("CLICOLOR_FORCE", "1")], false).interactive);
+        assert!(!caps(&[("TERM", "dumb")], true).interactive);
+        assert!(caps(&[("NO_COLOR", "1")], true).interactive);
+    }
+
+    #[test]
+    fn test_paint() {
+        let on = TermCaps {
+            color: true,
+            interactive: false,
+        };
+        assert_eq!(
+            on.paint(Style::Error, "CRITICAL"),
+            "\x1b[1;31mCRITICAL\x1b[0m"
+        );
+        assert_eq!(TermCaps::PLAIN.paint(Style::Error, "CRITICAL"), "CRITICAL");
+    }
+}
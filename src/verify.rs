@@ -0,0 +1,625 @@
+//! Dry-run apply against a disposable PostgreSQL database
+//! (`--verify-against-db`, feature `verify-db`).
+//!
+//! Static rules only see what the replayed catalog models. Some failures
+//! only show up when PostgreSQL runs the statement: a duplicate object the
+//! catalog does not track, a cast that fails on the column type, a function
+//! body that does not compile. The verifier replays the migration history
+//! into a database with `psql --single-transaction`, inside one transaction
+//! that is rolled back at the end, and reports each statement of a changed
+//! unit that fails as PGM036.
+//!
+//! Each statement runs with `EXECUTE` in its own `DO` block, whose exception
+//! handler turns a failure into a warning naming the statement, so a
+//! failing statement is undone and the rest of the history still runs.
+//! Inside the block the statement is a dollar-quoted string, so psql never
+//! interprets it. `ON_ERROR_STOP` is set: any other error stops psql, and
+//! the transaction is never committed.
+//!
+//! Migrations from an untrusted pull request must not reach the CI host or
+//! escape the transaction. Statements that did not parse are not sent, and
+//! a unit whose statements contain a psql backslash command or transaction
+//! control outside quotes and comments is refused as a whole
+//! ([`VerifyError::Refused`]).
+//!
+//! Statements that cannot run in a transaction block are adjusted or
+//! skipped: `CONCURRENTLY` is dropped from `CREATE INDEX` and `DROP INDEX`,
+//! and transaction control and `VACUUM` are not sent. Down migrations are
+//! not applied. With `output.redact_literals`, literals are masked in the
+//! server errors reported.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use thiserror::Error;
+
+use crate::input::MigrationUnit;
+use crate::output::redact::redact_error_message;
+use crate::parser::ir::{IrNode, Located, SourceSpan, StatementKind};
+use crate::parser::lex::{dollar_tag, is_ident_byte, skip_block_comment, skip_string};
+use crate::rules::{Finding, Rule, RuleId, UnitRef};
+
+/// Prefix of the warnings that report a failed statement.
+const MARKER: &str = "@@pgm-lint-verify ";
+
+/// Statements that begin, end, or split a transaction.
+const TRANSACTION_CONTROL: &[&str] = &[
+    "ABORT",
+    "BEGIN",
+    "COMMIT",
+    "END",
+    "PREPARE",
+    "RELEASE",
+    "ROLLBACK",
+    "SAVEPOINT",
+    "START",
+];
+
+#[derive(Debug, Error)]
+pub enum VerifyError {
+    #[error("Failed to execute psql at '{path}': {source}")]
+    Spawn {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("psql could not run the verification script: {message}")]
+    Psql { message: String },
+
+    #[error("Refusing to send {file}:{line} to the database: it contains {reason}")]
+    Refused {
+        file: PathBuf,
+        line: usize,
+        reason: &'static str,
+    },
+}
+
+/// A statement sent to the database.
+#[derive(Debug, Clone, PartialEq)]
+struct ScriptStatement {
+    file: PathBuf,
+    span: SourceSpan,
+    /// Whether a failure is reported (the statement belongs to a changed
+    /// unit and does not suppress PGM036).
+    report: bool,
+    unit: UnitRef,
+}
+
+/// The result of a verification run.
+#[derive(Debug, Default)]
+pub struct VerifyOutcome {
+    /// PGM036 findings for failing statements of changed units.
+    pub findings: Vec<Finding>,
+    /// Statements of unchanged units that failed. The database did not match
+    /// what the history expects, so findings after them may be noise.
+    pub history_failures: Vec<(PathBuf, usize, String)>,
+}
+
+/// A `psql` script replaying migration units in one rolled-back transaction.
+#[derive(Debug, Clone)]
+pub struct VerifyScript {
+    script: String,
+    statements: Vec<ScriptStatement>,
+    redact_literals: bool,
+}
+
+impl Default for VerifyScript {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VerifyScript {
+    pub fn new() -> Self {
+        Self {
+            script: "\\set VERBOSITY terse\n\
+                     SET client_min_messages = warning;\n"
+                .to_string(),
+            statements: Vec::new(),
+            redact_literals: false,
+        }
+    }
+
+    /// Mask literals in the server errors reported
+    /// (`output.redact_literals`).
+    pub fn with_redact_literals(mut self, redact: bool) -> Self {
+        self.redact_literals = redact;
+        self
+    }
+
+    /// Append the statements of `unit`. Failures are reported when `changed`
+    /// is set, unless the statement suppresses PGM036.
+    ///
+    /// Nothing is appended when the unit is refused: when a statement to be
+    /// sent contains a psql backslash command or transaction control.
+    pub fn push_unit(&mut self, unit: &MigrationUnit, changed: bool) -> Result<(), VerifyError> {
+        if unit.is_down {
+            return Ok(());
+        }
+        let mut script = String::new();
+        let mut statements = Vec::new();
+        let mut previous: Option<&Located<IrNode>> = None;
+        for stmt in &unit.statements {
+            // Nodes expanded from one statement share its text; run it once.
+            if previous.is_some_and(|p| p.span == stmt.span && p.raw_sql == stmt.raw_sql) {
+                continue;
+            }
+            previous = Some(stmt);
+            let Some(sql) = statement_sql(stmt) else {
+                continue;
+            };
+            if let Some(reason) = unsafe_content(&sql) {
+                return Err(VerifyError::Refused {
+                    file: unit.source_file.clone(),
+                    line: stmt.span.start_line,
+                    reason,
+                });
+            }
+            let report = changed
+                && !unit
+                    .suppressions
+                    .is_suppressed(RuleId::Pgm036, stmt.span.start_line);
+            script.push_str(&guarded(self.statements.len() + statements.len(), &sql));
+            statements.push(ScriptStatement {
+                file: unit.source_file.clone(),
+                span: stmt.span.clone(),
+                report,
+                unit: UnitRef::from(unit),
+            });
+        }
+        self.script.push_str(&script);
+        self.statements.append(&mut statements);
+        Ok(())
+    }
+
+    /// Whether any statement would be reported.
+    pub fn has_changed_statements(&self) -> bool {
+        self.statements.iter().any(|s| s.report)
+    }
+
+    /// Run the script with `psql` against `conninfo` (a connection string or
+    /// URI) and report the failing statements.
+    pub fn run(
+        &self,
+        rule: &dyn Rule,
+        psql: &Path,
+        conninfo: &str,
+    ) -> Result<VerifyOutcome, VerifyError> {
+        let mut child = Command::new(psql)
+            .args([
+                "--no-psqlrc",
+                "--quiet",
+                "--single-transaction",
+                "--variable",
+                "ON_ERROR_STOP=1",
+                "--dbname",
+                conninfo,
+                "--file",
+                "-",
+            ])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|source| VerifyError::Spawn {
+                path: psql.to_path_buf(),
+                source,
+            })?;
+
+        // `--single-transaction` commits at the end; roll back first, so
+        // that commit finds no transaction.
+        let script = format!("{}ROLLBACK;\n", self.script);
+        // Feed stdin from another thread while stderr is drained here: on a
+        // long history with many failures a full stderr pipe would block
+        // psql while we block writing the rest of the script.
+        let writer = child.stdin.take().map(|mut stdin| {
+            std::thread::spawn(move || {
+                // A write error means psql exited early; its status says why.
+                let _ = stdin.write_all(script.as_bytes());
+            })
+        });
+        let output = child.wait_with_output();
+        if let Some(writer) = writer {
+            let _ = writer.join();
+        }
+        let output = output.map_err(|source| VerifyError::Spawn {
+            path: psql.to_path_buf(),
+            source,
+        })?;
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.status.success() {
+            return Err(VerifyError::Psql {
+                message: stderr.trim().to_string(),
+            });
+        }
+
+        Ok(self.outcome(rule, &stderr))
+    }
+
+    /// Attribute the failures reported in `stderr` to their statements.
+    fn outcome(&self, rule: &dyn Rule, stderr: &str) -> VerifyOutcome {
+        let mut outcome = VerifyOutcome::default();
+        for (index, error) in parse_errors(stderr) {
+            let Some(stmt) = self.statements.get(index) else {
+                continue;
+            };
+            let error = if self.redact_literals {
+                redact_error_message(&error)
+            } else {
+                error
+            };
+            if !stmt.report {
+                outcome
+                    .history_failures
+                    .push((stmt.file.clone(), stmt.span.start_line, error));
+                continue;
+            }
+            let mut finding = rule
+                .make_finding(
+                    format!(
+                        "PostgreSQL rejected this statement when the migrations were applied \
+                         to the verification database: {error}. The migration will fail at \
+                         deploy."
+                    ),
+                    &stmt.file,
+                    &stmt.span,
+                )
+                .with_param("error", error);
+            finding.unit = Some(stmt.unit.clone());
+            outcome.findings.push(finding);
+        }
+        outcome
+    }
+}
+
+/// The SQL to send for `stmt`, or `None` when it is not sent.
+fn statement_sql(stmt: &Located<IrNode>) -> Option<String> {
+    let sql = stmt.raw_sql.trim().trim_end_matches(';').trim_end();
+    if sql.is_empty() {
+        return None;
+    }
+    let sql = match &stmt.node {
+        IrNode::Ignored {
+            kind: StatementKind::Transaction | StatementKind::Vacuum,
+            ..
+        }
+        | IrNode::VacuumAllTables
+        | IrNode::Unparseable { .. } => return None,
+        IrNode::CreateIndex(ci) if ci.concurrent => remove_keyword(sql, "CONCURRENTLY"),
+        IrNode::DropIndex(di) if di.concurrent => remove_keyword(sql, "CONCURRENTLY"),
+        _ => sql.to_string(),
+    };
+    Some(format!("{sql};"))
+}
+
+/// Remove the first occurrence of `keyword` as a whole word, ignoring case.
+fn remove_keyword(sql: &str, keyword: &str) -> String {
+    let upper = sql.to_ascii_uppercase();
+    let is_word_byte = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    let mut from = 0;
+    while let Some(pos) = upper[from..].find(keyword).map(|p| p + from) {
+        let end = pos + keyword.len();
+        let before_ok = pos == 0 || !is_word_byte(upper.as_bytes()[pos - 1]);
+        let after_ok = end == upper.len() || !is_word_byte(upper.as_bytes()[end]);
+        if before_ok && after_ok {
+            return format!("{}{}", &sql[..pos], sql[end..].trim_start());
+        }
+        from = end;
+    }
+    sql.to_string()
+}
+
+/// `sql` as a `DO` block that runs it with `EXECUTE` and reports a failure
+/// as a warning naming statement `index`. The dollar-quote tags are chosen
+/// so that `sql` cannot end them.
+fn guarded(index: usize, sql: &str) -> String {
+    let tag = (0..)
+        .map(|n| format!("pgml{n}"))
+        .find(|tag| !sql.contains(&format!("${tag}")))
+        .unwrap_or_default();
+    format!(
+        "DO ${tag}_do$ BEGIN EXECUTE ${tag}${sql}${tag}$; \
+         EXCEPTION WHEN OTHERS THEN RAISE WARNING '{MARKER}%: %', {index}, SQLERRM; \
+         END ${tag}_do$;\n"
+    )
+}
+
+/// Why `sql` must not be sent, if it contains a psql backslash command, or
+/// a statement that is transaction control, outside quotes and comments.
+fn unsafe_content(sql: &str) -> Option<&'static str> {
+    let bytes = sql.as_bytes();
+    let mut statement_start = true;
+    let mut i = 0;
+    while i < bytes.len() {
+        let prev_is_ident = i > 0 && is_ident_byte(bytes[i - 1]);
+        match bytes[i] {
+            b'\\' => return Some("a psql backslash command"),
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = sql[i..].find('\n').map_or(sql.len(), |n| i + n);
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => i = skip_block_comment(bytes, i + 2),
+            b'\'' => {
+                let backslash_escapes = i > 0 && matches!(bytes[i - 1], b'E' | b'e');
+                i = skip_string(bytes, i + 1, backslash_escapes);
+                statement_start = false;
+            }
+            b'"' => {
+                i = sql[i + 1..].find('"').map_or(sql.len(), |n| i + n + 2);
+                statement_start = false;
+            }
+            b'$' if !prev_is_ident => {
+                i = match dollar_tag(&sql[i..]) {
+                    Some(tag) => {
+                        let body = i + tag.len();
+                        sql[body..]
+                            .find(tag)
+                            .map_or(sql.len(), |n| body + n + tag.len())
+                    }
+                    None => i + 1,
+                };
+                statement_start = false;
+            }
+            b';' => {
+                statement_start = true;
+                i += 1;
+            }
+            b if b.is_ascii_whitespace() => i += 1,
+            _ if statement_start => {
+                let end = i + bytes[i..].iter().take_while(|&&b| is_ident_byte(b)).count();
+                let word = &sql[i..end.max(i + 1)];
+                if TRANSACTION_CONTROL
+                    .iter()
+                    .any(|kw| word.eq_ignore_ascii_case(kw))
+                {
+                    return Some("transaction control");
+                }
+                statement_start = false;
+                i = end.max(i + 1);
+            }
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// `(statement index, error message)` for each failure warning in `stderr`.
+fn parse_errors(stderr: &str) -> Vec<(usize, String)> {
+    stderr
+        .lines()
+        .filter_map(|line| {
+            let (_, warning) = line.split_once("WARNING:")?;
+            let (index, message) = warning
+                .trim_start()
+                .strip_prefix(MARKER)?
+                .split_once(": ")?;
+            Some((index.parse().ok()?, message.trim().to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::ir::{CreateIndex, QualifiedName};
+    use crate::rules::test_helpers::located_at;
+
+    fn unit(statements: Vec<Located<IrNode>>) -> MigrationUnit {
+        MigrationUnit {
+            id: "V002".to_string(),
+            source_file: PathBuf::from("migrations/V002.sql"),
+            statements,
+            source_line_offset: 1,
+            run_in_transaction: true,
+            is_down: false,
+            suppressions: Default::default(),
+            changeset: Default::default(),
+        }
+    }
+
+    fn with_sql(mut stmt: Located<IrNode>, sql: &str) -> Located<IrNode> {
+        stmt.raw_sql = sql.into();
+        stmt
+    }
+
+    #[test]
+    fn test_script_skips_and_adjusts_non_transactional_statements() {
+        let mut index = CreateIndex::test(
+            Some("idx_orders_status".to_string()),
+            QualifiedName::unqualified("orders"),
+        );
+        index.concurrent = true;
+        let stmts = vec![
+            with_sql(
+                located_at(
+                    IrNode::Ignored {
+                        kind: StatementKind::Transaction,
+                        raw_sql: "BEGIN".to_string(),
+                    },
+                    1,
+                ),
+                "BEGIN",
+            ),
+            with_sql(
+                located_at(index.into(), 2),
+                "CREATE INDEX concurrently idx_orders_status ON orders (status);",
+            ),
+        ];
+        let mut script = VerifyScript::new();
+        script.push_unit(&unit(stmts), true).expect("push");
+
+        assert!(
+            script.script.contains(
+                "EXECUTE $pgml0$CREATE INDEX idx_orders_status ON orders (status);$pgml0$"
+            )
+        );
+        // The transaction is psql's `--single-transaction`.
+        assert!(!script.script.contains("BEGIN;"));
+        assert_eq!(script.statements.len(), 1);
+        assert!(script.has_changed_statements());
+    }
+
+    fn unknown(line: usize, sql: &str) -> Located<IrNode> {
+        with_sql(
+            located_at(
+                IrNode::Ignored {
+                    kind: StatementKind::Unknown,
+                    raw_sql: String::new(),
+                },
+                line,
+            ),
+            sql,
+        )
+    }
+
+    #[test]
+    fn test_unparseable_statements_not_sent() {
+        let stmts = vec![
+            with_sql(
+                located_at(
+                    IrNode::Unparseable {
+                        raw_sql: String::new(),
+                        table_hint: None,
+                    },
+                    1,
+                ),
+                "\\! touch /tmp/pwned",
+            ),
+            unknown(2, "SELECT 1"),
+        ];
+        let mut script = VerifyScript::new();
+        script.push_unit(&unit(stmts), true).expect("push");
+
+        assert!(!script.script.contains("pwned"));
+        assert_eq!(script.statements.len(), 1);
+    }
+
+    #[test]
+    fn test_unit_with_meta_command_or_transaction_control_refused() {
+        for sql in [
+            "SELECT 1 \\! id",
+            "SELECT 1; COMMIT",
+            "/* x */ rollback",
+            "SELECT 1; end",
+        ] {
+            let mut script = VerifyScript::new();
+            let before = script.script.clone();
+            let result =
+                script.push_unit(&unit(vec![unknown(1, "SELECT 2"), unknown(2, sql)]), true);
+            assert!(
+                matches!(result, Err(VerifyError::Refused { line: 2, .. })),
+                "{sql}"
+            );
+            assert_eq!(script.script, before, "{sql}");
+            assert!(script.statements.is_empty(), "{sql}");
+        }
+    }
+
+    #[test]
+    fn test_quoted_meta_commands_and_keywords_allowed() {
+        for sql in [
+            "SELECT E'a\\\\b', 'COMMIT', \"end\"",
+            "CREATE FUNCTION f() RETURNS void AS $$ BEGIN RAISE NOTICE '\\!'; END $$ LANGUAGE plpgsql",
+            "SELECT 1 -- \\! id",
+            "UPDATE t SET begin_at = now()",
+        ] {
+            assert_eq!(unsafe_content(sql), None, "{sql}");
+        }
+    }
+
+    #[test]
+    fn test_guard_tags_not_in_statement() {
+        let guarded = guarded(0, "SELECT $pgml0$x$pgml0$");
+        assert!(
+            guarded
+                .starts_with("DO $pgml1_do$ BEGIN EXECUTE $pgml1$SELECT $pgml0$x$pgml0$$pgml1$;")
+        );
+    }
+
+    #[test]
+    fn test_errors_attributed_to_reported_index() {
+        let stderr = "psql:<stdin>:3: WARNING:  @@pgm-lint-verify 1: relation \"orders\" already exists\n\
+                      psql:<stdin>:9: WARNING:  there is no transaction in progress\n";
+        assert_eq!(
+            parse_errors(stderr),
+            vec![(1, "relation \"orders\" already exists".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_errors_redacted_when_requested() {
+        let mut script = VerifyScript::new().with_redact_literals(true);
+        script
+            .push_unit(&unit(vec![unknown(1, "SELECT '4111-1111'::int")]), true)
+            .expect("push");
+
+        let stderr = "psql:<stdin>:3: WARNING:  @@pgm-lint-verify 0: \
+                      invalid input syntax for type integer: \"4111-1111\"\n";
+        let outcome = script.outcome(&RuleId::Pgm036, stderr);
+
+        let finding = &outcome.findings[0];
+        assert!(!finding.message.contains("4111"), "{}", finding.message);
+        assert_eq!(
+            finding.params.get("error").map(String::as_str),
+            Some("invalid input syntax for type integer: '?'")
+        );
+    }
+
+    #[test]
+    fn test_history_failures_not_reported() {
+        let mut script = VerifyScript::new();
+        script
+            .push_unit(&unit(vec![unknown(1, "SELECT 1/0")]), false)
+            .expect("push");
+        script
+            .push_unit(&unit(vec![unknown(3, "SELECT 'x'::int")]), true)
+            .expect("push");
+
+        let stderr = "psql:<stdin>:3: WARNING:  @@pgm-lint-verify 0: division by zero\n\
+                      psql:<stdin>:4: WARNING:  @@pgm-lint-verify 1: \
+                      invalid input syntax for type integer: \"x\"\n";
+        let outcome = script.outcome(&RuleId::Pgm036, stderr);
+
+        assert_eq!(outcome.history_failures.len(), 1);
+        assert_eq!(outcome.findings.len(), 1);
+        assert_eq!(outcome.findings[0].start_line, 3);
+        assert_eq!(
+            outcome.findings[0].severity,
+            crate::rules::Severity::Blocker
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_does_not_block_on_large_stderr() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // A stand-in for psql that echoes every script line to stderr, so
+        // stderr fills long before the script has been written.
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let psql = dir.path().join("psql");
+        std::fs::write(
+            &psql,
+            "#!/bin/sh\nwhile IFS= read -r line; do echo \"$line\" >&2; done\n",
+        )
+        .expect("write");
+        std::fs::set_permissions(&psql, std::fs::Permissions::from_mode(0o755)).expect("chmod");
+
+        let stmts = (1..=5000)
+            .map(|line| {
+                unknown(
+                    line,
+                    "SELECT 'a statement long enough to fill the stderr pipe quickly'",
+                )
+            })
+            .collect();
+        let mut script = VerifyScript::new();
+        script.push_unit(&unit(stmts), true).expect("push");
+
+        let outcome = script
+            .run(&RuleId::Pgm036, &psql, "dbname=unused")
+            .expect("run failed");
+        assert!(outcome.findings.is_empty());
+    }
+}
@@ -5,7 +5,9 @@ use std::collections::HashSet;
 
 #[test]
 fn test_clean_repo_no_findings() {
-    let findings = common::lint_fixture::<&str>("clean", &[]);
+    // PGM526 (every new table needs a GRANT) and PGM514 (no GRANTs in
+    // migrations) are opposite opt-in policies; the clean repo follows PGM514.
+    let findings = common::lint_fixture_with_disabled("clean", &[], &["PGM526"]);
     assert!(
         findings.is_empty(),
         "Clean repo should have 0 findings but got {}: {:?}",
@@ -26,8 +28,9 @@ fn test_all_rules_trigger() {
     let findings = common::lint_fixture("all-rules", &changed);
     let rule_ids: HashSet<&str> = findings.iter().map(|f| f.rule_id.as_str()).collect();
 
-    // Every registered non-meta rule must fire at least once.
-    for id in RuleId::lint_rules() {
+    // Every registered non-meta rule must fire at least once, except those
+    // that need a live database.
+    for id in RuleId::lint_rules().filter(|id| !id.needs_database()) {
         assert!(
             rule_ids.contains(id.as_str()),
             "Rule {} is registered but did not fire. Add a violation to the all-rules fixture. Got:\n  {}",
@@ -47,7 +50,7 @@ fn test_suppressed_repo_no_findings() {
     let raw_rule_ids: HashSet<&str> = raw_findings.iter().map(|f| f.rule_id.as_str()).collect();
 
     let mut missing_rules: Vec<&str> = RuleId::lint_rules()
-        .filter(|id| !raw_rule_ids.contains(id.as_str()))
+        .filter(|id| !id.needs_database() && !raw_rule_ids.contains(id.as_str()))
         .map(|id| id.as_str())
         .collect();
     if !missing_rules.is_empty() {
@@ -84,9 +87,10 @@ fn test_all_files_changed_fresh_repo() {
     // All files passed as changed — no prior history to replay.
     // catalog_before starts empty for V001. DDL-safety rules like PGM001
     // must not fire because all tables are new.
-    let findings = common::lint_fixture(
+    let findings = common::lint_fixture_with_disabled(
         "clean",
         &["V001__create_users.sql", "V002__create_orders.sql"],
+        &["PGM526"],
     );
     assert!(
         findings.is_empty(),
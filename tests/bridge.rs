@@ -6,9 +6,10 @@ mod tests {
     use std::path::PathBuf;
 
     use pg_migration_lint::input::RawMigrationUnit;
-    use pg_migration_lint::input::liquibase_bridge::{BridgeLoader, resolve_source_paths};
+    use pg_migration_lint::input::liquibase_bridge::{
+        BridgeLoader, attach_suppressions, resolve_source_paths,
+    };
     use pg_migration_lint::input::liquibase_updatesql::UpdateSqlLoader;
-    use pg_migration_lint::suppress::parse_suppressions;
     use pg_migration_lint::{Finding, LintPipeline, RuleId, normalize};
 
     use super::common;
@@ -49,9 +50,9 @@ mod tests {
             if is_changed {
                 let mut unit_findings = pipeline.lint(unit, &all_rules);
 
-                let source = std::fs::read_to_string(&unit.source_file).unwrap_or_default();
-                let suppressions = parse_suppressions(&source);
+                let suppressions = &unit.suppressions;
                 unit_findings.retain(|f| !suppressions.is_suppressed(f.rule_id, f.start_line));
+                suppressions.apply_acknowledgments(&mut unit_findings);
 
                 all_findings.extend(unit_findings);
             } else {
@@ -70,6 +71,7 @@ mod tests {
         let loader = BridgeLoader::new(bridge_jar_path());
         let mut raw_units = loader.load(&master_xml).expect("Failed to load via bridge");
         resolve_source_paths(&mut raw_units, base_dir);
+        attach_suppressions(&mut raw_units);
 
         lint_loaded_units(raw_units, changed_ids)
     }
@@ -268,6 +270,7 @@ mod tests {
             .load(&master_xml)
             .expect("Failed to load via update-sql");
         resolve_source_paths(&mut raw_units, base_dir);
+        attach_suppressions(&mut raw_units);
 
         lint_loaded_units(raw_units, changed_ids)
     }
@@ -442,6 +445,7 @@ mod tests {
             .load(&master_xml)
             .expect("Failed to load multi-schema via bridge");
         resolve_source_paths(&mut raw_units, base_dir);
+        attach_suppressions(&mut raw_units);
 
         lint_loaded_units(raw_units, changed_ids)
     }
@@ -464,6 +468,7 @@ mod tests {
             .load(&master_xml)
             .expect("Failed to load multi-schema via update-sql");
         resolve_source_paths(&mut raw_units, base_dir);
+        attach_suppressions(&mut raw_units);
 
         lint_loaded_units(raw_units, changed_ids)
     }
@@ -505,9 +510,9 @@ mod tests {
             if is_changed {
                 let mut unit_findings = pipeline.lint(unit, &all_rules);
 
-                let source = std::fs::read_to_string(&unit.source_file).unwrap_or_default();
-                let suppressions = parse_suppressions(&source);
+                let suppressions = &unit.suppressions;
                 unit_findings.retain(|f| !suppressions.is_suppressed(f.rule_id, f.start_line));
+                suppressions.apply_acknowledgments(&mut unit_findings);
 
                 all_findings.extend(unit_findings);
             } else {
@@ -528,6 +533,7 @@ mod tests {
             .load(&master_xml)
             .expect("Failed to load fresh repo via bridge");
         resolve_source_paths(&mut raw_units, base_dir);
+        attach_suppressions(&mut raw_units);
         raw_units
     }
 
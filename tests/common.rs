@@ -2,10 +2,10 @@
 #![allow(dead_code)]
 
 use pg_migration_lint::LintPipeline;
+use pg_migration_lint::config::Config;
 use pg_migration_lint::input::sql::SqlLoader;
 use pg_migration_lint::normalize;
 use pg_migration_lint::rules::{Finding, RuleId, dedup_findings};
-use pg_migration_lint::suppress::parse_suppressions;
 use std::collections::HashSet;
 use std::path::PathBuf;
 
@@ -34,6 +34,21 @@ pub fn changed_files_for(fixture_name: &str) -> Vec<String> {
     names
 }
 
+/// Load `repos/{fixture_name}/pg-migration-lint.toml`, or the default config
+/// if the fixture has none.
+pub fn fixture_config(fixture_name: &str) -> Config {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/repos")
+        .join(fixture_name)
+        .join("pg-migration-lint.toml");
+    if path.exists() {
+        Config::from_file(&path)
+            .unwrap_or_else(|e| panic!("load fixture config {}: {e}", path.display()))
+    } else {
+        Config::default()
+    }
+}
+
 /// Run the full lint pipeline on a fixture repo.
 /// If `changed_files` is empty, all files are linted.
 pub fn lint_fixture<S: AsRef<str>>(fixture_name: &str, changed_filenames: &[S]) -> Vec<Finding> {
@@ -117,6 +132,14 @@ pub fn lint_fixture_inner<S: AsRef<str>>(
 
     normalize::normalize_schemas(&mut history.units, default_schema);
 
+    // Rules checked once the whole change is known (PGM522) honour
+    // suppressions while the pipeline records the unit, so drop them up front.
+    if skip_suppress {
+        for unit in &mut history.units {
+            unit.suppressions = Default::default();
+        }
+    }
+
     let changed: HashSet<PathBuf> = changed_filenames
         .iter()
         .map(|f| base.join(f.as_ref()))
@@ -130,7 +153,7 @@ pub fn lint_fixture_inner<S: AsRef<str>>(
         })
         .collect();
 
-    let mut pipeline = LintPipeline::new();
+    let mut pipeline = LintPipeline::new().with_config(fixture_config(fixture_name));
     let mut all_findings: Vec<Finding> = Vec::new();
 
     for unit in &history.units {
@@ -140,9 +163,9 @@ pub fn lint_fixture_inner<S: AsRef<str>>(
             let mut unit_findings = pipeline.lint(unit, &active_rules);
 
             if !skip_suppress {
-                let source = std::fs::read_to_string(&unit.source_file).unwrap_or_default();
-                let suppressions = parse_suppressions(&source);
+                let suppressions = &unit.suppressions;
                 unit_findings.retain(|f| !suppressions.is_suppressed(f.rule_id, f.start_line));
+                suppressions.apply_acknowledgments(&mut unit_findings);
             }
             dedup_findings(&mut unit_findings);
 
@@ -151,6 +174,7 @@ pub fn lint_fixture_inner<S: AsRef<str>>(
             pipeline.replay(unit);
         }
     }
+    all_findings.extend(pipeline.finish(&active_rules));
 
     all_findings
 }
@@ -56,6 +56,46 @@ dir = "{}"
 
 [cli]
 fail_on = "{}"
+
+# Matches the all-rules fixture config, so PGM511, the opt-in PGM514,
+# PGM519, PGM520, PGM524, PGM525, PGM526, PGM527, and PGM528, PGM517, PGM032,
+# and PGM034 fire there.
+[rules]
+enabled = ["PGM514", "PGM519", "PGM520", "PGM524", "PGM525", "PGM526", "PGM527", "PGM528"]
+
+[rules.pgm525]
+tables = ["customers"]
+
+[rules.pgm526]
+roles = ["app_rw", "app_ro"]
+
+[[rules.overrides]]
+rule = "PGM526"
+tables = ["ledger_entries"]
+
+[[rules.overrides]]
+rule = "PGM527"
+tables = ["vendor_invoices"]
+
+[schemas]
+required = "billing"
+
+[schemas.ownership]
+billing = ["billing_team"]
+
+[[patterns]]
+name = "pk-from-prebuilt-index"
+on = "add_primary_key"
+preceded_by = "create_index"
+
+[lock_budget]
+max_seconds = 5
+
+[lock_budget.table_rows]
+addresses = 50000000
+
+[catalog]
+strict_replay = true
 "#,
         migrations_path, format_list, output_dir, fail_on
     );
@@ -407,6 +447,304 @@ fn test_text_output_to_stdout() {
     );
 }
 
+#[test]
+fn test_pr_comment_output_respects_max_items() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let migrations_dir = fixture_path("all-rules").join("migrations");
+    let output_dir = tmp.path().join("output");
+
+    let config_path = write_temp_config(
+        tmp.path(),
+        &migrations_dir.to_string_lossy(),
+        &output_dir.to_string_lossy(),
+        &["sarif"],
+        "none",
+    );
+
+    let changed = comma_join(&changed_migration_files("all-rules"));
+
+    let output = run_lint(&[
+        "--config",
+        &config_path.to_string_lossy(),
+        "--changed-files",
+        &changed,
+        "--format",
+        "pr-comment",
+        "--max-items",
+        "3",
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let content =
+        std::fs::read_to_string(output_dir.join("pr-comment.md")).expect("read pr-comment.md");
+    assert!(content.starts_with("## pg-migration-lint\n"));
+    let rows = content
+        .lines()
+        .filter(|l| l.starts_with("| ") && !l.starts_with("| Severity") && !l.starts_with("| ---"))
+        .count();
+    assert_eq!(
+        rows, 3,
+        "table should list --max-items findings:\n{content}"
+    );
+    assert!(
+        content.contains("most severe of"),
+        "capped comment should say how many findings were left out:\n{content}"
+    );
+}
+
+#[test]
+fn test_only_families_limits_rules() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let migrations_dir = fixture_path("all-rules").join("migrations");
+    let output_dir = tmp.path().join("output");
+
+    let config_path = write_temp_config(
+        tmp.path(),
+        &migrations_dir.to_string_lossy(),
+        &output_dir.to_string_lossy(),
+        &["text"],
+        "none",
+    );
+
+    let changed = comma_join(&changed_migration_files("all-rules"));
+
+    let output = run_lint(&[
+        "--config",
+        &config_path.to_string_lossy(),
+        "--changed-files",
+        &changed,
+        "--format",
+        "text",
+        "--only",
+        "1xx,2xx",
+    ]);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("PGM101") && stdout.contains("PGM201"),
+        "Rules in the selected families should fire. stdout: {}",
+        stdout
+    );
+    assert!(
+        !stdout.contains("PGM001") && !stdout.contains("PGM501"),
+        "Rules outside the selected families should not fire. stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_exit_2_invalid_only_family() {
+    let output = run_lint(&["--only", "0xx,7xx"]);
+
+    assert_eq!(
+        output.status.code(),
+        Some(2),
+        "Unknown family should exit 2. stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("invalid rule family '7xx'"),
+        "Should name the bad family. stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_emit_catalog_writes_replayed_schema() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let migrations_dir = fixture_path("all-rules").join("migrations");
+    let output_dir = tmp.path().join("output");
+    let catalog_path = tmp.path().join("audit").join("catalog.json");
+
+    let config_path = write_temp_config(
+        tmp.path(),
+        &migrations_dir.to_string_lossy(),
+        &output_dir.to_string_lossy(),
+        &["text"],
+        "none",
+    );
+
+    let output = run_lint(&[
+        "--config",
+        &config_path.to_string_lossy(),
+        "--emit-catalog",
+        &catalog_path.to_string_lossy(),
+    ]);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let content = std::fs::read_to_string(&catalog_path).expect("read catalog");
+    let catalog: serde_json::Value = serde_json::from_str(&content).expect("parse catalog");
+    let tables = catalog["tables"].as_array().expect("tables array");
+
+    let names: Vec<&str> = tables
+        .iter()
+        .map(|t| t["name"].as_str().expect("table name"))
+        .collect();
+    let mut sorted = names.clone();
+    sorted.sort_unstable();
+    assert_eq!(names, sorted, "tables should be sorted by name");
+
+    let customers = tables
+        .iter()
+        .find(|t| t["name"] == "public.customers")
+        .expect("public.customers in catalog");
+    assert_eq!(customers["columns"][0]["name"], "id");
+    assert_eq!(customers["columns"][0]["type"], "int8");
+    assert!(
+        customers["constraints"]
+            .as_array()
+            .expect("constraints array")
+            .iter()
+            .any(|c| c["type"] == "primary_key"),
+        "customers should have a primary key: {customers}"
+    );
+}
+
+#[test]
+fn test_cache_reuses_identical_run() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let migrations_dir = fixture_path("all-rules").join("migrations");
+    let output_dir = tmp.path().join("output");
+
+    let config_path = write_temp_config(
+        tmp.path(),
+        &migrations_dir.to_string_lossy(),
+        &output_dir.to_string_lossy(),
+        &["json"],
+        "none",
+    );
+    let mut config = std::fs::read_to_string(&config_path).expect("read config");
+    config.push_str("\n[cache]\nenabled = true\n");
+    std::fs::write(&config_path, config).expect("write config");
+
+    let changed = comma_join(&changed_migration_files("all-rules"));
+    let lint = |extra: &[&str]| {
+        let config = config_path.to_string_lossy();
+        let mut args = vec!["--config", &config, "--changed-files", &changed];
+        args.extend_from_slice(extra);
+        let output = run_lint(&args);
+        assert_eq!(
+            output.status.code(),
+            Some(0),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let report = std::fs::read_to_string(output_dir.join("report.json")).expect("report");
+        (String::from_utf8_lossy(&output.stderr).into_owned(), report)
+    };
+
+    let (first_stderr, first_report) = lint(&[]);
+    assert!(!first_stderr.contains("reusing cached result"));
+    assert!(output_dir.join("cache").is_dir(), "cache entry written");
+
+    let (second_stderr, second_report) = lint(&[]);
+    assert!(
+        second_stderr.contains("reusing cached result"),
+        "stderr: {second_stderr}"
+    );
+    assert_eq!(first_report, second_report);
+
+    let (bypass_stderr, _) = lint(&["--no-cache"]);
+    assert!(!bypass_stderr.contains("reusing cached result"));
+}
+
+#[test]
+fn test_changed_from_git_lints_branch_changes() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let repo = tmp.path();
+    let git = |args: &[&str]| {
+        let output = Command::new("git")
+            .args(["-c", "user.name=lint", "-c", "user.email=lint@example.com"])
+            .args(args)
+            .current_dir(repo)
+            .output()
+            .expect("failed to execute git");
+        assert!(
+            output.status.success(),
+            "git {args:?}: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    };
+    let migrations = repo.join("migrations");
+    std::fs::create_dir_all(&migrations).expect("mkdir");
+    std::fs::write(repo.join("notes.sql"), "SELECT 1;").expect("write");
+    std::fs::write(
+        migrations.join("V001__init.sql"),
+        "CREATE TABLE t (id int);",
+    )
+    .expect("write");
+    std::fs::write(
+        migrations.join("V002__old_name.sql"),
+        "ALTER TABLE t ADD COLUMN a int;",
+    )
+    .expect("write");
+    git(&["init", "--quiet", "-b", "main"]);
+    git(&["add", "."]);
+    git(&["commit", "--quiet", "-m", "init"]);
+
+    git(&["checkout", "--quiet", "-b", "feature"]);
+    git(&[
+        "mv",
+        "migrations/V002__old_name.sql",
+        "migrations/V003__new_name.sql",
+    ]);
+    std::fs::write(
+        migrations.join("V004__index.sql"),
+        "CREATE INDEX idx_t_a ON t (a);",
+    )
+    .expect("write");
+    std::fs::write(repo.join("notes.sql"), "SELECT 2;").expect("write");
+    git(&["add", "."]);
+    git(&["commit", "--quiet", "-m", "feature"]);
+    std::fs::write(
+        migrations.join("V005__untracked.sql"),
+        "ALTER TABLE t ADD COLUMN b int;",
+    )
+    .expect("write");
+
+    let config_path = write_temp_config(repo, "migrations", "output", &["json"], "none");
+    let output = Command::new(binary_path())
+        .args(["--config", &config_path.to_string_lossy()])
+        .args(["--changed-from-git", "main", "--explain-changed-files"])
+        .current_dir(repo)
+        .output()
+        .expect("failed to execute pg-migration-lint binary");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(0), "stderr: {stderr}");
+
+    assert!(
+        stderr.contains("3 path(s) provided, 3 matched"),
+        "stderr: {stderr}"
+    );
+    for name in [
+        "V003__new_name.sql",
+        "V004__index.sql",
+        "V005__untracked.sql",
+    ] {
+        assert!(stderr.contains(name), "{name} missing. stderr: {stderr}");
+    }
+    for name in ["V001__init.sql", "V002__old_name.sql", "notes.sql"] {
+        assert!(!stderr.contains(name), "{name} listed. stderr: {stderr}");
+    }
+}
+
+#[test]
+fn test_changed_from_git_conflicts_with_changed_files() {
+    let output = run_lint(&[
+        "--changed-from-git",
+        "main",
+        "--changed-files",
+        "V001__init.sql",
+    ]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
 // ===========================================================================
 // CLI behavior tests
 // ===========================================================================
@@ -571,23 +909,23 @@ fn test_changed_files_from_file() {
 #[test]
 fn test_fail_on_cli_override() {
     // Use --fail-on to override the config file's fail_on setting.
-    // Config has fail_on = "critical", override with --fail-on info.
+    // Config has fail_on = "none", override with --fail-on info.
     let tmp = tempfile::tempdir().expect("tempdir");
     let migrations_dir = fixture_path("all-rules").join("migrations");
     let output_dir = tmp.path().join("output");
 
-    // Config says fail_on = "blocker" -- no findings are BLOCKER, so exit 0
+    // Config says fail_on = "none" -- nothing fails the run, so exit 0
     let config_path = write_temp_config(
         tmp.path(),
         &migrations_dir.to_string_lossy(),
         &output_dir.to_string_lossy(),
         &["text"],
-        "blocker",
+        "none",
     );
 
     let changed = comma_join(&changed_migration_files("all-rules"));
 
-    // Without override, should exit 0 (no BLOCKER findings)
+    // Without override, should exit 0
     let output_no_override = run_lint(&[
         "--config",
         &config_path.to_string_lossy(),
@@ -599,7 +937,7 @@ fn test_fail_on_cli_override() {
     assert_eq!(
         output_no_override.status.code(),
         Some(0),
-        "With fail_on=blocker and no blocker findings, should exit 0. stderr: {}",
+        "With fail_on=none, should exit 0. stderr: {}",
         String::from_utf8_lossy(&output_no_override.stderr)
     );
 
@@ -622,6 +960,61 @@ fn test_fail_on_cli_override() {
     );
 }
 
+#[test]
+fn test_fail_on_warnings_turns_replay_conflicts_into_tool_error() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let migrations_dir = tmp.path().join("migrations");
+    std::fs::create_dir_all(&migrations_dir).expect("create migrations dir");
+    std::fs::write(
+        migrations_dir.join("V001__create_orders.sql"),
+        "CREATE TABLE orders (id bigint PRIMARY KEY);\n",
+    )
+    .expect("write V001");
+    std::fs::write(
+        migrations_dir.join("V002__create_orders_again.sql"),
+        "CREATE TABLE IF NOT EXISTS orders (id bigint PRIMARY KEY);\n",
+    )
+    .expect("write V002");
+    let output_dir = tmp.path().join("output");
+    let config_path = write_temp_config(
+        tmp.path(),
+        &migrations_dir.to_string_lossy(),
+        &output_dir.to_string_lossy(),
+        &["text"],
+        "none",
+    );
+    let run = |extra: &[&str]| {
+        let mut args = vec!["--config", config_path.to_str().unwrap()];
+        args.extend_from_slice(extra);
+        run_lint(&args)
+    };
+
+    let output = run(&[]);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "Warnings alone should not fail the run. stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = run(&["--fail-on-warnings"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(2), "stderr: {stderr}");
+    assert!(
+        stderr.contains("V002__create_orders_again.sql:1: [replay_conflict]"),
+        "stderr: {stderr}"
+    );
+
+    // Only unparseable statements fail the run; there are none.
+    let output = run(&["--fail-on-warnings", "unparseable"]);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
 // ===========================================================================
 // Full pipeline E2E
 // ===========================================================================
@@ -708,8 +1101,9 @@ fn test_full_pipeline_with_findings() {
         .map(|r| r["ruleId"].as_str().expect("ruleId"))
         .collect();
 
-    // Build expected set from all lint rules, excluding meta rules.
-    for id in RuleId::lint_rules() {
+    // Build expected set from all lint rules, excluding meta rules and rules
+    // that need a live database.
+    for id in RuleId::lint_rules().filter(|id| !id.needs_database()) {
         assert!(
             sarif_rule_ids.contains(id.as_str()),
             "Rule {} is registered but not found in SARIF results. \
@@ -928,7 +1322,8 @@ fn test_multiple_format_outputs() {
 
 #[test]
 fn test_changed_files_relative_path_suffix_matching() {
-    // Exercise the suffix-matching fallback in changed-file detection (main.rs lines 155-159).
+    // Exercise the suffix-matching fallback in changed-file detection
+    // (input::changed).
     //
     // The config points to an absolute path for the migrations directory, so the
     // migration unit's source_file is absolute. We pass a RELATIVE path via
@@ -994,9 +1389,9 @@ fn test_changed_files_relative_path_suffix_matching() {
 #[test]
 fn test_changed_files_bare_filename_does_not_match() {
     // Verify that a bare filename (single path component) does NOT trigger
-    // the suffix matching fallback. The guard `cf.components().count() > 1`
-    // on line 159 of main.rs should prevent bare filenames from matching
-    // across directories.
+    // the suffix matching fallback. The guard `key.len() > 1`
+    // in input::changed should prevent bare filenames from matching across
+    // directories.
     //
     // This kills the `> 1` -> `>= 1` mutant
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -1052,6 +1447,57 @@ fn test_changed_files_bare_filename_does_not_match() {
         "Should report 0 findings when no files matched. stderr: {}",
         stderr
     );
+
+    // The unmatched path is called out instead of silently linting nothing
+    assert!(
+        stderr.contains("changed file 'V002__violations.sql' did not match any migration unit"),
+        "Should warn about the unmatched path. stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_explain_changed_files_lists_matches() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let migrations_dir = fixture_path("all-rules").join("migrations");
+    let output_dir = tmp.path().join("output");
+
+    let config_path = write_temp_config(
+        tmp.path(),
+        &migrations_dir.to_string_lossy(),
+        &output_dir.to_string_lossy(),
+        &["text"],
+        "info",
+    );
+
+    let output = run_lint(&[
+        "--config",
+        &config_path.to_string_lossy(),
+        "--changed-files",
+        "repos/all-rules/migrations/V002__violations.sql,V002__violations.sql",
+        "--explain-changed-files",
+        "--format",
+        "text",
+    ]);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(
+        stderr.contains("2 path(s) provided, 1 matched at least one unit"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(
+        stderr.contains("-> V002__violations.sql (")
+            && stderr.contains("unit's path ends with the provided path"),
+        "Should show the matched unit and the reason. stderr: {}",
+        stderr
+    );
+    assert!(
+        stderr.contains("matched no units"),
+        "Should list the bare filename as unmatched. stderr: {}",
+        stderr
+    );
 }
 
 // ===========================================================================
@@ -1420,3 +1866,128 @@ fail_on = "critical"
         stderr
     );
 }
+
+#[test]
+fn test_examples_show_writes_a_template_that_lints() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let out = tmp.path().join("template");
+
+    let output = run_lint(&[
+        "examples",
+        "show",
+        "not-null-rollout",
+        "--out",
+        &out.to_string_lossy(),
+    ]);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        out.join("migrations/V004__set_customer_ref_not_null.sql")
+            .exists()
+    );
+
+    // The written template is a working project: it lints with its own config.
+    let output = run_lint(&[
+        "--config",
+        &out.join("pg-migration-lint.toml").to_string_lossy(),
+        "--format",
+        "text",
+    ]);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stdout: {}\nstderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = run_lint(&["examples", "show", "no-such-example"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Available examples: rename-column"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn test_merge_reports_tags_runs_with_their_source() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let report = |name: &str| {
+        let path = tmp.path().join(format!("{name}.sarif"));
+        std::fs::write(
+            &path,
+            r#"{"version": "2.1.0", "runs": [{"tool": {"driver": {"name": "pg-migration-lint", "rules": []}}, "results": []}]}"#,
+        )
+        .expect("write report");
+        path
+    };
+    let billing = report("billing");
+    let orders = report("orders");
+    let merged_path = tmp.path().join("out/merged.sarif");
+
+    let output = run_lint(&[
+        "merge-reports",
+        &billing.to_string_lossy(),
+        &format!("svc-orders={}", orders.display()),
+        "--output",
+        &merged_path.to_string_lossy(),
+    ]);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let merged: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&merged_path).expect("read merged"))
+            .expect("merged report is JSON");
+    let ids: Vec<_> = merged["runs"]
+        .as_array()
+        .expect("runs")
+        .iter()
+        .map(|run| run["automationDetails"]["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, ["billing/", "svc-orders/"]);
+
+    // Inputs must match --format.
+    let output = run_lint(&[
+        "merge-reports",
+        &billing.to_string_lossy(),
+        "--format",
+        "sonarqube",
+    ]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("report 'billing' is a sarif report, expected sonarqube"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn test_rules_schema_describes_config_and_rules() {
+    let output = run_lint(&["rules", "schema"]);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).expect("schema is JSON");
+
+    assert!(schema["properties"]["lock_budget"].is_object());
+    let rules = schema["x-pg-migration-lint"]["rules"]
+        .as_array()
+        .expect("rules");
+    let pgm032 = rules
+        .iter()
+        .find(|r| r["id"] == "PGM032")
+        .expect("PGM032 listed");
+    assert_eq!(pgm032["config"], serde_json::json!(["lock_budget"]));
+}
@@ -1,8 +1,6 @@
 use std::{collections::HashSet, path::PathBuf};
 
-use pg_migration_lint::{
-    Finding, LintPipeline, RuleId, input::sql::SqlLoader, normalize, suppress::parse_suppressions,
-};
+use pg_migration_lint::{Finding, LintPipeline, RuleId, input::sql::SqlLoader, normalize};
 use rstest::rstest;
 
 use crate::common::{format_findings, lint_fixture, lint_fixture_rules};
@@ -174,8 +172,7 @@ fn test_enterprise_sliding_window() {
         }
         let mut unit_findings = pipeline.lint(unit, &all_rules);
 
-        let source = std::fs::read_to_string(&unit.source_file).unwrap_or_default();
-        let suppressions = parse_suppressions(&source);
+        let suppressions = &unit.suppressions;
         unit_findings.retain(|f| !suppressions.is_suppressed(f.rule_id, f.start_line));
 
         let filename = unit
@@ -14,7 +14,7 @@ CREATE TABLE audit_log (
 -- PGM006: Volatile default on existing table (clock_timestamp is truly volatile)
 ALTER TABLE customers ADD COLUMN token uuid DEFAULT gen_random_uuid();
 
--- PGM007: unsafe ALTER COLUMN TYPE on existing table
+-- PGM007: unsafe ALTER COLUMN TYPE on an indexed column of an existing table
 ALTER TABLE customers ALTER COLUMN email TYPE varchar(255);
 
 -- PGM008: ADD COLUMN NOT NULL without default on existing table
@@ -11,5 +11,5 @@ CREATE TABLE settings (
 -- PGM003: CONCURRENTLY inside transaction (SqlLoader sets run_in_transaction=true)
 CREATE INDEX CONCURRENTLY idx_customers_customer_id ON customers (customer_id);
 
--- PGM016: ADD PRIMARY KEY on existing table without prior unique constraint
+-- PGM016, PGM517: ADD PRIMARY KEY on existing table without prior unique constraint
 ALTER TABLE events ADD PRIMARY KEY (id);
@@ -33,3 +33,9 @@ ALTER TABLE audit_log_v2 ADD COLUMN description varchar(255);
 
 -- PGM109: floating-point type
 ALTER TABLE audit_log_v2 ADD COLUMN score double precision;
+
+-- PGM110: UUID stored as text
+ALTER TABLE audit_log_v2 ADD COLUMN request_uuid text DEFAULT gen_random_uuid()::text;
+
+-- PGM510: boolean NOT NULL without DEFAULT on new table
+ALTER TABLE audit_log_v2 ADD COLUMN is_archived boolean NOT NULL;
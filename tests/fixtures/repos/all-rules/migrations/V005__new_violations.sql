@@ -12,10 +12,10 @@ ALTER TABLE events ADD CONSTRAINT fk_events_customer
 -- PGM015: ADD CHECK without NOT VALID on existing table
 ALTER TABLE customers ADD CONSTRAINT chk_email CHECK (email <> '');
 
--- PGM504: RENAME TABLE on existing table
+-- PGM504, PGM520: RENAME TABLE on existing table
 ALTER TABLE accounts RENAME TO accounts_old;
 
--- PGM505: RENAME COLUMN on existing table
+-- PGM505, PGM519: RENAME COLUMN on existing table
 ALTER TABLE addresses RENAME COLUMN address_id TO addr_id;
 
 -- PGM017: ADD UNIQUE without USING INDEX on existing table
@@ -0,0 +1,4 @@
+-- PGM024: add column, backfill, and SET NOT NULL on existing table in one migration
+ALTER TABLE customers ADD COLUMN tier text;
+UPDATE customers SET tier = 'standard';
+ALTER TABLE customers ALTER COLUMN tier SET NOT NULL;
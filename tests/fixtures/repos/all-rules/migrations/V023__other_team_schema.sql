@@ -0,0 +1,2 @@
+-- PGM511: DDL on a schema owned by another team (see pg-migration-lint.toml)
+ALTER TABLE billing.invoices ADD COLUMN memo text;
@@ -0,0 +1,2 @@
+-- PGM512: rename an index on an existing table
+ALTER INDEX idx_customers_customer_id RENAME TO idx_customers_customer;
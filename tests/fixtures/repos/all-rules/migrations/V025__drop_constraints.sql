@@ -0,0 +1,5 @@
+-- PGM026: drop a foreign key on an existing table
+ALTER TABLE events DROP CONSTRAINT fk_events_customer;
+
+-- PGM025: drop a unique constraint on an existing table
+ALTER TABLE customers DROP CONSTRAINT customers_email_key;
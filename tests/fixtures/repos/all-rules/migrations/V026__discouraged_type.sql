@@ -0,0 +1,2 @@
+-- PGM111: hstore column (discouraged type)
+ALTER TABLE customers ADD COLUMN attrs hstore;
@@ -0,0 +1,2 @@
+-- PGM206: drop an extension that customers.attrs (hstore) depends on
+DROP EXTENSION hstore;
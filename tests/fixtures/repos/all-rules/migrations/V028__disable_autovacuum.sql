@@ -0,0 +1,2 @@
+-- PGM513: disable autovacuum on an existing table
+ALTER TABLE events SET (autovacuum_enabled = false);
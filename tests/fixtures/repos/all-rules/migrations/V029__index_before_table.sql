@@ -0,0 +1,2 @@
+-- PGM027 (with V030): uses a table that a later migration creates
+CREATE INDEX IF NOT EXISTS idx_shipments_order ON shipments (order_id);
@@ -0,0 +1,2 @@
+-- PGM027: creates the table V029 already indexed
+CREATE TABLE IF NOT EXISTS shipments (id bigint PRIMARY KEY, order_id bigint NOT NULL);
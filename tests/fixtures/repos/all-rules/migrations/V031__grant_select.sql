@@ -0,0 +1,2 @@
+-- PGM514: permission change bundled into a schema migration (opt-in rule)
+GRANT SELECT ON customers TO reporting;
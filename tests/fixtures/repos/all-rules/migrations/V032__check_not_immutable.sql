@@ -0,0 +1,6 @@
+-- PGM028: CHECK constraint with a non-immutable call
+CREATE TABLE IF NOT EXISTS promotions (
+    id bigint PRIMARY KEY,
+    starts_at timestamptz NOT NULL,
+    CONSTRAINT promotions_not_past CHECK (starts_at >= now())
+);
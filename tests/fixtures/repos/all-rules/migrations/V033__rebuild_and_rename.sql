@@ -0,0 +1,4 @@
+-- PGM515: table rebuilt by copy and renamed in one migration
+CREATE TABLE IF NOT EXISTS promotions_new (id bigint PRIMARY KEY, code text);
+INSERT INTO promotions_new (id) SELECT id FROM promotions;
+ALTER TABLE promotions_new RENAME TO promotions_v2;
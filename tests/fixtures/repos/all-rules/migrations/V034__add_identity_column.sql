@@ -0,0 +1,2 @@
+-- PGM029: identity column added to an existing table
+ALTER TABLE events ADD COLUMN seq_no bigint GENERATED ALWAYS AS IDENTITY;
@@ -0,0 +1,4 @@
+-- PGM030: two constraints added in one ALTER TABLE on an existing table
+ALTER TABLE customers
+    ADD CONSTRAINT customers_email_not_blank CHECK (email <> '') NOT VALID,
+    ADD CONSTRAINT customers_id_positive CHECK (id > 0) NOT VALID;
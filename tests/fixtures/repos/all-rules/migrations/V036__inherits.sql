@@ -0,0 +1,5 @@
+-- PGM516: legacy table inheritance
+CREATE TABLE IF NOT EXISTS events_archive (
+    archived_at timestamptz,
+    PRIMARY KEY (id)
+) INHERITS (events);
@@ -0,0 +1,3 @@
+-- PGM518: temp table without ON COMMIT DROP in a transactional migration
+CREATE TEMP TABLE customers_backfill (id int PRIMARY KEY);
+INSERT INTO customers_backfill SELECT id FROM customers;
@@ -0,0 +1,2 @@
+-- PGM031: bulk maintenance over a whole tablespace
+ALTER TABLE ALL IN TABLESPACE pg_default SET TABLESPACE fast_ssd;
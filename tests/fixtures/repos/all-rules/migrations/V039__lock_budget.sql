@@ -0,0 +1,2 @@
+-- PGM032: validating a CHECK scans ~50M rows under lock (budget 5s)
+ALTER TABLE addresses ADD CONSTRAINT addresses_addr_id_positive CHECK (addr_id > 0);
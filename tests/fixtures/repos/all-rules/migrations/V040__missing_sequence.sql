@@ -0,0 +1,2 @@
+-- PGM521: nextval() on a sequence no migration creates
+ALTER TABLE products ALTER COLUMN id SET DEFAULT nextval('product_id_seq');
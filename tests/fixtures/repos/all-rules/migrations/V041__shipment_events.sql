@@ -0,0 +1,5 @@
+-- PGM522: new table whose foreign key no migration in the change indexes
+CREATE TABLE shipment_events (
+    id bigint PRIMARY KEY,
+    customer_id bigint NOT NULL REFERENCES customers (id)
+);
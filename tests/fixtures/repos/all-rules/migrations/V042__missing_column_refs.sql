@@ -0,0 +1,4 @@
+-- PGM033: CHECK on a misspelled column, DEFAULT written in double quotes
+ALTER TABLE products
+    ADD CONSTRAINT products_code_length CHECK (length(prodcut_code) = 8) NOT VALID,
+    ALTER COLUMN name SET DEFAULT "unnamed";
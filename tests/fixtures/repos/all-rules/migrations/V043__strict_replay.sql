@@ -0,0 +1,2 @@
+-- PGM034: strict replay; customers already has an email column
+ALTER TABLE customers ADD COLUMN email text;
@@ -0,0 +1,2 @@
+-- PGM523: sequence created without OWNED BY
+CREATE SEQUENCE invoice_no_seq;
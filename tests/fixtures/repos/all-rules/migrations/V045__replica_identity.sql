@@ -0,0 +1,2 @@
+-- PGM035: REPLICA IDENTITY FULL on an existing table
+ALTER TABLE customers REPLICA IDENTITY FULL;
@@ -0,0 +1,2 @@
+-- PGM524: publication change in a schema migration (opt-in)
+CREATE PUBLICATION orders_pub FOR TABLE customers;
@@ -0,0 +1,7 @@
+-- PGM526: table created without a grant to app_rw (opt-in)
+CREATE TABLE ledger_entries (
+    id bigint PRIMARY KEY,
+    amount_cents bigint NOT NULL
+);
+
+GRANT SELECT ON ledger_entries TO app_ro;
@@ -0,0 +1,5 @@
+-- PGM527: table created in public instead of the billing service schema (opt-in)
+CREATE TABLE public.vendor_invoices (
+    id bigint PRIMARY KEY,
+    amount_cents bigint NOT NULL
+);
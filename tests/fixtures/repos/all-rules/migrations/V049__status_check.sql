@@ -0,0 +1,5 @@
+-- PGM528: CHECK lists five allowed values instead of using an enum type (opt-in)
+CREATE TABLE invoice_states (
+    id bigint PRIMARY KEY,
+    status text NOT NULL CHECK (status IN ('draft', 'sent', 'paid', 'void', 'overdue'))
+);
@@ -0,0 +1,3 @@
+-- PGM207: events.payload (text) dropped and re-added as jsonb, losing its data
+ALTER TABLE events DROP COLUMN payload;
+ALTER TABLE events ADD COLUMN payload jsonb;
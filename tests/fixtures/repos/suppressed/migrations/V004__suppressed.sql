@@ -1,4 +1,4 @@
--- pgm-lint:suppress-file PGM101,PGM102,PGM103,PGM104,PGM105,PGM107,PGM108,PGM109,PGM502,PGM006,PGM402
+-- pgm-lint:suppress-file PGM101,PGM102,PGM103,PGM104,PGM105,PGM107,PGM108,PGM109,PGM110,PGM502,PGM510,PGM006,PGM402
 
 -- PGM101: timestamp without time zone (suppressed)
 CREATE TABLE audit_log_v2 (
@@ -27,3 +27,9 @@ ALTER TABLE audit_log_v2 ADD COLUMN description varchar(255);
 
 -- PGM109: floating-point type (suppressed)
 ALTER TABLE audit_log_v2 ADD COLUMN score double precision;
+
+-- PGM110: UUID stored as text (suppressed)
+ALTER TABLE audit_log_v2 ADD COLUMN request_uuid text DEFAULT gen_random_uuid()::text;
+
+-- PGM510: boolean NOT NULL without DEFAULT on new table (suppressed)
+ALTER TABLE audit_log_v2 ADD COLUMN is_archived boolean NOT NULL;
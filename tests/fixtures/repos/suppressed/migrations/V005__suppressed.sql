@@ -1,4 +1,4 @@
--- pgm-lint:suppress-file PGM023,PGM501,PGM013,PGM014,PGM015,PGM017,PGM504,PGM505,PGM507
+-- pgm-lint:suppress-file PGM023,PGM501,PGM013,PGM014,PGM015,PGM017,PGM504,PGM505,PGM507,PGM519,PGM520
 
 ALTER TABLE customers ALTER COLUMN customer_id SET NOT NULL;
 
@@ -0,0 +1,5 @@
+-- pgm-lint:suppress-file PGM024
+
+ALTER TABLE customers ADD COLUMN tier text;
+UPDATE customers SET tier = 'standard';
+ALTER TABLE customers ALTER COLUMN tier SET NOT NULL;
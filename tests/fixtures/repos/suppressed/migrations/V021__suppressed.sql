@@ -0,0 +1,3 @@
+-- pgm-lint:suppress-file PGM511
+
+ALTER TABLE billing.invoices ADD COLUMN memo text;
@@ -0,0 +1,2 @@
+-- pgm-lint:suppress PGM512
+ALTER INDEX idx_customers_customer_id RENAME TO idx_customers_customer;
@@ -0,0 +1,4 @@
+-- pgm-lint:suppress-file PGM025,PGM026
+
+ALTER TABLE events DROP CONSTRAINT fk_events_customer;
+ALTER TABLE customers DROP CONSTRAINT customers_email_key;
@@ -0,0 +1,3 @@
+-- pgm-lint:suppress-file PGM111
+
+ALTER TABLE customers ADD COLUMN attrs hstore;
@@ -0,0 +1,3 @@
+-- pgm-lint:suppress-file PGM206
+
+DROP EXTENSION hstore;
@@ -0,0 +1,3 @@
+-- pgm-lint:suppress-file PGM513
+
+ALTER TABLE events SET (autovacuum_enabled = false);
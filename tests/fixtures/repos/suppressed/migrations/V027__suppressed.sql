@@ -0,0 +1 @@
+INSERT INTO parcels (id) VALUES (1);
@@ -0,0 +1,3 @@
+-- pgm-lint:suppress-file PGM027
+
+CREATE TABLE IF NOT EXISTS parcels (id bigint PRIMARY KEY);
@@ -0,0 +1,3 @@
+-- pgm-lint:suppress-file PGM514
+
+GRANT SELECT ON customers TO reporting;
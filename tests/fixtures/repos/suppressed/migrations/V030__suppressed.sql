@@ -0,0 +1,6 @@
+-- pgm-lint:suppress-file PGM028
+
+CREATE TABLE IF NOT EXISTS coupons (
+    id bigint PRIMARY KEY,
+    owner text NOT NULL CHECK (owner = CURRENT_USER)
+);
@@ -0,0 +1,5 @@
+-- pgm-lint:suppress-file PGM515
+
+CREATE TABLE IF NOT EXISTS coupons_new (id bigint PRIMARY KEY);
+INSERT INTO coupons_new (id) SELECT id FROM coupons;
+ALTER TABLE coupons_new RENAME TO coupons_v2;
@@ -0,0 +1,3 @@
+-- pgm-lint:suppress-file PGM029
+
+ALTER TABLE customers ADD COLUMN seq_no bigint GENERATED BY DEFAULT AS IDENTITY;
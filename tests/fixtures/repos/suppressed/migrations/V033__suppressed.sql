@@ -0,0 +1,5 @@
+-- pgm-lint:suppress-file PGM030
+
+ALTER TABLE customers
+    ADD CONSTRAINT customers_email_not_blank CHECK (email <> '') NOT VALID,
+    ADD CONSTRAINT customers_id_positive CHECK (id > 0) NOT VALID;
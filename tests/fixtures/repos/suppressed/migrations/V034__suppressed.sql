@@ -0,0 +1,6 @@
+-- pgm-lint:suppress-file PGM516
+
+CREATE TABLE IF NOT EXISTS customers_archive (
+    archived_at timestamptz,
+    PRIMARY KEY (id)
+) INHERITS (customers);
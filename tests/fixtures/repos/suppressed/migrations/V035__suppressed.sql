@@ -0,0 +1,4 @@
+-- pgm-lint:suppress-file PGM518
+
+CREATE TEMP TABLE IF NOT EXISTS customers_backfill (id bigint PRIMARY KEY);
+INSERT INTO customers_backfill SELECT id FROM customers;
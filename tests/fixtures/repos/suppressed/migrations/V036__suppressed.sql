@@ -0,0 +1,3 @@
+-- pgm-lint:suppress-file PGM031
+
+ALTER TABLE ALL IN TABLESPACE pg_default SET TABLESPACE fast_ssd;
@@ -0,0 +1,3 @@
+-- pgm-lint:suppress-file PGM015,PGM032
+
+ALTER TABLE addresses ADD CONSTRAINT addresses_addr_id_positive CHECK (addr_id > 0);
@@ -0,0 +1,3 @@
+-- pgm-lint:suppress-file PGM006,PGM521
+
+ALTER TABLE products ALTER COLUMN id SET DEFAULT nextval('product_id_seq');
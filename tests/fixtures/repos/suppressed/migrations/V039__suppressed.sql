@@ -0,0 +1,6 @@
+-- pgm-lint:suppress-file PGM501,PGM522
+
+CREATE TABLE IF NOT EXISTS shipment_events (
+    id bigint PRIMARY KEY,
+    customer_id bigint NOT NULL REFERENCES customers (id)
+);
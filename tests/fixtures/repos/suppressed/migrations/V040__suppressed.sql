@@ -0,0 +1,5 @@
+-- pgm-lint:suppress-file PGM033
+
+ALTER TABLE products
+    ADD CONSTRAINT products_code_length CHECK (length(prodcut_code) = 8) NOT VALID,
+    ALTER COLUMN name SET DEFAULT "unnamed";
@@ -0,0 +1,3 @@
+-- pgm-lint:suppress-file PGM034
+
+ALTER TABLE customers ADD COLUMN email text;
@@ -0,0 +1,3 @@
+-- pgm-lint:suppress-file PGM523,PGM527
+
+CREATE SEQUENCE invoice_no_seq;
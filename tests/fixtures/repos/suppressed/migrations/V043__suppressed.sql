@@ -0,0 +1,3 @@
+-- pgm-lint:suppress-file PGM035
+
+ALTER TABLE customers REPLICA IDENTITY FULL;
@@ -0,0 +1,3 @@
+-- pgm-lint:suppress-file PGM524
+
+CREATE PUBLICATION orders_pub FOR TABLE customers;
@@ -0,0 +1,3 @@
+-- pgm-lint:suppress-file PGM525
+
+ALTER TABLE events ADD COLUMN source text;
@@ -0,0 +1,6 @@
+-- pgm-lint:suppress-file PGM526
+
+CREATE TABLE IF NOT EXISTS ledger_entries (
+    id bigint PRIMARY KEY,
+    amount_cents bigint NOT NULL
+);
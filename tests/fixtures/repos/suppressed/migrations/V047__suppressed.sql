@@ -0,0 +1,6 @@
+-- pgm-lint:suppress-file PGM527
+
+CREATE TABLE IF NOT EXISTS public.vendor_invoices (
+    id bigint PRIMARY KEY,
+    amount_cents bigint NOT NULL
+);
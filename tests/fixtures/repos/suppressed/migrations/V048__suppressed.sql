@@ -0,0 +1,6 @@
+-- pgm-lint:suppress-file PGM528
+
+CREATE TABLE IF NOT EXISTS invoice_states (
+    id bigint PRIMARY KEY,
+    status text NOT NULL CHECK (status IN ('draft', 'sent', 'paid', 'void', 'overdue'))
+);
@@ -0,0 +1,4 @@
+-- pgm-lint:suppress-file PGM009,PGM023,PGM207
+
+ALTER TABLE events DROP COLUMN payload;
+ALTER TABLE events ADD COLUMN payload jsonb;
@@ -24,6 +24,10 @@ fn make_ctx<'a>(
         run_in_transaction: true,
         is_down: false,
         file,
+        config: pg_migration_lint::config::Config::default_ref(),
+        pending_references: PendingReferences::empty_ref(),
+        pattern_history: PatternHistory::empty_ref(),
+        assumed_tables: Default::default(),
     }
 }
 
@@ -36,6 +40,7 @@ fn located(node: IrNode) -> Located<IrNode> {
             start_offset: 0,
             end_offset: 0,
         },
+        raw_sql: Default::default(),
     }
 }
 
@@ -80,6 +85,9 @@ fn test_matrix_add_column_not_null_with_volatile_default() {
             }),
             is_inline_pk: false,
             is_serial: false,
+            is_identity: false,
+            if_not_exists: false,
+            spelling: None,
         })],
     }))];
 
@@ -108,6 +116,9 @@ fn test_matrix_add_column_not_null_without_default() {
             default_expr: None,
             is_inline_pk: false,
             is_serial: false,
+            is_identity: false,
+            if_not_exists: false,
+            spelling: None,
         })],
     }))];
 
@@ -204,6 +215,9 @@ fn test_matrix_create_table_no_pk_with_fk_no_index() {
                 default_expr: None,
                 is_inline_pk: false,
                 is_serial: false,
+                is_identity: false,
+                if_not_exists: false,
+                spelling: None,
             },
             ColumnDef {
                 name: "customer_id".to_string(),
@@ -212,6 +226,9 @@ fn test_matrix_create_table_no_pk_with_fk_no_index() {
                 default_expr: None,
                 is_inline_pk: false,
                 is_serial: false,
+                is_identity: false,
+                if_not_exists: false,
+                spelling: None,
             },
         ],
         constraints: vec![TableConstraint::ForeignKey {
@@ -225,6 +242,9 @@ fn test_matrix_create_table_no_pk_with_fk_no_index() {
         if_not_exists: false,
         partition_by: None,
         partition_of: None,
+        inherits: vec![],
+        storage_params: vec![],
+        on_commit: OnCommit::PreserveRows,
     }))];
 
     let findings = run_selected_rules(&stmts, &ctx, &["PGM501", "PGM502"]);
@@ -256,6 +276,9 @@ fn test_matrix_create_table_no_pk_but_unique_not_null() {
                 default_expr: None,
                 is_inline_pk: false,
                 is_serial: false,
+                is_identity: false,
+                if_not_exists: false,
+                spelling: None,
             },
             ColumnDef {
                 name: "name".to_string(),
@@ -264,6 +287,9 @@ fn test_matrix_create_table_no_pk_but_unique_not_null() {
                 default_expr: None,
                 is_inline_pk: false,
                 is_serial: false,
+                is_identity: false,
+                if_not_exists: false,
+                spelling: None,
             },
         ],
         constraints: vec![TableConstraint::Unique {
@@ -275,6 +301,9 @@ fn test_matrix_create_table_no_pk_but_unique_not_null() {
         if_not_exists: false,
         partition_by: None,
         partition_of: None,
+        inherits: vec![],
+        storage_params: vec![],
+        on_commit: OnCommit::PreserveRows,
     }))];
 
     let findings = run_selected_rules(&stmts, &ctx, &["PGM502", "PGM503"]);
@@ -391,6 +420,7 @@ fn test_matrix_add_check_without_not_valid() {
             name: Some("orders_status_check".to_string()),
             expression: "status IN ('pending', 'shipped', 'delivered')".to_string(),
             not_valid: false,
+            deps: CheckExprDeps::default(),
         })],
     }))];
 
@@ -449,7 +479,7 @@ fn test_matrix_down_migration_caps_severity() {
         ),
     ];
 
-    cap_for_down_migration(&mut findings);
+    cap_for_down_migration(&mut findings, &[]);
 
     // Sort by rule_id for stable snapshot
     findings.sort_by_key(|a| a.rule_id);
@@ -478,6 +508,9 @@ fn test_matrix_create_table_with_bad_types() {
                 }),
                 is_inline_pk: true,
                 is_serial: true,
+                is_identity: false,
+                if_not_exists: false,
+                spelling: None,
             },
             // PGM101: timestamp without time zone
             ColumnDef {
@@ -487,6 +520,9 @@ fn test_matrix_create_table_with_bad_types() {
                 default_expr: None,
                 is_inline_pk: false,
                 is_serial: false,
+                is_identity: false,
+                if_not_exists: false,
+                spelling: None,
             },
             // PGM103: char(10)
             ColumnDef {
@@ -496,6 +532,9 @@ fn test_matrix_create_table_with_bad_types() {
                 default_expr: None,
                 is_inline_pk: false,
                 is_serial: false,
+                is_identity: false,
+                if_not_exists: false,
+                spelling: None,
             },
             // PGM104: money
             ColumnDef {
@@ -505,6 +544,9 @@ fn test_matrix_create_table_with_bad_types() {
                 default_expr: None,
                 is_inline_pk: false,
                 is_serial: false,
+                is_identity: false,
+                if_not_exists: false,
+                spelling: None,
             },
         ],
         constraints: vec![],
@@ -512,6 +554,9 @@ fn test_matrix_create_table_with_bad_types() {
         if_not_exists: false,
         partition_by: None,
         partition_of: None,
+        inherits: vec![],
+        storage_params: vec![],
+        on_commit: OnCommit::PreserveRows,
     }))];
 
     let findings = run_selected_rules(&stmts, &ctx, &["PGM101", "PGM103", "PGM104", "PGM105"]);